@@ -0,0 +1,128 @@
+//! Tokenizer for the one-liner query DSL.
+//!
+//! The lexer yields identifiers, string literals, and punctuation, each with
+//! the byte span it covers. Whitespace and `#` line comments are skipped.
+
+use std::{iter::Peekable, ops::Range, str::CharIndices};
+
+/// The kind of a DSL token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TokenKind {
+    /// A bare identifier such as `pattern`.
+    Ident(String),
+    /// A double-quoted string literal with escapes resolved.
+    Str(String),
+    /// An opening parenthesis.
+    LParen,
+    /// A closing parenthesis.
+    RParen,
+}
+
+impl TokenKind {
+    /// Describes the token for diagnostics.
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            Self::Ident(name) => format!("`{name}`"),
+            Self::Str(_) => String::from("string literal"),
+            Self::LParen => String::from("`(`"),
+            Self::RParen => String::from("`)`"),
+        }
+    }
+}
+
+/// A token and the byte span it occupies in the query text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Token {
+    pub(crate) kind: TokenKind,
+    pub(crate) span: Range<usize>,
+}
+
+/// A tokenization failure and the span it concerns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LexError {
+    pub(crate) message: String,
+    pub(crate) span: Range<usize>,
+}
+
+/// Splits `source` into tokens.
+pub(crate) fn tokenize(source: &str) -> Result<Vec<Token>, LexError> {
+    let mut chars = source.char_indices().peekable();
+    let mut tokens = Vec::new();
+
+    while let Some((start, c)) = chars.next() {
+        let kind = match c {
+            '(' => TokenKind::LParen,
+            ')' => TokenKind::RParen,
+            '"' => TokenKind::Str(read_string(&mut chars, start, source.len())?),
+            '#' => {
+                skip_line(&mut chars);
+                continue;
+            }
+            _ if c.is_whitespace() => continue,
+            _ if c.is_ascii_alphabetic() || c == '_' => TokenKind::Ident(read_ident(&mut chars, c)),
+            other => {
+                return Err(LexError {
+                    message: format!("unexpected character `{other}`"),
+                    span: start..start + other.len_utf8(),
+                });
+            }
+        };
+        let end = chars.peek().map_or(source.len(), |(offset, _)| *offset);
+        tokens.push(Token {
+            kind,
+            span: start..end,
+        });
+    }
+
+    Ok(tokens)
+}
+
+fn read_ident(chars: &mut Peekable<CharIndices<'_>>, first: char) -> String {
+    let mut ident = String::from(first);
+    while let Some((_, c)) = chars.next_if(|(_, c)| c.is_ascii_alphanumeric() || *c == '_') {
+        ident.push(c);
+    }
+    ident
+}
+
+/// Reads the remainder of a string literal whose opening quote is at `start`.
+fn read_string(
+    chars: &mut Peekable<CharIndices<'_>>,
+    start: usize,
+    source_len: usize,
+) -> Result<String, LexError> {
+    let mut value = String::new();
+    while let Some((offset, c)) = chars.next() {
+        match c {
+            '"' => return Ok(value),
+            '\\' => match chars.next() {
+                Some((_, escaped)) => value.push(resolve_escape(escaped, offset)?),
+                None => break,
+            },
+            other => value.push(other),
+        }
+    }
+
+    Err(LexError {
+        message: String::from("unterminated string literal"),
+        span: start..source_len,
+    })
+}
+
+/// Resolves the character following a backslash at byte offset `backslash`.
+fn resolve_escape(escaped: char, backslash: usize) -> Result<char, LexError> {
+    match escaped {
+        '"' => Ok('"'),
+        '\\' => Ok('\\'),
+        'n' => Ok('\n'),
+        't' => Ok('\t'),
+        other => Err(LexError {
+            message: format!("unknown escape sequence `\\{other}`"),
+            span: backslash..backslash + 1 + other.len_utf8(),
+        }),
+    }
+}
+
+fn skip_line(chars: &mut Peekable<CharIndices<'_>>) {
+    while chars.next_if(|(_, c)| *c != '\n').is_some() {}
+}
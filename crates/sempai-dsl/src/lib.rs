@@ -0,0 +1,31 @@
+//! One-liner query DSL parsing for Sempai.
+//!
+//! This crate turns compact query expressions such as `pattern("foo($X)")`
+//! into the canonical [`Formula`](sempai_core::formula::Formula) model shared
+//! with YAML rules. Malformed input is reported as an
+//! `E_SEMPAI_DSL_PARSE` diagnostic carrying the byte span of the offending
+//! token.
+//!
+//! The grammar currently accepts a single `pattern("...")` atom. Operators,
+//! decorators, and the remaining atoms are planned.
+//!
+//! # Example
+//!
+//! ```
+//! use sempai_core::formula::{Atom, Formula};
+//! use sempai_dsl::parse_query;
+//!
+//! let formula = parse_query(r#"pattern("foo($X)")"#).expect("valid query");
+//! let Formula::Atom(Atom::Pattern(atom)) = formula.node else {
+//!     panic!("expected a pattern atom");
+//! };
+//! assert_eq!(atom.text, "foo($X)");
+//! ```
+
+mod lexer;
+mod parser;
+
+pub use parser::parse_query;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,152 @@
+//! Parser lowering DSL tokens into the canonical formula model.
+
+use std::{iter::Peekable, ops::Range, vec::IntoIter};
+
+use sempai_core::{
+    DiagnosticCode,
+    DiagnosticReport,
+    SourceSpan,
+    formula::{Atom, Decorated, Formula, PatternAtom},
+};
+
+use crate::lexer::{Token, TokenKind, tokenize};
+
+/// The only query function accepted so far.
+const PATTERN_FUNCTION: &str = "pattern";
+
+/// Parses a one-liner query into a decorated formula.
+///
+/// The returned formula carries the byte span of the whole expression.
+///
+/// # Errors
+///
+/// Returns an `E_SEMPAI_DSL_PARSE` diagnostic, anchored at the offending
+/// token, when the query is malformed or uses an unsupported function.
+///
+/// # Example
+///
+/// ```
+/// use sempai_core::DiagnosticCode;
+/// use sempai_dsl::parse_query;
+///
+/// let report = parse_query("pattern(").expect_err("unterminated call");
+/// let first = report.diagnostics().first().expect("one diagnostic");
+/// assert_eq!(first.code(), DiagnosticCode::ESempaiDslParse);
+/// ```
+pub fn parse_query(source: &str) -> Result<Decorated<Formula>, DiagnosticReport> {
+    let tokens = tokenize(source).map_err(|error| dsl_error(error.message, error.span))?;
+    let mut parser = Parser {
+        tokens: tokens.into_iter().peekable(),
+        end: source.len(),
+    };
+    let formula = parser.parse_pattern_call()?;
+    parser.expect_end()?;
+    Ok(formula)
+}
+
+struct Parser {
+    tokens: Peekable<IntoIter<Token>>,
+    end: usize,
+}
+
+impl Parser {
+    /// Parses `pattern("...")`.
+    fn parse_pattern_call(&mut self) -> Result<Decorated<Formula>, DiagnosticReport> {
+        let head = self.next_token("a query such as `pattern(\"...\")`")?;
+        match &head.kind {
+            TokenKind::Ident(name) if name == PATTERN_FUNCTION => {}
+            TokenKind::Ident(name) => {
+                return Err(dsl_error(
+                    format!("unsupported query function `{name}`; expected `{PATTERN_FUNCTION}`"),
+                    head.span,
+                ));
+            }
+            other => {
+                return Err(dsl_error(
+                    format!(
+                        "expected a query such as `pattern(\"...\")`, found {}",
+                        other.describe()
+                    ),
+                    head.span,
+                ));
+            }
+        }
+
+        self.expect(&TokenKind::LParen, "`(` after `pattern`")?;
+        let argument = self.next_token("a pattern string")?;
+        let TokenKind::Str(text) = argument.kind else {
+            return Err(dsl_error(
+                format!(
+                    "expected a pattern string, found {}",
+                    argument.kind.describe()
+                ),
+                argument.span,
+            ));
+        };
+        if text.trim().is_empty() {
+            return Err(dsl_error(
+                String::from("pattern text must not be empty"),
+                argument.span,
+            ));
+        }
+        let close = self.expect(&TokenKind::RParen, "`)` after the pattern string")?;
+
+        Ok(Decorated {
+            node: Formula::Atom(Atom::Pattern(PatternAtom { text })),
+            where_clauses: vec![],
+            as_name: None,
+            fix: None,
+            span: Some(source_span(&(head.span.start..close.span.end))),
+        })
+    }
+
+    /// Consumes the next token, which must be `kind`.
+    fn expect(&mut self, kind: &TokenKind, expected: &str) -> Result<Token, DiagnosticReport> {
+        let token = self.next_token(expected)?;
+        if token.kind == *kind {
+            Ok(token)
+        } else {
+            Err(dsl_error(
+                format!("expected {expected}, found {}", token.kind.describe()),
+                token.span,
+            ))
+        }
+    }
+
+    /// Consumes the next token, failing at end of input.
+    fn next_token(&mut self, expected: &str) -> Result<Token, DiagnosticReport> {
+        self.tokens.next().ok_or_else(|| {
+            dsl_error(
+                format!("expected {expected}, found end of input"),
+                self.end..self.end,
+            )
+        })
+    }
+
+    /// Fails if any tokens remain.
+    fn expect_end(&mut self) -> Result<(), DiagnosticReport> {
+        self.tokens.next().map_or(Ok(()), |token| {
+            Err(dsl_error(
+                format!(
+                    "unexpected {} after the end of the query",
+                    token.kind.describe()
+                ),
+                token.span,
+            ))
+        })
+    }
+}
+
+fn dsl_error(message: String, span: Range<usize>) -> DiagnosticReport {
+    DiagnosticReport::parser_error(
+        DiagnosticCode::ESempaiDslParse,
+        message,
+        Some(source_span(&span)),
+        vec![],
+    )
+}
+
+fn source_span(span: &Range<usize>) -> SourceSpan {
+    let offset = |byte: usize| u32::try_from(byte).unwrap_or(u32::MAX);
+    SourceSpan::new(offset(span.start), offset(span.end), None)
+}
@@ -0,0 +1,58 @@
+//! Tests for DSL tokenization.
+
+use rstest::rstest;
+
+use crate::lexer::{TokenKind, tokenize};
+
+fn kinds(source: &str) -> Vec<TokenKind> {
+    tokenize(source)
+        .expect("tokenize")
+        .into_iter()
+        .map(|token| token.kind)
+        .collect()
+}
+
+#[test]
+fn tokenizes_pattern_call_with_spans() {
+    let tokens = tokenize(r#"pattern("a")"#).expect("tokenize");
+
+    let spans: Vec<_> = tokens.iter().map(|token| token.span.clone()).collect();
+    assert_eq!(spans, [0..7, 7..8, 8..11, 11..12]);
+}
+
+#[rstest]
+#[case(r#""say \"hi\"""#, "say \"hi\"")]
+#[case(r#""a\\b""#, "a\\b")]
+#[case(r#""fn a() {\n}""#, "fn a() {\n}")]
+#[case(r#""\tx""#, "\tx")]
+#[case(r#""héllo""#, "héllo")]
+fn resolves_string_escapes(#[case] source: &str, #[case] expected: &str) {
+    assert_eq!(kinds(source), [TokenKind::Str(String::from(expected))]);
+}
+
+#[test]
+fn skips_whitespace_and_comments() {
+    assert_eq!(
+        kinds("# leading comment\n  pattern  # trailing\n ( )"),
+        [
+            TokenKind::Ident(String::from("pattern")),
+            TokenKind::LParen,
+            TokenKind::RParen,
+        ]
+    );
+}
+
+#[rstest]
+#[case(r#""open"#, "unterminated string literal", 0..5)]
+#[case(r#""trailing\"#, "unterminated string literal", 0..10)]
+#[case(r#"x "\q""#, "unknown escape sequence `\\q`", 3..5)]
+#[case("pattern{", "unexpected character `{`", 7..8)]
+fn reports_lexical_errors(
+    #[case] source: &str,
+    #[case] message: &str,
+    #[case] span: std::ops::Range<usize>,
+) {
+    let error = tokenize(source).expect_err("tokenization should fail");
+    assert_eq!(error.message, message);
+    assert_eq!(error.span, span);
+}
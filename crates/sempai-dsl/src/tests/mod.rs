@@ -0,0 +1,4 @@
+//! Unit tests for the `sempai_dsl` crate.
+
+mod lexer_tests;
+mod parser_tests;
@@ -0,0 +1,91 @@
+//! Tests for lowering DSL queries into formulas.
+
+use rstest::rstest;
+use sempai_core::{
+    DiagnosticCode,
+    formula::{Atom, Formula, PatternAtom},
+};
+
+use crate::parse_query;
+
+#[rstest]
+#[case(r#"pattern("foo($X)")"#, "foo($X)")]
+#[case("  pattern ( \"fn $F() { ... }\" )  ", "fn $F() { ... }")]
+#[case("# find calls\npattern(\"call(\\\"x\\\")\")", "call(\"x\")")]
+fn parses_pattern_atoms(#[case] source: &str, #[case] expected: &str) {
+    let formula = parse_query(source).expect("valid query");
+
+    assert_eq!(
+        formula.node,
+        Formula::Atom(Atom::Pattern(PatternAtom {
+            text: String::from(expected),
+        }))
+    );
+    assert!(formula.where_clauses.is_empty());
+    assert!(formula.as_name.is_none());
+    assert!(formula.fix.is_none());
+}
+
+#[test]
+fn formula_span_covers_the_expression() {
+    let formula = parse_query(r#"  pattern("a")  "#).expect("valid query");
+
+    let span = formula.span.expect("span");
+    assert_eq!((span.start(), span.end()), (2, 14));
+}
+
+#[rstest]
+#[case::empty(
+    "",
+    "expected a query such as `pattern(\"...\")`, found end of input",
+    0,
+    0
+)]
+#[case::unknown_function(
+    r#"regex("a")"#,
+    "unsupported query function `regex`; expected `pattern`",
+    0,
+    5
+)]
+#[case::bare_string(
+    r#""a""#,
+    "expected a query such as `pattern(\"...\")`, found string literal",
+    0,
+    3
+)]
+#[case::missing_paren(
+    r#"pattern "a""#,
+    "expected `(` after `pattern`, found string literal",
+    8,
+    11
+)]
+#[case::missing_argument("pattern()", "expected a pattern string, found `)`", 8, 9)]
+#[case::unclosed(
+    "pattern(\"a\"",
+    "expected `)` after the pattern string, found end of input",
+    11,
+    11
+)]
+#[case::empty_pattern(r#"pattern("  ")"#, "pattern text must not be empty", 8, 12)]
+#[case::trailing_tokens(
+    r#"pattern("a") pattern("b")"#,
+    "unexpected `pattern` after the end of the query",
+    13,
+    20
+)]
+#[case::lexical(r#"pattern("a\q")"#, "unknown escape sequence `\\q`", 10, 12)]
+fn reports_syntax_errors_with_spans(
+    #[case] source: &str,
+    #[case] message: &str,
+    #[case] start: u32,
+    #[case] end: u32,
+) {
+    let report = parse_query(source).expect_err("query should be rejected");
+
+    assert_eq!(report.len(), 1);
+    let diagnostic = report.diagnostics().first().expect("one diagnostic");
+    assert_eq!(diagnostic.code(), DiagnosticCode::ESempaiDslParse);
+    assert_eq!(diagnostic.message(), message);
+    let span = diagnostic.primary_span().expect("primary span");
+    assert_eq!((span.start(), span.end()), (start, end));
+}
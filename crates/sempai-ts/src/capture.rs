@@ -0,0 +1,61 @@
+//! Conversion of backend captures into Sempai capture values.
+
+use sempai_core::{CaptureValue, CapturedNode, LineCol, Span};
+use weaver_syntax::CapturedValue;
+
+/// Converts a capture, dropping punctuation such as the commas between the
+/// nodes of a sequence capture.
+///
+/// Node text longer than `max_text_bytes` is omitted.
+pub(crate) fn capture_value(value: &CapturedValue<'_>, max_text_bytes: usize) -> CaptureValue {
+    let node = |captured: &weaver_syntax::CapturedNode<'_>| {
+        let text = (captured.text().len() <= max_text_bytes).then(|| captured.text().to_owned());
+        CapturedNode::new(
+            span_of(captured.node()),
+            captured.node().kind().to_owned(),
+            text,
+        )
+    };
+    match value {
+        CapturedValue::Single(captured) => CaptureValue::Node(node(captured)),
+        CapturedValue::Multiple(captured) => CaptureValue::Nodes(
+            captured
+                .nodes()
+                .iter()
+                .filter(|candidate| candidate.node().is_named())
+                .map(node)
+                .collect(),
+        ),
+    }
+}
+
+/// Returns the text of `value`, taking a sequence capture's text from
+/// `source` so that the separators between its nodes are included.
+///
+/// Returns `None` when the text was omitted or falls outside `source`.
+pub(crate) fn capture_text<'s>(value: &'s CaptureValue, source: &'s str) -> Option<&'s str> {
+    match value {
+        CaptureValue::Node(node) => node.text(),
+        CaptureValue::Nodes(nodes) => match (nodes.first(), nodes.last()) {
+            (Some(first), Some(last)) => {
+                source.get(first.span().start_byte() as usize..last.span().end_byte() as usize)
+            }
+            _ => Some(""),
+        },
+        _ => None,
+    }
+}
+
+/// Returns the zero-based span covered by `node`.
+pub(crate) fn span_of(node: tree_sitter::Node<'_>) -> Span {
+    let position =
+        |point: tree_sitter::Point| LineCol::new(to_u32(point.row), to_u32(point.column));
+    Span::new(
+        to_u32(node.start_byte()),
+        to_u32(node.end_byte()),
+        position(node.start_position()),
+        position(node.end_position()),
+    )
+}
+
+fn to_u32(value: usize) -> u32 { u32::try_from(value).unwrap_or(u32::MAX) }
@@ -0,0 +1,78 @@
+//! Filtering of candidate matches by `where` clause constraints.
+
+use regex::Regex;
+use sempai_core::{
+    DiagnosticCode,
+    DiagnosticReport,
+    SourceSpan,
+    formula::{Constraint, WhereClause},
+};
+use weaver_syntax::MatchResult;
+
+use crate::capture::{capture_text, capture_value};
+
+/// A compiled `metavariable-regex` constraint.
+#[derive(Debug)]
+pub(crate) struct MetavariableRegex {
+    /// The metavariable name, including the leading `$`.
+    metavariable: String,
+    regex: Regex,
+}
+
+impl MetavariableRegex {
+    /// Returns whether the capture bound to the metavariable in `found`
+    /// matches the regular expression.
+    ///
+    /// Matches that do not bind the metavariable are rejected.
+    pub(crate) fn admits(&self, found: &MatchResult<'_>, source: &str) -> bool {
+        let Some(value) = self
+            .metavariable
+            .strip_prefix('$')
+            .and_then(|name| found.capture(name))
+        else {
+            return false;
+        };
+        let capture = capture_value(value, usize::MAX);
+        capture_text(&capture, source).is_some_and(|text| self.regex.is_match(text))
+    }
+}
+
+/// Compiles the constraints of a formula's `where` clauses.
+///
+/// # Errors
+///
+/// Returns an `E_SEMPAI_SCHEMA_INVALID` diagnostic, anchored at `span`, for a
+/// regular expression that does not compile, and a `NOT_IMPLEMENTED`
+/// diagnostic for constraints the backend cannot evaluate yet.
+pub(crate) fn compile_constraints(
+    clauses: &[WhereClause],
+    span: Option<&SourceSpan>,
+) -> Result<Vec<MetavariableRegex>, DiagnosticReport> {
+    clauses
+        .iter()
+        .map(|clause| match &clause.constraint {
+            Constraint::MetavariableRegex {
+                metavariable,
+                regex,
+            } => Regex::new(regex)
+                .map(|compiled| MetavariableRegex {
+                    metavariable: metavariable.clone(),
+                    regex: compiled,
+                })
+                .map_err(|error| {
+                    DiagnosticReport::single_error(
+                        DiagnosticCode::ESempaiSchemaInvalid,
+                        format!("invalid regex for metavariable `{metavariable}`: {error}"),
+                        span.cloned(),
+                        vec![],
+                    )
+                }),
+            Constraint::MetavariablePattern { .. } => Err(DiagnosticReport::not_implemented(
+                "executing `metavariable-pattern` constraints",
+            )),
+            Constraint::Other(_) => Err(DiagnosticReport::not_implemented(
+                "executing unrecognized `where` constraints",
+            )),
+        })
+        .collect()
+}
@@ -0,0 +1,149 @@
+//! Evaluation of normalized formulas into candidate matches.
+//!
+//! Pattern atoms produce the candidate matches. Disjunctions (`pattern-either`)
+//! take the union of their branches, while conjunctions (`patterns`) keep the
+//! candidates of their first positive term whose region every other positive
+//! term also matches, and drop those whose region satisfies a negated term
+//! (`pattern-not`). Each formula's `where` clauses then drop the matches whose
+//! captures fail a constraint, such as a `metavariable-regex`.
+
+use std::ops::Range;
+
+use sempai_core::{
+    DiagnosticCode,
+    DiagnosticReport,
+    SourceSpan,
+    formula::{Atom, Decorated, Formula, PatternAtom},
+};
+use weaver_syntax::{MatchResult, ParseResult, Pattern, SupportedLanguage};
+
+use crate::{constraint::compile_constraints, rewrite::rewrite_semgrep_tokens};
+
+/// Evaluates formulas against one parsed source snapshot.
+pub(crate) struct Evaluator<'a> {
+    grammar: SupportedLanguage,
+    parsed: &'a ParseResult,
+}
+
+impl<'a> Evaluator<'a> {
+    pub(crate) const fn new(grammar: SupportedLanguage, parsed: &'a ParseResult) -> Self {
+        Self { grammar, parsed }
+    }
+
+    /// Returns the matches of `formula` in source order.
+    pub(crate) fn evaluate(
+        &self,
+        formula: &Decorated<Formula>,
+    ) -> Result<Vec<MatchResult<'a>>, DiagnosticReport> {
+        let constraints = compile_constraints(&formula.where_clauses, formula.span.as_ref())?;
+        let mut matches = match &formula.node {
+            Formula::Atom(Atom::Pattern(atom)) => self.pattern(atom, formula.span.as_ref())?,
+            Formula::Or(branches) => self.union(branches)?,
+            Formula::And(terms) => self.conjunction(terms)?,
+            Formula::Atom(Atom::Regex(_)) => {
+                return Err(DiagnosticReport::not_implemented("executing regex atoms"));
+            }
+            Formula::Atom(Atom::TreeSitterQuery(_)) => {
+                return Err(DiagnosticReport::not_implemented(
+                    "executing Tree-sitter query atoms",
+                ));
+            }
+            Formula::Not(_) => {
+                return Err(DiagnosticReport::not_implemented(
+                    "executing negation outside a conjunction",
+                ));
+            }
+            Formula::Inside(_) | Formula::Anywhere(_) => {
+                return Err(DiagnosticReport::not_implemented(
+                    "executing `inside` and `anywhere` formulas",
+                ));
+            }
+        };
+        let source = self.parsed.source();
+        matches.retain(|found| {
+            constraints
+                .iter()
+                .all(|constraint| constraint.admits(found, source))
+        });
+        matches.sort_by_key(|found| {
+            let range = found.byte_range();
+            (range.start, std::cmp::Reverse(range.end))
+        });
+        Ok(matches)
+    }
+
+    fn pattern(
+        &self,
+        atom: &PatternAtom,
+        span: Option<&SourceSpan>,
+    ) -> Result<Vec<MatchResult<'a>>, DiagnosticReport> {
+        let pattern = Pattern::compile(&rewrite_semgrep_tokens(&atom.text), self.grammar).map_err(
+            |error| {
+                DiagnosticReport::single_error(
+                    DiagnosticCode::ESempaiPatternSnippetParseFailed,
+                    format!("pattern `{}` could not be compiled: {error}", atom.text),
+                    span.cloned(),
+                    vec![],
+                )
+            },
+        )?;
+        Ok(pattern.find_all(self.parsed))
+    }
+
+    /// Returns the matches of every branch, keeping the first match found for
+    /// each region.
+    fn union(
+        &self,
+        branches: &[Decorated<Formula>],
+    ) -> Result<Vec<MatchResult<'a>>, DiagnosticReport> {
+        let mut matches: Vec<MatchResult<'a>> = Vec::new();
+        for branch in branches {
+            let found = self.evaluate(branch)?;
+            let seen = regions(&matches);
+            matches.extend(
+                found
+                    .into_iter()
+                    .filter(|candidate| !seen.contains(&candidate.byte_range())),
+            );
+        }
+        Ok(matches)
+    }
+
+    /// Intersects the positive terms and subtracts the negated ones.
+    fn conjunction(
+        &self,
+        terms: &[Decorated<Formula>],
+    ) -> Result<Vec<MatchResult<'a>>, DiagnosticReport> {
+        let (negated, positive): (Vec<_>, Vec<_>) =
+            terms.iter().partition(|term| negated_inner(term).is_some());
+        let mut positive_terms = positive.into_iter();
+        let Some(first) = positive_terms.next() else {
+            return Err(DiagnosticReport::not_implemented(
+                "executing conjunctions without a positive pattern",
+            ));
+        };
+
+        let mut matches = self.evaluate(first)?;
+        for term in positive_terms {
+            let required = regions(&self.evaluate(term)?);
+            matches.retain(|candidate| required.contains(&candidate.byte_range()));
+        }
+        for inner in negated.into_iter().filter_map(negated_inner) {
+            let excluded = regions(&self.evaluate(inner)?);
+            matches.retain(|candidate| !excluded.contains(&candidate.byte_range()));
+        }
+        Ok(matches)
+    }
+}
+
+/// Returns the formula negated by `term`, if `term` is a negation.
+fn negated_inner(term: &Decorated<Formula>) -> Option<&Decorated<Formula>> {
+    match &term.node {
+        Formula::Not(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+fn regions(matches: &[MatchResult<'_>]) -> Vec<Range<usize>> {
+    matches.iter().map(MatchResult::byte_range).collect()
+}
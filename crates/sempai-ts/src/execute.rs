@@ -0,0 +1,131 @@
+//! Execution of normalized formulas against source snapshots.
+
+use std::collections::BTreeMap;
+
+use sempai_core::{
+    DiagnosticCode,
+    DiagnosticReport,
+    EngineConfig,
+    Language,
+    Match,
+    formula::{Decorated, Formula},
+};
+use weaver_syntax::{MatchResult, Parser};
+
+use crate::{
+    capture::{capture_value, span_of},
+    evaluate::Evaluator,
+    profile::grammar_for,
+};
+
+/// A source snapshot that a query runs against.
+#[derive(Debug, Clone, Copy)]
+pub struct SourceFile<'a> {
+    language: Language,
+    uri: &'a str,
+    source: &'a str,
+}
+
+impl<'a> SourceFile<'a> {
+    /// Creates a snapshot of `source`, identified by `uri`, written in
+    /// `language`.
+    #[must_use]
+    pub const fn new(language: Language, uri: &'a str, source: &'a str) -> Self {
+        Self {
+            language,
+            uri,
+            source,
+        }
+    }
+
+    /// Returns the language of the source.
+    #[must_use]
+    pub const fn language(&self) -> Language { self.language }
+
+    /// Returns the URI identifying the source.
+    #[must_use]
+    pub const fn uri(&self) -> &'a str { self.uri }
+
+    /// Returns the source text.
+    #[must_use]
+    pub const fn source(&self) -> &'a str { self.source }
+}
+
+/// Executes `formula` against `file`, attributing matches to `rule_id`.
+///
+/// Pattern atoms may be combined with `pattern-either` disjunctions and with
+/// `patterns` conjunctions containing `pattern-not` terms. A negated term
+/// removes every match whose region it also matches, and a
+/// `metavariable-regex` constraint removes every match whose capture text
+/// the regular expression does not match.
+///
+/// Matches are returned in source order. At most
+/// [`EngineConfig::max_matches_per_rule`] matches are returned, and capture
+/// text longer than [`EngineConfig::max_capture_text_bytes`] is omitted.
+///
+/// # Errors
+///
+/// Returns an `E_SEMPAI_PATTERN_SNIPPET_PARSE_FAILED` diagnostic if a pattern
+/// cannot be compiled for the file's language, an `E_SEMPAI_SCHEMA_INVALID`
+/// diagnostic if a constraint's regular expression is invalid, and a
+/// `NOT_IMPLEMENTED` diagnostic for formula shapes, constraints, or languages
+/// the backend does not support yet.
+pub fn execute(
+    rule_id: &str,
+    formula: &Decorated<Formula>,
+    file: &SourceFile<'_>,
+    config: &EngineConfig,
+) -> Result<Vec<Match>, DiagnosticReport> {
+    let grammar = grammar_for(file.language)?;
+    let parsed = Parser::new(grammar)
+        .and_then(|mut parser| parser.parse(file.source))
+        .map_err(|error| {
+            DiagnosticReport::single_error(
+                DiagnosticCode::ESempaiPatternSnippetParseFailed,
+                format!("failed to parse '{}': {error}", file.uri),
+                None,
+                vec![],
+            )
+        })?;
+
+    let builder = MatchBuilder {
+        rule_id,
+        uri: file.uri,
+        max_capture_text_bytes: config.max_capture_text_bytes(),
+    };
+    Ok(Evaluator::new(grammar, &parsed)
+        .evaluate(formula)?
+        .iter()
+        .take(config.max_matches_per_rule())
+        .map(|found| builder.build(found))
+        .collect())
+}
+
+/// Converts backend matches into Sempai [`Match`] values.
+struct MatchBuilder<'a> {
+    rule_id: &'a str,
+    uri: &'a str,
+    max_capture_text_bytes: usize,
+}
+
+impl MatchBuilder<'_> {
+    fn build(&self, found: &MatchResult<'_>) -> Match {
+        let captures = found
+            .captures()
+            .iter()
+            .map(|(name, value)| {
+                (
+                    format!("${name}"),
+                    capture_value(value, self.max_capture_text_bytes),
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+        Match::new(
+            self.rule_id.to_owned(),
+            self.uri.to_owned(),
+            span_of(found.node()),
+            None,
+            captures,
+        )
+    }
+}
@@ -0,0 +1,51 @@
+//! Tree-sitter execution backend for Sempai.
+//!
+//! This crate runs normalized Sempai formulas against source snapshots. Pattern
+//! atoms are rewritten from Semgrep syntax into the structural pattern language
+//! of `weaver-syntax`, matched over a Tree-sitter parse of the source, and
+//! reported as [`Match`](sempai_core::Match) values with Semgrep-style capture
+//! keys such as `$X`.
+//!
+//! Execution currently supports pattern atoms combined with disjunction and
+//! with conjunctions of positive and negated patterns, filtered by
+//! `metavariable-regex` constraints, for Rust, Python, and TypeScript. Other
+//! formula shapes, constraints, and languages return `NOT_IMPLEMENTED`
+//! diagnostics.
+//!
+//! # Example
+//!
+//! ```
+//! use sempai_core::{
+//!     EngineConfig,
+//!     Language,
+//!     formula::{Atom, Decorated, Formula, PatternAtom},
+//! };
+//! use sempai_ts::{SourceFile, execute};
+//!
+//! let formula = Decorated {
+//!     node: Formula::Atom(Atom::Pattern(PatternAtom {
+//!         text: String::from("foo($X)"),
+//!     })),
+//!     where_clauses: vec![],
+//!     as_name: None,
+//!     fix: None,
+//!     span: None,
+//! };
+//! let file = SourceFile::new(Language::Rust, "file:///lib.rs", "fn f() { foo(1); }");
+//!
+//! let matches = execute("demo", &formula, &file, &EngineConfig::default())?;
+//! assert_eq!(matches.len(), 1);
+//! # Ok::<(), sempai_core::DiagnosticReport>(())
+//! ```
+
+mod capture;
+mod constraint;
+mod evaluate;
+mod execute;
+mod profile;
+mod rewrite;
+
+pub use execute::{SourceFile, execute};
+
+#[cfg(test)]
+mod tests;
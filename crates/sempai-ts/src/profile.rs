@@ -0,0 +1,19 @@
+//! Mapping from Sempai languages to Tree-sitter grammars.
+
+use sempai_core::{DiagnosticReport, Language};
+use weaver_syntax::SupportedLanguage;
+
+/// Returns the grammar used to execute queries for `language`.
+///
+/// Languages without a bundled grammar report a `NOT_IMPLEMENTED`
+/// diagnostic.
+pub(crate) fn grammar_for(language: Language) -> Result<SupportedLanguage, DiagnosticReport> {
+    match language {
+        Language::Rust => Ok(SupportedLanguage::Rust),
+        Language::Python => Ok(SupportedLanguage::Python),
+        Language::TypeScript => Ok(SupportedLanguage::TypeScript),
+        other => Err(DiagnosticReport::not_implemented(&format!(
+            "query execution for {other}"
+        ))),
+    }
+}
@@ -0,0 +1,80 @@
+//! Rewriting of Semgrep pattern tokens into `weaver-syntax` pattern syntax.
+//!
+//! Semgrep and `weaver-syntax` share the `$X` and `$_` metavariable forms but
+//! spell sequences differently:
+//!
+//! - `$...ARGS` becomes `$$$ARGS`.
+//! - `...` becomes the anonymous sequence `$$$_`.
+//! - `"..."` becomes `"$_"`, which matches any double-quoted string.
+//!
+//! Other double-quoted string literals are copied verbatim, so an ellipsis
+//! inside a longer string is matched literally.
+
+use std::{iter::Peekable, str::Chars};
+
+/// Rewrites the Semgrep tokens in `pattern`.
+pub(crate) fn rewrite_semgrep_tokens(pattern: &str) -> String {
+    let mut rewritten = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if take_string_ellipsis(&mut chars) => rewritten.push_str("\"$_\""),
+            '"' => {
+                rewritten.push(c);
+                copy_string_literal(&mut chars, &mut rewritten);
+            }
+            '$' if take_ellipsis_prefix(&mut chars) => rewritten.push_str("$$$"),
+            '.' if take_remaining_dots(&mut chars) => rewritten.push_str("$$$_"),
+            other => rewritten.push(other),
+        }
+    }
+
+    rewritten
+}
+
+/// Copies a string literal body up to and including its closing quote.
+fn copy_string_literal(chars: &mut Peekable<Chars<'_>>, rewritten: &mut String) {
+    while let Some(c) = chars.next() {
+        rewritten.push(c);
+        match c {
+            '\\' => rewritten.extend(chars.next()),
+            '"' => return,
+            _ => {}
+        }
+    }
+}
+
+/// Consumes the `..."` of a `"..."` string wildcard following a `"`.
+fn take_string_ellipsis(chars: &mut Peekable<Chars<'_>>) -> bool {
+    let mut lookahead = chars.clone();
+    let is_wildcard = (0..3).all(|_| lookahead.next_if_eq(&'.').is_some())
+        && lookahead.next_if_eq(&'"').is_some();
+    if is_wildcard {
+        *chars = lookahead;
+    }
+    is_wildcard
+}
+
+/// Consumes the `...` of a `$...NAME` metavariable following a `$`.
+fn take_ellipsis_prefix(chars: &mut Peekable<Chars<'_>>) -> bool {
+    let mut lookahead = chars.clone();
+    let is_ellipsis_metavariable = (0..3).all(|_| lookahead.next_if_eq(&'.').is_some())
+        && lookahead
+            .peek()
+            .is_some_and(|c| c.is_ascii_uppercase() || *c == '_');
+    if is_ellipsis_metavariable {
+        *chars = lookahead;
+    }
+    is_ellipsis_metavariable
+}
+
+/// Consumes the last two dots of a `...` ellipsis whose first dot was read.
+fn take_remaining_dots(chars: &mut Peekable<Chars<'_>>) -> bool {
+    let mut lookahead = chars.clone();
+    let is_ellipsis = (0..2).all(|_| lookahead.next_if_eq(&'.').is_some());
+    if is_ellipsis {
+        *chars = lookahead;
+    }
+    is_ellipsis
+}
@@ -0,0 +1,99 @@
+//! Tests for filtering matches by `where` clause constraints.
+
+use rstest::rstest;
+use sempai_core::{
+    DiagnosticCode,
+    DiagnosticReport,
+    EngineConfig,
+    Language,
+    formula::{Atom, Constraint, Decorated, Formula, PatternAtom, WhereClause},
+};
+
+use crate::{SourceFile, execute};
+
+const PYTHON_CALLS: &str = concat!(
+    "os.system(cmd)\n",
+    "subprocess.call(cmd)\n",
+    "print(os.name)\n",
+    "run(a, b, c)\n",
+);
+
+fn constrained(pattern: &str, constraints: Vec<Constraint>) -> Decorated<Formula> {
+    Decorated {
+        node: Formula::Atom(Atom::Pattern(PatternAtom {
+            text: String::from(pattern),
+        })),
+        where_clauses: constraints
+            .into_iter()
+            .map(|constraint| WhereClause { constraint })
+            .collect(),
+        as_name: None,
+        fix: None,
+        span: None,
+    }
+}
+
+fn regex(metavariable: &str, pattern: &str) -> Constraint {
+    Constraint::MetavariableRegex {
+        metavariable: String::from(metavariable),
+        regex: String::from(pattern),
+    }
+}
+
+fn matched_lines(formula: &Decorated<Formula>) -> Result<Vec<u32>, DiagnosticReport> {
+    let file = SourceFile::new(Language::Python, "file:///calls.py", PYTHON_CALLS);
+    let matches = execute("test-rule", formula, &file, &EngineConfig::default())?;
+    Ok(matches
+        .iter()
+        .map(|found| found.span().start().line())
+        .collect())
+}
+
+#[rstest]
+#[case::dotted_prefix(regex("$FUNC", r"^os\."), vec![0])]
+#[case::unanchored(regex("$FUNC", "call"), vec![1])]
+#[case::unbound_metavariable(regex("$OTHER", "."), vec![])]
+fn metavariable_regex_filters_matches(#[case] constraint: Constraint, #[case] expected: Vec<u32>) {
+    let formula = constrained("$FUNC($...ARGS)", vec![constraint]);
+
+    assert_eq!(matched_lines(&formula).expect("execute"), expected);
+}
+
+#[test]
+fn sequence_capture_text_includes_separators() {
+    let formula = constrained("$FUNC(a, $...ARGS)", vec![regex("$ARGS", "^b, c$")]);
+
+    assert_eq!(matched_lines(&formula).expect("execute"), [3]);
+}
+
+#[test]
+fn every_constraint_must_hold() {
+    let formula = constrained(
+        "$FUNC($...ARGS)",
+        vec![regex("$FUNC", r"\."), regex("$FUNC", "call$")],
+    );
+
+    assert_eq!(matched_lines(&formula).expect("execute"), [1]);
+}
+
+#[rstest]
+#[case::invalid_regex(regex("$FUNC", "("), DiagnosticCode::ESempaiSchemaInvalid)]
+#[case::metavariable_pattern(
+    Constraint::MetavariablePattern {
+        metavariable: String::from("$FUNC"),
+        pattern: String::from("os.system"),
+    },
+    DiagnosticCode::NotImplemented
+)]
+#[case::unrecognized(Constraint::Other(String::from("{}")), DiagnosticCode::NotImplemented)]
+fn unusable_constraints_are_reported(
+    #[case] constraint: Constraint,
+    #[case] expected: DiagnosticCode,
+) {
+    let formula = constrained("$FUNC($...ARGS)", vec![constraint]);
+
+    let report = matched_lines(&formula).expect_err("constraint is rejected");
+
+    let first = report.diagnostics().first().expect("diagnostic");
+    assert_eq!(first.code(), expected);
+}
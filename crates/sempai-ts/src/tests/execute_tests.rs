@@ -0,0 +1,252 @@
+//! Tests for executing formulas against source snapshots.
+
+use rstest::rstest;
+use sempai_core::{
+    CaptureValue,
+    DiagnosticCode,
+    DiagnosticReport,
+    EngineConfig,
+    EngineLimits,
+    Language,
+    Match,
+    formula::{Atom, Decorated, Formula, PatternAtom},
+};
+
+use crate::{SourceFile, execute};
+
+const RUST_SOURCE: &str = "fn main() {\n    foo(1, a);\n    bar(2, b);\n    foo(name, c);\n}\n";
+
+fn decorated(node: Formula) -> Decorated<Formula> {
+    Decorated {
+        node,
+        where_clauses: vec![],
+        as_name: None,
+        fix: None,
+        span: None,
+    }
+}
+
+fn pattern(text: &str) -> Decorated<Formula> {
+    decorated(Formula::Atom(Atom::Pattern(PatternAtom {
+        text: String::from(text),
+    })))
+}
+
+fn run(
+    language: Language,
+    query: &str,
+    source: &str,
+    config: &EngineConfig,
+) -> Result<Vec<Match>, DiagnosticReport> {
+    let file = SourceFile::new(language, "file:///sample", source);
+    execute("test-rule", &pattern(query), &file, config)
+}
+
+fn matched_text<'a>(source: &'a str, found: &Match) -> &'a str {
+    let span = found.span();
+    source
+        .get(span.start_byte() as usize..span.end_byte() as usize)
+        .expect("span within source")
+}
+
+fn capture_text(found: &Match, name: &str) -> Option<String> {
+    match found.captures().get(name)? {
+        CaptureValue::Node(node) => node.text().map(str::to_owned),
+        CaptureValue::Nodes(nodes) => Some(
+            nodes
+                .iter()
+                .filter_map(|node| node.text())
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+        _ => None,
+    }
+}
+
+fn first_code<T: std::fmt::Debug>(result: Result<T, DiagnosticReport>) -> DiagnosticCode {
+    let report = result.expect_err("execution should fail");
+    report.diagnostics().first().expect("diagnostic").code()
+}
+
+#[test]
+fn pattern_matches_are_attributed_and_located() {
+    let matches = run(
+        Language::Rust,
+        "foo($X, $Y)",
+        RUST_SOURCE,
+        &EngineConfig::default(),
+    )
+    .expect("execute");
+
+    let texts: Vec<_> = matches
+        .iter()
+        .map(|found| matched_text(RUST_SOURCE, found))
+        .collect();
+    assert_eq!(texts, ["foo(1, a);", "foo(name, c);"]);
+
+    let first = matches.first().expect("first match");
+    assert_eq!(first.rule_id(), "test-rule");
+    assert_eq!(first.uri(), "file:///sample");
+    assert_eq!(
+        (first.span().start().line(), first.span().start().column()),
+        (1, 4)
+    );
+    assert!(first.focus().is_none());
+    assert_eq!(capture_text(first, "$X").as_deref(), Some("1"));
+}
+
+#[rstest]
+#[case::ellipsis("foo(...)", "def f():\n    foo()\n    foo(1, 2)\n", 2, None)]
+#[case::ellipsis_metavariable(
+    "foo(0, $...ARGS)",
+    "def f():\n    foo(0, 1, 2)\n    foo(1)\n",
+    1,
+    Some("1, 2")
+)]
+#[case::keyword_argument(
+    "subprocess.call($CMD, shell=True)",
+    "subprocess.call(cmd, shell=True)\nsubprocess.call(cmd)\n",
+    1,
+    Some("cmd")
+)]
+fn semgrep_tokens_are_honoured(
+    #[case] query: &str,
+    #[case] source: &str,
+    #[case] expected_count: usize,
+    #[case] captured: Option<&str>,
+) {
+    let matches = run(Language::Python, query, source, &EngineConfig::default()).expect("execute");
+
+    assert_eq!(matches.len(), expected_count);
+    if let Some(expected) = captured {
+        let first = matches.first().expect("first match");
+        let name = first.captures().keys().next().expect("one capture");
+        assert_eq!(capture_text(first, name).as_deref(), Some(expected));
+    }
+}
+
+#[test]
+fn match_count_is_capped_by_the_engine_limit() {
+    let config = EngineConfig::new(EngineLimits::new(1, 1024, 1024), false);
+
+    let matches = run(Language::Rust, "foo($X, $Y)", RUST_SOURCE, &config).expect("execute");
+
+    assert_eq!(matches.len(), 1);
+}
+
+#[test]
+fn oversized_capture_text_is_omitted() {
+    let config = EngineConfig::new(EngineLimits::new(10, 3, 1024), false);
+
+    let matches = run(Language::Rust, "foo($X, $Y)", RUST_SOURCE, &config).expect("execute");
+
+    let texts: Vec<_> = matches
+        .iter()
+        .map(|found| capture_text(found, "$X"))
+        .collect();
+    assert_eq!(texts, [Some(String::from("1")), None]);
+}
+
+#[test]
+fn unparseable_pattern_reports_snippet_failure() {
+    let result = run(
+        Language::Rust,
+        "foo(",
+        RUST_SOURCE,
+        &EngineConfig::default(),
+    );
+
+    assert_eq!(
+        first_code(result),
+        DiagnosticCode::ESempaiPatternSnippetParseFailed
+    );
+}
+
+#[test]
+fn unsupported_language_is_not_implemented() {
+    let result = run(Language::Go, "foo($X)", "", &EngineConfig::default());
+
+    assert_eq!(first_code(result), DiagnosticCode::NotImplemented);
+}
+
+const PYTHON_EVALS: &str = "eval(cmd)\neval(\"1 + 1\")\nexec(code)\neval(user)\n";
+
+fn not(term: Decorated<Formula>) -> Decorated<Formula> { decorated(Formula::Not(Box::new(term))) }
+
+fn matched_texts(
+    formula: &Decorated<Formula>,
+    source: &str,
+) -> Result<Vec<String>, DiagnosticReport> {
+    let file = SourceFile::new(Language::Python, "file:///sample.py", source);
+    let matches = execute("test-rule", formula, &file, &EngineConfig::default())?;
+    Ok(matches
+        .iter()
+        .map(|found| matched_text(source, found).to_owned())
+        .collect())
+}
+
+#[test]
+fn negated_patterns_remove_matching_regions() {
+    let formula = decorated(Formula::And(vec![
+        pattern("eval($X)"),
+        not(pattern(r#"eval("...")"#)),
+    ]));
+
+    let texts = matched_texts(&formula, PYTHON_EVALS).expect("execute");
+
+    assert_eq!(texts, ["eval(cmd)", "eval(user)"]);
+}
+
+#[test]
+fn conjunctions_keep_regions_matched_by_every_positive_term() {
+    let formula = decorated(Formula::And(vec![pattern("eval($X)"), pattern("$F(user)")]));
+
+    let texts = matched_texts(&formula, PYTHON_EVALS).expect("execute");
+
+    assert_eq!(texts, ["eval(user)"]);
+}
+
+#[test]
+fn disjunctions_merge_branches_in_source_order() {
+    let formula = decorated(Formula::Or(vec![
+        pattern("exec($X)"),
+        pattern("eval($X)"),
+        pattern("eval(cmd)"),
+    ]));
+
+    let texts = matched_texts(&formula, PYTHON_EVALS).expect("execute");
+
+    assert_eq!(
+        texts,
+        ["eval(cmd)", r#"eval("1 + 1")"#, "exec(code)", "eval(user)"]
+    );
+}
+
+#[test]
+fn negated_disjunctions_remove_every_branch() {
+    let formula = decorated(Formula::And(vec![
+        pattern("$F($X)"),
+        not(decorated(Formula::Or(vec![
+            pattern(r#"eval("...")"#),
+            pattern("exec($X)"),
+        ]))),
+    ]));
+
+    let texts = matched_texts(&formula, PYTHON_EVALS).expect("execute");
+
+    assert_eq!(texts, ["eval(cmd)", "eval(user)"]);
+}
+
+#[rstest]
+#[case::inside(decorated(Formula::Inside(Box::new(pattern("def f(): ...")))))]
+#[case::conjunction_with_inside(decorated(Formula::And(vec![
+    pattern("eval($X)"),
+    decorated(Formula::Inside(Box::new(pattern("def f(): ...")))),
+])))]
+#[case::bare_negation(not(pattern("eval($X)")))]
+fn unsupported_formulas_are_not_implemented(#[case] formula: Decorated<Formula>) {
+    assert_eq!(
+        first_code(matched_texts(&formula, PYTHON_EVALS)),
+        DiagnosticCode::NotImplemented
+    );
+}
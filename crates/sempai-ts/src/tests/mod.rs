@@ -0,0 +1,5 @@
+//! Unit tests for the `sempai_ts` crate.
+
+mod constraint_tests;
+mod execute_tests;
+mod rewrite_tests;
@@ -0,0 +1,21 @@
+//! Tests for Semgrep token rewriting.
+
+use rstest::rstest;
+
+use crate::rewrite::rewrite_semgrep_tokens;
+
+#[rstest]
+#[case("foo($X)", "foo($X)")]
+#[case("foo($_, $Y)", "foo($_, $Y)")]
+#[case("foo(...)", "foo($$$_)")]
+#[case("foo($...ARGS)", "foo($$$ARGS)")]
+#[case("foo($X, ...)", "foo($X, $$$_)")]
+#[case("fn $F() { ... }", "fn $F() { $$$_ }")]
+#[case("x..y", "x..y")]
+#[case(r#"log("...", $...REST)"#, r#"log("$_", $$$REST)"#)]
+#[case(r#"log("....")"#, r#"log("....")"#)]
+#[case(r#"log("... done")"#, r#"log("... done")"#)]
+#[case(r#"log("\"...", ...)"#, r#"log("\"...", $$$_)"#)]
+fn rewrites_semgrep_tokens(#[case] pattern: &str, #[case] expected: &str) {
+    assert_eq!(rewrite_semgrep_tokens(pattern), expected);
+}
@@ -55,6 +55,7 @@ pub struct Rule {
     pub(crate) mode_span: Option<SourceSpan>,
     pub(crate) message: Option<String>,
     pub(crate) languages: Vec<String>,
+    pub(crate) languages_span: Option<SourceSpan>,
     pub(crate) severity: Option<RuleSeverity>,
     pub(crate) min_version: Option<String>,
     pub(crate) max_version: Option<String>,
@@ -86,6 +87,10 @@ impl Rule {
     #[must_use]
     pub fn languages(&self) -> &[String] { &self.languages }
 
+    /// Returns the source span of the `languages` field when known.
+    #[must_use]
+    pub const fn languages_span(&self) -> Option<&SourceSpan> { self.languages_span.as_ref() }
+
     /// Returns the declared severity when present.
     #[must_use]
     pub const fn severity(&self) -> Option<&RuleSeverity> { self.severity.as_ref() }
@@ -195,6 +195,10 @@ fn build_rule(
         RuleMode::Taint => build_taint_rule(&raw, rule_span.clone())?,
     };
 
+    let languages_span = raw
+        .languages
+        .as_ref()
+        .and_then(|field| source_map.span_from_location(Some(field.referenced)));
     let languages = raw.languages.map(|value| value.value).unwrap_or_default();
     let message = raw.message.map(|value| value.value);
     let severity = raw
@@ -210,6 +214,7 @@ fn build_rule(
         mode_span,
         message,
         languages,
+        languages_span,
         severity,
         min_version,
         max_version,
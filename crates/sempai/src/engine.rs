@@ -15,6 +15,8 @@ use sempai_core::{
     Match,
     formula::{Decorated, Formula},
 };
+use sempai_dsl::parse_query;
+use sempai_ts::SourceFile;
 use sempai_yaml::{Rule, RulePrincipal, parse_rule_file};
 
 use crate::{
@@ -27,6 +29,7 @@ use crate::{
 #[derive(Debug)]
 pub struct QueryPlan {
     rule_id: String,
+    message: Option<String>,
     language: Language,
     /// The normalized canonical formula.
     formula: Arc<Decorated<Formula>>,
@@ -35,11 +38,13 @@ pub struct QueryPlan {
 impl QueryPlan {
     pub(crate) const fn new(
         rule_id: String,
+        message: Option<String>,
         language: Language,
         formula: Arc<Decorated<Formula>>,
     ) -> Self {
         Self {
             rule_id,
+            message,
             language,
             formula,
         }
@@ -49,6 +54,12 @@ impl QueryPlan {
     #[must_use]
     pub fn rule_id(&self) -> &str { &self.rule_id }
 
+    /// Returns the rule message when the source rule declared one.
+    ///
+    /// Plans compiled from the query DSL carry no message.
+    #[must_use]
+    pub fn message(&self) -> Option<&str> { self.message.as_deref() }
+
     /// Returns the target language.
     #[must_use]
     pub const fn language(&self) -> Language { self.language }
@@ -99,9 +110,16 @@ impl Engine {
 
     /// Compiles a YAML rule file into query plans.
     ///
+    /// One plan is produced for each declared language of each search rule.
+    /// Every plan retains the rule's `id` and `message`, so matches can be
+    /// attributed to the rule that produced them.
+    ///
     /// # Errors
     ///
-    /// Returns a diagnostic report if parsing, normalization, or validation fails.
+    /// Returns a diagnostic report if parsing, normalization, or validation
+    /// fails. Malformed YAML yields an `E_SEMPAI_YAML_PARSE` diagnostic and an
+    /// unknown language identifier yields an `E_SEMPAI_SCHEMA_INVALID`
+    /// diagnostic, each anchored at the offending source location.
     #[tracing::instrument(level = "info", skip_all, fields(rules = tracing::field::Empty))]
     pub fn compile_yaml(&self, yaml: &str) -> Result<Vec<QueryPlan>, DiagnosticReport> {
         let file = parse_rule_file(yaml, None)?;
@@ -141,36 +159,60 @@ impl Engine {
 
     /// Compiles a one-liner query DSL expression into a query plan.
     ///
+    /// The DSL currently accepts a single `pattern("...")` atom.
+    ///
     /// # Errors
     ///
-    /// Returns a diagnostic report if parsing or validation fails.
-    /// Currently returns a "not implemented" diagnostic for all inputs.
+    /// Returns an `E_SEMPAI_DSL_PARSE` diagnostic, anchored at the offending
+    /// token, if the expression is malformed.
+    #[tracing::instrument(level = "info", skip(self, dsl), fields(language = %language))]
     pub fn compile_dsl(
         &self,
-        _rule_id: &str,
-        _language: Language,
-        _dsl: &str,
+        rule_id: &str,
+        language: Language,
+        dsl: &str,
     ) -> Result<QueryPlan, DiagnosticReport> {
-        Err(DiagnosticReport::not_implemented("compile_dsl"))
+        let formula = parse_query(dsl)?;
+        tracing::debug!(rule_id, "dsl parsed successfully");
+        validate_formula(&formula)?;
+        Ok(QueryPlan::new(
+            rule_id.to_owned(),
+            None,
+            language,
+            Arc::new(formula),
+        ))
     }
 
     /// Executes a compiled query plan against a source snapshot.
     ///
+    /// Matches are attributed to the plan's rule id and returned in source
+    /// order, capped at the configured maximum matches per rule.
+    ///
     /// # Errors
     ///
-    /// Returns a diagnostic report if execution fails.
-    /// Currently returns a "not implemented" diagnostic for all inputs.
+    /// Returns an `E_SEMPAI_PATTERN_SNIPPET_PARSE_FAILED` diagnostic if a
+    /// pattern cannot be compiled for the plan's language, an
+    /// `E_SEMPAI_SCHEMA_INVALID` diagnostic if a `metavariable-regex` does not
+    /// compile, and a `NOT_IMPLEMENTED` diagnostic for formulas, constraints,
+    /// or languages the Tree-sitter backend does not support yet.
+    #[tracing::instrument(level = "info", skip(self, plan, source), fields(rule_id = plan.rule_id()))]
     pub fn execute(
         &self,
-        _plan: &QueryPlan,
-        _uri: &str,
-        _source: &str,
+        plan: &QueryPlan,
+        uri: &str,
+        source: &str,
     ) -> Result<Vec<Match>, DiagnosticReport> {
-        Err(DiagnosticReport::not_implemented("execute"))
+        let file = SourceFile::new(plan.language(), uri, source);
+        let matches = sempai_ts::execute(plan.rule_id(), plan.formula(), &file, &self.config)?;
+        tracing::debug!(matches = matches.len(), "plan executed");
+        Ok(matches)
     }
 }
 
 /// Compiles query plans for a single rule's languages.
+///
+/// Unknown language identifiers are reported against the rule's `languages`
+/// field, falling back to the rule span when the field location is unknown.
 fn compile_rule_plans(
     rule: &Rule,
     formula: Decorated<Formula>,
@@ -189,13 +231,14 @@ fn compile_rule_plans(
                 DiagnosticReport::validation_error(
                     DiagnosticCode::ESempaiSchemaInvalid,
                     format!("unsupported language '{lang_str}': {e}"),
-                    rule.rule_span().cloned(),
+                    rule.languages_span().or_else(|| rule.rule_span()).cloned(),
                     vec![],
                 )
             })?;
             tracing::debug!("query plan created");
             Ok(QueryPlan::new(
                 rule.id().to_owned(),
+                rule.message().map(str::to_owned),
                 language,
                 Arc::clone(&shared_formula),
             ))
@@ -27,12 +27,13 @@
 //! ```
 //! use sempai::{Engine, EngineConfig, Language};
 //!
-//! let config = EngineConfig::default();
-//! let engine = Engine::new(config);
-//! // Engine methods are stubbed and will return diagnostic errors
-//! // until the backend implementation is complete.
-//! let result = engine.compile_dsl("rule-1", Language::Rust, "pattern(\"fn $F\")");
-//! assert!(result.is_err());
+//! let engine = Engine::new(EngineConfig::default());
+//! let plan = engine.compile_dsl("rule-1", Language::Rust, "pattern(\"foo($X, $Y)\")")?;
+//!
+//! let matches = engine.execute(&plan, "file:///lib.rs", "fn f() { foo(1, 2); }")?;
+//! assert_eq!(matches.len(), 1);
+//! assert_eq!(matches[0].rule_id(), "rule-1");
+//! # Ok::<(), sempai::DiagnosticReport>(())
 //! ```
 
 mod engine;
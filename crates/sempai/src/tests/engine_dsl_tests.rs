@@ -0,0 +1,193 @@
+//! Tests for `Engine::compile_dsl` and `Engine::execute`.
+
+use rstest::rstest;
+use sempai_core::formula::{Atom, Formula, PatternAtom};
+
+use crate::{DiagnosticCode, Engine, EngineConfig, EngineLimits, Language, Match};
+
+const RUST_SOURCE: &str = "fn main() {\n    log(1, a);\n    skip(2, b);\n    log(3, c);\n}\n";
+
+fn default_engine() -> Engine { Engine::new(EngineConfig::default()) }
+
+fn matched_lines(matches: &[Match]) -> Vec<u32> {
+    matches
+        .iter()
+        .map(|found| found.span().start().line())
+        .collect()
+}
+
+#[test]
+fn compile_dsl_builds_a_pattern_plan() {
+    let plan = default_engine()
+        .compile_dsl("interactive", Language::Python, r#"pattern("eval($X)")"#)
+        .expect("valid DSL");
+
+    assert_eq!(plan.rule_id(), "interactive");
+    assert_eq!(plan.language(), Language::Python);
+    assert_eq!(
+        plan.formula().node,
+        Formula::Atom(Atom::Pattern(PatternAtom {
+            text: String::from("eval($X)"),
+        }))
+    );
+}
+
+#[rstest]
+#[case::unknown_function(r#"regex("a")"#, 0, 5)]
+#[case::unclosed(r#"pattern("a""#, 11, 11)]
+#[case::unterminated_string(r#"pattern("a)"#, 8, 11)]
+fn compile_dsl_reports_syntax_errors(#[case] dsl: &str, #[case] start: u32, #[case] end: u32) {
+    let report = default_engine()
+        .compile_dsl("interactive", Language::Rust, dsl)
+        .expect_err("malformed DSL");
+
+    let first = report.diagnostics().first().expect("one diagnostic");
+    assert_eq!(first.code(), DiagnosticCode::ESempaiDslParse);
+    let span = first.primary_span().expect("primary span");
+    assert_eq!((span.start(), span.end()), (start, end));
+}
+
+#[test]
+fn execute_runs_compiled_dsl_plans() {
+    let engine = default_engine();
+    let plan = engine
+        .compile_dsl("logging", Language::Rust, r#"pattern("log($N, $V)")"#)
+        .expect("valid DSL");
+
+    let matches = engine
+        .execute(&plan, "file:///src/main.rs", RUST_SOURCE)
+        .expect("execution succeeds");
+
+    assert_eq!(matched_lines(&matches), [1, 3]);
+    assert!(matches.iter().all(|found| found.rule_id() == "logging"));
+    assert!(
+        matches
+            .iter()
+            .all(|found| found.uri() == "file:///src/main.rs")
+    );
+    let first = matches.first().expect("first match");
+    assert_eq!(first.captures().keys().collect::<Vec<_>>(), ["$N", "$V"]);
+}
+
+#[test]
+fn execute_runs_compiled_yaml_plans() {
+    let engine = default_engine();
+    let plans = engine
+        .compile_yaml(concat!(
+            "rules:\n",
+            "  - id: demo.log\n",
+            "    message: logging call\n",
+            "    languages: [rust]\n",
+            "    severity: INFO\n",
+            "    pattern: log(...)\n",
+        ))
+        .expect("valid YAML");
+    let plan = plans.first().expect("one plan");
+
+    let matches = engine
+        .execute(plan, "file:///src/main.rs", RUST_SOURCE)
+        .expect("execution succeeds");
+
+    assert_eq!(matched_lines(&matches), [1, 3]);
+    assert!(matches.iter().all(|found| found.rule_id() == "demo.log"));
+}
+
+#[test]
+fn execute_caps_matches_per_rule() {
+    let engine = Engine::new(EngineConfig::new(EngineLimits::new(1, 1024, 1024), false));
+    let plan = engine
+        .compile_dsl("logging", Language::Rust, r#"pattern("log($N, $V)")"#)
+        .expect("valid DSL");
+
+    let matches = engine
+        .execute(&plan, "file:///src/main.rs", RUST_SOURCE)
+        .expect("execution succeeds");
+
+    assert_eq!(matched_lines(&matches), [1]);
+}
+
+#[test]
+fn execute_reports_unsupported_languages() {
+    let engine = default_engine();
+    let plan = engine
+        .compile_dsl("go-rule", Language::Go, r#"pattern("fmt.Println($X)")"#)
+        .expect("valid DSL");
+
+    let report = engine
+        .execute(&plan, "file:///main.go", "package main\n")
+        .expect_err("Go execution is not available");
+
+    let first = report.diagnostics().first().expect("one diagnostic");
+    assert_eq!(first.code(), DiagnosticCode::NotImplemented);
+}
+
+const PYTHON_EVALS: &str = "eval(cmd)\neval(\"1 + 1\")\nexec(code)\neval(user)\n";
+
+fn execute_python_rule(yaml: &str) -> Vec<u32> {
+    let engine = default_engine();
+    let plans = engine.compile_yaml(yaml).expect("valid YAML");
+    let plan = plans.first().expect("one plan");
+    let matches = engine
+        .execute(plan, "file:///src/app.py", PYTHON_EVALS)
+        .expect("execution succeeds");
+    matched_lines(&matches)
+}
+
+#[test]
+fn execute_skips_matches_excluded_by_pattern_not() {
+    let lines = execute_python_rule(concat!(
+        "rules:\n",
+        "  - id: demo.dynamic.eval\n",
+        "    message: eval of a non-literal expression\n",
+        "    languages: [python]\n",
+        "    severity: WARNING\n",
+        "    patterns:\n",
+        "      - pattern: eval($X)\n",
+        "      - pattern-not: eval(\"...\")\n",
+    ));
+
+    assert_eq!(lines, [0, 3]);
+}
+
+#[test]
+fn execute_unions_pattern_either_branches() {
+    let lines = execute_python_rule(concat!(
+        "rules:\n",
+        "  - id: demo.dynamic.code\n",
+        "    message: dynamic code execution\n",
+        "    languages: [python]\n",
+        "    severity: WARNING\n",
+        "    pattern-either:\n",
+        "      - pattern: exec($X)\n",
+        "      - pattern: eval(user)\n",
+    ));
+
+    assert_eq!(lines, [2, 3]);
+}
+
+#[test]
+fn execute_filters_captures_by_metavariable_regex() {
+    let engine = default_engine();
+    let plans = engine
+        .compile_yaml(concat!(
+            "rules:\n",
+            "  - id: demo.os.call\n",
+            "    message: direct operating system call\n",
+            "    languages: [python]\n",
+            "    severity: WARNING\n",
+            "    patterns:\n",
+            "      - pattern: $FUNC(...)\n",
+            "      - metavariable-regex:\n",
+            "          metavariable: $FUNC\n",
+            "          regex: ^os\\.\n",
+        ))
+        .expect("valid YAML");
+    let plan = plans.first().expect("one plan");
+    let source = "subprocess.call(cmd)\nos.system(cmd)\nprint(os.name)\n";
+
+    let matches = engine
+        .execute(plan, "file:///src/app.py", source)
+        .expect("execution succeeds");
+
+    assert_eq!(matched_lines(&matches), [1]);
+}
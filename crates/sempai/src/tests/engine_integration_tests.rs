@@ -3,8 +3,10 @@
 use sempai_core::formula::{Atom, Constraint, Decorated, Formula};
 
 use crate::{
+    DiagnosticCode,
     Engine,
     EngineConfig,
+    Language,
     semantic_check::{reset_validate_constraints_call_count, validate_constraints_call_count},
 };
 
@@ -103,3 +105,62 @@ fn compile_yaml_invokes_validate_constraints_for_where_clauses() {
     assert_eq!(validate_constraints_call_count(), 1);
     assert!(!formula.where_clauses.is_empty());
 }
+
+#[test]
+fn compile_yaml_plans_retain_rule_id_and_message() {
+    let yaml = concat!(
+        "rules:\n",
+        "  - id: demo.first\n",
+        "    message: first rule\n",
+        "    languages: [rust, python]\n",
+        "    severity: WARNING\n",
+        "    pattern: foo($X)\n",
+        "  - id: demo.second\n",
+        "    message: second rule\n",
+        "    languages: [python]\n",
+        "    severity: WARNING\n",
+        "    pattern: bar($X)\n",
+    );
+
+    let plans = compile_yaml(yaml);
+
+    let attributed: Vec<_> = plans
+        .iter()
+        .map(|plan| (plan.rule_id(), plan.message(), plan.language()))
+        .collect();
+    assert_eq!(
+        attributed,
+        [
+            ("demo.first", Some("first rule"), Language::Rust),
+            ("demo.first", Some("first rule"), Language::Python),
+            ("demo.second", Some("second rule"), Language::Python),
+        ]
+    );
+}
+
+#[test]
+fn compile_yaml_reports_unknown_languages_at_the_languages_field() {
+    let yaml = concat!(
+        "rules:\n",
+        "  - id: demo.unknown.language\n",
+        "    message: unknown language\n",
+        "    languages: [rust, cobol]\n",
+        "    severity: WARNING\n",
+        "    pattern: foo($X)\n",
+    );
+
+    let report = Engine::new(EngineConfig::default())
+        .compile_yaml(yaml)
+        .expect_err("unknown language");
+
+    let first = report.diagnostics().first().expect("one diagnostic");
+    assert_eq!(first.code(), DiagnosticCode::ESempaiSchemaInvalid);
+    assert!(
+        first.message().contains("'cobol'"),
+        "unexpected message: {}",
+        first.message()
+    );
+    let span = first.primary_span().expect("primary span");
+    let languages_start = yaml.find("[rust").expect("languages value");
+    assert_eq!(span.start() as usize, languages_start);
+}
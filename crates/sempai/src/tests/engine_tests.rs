@@ -1,6 +1,6 @@
 //! Tests for the `Engine` and `QueryPlan` types.
 
-use std::{collections::BTreeSet, sync::Arc};
+use std::collections::BTreeSet;
 
 use rstest::rstest;
 use sempai_core::formula::{Atom, Decorated, Formula, PatternAtom, TreeSitterQueryAtom};
@@ -368,26 +368,3 @@ fn compile_yaml_returns_unsupported_mode_for_unknown_modes() {
         "custom-mode",
     );
 }
-
-#[test]
-fn compile_dsl_returns_not_implemented() {
-    let engine = default_engine();
-    let result = engine.compile_dsl("test-rule", Language::Python, "pattern(\"def $F\")");
-    let (code, diag) = first_diagnostic_of_err(result);
-    assert_eq!(code, DiagnosticCode::NotImplemented);
-    assert!(diag.message().contains("compile_dsl"));
-}
-
-#[test]
-fn execute_returns_not_implemented() {
-    let engine = default_engine();
-    let plan = QueryPlan::new(
-        String::from("test-rule"),
-        Language::Rust,
-        Arc::new(dummy_formula()),
-    );
-    let result = engine.execute(&plan, "file:///test.rs", "fn main() {}");
-    let (code, diag) = first_diagnostic_of_err(result);
-    assert_eq!(code, DiagnosticCode::NotImplemented);
-    assert!(diag.message().contains("execute"));
-}
@@ -3,6 +3,7 @@
 mod behaviour;
 mod constraint_walker_proptest;
 mod diagnostic_snapshot_tests;
+mod engine_dsl_tests;
 mod engine_integration_tests;
 mod engine_tests;
 mod normalization_constraint_tests;
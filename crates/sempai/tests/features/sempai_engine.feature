@@ -40,12 +40,26 @@ Feature: Sempai engine facade behaviour
     Then compilation fails with code "E_SEMPAI_UNSUPPORTED_MODE"
     And the first diagnostic message contains "custom-mode"
 
-  Scenario: Engine compile_dsl returns not-implemented error
+  Scenario: Engine compile_dsl returns a query plan for a pattern
     Given an engine with default configuration
     When DSL "pattern(\"fn $F\")" is compiled for language "rust"
-    Then compilation fails with code "NOT_IMPLEMENTED"
+    Then compilation succeeds with 1 query plan
+    And the first query plan has rule id "interactive"
+    And the first query plan formula is pattern atom "fn $F"
+
+  Scenario: Engine compile_dsl reports DSL syntax errors
+    Given an engine with default configuration
+    When DSL "pattern(" is compiled for language "rust"
+    Then compilation fails with code "E_SEMPAI_DSL_PARSE"
+
+  Scenario: Engine execute returns matches for a compiled plan
+    Given an engine with default configuration
+    When DSL "pattern(\"foo($X, $Y)\")" is compiled for language "rust"
+    And the first query plan is executed against "fn main() { foo(1, 2); bar(3, 4); }"
+    Then execution produces 1 match
 
-  Scenario: Engine execute returns not-implemented error
+  Scenario: Engine execute reports languages without a backend
     Given an engine with default configuration
-    When a query plan is executed
+    When DSL "pattern(\"fmt.Println($X)\")" is compiled for language "go"
+    And the first query plan is executed against "package main"
     Then execution fails with code "NOT_IMPLEMENTED"
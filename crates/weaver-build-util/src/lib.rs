@@ -1,19 +1,19 @@
 //! Build-time utilities shared across Weaver build scripts.
 
-use std::{
-    env,
-    io,
-    process,
-    sync::atomic::{AtomicU64, Ordering},
-    time::{SystemTime, UNIX_EPOCH},
-};
+mod man_page;
+
+use std::env;
 
 use camino::{Utf8Path, Utf8PathBuf};
-use cap_std::fs::Dir;
-use time::{OffsetDateTime, format_description::well_known::Iso8601};
+pub use man_page::{write_man_page, write_man_pages};
+use time::{
+    OffsetDateTime,
+    format_description::well_known::{Iso8601, Rfc3339},
+    formatting::Formattable,
+};
 
 const FALLBACK_DATE: &str = "1970-01-01";
-static MAN_PAGE_TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+const FALLBACK_DATETIME: &str = "1970-01-01T00:00:00Z";
 
 struct SourceDate {
     raw: String,
@@ -42,20 +42,59 @@ enum SourceDateError {
 /// assert!(warnings.is_empty());
 /// ```
 pub fn manual_date(source_date_epoch: Option<&str>, warnings: &mut Vec<String>) -> String {
+    format_source_date(source_date_epoch, warnings, &Iso8601::DATE, FALLBACK_DATE)
+}
+
+/// Derive a full RFC 3339 timestamp from a `SOURCE_DATE_EPOCH` value.
+///
+/// Behaves like [`manual_date`], including its warnings, but keeps the time of
+/// day and falls back to the Unix epoch at midnight UTC.
+///
+/// # Examples
+/// ```
+/// use weaver_build_util::manual_datetime;
+///
+/// let mut warnings = Vec::new();
+/// let datetime = manual_datetime(Some("0"), &mut warnings);
+///
+/// assert_eq!(datetime, "1970-01-01T00:00:00Z");
+/// assert!(warnings.is_empty());
+/// ```
+///
+/// Invalid values fall back to the epoch and record a warning:
+/// ```
+/// use weaver_build_util::manual_datetime;
+///
+/// let mut warnings = Vec::new();
+/// let datetime = manual_datetime(Some("soon"), &mut warnings);
+///
+/// assert_eq!(datetime, "1970-01-01T00:00:00Z");
+/// assert_eq!(warnings.len(), 1);
+/// ```
+pub fn manual_datetime(source_date_epoch: Option<&str>, warnings: &mut Vec<String>) -> String {
+    format_source_date(source_date_epoch, warnings, &Rfc3339, FALLBACK_DATETIME)
+}
+
+fn format_source_date(
+    source_date_epoch: Option<&str>,
+    warnings: &mut Vec<String>,
+    format: &(impl Formattable + ?Sized),
+    fallback: &str,
+) -> String {
     let source = match source_date_time(source_date_epoch) {
         Ok(source) => source,
         Err(error) => {
-            push_source_date_warning(warnings, &error);
-            return FALLBACK_DATE.into();
+            push_source_date_warning(warnings, &error, fallback);
+            return fallback.into();
         }
     };
 
     let SourceDate { raw, value } = source;
-    value.format(&Iso8601::DATE).unwrap_or_else(|_| {
+    value.format(format).unwrap_or_else(|_| {
         warnings.push(format!(
-            "Invalid SOURCE_DATE_EPOCH '{raw}'; formatting failed; falling back to {FALLBACK_DATE}"
+            "Invalid SOURCE_DATE_EPOCH '{raw}'; formatting failed; falling back to {fallback}"
         ));
-        FALLBACK_DATE.into()
+        fallback.into()
     })
 }
 
@@ -93,16 +132,16 @@ fn source_date_time(source_date_epoch: Option<&str>) -> Result<SourceDate, Sourc
     })
 }
 
-fn push_source_date_warning(warnings: &mut Vec<String>, error: &SourceDateError) {
+fn push_source_date_warning(warnings: &mut Vec<String>, error: &SourceDateError, fallback: &str) {
     match error {
         SourceDateError::Missing => {}
         SourceDateError::InvalidInteger { raw } => warnings.push(format!(
             "Invalid SOURCE_DATE_EPOCH '{raw}'; expected integer seconds since Unix epoch; \
-             falling back to {FALLBACK_DATE}"
+             falling back to {fallback}"
         )),
         SourceDateError::InvalidTimestamp { raw } => warnings.push(format!(
             "Invalid SOURCE_DATE_EPOCH '{raw}'; not a valid Unix timestamp; falling back to \
-             {FALLBACK_DATE}"
+             {fallback}"
         )),
     }
 }
@@ -169,164 +208,3 @@ pub fn out_dir_for_target_profile(
         .unwrap_or_else(|| Utf8PathBuf::from("target"));
     base.join(format!("generated-man/{target}/{profile}"))
 }
-
-/// Creates a directory and all its parents using capability-based filesystem operations.
-fn create_dir_all_cap(base: &Dir, path: &Utf8Path) -> io::Result<()> {
-    let mut current_path = Utf8PathBuf::new();
-
-    for component in path.components() {
-        current_path.push(component.as_str());
-        match base.create_dir(&current_path) {
-            Ok(()) => {}
-            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {}
-            Err(err) => return Err(err),
-        }
-    }
-
-    Ok(())
-}
-
-fn find_existing_ancestor(dir: &Utf8Path) -> &Utf8Path {
-    let mut candidate = dir;
-    loop {
-        if Dir::open_ambient_dir(candidate, cap_std::ambient_authority()).is_ok() {
-            return candidate;
-        }
-        let Some(parent) = candidate.parent() else {
-            break;
-        };
-        if parent == candidate {
-            break;
-        }
-        candidate = parent;
-    }
-    Utf8Path::new(".")
-}
-
-fn ensure_target_dir(base_dir: Dir, relative_path: &Utf8Path) -> io::Result<Dir> {
-    if relative_path.as_str().is_empty() {
-        return Ok(base_dir);
-    }
-    create_dir_all_cap(&base_dir, relative_path)?;
-    base_dir.open_dir(relative_path)
-}
-
-fn staging_file_name(page_name: &str) -> String {
-    let nanos = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|duration| duration.as_nanos())
-        .unwrap_or_default();
-    let counter = MAN_PAGE_TMP_COUNTER.fetch_add(1, Ordering::Relaxed);
-    format!("{page_name}.tmp-{}-{nanos}-{counter}", process::id())
-}
-
-/// Write a man page to the provided directory with best-effort replacement.
-///
-/// # Errors
-/// Returns any filesystem errors encountered while creating the directory,
-/// writing the file, or replacing an existing page. On platforms where
-/// overwriting a destination rename is unavailable, replacement falls back to a
-/// non-atomic delete-then-rename sequence.
-///
-/// # Examples
-/// ```no_run
-/// use weaver_build_util::write_man_page;
-///
-/// let dir = camino::Utf8PathBuf::from(std::env::temp_dir().to_string_lossy().as_ref());
-/// let data = b".TH WEAVER 1 1970-01-01 weaver 0.1.0\n";
-/// let path = write_man_page(data, &dir, "weaver.1").expect("man page write failed");
-///
-/// assert!(path.ends_with("weaver.1"));
-/// ```
-pub fn write_man_page(data: &[u8], dir: &Utf8Path, page_name: &str) -> io::Result<Utf8PathBuf> {
-    let existing_ancestor = find_existing_ancestor(dir);
-    let base_dir = Dir::open_ambient_dir(existing_ancestor, cap_std::ambient_authority())?;
-    let relative_path = dir.strip_prefix(existing_ancestor).unwrap_or(dir);
-    let target_dir = ensure_target_dir(base_dir, relative_path)?;
-    let tmp = staging_file_name(page_name);
-    target_dir.write(&tmp, data)?;
-
-    match target_dir.rename(&tmp, &target_dir, page_name) {
-        Ok(()) => {}
-        Err(error) if should_retry_replace(&error) => {
-            match target_dir.rename(&tmp, &target_dir, page_name) {
-                Ok(()) => {}
-                Err(retry_error) if should_retry_replace(&retry_error) => {
-                    remove_existing_file(&target_dir, page_name)?;
-                    target_dir.rename(&tmp, &target_dir, page_name)?;
-                }
-                Err(retry_error) => return Err(retry_error),
-            }
-        }
-        Err(error) => return Err(error),
-    }
-
-    Ok(dir.join(page_name))
-}
-
-fn should_retry_replace(error: &io::Error) -> bool {
-    error.kind() == io::ErrorKind::AlreadyExists
-        || (cfg!(windows) && error.kind() == io::ErrorKind::PermissionDenied)
-}
-
-fn remove_existing_file(dir: &Dir, name: &str) -> io::Result<()> {
-    match dir.remove_file(name) {
-        Ok(()) => Ok(()),
-        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
-        Err(error) => Err(error),
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    //! Regression tests for capability-based filesystem helpers.
-
-    use super::*;
-
-    #[test]
-    fn write_man_page_creates_nested_directories() -> Result<(), String> {
-        let temp_dir = tempfile::tempdir().map_err(|error| format!("tempdir: {error}"))?;
-        let temp_path = Utf8PathBuf::from_path_buf(temp_dir.path().to_path_buf())
-            .map_err(|path| format!("utf-8 tempdir: {}", path.display()))?;
-        let nested_dir = temp_path.join("target/generated-man/test-target/debug");
-        let temp_dir_handle = Dir::open_ambient_dir(&temp_path, cap_std::ambient_authority())
-            .map_err(|error| format!("open tempdir: {error}"))?;
-        let existing_output_path = nested_dir.join("weaver.1");
-        let existing_relative_path = existing_output_path
-            .strip_prefix(&temp_path)
-            .map_err(|error| format!("existing path should live under tempdir: {error}"))?;
-        let existing_parent = existing_relative_path
-            .parent()
-            .ok_or_else(|| String::from("existing path should have parent"))?;
-        temp_dir_handle
-            .create_dir_all(existing_parent)
-            .map_err(|error| format!("create existing parent dirs: {error}"))?;
-        temp_dir_handle
-            .write(existing_relative_path, b"old content\n")
-            .map_err(|error| format!("seed existing man page: {error}"))?;
-
-        let output_path = write_man_page(b".TH WEAVER 1\n", &nested_dir, "weaver.1")
-            .map_err(|error| format!("write man page: {error}"))?;
-        let relative_output_path = output_path
-            .strip_prefix(&temp_path)
-            .map_err(|error| format!("output path should live under tempdir: {error}"))?;
-
-        let expected_output_path = nested_dir.join("weaver.1");
-        if output_path != expected_output_path {
-            return Err(format!(
-                "unexpected output path: expected {expected_output_path}, got {output_path}"
-            ));
-        }
-
-        let written_content = temp_dir_handle
-            .read_to_string(relative_output_path)
-            .map_err(|error| format!("read man page: {error}"))?;
-        if written_content != ".TH WEAVER 1\n" {
-            return Err(format!(
-                "unexpected man page content: expected {:?}, got {:?}",
-                ".TH WEAVER 1\n", written_content
-            ));
-        }
-        Ok(())
-    }
-}
@@ -0,0 +1,297 @@
+//! Atomic man page writers used by build scripts.
+
+use std::{
+    io::{self, Write},
+    process,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use cap_std::fs::Dir;
+use flate2::{Compression, write::GzEncoder};
+
+static MAN_PAGE_TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Creates a directory and all its parents using capability-based filesystem operations.
+fn create_dir_all_cap(base: &Dir, path: &Utf8Path) -> io::Result<()> {
+    let mut current_path = Utf8PathBuf::new();
+
+    for component in path.components() {
+        current_path.push(component.as_str());
+        match base.create_dir(&current_path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {}
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(())
+}
+
+fn find_existing_ancestor(dir: &Utf8Path) -> &Utf8Path {
+    let mut candidate = dir;
+    loop {
+        if Dir::open_ambient_dir(candidate, cap_std::ambient_authority()).is_ok() {
+            return candidate;
+        }
+        let Some(parent) = candidate.parent() else {
+            break;
+        };
+        if parent == candidate {
+            break;
+        }
+        candidate = parent;
+    }
+    Utf8Path::new(".")
+}
+
+fn ensure_target_dir(base_dir: Dir, relative_path: &Utf8Path) -> io::Result<Dir> {
+    if relative_path.as_str().is_empty() {
+        return Ok(base_dir);
+    }
+    create_dir_all_cap(&base_dir, relative_path)?;
+    base_dir.open_dir(relative_path)
+}
+
+fn staging_file_name(page_name: &str) -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default();
+    let counter = MAN_PAGE_TMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("{page_name}.tmp-{}-{nanos}-{counter}", process::id())
+}
+
+/// Write a man page to the provided directory with best-effort replacement.
+///
+/// # Errors
+/// Returns any filesystem errors encountered while creating the directory,
+/// writing the file, or replacing an existing page. On platforms where
+/// overwriting a destination rename is unavailable, replacement falls back to a
+/// non-atomic delete-then-rename sequence.
+///
+/// # Examples
+/// ```no_run
+/// use weaver_build_util::write_man_page;
+///
+/// let dir = camino::Utf8PathBuf::from(std::env::temp_dir().to_string_lossy().as_ref());
+/// let data = b".TH WEAVER 1 1970-01-01 weaver 0.1.0\n";
+/// let path = write_man_page(data, &dir, "weaver.1").expect("man page write failed");
+///
+/// assert!(path.ends_with("weaver.1"));
+/// ```
+pub fn write_man_page(data: &[u8], dir: &Utf8Path, page_name: &str) -> io::Result<Utf8PathBuf> {
+    let target_dir = open_output_dir(dir)?;
+    replace_file(&target_dir, page_name, data)?;
+    Ok(dir.join(page_name))
+}
+
+/// Write several man pages to the provided directory, optionally gzipping them.
+///
+/// Each `(page_name, data)` pair is written with the same best-effort atomic
+/// replacement as [`write_man_page`]. When `compress` is set, a
+/// `{page_name}.gz` copy is written alongside each page for packaging. The
+/// returned paths list every file written, with each compressed copy directly
+/// after its page.
+///
+/// # Errors
+/// Returns the first filesystem or compression error encountered; pages
+/// written before the failure are left in place.
+///
+/// # Examples
+/// ```no_run
+/// use weaver_build_util::write_man_pages;
+///
+/// let dir = camino::Utf8PathBuf::from(std::env::temp_dir().to_string_lossy().as_ref());
+/// let pages: &[(&str, &[u8])] = &[
+///     ("weaver.1", b".TH WEAVER 1\n"),
+///     ("weaver-daemon.1", b".TH WEAVER-DAEMON 1\n"),
+/// ];
+/// let paths = write_man_pages(pages, &dir, true).expect("man page write failed");
+///
+/// assert_eq!(paths.len(), 4);
+/// assert!(paths[1].ends_with("weaver.1.gz"));
+/// ```
+pub fn write_man_pages(
+    pages: &[(&str, &[u8])],
+    dir: &Utf8Path,
+    compress: bool,
+) -> io::Result<Vec<Utf8PathBuf>> {
+    let target_dir = open_output_dir(dir)?;
+    let mut written = Vec::new();
+    for &(page_name, data) in pages {
+        replace_file(&target_dir, page_name, data)?;
+        written.push(dir.join(page_name));
+        if compress {
+            let compressed_name = format!("{page_name}.gz");
+            replace_file(&target_dir, &compressed_name, &gzip(data)?)?;
+            written.push(dir.join(compressed_name));
+        }
+    }
+    Ok(written)
+}
+
+fn gzip(data: &[u8]) -> io::Result<Vec<u8>> {
+    // The default gzip header records no modification time, keeping the output
+    // reproducible.
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(data)?;
+    encoder.finish()
+}
+
+fn open_output_dir(dir: &Utf8Path) -> io::Result<Dir> {
+    let existing_ancestor = find_existing_ancestor(dir);
+    let base_dir = Dir::open_ambient_dir(existing_ancestor, cap_std::ambient_authority())?;
+    let relative_path = dir.strip_prefix(existing_ancestor).unwrap_or(dir);
+    ensure_target_dir(base_dir, relative_path)
+}
+
+/// Stages `data` beside `page_name` and renames it into place, retrying once
+/// before falling back to delete-then-rename.
+fn replace_file(target_dir: &Dir, page_name: &str, data: &[u8]) -> io::Result<()> {
+    let tmp = staging_file_name(page_name);
+    target_dir.write(&tmp, data)?;
+
+    match target_dir.rename(&tmp, target_dir, page_name) {
+        Ok(()) => {}
+        Err(error) if should_retry_replace(&error) => {
+            match target_dir.rename(&tmp, target_dir, page_name) {
+                Ok(()) => {}
+                Err(retry_error) if should_retry_replace(&retry_error) => {
+                    remove_existing_file(target_dir, page_name)?;
+                    target_dir.rename(&tmp, target_dir, page_name)?;
+                }
+                Err(retry_error) => return Err(retry_error),
+            }
+        }
+        Err(error) => return Err(error),
+    }
+
+    Ok(())
+}
+
+fn should_retry_replace(error: &io::Error) -> bool {
+    error.kind() == io::ErrorKind::AlreadyExists
+        || (cfg!(windows) && error.kind() == io::ErrorKind::PermissionDenied)
+}
+
+fn remove_existing_file(dir: &Dir, name: &str) -> io::Result<()> {
+    match dir.remove_file(name) {
+        Ok(()) => Ok(()),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(error) => Err(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Regression tests for capability-based filesystem helpers.
+
+    use super::*;
+
+    #[test]
+    fn write_man_page_creates_nested_directories() -> Result<(), String> {
+        let temp_dir = tempfile::tempdir().map_err(|error| format!("tempdir: {error}"))?;
+        let temp_path = Utf8PathBuf::from_path_buf(temp_dir.path().to_path_buf())
+            .map_err(|path| format!("utf-8 tempdir: {}", path.display()))?;
+        let nested_dir = temp_path.join("target/generated-man/test-target/debug");
+        let temp_dir_handle = Dir::open_ambient_dir(&temp_path, cap_std::ambient_authority())
+            .map_err(|error| format!("open tempdir: {error}"))?;
+        let existing_output_path = nested_dir.join("weaver.1");
+        let existing_relative_path = existing_output_path
+            .strip_prefix(&temp_path)
+            .map_err(|error| format!("existing path should live under tempdir: {error}"))?;
+        let existing_parent = existing_relative_path
+            .parent()
+            .ok_or_else(|| String::from("existing path should have parent"))?;
+        temp_dir_handle
+            .create_dir_all(existing_parent)
+            .map_err(|error| format!("create existing parent dirs: {error}"))?;
+        temp_dir_handle
+            .write(existing_relative_path, b"old content\n")
+            .map_err(|error| format!("seed existing man page: {error}"))?;
+
+        let output_path = write_man_page(b".TH WEAVER 1\n", &nested_dir, "weaver.1")
+            .map_err(|error| format!("write man page: {error}"))?;
+        let relative_output_path = output_path
+            .strip_prefix(&temp_path)
+            .map_err(|error| format!("output path should live under tempdir: {error}"))?;
+
+        let expected_output_path = nested_dir.join("weaver.1");
+        if output_path != expected_output_path {
+            return Err(format!(
+                "unexpected output path: expected {expected_output_path}, got {output_path}"
+            ));
+        }
+
+        let written_content = temp_dir_handle
+            .read_to_string(relative_output_path)
+            .map_err(|error| format!("read man page: {error}"))?;
+        if written_content != ".TH WEAVER 1\n" {
+            return Err(format!(
+                "unexpected man page content: expected {:?}, got {:?}",
+                ".TH WEAVER 1\n", written_content
+            ));
+        }
+        Ok(())
+    }
+    #[test]
+    fn write_man_pages_emits_raw_and_gzipped_pages() -> Result<(), String> {
+        use std::io::Read;
+
+        use flate2::read::GzDecoder;
+
+        let temp_dir = tempfile::tempdir().map_err(|error| format!("tempdir: {error}"))?;
+        let temp_path = Utf8PathBuf::from_path_buf(temp_dir.path().to_path_buf())
+            .map_err(|path| format!("utf-8 tempdir: {}", path.display()))?;
+        let output_dir = temp_path.join("generated-man");
+        let pages: &[(&str, &[u8])] = &[
+            ("weaver.1", b".TH WEAVER 1\n"),
+            ("weaver-daemon.1", b".TH WEAVER-DAEMON 1\n"),
+        ];
+
+        let written = write_man_pages(pages, &output_dir, true)
+            .map_err(|error| format!("write man pages: {error}"))?;
+
+        let expected: Vec<Utf8PathBuf> = [
+            "weaver.1",
+            "weaver.1.gz",
+            "weaver-daemon.1",
+            "weaver-daemon.1.gz",
+        ]
+        .iter()
+        .map(|name| output_dir.join(name))
+        .collect();
+        if written != expected {
+            return Err(format!(
+                "unexpected paths: expected {expected:?}, got {written:?}"
+            ));
+        }
+
+        let output_handle = Dir::open_ambient_dir(&output_dir, cap_std::ambient_authority())
+            .map_err(|error| format!("open output dir: {error}"))?;
+        for &(page_name, data) in pages {
+            let raw = output_handle
+                .read(page_name)
+                .map_err(|error| format!("read {page_name}: {error}"))?;
+            if raw != data {
+                return Err(format!("unexpected content in {page_name}: {raw:?}"));
+            }
+
+            let compressed = output_handle
+                .read(format!("{page_name}.gz"))
+                .map_err(|error| format!("read {page_name}.gz: {error}"))?;
+            let mut decompressed = Vec::new();
+            GzDecoder::new(compressed.as_slice())
+                .read_to_end(&mut decompressed)
+                .map_err(|error| format!("decompress {page_name}.gz: {error}"))?;
+            if decompressed != data {
+                return Err(format!(
+                    "unexpected decompressed content in {page_name}.gz: {decompressed:?}"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -132,7 +132,7 @@ fn normalise_comment_line(line: &str, language: SupportedLanguage) -> Option<Str
     match language {
         SupportedLanguage::Rust => rust_comment(line),
         SupportedLanguage::Python => python_comment(line),
-        SupportedLanguage::TypeScript => ts_comment(line),
+        SupportedLanguage::TypeScript | SupportedLanguage::JavaScript => ts_comment(line),
     }
 }
 
@@ -20,7 +20,7 @@ pub(super) fn top_level_imports(
     let kinds: &[&str] = match language {
         SupportedLanguage::Rust => &["use_declaration", "extern_crate_declaration"],
         SupportedLanguage::Python => &["import_statement", "import_from_statement"],
-        SupportedLanguage::TypeScript => &["import_statement"],
+        SupportedLanguage::TypeScript | SupportedLanguage::JavaScript => &["import_statement"],
     };
 
     let mut cursor = root.walk();
@@ -102,7 +102,7 @@ pub(super) fn normalise_import(language: SupportedLanguage, raw: &str) -> String
             .trim_start_matches("import ")
             .trim()
             .to_owned(),
-        SupportedLanguage::TypeScript => trimmed
+        SupportedLanguage::TypeScript | SupportedLanguage::JavaScript => trimmed
             .trim_start_matches("import ")
             .trim_end_matches(';')
             .trim()
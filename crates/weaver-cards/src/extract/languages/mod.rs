@@ -18,7 +18,9 @@ pub(super) fn collect_entities(
     match language {
         SupportedLanguage::Rust => rust::collect(root, source),
         SupportedLanguage::Python => python::collect(root, source),
-        SupportedLanguage::TypeScript => typescript::collect(root, source),
+        SupportedLanguage::TypeScript | SupportedLanguage::JavaScript => {
+            typescript::collect(root, source)
+        }
     }
 }
 
@@ -1,4 +1,7 @@
 //! TypeScript entity extraction rules.
+//!
+//! JavaScript shares these rules: its grammar uses the same declaration node
+//! kinds and simply never produces the TypeScript-only ones.
 
 use tree_sitter::Node;
 
@@ -25,6 +25,7 @@ pub(super) const fn to_card_language(language: SupportedLanguage) -> CardLanguag
         SupportedLanguage::Rust => CardLanguage::Rust,
         SupportedLanguage::Python => CardLanguage::Python,
         SupportedLanguage::TypeScript => CardLanguage::TypeScript,
+        SupportedLanguage::JavaScript => CardLanguage::JavaScript,
     }
 }
 
@@ -103,6 +103,9 @@ pub enum CardLanguage {
     /// TypeScript source.
     #[serde(rename = "typescript")]
     TypeScript,
+    /// JavaScript source.
+    #[serde(rename = "javascript")]
+    JavaScript,
 }
 
 /// Location-based reference to a symbol.
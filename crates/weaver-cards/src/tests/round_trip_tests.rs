@@ -102,6 +102,7 @@ fn refusal_response_round_trips() {
 #[case::rust(CardLanguage::Rust, "\"rust\"")]
 #[case::python(CardLanguage::Python, "\"python\"")]
 #[case::typescript(CardLanguage::TypeScript, "\"typescript\"")]
+#[case::javascript(CardLanguage::JavaScript, "\"javascript\"")]
 fn card_language_serialises_as_snake_case(#[case] lang: CardLanguage, #[case] expected: &str) {
     let json = serde_json::to_string(&lang).expect("serialize");
     assert_eq!(json, expected);
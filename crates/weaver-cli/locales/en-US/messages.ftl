@@ -34,6 +34,8 @@ weaver-after-help-observe-diagnostics = diagnostics
 weaver-after-help-observe-call-hierarchy = call-hierarchy
 weaver-after-help-observe-get-card = get-card
 weaver-after-help-observe-graph-slice = graph-slice
+weaver-after-help-observe-analyze = analyze
+weaver-after-help-observe-list-plugins = list-plugins
 weaver-after-help-act-heading = act — Perform code modifications
 weaver-after-help-act-rename-symbol = rename-symbol
 weaver-after-help-act-apply-edits = apply-edits
@@ -0,0 +1,137 @@
+//! On-disk cache for the `weaver --capabilities` report.
+//!
+//! Scripts that probe capabilities repeatedly would otherwise start every
+//! language server to rebuild the report on each invocation. The rendered
+//! report is stored in the runtime directory alongside the daemon's lock and
+//! PID files, keyed by a SHA-256 digest of the loaded configuration and the
+//! CLI version, and is reused for a short TTL.
+//! Any configuration change yields a different key, so a stale entry is never
+//! served for a different configuration. The cache is best-effort: read and
+//! write failures fall back to recomputing the report, and a configuration
+//! that cannot be serialised bypasses the cache entirely.
+
+use std::{
+    fmt::Write as _,
+    io,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use cap_std::{ambient_authority, fs::Dir};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::debug;
+use weaver_config::{Config, RuntimePaths};
+
+/// File name of the cached report inside the cache directory.
+pub(crate) const CAPABILITY_CACHE_FILE: &str = "capabilities.json";
+
+/// How long a cached report is served before it is recomputed.
+pub(crate) const CAPABILITY_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Serialised cache entry.
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    /// Key of the configuration the report was rendered for.
+    config_hash: String,
+    /// Seconds since the Unix epoch when the entry was written.
+    written_at: u64,
+    /// Rendered report, exactly as written to stdout.
+    report: String,
+}
+
+/// Location and lifetime of the cached capability report.
+#[derive(Debug, Clone)]
+pub(crate) struct CapabilityCache {
+    directory: PathBuf,
+    ttl: Duration,
+}
+
+impl CapabilityCache {
+    /// Creates a cache in `directory` whose entries expire after `ttl`.
+    pub(crate) fn new(directory: impl Into<PathBuf>, ttl: Duration) -> Self {
+        Self {
+            directory: directory.into(),
+            ttl,
+        }
+    }
+
+    /// Locates the cache in the runtime directory derived from `config`.
+    ///
+    /// Returns `None` when the runtime directory cannot be derived, in which
+    /// case the report is computed without caching.
+    pub(crate) fn for_config(config: &Config) -> Option<Self> {
+        RuntimePaths::from_config_readonly(config)
+            .ok()
+            .map(|paths| Self::new(paths.runtime_dir(), CAPABILITY_CACHE_TTL))
+    }
+
+    /// Returns the cached report for `config_hash` if it is still fresh.
+    pub(crate) fn load(&self, config_hash: &str) -> Option<String> {
+        let directory = Dir::open_ambient_dir(&self.directory, ambient_authority()).ok()?;
+        let contents = directory.read_to_string(CAPABILITY_CACHE_FILE).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&contents).ok()?;
+        if entry.config_hash != config_hash {
+            debug!(
+                directory = %self.directory.display(),
+                "capability cache keyed for another configuration"
+            );
+            return None;
+        }
+        let written_at = UNIX_EPOCH.checked_add(Duration::from_secs(entry.written_at))?;
+        // An entry from the future (clock skew) fails `duration_since` and is
+        // treated as stale.
+        let age = SystemTime::now().duration_since(written_at).ok()?;
+        (age < self.ttl).then_some(entry.report)
+    }
+
+    /// Stores `report` for `config_hash`, replacing any previous entry.
+    ///
+    /// Failures are logged and otherwise ignored.
+    pub(crate) fn store(&self, config_hash: &str, report: &str) {
+        let written_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        let entry = CacheEntry {
+            config_hash: config_hash.to_owned(),
+            written_at,
+            report: report.to_owned(),
+        };
+        let written = serde_json::to_vec(&entry)
+            .map_err(io::Error::from)
+            .and_then(|bytes| {
+                Dir::create_ambient_dir_all(&self.directory, ambient_authority())?;
+                Dir::open_ambient_dir(&self.directory, ambient_authority())?
+                    .write(CAPABILITY_CACHE_FILE, bytes)
+            });
+        if let Err(error) = written {
+            debug!(
+                directory = %self.directory.display(),
+                %error,
+                "failed to write capability cache"
+            );
+        }
+    }
+}
+
+/// Derives the cache key for `config`.
+///
+/// The key is a SHA-256 digest of the CLI version and the whole serialised
+/// configuration, so changing any setting or upgrading Weaver invalidates the
+/// cached report, and the same configuration maps to the same key in every
+/// build. Returns `None` when the configuration cannot be serialised, in
+/// which case the report must not be cached.
+pub(crate) fn config_hash(config: &Config) -> Option<String> {
+    let serialised = serde_json::to_vec(config)
+        .inspect_err(|error| debug!(%error, "configuration cannot be hashed for caching"))
+        .ok()?;
+    let mut hasher = Sha256::new();
+    hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+    hasher.update([0]);
+    hasher.update(&serialised);
+    let mut key = String::with_capacity(64);
+    for byte in hasher.finalize() {
+        write!(key, "{byte:02x}").ok()?;
+    }
+    Some(key)
+}
@@ -0,0 +1,153 @@
+//! Effective capability report emitted by `weaver --capabilities`.
+//!
+//! The report is a superset of the serialised [`CapabilityMatrix`]: every
+//! language keeps its configured `overrides` map and gains the status of its
+//! language server plus an `effective` map stating, for each known capability,
+//! whether configuration forces it on or off, or whether the server's
+//! negotiated [`CapabilitySummary`] makes it available. Each supported server
+//! is started and initialised through an [`LspHost`] to obtain that summary,
+//! then shut down when the probe finishes.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+use weaver_config::{CapabilityMatrix, CapabilityOverride, KNOWN_CAPABILITIES, KNOWN_LANGUAGES};
+use weaver_lsp_host::{
+    CapabilitySource,
+    CapabilityState,
+    CapabilitySummary,
+    Language,
+    LanguageServer,
+    LspHost,
+    adapter::ProcessLanguageServer,
+};
+
+/// Creates the language server probed for a language's capabilities.
+pub(crate) type ServerFactory = fn(Language) -> Box<dyn LanguageServer>;
+
+/// Spawns the configured language server process for `language`.
+pub(crate) fn process_server(language: Language) -> Box<dyn LanguageServer> {
+    Box::new(ProcessLanguageServer::new(language))
+}
+
+/// Whether a language server could be initialised for the probe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ServerStatus {
+    /// The server initialised and its capabilities were negotiated.
+    Ready,
+    /// No server is supported for the language, or it failed to initialise.
+    Unavailable,
+}
+
+/// Effective status of a capability after negotiation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum EffectiveCapability {
+    /// A `force` override advertises the capability regardless of the server.
+    ForcedOn,
+    /// A `deny` override withholds the capability regardless of the server.
+    ForcedOff,
+    /// The language server advertises the capability.
+    Available,
+    /// The language server is unavailable or does not advertise the capability.
+    Unavailable,
+}
+
+impl From<CapabilityState> for EffectiveCapability {
+    fn from(state: CapabilityState) -> Self {
+        match state.source {
+            CapabilitySource::ForcedOverride => Self::ForcedOn,
+            CapabilitySource::DeniedOverride => Self::ForcedOff,
+            CapabilitySource::ServerAdvertised => Self::Available,
+            CapabilitySource::MissingOnServer => Self::Unavailable,
+        }
+    }
+}
+
+impl From<Option<CapabilityOverride>> for EffectiveCapability {
+    /// Resolves a capability for a language without a ready server.
+    fn from(directive: Option<CapabilityOverride>) -> Self {
+        match directive {
+            Some(CapabilityOverride::Force) => Self::ForcedOn,
+            Some(CapabilityOverride::Deny) => Self::ForcedOff,
+            Some(CapabilityOverride::Allow) | None => Self::Unavailable,
+        }
+    }
+}
+
+/// Configured overrides and effective capabilities for one language.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct LanguageReport {
+    overrides: BTreeMap<String, CapabilityOverride>,
+    server: ServerStatus,
+    effective: BTreeMap<&'static str, EffectiveCapability>,
+}
+
+/// Capability report covering every known and configured language.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct CapabilityReport {
+    languages: BTreeMap<String, LanguageReport>,
+}
+
+impl CapabilityReport {
+    /// Builds the report by initialising a server for each supported language.
+    ///
+    /// Languages named only by overrides are reported alongside the known
+    /// languages so no configured override is dropped from the output.
+    pub(crate) fn probe(matrix: &CapabilityMatrix, servers: ServerFactory) -> Self {
+        let mut host = LspHost::new(matrix.clone());
+        let configured = matrix.languages.keys().map(String::as_str);
+        let names: BTreeSet<&str> = KNOWN_LANGUAGES.iter().copied().chain(configured).collect();
+        let languages = names
+            .into_iter()
+            .map(|language| {
+                let summary = negotiate(&mut host, servers, language);
+                let report = language_report(matrix, language, summary);
+                (String::from(language), report)
+            })
+            .collect();
+        Self { languages }
+    }
+}
+
+fn negotiate(host: &mut LspHost, servers: ServerFactory, name: &str) -> Option<CapabilitySummary> {
+    let language = name.parse::<Language>().ok()?;
+    host.register_language(language, servers(language)).ok()?;
+    host.initialize(language).ok()
+}
+
+fn language_report(
+    matrix: &CapabilityMatrix,
+    language: &str,
+    negotiated: Option<CapabilitySummary>,
+) -> LanguageReport {
+    let overrides = matrix
+        .languages
+        .get(language)
+        .map(|capabilities| capabilities.overrides.clone())
+        .unwrap_or_default();
+    let Some(summary) = negotiated else {
+        let effective = KNOWN_CAPABILITIES
+            .iter()
+            .map(|&capability| {
+                let status = EffectiveCapability::from(matrix.override_for(language, capability));
+                (capability, status)
+            })
+            .collect();
+        return LanguageReport {
+            overrides,
+            server: ServerStatus::Unavailable,
+            effective,
+        };
+    };
+    let effective = summary
+        .states()
+        .map(|state| (state.kind.key(), EffectiveCapability::from(state)))
+        .collect();
+    LanguageReport {
+        overrides,
+        server: ServerStatus::Ready,
+        effective,
+    }
+}
@@ -3,7 +3,10 @@
 //! This module defines the command-line interface structure used by
 //! both the runtime parser and the build script for manpage generation.
 
+use std::path::PathBuf;
+
 use clap::{Args, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
 
 /// Output format selection for domain command responses.
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
@@ -48,7 +51,7 @@ pub enum OutputFormat {
         "  observe \u{2014} Query code structure and relationships\n",
         "    get-definition    find-references    grep\n",
         "    diagnostics       call-hierarchy     get-card\n",
-        "    graph-slice\n",
+        "    graph-slice       analyze            list-plugins\n",
         "\n",
         "  act \u{2014} Perform code modifications\n",
         "    rename-symbol     apply-edits        apply-patch\n",
@@ -62,9 +65,21 @@ pub(crate) struct Cli {
     /// Prints the negotiated capability matrix and exits.
     #[arg(long)]
     pub(crate) capabilities: bool,
+    /// Recomputes `--capabilities` instead of reading or writing its cache.
+    #[arg(long)]
+    pub(crate) no_cache: bool,
     /// Controls how daemon output is rendered.
     #[arg(long, value_enum, default_value_t = OutputFormat::Auto)]
     pub(crate) output: OutputFormat,
+    /// Aborts a daemon request that has not finished within this many seconds.
+    #[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub(crate) timeout: Option<u64>,
+    /// Reads the `act apply-patch` patch from this file instead of stdin.
+    #[arg(long, value_name = "PATH", conflicts_with = "patch_stdin")]
+    pub(crate) patch_file: Option<PathBuf>,
+    /// Reads the `act apply-patch` patch from stdin (the default).
+    #[arg(long)]
+    pub(crate) patch_stdin: bool,
     /// Structured subcommands (for example `daemon start`).
     #[command(subcommand)]
     pub(crate) command: Option<CliCommand>,
@@ -99,6 +114,15 @@ pub(crate) enum CliCommand {
         #[command(subcommand)]
         action: DaemonAction,
     },
+    /// Prints the JSON Schema of the plugin protocol.
+    PluginSchema,
+    /// Prints a shell completion script to stdout.
+    #[command(hide = true)]
+    Completions {
+        /// The shell to generate completions for.
+        #[arg(value_enum)]
+        shell: Shell,
+    },
 }
 
 /// Resource-first definition commands.
@@ -67,7 +67,9 @@ impl CommandInvocation {
                         })?;
                 Ok(definition_get_invocation(record, args))
             }
-            CliCommand::Daemon { .. } => Err(AppError::MissingDomain),
+            CliCommand::Daemon { .. }
+            | CliCommand::PluginSchema
+            | CliCommand::Completions { .. } => Err(AppError::MissingDomain),
         }
     }
 }
@@ -178,6 +178,15 @@ mod ordering_invariant {
         "--log-format",
         "--capability-overrides",
         "--locale",
+        "--daemon-workers",
+        "--daemon-idle-timeout",
+        "--daemon-connect-attempts",
+        "--daemon-empty-line-limit",
+        "--plugin-inline-limit",
+        "--daemon-tls-ca",
+        "--daemon-tls-cert",
+        "--daemon-tls-key",
+        "--workspace-root",
     ];
 
     proptest! {
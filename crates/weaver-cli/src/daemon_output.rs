@@ -1,25 +1,25 @@
 //! Daemon response handling and output rendering.
 //!
 //! Owns parsing daemon messages and forwarding rendered output to the CLI
-//! streams.
+//! streams. Progress messages are rendered to stderr as status lines, and
+//! message kinds this client does not recognise are skipped so newer daemons
+//! can extend the protocol without breaking older clients.
 
 use std::io::{self, Read, Write};
 
 use serde::Deserialize;
+use weaver_config::SocketEndpoint;
 
-use crate::{
-    AppError,
-    EMPTY_LINE_LIMIT,
-    IoStreams,
-    OutputContext,
-    ResolvedOutputFormat,
-    render_human_output,
-};
+use crate::{AppError, IoStreams, OutputContext, ResolvedOutputFormat, render_human_output};
 
 /// Settings for rendering daemon output.
 pub(crate) struct OutputSettings<'a> {
     pub(crate) format: ResolvedOutputFormat,
     pub(crate) context: &'a OutputContext,
+    /// Consecutive empty lines tolerated before the stream is abandoned.
+    pub(crate) empty_line_limit: usize,
+    /// Daemon endpoint named in stream diagnostics.
+    pub(crate) endpoint: &'a SocketEndpoint,
 }
 
 /// Processes a single daemon message, writing output to the appropriate stream.
@@ -33,12 +33,28 @@ where
     W: Write,
     E: Write,
 {
-    let DaemonMessage::Stream { stream, data } = message else {
-        return Ok(());
-    };
-    let rendered = render_stream_payload(settings, &data);
-    forward_stream_payload(stream, rendered.as_deref().unwrap_or(&data), io)?;
-    Ok(())
+    match message {
+        DaemonMessage::Stream { stream, data } => {
+            let rendered = render_stream_payload(settings, &data);
+            forward_stream_payload(stream, rendered.as_deref().unwrap_or(&data), io)
+        }
+        DaemonMessage::Progress { message, fraction } => {
+            writeln!(io.stderr, "{}", progress_status_line(&message, fraction))
+                .map_err(AppError::ForwardResponse)
+        }
+        DaemonMessage::Exit { .. } | DaemonMessage::Unknown => Ok(()),
+    }
+}
+
+/// Formats a progress message as a single stderr status line.
+fn progress_status_line(message: &str, fraction: Option<f32>) -> String {
+    match fraction {
+        Some(fraction) => {
+            let percent = fraction.clamp(0.0, 1.0) * 100.0;
+            format!("progress: {message} ({percent:.0}%)")
+        }
+        None => format!("progress: {message}"),
+    }
 }
 
 fn render_stream_payload(settings: &OutputSettings<'_>, data: &str) -> Option<String> {
@@ -65,9 +81,28 @@ where
     .map_err(AppError::ForwardResponse)
 }
 
+/// Tracks blank lines the daemon sends in place of messages.
+#[derive(Debug, Default)]
+struct EmptyLines {
+    consecutive: usize,
+    discarded_bytes: usize,
+}
+
+impl EmptyLines {
+    /// Records a discarded blank line of `bytes` bytes.
+    fn record(&mut self, bytes: usize) {
+        self.consecutive += 1;
+        self.discarded_bytes += bytes;
+    }
+
+    /// Resets the consecutive count once a message arrives.
+    fn reset(&mut self) { self.consecutive = 0; }
+}
+
 /// Checks if the empty line limit has been reached and writes a warning if so.
 fn check_empty_line_limit<W, E, S>(
-    consecutive_empty_lines: usize,
+    empty_lines: &EmptyLines,
+    settings: &OutputSettings<'_>,
     io: &mut IoStreams<'_, S, W, E>,
 ) -> Result<bool, AppError>
 where
@@ -75,10 +110,14 @@ where
     W: Write,
     E: Write,
 {
-    if consecutive_empty_lines >= EMPTY_LINE_LIMIT {
+    if empty_lines.consecutive >= settings.empty_line_limit {
         writeln!(
             io.stderr,
-            "Warning: received {EMPTY_LINE_LIMIT} consecutive empty lines from daemon; aborting."
+            concat!(
+                "Warning: received {} consecutive empty lines from daemon at {}; ",
+                "aborting after discarding {} bytes."
+            ),
+            empty_lines.consecutive, settings.endpoint, empty_lines.discarded_bytes
         )
         .map_err(AppError::ForwardResponse)?;
         return Ok(true);
@@ -86,6 +125,12 @@ where
     Ok(false)
 }
 
+/// Reads newline-delimited daemon messages until the stream closes.
+///
+/// Bytes are buffered until a newline arrives, so a message the daemon
+/// flushes in several writes is parsed only once it is complete. A final line
+/// without a trailing newline means the daemon stopped mid-message and is
+/// reported as [`AppError::IncompleteMessage`].
 pub(crate) fn read_daemon_messages<R, W, E, S>(
     connection: &mut R,
     io: &mut IoStreams<'_, S, W, E>,
@@ -100,25 +145,29 @@ where
     use std::io::BufRead;
 
     let mut reader = io::BufReader::new(connection);
-    let mut line = String::new();
+    let mut line = Vec::new();
     let mut exit_status: Option<i32> = None;
-    let mut consecutive_empty_lines = 0;
+    let mut empty_lines = EmptyLines::default();
 
     while reader
-        .read_line(&mut line)
+        .read_until(b'\n', &mut line)
         .map_err(AppError::ReadResponse)?
         != 0
     {
-        if line.trim().is_empty() {
-            consecutive_empty_lines += 1;
-            if check_empty_line_limit(consecutive_empty_lines, io)? {
+        if line.last() != Some(&b'\n') {
+            return Err(AppError::IncompleteMessage { bytes: line.len() });
+        }
+        if line.trim_ascii().is_empty() {
+            empty_lines.record(line.len());
+            if check_empty_line_limit(&empty_lines, &settings, io)? {
                 break;
             }
             line.clear();
             continue;
         }
-        consecutive_empty_lines = 0;
-        let message: DaemonMessage = serde_json::from_str(&line).map_err(AppError::ParseMessage)?;
+        empty_lines.reset();
+        let message: DaemonMessage =
+            serde_json::from_slice(&line).map_err(AppError::ParseMessage)?;
         if let DaemonMessage::Exit { status } = &message {
             exit_status = Some(*status);
         }
@@ -129,14 +178,34 @@ where
     io.stdout.flush().map_err(AppError::ForwardResponse)?;
     io.stderr.flush().map_err(AppError::ForwardResponse)?;
 
+    if empty_lines.discarded_bytes > 0 {
+        tracing::debug!(
+            endpoint = %settings.endpoint,
+            discarded_bytes = empty_lines.discarded_bytes,
+            "discarded empty lines from daemon response"
+        );
+    }
     exit_status.ok_or(AppError::MissingExit)
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(tag = "kind", rename_all = "snake_case")]
 enum DaemonMessage {
-    Stream { stream: StreamTarget, data: String },
-    Exit { status: i32 },
+    Stream {
+        stream: StreamTarget,
+        data: String,
+    },
+    Progress {
+        message: String,
+        #[serde(default)]
+        fraction: Option<f32>,
+    },
+    Exit {
+        status: i32,
+    },
+    /// Any message kind introduced by a newer daemon.
+    #[serde(other)]
+    Unknown,
 }
 
 #[derive(Debug, Deserialize)]
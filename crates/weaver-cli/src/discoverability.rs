@@ -69,6 +69,8 @@ pub const DOMAIN_OPERATIONS: &[(&str, &str, &[&str])] = &[
             "call-hierarchy",
             "get-card",
             "graph-slice",
+            "analyze",
+            "list-plugins",
         ],
     ),
     (
@@ -1,6 +1,6 @@
 //! Error types and diagnostics helpers for the CLI runtime.
 
-use std::{io, sync::Arc};
+use std::{io, path::PathBuf, sync::Arc};
 
 use thiserror::Error;
 
@@ -33,6 +33,16 @@ pub(crate) enum AppError {
     Resolve { endpoint: String, source: io::Error },
     #[error("failed to connect to daemon at {endpoint}: {source}")]
     Connect { endpoint: String, source: io::Error },
+    #[error("TLS daemon endpoint {endpoint} requires --daemon-tls-ca")]
+    MissingTlsCa { endpoint: String },
+    #[error("--daemon-tls-cert and --daemon-tls-key must be given together")]
+    IncompleteTlsIdentity,
+    #[error("failed to load TLS material from {}: {source}", path.display())]
+    LoadTlsMaterial { path: PathBuf, source: io::Error },
+    #[error("invalid TLS configuration: {0}")]
+    TlsConfig(rustls::Error),
+    #[error("TLS handshake with daemon at {endpoint} failed: {source}")]
+    TlsHandshake { endpoint: String, source: io::Error },
     #[cfg(not(unix))]
     #[error("platform does not support Unix sockets: {0}")]
     UnsupportedUnixTransport(String),
@@ -48,16 +58,32 @@ pub(crate) enum AppError {
     ForwardResponse(io::Error),
     #[error("failed to read patch input: {0}")]
     ReadPatch(io::Error),
-    #[error("apply-patch requires patch content on stdin")]
+    #[error("apply-patch requires patch content on stdin or from --patch-file")]
     MissingPatchInput,
+    #[error("failed to open patch file {}: {source}", path.display())]
+    OpenPatchFile { path: PathBuf, source: io::Error },
+    #[error(
+        "--patch-file and --patch-stdin only apply to act apply-patch, not {domain} {operation}"
+    )]
+    PatchSourceNotApplicable { domain: String, operation: String },
     #[error("command request is {size} bytes, exceeding the {limit} byte JSONL request limit")]
     RequestTooLarge { size: usize, limit: usize },
     #[error("daemon closed the stream without sending an exit status")]
     MissingExit,
+    #[error("daemon closed the stream partway through a message ({bytes} bytes without a newline)")]
+    IncompleteMessage { bytes: usize },
+    #[error("daemon did not respond within {seconds}s; raise --timeout to wait longer")]
+    ResponseTimeout { seconds: u64 },
     #[error("failed to serialise capability matrix: {0}")]
     SerialiseCapabilities(serde_json::Error),
     #[error("failed to emit capabilities: {0}")]
     EmitCapabilities(io::Error),
+    #[error("failed to emit shell completions: {0}")]
+    EmitCompletions(io::Error),
+    #[error("failed to serialise plugin protocol schema: {0}")]
+    SerialisePluginSchema(serde_json::Error),
+    #[error("failed to emit plugin protocol schema: {0}")]
+    EmitPluginSchema(io::Error),
     #[error("failed to emit preflight guidance: {0}")]
     EmitGuidance(io::Error),
     #[error("daemon lifecycle command failed: {0}")]
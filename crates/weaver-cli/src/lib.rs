@@ -9,12 +9,16 @@ use std::{
     ffi::{OsStr, OsString},
     io::{Read, Write},
     process::ExitCode,
+    time::Duration,
 };
 
+use capability_report::{ServerFactory, process_server};
 use clap::Parser;
 use ortho_config::Localizer;
 
 mod actionable_guidance;
+mod capability_cache;
+mod capability_report;
 mod cli;
 mod command;
 mod command_surface;
@@ -27,6 +31,7 @@ mod lifecycle;
 mod localizer;
 pub mod output;
 mod preflight;
+mod request;
 mod runner_glue;
 mod runtime_utils;
 mod transport;
@@ -42,6 +47,15 @@ pub const SHARED_CONFIG_HELP_FLAGS: &[&str] = &[
     "--log-format <FORMAT>",
     "--capability-overrides <DIRECTIVE>",
     "--locale <LOCALE>",
+    "--daemon-workers <COUNT>",
+    "--daemon-idle-timeout <SECONDS>",
+    "--daemon-connect-attempts <COUNT>",
+    "--daemon-empty-line-limit <COUNT>",
+    "--plugin-inline-limit <BYTES>",
+    "--daemon-tls-ca <PATH>",
+    "--daemon-tls-cert <PATH>",
+    "--daemon-tls-key <PATH>",
+    "--workspace-root <PATH>",
 ];
 
 pub use cli::OutputFormat;
@@ -67,10 +81,18 @@ use lifecycle::{
 use localizer::build_localizer;
 pub use output::{OutputContext, ResolvedOutputFormat, render_human_output};
 pub(crate) use preflight::handle_preflight;
+use request::PatchSource;
 #[cfg(test)]
-pub(crate) use runner_glue::build_request;
-pub(crate) use runner_glue::execute_daemon_command;
-pub(crate) use runtime_utils::{exit_code_from_status, handle_capabilities_mode};
+pub(crate) use request::build_request;
+pub(crate) use runner_glue::{RequestOptions, execute_daemon_command};
+#[cfg(test)]
+pub(crate) use runtime_utils::emit_capabilities;
+pub(crate) use runtime_utils::{
+    exit_code_from_status,
+    handle_capabilities_mode,
+    handle_completions_mode,
+    handle_plugin_schema_mode,
+};
 #[cfg(test)]
 pub(crate) use transport::connect;
 
@@ -86,8 +108,17 @@ const CONFIG_CLI_FLAGS: &[&str] = &[
     "--log-format",
     "--capability-overrides",
     "--locale",
+    "--daemon-workers",
+    "--daemon-idle-timeout",
+    "--daemon-connect-attempts",
+    "--daemon-empty-line-limit",
+    "--plugin-inline-limit",
+    "--daemon-tls-ca",
+    "--daemon-tls-cert",
+    "--daemon-tls-key",
+    "--workspace-root",
 ];
-pub(crate) const EMPTY_LINE_LIMIT: usize = 10;
+
 /// Bundles the IO streams provided to the CLI runtime.
 ///
 /// `IoStreams` owns the long-lived writers used while parsing CLI arguments.
@@ -129,6 +160,7 @@ struct CliRunner<'a, R: Read, W: Write, E: Write, L: ConfigLoader> {
     io: &'a mut IoStreams<'a, R, W, E>,
     loader: &'a L,
     daemon_binary: Option<&'a OsStr>,
+    servers: ServerFactory,
 }
 
 impl<'a, R, W, E, L> CliRunner<'a, R, W, E, L>
@@ -143,6 +175,7 @@ where
             io,
             loader,
             daemon_binary: None,
+            servers: process_server,
         }
     }
 
@@ -152,6 +185,12 @@ where
         self
     }
 
+    #[cfg(test)]
+    fn with_servers(mut self, servers: ServerFactory) -> Self {
+        self.servers = servers;
+        self
+    }
+
     /// Parses CLI arguments, rendering help to stdout and returning `Ok(None)`
     /// if a `DisplayHelp` error is produced. Returns `Ok(Some(cli))` on
     /// success or `Err` on a genuine parse error or help-write failure.
@@ -213,6 +252,12 @@ where
             Ok(None) => return ExitCode::SUCCESS,
             Err(e) => return self.map_result_to_exit_code(Err(e)),
         };
+        if let Some(exit_code) = parsed_cli.as_ref().ok().and_then(|cli| {
+            handle_completions_mode(cli, self.io)
+                .or_else(|| handle_plugin_schema_mode(cli, self.io))
+        }) {
+            return exit_code;
+        }
 
         let result = parsed_cli
             .and_then(|cli| {
@@ -222,7 +267,9 @@ where
                     .map(|config| (cli, config))
             })
             .and_then(|(cli, config)| {
-                if let Some(exit_code) = handle_capabilities_mode(&cli, &config, self.io) {
+                if let Some(exit_code) =
+                    handle_capabilities_mode(&cli, &config, self.servers, self.io)
+                {
                     return Ok(exit_code);
                 }
 
@@ -241,7 +288,10 @@ where
                     return handler(invocation, context, &mut output).map_err(AppError::from);
                 }
 
-                let output_format = cli.output.resolve(self.io.stdout_is_terminal());
+                let mut options =
+                    RequestOptions::new(cli.output.resolve(self.io.stdout_is_terminal()));
+                options.timeout = cli.timeout.map(Duration::from_secs);
+                options.patch_source = PatchSource::from_cli(&cli);
                 let invocation = CommandInvocation::try_from(cli)?;
                 let context = LifecycleContext {
                     config: &config,
@@ -249,10 +299,7 @@ where
                     daemon_binary: self.daemon_binary,
                 };
                 Ok(execute_daemon_command(
-                    invocation,
-                    context,
-                    self.io,
-                    output_format,
+                    invocation, context, self.io, options,
                 ))
             });
 
@@ -319,6 +366,7 @@ pub(crate) fn run_with_daemon_binary<'a, I, R, W, E, L, F>(
     io: &'a mut IoStreams<'a, R, W, E>,
     loader: &'a L,
     daemon_binary: Option<&'a OsStr>,
+    servers: ServerFactory,
     localizer: &dyn Localizer,
     handler: F,
 ) -> ExitCode
@@ -336,6 +384,7 @@ where
 {
     CliRunner::new(io, loader)
         .with_daemon_binary(daemon_binary)
+        .with_servers(servers)
         .run_with_handler(args, localizer, handler)
 }
 
@@ -40,7 +40,7 @@ pub(super) fn socket_is_reachable(endpoint: &SocketEndpoint) -> Result<bool, Lif
 
 fn try_connect(endpoint: &SocketEndpoint) -> io::Result<()> {
     match endpoint {
-        SocketEndpoint::Tcp { host, port } => {
+        SocketEndpoint::Tcp { host, port, .. } => {
             let address = resolve_tcp(host, *port)?;
             TcpStream::connect_timeout(&address, SOCKET_PROBE_TIMEOUT).map(|_| ())
         }
@@ -5,8 +5,10 @@
 //! remain unchanged when JSON output is requested.
 
 mod models;
+mod plugins;
 mod render;
 mod source;
+mod verification;
 
 use weaver_daemon_types::UnknownOperationDetails;
 
@@ -20,13 +22,14 @@ use crate::output::{
         DiagnosticItem,
         DiagnosticsResponse,
         ReferenceResponse,
-        VerificationFailure,
         parse_capability_resolution,
         parse_definitions,
         parse_unknown_operation,
         parse_verification_failures,
     },
-    source::{SourceLocation, SourcePosition, extract_uri_argument, from_path_or_uri, from_uri},
+    plugins::{PluginListing, render_plugin_listing},
+    source::{SourceLocation, SourcePosition, extract_uri_argument, from_uri},
+    verification::render_verification_failures,
 };
 
 /// Output format after resolving `auto` based on TTY detection.
@@ -106,6 +109,9 @@ pub fn render_human_output(context: &OutputContext, data: &str) -> Option<String
         ("observe", "find-references") => serde_json::from_str::<ReferenceResponse>(trimmed)
             .ok()
             .map(render_references),
+        ("observe", "list-plugins") => serde_json::from_str::<PluginListing>(trimmed)
+            .ok()
+            .map(render_plugin_listing),
         ("verify", "diagnostics") => serde_json::from_str::<DiagnosticsResponse>(trimmed)
             .ok()
             .map(|response| render_diagnostics(response, context)),
@@ -203,17 +209,6 @@ fn render_diagnostics(response: DiagnosticsResponse, context: &OutputContext) ->
     render::render_locations(&locations)
 }
 
-fn render_verification_failures(failures: Vec<VerificationFailure>) -> String {
-    if failures.is_empty() {
-        return String::from("no verification failures reported\n");
-    }
-    let locations: Vec<SourceLocation> = failures
-        .into_iter()
-        .map(verification_failure_to_location)
-        .collect();
-    render::render_locations(&locations)
-}
-
 fn render_capability_resolution(resolution: CapabilityResolution) -> String {
     let details = resolution.details;
     let language = details.language.as_deref().unwrap_or("unknown");
@@ -286,24 +281,6 @@ fn diagnostic_to_location(
     }
 }
 
-fn verification_failure_to_location(failure: VerificationFailure) -> SourceLocation {
-    let label = if let Some(phase) = failure.phase.as_deref() {
-        format!("{phase}: {}", failure.message)
-    } else {
-        failure.message
-    };
-
-    match failure.location {
-        Some(location) => from_path_or_uri(&location, failure.line, failure.column, label),
-        None => SourceLocation::unresolved(
-            String::from("<unknown source>"),
-            SourcePosition::new(failure.line, failure.column),
-            label,
-            String::from("missing file path for verification failure"),
-        ),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     //! Unit tests for output format resolution and stream handling.
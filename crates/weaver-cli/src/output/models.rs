@@ -65,6 +65,11 @@ pub(crate) struct VerificationFailure {
     pub(crate) column: Option<u32>,
     /// Human-readable failure message.
     pub(crate) message: String,
+    /// Whether the edit introduced the failure, when the daemon compared it
+    /// with the pre-edit diagnostics.
+    pub(crate) is_new: Option<bool>,
+    /// Failing diagnostics the file had before the edit.
+    pub(crate) preexisting_count: Option<usize>,
 }
 
 /// Parsed capability-resolution payload emitted by daemon routing.
@@ -155,6 +160,8 @@ pub(crate) fn parse_verification_failures(payload: &str) -> Option<Vec<Verificat
             line: diagnostic.line,
             column: diagnostic.column,
             message,
+            is_new: diagnostic.is_new,
+            preexisting_count: diagnostic.preexisting_count,
         });
     }
 
@@ -205,6 +212,8 @@ struct VerificationDiagnostic {
     line: Option<u32>,
     column: Option<u32>,
     message: Option<String>,
+    is_new: Option<bool>,
+    preexisting_count: Option<usize>,
 }
 
 impl VerificationDiagnostic {
@@ -263,6 +272,7 @@ mod tests {
         assert_eq!(failures.len(), 1);
         assert_eq!(failures[0].phase.as_deref(), Some("SemanticLock"));
         assert_eq!(failures[0].line, Some(42));
+        assert_eq!(failures[0].is_new, None);
     }
 
     #[test]
@@ -0,0 +1,93 @@
+//! Human-readable rendering of `observe list-plugins` responses.
+
+use serde::Deserialize;
+
+/// Response payload for `observe list-plugins`.
+#[derive(Debug, Deserialize)]
+pub(super) struct PluginListing {
+    /// Registered plugins in name order.
+    plugins: Vec<PluginSummary>,
+}
+
+/// Manifest fields the daemon reports for one plugin.
+#[derive(Debug, Deserialize)]
+struct PluginSummary {
+    name: String,
+    version: String,
+    kind: String,
+    #[serde(default)]
+    languages: Vec<String>,
+    #[serde(default)]
+    capabilities: Vec<String>,
+    timeout_secs: u64,
+}
+
+/// Renders one block per plugin: a heading with its version, kind, and
+/// timeout, followed by its languages and capabilities.
+pub(super) fn render_plugin_listing(listing: PluginListing) -> String {
+    if listing.plugins.is_empty() {
+        return String::from("no plugins registered\n");
+    }
+    listing
+        .plugins
+        .iter()
+        .map(|plugin| {
+            format!(
+                "{} {} ({}, timeout {}s)\n  languages: {}\n  capabilities: {}\n",
+                plugin.name,
+                plugin.version,
+                plugin.kind,
+                plugin.timeout_secs,
+                join_or_none(&plugin.languages),
+                join_or_none(&plugin.capabilities),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn join_or_none(values: &[String]) -> String {
+    if values.is_empty() {
+        String::from("none")
+    } else {
+        values.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for plugin listing rendering.
+
+    use super::*;
+
+    #[test]
+    fn renders_each_plugin_with_capabilities() {
+        let payload = r#"{"plugins":[
+            {"name":"jedi","version":"0.2.0","kind":"sensor","languages":["python"],"capabilities":[],"timeout_secs":10},
+            {"name":"rope","version":"0.1.0","kind":"actuator","languages":["python"],"capabilities":["rename-symbol"],"timeout_secs":30}
+        ]}"#;
+        let listing: PluginListing = serde_json::from_str(payload).expect("listing");
+
+        let rendered = render_plugin_listing(listing);
+
+        assert_eq!(
+            rendered,
+            concat!(
+                "jedi 0.2.0 (sensor, timeout 10s)\n",
+                "  languages: python\n",
+                "  capabilities: none\n",
+                "\n",
+                "rope 0.1.0 (actuator, timeout 30s)\n",
+                "  languages: python\n",
+                "  capabilities: rename-symbol\n",
+            )
+        );
+    }
+
+    #[test]
+    fn reports_empty_registry() {
+        let listing: PluginListing = serde_json::from_str(r#"{"plugins":[]}"#).expect("listing");
+
+        assert_eq!(render_plugin_listing(listing), "no plugins registered\n");
+    }
+}
@@ -0,0 +1,122 @@
+//! Human-readable rendering of safety harness verification failures.
+
+use std::collections::HashMap;
+
+use super::{
+    models::VerificationFailure,
+    render,
+    source::{SourceLocation, SourcePosition, from_path_or_uri},
+};
+
+/// Renders verification failures with source context.
+///
+/// When the daemon compared the failures with the pre-edit diagnostics, a
+/// summary line such as `2 new diagnostics introduced (1 pre-existing)` comes
+/// first, so users can tell what the edit broke from what was already broken.
+pub(super) fn render_verification_failures(failures: Vec<VerificationFailure>) -> String {
+    if failures.is_empty() {
+        return String::from("no verification failures reported\n");
+    }
+    let mut rendered = baseline_summary(&failures).unwrap_or_default();
+    let locations: Vec<SourceLocation> = failures
+        .into_iter()
+        .map(verification_failure_to_location)
+        .collect();
+    rendered.push_str(&render::render_locations(&locations));
+    rendered
+}
+
+/// Summarises how many failures are new, counting each file's pre-existing
+/// diagnostics once.
+fn baseline_summary(failures: &[VerificationFailure]) -> Option<String> {
+    let new_count = failures
+        .iter()
+        .filter(|failure| failure.is_new == Some(true))
+        .count();
+    if new_count == 0 {
+        return None;
+    }
+    let preexisting_by_file: HashMap<Option<&str>, usize> = failures
+        .iter()
+        .filter_map(|failure| {
+            failure
+                .preexisting_count
+                .map(|count| (failure.location.as_deref(), count))
+        })
+        .collect();
+    let preexisting: usize = preexisting_by_file.values().sum();
+    let noun = if new_count == 1 {
+        "diagnostic"
+    } else {
+        "diagnostics"
+    };
+    Some(format!(
+        "{new_count} new {noun} introduced ({preexisting} pre-existing)\n"
+    ))
+}
+
+fn verification_failure_to_location(failure: VerificationFailure) -> SourceLocation {
+    let label = if let Some(phase) = failure.phase.as_deref() {
+        format!("{phase}: {}", failure.message)
+    } else {
+        failure.message
+    };
+
+    match failure.location {
+        Some(location) => from_path_or_uri(&location, failure.line, failure.column, label),
+        None => SourceLocation::unresolved(
+            String::from("<unknown source>"),
+            SourcePosition::new(failure.line, failure.column),
+            label,
+            String::from("missing file path for verification failure"),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for verification failure rendering.
+
+    use super::*;
+    use crate::output::models::parse_verification_failures;
+
+    #[test]
+    fn summarises_new_and_preexisting_diagnostics() {
+        let payload = r#"{
+  "status": "error",
+  "type": "VerificationError",
+  "details": {
+    "phase": "SemanticLock",
+    "failures": [
+      {"file": "src/main.py", "line": 3, "message": "undefined name", "is_new": true, "preexisting_count": 1},
+      {"file": "src/main.py", "line": 9, "message": "bad call", "is_new": true, "preexisting_count": 1}
+    ]
+  }
+}"#;
+        let failures = parse_verification_failures(payload).expect("verification");
+
+        let rendered = render_verification_failures(failures);
+
+        assert!(
+            rendered.starts_with("2 new diagnostics introduced (1 pre-existing)\n"),
+            "rendered: {rendered}"
+        );
+        assert!(rendered.contains("src/main.py"), "rendered: {rendered}");
+    }
+
+    #[test]
+    fn omits_summary_without_baseline_comparison() {
+        let payload = r#"{
+  "type": "VerificationError",
+  "details": {
+    "phase": "SyntacticLock",
+    "failures": [{"file": "src/main.py", "line": 1, "message": "syntax error"}]
+  }
+}"#;
+        let failures = parse_verification_failures(payload).expect("verification");
+
+        let rendered = render_verification_failures(failures);
+
+        assert!(!rendered.contains("introduced"), "rendered: {rendered}");
+    }
+}
@@ -180,6 +180,10 @@ mod tests {
         Cli {
             capabilities: false,
             output: OutputFormat::Auto,
+            timeout: None,
+            patch_file: None,
+            patch_stdin: false,
+            no_cache: false,
             command: None,
             domain: domain.map(str::to_string),
             operation: operation.map(str::to_string),
@@ -0,0 +1,344 @@
+//! Daemon request construction.
+//!
+//! Turns a parsed [`CommandInvocation`] into the [`CommandRequest`] sent to the
+//! daemon. `act apply-patch` requests carry patch content read from stdin or,
+//! with `--patch-file`, from a file; both paths share the same UTF-8 and size
+//! validation.
+
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use cap_std::fs::Dir;
+use weaver_daemon_types::JSONL_REQUEST_MAX_LINE_BYTES;
+
+use crate::{AppError, Cli, CommandInvocation, CommandRequest};
+
+/// Maximum patch size accepted from stdin or a patch file.
+///
+/// Mirrors the JSON Lines request line-size budget so apply-patch requests do
+/// not exceed the daemon transport limit. Patches or serialised requests that
+/// exceed this limit return [`AppError::RequestTooLarge`].
+const MAX_PATCH_BYTES: u64 = JSONL_REQUEST_MAX_LINE_BYTES as u64;
+
+/// Where `act apply-patch` reads its patch content from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum PatchSource {
+    /// Read the patch from standard input.
+    Stdin,
+    /// Read the patch from the file at this path.
+    File(PathBuf),
+}
+
+impl PatchSource {
+    /// Returns the source chosen with `--patch-file` or `--patch-stdin`.
+    ///
+    /// Returns `None` when neither flag was given, in which case apply-patch
+    /// falls back to reading stdin.
+    pub(crate) fn from_cli(cli: &Cli) -> Option<Self> {
+        match (&cli.patch_file, cli.patch_stdin) {
+            (Some(path), _) => Some(Self::File(path.clone())),
+            (None, true) => Some(Self::Stdin),
+            (None, false) => None,
+        }
+    }
+}
+
+/// Builds a [`CommandRequest`], reading patch content from `patch_source` if
+/// set.
+///
+/// Returns [`AppError::PatchSourceNotApplicable`] when a patch source was
+/// requested for anything other than `act apply-patch`, and
+/// [`AppError::OpenPatchFile`] when the patch file cannot be opened. Otherwise
+/// behaves like [`build_request`].
+pub(crate) fn build_request_from_source<R: Read>(
+    invocation: CommandInvocation,
+    patch_source: Option<&PatchSource>,
+    stdin: &mut R,
+) -> Result<CommandRequest, AppError> {
+    match patch_source {
+        Some(_) if !invocation.is_apply_patch() => Err(AppError::PatchSourceNotApplicable {
+            domain: invocation.domain,
+            operation: invocation.operation,
+        }),
+        Some(PatchSource::File(path)) => {
+            let mut file = open_ambient_file(path).map_err(|source| AppError::OpenPatchFile {
+                path: path.clone(),
+                source,
+            })?;
+            build_request(invocation, &mut file)
+        }
+        Some(PatchSource::Stdin) | None => build_request(invocation, stdin),
+    }
+}
+
+/// Opens `path` through a capability-scoped handle on its parent directory.
+pub(crate) fn open_ambient_file(path: &Path) -> std::io::Result<cap_std::fs::File> {
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let file_name = path.file_name().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, "missing file name")
+    })?;
+    let directory = Dir::open_ambient_dir(parent, cap_std::ambient_authority())?;
+    directory.open(file_name)
+}
+
+/// Builds a [`CommandRequest`] from `invocation`.
+///
+/// For `apply-patch` operations, reads patch content from `input` and returns
+/// [`AppError::MissingPatchInput`] if the content is empty after trimming.
+/// Content that is not valid UTF-8 returns [`AppError::ReadPatch`]. For all
+/// other operations, constructs the request directly from the invocation
+/// without reading `input`.
+pub(crate) fn build_request<R: Read>(
+    invocation: CommandInvocation,
+    input: &mut R,
+) -> Result<CommandRequest, AppError> {
+    if invocation.is_apply_patch() {
+        let mut patch = String::new();
+        input
+            .take(MAX_PATCH_BYTES + 1)
+            .read_to_string(&mut patch)
+            .map_err(AppError::ReadPatch)?;
+        if patch.len() as u64 > MAX_PATCH_BYTES {
+            return Err(AppError::RequestTooLarge {
+                size: patch.len(),
+                limit: JSONL_REQUEST_MAX_LINE_BYTES,
+            });
+        }
+        if patch.trim().is_empty() {
+            return Err(AppError::MissingPatchInput);
+        }
+        let request = CommandRequest::with_patch(invocation, patch);
+        enforce_request_line_limit(&request)?;
+        Ok(request)
+    } else {
+        let request = CommandRequest::from(invocation);
+        enforce_request_line_limit(&request)?;
+        Ok(request)
+    }
+}
+
+fn enforce_request_line_limit(request: &CommandRequest) -> Result<(), AppError> {
+    let json_len = serde_json::to_vec(request)
+        .map_err(AppError::SerialiseRequest)?
+        .len();
+    let request_line_len = json_len + 1;
+    if request_line_len > JSONL_REQUEST_MAX_LINE_BYTES {
+        return Err(AppError::RequestTooLarge {
+            size: request_line_len,
+            limit: JSONL_REQUEST_MAX_LINE_BYTES,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for daemon request construction helpers.
+
+    use std::io::Cursor;
+
+    use proptest::prelude::*;
+
+    use super::{MAX_PATCH_BYTES, PatchSource, build_request, build_request_from_source};
+    use crate::{AppError, CommandInvocation, CommandRequest};
+
+    enum ExpectedPatchRequest {
+        Ok,
+        MissingPatchInput,
+        Oversized,
+    }
+
+    fn observe_status_invocation() -> CommandInvocation {
+        CommandInvocation {
+            domain: "observe".to_owned(),
+            operation: "status".to_owned(),
+            arguments: Vec::new(),
+        }
+    }
+
+    fn observe_status_invocation_with_argument(argument: String) -> CommandInvocation {
+        CommandInvocation {
+            arguments: vec![argument],
+            ..observe_status_invocation()
+        }
+    }
+
+    fn apply_patch_invocation() -> CommandInvocation {
+        CommandInvocation {
+            domain: "act".to_owned(),
+            operation: "apply-patch".to_owned(),
+            arguments: Vec::new(),
+        }
+    }
+
+    /// Returns the number of payload bytes needed to reach a serialised JSON Lines
+    /// line length of exactly `len` bytes, using `template` to measure the fixed
+    /// envelope overhead.
+    ///
+    /// Panics if `len` does not exceed the envelope length, which would make the
+    /// requested line length impossible to achieve.
+    fn payload_size_for_jsonl_len(len: usize, template: &CommandRequest) -> usize {
+        let envelope_len = request_jsonl_len(template);
+        assert!(
+            len > envelope_len,
+            "requested JSONL line length {len} must exceed envelope length {envelope_len}"
+        );
+        len - envelope_len
+    }
+
+    fn patch_with_jsonl_len(len: usize) -> Vec<u8> {
+        let n = payload_size_for_jsonl_len(
+            len,
+            &CommandRequest::with_patch(apply_patch_invocation(), String::new()),
+        );
+        vec![b'a'; n]
+    }
+
+    fn argument_with_jsonl_len(len: usize) -> String {
+        let n = payload_size_for_jsonl_len(
+            len,
+            &CommandRequest::from(observe_status_invocation_with_argument(String::new())),
+        );
+        "a".repeat(n)
+    }
+
+    fn request_jsonl_len(request: &CommandRequest) -> usize {
+        match serde_json::to_vec(request) {
+            Ok(bytes) => bytes.len() + 1,
+            Err(error) => panic!("request must serialise: {error}"),
+        }
+    }
+
+    #[test]
+    fn non_patch_invocation_does_not_read_stdin() {
+        let mut stdin = Cursor::new(b"should not be read".to_vec());
+        let result = build_request(observe_status_invocation(), &mut stdin);
+        assert!(result.is_ok());
+        assert_eq!(
+            stdin.position(),
+            0,
+            "non-patch requests must not read stdin"
+        );
+    }
+
+    #[test]
+    fn non_patch_invocation_rejects_oversized_jsonl_request() {
+        let invocation = observe_status_invocation_with_argument(argument_with_jsonl_len(
+            MAX_PATCH_BYTES as usize + 1,
+        ));
+        let mut stdin = Cursor::new(b"should not be read".to_vec());
+        let result = build_request(invocation, &mut stdin);
+
+        assert!(matches!(result, Err(AppError::RequestTooLarge { .. })));
+    }
+
+    #[rstest::rstest]
+    #[case::reads_patch_from_stdin(
+        b"--- a/foo\n+++ b/foo\n@@ -1 +1 @@\n-old\n+new\n".to_vec(),
+        ExpectedPatchRequest::Ok
+    )]
+    #[case::returns_error_for_empty_stdin(
+        b"   \n".to_vec(),
+        ExpectedPatchRequest::MissingPatchInput
+    )]
+    #[case::accepts_request_at_jsonl_size_limit(
+        patch_with_jsonl_len(MAX_PATCH_BYTES as usize),
+        ExpectedPatchRequest::Ok
+    )]
+    #[case::returns_error_for_oversized_jsonl_request(
+        patch_with_jsonl_len(MAX_PATCH_BYTES as usize + 1),
+        ExpectedPatchRequest::Oversized
+    )]
+    fn apply_patch_stdin_cases(#[case] input: Vec<u8>, #[case] expected: ExpectedPatchRequest) {
+        let expected_patch = String::from_utf8(input.clone()).expect("patch input is UTF-8");
+        let mut stdin = Cursor::new(input);
+        let result = build_request(apply_patch_invocation(), &mut stdin);
+
+        match expected {
+            ExpectedPatchRequest::Ok => {
+                let request = result.expect("patch input should build a request");
+                assert_eq!(request.patch.as_deref(), Some(expected_patch.as_str()));
+                assert!(request_jsonl_len(&request) <= MAX_PATCH_BYTES as usize);
+            }
+            ExpectedPatchRequest::MissingPatchInput => {
+                assert!(matches!(result, Err(AppError::MissingPatchInput)));
+            }
+            ExpectedPatchRequest::Oversized => {
+                assert!(matches!(result, Err(AppError::RequestTooLarge { .. })));
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_utf8_patch_input() {
+        let mut stdin = Cursor::new(vec![b'+', 0xff, 0xfe, b'\n']);
+        let result = build_request(apply_patch_invocation(), &mut stdin);
+
+        assert!(matches!(result, Err(AppError::ReadPatch(_))));
+    }
+
+    #[rstest::rstest]
+    #[case::stdin(PatchSource::Stdin)]
+    #[case::file(PatchSource::File("changes.patch".into()))]
+    fn patch_sources_are_rejected_for_other_operations(#[case] source: PatchSource) {
+        let mut stdin = Cursor::new(b"should not be read".to_vec());
+        let result =
+            build_request_from_source(observe_status_invocation(), Some(&source), &mut stdin);
+
+        assert!(matches!(
+            result,
+            Err(AppError::PatchSourceNotApplicable { .. })
+        ));
+        assert_eq!(stdin.position(), 0, "rejected requests must not read stdin");
+    }
+
+    #[test]
+    fn missing_patch_file_names_the_path() {
+        let directory = tempfile::tempdir().expect("temp dir");
+        let path = directory.path().join("missing.patch");
+        let source = PatchSource::File(path.clone());
+        let mut stdin = Cursor::new(Vec::new());
+        let error = build_request_from_source(apply_patch_invocation(), Some(&source), &mut stdin)
+            .expect_err("missing file should fail");
+
+        assert!(matches!(error, AppError::OpenPatchFile { .. }));
+        assert!(error.to_string().contains(&path.display().to_string()));
+    }
+
+    proptest! {
+        #[test]
+        fn patch_at_or_below_limit_returns_only_expected_outcomes(
+            size in 1usize..=(MAX_PATCH_BYTES as usize)
+        ) {
+            let mut stdin = Cursor::new(vec![b'x'; size]);
+            let result = build_request(apply_patch_invocation(), &mut stdin);
+
+            prop_assert!(
+                result.is_ok() || matches!(result, Err(AppError::RequestTooLarge { .. })),
+                "build_request must return Ok or RequestTooLarge, not another error variant"
+            );
+        }
+
+        #[test]
+        fn jsonl_limit_is_consistently_enforced_for_non_patch(
+            len in (weaver_daemon_types::JSONL_REQUEST_MAX_LINE_BYTES + 1)
+                ..=(weaver_daemon_types::JSONL_REQUEST_MAX_LINE_BYTES + 4096)
+        ) {
+            let invocation = observe_status_invocation_with_argument(
+                argument_with_jsonl_len(len),
+            );
+            let mut stdin = Cursor::new(Vec::new());
+            let result = build_request(invocation, &mut stdin);
+
+            prop_assert!(
+                matches!(result, Err(AppError::RequestTooLarge { .. })),
+                "requests over the JSONL limit must always be rejected"
+            );
+        }
+    }
+}
@@ -1,20 +1,21 @@
 //! Runtime glue for daemon command execution.
 //!
 //! The CLI runner keeps argument parsing and configuration orchestration in
-//! `lib.rs`; this module owns the daemon transport path and request building so
-//! the top-level runtime stays small enough to scan.
+//! `lib.rs`; this module owns the daemon transport path so the top-level
+//! runtime stays small enough to scan. Request construction lives in
+//! `request`.
 
 use std::{
-    io::{Read, Write},
+    io::{self, Read, Write},
     process::ExitCode,
+    time::Duration,
 };
 
-use weaver_daemon_types::JSONL_REQUEST_MAX_LINE_BYTES;
+use weaver_daemon_types::CANCEL_REQUEST_LINE;
 
 use crate::{
     AppError,
     CommandInvocation,
-    CommandRequest,
     IoStreams,
     OutputContext,
     ResolvedOutputFormat,
@@ -23,22 +24,44 @@ use crate::{
     errors::is_daemon_not_running,
     exit_code_from_status,
     lifecycle::{LifecycleContext, try_auto_start_daemon},
-    transport::{self, Connection, connect, connect_with_retry},
+    request::{PatchSource, build_request_from_source},
+    transport::{self, Connection, DeadlineReader, connect_with_backoff, connect_with_retry},
 };
 
-/// Maximum patch size accepted from stdin.
-///
-/// Mirrors the JSON Lines request line-size budget so apply-patch requests do
-/// not exceed the daemon transport limit. Patches or serialised requests that
-/// exceed this limit return [`AppError::RequestTooLarge`].
-const MAX_PATCH_BYTES: u64 = JSONL_REQUEST_MAX_LINE_BYTES as u64;
+/// Per-request settings taken from the command line.
+#[derive(Clone, Debug)]
+pub(crate) struct RequestOptions {
+    /// Output format used to render daemon payloads.
+    pub(crate) output_format: ResolvedOutputFormat,
+    /// Longest time to wait for the daemon to finish responding.
+    pub(crate) timeout: Option<Duration>,
+    /// Explicit patch source for `act apply-patch`, if one was given.
+    pub(crate) patch_source: Option<PatchSource>,
+}
+
+impl RequestOptions {
+    /// Creates options that render with `output_format` and never time out.
+    pub(crate) const fn new(output_format: ResolvedOutputFormat) -> Self {
+        Self {
+            output_format,
+            timeout: None,
+            patch_source: None,
+        }
+    }
+}
 
 /// Executes a daemon-backed command end-to-end.
 ///
-/// Builds a [`CommandRequest`] from `invocation`, connects to the daemon socket
-/// (auto-starting the daemon if it is not running), writes the request as JSON
-/// Lines over the connection, and consumes daemon response messages,
-/// translating the final status into an [`ExitCode`].
+/// Builds a [`CommandRequest`](crate::CommandRequest) from `invocation`,
+/// reading any apply-patch content from the source in `options`, connects to
+/// the daemon socket (auto-starting the daemon if it is not running), writes
+/// the request as JSON Lines over the connection, and consumes daemon response
+/// messages, translating the final status into an [`ExitCode`].
+///
+/// When `options` carries a timeout, reading the response fails with
+/// [`AppError::ResponseTimeout`] once it has elapsed. Whenever the CLI stops
+/// reading before the daemon finishes, it asks the daemon to cancel the
+/// request.
 ///
 /// Writes a human-readable error message to `io.stderr` and returns
 /// [`ExitCode::FAILURE`] on any transport or IO error.
@@ -46,7 +69,7 @@ pub(crate) fn execute_daemon_command<R, W, E>(
     invocation: CommandInvocation,
     context: LifecycleContext<'_>,
     io: &mut IoStreams<'_, R, W, E>,
-    output_format: ResolvedOutputFormat,
+    options: RequestOptions,
 ) -> ExitCode
 where
     R: Read,
@@ -63,40 +86,87 @@ where
         invocation.operation.clone(),
         invocation.arguments.clone(),
     );
+    let request = match build_request_from_source(
+        invocation,
+        options.patch_source.as_ref(),
+        &mut *io.stdin,
+    ) {
+        Ok(request) => request,
+        Err(error) => return write_error_and_fail(&mut *io.stderr, error),
+    };
+    let config = context.config;
     let mut connection = match connect_or_start_daemon(context, &mut *io.stderr) {
         Ok(connection) => connection,
         Err(exit_code) => return exit_code,
     };
     tracing::debug!("connected to daemon socket");
-    let request = match build_request(invocation, &mut *io.stdin) {
-        Ok(request) => request,
-        Err(error) => return write_error_and_fail(&mut *io.stderr, error),
-    };
 
     if let Err(error) = request.write_jsonl(&mut connection) {
         return write_error_and_fail(&mut *io.stderr, error);
     }
 
+    let mut reader = DeadlineReader::new(&mut connection, options.timeout);
     match read_daemon_messages(
-        &mut connection,
+        &mut reader,
         io,
         OutputSettings {
-            format: output_format,
+            format: options.output_format,
             context: &output_context,
+            empty_line_limit: config.daemon_empty_line_limit(),
+            endpoint: config.daemon_socket(),
         },
     ) {
         Ok(status) => exit_code_from_status(status),
-        Err(error) => write_error_and_fail(&mut *io.stderr, error),
+        Err(error) => {
+            cancel_request(&mut connection);
+            write_error_and_fail(
+                &mut *io.stderr,
+                with_timeout_context(error, options.timeout),
+            )
+        }
+    }
+}
+
+/// Asks the daemon to stop working on a request the CLI has abandoned.
+///
+/// Cancellation is best effort: the daemon may already have closed the
+/// connection, and it treats the CLI disconnecting the same way.
+fn cancel_request(connection: &mut Connection) {
+    let sent = connection
+        .write_all(CANCEL_REQUEST_LINE.as_bytes())
+        .and_then(|()| connection.flush());
+    if let Err(error) = sent {
+        tracing::debug!(%error, "failed to send cancel message to daemon");
+    }
+}
+
+/// Reports an expired response deadline as [`AppError::ResponseTimeout`].
+fn with_timeout_context(error: AppError, timeout: Option<Duration>) -> AppError {
+    match (error, timeout) {
+        (AppError::ReadResponse(source), Some(timeout))
+            if source.kind() == io::ErrorKind::TimedOut =>
+        {
+            AppError::ResponseTimeout {
+                seconds: timeout.as_secs(),
+            }
+        }
+        (error, _) => error,
     }
 }
 
+/// Connects to the configured daemon, auto-starting it when nothing is
+/// listening.
+///
+/// TLS endpoints are never auto-started because `weaverd` only listens in
+/// plaintext; they are expected to sit behind a separately managed proxy.
 fn connect_or_start_daemon<E: Write>(
     context: LifecycleContext<'_>,
     stderr: &mut E,
 ) -> Result<Connection, ExitCode> {
-    match connect(context.config.daemon_socket()) {
+    let auto_start = !context.config.daemon_socket().is_tls();
+    match connect_with_backoff(context.config) {
         Ok(connection) => Ok(connection),
-        Err(error) if is_daemon_not_running(&error) => {
+        Err(error) if auto_start && is_daemon_not_running(&error) => {
             tracing::debug!("daemon not running; attempting auto-start");
             start_and_retry_daemon(context, stderr)
         }
@@ -104,55 +174,6 @@ fn connect_or_start_daemon<E: Write>(
     }
 }
 
-/// Builds a [`CommandRequest`] from `invocation`.
-///
-/// For `apply-patch` operations, reads patch content from `stdin` and returns
-/// [`AppError::MissingPatchInput`] if the content is empty after trimming. For
-/// all other operations, constructs the request directly from the invocation
-/// without reading stdin.
-pub(crate) fn build_request<R: Read>(
-    invocation: CommandInvocation,
-    stdin: &mut R,
-) -> Result<CommandRequest, AppError> {
-    if invocation.is_apply_patch() {
-        let mut patch = String::new();
-        stdin
-            .take(MAX_PATCH_BYTES + 1)
-            .read_to_string(&mut patch)
-            .map_err(AppError::ReadPatch)?;
-        if patch.len() as u64 > MAX_PATCH_BYTES {
-            return Err(AppError::RequestTooLarge {
-                size: patch.len(),
-                limit: JSONL_REQUEST_MAX_LINE_BYTES,
-            });
-        }
-        if patch.trim().is_empty() {
-            return Err(AppError::MissingPatchInput);
-        }
-        let request = CommandRequest::with_patch(invocation, patch);
-        enforce_request_line_limit(&request)?;
-        Ok(request)
-    } else {
-        let request = CommandRequest::from(invocation);
-        enforce_request_line_limit(&request)?;
-        Ok(request)
-    }
-}
-
-fn enforce_request_line_limit(request: &CommandRequest) -> Result<(), AppError> {
-    let json_len = serde_json::to_vec(request)
-        .map_err(AppError::SerialiseRequest)?
-        .len();
-    let request_line_len = json_len + 1;
-    if request_line_len > JSONL_REQUEST_MAX_LINE_BYTES {
-        return Err(AppError::RequestTooLarge {
-            size: request_line_len,
-            limit: JSONL_REQUEST_MAX_LINE_BYTES,
-        });
-    }
-    Ok(())
-}
-
 /// Writes `error` to `stderr` as a human-readable line and returns
 /// [`ExitCode::FAILURE`].
 ///
@@ -176,183 +197,8 @@ fn start_and_retry_daemon<E: Write>(
 
     // Retry briefly after daemon startup to tolerate socket-bind lag.
     tracing::debug!("retrying socket connection after daemon startup");
-    connect_with_retry(
-        context.config.daemon_socket(),
-        transport::CONNECTION_TIMEOUT,
-    )
-    .map_err(|error| {
+    connect_with_retry(context.config, transport::CONNECTION_TIMEOUT).map_err(|error| {
         tracing::warn!(error = %error, "failed to connect after daemon startup");
         write_error_and_fail(stderr, error)
     })
 }
-
-#[cfg(test)]
-mod tests {
-    //! Tests for daemon request construction helpers.
-
-    use std::io::Cursor;
-
-    use proptest::prelude::*;
-
-    use super::{MAX_PATCH_BYTES, build_request};
-    use crate::{AppError, CommandInvocation, CommandRequest};
-
-    enum ExpectedPatchRequest {
-        Ok,
-        MissingPatchInput,
-        Oversized,
-    }
-
-    fn observe_status_invocation() -> CommandInvocation {
-        CommandInvocation {
-            domain: "observe".to_owned(),
-            operation: "status".to_owned(),
-            arguments: Vec::new(),
-        }
-    }
-
-    fn observe_status_invocation_with_argument(argument: String) -> CommandInvocation {
-        CommandInvocation {
-            arguments: vec![argument],
-            ..observe_status_invocation()
-        }
-    }
-
-    fn apply_patch_invocation() -> CommandInvocation {
-        CommandInvocation {
-            domain: "act".to_owned(),
-            operation: "apply-patch".to_owned(),
-            arguments: Vec::new(),
-        }
-    }
-
-    /// Returns the number of payload bytes needed to reach a serialised JSON Lines
-    /// line length of exactly `len` bytes, using `template` to measure the fixed
-    /// envelope overhead.
-    ///
-    /// Panics if `len` does not exceed the envelope length, which would make the
-    /// requested line length impossible to achieve.
-    fn payload_size_for_jsonl_len(len: usize, template: &CommandRequest) -> usize {
-        let envelope_len = request_jsonl_len(template);
-        assert!(
-            len > envelope_len,
-            "requested JSONL line length {len} must exceed envelope length {envelope_len}"
-        );
-        len - envelope_len
-    }
-
-    fn patch_with_jsonl_len(len: usize) -> Vec<u8> {
-        let n = payload_size_for_jsonl_len(
-            len,
-            &CommandRequest::with_patch(apply_patch_invocation(), String::new()),
-        );
-        vec![b'a'; n]
-    }
-
-    fn argument_with_jsonl_len(len: usize) -> String {
-        let n = payload_size_for_jsonl_len(
-            len,
-            &CommandRequest::from(observe_status_invocation_with_argument(String::new())),
-        );
-        "a".repeat(n)
-    }
-
-    fn request_jsonl_len(request: &CommandRequest) -> usize {
-        match serde_json::to_vec(request) {
-            Ok(bytes) => bytes.len() + 1,
-            Err(error) => panic!("request must serialise: {error}"),
-        }
-    }
-
-    #[test]
-    fn non_patch_invocation_does_not_read_stdin() {
-        let mut stdin = Cursor::new(b"should not be read".to_vec());
-        let result = build_request(observe_status_invocation(), &mut stdin);
-        assert!(result.is_ok());
-        assert_eq!(
-            stdin.position(),
-            0,
-            "non-patch requests must not read stdin"
-        );
-    }
-
-    #[test]
-    fn non_patch_invocation_rejects_oversized_jsonl_request() {
-        let invocation = observe_status_invocation_with_argument(argument_with_jsonl_len(
-            MAX_PATCH_BYTES as usize + 1,
-        ));
-        let mut stdin = Cursor::new(b"should not be read".to_vec());
-        let result = build_request(invocation, &mut stdin);
-
-        assert!(matches!(result, Err(AppError::RequestTooLarge { .. })));
-    }
-
-    #[rstest::rstest]
-    #[case::reads_patch_from_stdin(
-        b"--- a/foo\n+++ b/foo\n@@ -1 +1 @@\n-old\n+new\n".to_vec(),
-        ExpectedPatchRequest::Ok
-    )]
-    #[case::returns_error_for_empty_stdin(
-        b"   \n".to_vec(),
-        ExpectedPatchRequest::MissingPatchInput
-    )]
-    #[case::accepts_request_at_jsonl_size_limit(
-        patch_with_jsonl_len(MAX_PATCH_BYTES as usize),
-        ExpectedPatchRequest::Ok
-    )]
-    #[case::returns_error_for_oversized_jsonl_request(
-        patch_with_jsonl_len(MAX_PATCH_BYTES as usize + 1),
-        ExpectedPatchRequest::Oversized
-    )]
-    fn apply_patch_stdin_cases(#[case] input: Vec<u8>, #[case] expected: ExpectedPatchRequest) {
-        let expected_patch = String::from_utf8(input.clone()).expect("patch input is UTF-8");
-        let mut stdin = Cursor::new(input);
-        let result = build_request(apply_patch_invocation(), &mut stdin);
-
-        match expected {
-            ExpectedPatchRequest::Ok => {
-                let request = result.expect("patch input should build a request");
-                assert_eq!(request.patch.as_deref(), Some(expected_patch.as_str()));
-                assert!(request_jsonl_len(&request) <= MAX_PATCH_BYTES as usize);
-            }
-            ExpectedPatchRequest::MissingPatchInput => {
-                assert!(matches!(result, Err(AppError::MissingPatchInput)));
-            }
-            ExpectedPatchRequest::Oversized => {
-                assert!(matches!(result, Err(AppError::RequestTooLarge { .. })));
-            }
-        }
-    }
-
-    proptest! {
-        #[test]
-        fn patch_at_or_below_limit_returns_only_expected_outcomes(
-            size in 1usize..=(MAX_PATCH_BYTES as usize)
-        ) {
-            let mut stdin = Cursor::new(vec![b'x'; size]);
-            let result = build_request(apply_patch_invocation(), &mut stdin);
-
-            prop_assert!(
-                result.is_ok() || matches!(result, Err(AppError::RequestTooLarge { .. })),
-                "build_request must return Ok or RequestTooLarge, not another error variant"
-            );
-        }
-
-        #[test]
-        fn jsonl_limit_is_consistently_enforced_for_non_patch(
-            len in (weaver_daemon_types::JSONL_REQUEST_MAX_LINE_BYTES + 1)
-                ..=(weaver_daemon_types::JSONL_REQUEST_MAX_LINE_BYTES + 4096)
-        ) {
-            let invocation = observe_status_invocation_with_argument(
-                argument_with_jsonl_len(len),
-            );
-            let mut stdin = Cursor::new(Vec::new());
-            let result = build_request(invocation, &mut stdin);
-
-            prop_assert!(
-                matches!(result, Err(AppError::RequestTooLarge { .. })),
-                "requests over the JSONL limit must always be rejected"
-            );
-        }
-    }
-}
@@ -5,18 +5,62 @@ use std::{
     process::ExitCode,
 };
 
-use weaver_config::{CapabilityMatrix, Config};
+use weaver_config::Config;
 
-use crate::{AppError, Cli, IoStreams};
+use crate::{
+    AppError,
+    Cli,
+    CliCommand,
+    IoStreams,
+    capability_cache::{CapabilityCache, config_hash},
+    capability_report::{CapabilityReport, ServerFactory},
+    help,
+};
+
+/// Binary name used in generated completion scripts.
+const COMPLETION_BIN_NAME: &str = "weaver";
 
-pub(crate) fn emit_capabilities<W>(config: &Config, stdout: &mut W) -> Result<(), AppError>
+/// Prints the configured overrides and effective capabilities as JSON.
+///
+/// Language servers are created with `servers` and initialised to negotiate
+/// their capabilities. When `cache` is provided, a fresh report cached for the
+/// same configuration is printed instead of recomputing it, and a recomputed
+/// report is stored for later invocations. A configuration that cannot be
+/// serialised into a cache key is always recomputed.
+pub(crate) fn emit_capabilities<W>(
+    config: &Config,
+    cache: Option<&CapabilityCache>,
+    servers: ServerFactory,
+    stdout: &mut W,
+) -> Result<(), AppError>
 where
     W: Write,
 {
-    let matrix: CapabilityMatrix = config.capability_matrix();
-    serde_json::to_writer_pretty(&mut *stdout, &matrix).map_err(AppError::SerialiseCapabilities)?;
+    let Some((cache, key)) = cache.zip(config_hash(config)) else {
+        return write_capabilities(stdout, &render_capabilities(config, servers)?);
+    };
+    let report = match cache.load(&key) {
+        Some(report) => report,
+        None => {
+            let report = render_capabilities(config, servers)?;
+            cache.store(&key, &report);
+            report
+        }
+    };
+    write_capabilities(stdout, &report)
+}
+
+fn render_capabilities(config: &Config, servers: ServerFactory) -> Result<String, AppError> {
+    let report = CapabilityReport::probe(&config.capability_matrix(), servers);
+    let mut rendered =
+        serde_json::to_string_pretty(&report).map_err(AppError::SerialiseCapabilities)?;
+    rendered.push('\n');
+    Ok(rendered)
+}
+
+fn write_capabilities<W: Write>(stdout: &mut W, report: &str) -> Result<(), AppError> {
     stdout
-        .write_all(b"\n")
+        .write_all(report.as_bytes())
         .map_err(AppError::EmitCapabilities)?;
     stdout.flush().map_err(AppError::EmitCapabilities)
 }
@@ -32,6 +76,7 @@ pub(crate) fn exit_code_from_status(status: i32) -> ExitCode {
 pub(crate) fn handle_capabilities_mode<R, W, E>(
     cli: &Cli,
     config: &Config,
+    servers: ServerFactory,
     io: &mut IoStreams<'_, R, W, E>,
 ) -> Option<ExitCode>
 where
@@ -43,7 +88,12 @@ where
         return None;
     }
 
-    match emit_capabilities(config, io.stdout) {
+    let cache = if cli.no_cache {
+        None
+    } else {
+        CapabilityCache::for_config(config)
+    };
+    match emit_capabilities(config, cache.as_ref(), servers, io.stdout) {
         Ok(()) => Some(ExitCode::SUCCESS),
         Err(error) => {
             writeln!(io.stderr, "{error}").ok();
@@ -51,3 +101,81 @@ where
         }
     }
 }
+
+/// Prints a completion script when the `completions` command was requested.
+///
+/// Runs before configuration loading so completions work without a config
+/// file or daemon. The script is generated from the augmented help command,
+/// so configuration flags complete alongside the clap-parsed arguments.
+pub(crate) fn handle_completions_mode<R, W, E>(
+    cli: &Cli,
+    io: &mut IoStreams<'_, R, W, E>,
+) -> Option<ExitCode>
+where
+    R: Read,
+    W: Write,
+    E: Write,
+{
+    let Some(CliCommand::Completions { shell }) = cli.command.as_ref() else {
+        return None;
+    };
+
+    // Generate into a buffer first: clap_complete panics on write errors,
+    // whereas a closed stdout should surface as an ordinary failure.
+    let mut script = Vec::new();
+    clap_complete::generate(
+        *shell,
+        &mut help::command(),
+        COMPLETION_BIN_NAME,
+        &mut script,
+    );
+    let written = io
+        .stdout
+        .write_all(&script)
+        .and_then(|()| io.stdout.flush());
+    match written {
+        Ok(()) => Some(ExitCode::SUCCESS),
+        Err(error) => {
+            writeln!(io.stderr, "{}", AppError::EmitCompletions(error)).ok();
+            Some(ExitCode::FAILURE)
+        }
+    }
+}
+
+/// Prints the plugin protocol JSON Schema when `plugin-schema` was requested.
+///
+/// Like completions, the schema is static, so it is emitted before
+/// configuration loading and needs neither a config file nor a daemon.
+pub(crate) fn handle_plugin_schema_mode<R, W, E>(
+    cli: &Cli,
+    io: &mut IoStreams<'_, R, W, E>,
+) -> Option<ExitCode>
+where
+    R: Read,
+    W: Write,
+    E: Write,
+{
+    if !matches!(cli.command, Some(CliCommand::PluginSchema)) {
+        return None;
+    }
+
+    match emit_plugin_schema(io.stdout) {
+        Ok(()) => Some(ExitCode::SUCCESS),
+        Err(error) => {
+            writeln!(io.stderr, "{error}").ok();
+            Some(ExitCode::FAILURE)
+        }
+    }
+}
+
+fn emit_plugin_schema<W>(stdout: &mut W) -> Result<(), AppError>
+where
+    W: Write,
+{
+    let schema = weaver_plugins::protocol::json_schema();
+    serde_json::to_writer_pretty(&mut *stdout, &schema).map_err(AppError::SerialisePluginSchema)?;
+    stdout
+        .write_all(b"\n")
+        .map_err(AppError::EmitPluginSchema)?;
+    stdout.flush().map_err(AppError::EmitPluginSchema)
+}
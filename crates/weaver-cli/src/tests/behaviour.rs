@@ -7,10 +7,10 @@ use std::cell::RefCell;
 
 use rstest_bdd_macros::{given, scenario, then, when};
 use serde_json::json;
+use weaver_config::DEFAULT_DAEMON_EMPTY_LINE_LIMIT;
 
 use super::support::*;
 use crate::{
-    EMPTY_LINE_LIMIT,
     lifecycle::{LifecycleCommand, LifecycleError},
     output::UNKNOWN_OPERATION_TYPE,
 };
@@ -25,6 +25,15 @@ const EXPECTED_SHARED_CONFIG_HELP_FLAGS: &[&str] = &[
     "--log-format <FORMAT>",
     "--capability-overrides <DIRECTIVE>",
     "--locale <LOCALE>",
+    "--daemon-workers <COUNT>",
+    "--daemon-idle-timeout <SECONDS>",
+    "--daemon-connect-attempts <COUNT>",
+    "--daemon-empty-line-limit <COUNT>",
+    "--plugin-inline-limit <BYTES>",
+    "--daemon-tls-ca <PATH>",
+    "--daemon-tls-cert <PATH>",
+    "--daemon-tls-key <PATH>",
+    "--workspace-root <PATH>",
 ];
 
 const SAMPLE_RUST_SOURCE: &str = "fn main() {\n    let value = 1;\n    value\n}\n";
@@ -122,11 +131,6 @@ fn given_lifecycle_error(world: &RefCell<TestWorld>) {
         });
 }
 
-#[given("capability overrides force python rename")]
-fn given_capability_override(world: &RefCell<TestWorld>) {
-    world.borrow_mut().configure_capability_override();
-}
-
 #[given("a running fake daemon sending malformed json")]
 fn given_malformed_daemon(world: &RefCell<TestWorld>) {
     world
@@ -148,7 +152,7 @@ fn given_daemon_missing_exit(world: &RefCell<TestWorld>) {
 #[given("a running fake daemon that emits empty lines")]
 fn given_daemon_with_empty_lines(world: &RefCell<TestWorld>) {
     let mut lines = Vec::new();
-    for _ in 0..EMPTY_LINE_LIMIT {
+    for _ in 0..DEFAULT_DAEMON_EMPTY_LINE_LIMIT {
         lines.push(String::new());
     }
     world
@@ -364,14 +368,6 @@ fn then_exit_failure(world: &RefCell<TestWorld>) {
         .expect("CLI did not fail as expected");
 }
 
-#[then("capabilities output is {fixture}")]
-fn then_capabilities(world: &RefCell<TestWorld>, fixture: String) {
-    world
-        .borrow()
-        .assert_capabilities_output(&fixture)
-        .expect("capabilities output mismatch");
-}
-
 #[scenario(path = "tests/features/weaver_cli.feature")]
 fn weaver_cli_behaviour(world: RefCell<TestWorld>) { let _ = world; }
 
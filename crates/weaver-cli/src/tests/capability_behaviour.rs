@@ -0,0 +1,41 @@
+//! BDD step definitions for the `--capabilities` probe scenarios.
+//!
+//! These steps configure capability overrides and compare the emitted report
+//! with golden fixtures. The CLI runs against stub language servers, so the
+//! negotiated capabilities are deterministic.
+
+use std::cell::RefCell;
+
+use rstest_bdd_macros::{given, scenario, then};
+
+use super::support::*;
+
+#[given("capability overrides force python rename")]
+fn given_capability_override(world: &RefCell<TestWorld>) {
+    world.borrow_mut().configure_capability_override();
+}
+
+#[given("capability overrides force python call hierarchy and deny rust definitions")]
+fn given_capability_overrides(world: &RefCell<TestWorld>) {
+    world.borrow_mut().configure_capability_overrides();
+}
+
+#[then("capabilities output is {fixture}")]
+fn then_capabilities(world: &RefCell<TestWorld>, fixture: String) {
+    world
+        .borrow()
+        .assert_capabilities_output(&fixture)
+        .expect("capabilities output mismatch");
+}
+
+#[scenario(
+    path = "tests/features/weaver_cli.feature",
+    name = "Probing capability output"
+)]
+fn probing_capability_output(world: RefCell<TestWorld>) { let _ = world; }
+
+#[scenario(
+    path = "tests/features/weaver_cli.feature",
+    name = "Probing negotiated capabilities with overrides"
+)]
+fn probing_negotiated_capabilities(world: RefCell<TestWorld>) { let _ = world; }
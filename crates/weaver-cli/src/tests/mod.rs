@@ -2,5 +2,6 @@
 //! supporting harness utilities such as fake daemons.
 
 mod behaviour;
+mod capability_behaviour;
 pub(crate) mod support;
 mod unit;
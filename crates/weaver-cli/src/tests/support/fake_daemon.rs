@@ -211,3 +211,44 @@ pub(in crate::tests) fn accept_unix_connection(
     let (stream, _) = listener.accept().context("accept unix connection")?;
     respond_to_request(stream, &lines)
 }
+
+/// Binds a Unix socket after `delay` and answers one request on it.
+///
+/// Simulates a daemon that is still starting when the CLI first connects. The
+/// listener polls for up to five seconds so the thread still finishes if the
+/// CLI never connects.
+#[cfg(unix)]
+pub(in crate::tests) fn spawn_delayed_unix_listener(
+    socket_path: std::path::PathBuf,
+    delay: Duration,
+) -> thread::JoinHandle<Result<()>> {
+    thread::spawn(move || -> Result<()> {
+        thread::sleep(delay);
+        let listener =
+            std::os::unix::net::UnixListener::bind(&socket_path).context("bind unix socket")?;
+        listener.set_nonblocking(true).context("set nonblocking")?;
+        let deadline = Instant::now()
+            .checked_add(Duration::from_secs(5))
+            .context("listener deadline overflow")?;
+
+        loop {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    return respond_to_request(stream, &super::default_daemon_lines())
+                        .context("respond to request");
+                }
+                Err(error)
+                    if error.kind() == io::ErrorKind::WouldBlock && Instant::now() < deadline =>
+                {
+                    thread::sleep(Duration::from_millis(25));
+                }
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => {
+                    return Err(anyhow!(
+                        "timed out waiting for CLI connection on Unix socket"
+                    ));
+                }
+                Err(error) => return Err(error).context("accept connection"),
+            }
+        }
+    })
+}
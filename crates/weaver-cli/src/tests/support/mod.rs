@@ -6,6 +6,7 @@
 
 mod fake_daemon;
 mod lifecycle;
+mod servers;
 
 use std::{
     cell::RefCell,
@@ -17,12 +18,13 @@ use std::{
 
 use anyhow::{Context, Result, ensure};
 use cap_std::fs::Dir;
-#[cfg(unix)]
-pub(super) use fake_daemon::accept_unix_connection;
 pub(super) use fake_daemon::{FakeDaemon, accept_tcp_connection, respond_to_request};
+#[cfg(unix)]
+pub(super) use fake_daemon::{accept_unix_connection, spawn_delayed_unix_listener};
 pub(super) use lifecycle::{LifecycleCall, TestLifecycle};
 pub(crate) use lifecycle::{temp_paths, write_health_json, write_health_snapshot};
 use rstest::fixture;
+pub(crate) use servers::stub_server;
 use tempfile::TempDir;
 use url::Url;
 use weaver_config::{CapabilityDirective, CapabilityOverride, Config, SocketEndpoint};
@@ -86,6 +88,17 @@ impl TestWorld {
         )];
     }
 
+    pub fn configure_capability_overrides(&mut self) {
+        self.config.capability_overrides = vec![
+            CapabilityDirective::new(
+                "python",
+                "observe.call-hierarchy",
+                CapabilityOverride::Force,
+            ),
+            CapabilityDirective::new("rust", "observe.get-definition", CapabilityOverride::Deny),
+        ];
+    }
+
     /// Configures the test to trigger auto-start behaviour that will fail.
     ///
     /// Sets up a socket endpoint that refuses connections and ensures the
@@ -97,6 +110,9 @@ impl TestWorld {
         // ConnectionRefused on some systems, so we use a high port instead.
         // The CLI will try to connect, fail, then attempt auto-start.
         self.config.daemon_socket = SocketEndpoint::tcp("127.0.0.1", 65535);
+        // Skip the connection backoff so the CLI falls through to auto-start
+        // straight away.
+        self.config.daemon_connect_attempts = 1;
         // Point to a non-existent binary so spawn fails quickly.
         self.daemon_binary = Some(OsString::from("/nonexistent/weaverd"));
     }
@@ -172,6 +188,7 @@ impl TestWorld {
             &mut io,
             &loader,
             daemon_binary,
+            stub_server,
             &localizer,
             |invocation, context, output| self.lifecycle.handle(invocation, context, output),
         );
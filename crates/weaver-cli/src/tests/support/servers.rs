@@ -0,0 +1,36 @@
+//! Stub language servers for capability probe tests.
+//!
+//! The Rust server advertises the core navigation requests, the Python server
+//! advertises everything, and the TypeScript server fails to initialise, so a
+//! single report covers ready, partial, and unavailable servers without
+//! spawning real processes.
+
+use weaver_lsp_host::{
+    Language,
+    LanguageServer,
+    ServerCapabilitySet,
+    test_support::{RecordingLanguageServer, ResponseSet},
+};
+
+/// Creates the stub language server for `language`.
+pub(crate) fn stub_server(language: Language) -> Box<dyn LanguageServer> {
+    let server = match language {
+        Language::Rust => RecordingLanguageServer::new(
+            ServerCapabilitySet::new(true, true, false).with_hover(true),
+            ResponseSet::default(),
+        ),
+        Language::Python => RecordingLanguageServer::new(
+            ServerCapabilitySet::new(true, true, true)
+                .with_call_hierarchy(true)
+                .with_hover(true)
+                .with_document_symbols(true)
+                .with_workspace_symbols(true),
+            ResponseSet::default(),
+        ),
+        Language::TypeScript => RecordingLanguageServer::failing_initialize(
+            ServerCapabilitySet::new(false, false, false),
+            "typescript-language-server not installed",
+        ),
+    };
+    Box::new(server)
+}
@@ -1,7 +1,8 @@
 //! Unit tests for Weaver CLI core functionality.
 //!
-//! Exercises command serialisation, daemon message parsing, configuration
-//! loading, and socket connection establishment (TCP and Unix domain sockets).
+//! Exercises command serialisation, configuration loading, and socket
+//! connection establishment (TCP and Unix domain sockets). Daemon message
+//! parsing lives in the `daemon_messages` submodule.
 
 use std::{
     cell::RefCell,
@@ -28,7 +29,6 @@ use crate::{
     CommandRequest,
     ConfigLoader,
     DaemonAction,
-    EMPTY_LINE_LIMIT,
     IoStreams,
     OutputContext,
     OutputFormat,
@@ -130,6 +130,10 @@ fn command_invocation_validation(
     let cli = Cli {
         capabilities: false,
         output: OutputFormat::Auto,
+        timeout: None,
+        patch_file: None,
+        patch_stdin: false,
+        no_cache: false,
         command: None,
         domain,
         operation,
@@ -169,54 +173,6 @@ fn exit_code_from_status_out_of_range_defaults_to_failure() {
     assert_eq!(exit_code_from_status(300), ExitCode::FAILURE);
 }
 
-fn test_read_daemon_messages(input: Vec<u8>) -> (Result<i32, AppError>, Vec<u8>, Vec<u8>) {
-    let mut cursor = Cursor::new(input);
-    let mut stdin = Cursor::new(Vec::new());
-    let mut stdout = Vec::new();
-    let mut stderr = Vec::new();
-    let mut io = IoStreams::new(&mut stdin, &mut stdout, &mut stderr, false);
-    let context = OutputContext::new("observe", "get-definition", Vec::new());
-    let result = read_daemon_messages(
-        &mut cursor,
-        &mut io,
-        OutputSettings {
-            format: ResolvedOutputFormat::Json,
-            context: &context,
-        },
-    );
-    (result, stdout, stderr)
-}
-
-#[test]
-fn read_daemon_messages_errors_without_exit() {
-    let input = b"{\"kind\":\"stream\",\"stream\":\"stdout\",\"data\":\"hi\"}\n".to_vec();
-    let (error, stdout, _stderr) = test_read_daemon_messages(input);
-    let error = error.unwrap_err();
-    assert!(matches!(error, AppError::MissingExit));
-    let stdout_text = decode_utf8(stdout, "stdout").expect("decode stdout");
-    assert_eq!(stdout_text, "hi");
-}
-
-#[test]
-fn read_daemon_messages_warns_after_empty_lines() {
-    let mut payload = Vec::new();
-    for _ in 0..EMPTY_LINE_LIMIT {
-        payload.extend_from_slice(b"\n");
-    }
-    let (error, _stdout, stderr) = test_read_daemon_messages(payload);
-    let error = error.unwrap_err();
-    assert!(matches!(error, AppError::MissingExit));
-    let warning = decode_utf8(stderr, "stderr").expect("decode stderr");
-    assert!(warning.contains("Warning: received"));
-}
-
-#[test]
-fn read_daemon_messages_fails_on_malformed_json() {
-    let (error, _stdout, _stderr) = test_read_daemon_messages(Vec::from("this is not json\n"));
-    let error = error.unwrap_err();
-    assert!(matches!(error, AppError::ParseMessage(_)));
-}
-
 #[test]
 fn run_with_loader_filters_configuration_arguments() {
     struct RecordingLoader {
@@ -291,7 +247,11 @@ where
 {
     let (endpoint, handle) = setup_listener();
 
-    let mut connection = connect(&endpoint).expect("connect to daemon");
+    let config = Config {
+        daemon_socket: endpoint,
+        ..Config::default()
+    };
+    let mut connection = connect(&config).expect("connect to daemon");
     let request = CommandRequest {
         command: CommandDescriptor {
             domain: "observe".into(),
@@ -313,6 +273,8 @@ where
         OutputSettings {
             format: ResolvedOutputFormat::Json,
             context: &context,
+            empty_line_limit: config.daemon_empty_line_limit(),
+            endpoint: config.daemon_socket(),
         },
     )
     .expect("read responses");
@@ -391,8 +353,17 @@ mod actionable_guidance;
 mod after_help;
 mod auto_start;
 mod bare_invocation;
+mod capability_cache;
+mod capability_report;
 mod command_surface;
+mod completions;
+mod connect_retry;
+mod daemon_messages;
 mod discoverability;
 mod help_output;
 mod missing_operation_guidance;
+mod output_modes;
+mod patch_file;
+mod plugin_schema;
+mod request_timeout;
 mod version_output;
@@ -4,11 +4,7 @@
 //! connection-refused errors, and that spawn failures are reported appropriately.
 
 #[cfg(unix)]
-use std::os::unix::net::UnixListener;
-#[cfg(unix)]
-use std::thread;
-#[cfg(unix)]
-use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::{ffi::OsStr, io::Cursor, process::ExitCode};
 
 use rstest::rstest;
@@ -16,13 +12,16 @@ use rstest::rstest;
 use tempfile::TempDir;
 use weaver_config::{Config, SocketEndpoint};
 
+#[cfg(unix)]
+use crate::tests::support::spawn_delayed_unix_listener;
 use crate::{
     CommandInvocation,
     IoStreams,
+    RequestOptions,
     ResolvedOutputFormat,
     execute_daemon_command,
     lifecycle::LifecycleContext,
-    tests::support::{decode_utf8, default_daemon_lines, respond_to_request, write_health_json},
+    tests::support::{decode_utf8, write_health_json},
 };
 
 /// Creates a minimal test invocation for daemon command tests.
@@ -55,6 +54,7 @@ fn auto_start_failure_paths(
     // requiring daemon setup or mock servers.
     let config = Config {
         daemon_socket: SocketEndpoint::tcp("127.0.0.1", 1),
+        daemon_connect_attempts: 1,
         ..Config::default()
     };
     let context = LifecycleContext {
@@ -68,7 +68,12 @@ fn auto_start_failure_paths(
     let mut stdin = Cursor::new(Vec::new());
     let mut io = IoStreams::new(&mut stdin, &mut stdout, &mut stderr, false);
 
-    let exit = execute_daemon_command(invocation, context, &mut io, ResolvedOutputFormat::Json);
+    let exit = execute_daemon_command(
+        invocation,
+        context,
+        &mut io,
+        RequestOptions::new(ResolvedOutputFormat::Json),
+    );
 
     assert_eq!(exit, ExitCode::FAILURE);
     let stderr_text = decode_utf8(stderr, "stderr").expect("stderr utf8");
@@ -90,6 +95,7 @@ fn auto_start_failure_paths(
 fn auto_start_missing_binary_shows_actionable_guidance() {
     let config = Config {
         daemon_socket: SocketEndpoint::tcp("127.0.0.1", 1),
+        daemon_connect_attempts: 1,
         ..Config::default()
     };
     let context = LifecycleContext {
@@ -103,7 +109,12 @@ fn auto_start_missing_binary_shows_actionable_guidance() {
     let mut stdin = Cursor::new(Vec::new());
     let mut io = IoStreams::new(&mut stdin, &mut stdout, &mut stderr, false);
 
-    let exit = execute_daemon_command(invocation, context, &mut io, ResolvedOutputFormat::Json);
+    let exit = execute_daemon_command(
+        invocation,
+        context,
+        &mut io,
+        RequestOptions::new(ResolvedOutputFormat::Json),
+    );
 
     assert_eq!(exit, ExitCode::FAILURE);
     let stderr_text = decode_utf8(stderr, "stderr").expect("stderr utf8");
@@ -137,46 +148,6 @@ fn auto_start_missing_binary_shows_actionable_guidance() {
     );
 }
 
-/// Writes a health snapshot JSON file to the specified path.
-#[cfg(unix)]
-fn spawn_delayed_unix_listener(
-    socket_path: std::path::PathBuf,
-) -> thread::JoinHandle<Result<(), String>> {
-    thread::spawn(move || -> Result<(), String> {
-        thread::sleep(Duration::from_millis(100));
-        let listener = UnixListener::bind(&socket_path)
-            .map_err(|error| format!("bind unix socket: {error}"))?;
-        listener
-            .set_nonblocking(true)
-            .map_err(|error| format!("set nonblocking: {error}"))?;
-        let deadline = Instant::now()
-            .checked_add(Duration::from_secs(5))
-            .ok_or_else(|| String::from("listener deadline overflow"))?;
-
-        loop {
-            match listener.accept() {
-                Ok((stream, _)) => {
-                    respond_to_request(stream, &default_daemon_lines())
-                        .map_err(|error| format!("respond to request: {error}"))?;
-                    return Ok(());
-                }
-                Err(error)
-                    if error.kind() == std::io::ErrorKind::WouldBlock
-                        && Instant::now() < deadline =>
-                {
-                    thread::sleep(Duration::from_millis(25));
-                }
-                Err(error) if error.kind() == std::io::ErrorKind::WouldBlock => {
-                    return Err(String::from(
-                        "timed out waiting for CLI retry connection on Unix socket",
-                    ));
-                }
-                Err(error) => return Err(format!("accept connection: {error}")),
-            }
-        }
-    })
-}
-
 #[cfg(unix)]
 fn assert_auto_start_success(exit: ExitCode, stderr_text: &str, stdout_text: &str) {
     assert_eq!(
@@ -227,12 +198,13 @@ fn auto_start_succeeds_and_proceeds() {
     write_health_json(&health_path, "ready", 12345, timestamp).expect("write health snapshot");
 
     // Bind the socket on a short delay so the first connect fails and the retry
-    // succeeds once auto-start wait handling completes. See
-    // `spawn_delayed_unix_listener` for the timing rationale.
-    let listener_handle = spawn_delayed_unix_listener(socket_path);
+    // succeeds once auto-start wait handling completes. A single initial
+    // attempt makes the CLI fall through to auto-start instead of backing off.
+    let listener_handle = spawn_delayed_unix_listener(socket_path, Duration::from_millis(100));
 
     let config = Config {
         daemon_socket: SocketEndpoint::unix(socket_str),
+        daemon_connect_attempts: 1,
         ..Config::default()
     };
     let context = LifecycleContext {
@@ -247,7 +219,12 @@ fn auto_start_succeeds_and_proceeds() {
     let mut stdin = Cursor::new(Vec::new());
     let mut io = IoStreams::new(&mut stdin, &mut stdout, &mut stderr, false);
 
-    let exit = execute_daemon_command(invocation, context, &mut io, ResolvedOutputFormat::Json);
+    let exit = execute_daemon_command(
+        invocation,
+        context,
+        &mut io,
+        RequestOptions::new(ResolvedOutputFormat::Json),
+    );
 
     listener_handle
         .join()
@@ -289,7 +266,12 @@ fn auto_start_times_out_when_daemon_slow() {
     let mut stdin = Cursor::new(Vec::new());
     let mut io = IoStreams::new(&mut stdin, &mut stdout, &mut stderr, false);
 
-    let exit = execute_daemon_command(invocation, context, &mut io, ResolvedOutputFormat::Json);
+    let exit = execute_daemon_command(
+        invocation,
+        context,
+        &mut io,
+        RequestOptions::new(ResolvedOutputFormat::Json),
+    );
 
     let stderr_text = decode_utf8(stderr, "stderr").expect("stderr utf8");
     assert_eq!(exit, ExitCode::FAILURE);
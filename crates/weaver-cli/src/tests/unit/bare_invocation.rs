@@ -224,6 +224,10 @@ fn bare_invocation_propagates_bare_help_write_failures() {
     let cli = Cli {
         capabilities: false,
         output: crate::OutputFormat::Auto,
+        timeout: None,
+        patch_file: None,
+        patch_stdin: false,
+        no_cache: false,
         command: None,
         domain: None,
         operation: None,
@@ -0,0 +1,118 @@
+//! Tests for the on-disk `--capabilities` cache.
+//!
+//! Verifies that a fresh entry is served in place of recomputing the report,
+//! and that configuration changes, expiry, and disabling the cache all force
+//! a recomputation.
+
+use std::time::Duration;
+
+use tempfile::TempDir;
+use weaver_config::{CapabilityDirective, CapabilityOverride, Config};
+
+use crate::{
+    capability_cache::{CAPABILITY_CACHE_FILE, CAPABILITY_CACHE_TTL, CapabilityCache, config_hash},
+    emit_capabilities,
+    tests::support::stub_server,
+};
+
+/// Sentinel report planted in the cache to prove it was read.
+const PLANTED_REPORT: &str = "{\"planted\":true}\n";
+
+fn config_with(directive: CapabilityOverride) -> Config {
+    Config {
+        capability_overrides: vec![CapabilityDirective::new(
+            "python",
+            "observe.get-definition",
+            directive,
+        )],
+        ..Config::default()
+    }
+}
+
+fn emit(config: &Config, cache: Option<&CapabilityCache>) -> String {
+    let mut stdout = Vec::new();
+    emit_capabilities(config, cache, stub_server, &mut stdout)
+        .expect("capabilities should be emitted");
+    String::from_utf8(stdout).expect("stdout utf8")
+}
+
+/// Replaces the cached report while keeping its key and timestamp, so a later
+/// call that prints the sentinel must have been served from the cache.
+fn plant_report(directory: &TempDir) {
+    let path = directory.path().join(CAPABILITY_CACHE_FILE);
+    let contents = std::fs::read_to_string(&path).expect("cache should have been written");
+    let mut entry: serde_json::Value = serde_json::from_str(&contents).expect("cache entry");
+    entry["report"] = serde_json::Value::from(PLANTED_REPORT);
+    std::fs::write(&path, entry.to_string()).expect("rewrite cache entry");
+}
+
+fn primed_cache(config: &Config, ttl: Duration) -> (TempDir, CapabilityCache, String) {
+    let directory = TempDir::new().expect("temporary directory");
+    let cache = CapabilityCache::new(directory.path(), ttl);
+    let computed = emit(config, Some(&cache));
+    plant_report(&directory);
+    (directory, cache, computed)
+}
+
+#[test]
+fn second_call_within_ttl_reads_the_cache() {
+    let config = config_with(CapabilityOverride::Force);
+    let (_directory, cache, computed) = primed_cache(&config, CAPABILITY_CACHE_TTL);
+
+    assert_eq!(computed, emit(&config, None));
+    assert_eq!(emit(&config, Some(&cache)), PLANTED_REPORT);
+}
+
+#[test]
+fn config_change_invalidates_the_cache() {
+    let (_directory, cache, _) = primed_cache(
+        &config_with(CapabilityOverride::Force),
+        CAPABILITY_CACHE_TTL,
+    );
+    let changed = config_with(CapabilityOverride::Deny);
+
+    let output = emit(&changed, Some(&cache));
+
+    assert_eq!(output, emit(&changed, None));
+    assert!(
+        output.contains("forced_off"),
+        "expected recomputed report: {output}"
+    );
+}
+
+#[test]
+fn expired_entries_are_recomputed() {
+    let config = config_with(CapabilityOverride::Force);
+    let (_directory, cache, computed) = primed_cache(&config, Duration::ZERO);
+
+    assert_eq!(emit(&config, Some(&cache)), computed);
+}
+
+#[test]
+fn disabled_cache_ignores_fresh_entries() {
+    let config = config_with(CapabilityOverride::Force);
+    let (_directory, _cache, computed) = primed_cache(&config, CAPABILITY_CACHE_TTL);
+
+    assert_eq!(emit(&config, None), computed);
+}
+
+#[test]
+fn unreadable_cache_directory_falls_back_to_computing() {
+    let directory = TempDir::new().expect("temporary directory");
+    let blocker = directory.path().join("not-a-directory");
+    std::fs::write(&blocker, b"").expect("create blocking file");
+    let cache = CapabilityCache::new(&blocker, CAPABILITY_CACHE_TTL);
+    let config = config_with(CapabilityOverride::Allow);
+
+    assert_eq!(emit(&config, Some(&cache)), emit(&config, None));
+}
+
+#[test]
+fn cache_key_is_a_stable_sha256_digest() {
+    let config = config_with(CapabilityOverride::Force);
+    let key = config_hash(&config).expect("configuration should serialise");
+
+    assert_eq!(config_hash(&config).as_deref(), Some(key.as_str()));
+    assert_eq!(key.len(), 64);
+    assert!(key.bytes().all(|byte| byte.is_ascii_hexdigit()));
+}
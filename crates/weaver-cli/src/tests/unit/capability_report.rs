@@ -0,0 +1,115 @@
+//! Tests for the `--capabilities` report.
+//!
+//! Verifies that the emitted JSON keeps the configured override matrix and
+//! adds each language's server status and the effective status of every known
+//! capability, negotiated against the stub language servers.
+
+use rstest::rstest;
+use serde_json::Value;
+use weaver_config::{CapabilityDirective, CapabilityOverride, Config};
+
+use crate::{
+    emit_capabilities,
+    tests::support::{read_fixture, stub_server},
+};
+
+fn emit(directives: Vec<CapabilityDirective>) -> String {
+    let config = Config {
+        capability_overrides: directives,
+        ..Config::default()
+    };
+    let mut stdout = Vec::new();
+    emit_capabilities(&config, None, stub_server, &mut stdout)
+        .expect("capabilities should be emitted");
+    String::from_utf8(stdout).expect("stdout utf8")
+}
+
+fn emit_json(directives: Vec<CapabilityDirective>) -> Value {
+    serde_json::from_str(&emit(directives)).expect("capabilities output should be JSON")
+}
+
+#[test]
+fn report_matches_golden_output() {
+    let stdout = emit(vec![
+        CapabilityDirective::new(
+            "python",
+            "observe.call-hierarchy",
+            CapabilityOverride::Force,
+        ),
+        CapabilityDirective::new("rust", "observe.get-definition", CapabilityOverride::Deny),
+    ]);
+
+    let expected = read_fixture("capabilities_overrides.json").expect("golden fixture");
+    assert_eq!(stdout, expected);
+}
+
+#[rstest]
+#[case::advertised("observe.get-definition", "available")]
+#[case::missing_on_server("verify.diagnostics", "unavailable")]
+fn ready_servers_report_negotiated_capabilities(#[case] capability: &str, #[case] expected: &str) {
+    let report = emit_json(Vec::new());
+
+    let language = &report["languages"]["rust"];
+    assert_eq!(language["server"], "ready");
+    assert_eq!(language["effective"][capability], expected);
+}
+
+#[rstest]
+#[case::force(CapabilityOverride::Force, "forced_on")]
+#[case::deny(CapabilityOverride::Deny, "forced_off")]
+#[case::allow(CapabilityOverride::Allow, "unavailable")]
+fn overrides_apply_when_the_server_is_missing_the_capability(
+    #[case] directive: CapabilityOverride,
+    #[case] expected: &str,
+) {
+    let report = emit_json(vec![CapabilityDirective::new(
+        "rust",
+        "verify.diagnostics",
+        directive,
+    )]);
+
+    assert_eq!(
+        report["languages"]["rust"]["effective"]["verify.diagnostics"],
+        expected
+    );
+}
+
+#[test]
+fn failed_servers_are_reported_unavailable() {
+    let report = emit_json(vec![CapabilityDirective::new(
+        "typescript",
+        "observe.get-definition",
+        CapabilityOverride::Force,
+    )]);
+
+    let language = &report["languages"]["typescript"];
+    assert_eq!(language["server"], "unavailable");
+    let effective = language["effective"]
+        .as_object()
+        .expect("effective should be an object");
+    assert_eq!(effective["observe.get-definition"], "forced_on");
+    assert!(
+        effective
+            .iter()
+            .filter(|(capability, _)| capability.as_str() != "observe.get-definition")
+            .all(|(_, status)| status == "unavailable"),
+        "expected only unavailable capabilities: {effective:?}"
+    );
+}
+
+#[test]
+fn overrides_for_other_languages_are_preserved() {
+    let report = emit_json(vec![CapabilityDirective::new(
+        "go",
+        "observe.get-definition",
+        CapabilityOverride::Deny,
+    )]);
+
+    let language = &report["languages"]["go"];
+    assert_eq!(language["overrides"]["observe.get-definition"], "deny");
+    assert_eq!(language["server"], "unavailable");
+    assert_eq!(
+        language["effective"]["observe.get-definition"],
+        "forced_off"
+    );
+}
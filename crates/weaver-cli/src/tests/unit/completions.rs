@@ -0,0 +1,69 @@
+//! Tests for `weaver completions <shell>`.
+//!
+//! Verifies that completion scripts are printed to stdout without loading
+//! configuration or contacting the daemon.
+
+use std::{ffi::OsString, io::Cursor, process::ExitCode};
+
+use rstest::rstest;
+use weaver_config::Config;
+
+use crate::{AppError, ConfigLoader, IoStreams, run_with_loader};
+
+/// A config loader that panics if called, proving that completion output
+/// short-circuits before configuration loading.
+struct PanickingLoader;
+
+impl ConfigLoader for PanickingLoader {
+    fn load(&self, _args: &[OsString]) -> Result<Config, AppError> {
+        panic!("completion output must not attempt configuration loading");
+    }
+}
+
+fn run_completions(shell: &str) -> (ExitCode, String, String) {
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut stdin = Cursor::new(Vec::new());
+    let mut io = IoStreams::new(&mut stdin, &mut stdout, &mut stderr, false);
+    let args = ["weaver", "completions", shell].map(OsString::from);
+    let exit = run_with_loader(args, &mut io, &PanickingLoader);
+    let stdout_text = String::from_utf8(stdout).expect("stdout utf8");
+    let stderr_text = String::from_utf8(stderr).expect("stderr utf8");
+    (exit, stdout_text, stderr_text)
+}
+
+#[test]
+fn bash_completions_cover_command_name_and_flags() {
+    let (exit, stdout, stderr) = run_completions("bash");
+
+    assert_eq!(exit, ExitCode::SUCCESS);
+    assert!(stderr.is_empty(), "unexpected stderr: {stderr}");
+    assert!(stdout.contains("weaver"), "missing command name: {stdout}");
+    assert!(
+        stdout.contains("--capabilities"),
+        "missing --capabilities: {stdout}"
+    );
+    assert!(
+        stdout.contains("--daemon-socket"),
+        "missing configuration flag: {stdout}"
+    );
+}
+
+#[rstest]
+#[case("zsh", "#compdef weaver")]
+#[case("fish", "complete -c weaver")]
+fn completions_support_other_shells(#[case] shell: &str, #[case] marker: &str) {
+    let (exit, stdout, _stderr) = run_completions(shell);
+
+    assert_eq!(exit, ExitCode::SUCCESS);
+    assert!(stdout.contains(marker), "missing {marker:?}: {stdout}");
+}
+
+#[test]
+fn completions_reject_unknown_shells() {
+    let (exit, stdout, stderr) = run_completions("tcsh");
+
+    assert_eq!(exit, ExitCode::FAILURE);
+    assert!(stdout.is_empty());
+    assert!(stderr.contains("tcsh"), "unexpected stderr: {stderr}");
+}
@@ -0,0 +1,134 @@
+//! Tests for the daemon connection backoff.
+//!
+//! Covers a daemon that binds its socket shortly after the CLI first tries to
+//! connect, the configurable attempt budget, and errors that must not be
+//! retried.
+
+#![cfg(unix)]
+
+use std::{
+    ffi::OsStr,
+    io::{self, Cursor},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use rstest::rstest;
+use tempfile::TempDir;
+use weaver_config::{Config, SocketEndpoint};
+
+use crate::{
+    AppError,
+    CommandInvocation,
+    IoStreams,
+    RequestOptions,
+    ResolvedOutputFormat,
+    execute_daemon_command,
+    lifecycle::LifecycleContext,
+    tests::support::{decode_utf8, spawn_delayed_unix_listener},
+    transport::connect_with_backoff,
+};
+
+fn socket_endpoint(dir: &TempDir) -> SocketEndpoint {
+    SocketEndpoint::unix(
+        dir.path()
+            .join("daemon.sock")
+            .to_string_lossy()
+            .into_owned(),
+    )
+}
+
+fn config_with_attempts(daemon_socket: SocketEndpoint, attempts: u32) -> Config {
+    Config {
+        daemon_socket,
+        daemon_connect_attempts: attempts,
+        ..Config::default()
+    }
+}
+
+#[test]
+fn initial_connection_waits_for_a_starting_daemon() {
+    let dir = TempDir::new().expect("tempdir");
+    let listener =
+        spawn_delayed_unix_listener(dir.path().join("daemon.sock"), Duration::from_millis(200));
+    let config = Config {
+        daemon_socket: socket_endpoint(&dir),
+        ..Config::default()
+    };
+    let context = LifecycleContext {
+        config: &config,
+        config_arguments: &[],
+        // Auto-start would fail, so success proves the backoff found the socket.
+        daemon_binary: Some(OsStr::new("/nonexistent/weaverd")),
+    };
+    let invocation = CommandInvocation {
+        domain: String::from("observe"),
+        operation: String::from("test"),
+        arguments: Vec::new(),
+    };
+    let mut stdin = Cursor::new(Vec::new());
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut io = IoStreams::new(&mut stdin, &mut stdout, &mut stderr, false);
+
+    let exit = execute_daemon_command(
+        invocation,
+        context,
+        &mut io,
+        RequestOptions::new(ResolvedOutputFormat::Json),
+    );
+
+    listener
+        .join()
+        .expect("listener thread")
+        .expect("listener should accept connection");
+    let stderr = decode_utf8(stderr, "stderr").expect("decode stderr");
+    assert_eq!(exit, ExitCode::from(17), "stderr: {stderr:?}");
+    assert!(
+        !stderr.contains("Waiting for daemon start"),
+        "CLI should not auto-start a daemon that is already starting: {stderr:?}"
+    );
+}
+
+#[rstest]
+#[case::single_attempt(1, Duration::ZERO, Duration::from_millis(100))]
+#[case::three_attempts(3, Duration::from_millis(375), Duration::from_secs(2))]
+fn attempt_budget_bounds_the_wait(
+    #[case] attempts: u32,
+    #[case] at_least: Duration,
+    #[case] under: Duration,
+) {
+    let dir = TempDir::new().expect("tempdir");
+
+    let started = Instant::now();
+    let result = connect_with_backoff(&config_with_attempts(socket_endpoint(&dir), attempts));
+    let elapsed = started.elapsed();
+
+    assert!(matches!(result, Err(AppError::Connect { .. })));
+    assert!(
+        elapsed >= at_least && elapsed < under,
+        "{attempts} attempts took {elapsed:?}"
+    );
+}
+
+#[test]
+fn non_transient_errors_fail_fast() {
+    // Unix socket paths longer than `sun_path` are rejected before connecting.
+    let endpoint = SocketEndpoint::unix(format!("/tmp/{}.sock", "x".repeat(200)));
+
+    let started = Instant::now();
+    let result = connect_with_backoff(&config_with_attempts(endpoint, 5));
+    let elapsed = started.elapsed();
+
+    match result {
+        Err(AppError::Connect { source, .. }) => {
+            assert_eq!(source.kind(), io::ErrorKind::InvalidInput);
+        }
+        Err(other) => panic!("unexpected error: {other}"),
+        Ok(_) => panic!("connecting to an invalid path must fail"),
+    }
+    assert!(
+        elapsed < Duration::from_millis(100),
+        "non-transient error was retried for {elapsed:?}"
+    );
+}
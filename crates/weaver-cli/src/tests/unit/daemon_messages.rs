@@ -0,0 +1,203 @@
+//! Unit tests for reading daemon response messages.
+
+use std::io::{self, Cursor, Read};
+
+use rstest::rstest;
+use weaver_config::{DEFAULT_DAEMON_EMPTY_LINE_LIMIT, SocketEndpoint};
+
+use crate::{
+    AppError,
+    IoStreams,
+    OutputContext,
+    OutputSettings,
+    ResolvedOutputFormat,
+    read_daemon_messages,
+    tests::support::decode_utf8,
+};
+
+/// Yields the underlying bytes at most `chunk` bytes per read, as a socket
+/// might when the daemon flushes a message in several writes.
+struct ChunkedReader {
+    input: Cursor<Vec<u8>>,
+    chunk: usize,
+}
+
+impl Read for ChunkedReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let (head, _) = buf.split_at_mut(buf.len().min(self.chunk));
+        self.input.read(head)
+    }
+}
+
+type ReadOutcome = (Result<i32, AppError>, Vec<u8>, Vec<u8>);
+
+fn test_read_daemon_messages(input: Vec<u8>) -> ReadOutcome {
+    read_messages_from(&mut Cursor::new(input), DEFAULT_DAEMON_EMPTY_LINE_LIMIT)
+}
+
+fn read_messages_from(connection: &mut impl Read, empty_line_limit: usize) -> ReadOutcome {
+    let endpoint = SocketEndpoint::tcp("127.0.0.1", 9779);
+    let mut stdin = Cursor::new(Vec::new());
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut io = IoStreams::new(&mut stdin, &mut stdout, &mut stderr, false);
+    let context = OutputContext::new("observe", "get-definition", Vec::new());
+    let result = read_daemon_messages(
+        connection,
+        &mut io,
+        OutputSettings {
+            format: ResolvedOutputFormat::Json,
+            context: &context,
+            empty_line_limit,
+            endpoint: &endpoint,
+        },
+    );
+    (result, stdout, stderr)
+}
+
+#[test]
+fn read_daemon_messages_errors_without_exit() {
+    let input = b"{\"kind\":\"stream\",\"stream\":\"stdout\",\"data\":\"hi\"}\n".to_vec();
+    let (error, stdout, _stderr) = test_read_daemon_messages(input);
+    let error = error.unwrap_err();
+    assert!(matches!(error, AppError::MissingExit));
+    let stdout_text = decode_utf8(stdout, "stdout").expect("decode stdout");
+    assert_eq!(stdout_text, "hi");
+}
+
+#[test]
+fn read_daemon_messages_warns_after_empty_lines() {
+    let mut payload = Vec::new();
+    for _ in 0..DEFAULT_DAEMON_EMPTY_LINE_LIMIT {
+        payload.extend_from_slice(b"\n");
+    }
+    let (error, _stdout, stderr) = test_read_daemon_messages(payload);
+    let error = error.unwrap_err();
+    assert!(matches!(error, AppError::MissingExit));
+    let warning = decode_utf8(stderr, "stderr").expect("decode stderr");
+    assert!(warning.contains("Warning: received"));
+}
+
+#[test]
+fn read_daemon_messages_fails_on_malformed_json() {
+    let (error, _stdout, _stderr) = test_read_daemon_messages(Vec::from("this is not json\n"));
+    let error = error.unwrap_err();
+    assert!(matches!(error, AppError::ParseMessage(_)));
+}
+
+#[rstest]
+#[case(
+    r#"{"kind":"progress","message":"waiting for rope"}"#,
+    "progress: waiting for rope\n"
+)]
+#[case(
+    r#"{"kind":"progress","message":"indexing","fraction":0.425}"#,
+    "progress: indexing (43%)\n"
+)]
+fn read_daemon_messages_renders_progress_to_stderr(#[case] line: &str, #[case] expected: &str) {
+    let input = format!("{line}\n{{\"kind\":\"exit\",\"status\":0}}\n").into_bytes();
+    let (status, stdout, stderr) = test_read_daemon_messages(input);
+    assert_eq!(status.expect("exit status"), 0);
+    assert!(stdout.is_empty());
+    assert_eq!(
+        decode_utf8(stderr, "stderr").expect("decode stderr"),
+        expected
+    );
+}
+
+#[test]
+fn read_daemon_messages_ignores_unknown_kinds() {
+    let input = concat!(
+        "{\"kind\":\"heartbeat\",\"sequence\":1}\n",
+        "{\"kind\":\"stream\",\"stream\":\"stdout\",\"data\":\"hi\"}\n",
+        "{\"kind\":\"exit\",\"status\":3}\n",
+    );
+    let (status, stdout, stderr) = test_read_daemon_messages(input.as_bytes().to_vec());
+    assert_eq!(status.expect("exit status"), 3);
+    assert_eq!(decode_utf8(stdout, "stdout").expect("decode stdout"), "hi");
+    assert!(stderr.is_empty());
+}
+
+#[rstest]
+#[case::single_bytes(1)]
+#[case::mid_message(7)]
+#[case::across_messages(40)]
+fn read_daemon_messages_reassembles_chunked_lines(#[case] chunk: usize) {
+    let input = concat!(
+        "{\"kind\":\"stream\",\"stream\":\"stdout\",\"data\":\"hello\"}\n",
+        "{\"kind\":\"exit\",\"status\":0}\n",
+    );
+    let mut connection = ChunkedReader {
+        input: Cursor::new(input.as_bytes().to_vec()),
+        chunk,
+    };
+
+    let (status, stdout, _stderr) =
+        read_messages_from(&mut connection, DEFAULT_DAEMON_EMPTY_LINE_LIMIT);
+
+    assert_eq!(status.expect("exit status"), 0);
+    assert_eq!(
+        decode_utf8(stdout, "stdout").expect("decode stdout"),
+        "hello"
+    );
+}
+
+#[test]
+fn read_daemon_messages_rejects_unterminated_final_line() {
+    let input = concat!(
+        "{\"kind\":\"stream\",\"stream\":\"stdout\",\"data\":\"hi\"}\n",
+        "{\"kind\":\"exit\",",
+    );
+    let (error, stdout, _stderr) = test_read_daemon_messages(input.as_bytes().to_vec());
+
+    let error = error.unwrap_err();
+    assert!(
+        matches!(error, AppError::IncompleteMessage { bytes: 15 }),
+        "expected incomplete message error, got {error:?}"
+    );
+    assert_eq!(decode_utf8(stdout, "stdout").expect("decode stdout"), "hi");
+}
+
+#[test]
+fn read_daemon_messages_aborts_at_configured_empty_line_limit() {
+    let input = concat!(
+        "\n",
+        "  \n",
+        "\n",
+        "{\"kind\":\"stream\",\"stream\":\"stdout\",\"data\":\"late\"}\n",
+        "{\"kind\":\"exit\",\"status\":0}\n",
+    );
+
+    let (error, stdout, stderr) = read_messages_from(&mut Cursor::new(input.as_bytes()), 3);
+
+    assert!(matches!(error.unwrap_err(), AppError::MissingExit));
+    assert!(
+        stdout.is_empty(),
+        "no message after the limit should be read"
+    );
+    assert_eq!(
+        decode_utf8(stderr, "stderr").expect("decode stderr"),
+        concat!(
+            "Warning: received 3 consecutive empty lines from daemon at ",
+            "tcp://127.0.0.1:9779; aborting after discarding 5 bytes.\n",
+        )
+    );
+}
+
+#[test]
+fn read_daemon_messages_tolerates_empty_lines_below_the_limit() {
+    let input = concat!(
+        "\n",
+        "\n",
+        "{\"kind\":\"stream\",\"stream\":\"stdout\",\"data\":\"hi\"}\n",
+        "\n",
+        "\n",
+        "{\"kind\":\"exit\",\"status\":0}\n",
+    );
+
+    let (status, stdout, stderr) = read_messages_from(&mut Cursor::new(input.as_bytes()), 3);
+
+    assert_eq!(status.expect("exit status"), 0);
+    assert_eq!(decode_utf8(stdout, "stdout").expect("decode stdout"), "hi");
+    assert!(stderr.is_empty());
+}
@@ -17,6 +17,15 @@ const EXPECTED_SHARED_CONFIG_HELP_FLAGS: &[&str] = &[
     "--log-format <FORMAT>",
     "--capability-overrides <DIRECTIVE>",
     "--locale <LOCALE>",
+    "--daemon-workers <COUNT>",
+    "--daemon-idle-timeout <SECONDS>",
+    "--daemon-connect-attempts <COUNT>",
+    "--daemon-empty-line-limit <COUNT>",
+    "--plugin-inline-limit <BYTES>",
+    "--daemon-tls-ca <PATH>",
+    "--daemon-tls-cert <PATH>",
+    "--daemon-tls-key <PATH>",
+    "--workspace-root <PATH>",
 ];
 
 struct PanickingLoader;
@@ -93,6 +102,15 @@ fn augmented_command_has_expected_arg_structure() {
         ("log-format", Some("FORMAT"), ArgAction::Set),
         ("capability-overrides", Some("DIRECTIVE"), ArgAction::Append),
         ("locale", Some("LOCALE"), ArgAction::Set),
+        ("daemon-workers", Some("COUNT"), ArgAction::Set),
+        ("daemon-idle-timeout", Some("SECONDS"), ArgAction::Set),
+        ("daemon-connect-attempts", Some("COUNT"), ArgAction::Set),
+        ("daemon-empty-line-limit", Some("COUNT"), ArgAction::Set),
+        ("plugin-inline-limit", Some("BYTES"), ArgAction::Set),
+        ("daemon-tls-ca", Some("PATH"), ArgAction::Set),
+        ("daemon-tls-cert", Some("PATH"), ArgAction::Set),
+        ("daemon-tls-key", Some("PATH"), ArgAction::Set),
+        ("workspace-root", Some("PATH"), ArgAction::Set),
     ];
 
     let cmd = help::command();
@@ -0,0 +1,69 @@
+//! Tests for `--output` mode selection.
+//!
+//! Feeds the same definition payload through the CLI under each output mode
+//! and terminal setting to confirm JSON mode forwards it untouched while human
+//! mode always renders it.
+
+use rstest::rstest;
+use serde_json::json;
+
+use crate::tests::support::{TestWorld, daemon_lines_for_stdout};
+
+const SOURCE: &str = "fn main() {\n    let value = 1;\n    value\n}\n";
+
+#[derive(Clone, Copy, Debug)]
+enum Rendering {
+    Human,
+    Raw,
+}
+
+/// Runs `get-definition` against a fake daemon and returns the payload it
+/// sent alongside the CLI's stdout.
+fn run_definition(output_flag: &str, stdout_is_terminal: bool) -> (String, String) {
+    let mut world = TestWorld {
+        stdout_is_terminal,
+        ..TestWorld::default()
+    };
+    world
+        .create_source_file("example.rs", SOURCE)
+        .expect("create source file");
+    let uri = world.source_uri().expect("source uri").to_owned();
+    let payload = json!([{ "uri": uri, "line": 2, "column": 5 }]).to_string();
+    world
+        .start_daemon_with_lines(daemon_lines_for_stdout(&payload))
+        .expect("start fake daemon");
+
+    let command = format!("{output_flag} observe get-definition --uri {uri} --position 2:5");
+    world.run(&command).expect("run definition command");
+    world
+        .assert_exit_code(0)
+        .expect("definition command succeeds");
+    (payload, world.stdout_text().expect("decode stdout"))
+}
+
+#[rstest]
+#[case::auto_on_terminal("", true, Rendering::Human)]
+#[case::auto_when_redirected("", false, Rendering::Raw)]
+#[case::human_on_terminal("--output human", true, Rendering::Human)]
+#[case::human_when_redirected("--output human", false, Rendering::Human)]
+#[case::json_on_terminal("--output json", true, Rendering::Raw)]
+#[case::json_when_redirected("--output json", false, Rendering::Raw)]
+fn output_mode_controls_payload_rendering(
+    #[case] output_flag: &str,
+    #[case] stdout_is_terminal: bool,
+    #[case] expected: Rendering,
+) {
+    let (payload, stdout) = run_definition(output_flag, stdout_is_terminal);
+
+    match expected {
+        Rendering::Raw => assert_eq!(stdout, payload, "JSON mode must forward the payload"),
+        Rendering::Human => {
+            assert!(
+                stdout.contains("example.rs"),
+                "missing file header: {stdout}"
+            );
+            assert!(stdout.contains("^ definition"), "missing caret: {stdout}");
+            assert!(!stdout.contains("\"uri\""), "raw JSON leaked: {stdout}");
+        }
+    }
+}
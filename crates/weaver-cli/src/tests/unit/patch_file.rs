@@ -0,0 +1,115 @@
+//! Tests for `--patch-file` and `--patch-stdin`.
+//!
+//! Confirms patch content read from a file reaches the daemon in the same
+//! serialised request as a piped patch, and that invalid patch sources are
+//! rejected before the daemon is contacted.
+
+use crate::tests::support::{TestWorld, write_test_file};
+
+const SAMPLE_PATCH: &str = concat!(
+    "diff --git a/src/main.rs b/src/main.rs\n",
+    "<<<<<<< SEARCH\n",
+    "fn main() {\n",
+    "    println!(\"Old Message\");\n",
+    "}\n",
+    "=======\n",
+    "fn main() {\n",
+    "    println!(\"New Message\");\n",
+    "}\n",
+    ">>>>>>> REPLACE\n",
+);
+
+/// Writes `content` to a temporary patch file and returns its path.
+fn world_with_patch_file(content: &[u8]) -> (TestWorld, String) {
+    let mut world = TestWorld::default();
+    world
+        .create_missing_source("changes.patch")
+        .expect("prepare patch location");
+    let path = world.source_path.clone().expect("patch path");
+    write_test_file(&path, content).expect("write patch file");
+    let path = path.to_str().expect("utf8 temp path").to_owned();
+    (world, path)
+}
+
+#[test]
+fn patch_file_request_matches_golden() {
+    let (mut world, path) = world_with_patch_file(SAMPLE_PATCH.as_bytes());
+    world.set_stdin("ignored when --patch-file is given");
+    world.start_daemon().expect("start fake daemon");
+
+    world
+        .run(&format!("--patch-file {path} act apply-patch"))
+        .expect("run apply-patch");
+
+    world.assert_exit_code(0).expect("apply-patch succeeds");
+    world
+        .assert_golden_request("request_act_apply_patch.jsonl")
+        .expect("request matches golden");
+}
+
+#[test]
+fn patch_stdin_request_matches_golden() {
+    let mut world = TestWorld::default();
+    world.set_stdin(SAMPLE_PATCH);
+    world.start_daemon().expect("start fake daemon");
+
+    world
+        .run("--patch-stdin act apply-patch")
+        .expect("run apply-patch");
+
+    world.assert_exit_code(0).expect("apply-patch succeeds");
+    world
+        .assert_golden_request("request_act_apply_patch.jsonl")
+        .expect("request matches golden");
+}
+
+#[test]
+fn patch_file_rejects_invalid_utf8() {
+    let (mut world, path) = world_with_patch_file(&[b'+', 0xff, 0xfe, b'\n']);
+
+    world
+        .run(&format!("--patch-file {path} act apply-patch"))
+        .expect("run apply-patch");
+
+    world.assert_failure().expect("invalid patch fails");
+    let stderr = world.stderr_text().expect("decode stderr");
+    assert!(
+        stderr.contains("failed to read patch input"),
+        "unexpected stderr: {stderr}"
+    );
+}
+
+#[test]
+fn patch_file_is_rejected_for_other_operations() {
+    let (mut world, path) = world_with_patch_file(SAMPLE_PATCH.as_bytes());
+
+    world
+        .run(&format!("--patch-file {path} observe get-definition"))
+        .expect("run observe command");
+
+    world.assert_failure().expect("patch source is rejected");
+    let stderr = world.stderr_text().expect("decode stderr");
+    assert!(
+        stderr.contains("only apply to act apply-patch"),
+        "unexpected stderr: {stderr}"
+    );
+    assert!(world.requests.is_empty());
+}
+
+#[test]
+fn patch_file_conflicts_with_patch_stdin() {
+    let (mut world, path) = world_with_patch_file(SAMPLE_PATCH.as_bytes());
+
+    world
+        .run(&format!(
+            "--patch-file {path} --patch-stdin act apply-patch"
+        ))
+        .expect("run apply-patch");
+
+    world.assert_failure().expect("conflicting flags fail");
+    let stderr = world.stderr_text().expect("decode stderr");
+    assert!(
+        stderr.contains("--patch-stdin"),
+        "unexpected stderr: {stderr}"
+    );
+}
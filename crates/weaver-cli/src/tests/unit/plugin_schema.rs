@@ -0,0 +1,44 @@
+//! Tests for `weaver plugin-schema`.
+//!
+//! Verifies that the plugin protocol schema is printed to stdout without
+//! loading configuration or contacting the daemon.
+
+use std::{ffi::OsString, io::Cursor, process::ExitCode};
+
+use serde_json::Value;
+use weaver_config::Config;
+
+use crate::{AppError, ConfigLoader, IoStreams, run_with_loader};
+
+/// A config loader that panics if called, proving that schema output
+/// short-circuits before configuration loading.
+struct PanickingLoader;
+
+impl ConfigLoader for PanickingLoader {
+    fn load(&self, _args: &[OsString]) -> Result<Config, AppError> {
+        panic!("plugin schema output must not attempt configuration loading");
+    }
+}
+
+#[test]
+fn plugin_schema_prints_protocol_schemas_as_json() {
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut stdin = Cursor::new(Vec::new());
+    let mut io = IoStreams::new(&mut stdin, &mut stdout, &mut stderr, false);
+    let args = ["weaver", "plugin-schema"].map(OsString::from);
+
+    let exit = run_with_loader(args, &mut io, &PanickingLoader);
+
+    assert_eq!(exit, ExitCode::SUCCESS);
+    assert!(stderr.is_empty(), "unexpected stderr: {stderr:?}");
+    let schema: Value = serde_json::from_slice(&stdout).expect("stdout is JSON");
+    assert!(schema.get("protocol_version").is_some_and(Value::is_u64));
+    for pointer in [
+        "/schemas/PluginRequest/properties/operation",
+        "/schemas/PluginResponse/properties/output",
+        "/schemas/PluginOutput",
+    ] {
+        assert!(schema.pointer(pointer).is_some(), "missing {pointer}");
+    }
+}
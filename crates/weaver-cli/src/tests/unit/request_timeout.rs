@@ -0,0 +1,88 @@
+//! Tests for the `--timeout` response deadline.
+//!
+//! Uses a fake daemon that accepts the request but never answers to confirm
+//! the CLI aborts instead of blocking forever, and cancels the request it
+//! abandons.
+
+use std::{
+    ffi::OsString,
+    io::{BufRead, BufReader, Cursor},
+    net::TcpListener,
+    process::ExitCode,
+    thread,
+    time::{Duration, Instant},
+};
+
+use clap::Parser;
+use weaver_config::{Config, SocketEndpoint};
+use weaver_daemon_types::CANCEL_REQUEST_LINE;
+
+use crate::{
+    Cli,
+    IoStreams,
+    run_with_loader,
+    tests::support::{StaticConfigLoader, decode_utf8},
+};
+
+/// Accepts one connection and reads its request without ever responding.
+///
+/// The thread returns the line the client sends after its request, which is
+/// empty if the client disconnects without sending one.
+fn spawn_silent_daemon() -> (u16, thread::JoinHandle<String>) {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).expect("bind silent daemon");
+    let port = listener.local_addr().expect("silent daemon address").port();
+    let handle = thread::spawn(move || {
+        let (stream, _) = listener.accept().expect("accept client");
+        let mut reader = BufReader::new(stream);
+        let mut request = String::new();
+        reader.read_line(&mut request).expect("read request");
+        let mut follow_up = String::new();
+        reader.read_line(&mut follow_up).expect("read follow-up");
+        follow_up
+    });
+    (port, handle)
+}
+
+#[test]
+fn timeout_aborts_when_daemon_never_responds() {
+    let (port, daemon) = spawn_silent_daemon();
+    let loader = StaticConfigLoader::new(Config {
+        daemon_socket: SocketEndpoint::tcp("127.0.0.1", port),
+        ..Config::default()
+    });
+    let mut stdin = Cursor::new(Vec::new());
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut io = IoStreams::new(&mut stdin, &mut stdout, &mut stderr, false);
+
+    let started = Instant::now();
+    let exit = run_with_loader(
+        ["weaver", "--timeout", "1", "observe", "get-definition"].map(OsString::from),
+        &mut io,
+        &loader,
+    );
+    let elapsed = started.elapsed();
+    let follow_up = daemon.join().expect("silent daemon thread panicked");
+
+    assert_eq!(exit, ExitCode::FAILURE);
+    assert!(
+        elapsed < Duration::from_secs(5),
+        "CLI waited {elapsed:?} despite a one second timeout"
+    );
+    let stderr = decode_utf8(stderr, "stderr").expect("decode stderr");
+    assert!(
+        stderr.contains("daemon did not respond within 1s"),
+        "unexpected stderr: {stderr}"
+    );
+    assert!(stdout.is_empty());
+    assert_eq!(
+        follow_up, CANCEL_REQUEST_LINE,
+        "the abandoned request should be cancelled"
+    );
+}
+
+#[test]
+fn timeout_rejects_zero_seconds() {
+    let result = Cli::try_parse_from(["weaver", "--timeout", "0", "observe", "get-definition"]);
+    assert!(result.is_err(), "a zero timeout must be rejected");
+}
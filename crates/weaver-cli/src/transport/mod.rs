@@ -0,0 +1,241 @@
+//! Socket transport helpers for the Weaver CLI.
+//!
+//! The functions here encapsulate establishing connections to daemon sockets and
+//! wrap the resulting streams in a uniform [`Connection`] type so that the rest
+//! of the CLI logic can remain transport agnostic. TCP endpoints using the
+//! `tcp+tls` scheme negotiate TLS in [`connect`]; Unix sockets stay plaintext.
+
+mod tls;
+
+#[cfg(unix)]
+use std::os::fd::{FromRawFd, IntoRawFd, OwnedFd};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+use std::{
+    io::{self, Read, Write},
+    net::{SocketAddr, TcpStream, ToSocketAddrs},
+    thread,
+    time::{Duration, Instant},
+};
+
+#[cfg(unix)]
+use socket2::{Domain, SockAddr, Socket, Type};
+use weaver_config::{Config, SocketEndpoint};
+
+use self::tls::TlsStream;
+use super::{AppError, is_daemon_not_running};
+
+pub(super) const CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
+const RETRY_INTERVAL: Duration = Duration::from_millis(25);
+/// Delay before the first backoff retry; each later retry doubles it.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(125);
+
+pub(super) enum Connection {
+    Tcp(TcpStream),
+    Tls(Box<TlsStream>),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl Read for Connection {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Tcp(stream) => stream.read(buf),
+            Self::Tls(stream) => stream.read(buf),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Connection {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Tcp(stream) => stream.write(buf),
+            Self::Tls(stream) => stream.write(buf),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Tcp(stream) => stream.flush(),
+            Self::Tls(stream) => stream.flush(),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.flush(),
+        }
+    }
+}
+
+impl Connection {
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        match self {
+            Self::Tcp(stream) => stream.set_read_timeout(timeout),
+            Self::Tls(stream) => stream.sock.set_read_timeout(timeout),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.set_read_timeout(timeout),
+        }
+    }
+}
+
+/// Reader that fails with [`io::ErrorKind::TimedOut`] once a deadline passes.
+///
+/// Each read waits at most until the deadline, so a daemon that accepts a
+/// request but never finishes answering cannot block the CLI indefinitely.
+/// Without a deadline reads block as long as the connection stays open.
+pub(super) struct DeadlineReader<'a> {
+    connection: &'a mut Connection,
+    deadline: Option<Instant>,
+}
+
+impl<'a> DeadlineReader<'a> {
+    pub(super) fn new(connection: &'a mut Connection, timeout: Option<Duration>) -> Self {
+        let deadline = timeout.and_then(|limit| Instant::now().checked_add(limit));
+        Self {
+            connection,
+            deadline,
+        }
+    }
+}
+
+impl Read for DeadlineReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some(deadline) = self.deadline else {
+            return self.connection.read(buf);
+        };
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(deadline_expired());
+        }
+        self.connection.set_read_timeout(Some(remaining))?;
+        // Socket read timeouts surface as `WouldBlock` on Unix and `TimedOut`
+        // on Windows.
+        self.connection
+            .read(buf)
+            .map_err(|error| match error.kind() {
+                io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => deadline_expired(),
+                _ => error,
+            })
+    }
+}
+
+fn deadline_expired() -> io::Error {
+    io::Error::new(io::ErrorKind::TimedOut, "request deadline expired")
+}
+
+/// Connects to the daemon socket named by `config`.
+///
+/// For `tcp+tls` endpoints the TLS handshake completes before this returns, so
+/// certificate problems surface as [`AppError::TlsHandshake`] rather than as a
+/// failed request.
+pub(super) fn connect(config: &Config) -> Result<Connection, AppError> {
+    let endpoint = config.daemon_socket();
+    match endpoint {
+        SocketEndpoint::Tcp { host, port, tls } => {
+            let endpoint_display = endpoint.to_string();
+            let client = tls.then(|| tls::client_config(config)).transpose()?;
+            let address = resolve_tcp_address(host, *port).map_err(|error| AppError::Resolve {
+                endpoint: endpoint_display.clone(),
+                source: error,
+            })?;
+
+            let stream =
+                TcpStream::connect_timeout(&address, CONNECTION_TIMEOUT).map_err(|source| {
+                    AppError::Connect {
+                        endpoint: endpoint_display.clone(),
+                        source,
+                    }
+                })?;
+            let Some(client) = client else {
+                return Ok(Connection::Tcp(stream));
+            };
+            tls::handshake(client, host, stream)
+                .map(|stream| Connection::Tls(Box::new(stream)))
+                .map_err(|source| AppError::TlsHandshake {
+                    endpoint: endpoint_display,
+                    source,
+                })
+        }
+        SocketEndpoint::Unix { path } => {
+            #[cfg(unix)]
+            {
+                connect_unix(path.as_str()).map_err(|source| AppError::Connect {
+                    endpoint: endpoint.to_string(),
+                    source,
+                })
+            }
+
+            #[cfg(not(unix))]
+            {
+                Err(AppError::UnsupportedUnixTransport(endpoint.to_string()))
+            }
+        }
+    }
+}
+
+pub(super) fn connect_with_retry(
+    config: &Config,
+    retry_window: Duration,
+) -> Result<Connection, AppError> {
+    let deadline = Instant::now().checked_add(retry_window);
+    loop {
+        match connect(config) {
+            Ok(connection) => return Ok(connection),
+            Err(error)
+                if is_daemon_not_running(&error)
+                    && deadline.is_some_and(|limit| Instant::now() < limit) =>
+            {
+                let sleep_duration = deadline
+                    .and_then(|limit| limit.checked_duration_since(Instant::now()))
+                    .map_or(RETRY_INTERVAL, |remaining| remaining.min(RETRY_INTERVAL));
+                thread::sleep(sleep_duration);
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+/// Connects to the daemon, retrying with exponential backoff while nothing is
+/// listening yet.
+///
+/// Makes at most [`Config::daemon_connect_attempts`] attempts, sleeping
+/// [`INITIAL_BACKOFF`] before the first retry and doubling the delay after each
+/// one, so five attempts span roughly two seconds. This gives a daemon that is still
+/// starting time to bind its socket. Only errors accepted by
+/// [`is_daemon_not_running`] are retried; any other error is returned
+/// immediately.
+pub(super) fn connect_with_backoff(config: &Config) -> Result<Connection, AppError> {
+    let mut remaining = config.daemon_connect_attempts();
+    let mut delay = INITIAL_BACKOFF;
+    loop {
+        remaining -= 1;
+        match connect(config) {
+            Err(error) if remaining > 0 && is_daemon_not_running(&error) => {
+                tracing::debug!(%error, ?delay, remaining, "daemon not listening; retrying");
+                thread::sleep(delay);
+                delay = delay.saturating_mul(2);
+            }
+            result => return result,
+        }
+    }
+}
+
+fn resolve_tcp_address(host: &str, port: u16) -> io::Result<SocketAddr> {
+    let mut addrs = (host, port).to_socket_addrs()?;
+    addrs
+        .find(|addr| matches!(addr, SocketAddr::V4(_) | SocketAddr::V6(_)))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::AddrNotAvailable, "no resolved addresses"))
+}
+
+#[cfg(unix)]
+fn connect_unix(path: &str) -> io::Result<Connection> {
+    let socket = Socket::new(Domain::UNIX, Type::STREAM, None)?;
+    let address = SockAddr::unix(path)?;
+    socket.connect_timeout(&address, CONNECTION_TIMEOUT)?;
+    let fd = socket.into_raw_fd();
+    // SAFETY: `from_raw_fd` takes ownership of the file descriptor produced by
+    // `into_raw_fd`, so the resulting `OwnedFd` is valid and uniquely owned.
+    let owned = unsafe { OwnedFd::from_raw_fd(fd) };
+    Ok(Connection::Unix(UnixStream::from(owned)))
+}
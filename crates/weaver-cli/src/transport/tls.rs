@@ -0,0 +1,121 @@
+//! TLS negotiation for `tcp+tls` daemon endpoints.
+//!
+//! Builds a rustls client configuration from the CA, certificate, and key
+//! paths in [`Config`] and completes the handshake eagerly so certificate
+//! failures are reported before any request bytes are sent.
+
+use std::{
+    io::{self, Read},
+    net::TcpStream,
+    path::Path,
+    sync::Arc,
+};
+
+use rustls::{
+    ClientConfig,
+    ClientConnection,
+    RootCertStore,
+    StreamOwned,
+    pki_types::{CertificateDer, PrivateKeyDer, ServerName, pem::PemObject},
+};
+use weaver_config::Config;
+
+use super::CONNECTION_TIMEOUT;
+use crate::{AppError, request::open_ambient_file};
+
+/// A TCP stream wrapped in an established TLS client session.
+pub(crate) type TlsStream = StreamOwned<ClientConnection, TcpStream>;
+
+/// Builds the TLS client configuration for the daemon endpoint in `config`.
+///
+/// The daemon is verified against `--daemon-tls-ca` only; system roots are
+/// never trusted. A client identity is presented when both
+/// `--daemon-tls-cert` and `--daemon-tls-key` are set.
+pub(super) fn client_config(config: &Config) -> Result<Arc<ClientConfig>, AppError> {
+    let ca_path = config
+        .daemon_tls_ca()
+        .ok_or_else(|| AppError::MissingTlsCa {
+            endpoint: config.daemon_socket().to_string(),
+        })?;
+    let mut roots = RootCertStore::empty();
+    for certificate in load_certificates(ca_path.as_std_path())? {
+        roots.add(certificate).map_err(AppError::TlsConfig)?;
+    }
+
+    let builder =
+        ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(AppError::TlsConfig)?
+            .with_root_certificates(roots);
+
+    let client = match (config.daemon_tls_cert(), config.daemon_tls_key()) {
+        (Some(cert_path), Some(key_path)) => {
+            let certificates = load_certificates(cert_path.as_std_path())?;
+            let key = load_private_key(key_path.as_std_path())?;
+            builder
+                .with_client_auth_cert(certificates, key)
+                .map_err(AppError::TlsConfig)?
+        }
+        (None, None) => builder.with_no_client_auth(),
+        _ => return Err(AppError::IncompleteTlsIdentity),
+    };
+    Ok(Arc::new(client))
+}
+
+/// Completes a TLS handshake with `host` over `stream`.
+///
+/// The handshake is bounded by [`CONNECTION_TIMEOUT`]; the read timeout is
+/// cleared again before the stream is returned.
+pub(super) fn handshake(
+    client: Arc<ClientConfig>,
+    host: &str,
+    mut stream: TcpStream,
+) -> io::Result<TlsStream> {
+    let server_name = ServerName::try_from(host.to_owned())
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+    let mut connection = ClientConnection::new(client, server_name).map_err(io::Error::other)?;
+    stream.set_read_timeout(Some(CONNECTION_TIMEOUT))?;
+    while connection.is_handshaking() {
+        connection.complete_io(&mut stream)?;
+    }
+    stream.set_read_timeout(None)?;
+    Ok(StreamOwned::new(connection, stream))
+}
+
+fn read_pem(path: &Path) -> Result<Vec<u8>, AppError> {
+    let load_error = |source| AppError::LoadTlsMaterial {
+        path: path.to_path_buf(),
+        source,
+    };
+    let mut pem = Vec::new();
+    open_ambient_file(path)
+        .and_then(|mut file| file.read_to_end(&mut pem))
+        .map_err(load_error)?;
+    Ok(pem)
+}
+
+fn load_certificates(path: &Path) -> Result<Vec<CertificateDer<'static>>, AppError> {
+    let pem = read_pem(path)?;
+    let certificates = CertificateDer::pem_slice_iter(&pem)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| invalid_pem(path, error))?;
+    if certificates.is_empty() {
+        return Err(invalid_pem(path, "no certificates found"));
+    }
+    Ok(certificates)
+}
+
+fn load_private_key(path: &Path) -> Result<PrivateKeyDer<'static>, AppError> {
+    let pem = read_pem(path)?;
+    PrivateKeyDer::from_pem_slice(&pem).map_err(|error| invalid_pem(path, error))
+}
+
+fn invalid_pem(
+    path: &Path,
+    error: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+) -> AppError {
+    AppError::LoadTlsMaterial {
+        path: path.to_path_buf(),
+        source: io::Error::new(io::ErrorKind::InvalidData, error),
+    }
+}
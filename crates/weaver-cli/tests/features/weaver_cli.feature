@@ -30,6 +30,13 @@ Feature: Weaver CLI behaviour
     And stderr is ""
     And the CLI exits with code 0
 
+  Scenario: Probing negotiated capabilities with overrides
+    Given capability overrides force python call hierarchy and deny rust definitions
+    When the operator runs "--capabilities"
+    Then capabilities output is "capabilities_overrides.json"
+    And stderr is ""
+    And the CLI exits with code 0
+
   Scenario: Rejecting a missing operation
     When the operator runs "observe"
     Then the CLI fails
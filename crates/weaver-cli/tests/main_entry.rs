@@ -17,6 +17,15 @@ const EXPECTED_SHARED_CONFIG_HELP_FLAGS: &[&str] = &[
     "--log-format <FORMAT>",
     "--capability-overrides <DIRECTIVE>",
     "--locale <LOCALE>",
+    "--daemon-workers <COUNT>",
+    "--daemon-idle-timeout <SECONDS>",
+    "--daemon-connect-attempts <COUNT>",
+    "--daemon-empty-line-limit <COUNT>",
+    "--plugin-inline-limit <BYTES>",
+    "--daemon-tls-ca <PATH>",
+    "--daemon-tls-cert <PATH>",
+    "--daemon-tls-key <PATH>",
+    "--workspace-root <PATH>",
 ];
 
 #[test]
@@ -0,0 +1,135 @@
+//! Integration tests for `tcp+tls` daemon endpoints.
+//!
+//! Runs the `weaver` binary against a fake daemon that terminates TLS with a
+//! freshly generated self-signed certificate, confirming the request and
+//! response travel over the encrypted stream and that an untrusted certificate
+//! is rejected during the handshake.
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread,
+};
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::str::{contains, is_empty};
+use rustls::{
+    ServerConfig,
+    ServerConnection,
+    StreamOwned,
+    pki_types::{PrivateKeyDer, PrivatePkcs8KeyDer},
+};
+use tempfile::TempDir;
+
+const DAEMON_LINES: &[&str] = &[
+    r#"{"kind":"stream","stream":"stdout","data":"daemon says hello"}"#,
+    r#"{"kind":"exit","status":17}"#,
+];
+
+/// A self-signed certificate for `localhost` and `127.0.0.1`.
+struct TestCertificate {
+    certificate: rcgen::CertifiedKey<rcgen::KeyPair>,
+}
+
+impl TestCertificate {
+    fn generate() -> Self {
+        let names = vec![String::from("localhost"), String::from("127.0.0.1")];
+        let certificate =
+            rcgen::generate_simple_self_signed(names).expect("generate self-signed certificate");
+        Self { certificate }
+    }
+
+    fn write_pem(&self, dir: &TempDir, name: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        std::fs::write(&path, self.certificate.cert.pem()).expect("write certificate PEM");
+        path
+    }
+
+    fn server_config(&self) -> Arc<ServerConfig> {
+        let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(
+            self.certificate.signing_key.serialize_der(),
+        ));
+        let config =
+            ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+                .with_safe_default_protocol_versions()
+                .expect("protocol versions")
+                .with_no_client_auth()
+                .with_single_cert(vec![self.certificate.cert.der().clone()], key)
+                .expect("server certificate");
+        Arc::new(config)
+    }
+}
+
+/// Accepts one TLS connection, reads the request line, and replies with
+/// [`DAEMON_LINES`].
+///
+/// The thread returns the request it read, or `None` when the handshake
+/// failed.
+fn spawn_tls_daemon(config: Arc<ServerConfig>) -> (u16, thread::JoinHandle<Option<String>>) {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).expect("bind TLS daemon");
+    let port = listener.local_addr().expect("TLS daemon address").port();
+    let handle = thread::spawn(move || {
+        let (tcp, _) = listener.accept().expect("accept client");
+        let connection = ServerConnection::new(config).expect("server connection");
+        let mut stream = StreamOwned::new(connection, tcp);
+        let mut request = String::new();
+        BufReader::new(&mut stream).read_line(&mut request).ok()?;
+        for line in DAEMON_LINES {
+            writeln!(stream, "{line}").expect("write daemon line");
+        }
+        stream.conn.send_close_notify();
+        stream.flush().expect("flush daemon response");
+        Some(request)
+    });
+    (port, handle)
+}
+
+fn run_weaver(port: u16, ca_path: &Path) -> assert_cmd::assert::Assert {
+    let mut command = cargo_bin_cmd!("weaver");
+    command
+        .arg("--daemon-socket")
+        .arg(format!("tcp+tls://127.0.0.1:{port}"))
+        .arg("--daemon-tls-ca")
+        .arg(ca_path)
+        .args(["observe", "get-definition"]);
+    command.assert()
+}
+
+#[test]
+fn request_round_trips_over_tls() {
+    let dir = TempDir::new().expect("tempdir");
+    let certificate = TestCertificate::generate();
+    let ca_path = certificate.write_pem(&dir, "daemon-ca.pem");
+    let (port, daemon) = spawn_tls_daemon(certificate.server_config());
+
+    run_weaver(port, &ca_path)
+        .code(17)
+        .stdout(contains("daemon says hello"));
+
+    let request = daemon
+        .join()
+        .expect("TLS daemon thread")
+        .expect("daemon should complete the handshake");
+    assert!(
+        request.contains("\"get-definition\""),
+        "unexpected request: {request}"
+    );
+}
+
+#[test]
+fn untrusted_certificate_fails_the_handshake() {
+    let dir = TempDir::new().expect("tempdir");
+    let daemon_certificate = TestCertificate::generate();
+    let other_ca = TestCertificate::generate().write_pem(&dir, "other-ca.pem");
+    let (port, daemon) = spawn_tls_daemon(daemon_certificate.server_config());
+
+    run_weaver(port, &other_ca)
+        .failure()
+        .stdout(is_empty())
+        .stderr(contains("TLS handshake with daemon at tcp+tls://127.0.0.1"));
+
+    let request = daemon.join().expect("TLS daemon thread");
+    assert!(request.is_none(), "daemon must not receive a request");
+}
@@ -3,12 +3,27 @@
 //! The module exposes helpers for parsing capability directives supplied by
 //! configuration sources and constructing normalised lookup tables for the
 //! daemon and CLI.
-use std::{collections::BTreeMap, fmt, str::FromStr};
+use std::{collections::BTreeMap, fmt, str::FromStr, sync::Arc};
 
+use ortho_config::{OrthoError, OrthoResult};
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumString};
 use thiserror::Error;
 
+/// Language identifiers that accept capability overrides.
+pub const KNOWN_LANGUAGES: &[&str] = &["python", "rust", "typescript"];
+
+/// Capability keys that accept overrides, in dot-separated form.
+pub const KNOWN_CAPABILITIES: &[&str] = &[
+    "observe.call-hierarchy",
+    "observe.document-symbols",
+    "observe.find-references",
+    "observe.get-card-hover",
+    "observe.get-definition",
+    "observe.workspace-symbols",
+    "verify.diagnostics",
+];
+
 /// Directive applied to a capability during negotiation.
 #[derive(
     Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq, EnumString, Display,
@@ -45,6 +60,36 @@ pub enum CapabilityDirectiveParseError {
     InvalidDirective(String),
 }
 
+/// Errors produced when a parsed [`CapabilityDirective`] names a language or
+/// capability that no backend recognises.
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum CapabilityDirectiveValidationError {
+    /// The language identifier is not one of [`KNOWN_LANGUAGES`].
+    #[error(
+        "capability override '{directive}' names unknown language '{language}'; valid languages \
+         are: {}",
+        KNOWN_LANGUAGES.join(", ")
+    )]
+    UnknownLanguage {
+        /// The offending directive, as written.
+        directive: String,
+        /// The unrecognised language identifier.
+        language: String,
+    },
+    /// The capability identifier is not one of [`KNOWN_CAPABILITIES`].
+    #[error(
+        "capability override '{directive}' names unknown capability '{capability}'; valid \
+         capabilities are: {}",
+        KNOWN_CAPABILITIES.join(", ")
+    )]
+    UnknownCapability {
+        /// The offending directive, as written.
+        directive: String,
+        /// The unrecognised capability identifier.
+        capability: String,
+    },
+}
+
 /// Declarative override for a capability.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 pub struct CapabilityDirective {
@@ -70,6 +115,33 @@ impl CapabilityDirective {
             directive,
         }
     }
+
+    /// Checks that the language and capability are ones Weaver knows about.
+    ///
+    /// Comparison ignores case and surrounding whitespace, matching lookups
+    /// in [`CapabilityMatrix`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CapabilityDirectiveValidationError`] naming this directive
+    /// when either identifier is unknown.
+    pub fn validate(&self) -> Result<(), CapabilityDirectiveValidationError> {
+        let language = normalise_key(&self.language);
+        if !KNOWN_LANGUAGES.contains(&language.as_str()) {
+            return Err(CapabilityDirectiveValidationError::UnknownLanguage {
+                directive: self.to_string(),
+                language,
+            });
+        }
+        let capability = normalise_key(&self.capability);
+        if !KNOWN_CAPABILITIES.contains(&capability.as_str()) {
+            return Err(CapabilityDirectiveValidationError::UnknownCapability {
+                directive: self.to_string(),
+                capability,
+            });
+        }
+        Ok(())
+    }
 }
 
 impl fmt::Display for CapabilityDirective {
@@ -181,6 +253,20 @@ pub fn deduplicate_directives(directives: &mut Vec<CapabilityDirective>) {
     *directives = merged.into_values().collect();
 }
 
+/// Validates every directive, reporting the first unknown entry as a
+/// configuration validation error against `capability_overrides`.
+pub(crate) fn validate_directives(directives: &[CapabilityDirective]) -> OrthoResult<()> {
+    directives
+        .iter()
+        .try_for_each(CapabilityDirective::validate)
+        .map_err(|error| {
+            Arc::new(OrthoError::Validation {
+                key: String::from("capability_overrides"),
+                message: error.to_string(),
+            })
+        })
+}
+
 fn normalise_key(key: &str) -> String { key.trim().to_lowercase() }
 
 #[cfg(test)]
@@ -225,6 +311,63 @@ mod tests {
         assert_eq!(directive.directive, CapabilityOverride::Deny);
     }
 
+    #[test]
+    fn validates_known_directives() {
+        let directive: CapabilityDirective = " Python : Observe.Call-Hierarchy = force "
+            .parse()
+            .expect("valid directive");
+
+        assert_eq!(directive.validate(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_misspelt_capability_listing_valid_ids() {
+        let directive: CapabilityDirective = "rust:observe.get-defintion=deny"
+            .parse()
+            .expect("syntactically valid directive");
+
+        let error = directive.validate().expect_err("unknown capability");
+
+        assert_eq!(
+            error,
+            CapabilityDirectiveValidationError::UnknownCapability {
+                directive: String::from("rust:observe.get-defintion=deny"),
+                capability: String::from("observe.get-defintion"),
+            }
+        );
+        let message = error.to_string();
+        assert!(
+            message.contains("'rust:observe.get-defintion=deny'"),
+            "{message}"
+        );
+        for capability in KNOWN_CAPABILITIES {
+            assert!(message.contains(capability), "{message} lacks {capability}");
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_language_listing_valid_languages() {
+        let directive: CapabilityDirective = "cobol:observe.get-definition=force"
+            .parse()
+            .expect("syntactically valid directive");
+
+        let error = directive.validate().expect_err("unknown language");
+
+        assert_eq!(
+            error,
+            CapabilityDirectiveValidationError::UnknownLanguage {
+                directive: String::from("cobol:observe.get-definition=force"),
+                language: String::from("cobol"),
+            }
+        );
+        assert!(
+            error
+                .to_string()
+                .ends_with("valid languages are: python, rust, typescript"),
+            "{error}"
+        );
+    }
+
     #[test]
     fn rejects_empty_language_or_capability() {
         let empty_language = ":rename=force".parse::<CapabilityDirective>();
@@ -1,7 +1,9 @@
 //! Houses the shared configuration defaults consumed across the Weaver
 //! binaries.
 //!
-//! The functions exported here define the default log filter and format while
+//! The functions exported here define the default log filter, log format,
+//! daemon worker pool size, idle timeout, connection retry budget, empty
+//! response line limit, and plugin inline content limit while
 //! discovering the daemon socket endpoint in a platform-aware fashion. On Unix
 //! targets the socket prefers the XDG runtime directory and, when that location
 //! is unavailable, falls back to a user-namespaced directory under the system
@@ -20,6 +22,22 @@ use crate::socket::SocketEndpoint;
 /// Default TCP port used when Unix domain sockets are not available.
 pub const DEFAULT_TCP_PORT: u16 = 9779;
 
+/// Default number of worker threads the daemon uses to serve connections.
+pub const DEFAULT_DAEMON_WORKERS: usize = 8;
+
+/// Default daemon idle timeout in seconds; zero keeps the daemon running.
+pub const DEFAULT_DAEMON_IDLE_TIMEOUT_SECS: u64 = 0;
+
+/// Default number of attempts the CLI makes to reach a daemon that is starting.
+pub const DEFAULT_DAEMON_CONNECT_ATTEMPTS: u32 = 5;
+
+/// Default number of consecutive empty daemon lines the CLI tolerates.
+pub const DEFAULT_DAEMON_EMPTY_LINE_LIMIT: usize = 10;
+
+/// Default plugin inline content limit in bytes; zero always sends files
+/// inline.
+pub const DEFAULT_PLUGIN_INLINE_LIMIT_BYTES: u64 = 0;
+
 /// Default log filter expression used by the binaries.
 pub const DEFAULT_LOG_FILTER: &str = "info";
 
@@ -32,6 +50,21 @@ pub fn default_log_filter_string() -> String { DEFAULT_LOG_FILTER.to_string() }
 /// Default logging format for the binaries.
 pub fn default_log_format() -> crate::logging::LogFormat { crate::logging::LogFormat::Json }
 
+/// Default number of daemon worker threads.
+pub fn default_daemon_workers() -> usize { DEFAULT_DAEMON_WORKERS }
+
+/// Default daemon idle timeout in seconds, which disables idle shutdown.
+pub fn default_daemon_idle_timeout() -> u64 { DEFAULT_DAEMON_IDLE_TIMEOUT_SECS }
+
+/// Default number of daemon connection attempts made by the CLI.
+pub fn default_daemon_connect_attempts() -> u32 { DEFAULT_DAEMON_CONNECT_ATTEMPTS }
+
+/// Default consecutive empty line limit applied to daemon responses.
+pub fn default_daemon_empty_line_limit() -> usize { DEFAULT_DAEMON_EMPTY_LINE_LIMIT }
+
+/// Default plugin inline content limit, which keeps every file inline.
+pub fn default_plugin_inline_limit() -> u64 { DEFAULT_PLUGIN_INLINE_LIMIT_BYTES }
+
 /// Computes the default socket endpoint for the daemon.
 pub fn default_socket_endpoint() -> SocketEndpoint { default_socket_endpoint_inner() }
 
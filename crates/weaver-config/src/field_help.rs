@@ -0,0 +1,83 @@
+//! Canonical short help text for configuration fields.
+//!
+//! The CLI renders these strings when it folds the shared configuration flags
+//! into its own help output, keyed by the help IDs `OrthoConfig` records for
+//! each field.
+
+const CONFIG_FIELD_HELP: &[(&str, &str)] = &[
+    (
+        "weaver.fields.daemon_socket.help",
+        "Overrides the daemon transport endpoint",
+    ),
+    ("weaver.fields.log_filter.help", "Sets the tracing filter"),
+    (
+        "weaver.fields.log_format.help",
+        "Selects the structured log output format",
+    ),
+    (
+        "weaver.fields.capability_overrides.help",
+        "Appends a language capability override directive",
+    ),
+    (
+        "weaver.fields.locale.help",
+        "Selects the operator-facing locale",
+    ),
+    (
+        "weaver.fields.daemon_workers.help",
+        "Sets how many requests the daemon serves concurrently",
+    ),
+    (
+        "weaver.fields.daemon_idle_timeout.help",
+        "Stops the daemon after this many idle seconds (0 disables)",
+    ),
+    (
+        "weaver.fields.daemon_connect_attempts.help",
+        "Sets how many times the CLI tries to reach a starting daemon",
+    ),
+    (
+        "weaver.fields.daemon_empty_line_limit.help",
+        "Sets how many consecutive empty daemon lines the CLI tolerates",
+    ),
+    (
+        "weaver.fields.plugin_inline_limit.help",
+        "Passes plugin files over this many bytes by reference (0 disables)",
+    ),
+    (
+        "weaver.fields.daemon_tls_ca.help",
+        "Trusts this CA certificate (PEM) for TLS daemon endpoints",
+    ),
+    (
+        "weaver.fields.daemon_tls_cert.help",
+        "Presents this client certificate (PEM) to TLS daemon endpoints",
+    ),
+    (
+        "weaver.fields.daemon_tls_key.help",
+        "Signs TLS client authentication with this private key (PEM)",
+    ),
+    (
+        "weaver.fields.workspace_root.help",
+        "Resolves daemon request paths against this directory",
+    ),
+];
+const DEFAULT_CONFIG_FIELD_HELP: &str = "Overrides a shared configuration value";
+
+/// Returns the canonical short help text for a configuration field help ID.
+///
+/// Use this helper when rendering `Config::get_doc_metadata()` into
+/// user-facing help surfaces.
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(
+///     weaver_config::config_field_help("weaver.fields.locale.help"),
+///     "Selects the operator-facing locale"
+/// );
+/// ```
+#[must_use]
+pub fn config_field_help(help_id: &str) -> &'static str {
+    CONFIG_FIELD_HELP
+        .iter()
+        .find_map(|(candidate, help)| (*candidate == help_id).then_some(*help))
+        .unwrap_or(DEFAULT_CONFIG_FIELD_HELP)
+}
@@ -3,12 +3,14 @@
 //! This crate exposes the [`Config`] structure consumed by `weaver` and
 //! `weaverd`. Configuration values are layered using [`ortho_config`], merging
 //! configuration files, environment variables, and command-line arguments in
-//! increasing precedence. The schema focuses on four core concerns:
+//! increasing precedence. The schema focuses on six core concerns:
 //!
-//! - Transport sockets used by the daemon and client.
+//! - Transport sockets used by the daemon and client, with optional client TLS.
 //! - Structured logging defaults.
 //! - User-defined capability overrides.
 //! - Locale identifier for internationalization surfaces.
+//! - Daemon worker pool sizing, idle shutdown, connection retries, and response stream limits.
+//! - The workspace root the daemon resolves request paths against.
 //!
 //! ```rust,no_run
 //! use weaver_config::Config;
@@ -26,26 +28,44 @@
 
 mod capability;
 mod defaults;
+mod field_help;
 mod locale;
 mod logging;
 mod runtime;
 mod socket;
 
-use capability::deduplicate_directives;
+use std::time::Duration;
+
+use camino::{Utf8Path, Utf8PathBuf};
 pub use capability::{
     CapabilityDirective,
     CapabilityDirectiveParseError,
+    CapabilityDirectiveValidationError,
     CapabilityMatrix,
     CapabilityOverride,
+    KNOWN_CAPABILITIES,
+    KNOWN_LANGUAGES,
     LanguageCapabilities,
 };
+use capability::{deduplicate_directives, validate_directives};
 pub use defaults::{
+    DEFAULT_DAEMON_CONNECT_ATTEMPTS,
+    DEFAULT_DAEMON_EMPTY_LINE_LIMIT,
+    DEFAULT_DAEMON_IDLE_TIMEOUT_SECS,
+    DEFAULT_DAEMON_WORKERS,
     DEFAULT_LOG_FILTER,
+    DEFAULT_PLUGIN_INLINE_LIMIT_BYTES,
     DEFAULT_TCP_PORT,
+    default_daemon_connect_attempts,
+    default_daemon_empty_line_limit,
+    default_daemon_idle_timeout,
+    default_daemon_workers,
     default_log_filter,
     default_log_format,
+    default_plugin_inline_limit,
     default_socket_endpoint,
 };
+pub use field_help::config_field_help;
 pub use locale::{Locale, LocaleParseError};
 pub use logging::{LogFormat, LogFormatParseError};
 use ortho_config::OrthoConfig;
@@ -55,48 +75,6 @@ pub use socket::{SocketEndpoint, SocketParseError, SocketPreparationError};
 
 fn default_locale() -> Locale { Locale::en_us() }
 
-const CONFIG_FIELD_HELP: &[(&str, &str)] = &[
-    (
-        "weaver.fields.daemon_socket.help",
-        "Overrides the daemon transport endpoint",
-    ),
-    ("weaver.fields.log_filter.help", "Sets the tracing filter"),
-    (
-        "weaver.fields.log_format.help",
-        "Selects the structured log output format",
-    ),
-    (
-        "weaver.fields.capability_overrides.help",
-        "Appends a language capability override directive",
-    ),
-    (
-        "weaver.fields.locale.help",
-        "Selects the operator-facing locale",
-    ),
-];
-const DEFAULT_CONFIG_FIELD_HELP: &str = "Overrides a shared configuration value";
-
-/// Returns the canonical short help text for a configuration field help ID.
-///
-/// Use this helper when rendering `Config::get_doc_metadata()` into
-/// user-facing help surfaces.
-///
-/// # Examples
-///
-/// ```rust
-/// assert_eq!(
-///     weaver_config::config_field_help("weaver.fields.locale.help"),
-///     "Selects the operator-facing locale"
-/// );
-/// ```
-#[must_use]
-pub fn config_field_help(help_id: &str) -> &'static str {
-    CONFIG_FIELD_HELP
-        .iter()
-        .find_map(|(candidate, help)| (*candidate == help_id).then_some(*help))
-        .unwrap_or(DEFAULT_CONFIG_FIELD_HELP)
-}
-
 /// Complete configuration merged from defaults, files, environment, and CLI.
 #[derive(Debug, Clone, Deserialize, Serialize, OrthoConfig)]
 #[serde(default)]
@@ -152,11 +130,95 @@ pub struct Config {
         cli(value_name = "LOCALE")
     )]
     pub locale: Locale,
+    /// Number of daemon worker threads serving client connections.
+    #[serde(default = "default_daemon_workers")]
+    #[ortho_config(
+        default = crate::default_daemon_workers(),
+        cli_long = "daemon-workers",
+        cli_short = 'w',
+        cli(value_name = "COUNT")
+    )]
+    pub daemon_workers: usize,
+    /// Seconds without client connections before the daemon shuts down.
+    ///
+    /// Zero disables the idle timeout.
+    #[serde(default = "default_daemon_idle_timeout")]
+    #[ortho_config(
+        default = crate::default_daemon_idle_timeout(),
+        cli_long = "daemon-idle-timeout",
+        cli_short = 'i',
+        cli(value_name = "SECONDS")
+    )]
+    pub daemon_idle_timeout: u64,
+    /// Attempts the CLI makes to connect before treating the daemon as absent.
+    ///
+    /// Retries back off exponentially and only follow errors showing that
+    /// nothing is listening on the socket yet.
+    #[serde(default = "default_daemon_connect_attempts")]
+    #[ortho_config(
+        default = crate::default_daemon_connect_attempts(),
+        cli_long = "daemon-connect-attempts",
+        cli_short = 'a',
+        cli(value_name = "COUNT")
+    )]
+    pub daemon_connect_attempts: u32,
+    /// Consecutive empty lines the CLI accepts from the daemon before it
+    /// abandons the response stream.
+    #[serde(default = "default_daemon_empty_line_limit")]
+    #[ortho_config(
+        default = crate::default_daemon_empty_line_limit(),
+        cli_long = "daemon-empty-line-limit",
+        cli_short = 'e',
+        cli(value_name = "COUNT")
+    )]
+    pub daemon_empty_line_limit: usize,
+    /// Size in bytes above which file content is passed to plugins by
+    /// reference instead of inline in the request line.
+    ///
+    /// Zero keeps every file inline.
+    #[serde(default = "default_plugin_inline_limit")]
+    #[ortho_config(
+        default = crate::default_plugin_inline_limit(),
+        cli_long = "plugin-inline-limit",
+        cli_short = 'p',
+        cli(value_name = "BYTES")
+    )]
+    pub plugin_inline_limit: u64,
+    /// CA certificate bundle used to verify `tcp+tls` daemon endpoints.
+    #[serde(default)]
+    #[ortho_config(cli_long = "daemon-tls-ca", cli_short = 't', cli(value_name = "PATH"))]
+    pub daemon_tls_ca: Option<Utf8PathBuf>,
+    /// Client certificate chain presented to `tcp+tls` daemon endpoints.
+    ///
+    /// Must be set together with [`Config::daemon_tls_key`].
+    #[serde(default)]
+    #[ortho_config(
+        cli_long = "daemon-tls-cert",
+        cli_short = 'T',
+        cli(value_name = "PATH")
+    )]
+    pub daemon_tls_cert: Option<Utf8PathBuf>,
+    /// Private key matching [`Config::daemon_tls_cert`].
+    #[serde(default)]
+    #[ortho_config(cli_long = "daemon-tls-key", cli_short = 'k', cli(value_name = "PATH"))]
+    pub daemon_tls_key: Option<Utf8PathBuf>,
+    /// Workspace directory the daemon resolves request paths against.
+    ///
+    /// Must be an absolute path to an existing directory. When unset, the
+    /// daemon uses the working directory it was started from.
+    #[serde(default)]
+    #[ortho_config(cli_long = "workspace-root", cli_short = 'W', cli(value_name = "PATH"))]
+    pub workspace_root: Option<Utf8PathBuf>,
 }
 
 impl Config {
     /// Loads configuration from defaults, discovery, environment, and CLI.
     ///
+    /// # Errors
+    ///
+    /// Returns an error if any source fails to load or merge, or if a
+    /// capability override names an unknown language or capability.
+    ///
     /// # Panics
     ///
     /// This wrapper does not introduce its own panic paths, but the
@@ -165,11 +227,17 @@ impl Config {
     pub fn load() -> ortho_config::OrthoResult<Self> {
         let mut config = <Self as OrthoConfig>::load()?;
         config.normalise_capability_overrides();
+        validate_directives(&config.capability_overrides)?;
         Ok(config)
     }
 
     /// Loads configuration using a custom iterator of CLI arguments.
     ///
+    /// # Errors
+    ///
+    /// Returns an error if any source fails to load or merge, or if a
+    /// capability override names an unknown language or capability.
+    ///
     /// # Panics
     ///
     /// This wrapper does not introduce its own panic paths, but the
@@ -182,6 +250,7 @@ impl Config {
     {
         let mut config = <Self as OrthoConfig>::load_from_iter(iter)?;
         config.normalise_capability_overrides();
+        validate_directives(&config.capability_overrides)?;
         Ok(config)
     }
 
@@ -207,6 +276,63 @@ impl Config {
     #[must_use]
     pub fn locale(&self) -> &Locale { &self.locale }
 
+    /// Accessor for the daemon worker pool size.
+    ///
+    /// A configured value of zero is treated as one so the daemon always has
+    /// a worker available to serve requests.
+    #[must_use]
+    pub fn daemon_workers(&self) -> usize { self.daemon_workers.max(1) }
+
+    /// Accessor for the daemon idle timeout.
+    ///
+    /// Returns `None` when the timeout is disabled by a value of zero.
+    #[must_use]
+    pub fn daemon_idle_timeout(&self) -> Option<Duration> {
+        (self.daemon_idle_timeout > 0).then_some(Duration::from_secs(self.daemon_idle_timeout))
+    }
+
+    /// Accessor for the daemon connection attempt budget.
+    ///
+    /// A configured value of zero is treated as one so the CLI always tries
+    /// to connect at least once.
+    #[must_use]
+    pub fn daemon_connect_attempts(&self) -> u32 { self.daemon_connect_attempts.max(1) }
+
+    /// Accessor for the consecutive empty line limit on daemon responses.
+    ///
+    /// A configured value of zero is treated as one so a single stray blank
+    /// line still aborts rather than disabling the guard.
+    #[must_use]
+    pub fn daemon_empty_line_limit(&self) -> usize { self.daemon_empty_line_limit.max(1) }
+
+    /// Accessor for the plugin inline content limit in bytes.
+    ///
+    /// Returns `None` when the limit is disabled by a value of zero, in which
+    /// case every file is sent inline.
+    #[must_use]
+    pub fn plugin_inline_limit(&self) -> Option<u64> {
+        (self.plugin_inline_limit > 0).then_some(self.plugin_inline_limit)
+    }
+
+    /// Accessor for the CA certificate used to verify TLS daemon endpoints.
+    #[must_use]
+    pub fn daemon_tls_ca(&self) -> Option<&Utf8Path> { self.daemon_tls_ca.as_deref() }
+
+    /// Accessor for the client certificate presented to TLS daemon endpoints.
+    #[must_use]
+    pub fn daemon_tls_cert(&self) -> Option<&Utf8Path> { self.daemon_tls_cert.as_deref() }
+
+    /// Accessor for the private key matching the TLS client certificate.
+    #[must_use]
+    pub fn daemon_tls_key(&self) -> Option<&Utf8Path> { self.daemon_tls_key.as_deref() }
+
+    /// Accessor for the configured daemon workspace root.
+    ///
+    /// Returns `None` when the daemon should fall back to its working
+    /// directory.
+    #[must_use]
+    pub fn workspace_root(&self) -> Option<&Utf8Path> { self.workspace_root.as_deref() }
+
     fn normalise_capability_overrides(&mut self) {
         deduplicate_directives(&mut self.capability_overrides);
     }
@@ -220,6 +346,15 @@ impl Default for Config {
             log_format: default_log_format(),
             capability_overrides: Vec::new(),
             locale: default_locale(),
+            daemon_workers: default_daemon_workers(),
+            daemon_idle_timeout: default_daemon_idle_timeout(),
+            daemon_connect_attempts: default_daemon_connect_attempts(),
+            daemon_empty_line_limit: default_daemon_empty_line_limit(),
+            plugin_inline_limit: default_plugin_inline_limit(),
+            daemon_tls_ca: None,
+            daemon_tls_cert: None,
+            daemon_tls_key: None,
+            workspace_root: None,
         };
         config.normalise_capability_overrides();
         config
@@ -13,6 +13,9 @@ use url::Url;
 
 mod preparation;
 
+/// URL scheme for TCP endpoints secured with TLS.
+const TCP_TLS_SCHEME: &str = "tcp+tls";
+
 pub use preparation::SocketPreparationError;
 
 /// Declarative configuration for daemon sockets.
@@ -21,8 +24,14 @@ pub use preparation::SocketPreparationError;
 pub enum SocketEndpoint {
     /// Unix domain socket endpoint.
     Unix { path: Utf8PathBuf },
-    /// TCP socket endpoint.
-    Tcp { host: String, port: u16 },
+    /// TCP socket endpoint, optionally secured with TLS.
+    Tcp {
+        host: String,
+        port: u16,
+        /// Whether clients negotiate TLS after connecting.
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        tls: bool,
+    },
 }
 
 impl SocketEndpoint {
@@ -30,15 +39,32 @@ impl SocketEndpoint {
     #[must_use]
     pub fn unix(path: impl Into<Utf8PathBuf>) -> Self { Self::Unix { path: path.into() } }
 
-    /// Builds a TCP socket endpoint.
+    /// Builds a plaintext TCP socket endpoint.
     #[must_use]
     pub fn tcp(host: impl Into<String>, port: u16) -> Self {
         Self::Tcp {
             host: host.into(),
             port,
+            tls: false,
         }
     }
 
+    /// Builds a TCP socket endpoint that clients secure with TLS.
+    #[must_use]
+    pub fn tcp_tls(host: impl Into<String>, port: u16) -> Self {
+        Self::Tcp {
+            host: host.into(),
+            port,
+            tls: true,
+        }
+    }
+
+    /// Returns `true` when clients must negotiate TLS with this endpoint.
+    ///
+    /// Unix domain sockets are always plaintext.
+    #[must_use]
+    pub const fn is_tls(&self) -> bool { matches!(self, Self::Tcp { tls: true, .. }) }
+
     /// Returns the Unix socket path when the endpoint uses the Unix transport.
     #[must_use]
     pub fn unix_path(&self) -> Option<&Utf8Path> {
@@ -68,11 +94,12 @@ impl fmt::Display for SocketEndpoint {
                 url.set_path(path.as_str());
                 write!(formatter, "{url}")
             }
-            Self::Tcp { host, port } => {
+            Self::Tcp { host, port, tls } => {
+                let scheme = if *tls { TCP_TLS_SCHEME } else { "tcp" };
                 if host.contains(':') {
-                    write!(formatter, "tcp://[{host}]:{port}")
+                    write!(formatter, "{scheme}://[{host}]:{port}")
                 } else {
-                    write!(formatter, "tcp://{host}:{port}")
+                    write!(formatter, "{scheme}://{host}:{port}")
                 }
             }
         }
@@ -86,7 +113,8 @@ impl FromStr for SocketEndpoint {
         let url = Url::parse(input)?;
         match url.scheme() {
             "unix" => parse_unix_endpoint(&url, input),
-            "tcp" => parse_tcp_endpoint(&url, input),
+            "tcp" => parse_tcp_endpoint(&url, input, false),
+            TCP_TLS_SCHEME => parse_tcp_endpoint(&url, input, true),
             other => Err(SocketParseError::UnsupportedScheme(other.to_string())),
         }
     }
@@ -108,7 +136,11 @@ fn parse_unix_endpoint(url: &Url, input: &str) -> Result<SocketEndpoint, SocketP
     Ok(SocketEndpoint::unix(decoded_path))
 }
 
-fn parse_tcp_endpoint(url: &Url, input: &str) -> Result<SocketEndpoint, SocketParseError> {
+fn parse_tcp_endpoint(
+    url: &Url,
+    input: &str,
+    tls: bool,
+) -> Result<SocketEndpoint, SocketParseError> {
     if tcp_url_has_invalid_components(url) {
         return Err(SocketParseError::InvalidTcpUrl(input.to_string()));
     }
@@ -119,7 +151,7 @@ fn parse_tcp_endpoint(url: &Url, input: &str) -> Result<SocketEndpoint, SocketPa
     let port = url
         .port()
         .ok_or_else(|| SocketParseError::MissingPort(input.to_string()))?;
-    Ok(SocketEndpoint::tcp(host, port))
+    Ok(SocketEndpoint::Tcp { host, port, tls })
 }
 
 fn tcp_url_has_invalid_components(url: &Url) -> bool {
@@ -240,6 +272,38 @@ mod tests {
         assert!(matches!(result, Err(SocketParseError::InvalidTcpUrl(_))));
     }
 
+    #[test]
+    fn parse_tls_socket_sets_flag() {
+        let endpoint: SocketEndpoint = "tcp+tls://daemon.example:9443"
+            .parse()
+            .expect("valid TLS socket URL");
+
+        assert_eq!(endpoint, SocketEndpoint::tcp_tls("daemon.example", 9443));
+        assert!(endpoint.is_tls());
+        assert_eq!(endpoint.to_string(), "tcp+tls://daemon.example:9443");
+    }
+
+    #[test]
+    fn plaintext_endpoints_are_not_tls() {
+        assert!(!SocketEndpoint::tcp("127.0.0.1", 9000).is_tls());
+        assert!(!SocketEndpoint::unix("/tmp/weaver.sock").is_tls());
+    }
+
+    #[test]
+    fn tls_flag_is_omitted_when_disabled() {
+        let plain = serde_json::to_value(SocketEndpoint::tcp("127.0.0.1", 9000)).expect("json");
+        assert!(plain.get("tls").is_none(), "unexpected tls field: {plain}");
+
+        let secured: SocketEndpoint = serde_json::from_value(serde_json::json!({
+            "transport": "tcp",
+            "host": "127.0.0.1",
+            "port": 9000,
+            "tls": true,
+        }))
+        .expect("deserialise TLS endpoint");
+        assert!(secured.is_tls());
+    }
+
     #[test]
     fn display_tcp_ipv6_roundtrip() {
         let endpoint: SocketEndpoint = "tcp://[::1]:9000"
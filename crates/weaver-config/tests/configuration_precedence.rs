@@ -12,6 +12,9 @@ use tempfile::TempDir;
 use weaver_config::{
     Config,
     SocketEndpoint,
+    default_daemon_connect_attempts,
+    default_daemon_empty_line_limit,
+    default_daemon_workers,
     default_log_filter,
     default_log_format,
     default_socket_endpoint,
@@ -88,7 +91,7 @@ impl Harness {
                     path
                 )
             }
-            SocketEndpoint::Tcp { host, port } => format!(
+            SocketEndpoint::Tcp { host, port, .. } => format!(
                 "daemon_socket = {{ transport = \"tcp\", host = \"{}\", port = {} }}\n",
                 host, port
             ),
@@ -245,6 +248,18 @@ fn then_defaults_applied(harness: &Harness) {
     assert_eq!(config.log_filter(), default_log_filter());
     assert_eq!(config.log_format(), default_log_format());
     assert_eq!(config.locale().to_string(), "en-US");
+    assert_eq!(config.daemon_workers(), default_daemon_workers());
+    assert_eq!(config.daemon_idle_timeout(), None);
+    assert_eq!(
+        config.daemon_connect_attempts(),
+        default_daemon_connect_attempts()
+    );
+    assert_eq!(
+        config.daemon_empty_line_limit(),
+        default_daemon_empty_line_limit()
+    );
+    assert_eq!(config.plugin_inline_limit(), None);
+    assert_eq!(config.workspace_root(), None);
 
     let matrix = config.capability_matrix();
     assert!(
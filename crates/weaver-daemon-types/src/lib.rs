@@ -18,6 +18,24 @@ use serde::Deserialize;
 /// same transport budget.
 pub const JSONL_REQUEST_MAX_LINE_BYTES: usize = 1024 * 1024;
 
+/// Control line a client writes after its request to abandon it.
+///
+/// The daemon stops working on the request at its next cancellation
+/// checkpoint and sends no further response. Closing the connection has the
+/// same effect, so clients that exit abruptly need not send it.
+pub const CANCEL_REQUEST_LINE: &str = "{\"kind\":\"cancel\"}\n";
+
+/// Control messages a client may send after its request line.
+///
+/// The daemon deserialises each further line with this type and ignores
+/// lines it does not recognise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ClientControl {
+    /// Abandons the in-flight request.
+    Cancel,
+}
+
 /// Wire-protocol discriminator for unknown-operation error payloads.
 ///
 /// This constant is part of the JSONL protocol contract between the daemon
@@ -0,0 +1,179 @@
+//! Cycle detection for call graphs.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    edge::CallEdge,
+    graph::CallGraph,
+    node::{CallNode, NodeId},
+};
+
+impl CallGraph {
+    /// Returns the call cycles in the graph.
+    ///
+    /// Each cycle is a strongly connected component that either holds more
+    /// than one node or consists of a single node that calls itself. The IDs
+    /// within a component are sorted, and components are sorted by their
+    /// first ID, so the result is deterministic.
+    ///
+    /// Components are found with Tarjan's algorithm over the outgoing edge
+    /// index, using an explicit stack so deep call chains cannot overflow the
+    /// thread's stack.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weaver_graph::{CallEdge, CallGraph, CallNode, EdgeSource, Position, SymbolKind};
+    ///
+    /// let node = CallNode::new(
+    ///     "recurse",
+    ///     SymbolKind::Function,
+    ///     "/src/lib.rs",
+    ///     Position::new(3, 0),
+    /// );
+    /// let id = node.id().clone();
+    ///
+    /// let mut graph = CallGraph::new();
+    /// graph.add_node(node);
+    /// graph.add_edge(CallEdge::new(id.clone(), id.clone(), EdgeSource::Lsp));
+    ///
+    /// assert_eq!(graph.find_cycles(), vec![vec![id]]);
+    /// ```
+    #[must_use]
+    pub fn find_cycles(&self) -> Vec<Vec<NodeId>> {
+        let mut roots: Vec<&NodeId> = self.nodes().map(CallNode::id).collect();
+        roots.sort_by(|left, right| left.as_str().cmp(right.as_str()));
+
+        let mut search = Tarjan::new(self);
+        for root in roots {
+            if !search.index_of.contains_key(root) {
+                search.visit(root);
+            }
+        }
+
+        let mut cycles: Vec<Vec<NodeId>> = search
+            .components
+            .into_iter()
+            .filter(|component| self.is_cycle(component))
+            .map(|component| {
+                let mut ids: Vec<NodeId> = component.into_iter().cloned().collect();
+                ids.sort_by(|left, right| left.as_str().cmp(right.as_str()));
+                ids
+            })
+            .collect();
+        cycles.sort_by(|left, right| first_id(left).cmp(&first_id(right)));
+        cycles
+    }
+
+    /// Returns whether a strongly connected component forms a cycle.
+    fn is_cycle(&self, component: &[&NodeId]) -> bool {
+        match component {
+            [node] => self.outgoing_edges(node).any(|edge| edge.callee() == *node),
+            _ => component.len() > 1,
+        }
+    }
+}
+
+/// Returns the first ID of a sorted component.
+fn first_id(ids: &[NodeId]) -> Option<&str> { ids.first().map(NodeId::as_str) }
+
+/// A node being explored, with the callees not yet visited from it.
+struct Frame<'g> {
+    node: &'g NodeId,
+    callees: std::vec::IntoIter<&'g NodeId>,
+}
+
+/// State for Tarjan's strongly connected components algorithm.
+struct Tarjan<'g> {
+    graph: &'g CallGraph,
+    next_index: usize,
+    index_of: HashMap<&'g NodeId, usize>,
+    lowlink: HashMap<&'g NodeId, usize>,
+    stack: Vec<&'g NodeId>,
+    on_stack: HashSet<&'g NodeId>,
+    components: Vec<Vec<&'g NodeId>>,
+}
+
+impl<'g> Tarjan<'g> {
+    fn new(graph: &'g CallGraph) -> Self {
+        Self {
+            graph,
+            next_index: 0,
+            index_of: HashMap::new(),
+            lowlink: HashMap::new(),
+            stack: Vec::new(),
+            on_stack: HashSet::new(),
+            components: Vec::new(),
+        }
+    }
+
+    /// Explores every node reachable from `root` depth-first.
+    fn visit(&mut self, root: &'g NodeId) {
+        let mut frames = vec![self.enter(root)];
+        while let Some(frame) = frames.last_mut() {
+            let node = frame.node;
+            let Some(callee) = frame.callees.next() else {
+                frames.pop();
+                self.leave(node, frames.last().map(|parent| parent.node));
+                continue;
+            };
+            match self.index_of.get(callee).copied() {
+                None => frames.push(self.enter(callee)),
+                Some(callee_index) if self.on_stack.contains(callee) => {
+                    self.lower(node, callee_index);
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    /// Assigns `node` its discovery index and pushes it onto the stack.
+    fn enter(&mut self, node: &'g NodeId) -> Frame<'g> {
+        self.index_of.insert(node, self.next_index);
+        self.lowlink.insert(node, self.next_index);
+        self.next_index += 1;
+        self.stack.push(node);
+        self.on_stack.insert(node);
+
+        let mut callees: Vec<&'g NodeId> = self
+            .graph
+            .outgoing_edges(node)
+            .map(CallEdge::callee)
+            .collect();
+        callees.sort_by(|left, right| left.as_str().cmp(right.as_str()));
+        callees.dedup();
+        Frame {
+            node,
+            callees: callees.into_iter(),
+        }
+    }
+
+    /// Lowers the lowlink of `node` to `candidate` if it is smaller.
+    fn lower(&mut self, node: &'g NodeId, candidate: usize) {
+        if let Some(lowlink) = self.lowlink.get_mut(node) {
+            *lowlink = (*lowlink).min(candidate);
+        }
+    }
+
+    /// Finishes `node`, propagating its lowlink to `parent` and popping its
+    /// component when `node` is the component's root.
+    fn leave(&mut self, node: &'g NodeId, parent: Option<&'g NodeId>) {
+        let lowlink = self.lowlink.get(node).copied().unwrap_or_default();
+        if let Some(parent_node) = parent {
+            self.lower(parent_node, lowlink);
+        }
+        if self.index_of.get(node) != Some(&lowlink) {
+            return;
+        }
+
+        let mut component = Vec::new();
+        while let Some(member) = self.stack.pop() {
+            self.on_stack.remove(member);
+            component.push(member);
+            if member == node {
+                break;
+            }
+        }
+        self.components.push(component);
+    }
+}
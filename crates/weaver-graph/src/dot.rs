@@ -0,0 +1,91 @@
+//! Graphviz DOT export for call graphs.
+
+use std::fmt;
+
+use crate::{graph::CallGraph, node::CallNode};
+
+impl CallGraph {
+    /// Renders the graph as a Graphviz `digraph`.
+    ///
+    /// Each node is emitted with its ID as the DOT identifier and its
+    /// qualified name as the label. Each edge is labelled with its
+    /// [`EdgeSource`](crate::EdgeSource). Nodes are sorted by ID and edges
+    /// keep their insertion order, so equal graphs render identically.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weaver_graph::{CallEdge, CallGraph, CallNode, EdgeSource, Position, SymbolKind};
+    ///
+    /// let caller = CallNode::new(
+    ///     "main",
+    ///     SymbolKind::Function,
+    ///     "/src/main.rs",
+    ///     Position::new(0, 0),
+    /// );
+    /// let callee = CallNode::new(
+    ///     "run",
+    ///     SymbolKind::Function,
+    ///     "/src/main.rs",
+    ///     Position::new(4, 0),
+    /// );
+    /// let edge = CallEdge::new(caller.id().clone(), callee.id().clone(), EdgeSource::Lsp);
+    ///
+    /// let mut graph = CallGraph::new();
+    /// graph.add_node(caller);
+    /// graph.add_node(callee);
+    /// graph.add_edge(edge);
+    ///
+    /// let dot = graph.to_dot();
+    /// assert!(dot.starts_with("digraph call_graph {"));
+    /// assert!(dot.contains(r#""/src/main.rs:0:0:main" -> "/src/main.rs:4:0:run" [label="lsp"];"#));
+    /// ```
+    #[must_use]
+    pub fn to_dot(&self) -> String { Dot(self).to_string() }
+}
+
+/// Display adapter that writes a [`CallGraph`] in DOT syntax.
+struct Dot<'a>(&'a CallGraph);
+
+impl fmt::Display for Dot<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut nodes: Vec<&CallNode> = self.0.nodes().collect();
+        nodes.sort_by(|left, right| left.id().as_str().cmp(right.id().as_str()));
+
+        writeln!(f, "digraph call_graph {{")?;
+        for node in nodes {
+            writeln!(
+                f,
+                "    \"{}\" [label=\"{}\"];",
+                escape(node.id().as_str()),
+                escape(&node.qualified_name())
+            )?;
+        }
+        for edge in self.0.edges() {
+            writeln!(
+                f,
+                "    \"{}\" -> \"{}\" [label=\"{}\"];",
+                escape(edge.caller().as_str()),
+                escape(edge.callee().as_str()),
+                edge.source()
+            )?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/// Escapes `text` for use inside a double-quoted DOT string.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '"' | '\\' => {
+                escaped.push('\\');
+                escaped.push(character);
+            }
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
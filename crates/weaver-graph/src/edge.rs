@@ -1,15 +1,23 @@
 //! Call graph edge representation.
 
+use serde::{Deserialize, Serialize};
+
 use crate::node::{NodeId, Position};
 
 /// Provenance of a call edge, indicating its source.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Sources serialize as the same labels used by their `Display`
+/// implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EdgeSource {
     /// Edge discovered via LSP call hierarchy.
+    #[serde(rename = "lsp")]
     Lsp,
     /// Edge discovered via static analysis.
-    StaticAnalysis,
+    #[serde(rename = "static")]
+    Static,
     /// Edge discovered via dynamic profiling.
+    #[serde(rename = "dynamic")]
     DynamicProfiling,
 }
 
@@ -17,7 +25,7 @@ impl std::fmt::Display for EdgeSource {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let label = match self {
             Self::Lsp => "lsp",
-            Self::StaticAnalysis => "static",
+            Self::Static => "static",
             Self::DynamicProfiling => "dynamic",
         };
         f.write_str(label)
@@ -48,6 +48,22 @@ pub enum GraphError {
     /// A validation error occurred (e.g., invalid path or URI).
     #[error("validation error: {0}")]
     Validation(String),
+
+    /// An external analysis tool could not be found on `PATH`.
+    #[error("analysis tool '{tool}' is not available on PATH")]
+    ToolUnavailable {
+        /// Command name of the missing tool.
+        tool: String,
+    },
+
+    /// An external analysis tool exited unsuccessfully.
+    #[error("analysis tool '{tool}' failed: {message}")]
+    ToolFailed {
+        /// Command name of the failing tool.
+        tool: String,
+        /// Diagnostic output reported by the tool.
+        message: String,
+    },
 }
 
 impl GraphError {
@@ -85,4 +101,19 @@ impl GraphError {
     /// Creates a new `Validation` error.
     #[must_use]
     pub fn validation(message: impl Into<String>) -> Self { Self::Validation(message.into()) }
+
+    /// Creates a new `ToolUnavailable` error.
+    #[must_use]
+    pub fn tool_unavailable(tool: impl Into<String>) -> Self {
+        Self::ToolUnavailable { tool: tool.into() }
+    }
+
+    /// Creates a new `ToolFailed` error.
+    #[must_use]
+    pub fn tool_failed(tool: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::ToolFailed {
+            tool: tool.into(),
+            message: message.into(),
+        }
+    }
 }
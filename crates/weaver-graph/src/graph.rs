@@ -3,16 +3,19 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::{
-    edge::CallEdge,
+    edge::{CallEdge, EdgeSource},
     error::GraphError,
-    node::{CallNode, NodeId},
+    node::{CallNode, NodeId, SymbolKind},
 };
 
 /// A call graph with bidirectional indexing for efficient traversal.
 ///
 /// The graph maintains indices for both incoming calls (callers) and outgoing
 /// calls (callees) to support efficient queries in either direction.
-#[derive(Debug, Clone, Default)]
+///
+/// Two graphs are equal when they hold the same nodes and the same edges in
+/// the same order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct CallGraph {
     /// All nodes in the graph, keyed by node ID.
     nodes: HashMap<NodeId, CallNode>,
@@ -68,6 +71,11 @@ impl CallGraph {
     /// Returns an iterator over all nodes in the graph.
     pub fn nodes(&self) -> impl Iterator<Item = &CallNode> { self.nodes.values() }
 
+    /// Returns the nodes whose symbol kind is `kind`, in no particular order.
+    pub fn nodes_of_kind(&self, kind: SymbolKind) -> impl Iterator<Item = &CallNode> {
+        self.nodes().filter(move |node| node.kind() == kind)
+    }
+
     /// Returns the number of nodes in the graph.
     #[must_use]
     pub fn node_count(&self) -> usize { self.nodes.len() }
@@ -123,18 +131,37 @@ impl CallGraph {
 
     /// Merges another graph into this one.
     ///
-    /// Nodes with the same ID are replaced. All edges from the other graph
-    /// are added without deduplication—if both graphs contain the same edge
-    /// (same caller→callee with same source), it will appear twice.
+    /// Nodes are unioned by ID, with nodes from `other` replacing existing
+    /// nodes that share their ID. Edges are unioned by caller, callee, and
+    /// source: an edge already reported by the same source is skipped, while
+    /// the same call reported by a different source is kept as a separate
+    /// entry, so fused graphs still record every source that saw a call.
     pub fn merge(&mut self, other: Self) {
         for node in other.nodes.into_values() {
             self.add_node(node);
         }
         for edge in other.edges {
-            self.add_edge(edge);
+            if !self.contains_edge(&edge) {
+                self.add_edge(edge);
+            }
         }
     }
 
+    /// Returns the sources that reported a call from `from` to `to`, in no
+    /// particular order.
+    pub fn edge_sources(&self, from: &NodeId, to: &NodeId) -> impl Iterator<Item = EdgeSource> {
+        self.outgoing_edges(from)
+            .filter(move |edge| edge.callee() == to)
+            .map(CallEdge::source)
+    }
+
+    /// Returns whether an edge with the same caller, callee, and source as
+    /// `edge` is already present.
+    pub(crate) fn contains_edge(&self, edge: &CallEdge) -> bool {
+        self.edge_sources(edge.caller(), edge.callee())
+            .any(|source| source == edge.source())
+    }
+
     /// Finds a node by name.
     ///
     /// Returns the first node with a matching name. For methods, searches both
@@ -0,0 +1,179 @@
+//! JSON serialization for call graphs.
+//!
+//! The schema mirrors the call hierarchy payload described in the users'
+//! guide, with each node's file given as a `path`:
+//!
+//! ```json
+//! {
+//!   "nodes": [
+//!     { "id": "...", "name": "...", "kind": "function", "path": "...",
+//!       "line": 0, "column": 0, "container": null }
+//!   ],
+//!   "edges": [
+//!     { "caller": "...", "callee": "...", "source": "lsp",
+//!       "call_site": { "line": 0, "column": 0 } }
+//!   ]
+//! }
+//! ```
+
+use camino::Utf8PathBuf;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::{
+    edge::{CallEdge, EdgeSource},
+    error::GraphError,
+    graph::CallGraph,
+    node::{CallNode, NodeId, Position, SymbolKind},
+};
+
+/// Serialized form of a [`CallNode`].
+#[derive(Debug, Serialize, Deserialize)]
+struct NodeRecord {
+    id: NodeId,
+    name: String,
+    kind: SymbolKind,
+    path: Utf8PathBuf,
+    line: u32,
+    column: u32,
+    container: Option<String>,
+}
+
+/// Serialized form of a [`CallEdge`].
+#[derive(Debug, Serialize, Deserialize)]
+struct EdgeRecord {
+    caller: NodeId,
+    callee: NodeId,
+    source: EdgeSource,
+    call_site: Option<Position>,
+}
+
+/// Serialized form of a [`CallGraph`].
+#[derive(Debug, Deserialize)]
+struct GraphRecord {
+    nodes: Vec<NodeRecord>,
+    edges: Vec<EdgeRecord>,
+}
+
+impl From<&CallNode> for NodeRecord {
+    fn from(node: &CallNode) -> Self {
+        Self {
+            id: node.id().clone(),
+            name: node.name().to_owned(),
+            kind: node.kind(),
+            path: node.path().clone(),
+            line: node.line(),
+            column: node.column(),
+            container: node.container().map(str::to_owned),
+        }
+    }
+}
+
+impl From<&CallEdge> for EdgeRecord {
+    fn from(edge: &CallEdge) -> Self {
+        Self {
+            caller: edge.caller().clone(),
+            callee: edge.callee().clone(),
+            source: edge.source(),
+            call_site: edge.call_site(),
+        }
+    }
+}
+
+impl TryFrom<NodeRecord> for CallNode {
+    type Error = GraphError;
+
+    fn try_from(record: NodeRecord) -> Result<Self, Self::Error> {
+        let base = Self::new(
+            record.name,
+            record.kind,
+            record.path,
+            Position::new(record.line, record.column),
+        );
+        let node = match record.container {
+            Some(container) => base.with_container(container),
+            None => base,
+        };
+        if node.id() == &record.id {
+            Ok(node)
+        } else {
+            Err(GraphError::validation(format!(
+                "node id '{}' does not match its location; expected '{}'",
+                record.id,
+                node.id()
+            )))
+        }
+    }
+}
+
+impl CallGraph {
+    /// Serializes the graph into a JSON value with `nodes` and `edges` arrays.
+    ///
+    /// Nodes are sorted by ID and edges keep their insertion order, so equal
+    /// graphs serialize identically. [`CallGraph::from_json`] reloads the
+    /// value into an equal graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weaver_graph::{CallGraph, CallNode, Position, SymbolKind};
+    ///
+    /// let mut graph = CallGraph::new();
+    /// graph.add_node(CallNode::new(
+    ///     "main",
+    ///     SymbolKind::Function,
+    ///     "/src/main.rs",
+    ///     Position::new(0, 0),
+    /// ));
+    ///
+    /// let json = graph.to_json();
+    /// assert_eq!(json["nodes"][0]["kind"], "function");
+    /// assert_eq!(CallGraph::from_json(&json)?, graph);
+    /// # Ok::<(), weaver_graph::GraphError>(())
+    /// ```
+    #[must_use]
+    pub fn to_json(&self) -> Value {
+        let mut nodes: Vec<NodeRecord> = self.nodes().map(NodeRecord::from).collect();
+        nodes.sort_by(|left, right| left.id.as_str().cmp(right.id.as_str()));
+        let edges: Vec<EdgeRecord> = self.edges().map(EdgeRecord::from).collect();
+        json!({ "nodes": nodes, "edges": edges })
+    }
+
+    /// Rebuilds a graph from the JSON produced by [`CallGraph::to_json`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::Validation`] if the value does not follow the
+    /// schema, if a node ID does not match the node's path, position, and
+    /// name, or if an edge refers to a node that is not listed.
+    pub fn from_json(value: &Value) -> Result<Self, GraphError> {
+        let record = GraphRecord::deserialize(value)
+            .map_err(|error| GraphError::validation(format!("invalid call graph JSON: {error}")))?;
+
+        let mut graph = Self::new();
+        for node in record.nodes {
+            graph.add_node(CallNode::try_from(node)?);
+        }
+        for edge in record.edges {
+            ensure_endpoints_exist(&graph, &edge)?;
+            let base = CallEdge::new(edge.caller, edge.callee, edge.source);
+            graph.add_edge(match edge.call_site {
+                Some(position) => base.with_call_site(position),
+                None => base,
+            });
+        }
+        Ok(graph)
+    }
+}
+
+/// Checks that both endpoints of `edge` are nodes of `graph`.
+fn ensure_endpoints_exist(graph: &CallGraph, edge: &EdgeRecord) -> Result<(), GraphError> {
+    [&edge.caller, &edge.callee]
+        .into_iter()
+        .find(|endpoint| !graph.contains_node(endpoint))
+        .map_or(Ok(()), |endpoint| {
+            Err(GraphError::validation(format!(
+                "edge refers to unknown node '{endpoint}'"
+            )))
+        })
+}
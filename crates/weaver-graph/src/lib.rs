@@ -6,8 +6,8 @@
 //!
 //! - **LSP Provider**: Uses `textDocument/callHierarchy` requests for semantic call graph
 //!   information
-//! - **Static Analysis Provider** (planned): Wraps language-specific tools like `PyCG` for deeper
-//!   analysis
+//! - **Static Analysis Provider**: Runs language-specific tools like `PyCG` over a whole project
+//!   for deeper analysis
 //! - **Dynamic Analysis Provider** (planned): Ingests profiling data from tools like gprof and
 //!   callgrind
 //!
@@ -17,11 +17,23 @@
 //! - [`CallEdge`] - Represents a call relationship between two nodes
 //! - [`CallGraph`] - The complete graph structure with bidirectional indexing
 //!
+//! A [`CallGraph`] can be exported as a Graphviz `digraph` with
+//! [`CallGraph::to_dot`], and converted to and from JSON with
+//! [`CallGraph::to_json`] and [`CallGraph::from_json`]. Recursive call
+//! cycles are reported by [`CallGraph::find_cycles`], and
+//! [`CallGraph::shortest_path`] shows how one function reaches another.
+//!
 //! # Providers
 //!
 //! The [`CallGraphProvider`] trait abstracts over different data sources. The
 //! initial implementation provides [`LspCallGraphProvider`] which queries LSP
-//! servers for call hierarchy information.
+//! servers for call hierarchy information; its traversal can be restricted to
+//! chosen [`SymbolKind`]s with [`LspCallGraphProvider::with_expand_kinds`].
+//! [`PyCgCallGraphProvider`] runs `PyCG` over a Python project and tags its
+//! edges as [`EdgeSource::Static`].
+//! Graphs from different providers are fused with [`CallGraph::merge`],
+//! which keeps one edge per source so [`CallGraph::edge_sources`] can report
+//! which providers saw each call.
 //!
 //! # Example
 //!
@@ -40,11 +52,16 @@
 //! }
 //! ```
 
+mod cycles;
+mod dot;
 mod edge;
 mod error;
 mod graph;
+mod json;
 mod node;
+mod path;
 mod provider;
+mod pycg;
 mod uri;
 
 pub use edge::{CallEdge, EdgeSource};
@@ -52,6 +69,7 @@ pub use error::GraphError;
 pub use graph::CallGraph;
 pub use node::{CallNode, NodeId, Position, SymbolKind};
 pub use provider::{CallGraphProvider, CallHierarchyClient, LspCallGraphProvider, SourcePosition};
+pub use pycg::PyCgCallGraphProvider;
 
 #[cfg(test)]
 mod tests;
@@ -1,12 +1,14 @@
 //! Call graph node representation.
 
 use camino::Utf8PathBuf;
+use serde::{Deserialize, Serialize};
 
 /// Unique identifier for a node in the call graph.
 ///
 /// Node IDs are constructed from the symbol's location to ensure uniqueness
 /// across the codebase. The format is `{path}:{line}:{column}:{name}`.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct NodeId(String);
 
 impl NodeId {
@@ -26,7 +28,7 @@ impl std::fmt::Display for NodeId {
 }
 
 /// Position in source code (line and column).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Position {
     /// Zero-based line number.
     pub line: u32,
@@ -43,7 +45,9 @@ impl Position {
 /// Kind of symbol represented by a call graph node.
 ///
 /// This mirrors LSP's `SymbolKind` but only includes callable symbols.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+/// Kinds serialize as lowercase names such as `"function"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SymbolKind {
     /// A function definition.
     Function,
@@ -0,0 +1,89 @@
+//! Shortest call path queries.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::{edge::CallEdge, graph::CallGraph, node::NodeId};
+
+impl CallGraph {
+    /// Returns a shortest chain of calls leading from `from` to `to`.
+    ///
+    /// The path starts with `from`, ends with `to`, and follows callee edges,
+    /// so it answers "how does function A reach function B". A node reaches
+    /// itself through a single-element path. When several paths share the
+    /// minimal length, callees are explored in ID order, so the same path is
+    /// returned on every call.
+    ///
+    /// Returns `None` when `from` is not a node of the graph or `to` cannot
+    /// be reached from it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weaver_graph::{CallEdge, CallGraph, CallNode, EdgeSource, Position, SymbolKind};
+    ///
+    /// let node = |name: &str, line| {
+    ///     CallNode::new(
+    ///         name,
+    ///         SymbolKind::Function,
+    ///         "/src/lib.rs",
+    ///         Position::new(line, 0),
+    ///     )
+    /// };
+    /// let (main, run) = (node("main", 0), node("run", 5));
+    /// let (main_id, run_id) = (main.id().clone(), run.id().clone());
+    ///
+    /// let mut graph = CallGraph::new();
+    /// graph.add_node(main);
+    /// graph.add_node(run);
+    /// graph.add_edge(CallEdge::new(
+    ///     main_id.clone(),
+    ///     run_id.clone(),
+    ///     EdgeSource::Lsp,
+    /// ));
+    ///
+    /// assert_eq!(
+    ///     graph.shortest_path(&main_id, &run_id),
+    ///     Some(vec![main_id.clone(), run_id.clone()])
+    /// );
+    /// assert_eq!(graph.shortest_path(&run_id, &main_id), None);
+    /// ```
+    #[must_use]
+    pub fn shortest_path(&self, from: &NodeId, to: &NodeId) -> Option<Vec<NodeId>> {
+        if !self.contains_node(from) {
+            return None;
+        }
+
+        let mut predecessor: HashMap<&NodeId, Option<&NodeId>> = HashMap::from([(from, None)]);
+        let mut queue = VecDeque::from([from]);
+        while let Some(node) = queue.pop_front() {
+            if node == to {
+                return Some(unwind(&predecessor, to));
+            }
+            let mut callees: Vec<&NodeId> = self
+                .outgoing_edges(node)
+                .map(CallEdge::callee)
+                .filter(|callee| !predecessor.contains_key(callee))
+                .collect();
+            callees.sort_by(|left, right| left.as_str().cmp(right.as_str()));
+            callees.dedup();
+            for callee in callees {
+                predecessor.insert(callee, Some(node));
+                queue.push_back(callee);
+            }
+        }
+        None
+    }
+}
+
+/// Follows `predecessor` links back from `target` to the search origin and
+/// returns the path in call order.
+fn unwind(predecessor: &HashMap<&NodeId, Option<&NodeId>>, target: &NodeId) -> Vec<NodeId> {
+    let mut path = vec![target.clone()];
+    let mut current = predecessor.get(target).copied().flatten();
+    while let Some(node) = current {
+        path.push(node.clone());
+        current = predecessor.get(node).copied().flatten();
+    }
+    path.reverse();
+    path
+}
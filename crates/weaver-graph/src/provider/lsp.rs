@@ -0,0 +1,336 @@
+//! Call graph provider backed by LSP call hierarchy requests.
+
+use std::collections::{BTreeSet, HashSet};
+
+use lsp_types::{
+    CallHierarchyIncomingCallsParams,
+    CallHierarchyItem,
+    CallHierarchyOutgoingCallsParams,
+    CallHierarchyPrepareParams,
+    Position as LspPosition,
+    Range,
+    TextDocumentIdentifier,
+    TextDocumentPositionParams,
+    WorkDoneProgressParams,
+};
+
+use super::{CallGraphProvider, CallHierarchyClient, SourcePosition};
+use crate::{
+    edge::{CallEdge, EdgeSource},
+    error::GraphError,
+    graph::CallGraph,
+    node::{CallNode, NodeId, Position, SymbolKind},
+    uri::{path_to_uri, uri_to_path},
+};
+
+/// LSP-based call graph provider.
+///
+/// Uses `textDocument/callHierarchy` requests to build call graphs from
+/// language server data.
+pub struct LspCallGraphProvider<C> {
+    client: C,
+    expand_kinds: Option<BTreeSet<SymbolKind>>,
+}
+
+impl<C> LspCallGraphProvider<C> {
+    /// Creates a new LSP call graph provider with the given client.
+    #[must_use]
+    pub const fn new(client: C) -> Self {
+        Self {
+            client,
+            expand_kinds: None,
+        }
+    }
+
+    /// Restricts traversal to symbols of the given kinds.
+    ///
+    /// Nodes of any other kind, including the symbol at the requested
+    /// position, are still added to the graph when discovered, but their
+    /// callers and callees are not requested. For example, allowing only
+    /// [`SymbolKind::Function`] keeps methods as leaves of the graph.
+    #[must_use]
+    pub fn with_expand_kinds(mut self, kinds: impl IntoIterator<Item = SymbolKind>) -> Self {
+        self.expand_kinds = Some(kinds.into_iter().collect());
+        self
+    }
+
+    /// Builds an empty traversal carrying the configured kind allowlist.
+    fn traversal(&self) -> Traversal {
+        Traversal {
+            expand_kinds: self.expand_kinds.clone(),
+            ..Traversal::default()
+        }
+    }
+}
+
+impl<C: CallHierarchyClient> CallGraphProvider for LspCallGraphProvider<C> {
+    fn build_graph(
+        &mut self,
+        position: &SourcePosition,
+        depth: u32,
+    ) -> Result<CallGraph, GraphError> {
+        let mut traversal = self.traversal();
+
+        // Prepare call hierarchy at the position
+        let items = self.prepare_at_position(position)?;
+
+        if items.is_empty() {
+            return Err(GraphError::symbol_not_found(
+                &position.path,
+                position.line(),
+                position.column(),
+            ));
+        }
+
+        // Add the root nodes and explore both directions
+        for item in &items {
+            let node = call_hierarchy_item_to_node(item);
+            traversal.graph.add_node(node);
+        }
+
+        if depth > 0 {
+            // Explore callers and callees
+            for item in &items {
+                self.explore_callers(&mut traversal, item, depth)?;
+                self.explore_callees(&mut traversal, item, depth)?;
+            }
+        }
+
+        Ok(traversal.graph)
+    }
+
+    fn callers_graph(
+        &mut self,
+        position: &SourcePosition,
+        depth: u32,
+    ) -> Result<CallGraph, GraphError> {
+        self.build_directional_graph(position, depth, |provider, traversal, item, d| {
+            provider.explore_callers(traversal, item, d)
+        })
+    }
+
+    fn callees_graph(
+        &mut self,
+        position: &SourcePosition,
+        depth: u32,
+    ) -> Result<CallGraph, GraphError> {
+        self.build_directional_graph(position, depth, |provider, traversal, item, d| {
+            provider.explore_callees(traversal, item, d)
+        })
+    }
+}
+
+/// A call graph under construction, together with the symbols already
+/// expanded in each direction.
+///
+/// Each symbol is queried for its callers and its callees at most once per
+/// build, so recursive code and call chains that meet again do not trigger
+/// repeated requests. Symbols whose kind is outside `expand_kinds`, when set,
+/// are never queried.
+#[derive(Default)]
+struct Traversal {
+    graph: CallGraph,
+    expanded_callers: HashSet<NodeId>,
+    expanded_callees: HashSet<NodeId>,
+    expand_kinds: Option<BTreeSet<SymbolKind>>,
+}
+
+impl<C: CallHierarchyClient> LspCallGraphProvider<C> {
+    /// Shared helper for building directional graphs (callers or callees).
+    fn build_directional_graph<F>(
+        &mut self,
+        position: &SourcePosition,
+        depth: u32,
+        explore_fn: F,
+    ) -> Result<CallGraph, GraphError>
+    where
+        F: Fn(&mut Self, &mut Traversal, &CallHierarchyItem, u32) -> Result<(), GraphError>,
+    {
+        let mut traversal = self.traversal();
+
+        let items = self.prepare_at_position(position)?;
+
+        if items.is_empty() {
+            return Err(GraphError::symbol_not_found(
+                &position.path,
+                position.line(),
+                position.column(),
+            ));
+        }
+
+        for item in &items {
+            let node = call_hierarchy_item_to_node(item);
+            traversal.graph.add_node(node);
+            explore_fn(self, &mut traversal, item, depth)?;
+        }
+
+        Ok(traversal.graph)
+    }
+
+    fn prepare_at_position(
+        &mut self,
+        position: &SourcePosition,
+    ) -> Result<Vec<CallHierarchyItem>, GraphError> {
+        let uri = path_to_uri(&position.path)?;
+        let params = CallHierarchyPrepareParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri },
+                position: LspPosition::new(position.line(), position.column()),
+            },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        };
+
+        self.client
+            .prepare_call_hierarchy(params)
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Explores callers breadth-first, up to `depth` calls away from `item`.
+    fn explore_callers(
+        &mut self,
+        traversal: &mut Traversal,
+        item: &CallHierarchyItem,
+        depth: u32,
+    ) -> Result<(), GraphError> {
+        let mut frontier = vec![item.clone()];
+        for _ in 0..depth {
+            let mut next = Vec::new();
+            let unexpanded = frontier
+                .iter()
+                .filter(|current| kind_allowed(traversal.expand_kinds.as_ref(), current))
+                .filter(|current| traversal.expanded_callers.insert(item_id(current)));
+            for current in unexpanded {
+                next.extend(self.add_callers(&mut traversal.graph, current)?);
+            }
+            frontier = next;
+        }
+        Ok(())
+    }
+
+    /// Explores callees breadth-first, up to `depth` calls away from `item`.
+    fn explore_callees(
+        &mut self,
+        traversal: &mut Traversal,
+        item: &CallHierarchyItem,
+        depth: u32,
+    ) -> Result<(), GraphError> {
+        let mut frontier = vec![item.clone()];
+        for _ in 0..depth {
+            let mut next = Vec::new();
+            let unexpanded = frontier
+                .iter()
+                .filter(|current| kind_allowed(traversal.expand_kinds.as_ref(), current))
+                .filter(|current| traversal.expanded_callees.insert(item_id(current)));
+            for current in unexpanded {
+                next.extend(self.add_callees(&mut traversal.graph, current)?);
+            }
+            frontier = next;
+        }
+        Ok(())
+    }
+
+    /// Adds the direct callers of `item` to `graph` and returns them.
+    fn add_callers(
+        &mut self,
+        graph: &mut CallGraph,
+        item: &CallHierarchyItem,
+    ) -> Result<Vec<CallHierarchyItem>, GraphError> {
+        let params = CallHierarchyIncomingCallsParams {
+            item: item.clone(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: lsp_types::PartialResultParams::default(),
+        };
+
+        let incoming = self.client.incoming_calls(params)?.unwrap_or_default();
+        let target_id = item_id(item);
+
+        let mut callers = Vec::with_capacity(incoming.len());
+        for call in incoming {
+            let from_node = call_hierarchy_item_to_node(&call.from);
+            let from_id = from_node.id().clone();
+
+            if !graph.contains_node(&from_id) {
+                graph.add_node(from_node);
+            }
+
+            // Create edge from caller to callee
+            let edge = call_edge(from_id, target_id.clone(), &call.from_ranges);
+            if !graph.contains_edge(&edge) {
+                graph.add_edge(edge);
+            }
+            callers.push(call.from);
+        }
+
+        Ok(callers)
+    }
+
+    /// Adds the direct callees of `item` to `graph` and returns them.
+    fn add_callees(
+        &mut self,
+        graph: &mut CallGraph,
+        item: &CallHierarchyItem,
+    ) -> Result<Vec<CallHierarchyItem>, GraphError> {
+        let params = CallHierarchyOutgoingCallsParams {
+            item: item.clone(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: lsp_types::PartialResultParams::default(),
+        };
+
+        let outgoing = self.client.outgoing_calls(params)?.unwrap_or_default();
+        let source_id = item_id(item);
+
+        let mut callees = Vec::with_capacity(outgoing.len());
+        for call in outgoing {
+            let target_node = call_hierarchy_item_to_node(&call.to);
+            let target_id = target_node.id().clone();
+
+            if !graph.contains_node(&target_id) {
+                graph.add_node(target_node);
+            }
+
+            // Create edge from caller to callee
+            let edge = call_edge(source_id.clone(), target_id, &call.from_ranges);
+            if !graph.contains_edge(&edge) {
+                graph.add_edge(edge);
+            }
+            callees.push(call.to);
+        }
+
+        Ok(callees)
+    }
+}
+
+/// Returns whether the optional kind allowlist permits expanding `item`.
+fn kind_allowed(kinds: Option<&BTreeSet<SymbolKind>>, item: &CallHierarchyItem) -> bool {
+    kinds.is_none_or(|allowed| allowed.contains(&SymbolKind::from_lsp(item.kind)))
+}
+
+/// Returns the node ID of the symbol described by `item`.
+fn item_id(item: &CallHierarchyItem) -> NodeId { call_hierarchy_item_to_node(item).id().clone() }
+
+/// Builds an LSP edge, using the first call site range if available.
+const fn call_edge(from_caller: NodeId, to_callee: NodeId, ranges: &[Range]) -> CallEdge {
+    let edge = CallEdge::new(from_caller, to_callee, EdgeSource::Lsp);
+    match ranges.first() {
+        Some(range) => edge.with_call_site(Position::new(range.start.line, range.start.character)),
+        None => edge,
+    }
+}
+
+/// Converts an LSP `CallHierarchyItem` to our domain `CallNode`.
+fn call_hierarchy_item_to_node(item: &CallHierarchyItem) -> CallNode {
+    let path = uri_to_path(&item.uri);
+    let kind = SymbolKind::from_lsp(item.kind);
+    let position = Position::new(
+        item.selection_range.start.line,
+        item.selection_range.start.character,
+    );
+
+    let mut node = CallNode::new(&item.name, kind, path, position);
+
+    if let Some(detail) = &item.detail {
+        node = node.with_container(detail.clone());
+    }
+
+    node
+}
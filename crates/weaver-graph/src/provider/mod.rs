@@ -0,0 +1,129 @@
+//! Provider abstractions for call graph construction.
+//!
+//! This module defines the [`CallGraphProvider`] trait and its LSP-based
+//! implementation. The provider pattern enables fusion of multiple data
+//! sources (LSP, static analysis, profiling) for richer call graphs.
+
+mod lsp;
+
+use camino::Utf8PathBuf;
+use lsp_types::{
+    CallHierarchyIncomingCallsParams,
+    CallHierarchyItem,
+    CallHierarchyOutgoingCallsParams,
+    CallHierarchyPrepareParams,
+};
+
+pub use self::lsp::LspCallGraphProvider;
+use crate::{error::GraphError, graph::CallGraph, node::Position};
+
+/// A position in a source file for initiating call graph queries.
+#[derive(Debug, Clone)]
+pub struct SourcePosition {
+    /// Path to the source file.
+    pub path: Utf8PathBuf,
+    /// Position within the file (0-based line and column).
+    pub position: Position,
+}
+
+impl SourcePosition {
+    /// Creates a new source position.
+    #[must_use]
+    pub fn new(path: impl Into<Utf8PathBuf>, line: u32, column: u32) -> Self {
+        Self {
+            path: path.into(),
+            position: Position::new(line, column),
+        }
+    }
+
+    /// Returns the line number (0-based).
+    #[must_use]
+    pub const fn line(&self) -> u32 { self.position.line }
+
+    /// Returns the column number (0-based).
+    #[must_use]
+    pub const fn column(&self) -> u32 { self.position.column }
+}
+
+/// Trait for call graph data providers.
+///
+/// Implementations can source call graph data from various backends:
+/// - LSP servers via `textDocument/callHierarchy`
+/// - Static analysis tools
+/// - Dynamic profiling data
+pub trait CallGraphProvider {
+    /// Builds a call graph starting from the given position.
+    ///
+    /// # Arguments
+    /// * `position` - Starting position for the call graph
+    /// * `depth` - Maximum traversal depth (0 = just the item at position)
+    ///
+    /// # Errors
+    /// Returns an error if the symbol cannot be found or the provider fails.
+    fn build_graph(
+        &mut self,
+        position: &SourcePosition,
+        depth: u32,
+    ) -> Result<CallGraph, GraphError>;
+
+    /// Builds a call graph showing callers of the symbol at the position.
+    ///
+    /// # Arguments
+    /// * `position` - Position of the symbol to find callers for
+    /// * `depth` - Maximum traversal depth
+    ///
+    /// # Errors
+    /// Returns an error if the symbol cannot be found or the provider fails.
+    fn callers_graph(
+        &mut self,
+        position: &SourcePosition,
+        depth: u32,
+    ) -> Result<CallGraph, GraphError>;
+
+    /// Builds a call graph showing callees of the symbol at the position.
+    ///
+    /// # Arguments
+    /// * `position` - Position of the symbol to find callees for
+    /// * `depth` - Maximum traversal depth
+    ///
+    /// # Errors
+    /// Returns an error if the symbol cannot be found or the provider fails.
+    fn callees_graph(
+        &mut self,
+        position: &SourcePosition,
+        depth: u32,
+    ) -> Result<CallGraph, GraphError>;
+}
+
+/// Client abstraction for LSP call hierarchy operations.
+///
+/// This trait enables testing with mock clients and abstracts over
+/// different LSP client implementations.
+pub trait CallHierarchyClient {
+    /// Prepares call hierarchy items at the given position.
+    ///
+    /// # Errors
+    /// Returns an error if the LSP request fails.
+    fn prepare_call_hierarchy(
+        &mut self,
+        params: CallHierarchyPrepareParams,
+    ) -> Result<Option<Vec<CallHierarchyItem>>, GraphError>;
+
+    /// Gets incoming calls for the given item.
+    ///
+    /// # Errors
+    /// Returns an error if the LSP request fails.
+    fn incoming_calls(
+        &mut self,
+        params: CallHierarchyIncomingCallsParams,
+    ) -> Result<Option<Vec<lsp_types::CallHierarchyIncomingCall>>, GraphError>;
+
+    /// Gets outgoing calls for the given item.
+    ///
+    /// # Errors
+    /// Returns an error if the LSP request fails.
+    fn outgoing_calls(
+        &mut self,
+        params: CallHierarchyOutgoingCallsParams,
+    ) -> Result<Option<Vec<lsp_types::CallHierarchyOutgoingCall>>, GraphError>;
+}
@@ -0,0 +1,187 @@
+//! Static call graph provider for Python backed by `PyCG`.
+//!
+//! `PyCG` analyses a whole package at once and reports, for every function
+//! it finds, the fully qualified names of the functions it may call. This
+//! module runs the tool over a project root, maps each dotted name back to
+//! its definition in the project's sources, and answers provider queries by
+//! slicing the resulting project-wide graph around the requested symbol.
+
+mod resolve;
+mod slice;
+
+use std::process::Command;
+
+use camino::{Utf8Path, Utf8PathBuf};
+
+pub(crate) use self::resolve::graph_from_pycg;
+use self::slice::{Direction, slice};
+use crate::{
+    error::GraphError,
+    graph::CallGraph,
+    node::{CallNode, NodeId},
+    provider::{CallGraphProvider, SourcePosition},
+};
+
+/// Command used to invoke `PyCG` unless overridden.
+const DEFAULT_COMMAND: &str = "pycg";
+
+/// Call graph provider that runs `PyCG` over a Python project.
+///
+/// The project is analysed once, on the first query, and the resulting graph
+/// is reused for later queries. Every edge is tagged with
+/// [`EdgeSource::Static`]. Calls into code outside the project root, such as
+/// builtins and third-party libraries, are omitted because they have no
+/// source location to anchor a node to.
+#[derive(Debug)]
+pub struct PyCgCallGraphProvider {
+    root: Utf8PathBuf,
+    command: String,
+    graph: Option<CallGraph>,
+}
+
+impl PyCgCallGraphProvider {
+    /// Creates a provider for the Python package rooted at `root`.
+    #[must_use]
+    pub fn new(root: impl Into<Utf8PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            command: DEFAULT_COMMAND.to_owned(),
+            graph: None,
+        }
+    }
+
+    /// Overrides the command used to invoke `PyCG`.
+    #[must_use]
+    pub fn with_command(mut self, command: impl Into<String>) -> Self {
+        self.command = command.into();
+        self
+    }
+
+    /// Returns the project root analysed by this provider.
+    #[must_use]
+    pub fn root(&self) -> &Utf8Path { &self.root }
+
+    /// Returns the call graph for the whole project, running `PyCG` if the
+    /// project has not been analysed yet.
+    ///
+    /// # Errors
+    /// Returns [`GraphError::ToolUnavailable`] when the `PyCG` command is not
+    /// on `PATH`, [`GraphError::ToolFailed`] when it exits unsuccessfully,
+    /// and [`GraphError::Validation`] when its output cannot be parsed.
+    pub fn analyse(&mut self) -> Result<&CallGraph, GraphError> {
+        let graph = match self.graph.take() {
+            Some(graph) => graph,
+            None => run_pycg(&self.command, &self.root)?,
+        };
+        Ok(self.graph.insert(graph))
+    }
+
+    /// Slices the project graph around the symbols defined at `position`.
+    fn slice_at(
+        &mut self,
+        position: &SourcePosition,
+        depth: u32,
+        directions: &[Direction],
+    ) -> Result<CallGraph, GraphError> {
+        let graph = self.analyse()?;
+        let mut roots: Vec<&NodeId> = graph
+            .nodes()
+            .filter(|node| *node.path() == position.path && node.line() == position.line())
+            .map(CallNode::id)
+            .collect();
+        if roots.is_empty() {
+            return Err(GraphError::symbol_not_found(
+                &position.path,
+                position.line(),
+                position.column(),
+            ));
+        }
+        roots.sort_by(|left, right| left.as_str().cmp(right.as_str()));
+        Ok(slice(graph, &roots, depth, directions))
+    }
+}
+
+impl CallGraphProvider for PyCgCallGraphProvider {
+    fn build_graph(
+        &mut self,
+        position: &SourcePosition,
+        depth: u32,
+    ) -> Result<CallGraph, GraphError> {
+        self.slice_at(position, depth, &[Direction::Callers, Direction::Callees])
+    }
+
+    fn callers_graph(
+        &mut self,
+        position: &SourcePosition,
+        depth: u32,
+    ) -> Result<CallGraph, GraphError> {
+        self.slice_at(position, depth, &[Direction::Callers])
+    }
+
+    fn callees_graph(
+        &mut self,
+        position: &SourcePosition,
+        depth: u32,
+    ) -> Result<CallGraph, GraphError> {
+        self.slice_at(position, depth, &[Direction::Callees])
+    }
+}
+
+/// Checks if a command is available on the system PATH.
+fn command_available(cmd: &str) -> bool {
+    Command::new("which")
+        .arg(cmd)
+        .output()
+        .is_ok_and(|out| out.status.success())
+}
+
+/// Runs `PyCG` over every Python file below `root` and builds the project
+/// graph from its output.
+fn run_pycg(command: &str, root: &Utf8Path) -> Result<CallGraph, GraphError> {
+    if !command_available(command) {
+        return Err(GraphError::tool_unavailable(command));
+    }
+    let entry_points = python_files(root)?;
+    if entry_points.is_empty() {
+        return Ok(CallGraph::new());
+    }
+
+    let output = Command::new(command)
+        .arg("--package")
+        .arg(root)
+        .args(&entry_points)
+        .output()
+        .map_err(|err| GraphError::io(format!("failed to run '{command}'"), err))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GraphError::tool_failed(command, stderr.trim()));
+    }
+    graph_from_pycg(root, &output.stdout)
+}
+
+/// Lists the Python files below `root` in path order, skipping hidden
+/// directories and bytecode caches.
+fn python_files(root: &Utf8Path) -> Result<Vec<Utf8PathBuf>, GraphError> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries = std::fs::read_dir(&dir)
+            .map_err(|err| GraphError::io(format!("failed to read '{dir}'"), err))?;
+        let paths = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| Utf8PathBuf::try_from(entry.path()).ok())
+            .filter(|path| path.file_name().is_some_and(is_visible));
+        for path in paths {
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.extension() == Some("py") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Returns whether a directory entry should be searched for Python files.
+fn is_visible(name: &str) -> bool { !name.starts_with('.') && name != "__pycache__" }
@@ -0,0 +1,190 @@
+//! Resolution of `PyCG`'s dotted names to definitions in project sources.
+
+use std::collections::{BTreeMap, HashMap};
+
+use camino::{Utf8Path, Utf8PathBuf};
+
+use crate::{
+    edge::{CallEdge, EdgeSource},
+    error::GraphError,
+    graph::CallGraph,
+    node::{CallNode, NodeId, Position, SymbolKind},
+};
+
+/// Builds a call graph from `PyCG`'s JSON output for the project at `root`.
+///
+/// The output maps each fully qualified caller name to the names it calls.
+/// Names that do not resolve to a definition inside `root` are skipped,
+/// along with module-level entries, which are not callable symbols.
+pub(crate) fn graph_from_pycg(root: &Utf8Path, output: &[u8]) -> Result<CallGraph, GraphError> {
+    let calls: BTreeMap<String, Vec<String>> = serde_json::from_slice(output)
+        .map_err(|err| GraphError::validation(format!("invalid PyCG output: {err}")))?;
+
+    let mut resolver = Resolver::new(root);
+    let mut graph = CallGraph::new();
+    for (caller, callees) in &calls {
+        let Some(caller_id) = resolver.add(&mut graph, caller) else {
+            continue;
+        };
+        for callee in callees {
+            if let Some(callee_id) = resolver.add(&mut graph, callee) {
+                graph.add_edge(CallEdge::new(
+                    caller_id.clone(),
+                    callee_id,
+                    EdgeSource::Static,
+                ));
+            }
+        }
+    }
+    Ok(graph)
+}
+
+/// Maps `PyCG`'s dotted names onto definitions in the project's sources.
+struct Resolver<'r> {
+    root: &'r Utf8Path,
+    sources: HashMap<Utf8PathBuf, String>,
+    resolved: HashMap<String, Option<NodeId>>,
+}
+
+impl<'r> Resolver<'r> {
+    fn new(root: &'r Utf8Path) -> Self {
+        Self {
+            root,
+            sources: HashMap::new(),
+            resolved: HashMap::new(),
+        }
+    }
+
+    /// Adds the node named by `name` to `graph` and returns its ID, or
+    /// `None` when the name does not resolve to a project definition.
+    fn add(&mut self, graph: &mut CallGraph, name: &str) -> Option<NodeId> {
+        if let Some(resolved) = self.resolved.get(name) {
+            return resolved.clone();
+        }
+        let node = self.resolve(name);
+        let id = node.as_ref().map(|found| found.id().clone());
+        if let Some(found) = node {
+            graph.add_node(found);
+        }
+        self.resolved.insert(name.to_owned(), id.clone());
+        id
+    }
+
+    fn resolve(&mut self, name: &str) -> Option<CallNode> {
+        let parts: Vec<&str> = name.split('.').collect();
+        let (path, module_len) = locate_module(self.root, &parts)?;
+        let symbols = parts.get(module_len..).filter(|rest| !rest.is_empty())?;
+        let source = self.source(&path)?;
+        let (symbol, definition) = find_symbol(source, symbols)?;
+
+        let mut node = CallNode::new(
+            symbol.name,
+            definition.kind(&symbol),
+            path,
+            definition.position,
+        );
+        if let Some(container) = symbol.container {
+            node = node.with_container(container);
+        }
+        Some(node)
+    }
+
+    fn source(&mut self, path: &Utf8Path) -> Option<&str> {
+        if !self.sources.contains_key(path) {
+            let text = std::fs::read_to_string(path).ok()?;
+            self.sources.insert(path.to_path_buf(), text);
+        }
+        self.sources.get(path).map(String::as_str)
+    }
+}
+
+/// Finds the file defining the longest module prefix of `parts`, returning
+/// its path and the number of parts naming the module.
+fn locate_module(root: &Utf8Path, parts: &[&str]) -> Option<(Utf8PathBuf, usize)> {
+    (1..=parts.len()).rev().find_map(|len| {
+        let base = root.join(parts.get(..len)?.join("/"));
+        let module = base.with_extension("py");
+        let package = base.join("__init__.py");
+        [module, package]
+            .into_iter()
+            .find(|candidate| candidate.is_file())
+            .map(|path| (path, len))
+    })
+}
+
+/// A symbol named within a module, split into its name and container.
+struct Symbol<'n> {
+    name: &'n str,
+    container: Option<String>,
+    parent_is_class: bool,
+}
+
+/// A `def` or `class` statement located in a source file.
+struct Definition {
+    position: Position,
+    is_class: bool,
+}
+
+impl Definition {
+    fn kind(&self, symbol: &Symbol<'_>) -> SymbolKind {
+        if self.is_class || symbol.name == "__init__" {
+            SymbolKind::Constructor
+        } else if symbol.parent_is_class {
+            SymbolKind::Method
+        } else {
+            SymbolKind::Function
+        }
+    }
+}
+
+/// Locates the nested definition named by `symbols` in `source`.
+///
+/// Each enclosing definition is found first, and the search for the next
+/// name resumes after it, so methods that share a name in different classes
+/// resolve to the right definition.
+fn find_symbol<'n>(source: &str, symbols: &[&'n str]) -> Option<(Symbol<'n>, Definition)> {
+    let (name, parents) = symbols.split_last()?;
+    let lines: Vec<&str> = source.lines().collect();
+    let mut next_line = 0;
+    let mut parent_is_class = false;
+    for parent in parents {
+        let found = find_definition(&lines, next_line, parent)?;
+        next_line = usize::try_from(found.position.line).ok()? + 1;
+        parent_is_class = found.is_class;
+    }
+    let definition = find_definition(&lines, next_line, name)?;
+    let symbol = Symbol {
+        name,
+        container: (!parents.is_empty()).then(|| parents.join(".")),
+        parent_is_class,
+    };
+    Some((symbol, definition))
+}
+
+/// Finds the first `def`, `async def`, or `class` statement for `name` at or
+/// after `start_line`.
+fn find_definition(lines: &[&str], start_line: usize, name: &str) -> Option<Definition> {
+    lines
+        .iter()
+        .enumerate()
+        .skip(start_line)
+        .find_map(|(index, line)| {
+            let column = definition_column(line, name)?;
+            let position = Position::new(u32::try_from(index).ok()?, column);
+            let is_class = line.trim_start().starts_with("class ");
+            Some(Definition { position, is_class })
+        })
+}
+
+/// Returns the column of `name` when `line` defines it.
+fn definition_column(line: &str, name: &str) -> Option<u32> {
+    let body = line.trim_start();
+    let rest = ["async def ", "def ", "class "]
+        .into_iter()
+        .find_map(|keyword| body.strip_prefix(keyword))?;
+    let after = rest.strip_prefix(name)?;
+    if !after.starts_with(['(', ':', ' ']) {
+        return None;
+    }
+    u32::try_from(line.len() - rest.len()).ok()
+}
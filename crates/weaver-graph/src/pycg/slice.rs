@@ -0,0 +1,99 @@
+//! Depth-limited slices of a project-wide call graph.
+
+use std::collections::HashSet;
+
+use crate::{edge::CallEdge, graph::CallGraph, node::NodeId};
+
+/// Direction in which a slice follows edges away from its roots.
+#[derive(Clone, Copy)]
+pub(super) enum Direction {
+    Callers,
+    Callees,
+}
+
+impl Direction {
+    fn edges<'g>(self, graph: &'g CallGraph, node: &NodeId) -> Vec<&'g CallEdge> {
+        match self {
+            Self::Callers => graph.incoming_edges(node).collect(),
+            Self::Callees => graph.outgoing_edges(node).collect(),
+        }
+    }
+
+    const fn neighbour(self, edge: &CallEdge) -> &NodeId {
+        match self {
+            Self::Callers => edge.caller(),
+            Self::Callees => edge.callee(),
+        }
+    }
+}
+
+/// Copies the nodes within `depth` calls of `roots` in each direction, and
+/// the edges followed to reach them, into a new graph.
+pub(super) fn slice(
+    graph: &CallGraph,
+    roots: &[&NodeId],
+    depth: u32,
+    directions: &[Direction],
+) -> CallGraph {
+    let mut slicer = Slicer {
+        graph,
+        sliced: CallGraph::new(),
+    };
+    for root in roots {
+        slicer.copy_node(root);
+    }
+    for &direction in directions {
+        let mut seen: HashSet<&NodeId> = roots.iter().copied().collect();
+        let mut frontier = roots.to_vec();
+        for _ in 0..depth {
+            frontier = slicer.expand(&frontier, direction, &mut seen);
+        }
+    }
+    slicer.sliced
+}
+
+/// Accumulates a slice of `graph` while it is being traversed.
+struct Slicer<'g> {
+    graph: &'g CallGraph,
+    sliced: CallGraph,
+}
+
+impl<'g> Slicer<'g> {
+    /// Follows the edges leaving `frontier` in `direction`, returning the
+    /// newly reached nodes.
+    fn expand(
+        &mut self,
+        frontier: &[&'g NodeId],
+        direction: Direction,
+        seen: &mut HashSet<&'g NodeId>,
+    ) -> Vec<&'g NodeId> {
+        let graph = self.graph;
+        let mut reached = Vec::new();
+        let edges = frontier
+            .iter()
+            .flat_map(|node| direction.edges(graph, node));
+        for edge in edges {
+            let neighbour = direction.neighbour(edge);
+            if seen.insert(neighbour) {
+                self.copy_node(neighbour);
+                reached.push(neighbour);
+            }
+            if !self.contains_edge(edge) {
+                self.sliced.add_edge(edge.clone());
+            }
+        }
+        reached
+    }
+
+    fn contains_edge(&self, edge: &CallEdge) -> bool {
+        self.sliced
+            .outgoing_edges(edge.caller())
+            .any(|existing| existing == edge)
+    }
+
+    fn copy_node(&mut self, id: &NodeId) {
+        if let Some(node) = self.graph.node(id) {
+            self.sliced.add_node(node.clone());
+        }
+    }
+}
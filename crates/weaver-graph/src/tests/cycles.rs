@@ -0,0 +1,87 @@
+//! Tests for call cycle detection.
+
+use crate::{
+    edge::{CallEdge, EdgeSource},
+    graph::CallGraph,
+    node::{CallNode, NodeId, Position, SymbolKind},
+};
+
+/// Builds a graph of functions named by `names`, connected by `calls`
+/// expressed as `(caller, callee)` indices into `names`.
+fn graph_of(names: &[&str], calls: &[(usize, usize)]) -> (CallGraph, Vec<NodeId>) {
+    let mut graph = CallGraph::new();
+    let mut ids = Vec::new();
+    for (line, name) in (0..).zip(names) {
+        let node = CallNode::new(
+            *name,
+            SymbolKind::Function,
+            "/src/lib.rs",
+            Position::new(line, 0),
+        );
+        ids.push(node.id().clone());
+        graph.add_node(node);
+    }
+    for &(from, to) in calls {
+        let caller = ids.get(from).expect("caller index").clone();
+        let callee = ids.get(to).expect("callee index").clone();
+        graph.add_edge(CallEdge::new(caller, callee, EdgeSource::Lsp));
+    }
+    (graph, ids)
+}
+
+fn pick(ids: &[NodeId], indices: &[usize]) -> Vec<NodeId> {
+    indices
+        .iter()
+        .map(|&index| ids.get(index).expect("node index").clone())
+        .collect()
+}
+
+#[test]
+fn mutual_recursion_forms_one_cycle() {
+    let (graph, ids) = graph_of(&["a", "b"], &[(0, 1), (1, 0)]);
+
+    assert_eq!(graph.find_cycles(), vec![pick(&ids, &[0, 1])]);
+}
+
+#[test]
+fn self_recursive_node_forms_a_cycle() {
+    let (graph, ids) = graph_of(&["recurse", "leaf"], &[(0, 0), (0, 1)]);
+
+    assert_eq!(graph.find_cycles(), vec![pick(&ids, &[0])]);
+}
+
+#[test]
+fn acyclic_graph_has_no_cycles() {
+    let (graph, _) = graph_of(&["a", "b", "c"], &[(0, 1), (1, 2), (0, 2)]);
+
+    assert!(graph.find_cycles().is_empty());
+}
+
+#[test]
+fn separate_cycles_are_reported_in_order() {
+    let (graph, ids) = graph_of(
+        &["a", "b", "c", "d", "e", "f"],
+        &[
+            (0, 1),
+            (1, 2),
+            (2, 1),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (5, 3),
+            (4, 4),
+        ],
+    );
+
+    assert_eq!(
+        graph.find_cycles(),
+        vec![pick(&ids, &[1, 2]), pick(&ids, &[3, 4, 5])]
+    );
+}
+
+#[test]
+fn duplicate_edges_do_not_affect_cycles() {
+    let (graph, ids) = graph_of(&["a", "b"], &[(0, 1), (0, 1), (1, 0)]);
+
+    assert_eq!(graph.find_cycles(), vec![pick(&ids, &[0, 1])]);
+}
@@ -0,0 +1,125 @@
+//! Tests for Graphviz DOT export.
+
+use crate::{
+    edge::{CallEdge, EdgeSource},
+    graph::CallGraph,
+    node::{CallNode, Position, SymbolKind},
+};
+
+fn sample_graph() -> CallGraph {
+    let main = CallNode::new(
+        "main",
+        SymbolKind::Function,
+        "/src/main.rs",
+        Position::new(0, 0),
+    );
+    let run = CallNode::new(
+        "run",
+        SymbolKind::Method,
+        "/src/app.rs",
+        Position::new(12, 4),
+    )
+    .with_container("App");
+    let helper = CallNode::new(
+        "helper",
+        SymbolKind::Function,
+        "/src/app.rs",
+        Position::new(30, 0),
+    );
+
+    let edges = [
+        CallEdge::new(main.id().clone(), run.id().clone(), EdgeSource::Lsp)
+            .with_call_site(Position::new(2, 4)),
+        CallEdge::new(run.id().clone(), helper.id().clone(), EdgeSource::Static),
+        CallEdge::new(
+            main.id().clone(),
+            helper.id().clone(),
+            EdgeSource::DynamicProfiling,
+        ),
+    ];
+
+    let mut graph = CallGraph::new();
+    graph.add_node(main);
+    graph.add_node(run);
+    graph.add_node(helper);
+    for edge in edges {
+        graph.add_edge(edge);
+    }
+    graph
+}
+
+#[test]
+fn dot_output_is_a_digraph() {
+    let dot = sample_graph().to_dot();
+
+    assert!(dot.starts_with("digraph call_graph {\n"));
+    assert!(dot.ends_with("}\n"));
+}
+
+#[test]
+fn dot_output_declares_every_node_with_its_qualified_name() {
+    let graph = sample_graph();
+    let dot = graph.to_dot();
+
+    for node in graph.nodes() {
+        let declaration = format!(
+            "    \"{}\" [label=\"{}\"];",
+            node.id(),
+            node.qualified_name()
+        );
+        assert!(
+            dot.lines().any(|line| line == declaration),
+            "missing node declaration {declaration} in:\n{dot}"
+        );
+    }
+    assert!(dot.contains("[label=\"App.run\"]"));
+}
+
+#[test]
+fn dot_output_has_an_edge_line_for_every_call_edge() {
+    let graph = sample_graph();
+    let dot = graph.to_dot();
+
+    for edge in graph.edges() {
+        let line = format!(
+            "    \"{}\" -> \"{}\" [label=\"{}\"];",
+            edge.caller(),
+            edge.callee(),
+            edge.source()
+        );
+        assert!(
+            dot.lines().any(|candidate| candidate == line),
+            "missing edge line {line} in:\n{dot}"
+        );
+    }
+    assert_eq!(
+        dot.lines().filter(|line| line.contains(" -> ")).count(),
+        graph.edge_count()
+    );
+}
+
+#[test]
+fn dot_output_is_deterministic() {
+    assert_eq!(sample_graph().to_dot(), sample_graph().to_dot());
+}
+
+#[test]
+fn dot_output_escapes_quotes_and_backslashes() {
+    let mut graph = CallGraph::new();
+    graph.add_node(
+        CallNode::new(
+            "say\"hi\"",
+            SymbolKind::Function,
+            "C:\\src\\lib.rs",
+            Position::new(1, 2),
+        )
+        .with_container("Greeter"),
+    );
+
+    let dot = graph.to_dot();
+
+    assert!(
+        dot.contains(r#"    "C:\\src\\lib.rs:1:2:say\"hi\"" [label="Greeter.say\"hi\""];"#),
+        "unexpected escaping in:\n{dot}"
+    );
+}
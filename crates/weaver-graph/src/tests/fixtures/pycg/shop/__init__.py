@@ -0,0 +1,3 @@
+from shop.cart import checkout
+
+__all__ = ["checkout"]
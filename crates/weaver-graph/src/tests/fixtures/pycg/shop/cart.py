@@ -0,0 +1,20 @@
+from shop.pricing import apply_discount, tax
+
+
+class Cart:
+    def __init__(self):
+        self.items = []
+
+    def add(self, price):
+        self.items.append(price)
+
+    def total(self):
+        subtotal = apply_discount(sum(self.items), 0.1)
+        return subtotal + tax(subtotal)
+
+
+def checkout(prices):
+    cart = Cart()
+    for price in prices:
+        cart.add(price)
+    print(cart.total())
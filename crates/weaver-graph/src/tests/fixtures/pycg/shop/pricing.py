@@ -0,0 +1,6 @@
+def apply_discount(total, rate):
+    return round(total * (1 - rate), 2)
+
+
+def tax(total):
+    return total * 0.2
@@ -0,0 +1,142 @@
+//! Tests for JSON serialization of call graphs.
+
+use rstest::rstest;
+use serde_json::{Value, json};
+
+use crate::{
+    edge::{CallEdge, EdgeSource},
+    error::GraphError,
+    graph::CallGraph,
+    node::{CallNode, Position, SymbolKind},
+};
+
+/// Builds the caller/callee graph used by the graph operation tests.
+fn two_node_graph() -> CallGraph {
+    let caller = CallNode::new(
+        "caller",
+        SymbolKind::Function,
+        "/src/lib.rs",
+        Position::new(5, 0),
+    );
+    let callee = CallNode::new(
+        "callee",
+        SymbolKind::Method,
+        "/src/lib.rs",
+        Position::new(20, 4),
+    )
+    .with_container("Service");
+    let edge = CallEdge::new(caller.id().clone(), callee.id().clone(), EdgeSource::Lsp)
+        .with_call_site(Position::new(7, 8));
+
+    let mut graph = CallGraph::new();
+    graph.add_node(caller);
+    graph.add_node(callee);
+    graph.add_edge(edge);
+    graph
+}
+
+#[test]
+fn to_json_uses_the_documented_schema() {
+    assert_eq!(
+        two_node_graph().to_json(),
+        json!({
+            "nodes": [
+                {
+                    "id": "/src/lib.rs:20:4:callee",
+                    "name": "callee",
+                    "kind": "method",
+                    "path": "/src/lib.rs",
+                    "line": 20,
+                    "column": 4,
+                    "container": "Service"
+                },
+                {
+                    "id": "/src/lib.rs:5:0:caller",
+                    "name": "caller",
+                    "kind": "function",
+                    "path": "/src/lib.rs",
+                    "line": 5,
+                    "column": 0,
+                    "container": null
+                }
+            ],
+            "edges": [
+                {
+                    "caller": "/src/lib.rs:5:0:caller",
+                    "callee": "/src/lib.rs:20:4:callee",
+                    "source": "lsp",
+                    "call_site": { "line": 7, "column": 8 }
+                }
+            ]
+        })
+    );
+}
+
+#[test]
+fn json_round_trip_reloads_an_equal_graph() {
+    let graph = two_node_graph();
+
+    let reloaded = CallGraph::from_json(&graph.to_json()).expect("valid graph JSON");
+
+    assert_eq!(reloaded, graph);
+    let callee = graph.find_by_name("callee").expect("callee node").id();
+    assert_eq!(
+        reloaded
+            .callers_of(callee)
+            .map(CallNode::name)
+            .collect::<Vec<_>>(),
+        ["caller"]
+    );
+}
+
+#[rstest]
+#[case::static_analysis(EdgeSource::Static, "static")]
+#[case::dynamic_profiling(EdgeSource::DynamicProfiling, "dynamic")]
+fn edge_sources_serialize_as_their_display_labels(#[case] source: EdgeSource, #[case] label: &str) {
+    let value = serde_json::to_value(source).expect("serialize source");
+
+    assert_eq!(value, Value::from(label));
+    assert_eq!(source.to_string(), label);
+}
+
+#[rstest]
+#[case::not_an_object(json!([]), "invalid call graph JSON")]
+#[case::unknown_kind(
+    json!({
+        "nodes": [{
+            "id": "/a.rs:1:0:f", "name": "f", "kind": "macro", "path": "/a.rs",
+            "line": 1, "column": 0, "container": null
+        }],
+        "edges": []
+    }),
+    "invalid call graph JSON"
+)]
+#[case::mismatched_id(
+    json!({
+        "nodes": [{
+            "id": "/a.rs:9:9:g", "name": "f", "kind": "function", "path": "/a.rs",
+            "line": 1, "column": 0, "container": null
+        }],
+        "edges": []
+    }),
+    "does not match its location"
+)]
+#[case::unknown_endpoint(
+    json!({
+        "nodes": [],
+        "edges": [{
+            "caller": "/a.rs:1:0:f", "callee": "/a.rs:2:0:g", "source": "lsp",
+            "call_site": null
+        }]
+    }),
+    "edge refers to unknown node '/a.rs:1:0:f'"
+)]
+fn from_json_rejects_invalid_graphs(#[case] value: Value, #[case] fragment: &str) {
+    let error = CallGraph::from_json(&value).expect_err("graph JSON should be rejected");
+
+    assert!(matches!(error, GraphError::Validation(_)));
+    assert!(
+        error.to_string().contains(fragment),
+        "unexpected error: {error}"
+    );
+}
@@ -0,0 +1,82 @@
+//! Tests for merging call graphs from different sources.
+
+use crate::{
+    edge::{CallEdge, EdgeSource},
+    graph::CallGraph,
+    node::{CallNode, NodeId, Position, SymbolKind},
+};
+
+fn node(name: &str, line: u32) -> CallNode {
+    CallNode::new(
+        name,
+        SymbolKind::Function,
+        "/src/app.py",
+        Position::new(line, 4),
+    )
+}
+
+/// Builds a graph holding `main`, `load`, and `save`, with the given calls
+/// from `main` all reported by `source`.
+fn graph_with_calls(callees: &[&str], source: EdgeSource) -> (CallGraph, NodeId) {
+    let main = node("main", 0);
+    let main_id = main.id().clone();
+    let mut graph = CallGraph::new();
+    graph.add_node(main);
+    for (line, name) in (10..).step_by(10).zip(["load", "save"]) {
+        let callee = node(name, line);
+        if callees.contains(&name) {
+            graph.add_edge(CallEdge::new(main_id.clone(), callee.id().clone(), source));
+        }
+        graph.add_node(callee);
+    }
+    (graph, main_id)
+}
+
+fn id_of(graph: &CallGraph, name: &str) -> NodeId {
+    graph.find_by_name(name).expect("node exists").id().clone()
+}
+
+#[test]
+fn lsp_only_and_static_only_edges_coexist() {
+    let (mut graph, main) = graph_with_calls(&["load"], EdgeSource::Lsp);
+    let (static_graph, _) = graph_with_calls(&["save"], EdgeSource::Static);
+
+    graph.merge(static_graph);
+
+    assert_eq!(graph.node_count(), 3);
+    assert_eq!(graph.edge_count(), 2);
+    let load = id_of(&graph, "load");
+    let save = id_of(&graph, "save");
+    assert_eq!(
+        graph.edge_sources(&main, &load).collect::<Vec<_>>(),
+        [EdgeSource::Lsp]
+    );
+    assert_eq!(
+        graph.edge_sources(&main, &save).collect::<Vec<_>>(),
+        [EdgeSource::Static]
+    );
+}
+
+#[test]
+fn call_seen_by_two_sources_keeps_both_entries() {
+    let (mut graph, main) = graph_with_calls(&["load"], EdgeSource::Lsp);
+    let (static_graph, _) = graph_with_calls(&["load"], EdgeSource::Static);
+
+    graph.merge(static_graph);
+
+    let load = id_of(&graph, "load");
+    let mut sources: Vec<EdgeSource> = graph.edge_sources(&main, &load).collect();
+    sources.sort_by_key(ToString::to_string);
+    assert_eq!(sources, [EdgeSource::Lsp, EdgeSource::Static]);
+    assert_eq!(graph.callers_of(&load).count(), 2);
+}
+
+#[test]
+fn merging_a_graph_into_a_copy_adds_nothing() {
+    let (mut graph, _) = graph_with_calls(&["load", "save"], EdgeSource::Lsp);
+    let copy = graph.clone();
+
+    graph.merge(copy.clone());
+
+    assert_eq!(graph, copy);
+}
@@ -89,6 +89,30 @@ mod graph_tests {
         assert_eq!(callees.first().map(|n| n.name()), Some("callee"));
     }
 
+    #[test]
+    fn nodes_of_kind_excludes_other_kinds() {
+        let mut graph = CallGraph::new();
+        for (name, kind, line) in [
+            ("parse", SymbolKind::Function, 1),
+            ("render", SymbolKind::Method, 5),
+            ("new", SymbolKind::Constructor, 9),
+        ] {
+            graph.add_node(CallNode::new(
+                name,
+                kind,
+                "/src/lib.rs",
+                Position::new(line, 0),
+            ));
+        }
+
+        let functions: Vec<_> = graph.nodes_of_kind(SymbolKind::Function).collect();
+
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions.first().map(|n| n.name()), Some("parse"));
+        assert_eq!(graph.nodes_of_kind(SymbolKind::Method).count(), 1);
+        assert_eq!(graph.nodes_of_kind(SymbolKind::Property).count(), 0);
+    }
+
     #[test]
     fn find_by_name_works() {
         let mut graph = CallGraph::new();
@@ -180,5 +204,11 @@ mod edge_tests {
 }
 
 mod behaviour;
+mod cycles;
+mod dot;
+mod json;
+mod merge;
+mod path;
 mod provider;
+mod pycg;
 mod support;
@@ -0,0 +1,92 @@
+//! Tests for shortest call path queries.
+
+use rstest::rstest;
+
+use crate::{
+    edge::{CallEdge, EdgeSource},
+    graph::CallGraph,
+    node::{CallNode, NodeId, Position, SymbolKind},
+};
+
+/// Builds the graph
+///
+/// ```text
+/// main -> parse -> lex -> read
+///   |                       ^
+///   +------> load ----------+
+/// ```
+///
+/// plus an isolated `orphan` node, returning the IDs in declaration order.
+fn sample_graph() -> (CallGraph, Vec<NodeId>) {
+    let names = ["main", "parse", "lex", "read", "load", "orphan"];
+    let calls = [(0, 1), (1, 2), (2, 3), (0, 4), (4, 3)];
+
+    let mut graph = CallGraph::new();
+    let mut ids = Vec::new();
+    for (line, name) in (0..).zip(names) {
+        let node = CallNode::new(
+            name,
+            SymbolKind::Function,
+            "/src/lib.rs",
+            Position::new(line, 0),
+        );
+        ids.push(node.id().clone());
+        graph.add_node(node);
+    }
+    for (from, to) in calls {
+        graph.add_edge(CallEdge::new(id(&ids, from), id(&ids, to), EdgeSource::Lsp));
+    }
+    (graph, ids)
+}
+
+fn id(ids: &[NodeId], index: usize) -> NodeId { ids.get(index).expect("node index").clone() }
+
+fn names(graph: &CallGraph, path: &[NodeId]) -> Vec<String> {
+    path.iter()
+        .map(|node_id| {
+            graph
+                .get_node(node_id)
+                .expect("path node")
+                .name()
+                .to_owned()
+        })
+        .collect()
+}
+
+#[rstest]
+#[case::direct_call(0, 1, &["main", "parse"])]
+#[case::shortcut_beats_longer_chain(0, 3, &["main", "load", "read"])]
+#[case::multi_hop(1, 3, &["parse", "lex", "read"])]
+#[case::same_node(2, 2, &["lex"])]
+fn reachable_pairs_return_a_minimal_path(
+    #[case] from: usize,
+    #[case] to: usize,
+    #[case] expected: &[&str],
+) {
+    let (graph, ids) = sample_graph();
+
+    let path = graph
+        .shortest_path(&id(&ids, from), &id(&ids, to))
+        .expect("target is reachable");
+
+    assert_eq!(names(&graph, &path), expected);
+}
+
+#[rstest]
+#[case::against_call_direction(3, 0)]
+#[case::isolated_target(0, 5)]
+#[case::isolated_source(5, 0)]
+fn unreachable_pairs_return_none(#[case] from: usize, #[case] to: usize) {
+    let (graph, ids) = sample_graph();
+
+    assert_eq!(graph.shortest_path(&id(&ids, from), &id(&ids, to)), None);
+}
+
+#[test]
+fn unknown_source_returns_none() {
+    let (graph, ids) = sample_graph();
+    let missing = NodeId::new(&"/src/other.rs".into(), 0, 0, "missing");
+
+    assert_eq!(graph.shortest_path(&missing, &id(&ids, 0)), None);
+    assert_eq!(graph.shortest_path(&missing, &missing), None);
+}
@@ -13,9 +13,11 @@ use lsp_types::{
 
 use crate::{
     CallGraph,
+    CallNode,
     GraphError,
+    SymbolKind,
     provider::{CallGraphProvider, CallHierarchyClient, LspCallGraphProvider, SourcePosition},
-    tests::support::{Response, incoming_call, item, outgoing_call},
+    tests::support::{Response, incoming_call, item, item_of_kind, outgoing_call},
 };
 
 #[derive(Debug, Default)]
@@ -226,3 +228,105 @@ fn build_graph_propagates_prepare_error() {
         matches!(err, GraphError::Validation(_))
     });
 }
+
+/// Builds a provider for a function `a` that calls itself.
+fn recursive_provider(counts: &Arc<Mutex<CallCounts>>) -> LspCallGraphProvider<TestClient> {
+    let client = TestClient::new(
+        Response::Ok(Some(vec![item("a", 1, 1)])),
+        Response::Ok(Some(vec![incoming_call("a", 1, 1)])),
+        Response::Ok(Some(vec![outgoing_call("a", 1, 1)])),
+        Arc::clone(counts),
+    );
+    LspCallGraphProvider::new(client)
+}
+
+#[test]
+fn build_graph_expands_recursive_symbol_once() {
+    let counts = Arc::new(Mutex::new(CallCounts::default()));
+    let mut provider = recursive_provider(&counts);
+
+    let graph = build_graph(&mut provider, 3);
+
+    assert_eq!(graph.node_count(), 1);
+    assert_eq!(graph.edge_count(), 1);
+    let call_counts = counts.lock().expect("call count mutex poisoned");
+    assert_eq!(call_counts.incoming, 1);
+    assert_eq!(call_counts.outgoing, 1);
+}
+
+#[test]
+fn callees_graph_expands_recursive_symbol_once() {
+    let counts = Arc::new(Mutex::new(CallCounts::default()));
+    let mut provider = recursive_provider(&counts);
+    let position = SourcePosition::new("/src/main.rs", 1, 1);
+
+    let graph = provider
+        .callees_graph(&position, 3)
+        .expect("callees graph should build");
+
+    assert_eq!(graph.edge_count(), 1);
+    let call_counts = counts.lock().expect("call count mutex poisoned");
+    assert_eq!(call_counts.incoming, 0);
+    assert_eq!(call_counts.outgoing, 1);
+}
+
+/// Builds a provider whose every symbol calls a function `helper` and a
+/// method `render`.
+fn mixed_kind_provider(
+    root_kind: lsp_types::SymbolKind,
+    counts: &Arc<Mutex<CallCounts>>,
+) -> LspCallGraphProvider<TestClient> {
+    let callees = [
+        item("helper", 5, 0),
+        item_of_kind("render", lsp_types::SymbolKind::METHOD, (9, 4)),
+    ]
+    .into_iter()
+    .map(|to| CallHierarchyOutgoingCall {
+        from_ranges: Vec::new(),
+        to,
+    })
+    .collect();
+    let client = TestClient::new(
+        Response::Ok(Some(vec![item_of_kind("main", root_kind, (1, 1))])),
+        Response::Ok(None),
+        Response::Ok(Some(callees)),
+        Arc::clone(counts),
+    );
+    LspCallGraphProvider::new(client).with_expand_kinds([SymbolKind::Function])
+}
+
+#[test]
+fn kind_allowlist_keeps_methods_as_unexpanded_leaves() {
+    let counts = Arc::new(Mutex::new(CallCounts::default()));
+    let mut provider = mixed_kind_provider(lsp_types::SymbolKind::FUNCTION, &counts);
+
+    let graph = build_graph(&mut provider, 2);
+
+    let methods: Vec<&str> = graph
+        .nodes_of_kind(SymbolKind::Method)
+        .map(CallNode::name)
+        .collect();
+    assert_eq!(methods, ["render"]);
+    let mut functions: Vec<&str> = graph
+        .nodes_of_kind(SymbolKind::Function)
+        .map(CallNode::name)
+        .collect();
+    functions.sort_unstable();
+    assert_eq!(functions, ["helper", "main"]);
+    // `main` and `helper` are expanded; `render` is not.
+    let call_counts = counts.lock().expect("call count mutex poisoned");
+    assert_eq!(call_counts.outgoing, 2);
+}
+
+#[test]
+fn kind_allowlist_applies_to_the_starting_symbol() {
+    let counts = Arc::new(Mutex::new(CallCounts::default()));
+    let mut provider = mixed_kind_provider(lsp_types::SymbolKind::METHOD, &counts);
+
+    let graph = build_graph(&mut provider, 2);
+
+    assert_eq!(graph.node_count(), 1);
+    assert_eq!(graph.nodes_of_kind(SymbolKind::Function).count(), 0);
+    let call_counts = counts.lock().expect("call count mutex poisoned");
+    assert_eq!((call_counts.incoming, call_counts.outgoing), (0, 0));
+}
@@ -0,0 +1,135 @@
+//! Tests for the `PyCG` static call graph provider.
+//!
+//! The fixture package under `fixtures/pycg` is analysed by a stand-in
+//! script that prints output recorded from `PyCG`, so the tests run without
+//! the real tool installed.
+
+use rstest::{fixture, rstest};
+
+use crate::{
+    edge::EdgeSource,
+    error::GraphError,
+    graph::CallGraph,
+    node::{CallNode, SymbolKind},
+    provider::{CallGraphProvider, SourcePosition},
+    pycg::{PyCgCallGraphProvider, graph_from_pycg},
+};
+
+const FIXTURE_ROOT: &str = "src/tests/fixtures/pycg";
+const FAKE_PYCG: &str = "src/tests/fixtures/fake-pycg";
+
+#[fixture]
+fn provider() -> PyCgCallGraphProvider {
+    PyCgCallGraphProvider::new(FIXTURE_ROOT).with_command(FAKE_PYCG)
+}
+
+fn cart_position(line: u32) -> SourcePosition {
+    SourcePosition::new(format!("{FIXTURE_ROOT}/shop/cart.py"), line, 4)
+}
+
+fn sorted_names(graph: &CallGraph) -> Vec<String> {
+    let mut names: Vec<String> = graph.nodes().map(CallNode::qualified_name).collect();
+    names.sort();
+    names
+}
+
+#[cfg(unix)]
+#[rstest]
+fn project_graph_matches_snapshot(mut provider: PyCgCallGraphProvider) {
+    let graph = provider.analyse().expect("fixture analyses");
+
+    assert!(
+        graph
+            .edges()
+            .all(|edge| edge.source() == EdgeSource::Static)
+    );
+    insta::assert_snapshot!(graph.to_dot());
+}
+
+#[cfg(unix)]
+#[rstest]
+fn project_graph_classifies_definitions(mut provider: PyCgCallGraphProvider) {
+    let graph = provider.analyse().expect("fixture analyses");
+    let kind_of = |name: &str| graph.find_by_name(name).map(CallNode::kind);
+
+    assert_eq!(kind_of("checkout"), Some(SymbolKind::Function));
+    assert_eq!(kind_of("Cart.add"), Some(SymbolKind::Method));
+    assert_eq!(kind_of("Cart.__init__"), Some(SymbolKind::Constructor));
+    assert_eq!(kind_of("print"), None);
+}
+
+#[cfg(unix)]
+#[rstest]
+fn callees_graph_slices_from_the_symbol(mut provider: PyCgCallGraphProvider) {
+    let graph = provider
+        .callees_graph(&cart_position(15), 1)
+        .expect("checkout is defined on line 15");
+
+    assert_eq!(
+        sorted_names(&graph),
+        ["Cart.__init__", "Cart.add", "Cart.total", "checkout"]
+    );
+    assert_eq!(graph.edge_count(), 3);
+}
+
+#[cfg(unix)]
+#[rstest]
+fn callers_graph_follows_depth(mut provider: PyCgCallGraphProvider) {
+    let position = SourcePosition::new(format!("{FIXTURE_ROOT}/shop/pricing.py"), 4, 4);
+
+    let shallow = provider
+        .callers_graph(&position, 1)
+        .expect("tax is defined");
+    let deep = provider
+        .callers_graph(&position, 2)
+        .expect("tax is defined");
+
+    assert_eq!(sorted_names(&shallow), ["Cart.total", "tax"]);
+    assert_eq!(sorted_names(&deep), ["Cart.total", "checkout", "tax"]);
+}
+
+#[cfg(unix)]
+#[rstest]
+fn build_graph_combines_both_directions(mut provider: PyCgCallGraphProvider) {
+    let graph = provider
+        .build_graph(&cart_position(10), 1)
+        .expect("total is defined on line 10");
+
+    assert_eq!(
+        sorted_names(&graph),
+        ["Cart.total", "apply_discount", "checkout", "tax"]
+    );
+    assert_eq!(graph.edge_count(), 3);
+}
+
+#[cfg(unix)]
+#[rstest]
+fn unknown_position_reports_missing_symbol(mut provider: PyCgCallGraphProvider) {
+    let error = provider
+        .build_graph(&cart_position(2), 1)
+        .expect_err("line 2 defines nothing");
+
+    assert!(matches!(error, GraphError::SymbolNotFound { line: 2, .. }));
+}
+
+#[test]
+fn missing_tool_is_reported_gracefully() {
+    let mut provider =
+        PyCgCallGraphProvider::new(FIXTURE_ROOT).with_command("weaver-test-missing-pycg");
+
+    let error = provider.analyse().expect_err("tool is not installed");
+
+    assert!(matches!(
+        error,
+        GraphError::ToolUnavailable { ref tool } if tool == "weaver-test-missing-pycg"
+    ));
+}
+
+#[rstest]
+#[case::not_a_mapping(b"[]".as_slice())]
+#[case::callee_not_a_string(br#"{"shop.pricing.tax": [1]}"#.as_slice())]
+fn malformed_output_is_rejected(#[case] output: &[u8]) {
+    let error = graph_from_pycg(FIXTURE_ROOT.into(), output).expect_err("output is malformed");
+
+    assert!(matches!(error, GraphError::Validation(_)));
+}
@@ -42,9 +42,18 @@ fn range(line: u32, column: u32) -> Range {
 
 /// Builds a test call hierarchy item for the provided name and position.
 pub(super) fn item(name: &str, line: u32, column: u32) -> CallHierarchyItem {
+    item_of_kind(name, SymbolKind::FUNCTION, (line, column))
+}
+
+/// Builds a test call hierarchy item of the given symbol kind.
+pub(super) fn item_of_kind(
+    name: &str,
+    kind: SymbolKind,
+    (line, column): (u32, u32),
+) -> CallHierarchyItem {
     CallHierarchyItem {
         name: name.to_owned(),
-        kind: SymbolKind::FUNCTION,
+        kind,
         tags: None,
         detail: None,
         uri: test_uri(),
@@ -15,6 +15,9 @@ use lsp_types::{
     DidOpenTextDocumentParams,
     DocumentDiagnosticParams,
     DocumentDiagnosticReport,
+    DocumentSymbolClientCapabilities,
+    DocumentSymbolParams,
+    DocumentSymbolResponse,
     GeneralClientCapabilities,
     GotoDefinitionParams,
     GotoDefinitionResponse,
@@ -24,11 +27,14 @@ use lsp_types::{
     InitializeParams,
     InitializeResult,
     InitializedParams,
+    OneOf,
     PositionEncodingKind,
     ReferenceParams,
     TextDocumentClientCapabilities,
     TextDocumentIdentifier,
     Uri,
+    WorkspaceSymbolParams,
+    WorkspaceSymbolResponse,
 };
 use tracing::debug;
 
@@ -46,6 +52,10 @@ impl ProcessLanguageServer {
                 }),
                 text_document: Some(TextDocumentClientCapabilities {
                     call_hierarchy: Some(CallHierarchyClientCapabilities::default()),
+                    document_symbol: Some(DocumentSymbolClientCapabilities {
+                        hierarchical_document_symbol_support: Some(true),
+                        ..Default::default()
+                    }),
                     ..Default::default()
                 }),
                 ..Default::default()
@@ -92,6 +102,14 @@ impl ProcessLanguageServer {
         let diagnostics_supported = caps.diagnostic_provider.is_some();
         let call_hierarchy_supported = caps.call_hierarchy_provider.is_some();
         let hover_supported = supports_hover(&caps.hover_provider);
+        let document_symbols_supported = matches!(
+            caps.document_symbol_provider,
+            Some(OneOf::Left(true) | OneOf::Right(_))
+        );
+        let workspace_symbols_supported = matches!(
+            caps.workspace_symbol_provider,
+            Some(OneOf::Left(true) | OneOf::Right(_))
+        );
 
         debug!(
             target: ADAPTER_TARGET,
@@ -101,6 +119,8 @@ impl ProcessLanguageServer {
             diagnostics = diagnostics_supported,
             call_hierarchy = call_hierarchy_supported,
             hover = hover_supported,
+            document_symbols = document_symbols_supported,
+            workspace_symbols = workspace_symbols_supported,
             "language server initialized with capabilities"
         );
 
@@ -111,6 +131,8 @@ impl ProcessLanguageServer {
         )
         .with_call_hierarchy(call_hierarchy_supported)
         .with_hover(hover_supported)
+        .with_document_symbols(document_symbols_supported)
+        .with_workspace_symbols(workspace_symbols_supported)
         .with_position_encoding(position_encoding.cloned())
     }
 }
@@ -235,6 +257,22 @@ impl LanguageServer for ProcessLanguageServer {
         self.send_request_optional("textDocument/hover", params)
             .map_err(|e| LanguageServerError::with_source("hover request failed", e))
     }
+
+    fn document_symbols(
+        &mut self,
+        params: DocumentSymbolParams,
+    ) -> Result<Option<DocumentSymbolResponse>, LanguageServerError> {
+        self.send_request_optional("textDocument/documentSymbol", params)
+            .map_err(|e| LanguageServerError::with_source("documentSymbol request failed", e))
+    }
+
+    fn workspace_symbols(
+        &mut self,
+        params: WorkspaceSymbolParams,
+    ) -> Result<Option<WorkspaceSymbolResponse>, LanguageServerError> {
+        self.send_request_optional("workspace/symbol", params)
+            .map_err(|e| LanguageServerError::with_source("workspaceSymbol request failed", e))
+    }
 }
 
 fn supports_hover(capability: &Option<HoverProviderCapability>) -> bool {
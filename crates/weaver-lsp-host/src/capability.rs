@@ -19,6 +19,10 @@ pub enum CapabilityKind {
     CallHierarchy,
     /// `textDocument/hover`.
     Hover,
+    /// `textDocument/documentSymbol`.
+    DocumentSymbols,
+    /// `workspace/symbol`.
+    WorkspaceSymbols,
 }
 
 impl CapabilityKind {
@@ -31,6 +35,8 @@ impl CapabilityKind {
             Self::Diagnostics => "verify.diagnostics",
             Self::CallHierarchy => "observe.call-hierarchy",
             Self::Hover => "observe.get-card-hover",
+            Self::DocumentSymbols => "observe.document-symbols",
+            Self::WorkspaceSymbols => "observe.workspace-symbols",
         }
     }
 }
@@ -136,6 +142,8 @@ pub(crate) fn resolve_capabilities(
         CapabilityKind::Diagnostics,
         CapabilityKind::CallHierarchy,
         CapabilityKind::Hover,
+        CapabilityKind::DocumentSymbols,
+        CapabilityKind::WorkspaceSymbols,
     ] {
         let state = resolve_state(language, capability, &advertised, overrides);
         states.insert(capability, state);
@@ -184,6 +192,14 @@ fn resolve_state(
             let available = advertised.supports_hover();
             (available, capability_source(available))
         }
+        CapabilityKind::DocumentSymbols => {
+            let available = advertised.supports_document_symbols();
+            (available, capability_source(available))
+        }
+        CapabilityKind::WorkspaceSymbols => {
+            let available = advertised.supports_workspace_symbols();
+            (available, capability_source(available))
+        }
     };
 
     CapabilityState::new(capability, available, source)
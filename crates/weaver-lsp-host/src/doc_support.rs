@@ -20,8 +20,6 @@ use lsp_types::{
     DidOpenTextDocumentParams as DidOpenParams,
     GotoDefinitionParams,
     GotoDefinitionResponse,
-    Hover,
-    HoverParams,
     Location,
     ReferenceParams,
     Uri,
@@ -45,6 +43,8 @@ impl LanguageServer for DocStubServer {
             diagnostics: false,
             call_hierarchy: false,
             hover: false,
+            document_symbols: false,
+            workspace_symbols: false,
             position_encoding: None,
         })
     }
@@ -97,10 +97,6 @@ impl LanguageServer for DocStubServer {
     ) -> Result<Option<Vec<CallHierarchyOutgoingCall>>, LanguageServerError> {
         Ok(None)
     }
-
-    fn hover(&mut self, _params: HoverParams) -> Result<Option<Hover>, LanguageServerError> {
-        Ok(None)
-    }
 }
 
 /// Builds an [`LspHost`] with a registered Rust stub server.
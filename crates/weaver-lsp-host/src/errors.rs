@@ -11,7 +11,7 @@ use crate::{
 };
 
 /// Operation being executed when an error occurred.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum HostOperation {
     /// Server initialisation handshake.
     Initialise,
@@ -35,6 +35,10 @@ pub enum HostOperation {
     OutgoingCalls,
     /// `textDocument/hover` request.
     Hover,
+    /// `textDocument/documentSymbol` request.
+    DocumentSymbols,
+    /// `workspace/symbol` request.
+    WorkspaceSymbols,
 }
 
 impl fmt::Display for HostOperation {
@@ -51,6 +55,8 @@ impl fmt::Display for HostOperation {
             Self::IncomingCalls => "incomingCalls",
             Self::OutgoingCalls => "outgoingCalls",
             Self::Hover => "hover",
+            Self::DocumentSymbols => "documentSymbol",
+            Self::WorkspaceSymbols => "workspaceSymbol",
         };
         formatter.write_str(label)
     }
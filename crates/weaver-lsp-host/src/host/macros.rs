@@ -0,0 +1,55 @@
+//! Macros that generate the [`super::LspHost`] request and notification
+//! methods.
+//!
+//! Each generated method forwards to the matching [`crate::LanguageServer`]
+//! method, so adding an LSP request only needs one invocation in the host.
+
+macro_rules! lsp_method {
+    (
+        $(#[$meta:meta])* $vis:vis fn $name:ident(
+            &mut self,
+            language: Language,
+            $param:ident : $pty:ty $(,)?
+        ) -> $ret:ty {
+            $cap:expr,
+            $op:expr,
+            $server_method:ident
+        }
+    ) => {
+        $(#[$meta])* $vis fn $name(
+            &mut self,
+            language: Language,
+            $param: $pty,
+        ) -> $ret {
+            self.call_with_capability(
+                language,
+                CallSpec {
+                    capability: $cap,
+                    operation: $op,
+                },
+                move |server| server.$server_method($param),
+            )
+        }
+    };
+}
+
+macro_rules! lsp_notification {
+    (
+        $(#[$meta:meta])* $vis:vis fn $name:ident(
+            &mut self,
+            language: Language,
+            $param:ident : $pty:ty $(,)?
+        ) -> $ret:ty {
+            $op:expr,
+            $server_method:ident
+        }
+    ) => {
+        $(#[$meta])* $vis fn $name(
+            &mut self,
+            language: Language,
+            $param: $pty,
+        ) -> $ret {
+            self.call_on_server(language, $op, move |server| server.$server_method($param))
+        }
+    };
+}
@@ -1,6 +1,10 @@
 //! Host facade that mediates access to per-language servers.
 
-use std::collections::HashMap;
+#[macro_use]
+mod macros;
+mod workspace_symbols;
+
+use std::{collections::HashMap, time::Instant};
 
 use lsp_types::{
     CallHierarchyIncomingCall,
@@ -12,6 +16,8 @@ use lsp_types::{
     DidChangeTextDocumentParams,
     DidCloseTextDocumentParams,
     DidOpenTextDocumentParams,
+    DocumentSymbolParams,
+    DocumentSymbolResponse,
     GotoDefinitionParams,
     GotoDefinitionResponse,
     Hover,
@@ -24,6 +30,7 @@ use crate::{
     capability::{CapabilityKind, CapabilitySummary, resolve_capabilities},
     errors::{HostOperation, LspHostError},
     language::Language,
+    metrics::{LatencyMetrics, LatencyStats, record_latency},
     server::{LanguageServer, LanguageServerError},
 };
 
@@ -70,60 +77,11 @@ impl CallContext {
     }
 }
 
-macro_rules! lsp_method {
-    (
-        $(#[$meta:meta])* $vis:vis fn $name:ident(
-            &mut self,
-            language: Language,
-            $param:ident : $pty:ty $(,)?
-        ) -> $ret:ty {
-            $cap:expr,
-            $op:expr,
-            $server_method:ident
-        }
-    ) => {
-        $(#[$meta])* $vis fn $name(
-            &mut self,
-            language: Language,
-            $param: $pty,
-        ) -> $ret {
-            self.call_with_capability(
-                language,
-                CallSpec {
-                    capability: $cap,
-                    operation: $op,
-                },
-                move |server| server.$server_method($param),
-            )
-        }
-    };
-}
-
-macro_rules! lsp_notification {
-    (
-        $(#[$meta:meta])* $vis:vis fn $name:ident(
-            &mut self,
-            language: Language,
-            $param:ident : $pty:ty $(,)?
-        ) -> $ret:ty {
-            $op:expr,
-            $server_method:ident
-        }
-    ) => {
-        $(#[$meta])* $vis fn $name(
-            &mut self,
-            language: Language,
-            $param: $pty,
-        ) -> $ret {
-            self.call_on_server(language, $op, move |server| server.$server_method($param))
-        }
-    };
-}
-
 /// Orchestrates multiple language servers and applies capability overrides.
 pub struct LspHost {
     overrides: weaver_config::CapabilityMatrix,
     sessions: HashMap<Language, Session>,
+    metrics: LatencyMetrics,
 }
 
 impl LspHost {
@@ -133,6 +91,7 @@ impl LspHost {
         Self {
             overrides,
             sessions: HashMap::new(),
+            metrics: HashMap::new(),
         }
     }
 
@@ -177,6 +136,13 @@ impl LspHost {
             })
     }
 
+    /// Returns latency statistics for every request routed so far, keyed by
+    /// language and operation.
+    #[must_use]
+    pub fn metrics(&self) -> HashMap<(Language, HostOperation), LatencyStats> {
+        self.metrics.clone()
+    }
+
     lsp_method!(
         /// Routes a definition request to the configured language server.
         pub fn goto_definition(
@@ -218,7 +184,7 @@ impl LspHost {
 
     lsp_notification!(
         /// Notifies the server that a document has been opened with in-memory content.
-        #[doc = include_str!("../docs/did_open.md")]
+        #[doc = include_str!("../../docs/did_open.md")]
         pub fn did_open(
             &mut self,
             language: Language,
@@ -231,7 +197,7 @@ impl LspHost {
 
     lsp_notification!(
         /// Notifies the server that a document has changed with in-memory content.
-        #[doc = include_str!("../docs/did_change.md")]
+        #[doc = include_str!("../../docs/did_change.md")]
         pub fn did_change(
             &mut self,
             language: Language,
@@ -244,7 +210,7 @@ impl LspHost {
 
     lsp_notification!(
         /// Notifies the server that a document has been closed.
-        #[doc = include_str!("../docs/did_close.md")]
+        #[doc = include_str!("../../docs/did_close.md")]
         pub fn did_close(
             &mut self,
             language: Language,
@@ -310,6 +276,22 @@ impl LspHost {
         }
     );
 
+    lsp_method!(
+        /// Routes a document symbol request to the configured language server.
+        ///
+        /// The response may be a flat symbol list or a nested outline, depending
+        /// on what the server returns.
+        pub fn document_symbols(
+            &mut self,
+            language: Language,
+            params: DocumentSymbolParams,
+        ) -> Result<Option<DocumentSymbolResponse>, LspHostError> {
+            CapabilityKind::DocumentSymbols,
+            HostOperation::DocumentSymbols,
+            document_symbols
+        }
+    );
+
     fn call_with_context<F, T>(&mut self, context: CallContext, call: F) -> Result<T, LspHostError>
     where
         F: FnOnce(&mut dyn LanguageServer) -> Result<T, LanguageServerError>,
@@ -365,8 +347,14 @@ impl LspHost {
             }
         }
 
-        call(session.server.as_mut())
-            .map_err(|source| LspHostError::server(context.language, context.operation, source))
+        let started = Instant::now();
+        let result = call(session.server.as_mut());
+        record_latency(
+            &mut self.metrics,
+            (context.language, context.operation),
+            started.elapsed(),
+        );
+        result.map_err(|source| LspHostError::server(context.language, context.operation, source))
     }
 
     fn ensure_initialized(
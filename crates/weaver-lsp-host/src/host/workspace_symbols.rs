@@ -0,0 +1,104 @@
+//! Workspace symbol search fanned out across every registered server.
+
+use std::time::Instant;
+
+use lsp_types::{
+    Location,
+    OneOf,
+    Range,
+    SymbolInformation,
+    WorkspaceSymbol,
+    WorkspaceSymbolParams,
+    WorkspaceSymbolResponse,
+};
+
+use super::LspHost;
+use crate::{
+    capability::CapabilityKind,
+    errors::{HostOperation, LspHostError},
+    metrics::record_latency,
+};
+
+impl LspHost {
+    /// Searches every registered language server for symbols matching `query`.
+    ///
+    /// Servers whose resolved capabilities do not enable workspace symbols are
+    /// skipped rather than reported as errors. Results from all remaining
+    /// servers are merged and sorted by name, then by location, so callers see
+    /// the same order regardless of registration order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when a server fails to initialise or rejects the
+    /// request.
+    pub fn workspace_symbols(
+        &mut self,
+        query: &str,
+    ) -> Result<Vec<SymbolInformation>, LspHostError> {
+        let overrides = &self.overrides;
+        let mut symbols = Vec::new();
+        for (&language, session) in &mut self.sessions {
+            let summary = Self::ensure_initialized(language, session, overrides)?;
+            if !summary.state(CapabilityKind::WorkspaceSymbols).enabled {
+                continue;
+            }
+
+            let params = WorkspaceSymbolParams {
+                query: query.to_owned(),
+                ..WorkspaceSymbolParams::default()
+            };
+            let started = Instant::now();
+            let result = session.server.workspace_symbols(params);
+            record_latency(
+                &mut self.metrics,
+                (language, HostOperation::WorkspaceSymbols),
+                started.elapsed(),
+            );
+            let response = result.map_err(|source| {
+                LspHostError::server(language, HostOperation::WorkspaceSymbols, source)
+            })?;
+            symbols.extend(flatten_response(response));
+        }
+
+        symbols.sort_by(|left, right| {
+            left.name
+                .cmp(&right.name)
+                .then_with(|| left.location.uri.as_str().cmp(right.location.uri.as_str()))
+                .then_with(|| left.location.range.start.cmp(&right.location.range.start))
+        });
+        Ok(symbols)
+    }
+}
+
+fn flatten_response(response: Option<WorkspaceSymbolResponse>) -> Vec<SymbolInformation> {
+    match response {
+        None => Vec::new(),
+        Some(WorkspaceSymbolResponse::Flat(symbols)) => symbols,
+        Some(WorkspaceSymbolResponse::Nested(symbols)) => {
+            symbols.into_iter().map(into_symbol_information).collect()
+        }
+    }
+}
+
+/// Converts a `WorkspaceSymbol` into the flat representation.
+///
+/// Servers may omit the range and return only a URI, deferring the range to
+/// `workspaceSymbol/resolve`; such symbols point at the start of the document.
+#[expect(
+    deprecated,
+    reason = "SymbolInformation still requires the deprecated field"
+)]
+fn into_symbol_information(symbol: WorkspaceSymbol) -> SymbolInformation {
+    let location = match symbol.location {
+        OneOf::Left(location) => location,
+        OneOf::Right(location) => Location::new(location.uri, Range::default()),
+    };
+    SymbolInformation {
+        name: symbol.name,
+        kind: symbol.kind,
+        tags: symbol.tags,
+        deprecated: None,
+        location,
+        container_name: symbol.container_name,
+    }
+}
@@ -15,12 +15,16 @@ pub mod doc_support;
 mod errors;
 mod host;
 mod language;
+mod metrics;
 mod server;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
 
 pub use capability::{CapabilityKind, CapabilitySource, CapabilityState, CapabilitySummary};
 pub use errors::{HostOperation, LspHostError};
 pub use host::LspHost;
 pub use language::{Language, LanguageParseError};
+pub use metrics::LatencyStats;
 pub use server::{LanguageServer, LanguageServerError, ServerCapabilitySet};
 
 #[cfg(test)]
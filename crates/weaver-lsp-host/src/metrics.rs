@@ -0,0 +1,69 @@
+//! Request latency statistics collected by the host.
+
+use std::{collections::HashMap, time::Duration};
+
+use crate::{errors::HostOperation, language::Language};
+
+/// Summary of the time spent in requests for one language and operation.
+///
+/// Every routed request is recorded, whether the server answered it or failed,
+/// so slow error paths remain visible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyStats {
+    count: u64,
+    min: Duration,
+    max: Duration,
+    total: Duration,
+}
+
+impl LatencyStats {
+    pub(crate) const fn new(elapsed: Duration) -> Self {
+        Self {
+            count: 1,
+            min: elapsed,
+            max: elapsed,
+            total: elapsed,
+        }
+    }
+
+    pub(crate) fn record(&mut self, elapsed: Duration) {
+        self.count += 1;
+        self.min = self.min.min(elapsed);
+        self.max = self.max.max(elapsed);
+        self.total = self.total.saturating_add(elapsed);
+    }
+
+    /// Number of requests recorded.
+    #[must_use]
+    pub const fn count(&self) -> u64 { self.count }
+
+    /// Shortest recorded request.
+    #[must_use]
+    pub const fn min(&self) -> Duration { self.min }
+
+    /// Longest recorded request.
+    #[must_use]
+    pub const fn max(&self) -> Duration { self.max }
+
+    /// Mean duration across all recorded requests.
+    #[must_use]
+    pub fn mean(&self) -> Duration {
+        let nanos = self.total.as_nanos() / u128::from(self.count);
+        Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+    }
+}
+
+/// Latency statistics keyed by language and operation.
+pub(crate) type LatencyMetrics = HashMap<(Language, HostOperation), LatencyStats>;
+
+/// Adds `elapsed` to the statistics for `key`.
+pub(crate) fn record_latency(
+    metrics: &mut LatencyMetrics,
+    key: (Language, HostOperation),
+    elapsed: Duration,
+) {
+    metrics
+        .entry(key)
+        .and_modify(|stats| stats.record(elapsed))
+        .or_insert_with(|| LatencyStats::new(elapsed));
+}
@@ -13,6 +13,8 @@ use lsp_types::{
     DidChangeTextDocumentParams,
     DidCloseTextDocumentParams,
     DidOpenTextDocumentParams,
+    DocumentSymbolParams,
+    DocumentSymbolResponse,
     GotoDefinitionParams,
     GotoDefinitionResponse,
     Hover,
@@ -20,6 +22,8 @@ use lsp_types::{
     PositionEncodingKind,
     ReferenceParams,
     Uri,
+    WorkspaceSymbolParams,
+    WorkspaceSymbolResponse,
 };
 use thiserror::Error;
 
@@ -31,6 +35,8 @@ pub struct ServerCapabilitySet {
     pub(crate) diagnostics: bool,
     pub(crate) call_hierarchy: bool,
     pub(crate) hover: bool,
+    pub(crate) document_symbols: bool,
+    pub(crate) workspace_symbols: bool,
     pub(crate) position_encoding: Option<PositionEncodingKind>,
 }
 
@@ -44,6 +50,8 @@ impl ServerCapabilitySet {
             diagnostics,
             call_hierarchy: false,
             hover: false,
+            document_symbols: false,
+            workspace_symbols: false,
             position_encoding: None,
         }
     }
@@ -62,6 +70,20 @@ impl ServerCapabilitySet {
         self
     }
 
+    /// Builds a capability set with document symbol support.
+    #[must_use]
+    pub fn with_document_symbols(mut self, supported: bool) -> Self {
+        self.document_symbols = supported;
+        self
+    }
+
+    /// Builds a capability set with workspace symbol support.
+    #[must_use]
+    pub fn with_workspace_symbols(mut self, supported: bool) -> Self {
+        self.workspace_symbols = supported;
+        self
+    }
+
     /// Builds a capability set with position encoding.
     #[must_use]
     pub fn with_position_encoding(mut self, encoding: Option<PositionEncodingKind>) -> Self {
@@ -89,6 +111,14 @@ impl ServerCapabilitySet {
     #[must_use]
     pub const fn supports_hover(&self) -> bool { self.hover }
 
+    /// Whether the server reports support for `textDocument/documentSymbol`.
+    #[must_use]
+    pub const fn supports_document_symbols(&self) -> bool { self.document_symbols }
+
+    /// Whether the server reports support for `workspace/symbol`.
+    #[must_use]
+    pub const fn supports_workspace_symbols(&self) -> bool { self.workspace_symbols }
+
     /// Returns the negotiated position encoding.
     ///
     /// When `Some(PositionEncodingKind::UTF8)`, Tree-sitter byte offsets can be
@@ -193,7 +223,44 @@ pub trait LanguageServer: Send {
     ) -> Result<Option<Vec<CallHierarchyOutgoingCall>>, LanguageServerError>;
 
     /// Handles a `textDocument/hover` request.
-    fn hover(&mut self, params: HoverParams) -> Result<Option<Hover>, LanguageServerError>;
+    ///
+    /// The default implementation reports hover as unsupported, so servers
+    /// without hover support need not implement it. Hosts only route hover
+    /// requests here when the capability is advertised or forced.
+    fn hover(&mut self, params: HoverParams) -> Result<Option<Hover>, LanguageServerError> {
+        let _ = params;
+        Err(LanguageServerError::new(
+            "textDocument/hover is not supported by this language server",
+        ))
+    }
+
+    /// Handles a `textDocument/documentSymbol` request.
+    ///
+    /// Like [`LanguageServer::hover`], the default implementation reports the
+    /// request as unsupported.
+    fn document_symbols(
+        &mut self,
+        params: DocumentSymbolParams,
+    ) -> Result<Option<DocumentSymbolResponse>, LanguageServerError> {
+        let _ = params;
+        Err(LanguageServerError::new(
+            "textDocument/documentSymbol is not supported by this language server",
+        ))
+    }
+
+    /// Handles a `workspace/symbol` request.
+    ///
+    /// Like [`LanguageServer::hover`], the default implementation reports the
+    /// request as unsupported.
+    fn workspace_symbols(
+        &mut self,
+        params: WorkspaceSymbolParams,
+    ) -> Result<Option<WorkspaceSymbolResponse>, LanguageServerError> {
+        let _ = params;
+        Err(LanguageServerError::new(
+            "workspace/symbol is not supported by this language server",
+        ))
+    }
 }
 
 impl fmt::Debug for dyn LanguageServer {
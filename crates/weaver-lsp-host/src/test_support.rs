@@ -1,4 +1,7 @@
-//! Recording language server used in tests.
+//! Recording language server test double.
+//!
+//! Enable the `test-support` feature to use [`RecordingLanguageServer`] from
+//! other crates' tests.
 
 use std::sync::{Arc, Mutex};
 
@@ -13,6 +16,8 @@ use lsp_types::{
     DidChangeTextDocumentParams,
     DidCloseTextDocumentParams,
     DidOpenTextDocumentParams,
+    DocumentSymbolParams,
+    DocumentSymbolResponse,
     GotoDefinitionParams,
     GotoDefinitionResponse,
     Hover,
@@ -20,6 +25,8 @@ use lsp_types::{
     Location,
     ReferenceParams,
     Uri,
+    WorkspaceSymbolParams,
+    WorkspaceSymbolResponse,
 };
 
 use crate::server::{LanguageServer, LanguageServerError, ServerCapabilitySet};
@@ -49,6 +56,10 @@ pub enum CallKind {
     OutgoingCalls,
     /// `textDocument/hover` was invoked.
     Hover,
+    /// `textDocument/documentSymbol` was invoked.
+    DocumentSymbols,
+    /// `workspace/symbol` was invoked.
+    WorkspaceSymbols,
 }
 
 /// Test double that records every request routed through it.
@@ -156,17 +167,23 @@ impl LanguageServer for RecordingLanguageServer {
 
     fn references(
         &mut self,
-        _params: ReferenceParams,
+        params: ReferenceParams,
     ) -> Result<Vec<Location>, LanguageServerError> {
+        with_state(&self.shared, |state| state.reference_requests.push(params));
         self.handle_request(CallKind::References, "references", |responses| {
             responses.references.clone()
         })
     }
 
     fn diagnostics(&mut self, _uri: Uri) -> Result<Vec<Diagnostic>, LanguageServerError> {
-        self.handle_request(CallKind::Diagnostics, "diagnostics", |responses| {
-            responses.diagnostics.clone()
-        })
+        self.validate_and_execute(
+            CallKind::Diagnostics,
+            "diagnostics",
+            |responses| match &responses.diagnostics_error {
+                Some(message) => Err(LanguageServerError::new(message.clone())),
+                None => Ok(responses.diagnostics.clone()),
+            },
+        )
     }
 
     fn did_open(&mut self, _params: DidOpenTextDocumentParams) -> Result<(), LanguageServerError> {
@@ -227,6 +244,24 @@ impl LanguageServer for RecordingLanguageServer {
             responses.hover.clone()
         })
     }
+
+    fn document_symbols(
+        &mut self,
+        _params: DocumentSymbolParams,
+    ) -> Result<Option<DocumentSymbolResponse>, LanguageServerError> {
+        self.handle_request(CallKind::DocumentSymbols, "documentSymbol", |responses| {
+            responses.document_symbols.clone()
+        })
+    }
+
+    fn workspace_symbols(
+        &mut self,
+        _params: WorkspaceSymbolParams,
+    ) -> Result<Option<WorkspaceSymbolResponse>, LanguageServerError> {
+        self.handle_request(CallKind::WorkspaceSymbols, "workspaceSymbol", |responses| {
+            responses.workspace_symbols.clone()
+        })
+    }
 }
 
 /// Handle that exposes recorded state for assertions.
@@ -238,6 +273,11 @@ pub struct RecordingServerHandle {
 impl RecordingServerHandle {
     /// Returns the ordered list of calls the server observed.
     pub fn calls(&self) -> Vec<CallKind> { with_state(&self.shared, |state| state.calls.clone()) }
+
+    /// Returns the parameters of every references request, in call order.
+    pub fn reference_requests(&self) -> Vec<ReferenceParams> {
+        with_state(&self.shared, |state| state.reference_requests.clone())
+    }
 }
 
 fn with_state<R, F>(shared: &Arc<Mutex<RecordingState>>, action: F) -> R
@@ -257,12 +297,18 @@ pub struct ResponseSet {
     pub references: Vec<Location>,
     /// Response returned for diagnostics requests.
     pub diagnostics: Vec<Diagnostic>,
+    /// Error returned for diagnostics requests instead of `diagnostics`.
+    pub diagnostics_error: Option<String>,
     /// Errors returned for document sync notifications.
     pub document_sync: DocumentSyncErrors,
     /// Responses for call hierarchy requests.
     pub call_hierarchy: CallHierarchyResponses,
     /// Response returned for hover requests.
     pub hover: Option<Hover>,
+    /// Response returned for document symbol requests.
+    pub document_symbols: Option<DocumentSymbolResponse>,
+    /// Response returned for workspace symbol requests.
+    pub workspace_symbols: Option<WorkspaceSymbolResponse>,
 }
 
 impl Default for ResponseSet {
@@ -271,9 +317,12 @@ impl Default for ResponseSet {
             definition: GotoDefinitionResponse::Array(Vec::new()),
             references: Vec::new(),
             diagnostics: Vec::new(),
+            diagnostics_error: None,
             document_sync: DocumentSyncErrors::default(),
             call_hierarchy: CallHierarchyResponses::default(),
             hover: None,
+            document_symbols: None,
+            workspace_symbols: None,
         }
     }
 }
@@ -305,6 +354,7 @@ struct RecordingState {
     capabilities: ServerCapabilitySet,
     responses: ResponseSet,
     calls: Vec<CallKind>,
+    reference_requests: Vec<ReferenceParams>,
     initialised: bool,
     fail_initialise: Option<String>,
 }
@@ -319,6 +369,7 @@ impl RecordingState {
             capabilities,
             responses,
             calls: Vec::new(),
+            reference_requests: Vec::new(),
             initialised: false,
             fail_initialise,
         }
@@ -326,55 +377,3 @@ impl RecordingState {
 
     fn record_call(&mut self, kind: CallKind) { self.calls.push(kind); }
 }
-
-#[cfg(test)]
-mod tests {
-    //! Unit tests for the recording server test double.
-
-    use lsp_types::{
-        HoverContents,
-        MarkupContent,
-        MarkupKind,
-        Position,
-        TextDocumentIdentifier,
-        TextDocumentPositionParams,
-    };
-
-    use super::*;
-
-    #[test]
-    fn records_and_returns_hover_calls() {
-        let expected_hover = Hover {
-            contents: HoverContents::Markup(MarkupContent {
-                kind: MarkupKind::Markdown,
-                value: String::from("```rust\nfn greet()\n```"),
-            }),
-            range: None,
-        };
-        let responses = ResponseSet {
-            hover: Some(expected_hover.clone()),
-            ..ResponseSet::default()
-        };
-        let mut server = RecordingLanguageServer::new(
-            ServerCapabilitySet::new(false, false, false).with_hover(true),
-            responses,
-        );
-        let handle = server.handle();
-
-        server.initialize().expect("server should initialise");
-        let hover = server
-            .hover(HoverParams {
-                text_document_position_params: TextDocumentPositionParams {
-                    text_document: TextDocumentIdentifier {
-                        uri: "file:///test.rs".parse().expect("uri should parse"),
-                    },
-                    position: Position::new(0, 0),
-                },
-                work_done_progress_params: Default::default(),
-            })
-            .expect("hover should succeed");
-
-        assert_eq!(hover, Some(expected_hover));
-        assert_eq!(handle.calls(), vec![CallKind::Initialise, CallKind::Hover]);
-    }
-}
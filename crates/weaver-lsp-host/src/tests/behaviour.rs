@@ -37,7 +37,9 @@ fn given_all_languages(world: &RefCell<TestWorld>) {
     let responses = sample_responses();
     let all_caps = ServerCapabilitySet::new(true, true, true)
         .with_call_hierarchy(true)
-        .with_hover(true);
+        .with_hover(true)
+        .with_document_symbols(true)
+        .with_workspace_symbols(true);
     let configs = vec![
         TestServerConfig {
             language: Language::Rust,
@@ -348,9 +350,12 @@ fn sample_responses() -> ResponseSet {
             range: lsp_types::Range::default(),
         }],
         diagnostics: vec![Diagnostic::default()],
+        diagnostics_error: None,
         document_sync: DocumentSyncErrors::default(),
         call_hierarchy: Default::default(),
         hover: None,
+        document_symbols: None,
+        workspace_symbols: None,
     }
 }
 
@@ -0,0 +1,59 @@
+//! Tests for the per-language request latency metrics.
+
+use std::time::Duration;
+
+use rstest::rstest;
+use weaver_config::CapabilityMatrix;
+
+use crate::{
+    LspHost,
+    errors::HostOperation,
+    language::Language,
+    metrics::LatencyStats,
+    server::ServerCapabilitySet,
+    tests::support::{RecordingLanguageServer, ResponseSet, definition_params},
+};
+
+fn host_with_definition_server() -> LspHost {
+    let server = RecordingLanguageServer::new(
+        ServerCapabilitySet::new(true, false, false),
+        ResponseSet::default(),
+    );
+    let mut host = LspHost::new(CapabilityMatrix::default());
+    host.register_language(Language::Rust, Box::new(server))
+        .expect("registration failed");
+    host
+}
+
+fn definition_count(host: &LspHost) -> Option<u64> {
+    host.metrics()
+        .get(&(Language::Rust, HostOperation::Definition))
+        .map(LatencyStats::count)
+}
+
+#[rstest]
+fn definition_calls_increment_the_request_count() {
+    let mut host = host_with_definition_server();
+    assert_eq!(definition_count(&host), None);
+
+    host.goto_definition(Language::Rust, definition_params())
+        .expect("definition should succeed");
+    assert_eq!(definition_count(&host), Some(1));
+
+    host.goto_definition(Language::Rust, definition_params())
+        .expect("definition should succeed");
+    assert_eq!(definition_count(&host), Some(2));
+    assert_eq!(host.metrics().len(), 1, "only definition was routed");
+}
+
+#[rstest]
+fn stats_track_min_max_and_mean() {
+    let mut stats = LatencyStats::new(Duration::from_millis(30));
+    stats.record(Duration::from_millis(10));
+    stats.record(Duration::from_millis(20));
+
+    assert_eq!(stats.count(), 3);
+    assert_eq!(stats.min(), Duration::from_millis(10));
+    assert_eq!(stats.max(), Duration::from_millis(30));
+    assert_eq!(stats.mean(), Duration::from_millis(20));
+}
@@ -2,5 +2,7 @@
 
 mod adapter_behaviour;
 mod behaviour;
+mod metrics;
+mod optional_requests;
 mod support;
 mod unit;
@@ -0,0 +1,311 @@
+//! Tests for hover, document symbol, and workspace symbol routing through the
+//! host.
+//!
+//! All three requests are optional server features, so the tests cover the
+//! recording server's canned responses and the capability checks that stop a
+//! request before it reaches the backend. Workspace symbol searches also fan
+//! out across every registered server.
+
+use lsp_types::{
+    DocumentSymbolParams,
+    DocumentSymbolResponse,
+    Hover,
+    HoverContents,
+    HoverParams,
+    Location,
+    MarkupContent,
+    MarkupKind,
+    Range,
+    SymbolInformation,
+    SymbolKind,
+    TextDocumentIdentifier,
+    WorkspaceSymbolResponse,
+};
+use rstest::rstest;
+use weaver_config::{CapabilityMatrix, CapabilityOverride};
+
+use crate::{
+    LspHost,
+    capability::{CapabilityKind, CapabilitySource},
+    errors::LspHostError,
+    language::Language,
+    server::{LanguageServer, ServerCapabilitySet},
+    test_support::RecordingServerHandle,
+    tests::support::{CallKind, RecordingLanguageServer, ResponseSet, hover_params, sample_uri},
+};
+
+fn host_with_server(
+    capabilities: ServerCapabilitySet,
+    responses: ResponseSet,
+    overrides: CapabilityMatrix,
+) -> (LspHost, RecordingServerHandle) {
+    let server = RecordingLanguageServer::new(capabilities, responses);
+    let handle = server.handle();
+    let mut host = LspHost::new(overrides);
+    host.register_language(Language::Rust, Box::new(server))
+        .expect("registration failed");
+    (host, handle)
+}
+
+fn document_symbol_params() -> DocumentSymbolParams {
+    DocumentSymbolParams {
+        text_document: TextDocumentIdentifier { uri: sample_uri() },
+        work_done_progress_params: lsp_types::WorkDoneProgressParams::default(),
+        partial_result_params: lsp_types::PartialResultParams::default(),
+    }
+}
+
+fn symbol(name: &str, kind: SymbolKind, line: u32) -> SymbolInformation {
+    symbol_in(sample_uri(), name, kind, line)
+}
+
+#[expect(deprecated, reason = "SymbolInformation::deprecated must still be set")]
+fn symbol_in(uri: lsp_types::Uri, name: &str, kind: SymbolKind, line: u32) -> SymbolInformation {
+    SymbolInformation {
+        name: name.to_owned(),
+        kind,
+        tags: None,
+        deprecated: None,
+        location: Location {
+            uri,
+            range: Range::new(
+                lsp_types::Position::new(line, 0),
+                lsp_types::Position::new(line, 10),
+            ),
+        },
+        container_name: None,
+    }
+}
+
+#[rstest]
+fn records_and_returns_hover_calls() {
+    let expected_hover = Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: String::from("```rust\nfn greet()\n```"),
+        }),
+        range: None,
+    };
+    let responses = ResponseSet {
+        hover: Some(expected_hover.clone()),
+        ..ResponseSet::default()
+    };
+    let mut server = RecordingLanguageServer::new(
+        ServerCapabilitySet::new(false, false, false).with_hover(true),
+        responses,
+    );
+    let handle = server.handle();
+
+    server.initialize().expect("server should initialise");
+    let hover = server
+        .hover(HoverParams {
+            text_document_position_params: lsp_types::TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier {
+                    uri: "file:///test.rs".parse().expect("uri should parse"),
+                },
+                position: lsp_types::Position::new(0, 0),
+            },
+            work_done_progress_params: Default::default(),
+        })
+        .expect("hover should succeed");
+
+    assert_eq!(hover, Some(expected_hover));
+    assert_eq!(handle.calls(), vec![CallKind::Initialise, CallKind::Hover]);
+}
+
+#[rstest]
+fn routes_hover_to_recording_server() {
+    let expected = Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: String::from("```rust\nfn main()\n```"),
+        }),
+        range: None,
+    };
+    let responses = ResponseSet {
+        hover: Some(expected.clone()),
+        ..ResponseSet::default()
+    };
+    let (mut host, handle) = host_with_server(
+        ServerCapabilitySet::new(true, true, true).with_hover(true),
+        responses,
+        CapabilityMatrix::default(),
+    );
+
+    let hover = host
+        .hover(Language::Rust, hover_params())
+        .expect("hover should succeed");
+
+    assert_eq!(hover, Some(expected));
+    assert_eq!(handle.calls(), vec![CallKind::Initialise, CallKind::Hover]);
+}
+
+#[rstest]
+fn rejects_hover_when_server_does_not_advertise_it() {
+    let (mut host, handle) = host_with_server(
+        ServerCapabilitySet::new(true, true, true),
+        ResponseSet::default(),
+        CapabilityMatrix::default(),
+    );
+
+    match host.hover(Language::Rust, hover_params()) {
+        Err(LspHostError::CapabilityUnavailable {
+            capability, reason, ..
+        }) => {
+            assert_eq!(capability, CapabilityKind::Hover);
+            assert_eq!(reason, CapabilitySource::MissingOnServer);
+        }
+        other => panic!("expected capability error, got {other:?}"),
+    }
+    assert_eq!(handle.calls(), vec![CallKind::Initialise]);
+}
+
+#[rstest]
+fn routes_flat_document_symbols_to_recording_server() {
+    let expected = DocumentSymbolResponse::Flat(vec![
+        symbol("Config", SymbolKind::STRUCT, 0),
+        symbol("main", SymbolKind::FUNCTION, 4),
+    ]);
+    let responses = ResponseSet {
+        document_symbols: Some(expected.clone()),
+        ..ResponseSet::default()
+    };
+    let (mut host, handle) = host_with_server(
+        ServerCapabilitySet::new(false, false, false).with_document_symbols(true),
+        responses,
+        CapabilityMatrix::default(),
+    );
+
+    let symbols = host
+        .document_symbols(Language::Rust, document_symbol_params())
+        .expect("document symbols should succeed");
+
+    assert_eq!(symbols, Some(expected));
+    assert_eq!(
+        handle.calls(),
+        vec![CallKind::Initialise, CallKind::DocumentSymbols]
+    );
+}
+
+#[rstest]
+fn denied_document_symbols_never_reach_the_server() {
+    let mut overrides = CapabilityMatrix::default();
+    overrides.set_override(
+        Language::Rust.as_str(),
+        CapabilityKind::DocumentSymbols.key(),
+        CapabilityOverride::Deny,
+    );
+    let (mut host, handle) = host_with_server(
+        ServerCapabilitySet::new(false, false, false).with_document_symbols(true),
+        ResponseSet::default(),
+        overrides,
+    );
+
+    match host.document_symbols(Language::Rust, document_symbol_params()) {
+        Err(LspHostError::CapabilityUnavailable {
+            capability, reason, ..
+        }) => {
+            assert_eq!(capability, CapabilityKind::DocumentSymbols);
+            assert_eq!(reason, CapabilitySource::DeniedOverride);
+        }
+        other => panic!("expected capability error, got {other:?}"),
+    }
+    assert_eq!(handle.calls(), vec![CallKind::Initialise]);
+}
+
+fn workspace_symbol_server(
+    host: &mut LspHost,
+    language: Language,
+    capabilities: ServerCapabilitySet,
+    symbols: Vec<SymbolInformation>,
+) -> RecordingServerHandle {
+    let responses = ResponseSet {
+        workspace_symbols: Some(WorkspaceSymbolResponse::Flat(symbols)),
+        ..ResponseSet::default()
+    };
+    let server = RecordingLanguageServer::new(capabilities, responses);
+    let handle = server.handle();
+    host.register_language(language, Box::new(server))
+        .expect("registration failed");
+    handle
+}
+
+#[rstest]
+fn merges_workspace_symbols_from_every_server() {
+    let python_uri: lsp_types::Uri = "file:///workspace/app.py"
+        .parse()
+        .expect("uri should parse");
+    let supported = ServerCapabilitySet::new(false, false, false).with_workspace_symbols(true);
+    let mut host = LspHost::new(CapabilityMatrix::default());
+    let rust = workspace_symbol_server(
+        &mut host,
+        Language::Rust,
+        supported.clone(),
+        vec![
+            symbol("main", SymbolKind::FUNCTION, 4),
+            symbol("Config", SymbolKind::STRUCT, 0),
+        ],
+    );
+    let python = workspace_symbol_server(
+        &mut host,
+        Language::Python,
+        supported,
+        vec![symbol_in(
+            python_uri.clone(),
+            "load_config",
+            SymbolKind::FUNCTION,
+            2,
+        )],
+    );
+
+    let symbols = host
+        .workspace_symbols("config")
+        .expect("workspace symbols should succeed");
+
+    assert_eq!(
+        symbols,
+        vec![
+            symbol("Config", SymbolKind::STRUCT, 0),
+            symbol_in(python_uri, "load_config", SymbolKind::FUNCTION, 2),
+            symbol("main", SymbolKind::FUNCTION, 4),
+        ]
+    );
+    for handle in [rust, python] {
+        assert_eq!(
+            handle.calls(),
+            vec![CallKind::Initialise, CallKind::WorkspaceSymbols]
+        );
+    }
+}
+
+#[rstest]
+fn workspace_symbols_skip_servers_without_the_capability() {
+    let mut host = LspHost::new(CapabilityMatrix::default());
+    let rust = workspace_symbol_server(
+        &mut host,
+        Language::Rust,
+        ServerCapabilitySet::new(false, false, false).with_workspace_symbols(true),
+        vec![symbol("main", SymbolKind::FUNCTION, 4)],
+    );
+    let python = workspace_symbol_server(
+        &mut host,
+        Language::Python,
+        ServerCapabilitySet::new(false, false, false),
+        vec![symbol("ignored", SymbolKind::FUNCTION, 0)],
+    );
+
+    let symbols = host
+        .workspace_symbols("main")
+        .expect("workspace symbols should succeed");
+
+    assert_eq!(symbols, vec![symbol("main", SymbolKind::FUNCTION, 4)]);
+    assert_eq!(
+        python.calls(),
+        vec![CallKind::Initialise],
+        "servers without workspace symbols must not be queried"
+    );
+    assert_eq!(
+        rust.calls(),
+        vec![CallKind::Initialise, CallKind::WorkspaceSymbols]
+    );
+}
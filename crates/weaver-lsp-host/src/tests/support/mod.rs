@@ -1,6 +1,5 @@
 //! Shared fixtures and helpers for host tests.
 
-mod recording_server;
 mod world;
 
 use std::str::FromStr;
@@ -10,6 +9,7 @@ use lsp_types::{
     DidCloseTextDocumentParams,
     DidOpenTextDocumentParams,
     GotoDefinitionParams,
+    HoverParams,
     ReferenceContext,
     ReferenceParams,
     TextDocumentContentChangeEvent,
@@ -19,11 +19,12 @@ use lsp_types::{
     Uri,
     VersionedTextDocumentIdentifier,
 };
-pub use recording_server::{CallKind, DocumentSyncErrors, RecordingLanguageServer, ResponseSet};
 use rstest::fixture;
 use weaver_test_macros::allow_fixture_expansion_lints;
 pub use world::{TestServerConfig, TestWorld};
 
+pub use crate::test_support::{CallKind, DocumentSyncErrors, RecordingLanguageServer, ResponseSet};
+
 /// Common URI used by host tests.
 #[allow_fixture_expansion_lints]
 #[fixture]
@@ -42,6 +43,18 @@ pub fn definition_params() -> GotoDefinitionParams {
     }
 }
 
+/// Builds a hover request for the sample URI.
+#[must_use]
+pub fn hover_params() -> HoverParams {
+    HoverParams {
+        text_document_position_params: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier { uri: sample_uri() },
+            position: lsp_types::Position::new(1, 2),
+        },
+        work_done_progress_params: lsp_types::WorkDoneProgressParams::default(),
+    }
+}
+
 /// Builds a references request for the sample URI.
 #[must_use]
 pub fn reference_params() -> ReferenceParams {
@@ -12,18 +12,13 @@ use lsp_types::{
     Uri,
 };
 
-use super::recording_server::{
-    CallKind,
-    RecordingLanguageServer,
-    RecordingServerHandle,
-    ResponseSet,
-};
 use crate::{
     LspHost,
     capability::CapabilitySummary,
     errors::LspHostError,
     language::Language,
     server::ServerCapabilitySet,
+    test_support::{CallKind, RecordingLanguageServer, RecordingServerHandle, ResponseSet},
 };
 
 /// Configuration used to seed a stub server for a language.
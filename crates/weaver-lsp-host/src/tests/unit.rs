@@ -3,7 +3,7 @@
 use std::str::FromStr;
 
 use rstest::rstest;
-use weaver_config::{CapabilityMatrix, CapabilityOverride};
+use weaver_config::{CapabilityMatrix, CapabilityOverride, KNOWN_CAPABILITIES, KNOWN_LANGUAGES};
 
 use crate::{
     capability::{CapabilityKind, CapabilitySource},
@@ -19,6 +19,7 @@ use crate::{
         did_change_params,
         did_close_params,
         did_open_params,
+        hover_params,
         sample_uri,
     },
 };
@@ -104,13 +105,6 @@ macro_rules! failing_server {
             {
                 Ok(None)
             }
-
-            fn hover(
-                &mut self,
-                _params: lsp_types::HoverParams,
-            ) -> Result<Option<lsp_types::Hover>, LanguageServerError> {
-                Ok(None)
-            }
         }
     };
 }
@@ -176,6 +170,31 @@ fn applies_force_and_deny_overrides() {
     assert_eq!(diagnostics.source, CapabilitySource::DeniedOverride);
 }
 
+#[rstest]
+fn override_keys_are_accepted_by_configuration_validation() {
+    for language in [Language::Rust, Language::Python, Language::TypeScript] {
+        assert!(
+            KNOWN_LANGUAGES.contains(&language.as_str()),
+            "weaver-config rejects overrides for {language}"
+        );
+    }
+    for capability in [
+        CapabilityKind::Definition,
+        CapabilityKind::References,
+        CapabilityKind::Diagnostics,
+        CapabilityKind::CallHierarchy,
+        CapabilityKind::Hover,
+        CapabilityKind::DocumentSymbols,
+        CapabilityKind::WorkspaceSymbols,
+    ] {
+        assert!(
+            KNOWN_CAPABILITIES.contains(&capability.key()),
+            "weaver-config rejects overrides for {}",
+            capability.key()
+        );
+    }
+}
+
 #[rstest]
 fn parses_known_languages() {
     assert_eq!(
@@ -274,6 +293,29 @@ fn propagates_server_error_from_did_close() {
     });
 }
 
+#[rstest]
+fn forced_hover_reaches_default_unsupported_implementation() {
+    let mut overrides = CapabilityMatrix::default();
+    overrides.set_override(
+        Language::Rust.as_str(),
+        CapabilityKind::Hover.key(),
+        CapabilityOverride::Force,
+    );
+    let mut host = crate::LspHost::new(overrides);
+    host.register_language(Language::Rust, Box::new(FailingDefinitionServer))
+        .expect("registration failed");
+
+    match host.hover(Language::Rust, hover_params()) {
+        Err(LspHostError::Server {
+            operation, source, ..
+        }) => {
+            assert_eq!(operation, HostOperation::Hover);
+            assert!(source.message().contains("not supported"), "{source}");
+        }
+        other => panic!("expected server error, got {other:?}"),
+    }
+}
+
 #[rstest]
 fn calls_initialise_before_requests() {
     assert_initialise_before(
@@ -1,42 +1,131 @@
-//! Argument parsing for `rename-symbol` plugin requests.
+//! Argument parsing for `rename-symbol` and `extract-method` plugin requests.
 //!
 //! Validates and extracts the `uri`, `position`, and `new_name` fields from a
 //! rename-symbol plugin request, converting the `position` string to the byte
-//! offset required by the rope adapter.
+//! offset required by the rope adapter. Callers may instead supply one-indexed
+//! `line` and `column` coordinates, which are resolved against the target
+//! file once its content is known. Extract-method requests carry a
+//! `start_offset`/`end_offset` byte range and the `new_name` of the method.
+//! Both operations reject a `new_name` that is not a legal Python identifier.
 
-use std::collections::HashMap;
+use std::{collections::HashMap, ops::Range};
+
+use crate::identifier::identifier_error;
+
+/// Location of the symbol to rename, as supplied by the caller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RenamePosition {
+    /// Explicit UTF-8 byte offset from the `position` field.
+    Offset(usize),
+    /// One-indexed line and Unicode-character column.
+    LineColumn { line: u32, column: u32 },
+}
 
 /// Validated rename-symbol arguments extracted from a plugin request.
 pub(crate) struct RenameSymbolArgs {
-    offset: usize,
+    position: RenamePosition,
     new_name: String,
 }
 
 impl RenameSymbolArgs {
-    /// Returns the byte offset parsed from the `position` field.
-    pub(crate) const fn offset(&self) -> usize { self.offset }
+    /// Resolves the requested position to a byte offset within `content`.
+    ///
+    /// Explicit offsets are returned unchanged; line/column coordinates are
+    /// converted by scanning `content` as UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// Returns a human-readable error message when the coordinates fall
+    /// outside `content`.
+    pub(crate) fn offset_in(&self, content: &str) -> Result<usize, String> {
+        match self.position {
+            RenamePosition::Offset(offset) => Ok(offset),
+            RenamePosition::LineColumn { line, column } => {
+                line_column_to_byte_offset(content, line, column)
+            }
+        }
+    }
 
     /// Returns the new symbol name.
     pub(crate) fn new_name(&self) -> &str { &self.new_name }
 }
 
+/// Validated extract-method arguments extracted from a plugin request.
+pub(crate) struct ExtractMethodArgs {
+    range: Range<usize>,
+    new_name: String,
+}
+
+impl ExtractMethodArgs {
+    /// Returns the byte range of the code to extract.
+    pub(crate) fn range(&self) -> Range<usize> { self.range.clone() }
+
+    /// Returns the name of the extracted method.
+    pub(crate) fn new_name(&self) -> &str { &self.new_name }
+}
+
 /// Parses and validates rename-symbol arguments from the request map.
 ///
 /// Expects `uri` (non-empty string), `position` (parseable as `usize`), and
-/// `new_name` (non-empty string). The `uri` is validated for presence but the
-/// file payload in the request is authoritative for content.
+/// `new_name` (a valid Python identifier). The `uri` is validated for presence
+/// but the file payload in the request is authoritative for content. When
+/// `position` is absent, `line` and `column` (each parseable as a positive
+/// `u32`) are accepted instead; an explicit `position` always takes
+/// precedence.
 ///
 /// # Errors
 ///
 /// Returns a human-readable error message if any required field is missing,
-/// has the wrong type, or is empty.
+/// has the wrong type, or is empty, or if `new_name` is not a legal Python
+/// identifier.
 pub(crate) fn parse_rename_symbol_arguments(
     arguments: &HashMap<String, serde_json::Value>,
 ) -> Result<RenameSymbolArgs, String> {
     validate_uri(arguments)?;
-    let offset = parse_position(arguments)?;
-    let new_name = parse_new_name(arguments)?;
-    Ok(RenameSymbolArgs { offset, new_name })
+    let position = parse_position(arguments)?;
+    let new_name = parse_new_name(arguments, "rename-symbol")?;
+    Ok(RenameSymbolArgs { position, new_name })
+}
+
+/// Parses and validates extract-method arguments from the request map.
+///
+/// Expects `start_offset` and `end_offset` (each parseable as `usize`, with
+/// `start_offset < end_offset`) and a `new_name` that is a valid Python
+/// identifier.
+///
+/// # Errors
+///
+/// Returns a human-readable error message if any required field is missing,
+/// has the wrong type, or describes an empty range.
+pub(crate) fn parse_extract_method_arguments(
+    arguments: &HashMap<String, serde_json::Value>,
+) -> Result<ExtractMethodArgs, String> {
+    let start = parse_extract_offset(arguments, "start_offset")?;
+    let end = parse_extract_offset(arguments, "end_offset")?;
+    if start >= end {
+        return Err(format!(
+            "start_offset ({start}) must be less than end_offset ({end})"
+        ));
+    }
+    let new_name = parse_new_name(arguments, "extract-method")?;
+    Ok(ExtractMethodArgs {
+        range: start..end,
+        new_name,
+    })
+}
+
+/// Parses one of the extract-method byte offsets.
+fn parse_extract_offset(
+    arguments: &HashMap<String, serde_json::Value>,
+    key: &str,
+) -> Result<usize, String> {
+    let value = arguments
+        .get(key)
+        .ok_or_else(|| format!("extract-method operation requires '{key}' argument"))?;
+    let text = json_value_to_string(value)
+        .ok_or_else(|| format!("{key} argument must be a string or number"))?;
+    text.parse::<usize>()
+        .map_err(|error| format!("{key} must be a non-negative integer: {error}"))
 }
 
 /// Validates that `uri` is present and non-empty.
@@ -53,29 +142,108 @@ fn validate_uri(arguments: &HashMap<String, serde_json::Value>) -> Result<(), St
     Ok(())
 }
 
-/// Parses `position` as a byte offset.
-fn parse_position(arguments: &HashMap<String, serde_json::Value>) -> Result<usize, String> {
-    let position_value = arguments
-        .get("position")
-        .ok_or_else(|| String::from("rename-symbol operation requires 'position' argument"))?;
+/// Parses `position` as a byte offset, falling back to `line`/`column`.
+fn parse_position(
+    arguments: &HashMap<String, serde_json::Value>,
+) -> Result<RenamePosition, String> {
+    let Some(position_value) = arguments.get("position") else {
+        return parse_line_column(arguments);
+    };
     let position_string = json_value_to_string(position_value)
         .ok_or_else(|| String::from("position argument must be a string or number"))?;
     position_string
         .parse::<usize>()
+        .map(RenamePosition::Offset)
         .map_err(|error| format!("position must be a non-negative integer: {error}"))
 }
 
-/// Parses and validates `new_name`.
-fn parse_new_name(arguments: &HashMap<String, serde_json::Value>) -> Result<String, String> {
+/// Parses one-indexed `line` and `column` coordinates.
+fn parse_line_column(
+    arguments: &HashMap<String, serde_json::Value>,
+) -> Result<RenamePosition, String> {
+    let (Some(line_value), Some(column_value)) = (arguments.get("line"), arguments.get("column"))
+    else {
+        return Err(String::from(
+            "rename-symbol operation requires 'position' argument or 'line' and 'column' arguments",
+        ));
+    };
+    let line = parse_coordinate("line", line_value)?;
+    let column = parse_coordinate("column", column_value)?;
+    Ok(RenamePosition::LineColumn { line, column })
+}
+
+fn parse_coordinate(name: &str, value: &serde_json::Value) -> Result<u32, String> {
+    let text = json_value_to_string(value)
+        .ok_or_else(|| format!("{name} argument must be a string or number"))?;
+    match text.parse::<u32>() {
+        Ok(0) => Err(format!("{name} must be >= 1")),
+        Ok(coordinate) => Ok(coordinate),
+        Err(error) => Err(format!("{name} must be a positive integer: {error}")),
+    }
+}
+
+/// Converts a one-indexed line and Unicode-character column to a byte offset.
+///
+/// The column may point one character past the end of the line so callers
+/// can address a symbol that ends the line.
+fn line_column_to_byte_offset(content: &str, line: u32, column: u32) -> Result<usize, String> {
+    let line_count = content.split_inclusive('\n').count();
+    let Some((line_start, target_line)) = line_entry(content, line) else {
+        return Err(format!(
+            "line {line} is outside the file, which has {line_count} line(s)"
+        ));
+    };
+
+    let visible = target_line
+        .strip_suffix('\n')
+        .map_or(target_line, |text| text.strip_suffix('\r').unwrap_or(text));
+    let char_count = visible.chars().count();
+    let column_index = (column as usize).saturating_sub(1);
+    if column_index > char_count {
+        return Err(format!(
+            "column {column} is outside line {line}, which has {char_count} character(s); the \
+             file has {line_count} line(s)"
+        ));
+    }
+
+    let column_offset = visible
+        .char_indices()
+        .nth(column_index)
+        .map_or(visible.len(), |(offset, _)| offset);
+    Ok(line_start + column_offset)
+}
+
+/// Returns the byte offset and text of the one-indexed `line`.
+fn line_entry(content: &str, line: u32) -> Option<(usize, &str)> {
+    let mut start = 0usize;
+    for (index, text) in content.split_inclusive('\n').enumerate() {
+        if index + 1 == line as usize {
+            return Some((start, text));
+        }
+        start += text.len();
+    }
+    None
+}
+
+/// Parses `new_name` and validates it as a Python identifier.
+fn parse_new_name(
+    arguments: &HashMap<String, serde_json::Value>,
+    operation: &str,
+) -> Result<String, String> {
     let new_name_value = arguments
         .get("new_name")
-        .ok_or_else(|| String::from("rename-symbol operation requires 'new_name' argument"))?;
+        .ok_or_else(|| format!("{operation} operation requires 'new_name' argument"))?;
     let new_name = new_name_value
         .as_str()
         .ok_or_else(|| String::from("new_name argument must be a string"))?;
     if new_name.trim().is_empty() {
         return Err(String::from("new_name argument must not be empty"));
     }
+    if let Some(reason) = identifier_error(new_name) {
+        return Err(format!(
+            "new_name '{new_name}' is not a valid Python identifier: {reason}"
+        ));
+    }
     Ok(String::from(new_name))
 }
 
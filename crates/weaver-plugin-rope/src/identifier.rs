@@ -0,0 +1,62 @@
+//! Python identifier validation for refactoring names.
+//!
+//! Rope accepts whatever name it is given and only fails once it has parsed
+//! the project, producing a traceback that does not explain the real problem.
+//! Checking the name up front against Python's lexical rules lets the plugin
+//! reject it with a precise diagnostic before any subprocess is spawned.
+
+use unicode_ident::{is_xid_continue, is_xid_start};
+
+/// Reserved words that can never be used as Python identifiers.
+///
+/// Soft keywords such as `match`, `case`, and `type` are deliberately absent
+/// because they remain valid names outside their special syntax.
+const PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// Returns `true` when `name` is a legal Python identifier that is not a
+/// reserved keyword.
+///
+/// Follows the Python 3 lexical rules: the first character must be a letter
+/// (Unicode `XID_Start`) or underscore, and every following character a
+/// letter, digit, or underscore (Unicode `XID_Continue`).
+///
+/// # Examples
+///
+/// ```
+/// use weaver_plugin_rope::is_valid_python_identifier;
+///
+/// assert!(is_valid_python_identifier("renamed_value"));
+/// assert!(is_valid_python_identifier("café"));
+/// assert!(!is_valid_python_identifier("2foo"));
+/// assert!(!is_valid_python_identifier("class"));
+/// ```
+#[must_use]
+pub fn is_valid_python_identifier(name: &str) -> bool { identifier_error(name).is_none() }
+
+/// Explains why `name` is not a legal Python identifier, or returns `None`
+/// when it is.
+pub(crate) fn identifier_error(name: &str) -> Option<String> {
+    let mut characters = name.chars();
+    let Some(first) = characters.next() else {
+        return Some(String::from("it is empty"));
+    };
+    if first != '_' && !is_xid_start(first) {
+        return Some(format!(
+            "it must start with a letter or underscore, not '{first}'"
+        ));
+    }
+    if let Some(invalid) = characters.find(|character| !is_xid_continue(*character)) {
+        return Some(format!(
+            "it may only contain letters, digits, and underscores, but contains '{invalid}'"
+        ));
+    }
+    if PYTHON_KEYWORDS.contains(&name) {
+        return Some(format!("'{name}' is a reserved Python keyword"));
+    }
+    None
+}
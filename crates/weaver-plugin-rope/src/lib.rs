@@ -1,118 +1,91 @@
 //! Rope-backed actuator plugin entrypoint and request dispatcher.
 //!
-//! This crate implements a one-shot plugin protocol handler compatible with
-//! `weaver-plugins`. The plugin reads exactly one JSONL request from stdin,
+//! This crate implements a plugin protocol handler compatible with
+//! `weaver-plugins`. By default the plugin reads one JSONL request from stdin,
 //! executes a refactoring operation, and writes one JSONL response to stdout.
+//! In persistent mode ([`run_persistent`]) it keeps one rope server alive and
+//! answers each request line with one response line until stdin is closed.
 
 mod arguments;
+mod identifier;
+mod persistent;
+mod python;
+mod traceback;
 mod workspace_fs;
 
 #[cfg(test)]
 mod tests;
 
 use std::{
+    collections::BTreeMap,
     fmt,
     io::{BufRead, Write},
+    ops::Range,
     path::{Component, Path, PathBuf},
-    process::Command,
+    time::Duration,
 };
 
-use tempfile::TempDir;
 use thiserror::Error;
 use weaver_plugins::{
     capability::ReasonCode,
     protocol::{
         DiagnosticSeverity,
         FilePayload,
+        PING_OPERATION,
         PluginDiagnostic,
         PluginOutput,
         PluginRequest,
         PluginResponse,
+        build_multi_file_patch,
+        search_replace_patch,
     },
 };
 
-use crate::arguments::parse_rename_symbol_arguments;
 pub(crate) use crate::workspace_fs::write_workspace_file;
+use crate::{
+    arguments::{parse_extract_method_arguments, parse_rename_symbol_arguments},
+    traceback::{SourceLocation, locate_failure, summary_line},
+};
+pub use crate::{
+    identifier::is_valid_python_identifier,
+    persistent::PersistentRopeAdapter,
+    python::{DEFAULT_PYTHON_TIMEOUT, PythonRopeAdapter},
+};
 
-const PYTHON_BINARY: &str = "python3";
-const PYTHON_RENAME_SCRIPT: &str = concat!(
-    "import os,sys\n",
-    "from rope.base.project import Project\n",
-    "from rope.refactor.rename import Rename\n",
-    "root, rel_path, offset_s, new_name = sys.argv[1:5]\n",
-    "offset = int(offset_s)\n",
-    "project = Project(root)\n",
-    "try:\n",
-    "    resource = project.get_resource(rel_path)\n",
-    "    renamer = Rename(project, resource, offset)\n",
-    "    changes = renamer.get_changes(new_name)\n",
-    "    project.do(changes)\n",
-    "    with open(os.path.join(root, rel_path), 'r', encoding='utf-8') as handle:\n",
-    "        sys.stdout.write(handle.read())\n",
-    "finally:\n",
-    "    project.close()\n",
-);
+/// Post-refactoring content of every file touched by a rope change set,
+/// keyed by slash-separated workspace-relative path.
+pub type ChangedFiles = BTreeMap<String, String>;
 
 /// Refactoring adapter abstraction used to keep behaviour deterministic in tests.
 pub trait RopeAdapter {
-    /// Executes a rename operation and returns the modified file content.
+    /// Executes a rename operation and returns every modified file.
+    ///
+    /// The symbol at `offset` is resolved in the first entry of `files`. The
+    /// remaining files are materialized alongside it so rope can update
+    /// importers and other references outside the target file.
     ///
     /// # Errors
     ///
     /// Returns an error if the adapter cannot complete the operation.
     fn rename(
         &self,
-        file: &FilePayload,
+        files: &[FilePayload],
         offset: usize,
         new_name: &str,
-    ) -> Result<String, RopeAdapterError>;
-}
-
-/// Adapter that delegates to the Python `rope` library.
-pub struct PythonRopeAdapter;
+    ) -> Result<ChangedFiles, RopeAdapterError>;
 
-impl RopeAdapter for PythonRopeAdapter {
-    fn rename(
+    /// Extracts the byte range of `file` into a new method and returns the
+    /// modified file content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the adapter cannot complete the operation.
+    fn extract_method(
         &self,
         file: &FilePayload,
-        offset: usize,
+        range: Range<usize>,
         new_name: &str,
-    ) -> Result<String, RopeAdapterError> {
-        let workspace =
-            TempDir::new().map_err(|source| RopeAdapterError::WorkspaceCreate { source })?;
-        write_workspace_file(workspace.path(), file.path(), file.content())?;
-
-        let relative_path = path_to_slash(file.path());
-        let mut command = Command::new(PYTHON_BINARY);
-        command.arg("-c");
-        command.arg(PYTHON_RENAME_SCRIPT);
-        command.arg(workspace.path());
-        command.arg(relative_path);
-        command.arg(offset.to_string());
-        command.arg(new_name);
-
-        let output = command
-            .output()
-            .map_err(|source| RopeAdapterError::Spawn { source })?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_owned();
-            return Err(RopeAdapterError::EngineFailed {
-                message: if stderr.is_empty() {
-                    String::from("python rope adapter failed without stderr output")
-                } else {
-                    stderr
-                },
-            });
-        }
-
-        let modified =
-            String::from_utf8(output.stdout).map_err(|source| RopeAdapterError::InvalidOutput {
-                message: source.to_string(),
-            })?;
-
-        Ok(modified)
-    }
+    ) -> Result<String, RopeAdapterError>;
 }
 
 /// Errors raised while dispatching plugin requests.
@@ -178,6 +151,12 @@ pub enum RopeAdapterError {
         /// Validation message.
         message: String,
     },
+    /// The Python adapter did not finish before its deadline and was killed.
+    #[error("python rope adapter timed out after {limit:?}")]
+    Timeout {
+        /// Configured timeout that was exceeded.
+        limit: Duration,
+    },
 }
 
 /// Structured failure carrying an optional reason code for diagnostics.
@@ -185,6 +164,7 @@ pub enum RopeAdapterError {
 pub(crate) struct PluginFailure {
     message: String,
     reason_code: Option<ReasonCode>,
+    location: Option<SourceLocation>,
 }
 
 impl PluginFailure {
@@ -193,6 +173,7 @@ impl PluginFailure {
         Self {
             message: message.into(),
             reason_code: None,
+            location: None,
         }
     }
 
@@ -201,6 +182,36 @@ impl PluginFailure {
         Self {
             message: message.into(),
             reason_code: Some(reason),
+            location: None,
+        }
+    }
+
+    /// Converts an adapter error into a failure.
+    ///
+    /// Engine failures are searched for the request file and line that rope
+    /// blamed; when one is found the diagnostic carries that location and
+    /// the traceback is condensed to its final exception line. Otherwise the
+    /// raw error text is kept so nothing is lost.
+    pub(crate) fn from_adapter_error(
+        error: &RopeAdapterError,
+        files: &[FilePayload],
+        engine_reason: Option<ReasonCode>,
+    ) -> Self {
+        let RopeAdapterError::EngineFailed { message } = error else {
+            return Self::plain(error.to_string());
+        };
+        let location = locate_failure(message, files);
+        let text = match (&location, summary_line(message)) {
+            (Some(_), Some(summary)) => RopeAdapterError::EngineFailed {
+                message: summary.to_owned(),
+            }
+            .to_string(),
+            _ => error.to_string(),
+        };
+        Self {
+            message: text,
+            reason_code: engine_reason,
+            location,
         }
     }
 }
@@ -244,7 +255,40 @@ pub fn run_with_adapter<R: RopeAdapter>(
 ///
 /// Returns an error if the response cannot be written.
 pub fn run(stdin: &mut impl BufRead, stdout: &mut impl Write) -> Result<(), PluginDispatchError> {
-    run_with_adapter(stdin, stdout, &PythonRopeAdapter)
+    run_with_adapter(stdin, stdout, &PythonRopeAdapter::default())
+}
+
+/// Executes plugin requests from `stdin` until end of input, writing one
+/// response line per request line.
+///
+/// Unlike [`run_with_adapter`], the same `adapter` serves every request, so
+/// adapters that keep state between calls (such as [`PersistentRopeAdapter`])
+/// can amortize their start-up cost.
+///
+/// # Errors
+///
+/// Returns an error if any response cannot be serialized or written.
+pub fn serve_with_adapter<R: RopeAdapter>(
+    stdin: &mut impl BufRead,
+    stdout: &mut impl Write,
+    adapter: &R,
+) -> Result<(), PluginDispatchError> {
+    while stdin.fill_buf().is_ok_and(|buffer| !buffer.is_empty()) {
+        run_with_adapter(stdin, stdout, adapter)?;
+    }
+    Ok(())
+}
+
+/// Serves plugin requests in a loop using a persistent rope server.
+///
+/// # Errors
+///
+/// Returns an error if a response cannot be written.
+pub fn run_persistent(
+    stdin: &mut impl BufRead,
+    stdout: &mut impl Write,
+) -> Result<(), PluginDispatchError> {
+    serve_with_adapter(stdin, stdout, &PersistentRopeAdapter::new())
 }
 
 fn read_request(stdin: &mut impl BufRead) -> Result<PluginRequest, PluginFailure> {
@@ -257,8 +301,12 @@ fn read_request(stdin: &mut impl BufRead) -> Result<PluginRequest, PluginFailure
         return Err(PluginFailure::plain("plugin request was empty"));
     }
 
-    serde_json::from_str(line.trim())
-        .map_err(|error| PluginFailure::plain(format!("invalid plugin request JSON: {error}")))
+    let mut request: PluginRequest = serde_json::from_str(line.trim())
+        .map_err(|error| PluginFailure::plain(format!("invalid plugin request JSON: {error}")))?;
+    request.load_file_references().map_err(|error| {
+        PluginFailure::plain(format!("failed to read referenced file content: {error}"))
+    })?;
+    Ok(request)
 }
 
 fn execute_request<R: RopeAdapter>(
@@ -266,7 +314,9 @@ fn execute_request<R: RopeAdapter>(
     request: &PluginRequest,
 ) -> Result<PluginResponse, PluginFailure> {
     match request.operation() {
+        PING_OPERATION => Ok(PluginResponse::success(PluginOutput::Empty)),
         "rename-symbol" => execute_rename(adapter, request),
+        "extract-method" => execute_extract_method(adapter, request),
         other => Err(PluginFailure::with_reason(
             format!("unsupported refactoring operation '{other}'"),
             ReasonCode::OperationNotSupported,
@@ -280,14 +330,99 @@ fn execute_rename<R: RopeAdapter>(
 ) -> Result<PluginResponse, PluginFailure> {
     let args = parse_rename_symbol_arguments(request.arguments())
         .map_err(|msg| PluginFailure::with_reason(msg, ReasonCode::IncompletePayload))?;
+    if request.files().is_empty() {
+        return Err(PluginFailure::with_reason(
+            "rename-symbol operation requires at least one file payload",
+            ReasonCode::IncompletePayload,
+        ));
+    }
+    for payload in request.files() {
+        validate_relative_path(payload.path()).map_err(|error| {
+            PluginFailure::with_reason(error.to_string(), ReasonCode::IncompletePayload)
+        })?;
+    }
 
-    let files = request.files();
-    let file = match files {
+    let target = request.files().first().map_or("", FilePayload::content);
+    let offset = args
+        .offset_in(target)
+        .map_err(|msg| PluginFailure::with_reason(msg, ReasonCode::IncompletePayload))?;
+
+    let changed = adapter
+        .rename(request.files(), offset, args.new_name())
+        .map_err(|error| {
+            PluginFailure::from_adapter_error(
+                &error,
+                request.files(),
+                Some(ReasonCode::SymbolNotFound),
+            )
+        })?;
+
+    let patch = build_multi_file_patch(request.files(), &changed, |path| Some(path_to_slash(path)))
+        .map_err(|error| {
+            PluginFailure::plain(format!(
+                "rope modified '{}', which was not supplied in the request",
+                error.path()
+            ))
+        })?;
+    if patch.is_empty() {
+        return Err(PluginFailure::with_reason(
+            String::from("rename operation produced no content changes"),
+            ReasonCode::SymbolNotFound,
+        ));
+    }
+
+    Ok(PluginResponse::success(PluginOutput::Diff {
+        content: patch,
+    }))
+}
+
+fn execute_extract_method<R: RopeAdapter>(
+    adapter: &R,
+    request: &PluginRequest,
+) -> Result<PluginResponse, PluginFailure> {
+    let args = parse_extract_method_arguments(request.arguments())
+        .map_err(|msg| PluginFailure::with_reason(msg, ReasonCode::IncompletePayload))?;
+    let file = single_file_payload("extract-method", request)?;
+
+    if args.range().end > file.content().len() {
+        return Err(PluginFailure::with_reason(
+            format!(
+                "end_offset {} exceeds file length {}",
+                args.range().end,
+                file.content().len()
+            ),
+            ReasonCode::IncompletePayload,
+        ));
+    }
+
+    // Rope explains invalid selections (for example ranges that cut through
+    // a statement) in its own words, so engine failures pass through as-is.
+    let modified = adapter
+        .extract_method(file, args.range(), args.new_name())
+        .map_err(|error| {
+            PluginFailure::from_adapter_error(&error, std::slice::from_ref(file), None)
+        })?;
+
+    if modified == file.content() {
+        return Err(PluginFailure::plain(
+            "extract-method operation produced no content changes",
+        ));
+    }
+
+    Ok(diff_response(file, &modified))
+}
+
+/// Returns the only file payload in `request`, validating its path.
+fn single_file_payload<'a>(
+    operation: &str,
+    request: &'a PluginRequest,
+) -> Result<&'a FilePayload, PluginFailure> {
+    let file = match request.files() {
         [single] => single,
         other => {
             return Err(PluginFailure::with_reason(
                 format!(
-                    "rename-symbol operation requires exactly one file payload, got {}",
+                    "{operation} operation requires exactly one file payload, got {}",
                     other.len()
                 ),
                 ReasonCode::IncompletePayload,
@@ -298,27 +433,12 @@ fn execute_rename<R: RopeAdapter>(
     validate_relative_path(file.path()).map_err(|error| {
         PluginFailure::with_reason(error.to_string(), ReasonCode::IncompletePayload)
     })?;
+    Ok(file)
+}
 
-    let modified = adapter
-        .rename(file, args.offset(), args.new_name())
-        .map_err(|error| match &error {
-            RopeAdapterError::EngineFailed { .. } => {
-                PluginFailure::with_reason(error.to_string(), ReasonCode::SymbolNotFound)
-            }
-            _ => PluginFailure::plain(error.to_string()),
-        })?;
-
-    if modified == file.content() {
-        return Err(PluginFailure::with_reason(
-            String::from("rename operation produced no content changes"),
-            ReasonCode::SymbolNotFound,
-        ));
-    }
-
-    let patch = build_search_replace_patch(file.path(), file.content(), &modified);
-    Ok(PluginResponse::success(PluginOutput::Diff {
-        content: patch,
-    }))
+fn diff_response(file: &FilePayload, modified: &str) -> PluginResponse {
+    let patch = build_search_replace_patch(file.path(), file.content(), modified);
+    PluginResponse::success(PluginOutput::Diff { content: patch })
 }
 
 fn validate_relative_path(path: &Path) -> Result<(), RopeAdapterError> {
@@ -343,25 +463,7 @@ fn validate_relative_path(path: &Path) -> Result<(), RopeAdapterError> {
 }
 
 fn build_search_replace_patch(path: &Path, original: &str, modified: &str) -> String {
-    let unix_path = path_to_slash(path);
-    let sep_after_original = if original.ends_with('\n') { "" } else { "\n" };
-    let sep_after_modified = if modified.ends_with('\n') { "" } else { "\n" };
-
-    format!(
-        concat!(
-            "diff --git a/{unix_path} b/{unix_path}\n",
-            "<<<<<<< SEARCH\n",
-            "{original}{sep_a}",
-            "=======\n",
-            "{modified}{sep_b}",
-            ">>>>>>> REPLACE\n",
-        ),
-        unix_path = unix_path,
-        original = original,
-        sep_a = sep_after_original,
-        modified = modified,
-        sep_b = sep_after_modified,
-    )
+    search_replace_patch(&path_to_slash(path), original, modified)
 }
 
 fn path_to_slash(path: &Path) -> String {
@@ -379,5 +481,8 @@ pub(crate) fn failure_response(failure: PluginFailure) -> PluginResponse {
     if let Some(code) = failure.reason_code {
         diagnostic = diagnostic.with_reason_code(code);
     }
+    if let Some(location) = failure.location {
+        diagnostic = diagnostic.with_file(location.path).with_line(location.line);
+    }
     PluginResponse::failure(vec![diagnostic])
 }
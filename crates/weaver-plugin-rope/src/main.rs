@@ -1,8 +1,14 @@
 //! Binary entrypoint for the rope actuator plugin.
+//!
+//! By default the plugin handles exactly one request. Passing `--persistent`
+//! keeps a rope server alive and handles one request per input line until
+//! stdin is closed.
 
 use std::io::{self, BufReader, Write};
 
-use weaver_plugin_rope::run;
+use weaver_plugin_rope::{run, run_persistent};
+
+const PERSISTENT_FLAG: &str = "--persistent";
 
 fn main() {
     let stdin = io::stdin();
@@ -10,7 +16,13 @@ fn main() {
     let stdout = io::stdout();
     let mut writer = stdout.lock();
 
-    if let Err(error) = run(&mut reader, &mut writer) {
+    let result = if std::env::args().skip(1).any(|arg| arg == PERSISTENT_FLAG) {
+        run_persistent(&mut reader, &mut writer)
+    } else {
+        run(&mut reader, &mut writer)
+    };
+
+    if let Err(error) = result {
         writeln!(io::stderr().lock(), "{error}").ok();
         std::process::exit(1);
     }
@@ -0,0 +1,337 @@
+//! Long-lived rope adapter backed by a single Python server process.
+//!
+//! The one-shot [`crate::PythonRopeAdapter`] pays for interpreter start-up and
+//! a full project scan on every request. [`PersistentRopeAdapter`] instead
+//! starts one Python process running a small line-framed server: each request
+//! is a JSON object on stdin and each reply a JSON object on stdout. The server
+//! keeps its rope `Project` open for as long as the workspace root is
+//! unchanged, so only files touched since the previous request are re-parsed.
+//!
+//! Every exchange is bounded by the adapter's timeout. A server that does not
+//! reply in time cannot be resynchronized with its request stream, so it is
+//! killed and the next request starts a fresh one.
+
+use std::{
+    collections::BTreeSet,
+    io::{self, BufRead, BufReader, Write},
+    ops::Range,
+    path::Path,
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+    sync::{
+        Mutex,
+        PoisonError,
+        mpsc::{self, Receiver, RecvTimeoutError},
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use serde_json::{Value, json};
+use tempfile::TempDir;
+use weaver_plugins::protocol::FilePayload;
+
+use crate::{
+    ChangedFiles,
+    RopeAdapter,
+    RopeAdapterError,
+    path_to_slash,
+    python::{DEFAULT_PYTHON_TIMEOUT, char_offset, collect, drain, resolve_python_binary},
+    workspace_fs::remove_workspace_file,
+    write_workspace_file,
+};
+
+const PYTHON_SERVER_SCRIPT: &str = concat!(
+    "import json,sys,traceback\n",
+    "from rope.base.project import Project\n",
+    "from rope.refactor.rename import Rename\n",
+    "from rope.refactor.extract import ExtractMethod\n",
+    "state = {'root': None, 'project': None}\n",
+    "def project_for(root):\n",
+    "    if state['root'] != root:\n",
+    "        if state['project'] is not None:\n",
+    "            state['project'].close()\n",
+    "        state['root'], state['project'] = root, Project(root)\n",
+    "    else:\n",
+    "        state['project'].validate()\n",
+    "    return state['project']\n",
+    "def handle(req):\n",
+    "    project = project_for(req['root'])\n",
+    "    resource = project.get_resource(req['path'])\n",
+    "    if req['op'] == 'rename-symbol':\n",
+    "        refactoring = Rename(project, resource, req['offset'])\n",
+    "    else:\n",
+    "        refactoring = ExtractMethod(project, resource, req['start'], req['end'])\n",
+    "    changes = refactoring.get_changes(req['new_name'])\n",
+    "    changed = [r for r in changes.get_changed_resources() if not r.is_folder()]\n",
+    "    project.do(changes)\n",
+    "    return {r.path: r.read() for r in changed if r.exists()}\n",
+    "for line in sys.stdin:\n",
+    "    try:\n",
+    "        reply = {'ok': True, 'changes': handle(json.loads(line))}\n",
+    "    except Exception:\n",
+    "        reply = {'ok': False, 'error': traceback.format_exc().strip()}\n",
+    "    sys.stdout.write(json.dumps(reply) + '\\n')\n",
+    "    sys.stdout.flush()\n",
+);
+
+/// Adapter that reuses one Python rope server across requests.
+///
+/// The server is started lazily on first use and restarted if it exits. All
+/// requests share one temporary workspace so the server's project root stays
+/// stable; files staged by an earlier request but absent from the current one
+/// are removed before the request is forwarded. A server that does not reply
+/// within the timeout is killed and reported as [`RopeAdapterError::Timeout`].
+#[derive(Debug)]
+pub struct PersistentRopeAdapter {
+    session: Mutex<Option<Session>>,
+    timeout: Duration,
+}
+
+impl PersistentRopeAdapter {
+    /// Creates an adapter whose server has not been started yet.
+    #[must_use]
+    pub fn new() -> Self { Self::default() }
+
+    /// Creates an adapter that kills the server when a reply takes longer
+    /// than `timeout`.
+    #[must_use]
+    pub const fn with_timeout(timeout: Duration) -> Self {
+        Self {
+            session: Mutex::new(None),
+            timeout,
+        }
+    }
+
+    /// Returns the configured per-request timeout.
+    #[must_use]
+    pub const fn timeout(&self) -> Duration { self.timeout }
+
+    /// Stages `files`, sends `request` to the server, and returns its changes.
+    fn call(
+        &self,
+        files: &[FilePayload],
+        request: Value,
+    ) -> Result<ChangedFiles, RopeAdapterError> {
+        let mut guard = self.session.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut session = match guard.take().and_then(Session::into_running) {
+            Some(session) => session,
+            None => Session::start()?,
+        };
+
+        let result = session
+            .stage(files)
+            .and_then(|()| session.exchange(request, self.timeout));
+        // Only keep a live server; a dead one is restarted on the next call.
+        if session.is_running() {
+            *guard = Some(session);
+        }
+        result
+    }
+}
+
+impl Default for PersistentRopeAdapter {
+    fn default() -> Self { Self::with_timeout(DEFAULT_PYTHON_TIMEOUT) }
+}
+
+impl RopeAdapter for PersistentRopeAdapter {
+    fn rename(
+        &self,
+        files: &[FilePayload],
+        offset: usize,
+        new_name: &str,
+    ) -> Result<ChangedFiles, RopeAdapterError> {
+        let target = target_path(files)?;
+        let position = files
+            .first()
+            .map_or(offset, |payload| char_offset(payload.content(), offset));
+        self.call(
+            files,
+            json!({
+                "op": "rename-symbol",
+                "path": target,
+                "offset": position,
+                "new_name": new_name,
+            }),
+        )
+    }
+
+    fn extract_method(
+        &self,
+        file: &FilePayload,
+        range: Range<usize>,
+        new_name: &str,
+    ) -> Result<String, RopeAdapterError> {
+        let target = path_to_slash(file.path());
+        let mut changed = self.call(
+            std::slice::from_ref(file),
+            json!({
+                "op": "extract-method",
+                "path": target,
+                "start": char_offset(file.content(), range.start),
+                "end": char_offset(file.content(), range.end),
+                "new_name": new_name,
+            }),
+        )?;
+        Ok(changed
+            .remove(&target)
+            .unwrap_or_else(|| file.content().to_owned()))
+    }
+}
+
+fn target_path(files: &[FilePayload]) -> Result<String, RopeAdapterError> {
+    files
+        .first()
+        .map(|payload| path_to_slash(payload.path()))
+        .ok_or_else(|| RopeAdapterError::InvalidPath {
+            message: String::from("no file payload supplied"),
+        })
+}
+
+/// A running server process together with the workspace it operates on.
+#[derive(Debug)]
+struct Session {
+    child: Child,
+    stdin: ChildStdin,
+    replies: Receiver<String>,
+    stderr: Option<JoinHandle<Vec<u8>>>,
+    workspace: TempDir,
+    staged: BTreeSet<String>,
+}
+
+impl Session {
+    fn start() -> Result<Self, RopeAdapterError> {
+        let workspace =
+            TempDir::new().map_err(|source| RopeAdapterError::WorkspaceCreate { source })?;
+        let mut child = Command::new(resolve_python_binary())
+            .arg("-c")
+            .arg(PYTHON_SERVER_SCRIPT)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|source| RopeAdapterError::Spawn { source })?;
+        let stderr = drain(child.stderr.take());
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            return Err(RopeAdapterError::Spawn {
+                source: io::Error::other("python server pipes were not captured"),
+            });
+        };
+        Ok(Self {
+            child,
+            stdin,
+            replies: forward_replies(stdout),
+            stderr,
+            workspace,
+            staged: BTreeSet::new(),
+        })
+    }
+
+    fn is_running(&mut self) -> bool { matches!(self.child.try_wait(), Ok(None)) }
+
+    fn into_running(mut self) -> Option<Self> { self.is_running().then_some(self) }
+
+    /// Writes `files` into the workspace and removes files left over from
+    /// earlier requests so rope does not see stale modules.
+    fn stage(&mut self, files: &[FilePayload]) -> Result<(), RopeAdapterError> {
+        let root = self.workspace.path();
+        let current: BTreeSet<String> = files
+            .iter()
+            .map(|payload| path_to_slash(payload.path()))
+            .collect();
+        for stale in self.staged.difference(&current) {
+            remove_workspace_file(root, Path::new(stale))?;
+        }
+        for payload in files {
+            write_workspace_file(root, payload.path(), payload.content())?;
+        }
+        self.staged = current;
+        Ok(())
+    }
+
+    fn exchange(
+        &mut self,
+        mut request: Value,
+        timeout: Duration,
+    ) -> Result<ChangedFiles, RopeAdapterError> {
+        if let Some(fields) = request.as_object_mut() {
+            fields.insert(
+                String::from("root"),
+                Value::from(self.workspace.path().to_string_lossy().into_owned()),
+            );
+        }
+        let mut line = request.to_string();
+        line.push('\n');
+        let sent = self
+            .stdin
+            .write_all(line.as_bytes())
+            .and_then(|()| self.stdin.flush());
+        if sent.is_err() {
+            return Err(self.server_exited());
+        }
+        match self.replies.recv_timeout(timeout) {
+            Ok(reply) => parse_reply(&reply),
+            Err(RecvTimeoutError::Timeout) => {
+                // Killing the server makes `call` discard this session, so
+                // the next request is served by a freshly started process.
+                self.child.kill().ok();
+                self.child.wait().ok();
+                Err(RopeAdapterError::Timeout { limit: timeout })
+            }
+            Err(RecvTimeoutError::Disconnected) => Err(self.server_exited()),
+        }
+    }
+
+    /// Reaps the server after it stopped responding and reports its stderr.
+    fn server_exited(&mut self) -> RopeAdapterError {
+        self.child.kill().ok();
+        self.child.wait().ok();
+        let captured = collect(self.stderr.take());
+        let stderr = String::from_utf8_lossy(&captured).trim().to_owned();
+        RopeAdapterError::EngineFailed {
+            message: if stderr.is_empty() {
+                String::from("persistent rope server exited unexpectedly")
+            } else {
+                stderr
+            },
+        }
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        self.child.kill().ok();
+        self.child.wait().ok();
+    }
+}
+
+/// Forwards each line the server writes to stdout over a channel so replies
+/// can be awaited with a deadline. The channel closes when stdout does.
+fn forward_replies(stdout: ChildStdout) -> Receiver<String> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+fn parse_reply(reply: &str) -> Result<ChangedFiles, RopeAdapterError> {
+    let invalid = |message: String| RopeAdapterError::InvalidOutput { message };
+    let value: Value =
+        serde_json::from_str(reply.trim()).map_err(|error| invalid(error.to_string()))?;
+    match value.get("ok").and_then(Value::as_bool) {
+        Some(true) => serde_json::from_value(value.get("changes").cloned().unwrap_or(Value::Null))
+            .map_err(|error| invalid(error.to_string())),
+        Some(false) => Err(RopeAdapterError::EngineFailed {
+            message: value
+                .get("error")
+                .and_then(Value::as_str)
+                .unwrap_or("persistent rope server reported an unknown error")
+                .to_owned(),
+        }),
+        None => Err(invalid(String::from("reply is missing the 'ok' field"))),
+    }
+}
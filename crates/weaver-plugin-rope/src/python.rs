@@ -0,0 +1,265 @@
+//! Python-backed rope adapter.
+//!
+//! Each operation materializes the request files in a temporary workspace and
+//! runs a short inline script with the configured Python interpreter. The
+//! script applies the rope change set and reports every modified resource as
+//! a JSON object on stdout.
+
+use std::{
+    io::Read,
+    ops::Range,
+    process::{Child, Command, ExitStatus, Stdio},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use tempfile::TempDir;
+use weaver_plugins::protocol::FilePayload;
+
+use crate::{ChangedFiles, RopeAdapter, RopeAdapterError, path_to_slash, write_workspace_file};
+
+const PYTHON_BINARY: &str = "python3";
+pub(crate) const PYTHON_BINARY_ENV: &str = "WEAVER_PYTHON_BINARY";
+/// Default wall-clock limit for a single rope invocation.
+pub const DEFAULT_PYTHON_TIMEOUT: Duration = Duration::from_secs(30);
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+const PYTHON_RENAME_SCRIPT: &str = concat!(
+    "import json,sys\n",
+    "from rope.base.project import Project\n",
+    "from rope.refactor.rename import Rename\n",
+    "root, rel_path, offset_s, new_name = sys.argv[1:5]\n",
+    "offset = int(offset_s)\n",
+    "project = Project(root)\n",
+    "try:\n",
+    "    resource = project.get_resource(rel_path)\n",
+    "    renamer = Rename(project, resource, offset)\n",
+    "    changes = renamer.get_changes(new_name)\n",
+    "    changed = [r for r in changes.get_changed_resources() if not r.is_folder()]\n",
+    "    project.do(changes)\n",
+    "    json.dump({r.path: r.read() for r in changed if r.exists()}, sys.stdout)\n",
+    "finally:\n",
+    "    project.close()\n",
+);
+const PYTHON_EXTRACT_METHOD_SCRIPT: &str = concat!(
+    "import json,sys\n",
+    "from rope.base.project import Project\n",
+    "from rope.refactor.extract import ExtractMethod\n",
+    "root, rel_path, start_s, end_s, new_name = sys.argv[1:6]\n",
+    "start = int(start_s)\n",
+    "end = int(end_s)\n",
+    "project = Project(root)\n",
+    "try:\n",
+    "    resource = project.get_resource(rel_path)\n",
+    "    extractor = ExtractMethod(project, resource, start, end)\n",
+    "    changes = extractor.get_changes(new_name)\n",
+    "    changed = [r for r in changes.get_changed_resources() if not r.is_folder()]\n",
+    "    project.do(changes)\n",
+    "    json.dump({r.path: r.read() for r in changed if r.exists()}, sys.stdout)\n",
+    "finally:\n",
+    "    project.close()\n",
+);
+
+/// Adapter that delegates to the Python `rope` library.
+///
+/// Each invocation is bounded by a timeout; an interpreter that outlives it
+/// is killed and reported as [`RopeAdapterError::Timeout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PythonRopeAdapter {
+    timeout: Duration,
+}
+
+impl PythonRopeAdapter {
+    /// Creates an adapter that kills the interpreter after `timeout`.
+    #[must_use]
+    pub const fn with_timeout(timeout: Duration) -> Self { Self { timeout } }
+
+    /// Returns the configured per-invocation timeout.
+    #[must_use]
+    pub const fn timeout(&self) -> Duration { self.timeout }
+
+    /// Materializes `files` in a temporary workspace and runs `script` against
+    /// it.
+    ///
+    /// The script receives the workspace root and the slash-separated relative
+    /// path of the first file, followed by `extra_args`, and must print a JSON
+    /// object mapping each changed resource path to its new content.
+    fn run_script(
+        &self,
+        script: &str,
+        files: &[FilePayload],
+        extra_args: &[String],
+    ) -> Result<ChangedFiles, RopeAdapterError> {
+        let target = files.first().ok_or_else(|| RopeAdapterError::InvalidPath {
+            message: String::from("no file payload supplied"),
+        })?;
+        let workspace =
+            TempDir::new().map_err(|source| RopeAdapterError::WorkspaceCreate { source })?;
+        for payload in files {
+            write_workspace_file(workspace.path(), payload.path(), payload.content())?;
+        }
+
+        let mut command = Command::new(resolve_python_binary());
+        command
+            .arg("-c")
+            .arg(script)
+            .arg(workspace.path())
+            .arg(path_to_slash(target.path()))
+            .args(extra_args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let output = run_with_deadline(&mut command, self.timeout)?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_owned();
+            return Err(RopeAdapterError::EngineFailed {
+                message: if stderr.is_empty() {
+                    String::from("python rope adapter failed without stderr output")
+                } else {
+                    stderr
+                },
+            });
+        }
+
+        serde_json::from_slice(&output.stdout).map_err(|source| RopeAdapterError::InvalidOutput {
+            message: source.to_string(),
+        })
+    }
+}
+
+impl Default for PythonRopeAdapter {
+    fn default() -> Self { Self::with_timeout(DEFAULT_PYTHON_TIMEOUT) }
+}
+
+impl RopeAdapter for PythonRopeAdapter {
+    fn rename(
+        &self,
+        files: &[FilePayload],
+        offset: usize,
+        new_name: &str,
+    ) -> Result<ChangedFiles, RopeAdapterError> {
+        let position = files
+            .first()
+            .map_or(offset, |target| char_offset(target.content(), offset));
+        self.run_script(
+            PYTHON_RENAME_SCRIPT,
+            files,
+            &[position.to_string(), String::from(new_name)],
+        )
+    }
+
+    fn extract_method(
+        &self,
+        file: &FilePayload,
+        range: Range<usize>,
+        new_name: &str,
+    ) -> Result<String, RopeAdapterError> {
+        let mut changed = self.run_script(
+            PYTHON_EXTRACT_METHOD_SCRIPT,
+            std::slice::from_ref(file),
+            &[
+                char_offset(file.content(), range.start).to_string(),
+                char_offset(file.content(), range.end).to_string(),
+                String::from(new_name),
+            ],
+        )?;
+        Ok(changed
+            .remove(&path_to_slash(file.path()))
+            .unwrap_or_else(|| file.content().to_owned()))
+    }
+}
+
+/// Captured result of a child process that exited before its deadline.
+struct ScriptOutput {
+    status: ExitStatus,
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+}
+
+/// Spawns `command` and waits for it to exit, killing it once `timeout`
+/// elapses.
+///
+/// Both output pipes are drained on background threads so a chatty script
+/// cannot block on a full pipe buffer while the deadline loop is polling.
+fn run_with_deadline(
+    command: &mut Command,
+    timeout: Duration,
+) -> Result<ScriptOutput, RopeAdapterError> {
+    let mut child = command
+        .spawn()
+        .map_err(|source| RopeAdapterError::Spawn { source })?;
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let status = wait_with_deadline(&mut child, timeout)?;
+    Ok(ScriptOutput {
+        status,
+        stdout: collect(stdout),
+        stderr: collect(stderr),
+    })
+}
+
+fn wait_with_deadline(
+    child: &mut Child,
+    timeout: Duration,
+) -> Result<ExitStatus, RopeAdapterError> {
+    let start = Instant::now();
+    loop {
+        if let Some(status) = child
+            .try_wait()
+            .map_err(|source| RopeAdapterError::Spawn { source })?
+        {
+            return Ok(status);
+        }
+        if start.elapsed() > timeout {
+            // The process may exit between the poll and the kill; either way
+            // it must be reaped so it does not linger as a zombie.
+            child.kill().ok();
+            child.wait().ok();
+            return Err(RopeAdapterError::Timeout { limit: timeout });
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Converts a UTF-8 byte offset into `content` to the character offset rope
+/// expects.
+///
+/// Rope indexes Python `str` values by code point, so a byte offset would
+/// drift by one position for every continuation byte before it. An offset
+/// that falls inside a multi-byte character counts that character as
+/// preceding it.
+pub(crate) fn char_offset(content: &str, byte_offset: usize) -> usize {
+    content
+        .char_indices()
+        .take_while(|&(index, _)| index < byte_offset)
+        .count()
+}
+
+/// Reads `pipe` to completion on a background thread.
+pub(crate) fn drain(pipe: Option<impl Read + Send + 'static>) -> Option<JoinHandle<Vec<u8>>> {
+    pipe.map(|mut reader| {
+        thread::spawn(move || {
+            let mut buffer = Vec::new();
+            reader.read_to_end(&mut buffer).ok();
+            buffer
+        })
+    })
+}
+
+/// Joins a [`drain`] thread, yielding an empty buffer if it panicked.
+pub(crate) fn collect(handle: Option<JoinHandle<Vec<u8>>>) -> Vec<u8> {
+    handle
+        .and_then(|reader| reader.join().ok())
+        .unwrap_or_default()
+}
+
+/// Returns the Python interpreter to run, honouring `WEAVER_PYTHON_BINARY`.
+pub(crate) fn resolve_python_binary() -> String {
+    std::env::var(PYTHON_BINARY_ENV)
+        .ok()
+        .map(|candidate| candidate.trim().to_owned())
+        .filter(|candidate| !candidate.is_empty())
+        .unwrap_or_else(|| String::from(PYTHON_BINARY))
+}
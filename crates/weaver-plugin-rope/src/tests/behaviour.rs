@@ -14,7 +14,7 @@ use weaver_plugins::protocol::{
 };
 use weaver_test_macros::allow_fixture_expansion_lints;
 
-use crate::{RopeAdapter, RopeAdapterError, execute_request, failure_response};
+use crate::{ChangedFiles, RopeAdapter, RopeAdapterError, execute_request, failure_response};
 
 #[derive(Default)]
 struct World {
@@ -40,9 +40,16 @@ mock! {
     impl RopeAdapter for BehaviourAdapter {
         fn rename(
             &self,
-            file: &FilePayload,
+            files: &[FilePayload],
             offset: usize,
             new_name: &str,
+        ) -> Result<ChangedFiles, RopeAdapterError>;
+
+        fn extract_method(
+            &self,
+            file: &FilePayload,
+            range: std::ops::Range<usize>,
+            new_name: &str,
         ) -> Result<String, RopeAdapterError>;
     }
 }
@@ -57,12 +64,20 @@ fn should_invoke_rename(request: &PluginRequest) -> bool {
 
 fn configure_adapter_for_mode(adapter: &mut MockBehaviourAdapter, mode: AdapterMode) {
     adapter.expect_rename().once().returning(
-        move |file: &FilePayload, _offset: usize, _new_name: &str| match mode {
-            AdapterMode::Success => Ok(file.content().replace("old_name", "new_name")),
-            AdapterMode::NoChange => Ok(file.content().to_owned()),
-            AdapterMode::Fails => Err(RopeAdapterError::EngineFailed {
-                message: String::from("rope engine failed"),
-            }),
+        move |files: &[FilePayload], _offset: usize, _new_name: &str| {
+            let file = files
+                .first()
+                .expect("rename should receive the target file");
+            let content = match mode {
+                AdapterMode::Success => file.content().replace("old_name", "new_name"),
+                AdapterMode::NoChange => file.content().to_owned(),
+                AdapterMode::Fails => {
+                    return Err(RopeAdapterError::EngineFailed {
+                        message: String::from("rope engine failed"),
+                    });
+                }
+            };
+            Ok(ChangedFiles::from([(String::from("src/main.py"), content)]))
         },
     );
 }
@@ -139,6 +154,7 @@ fn resolved_response(world: &World) -> PluginResponse {
         Err(failure) => failure_response(crate::PluginFailure {
             message: failure.message.clone(),
             reason_code: failure.reason_code,
+            location: failure.location.clone(),
         }),
     }
 }
@@ -0,0 +1,85 @@
+//! Tests for Python identifier validation of refactoring names.
+
+use std::collections::HashMap;
+
+use rstest::rstest;
+use weaver_plugins::capability::ReasonCode;
+
+use super::{adapter_unused, extract_request, rename_arguments, request_with_args};
+use crate::{execute_request, is_valid_python_identifier};
+
+#[rstest]
+#[case::ascii("renamed_value")]
+#[case::leading_underscore("_private")]
+#[case::dunder("__init__")]
+#[case::trailing_digits("value2")]
+#[case::soft_keyword("match")]
+#[case::unicode_letters("café")]
+#[case::non_latin_script("переменная")]
+#[case::cjk("変数")]
+fn accepts_valid_identifiers(#[case] name: &str) {
+    assert!(
+        is_valid_python_identifier(name),
+        "expected '{name}' to be valid"
+    );
+}
+
+#[rstest]
+#[case::empty("")]
+#[case::leading_digit("2foo")]
+#[case::embedded_space("a b")]
+#[case::surrounding_whitespace(" name")]
+#[case::hyphen("my-name")]
+#[case::dotted("module.name")]
+#[case::keyword_class("class")]
+#[case::keyword_none("None")]
+#[case::keyword_await("await")]
+#[case::emoji("name🙂")]
+fn rejects_invalid_identifiers(#[case] name: &str) {
+    assert!(
+        !is_valid_python_identifier(name),
+        "expected '{name}' to be invalid"
+    );
+}
+
+#[rstest]
+#[case::leading_digit("2foo", "must start with a letter or underscore")]
+#[case::embedded_space("a b", "contains ' '")]
+#[case::keyword("class", "'class' is a reserved Python keyword")]
+fn rename_rejects_invalid_new_name_before_adapter(
+    #[case] new_name: &str,
+    #[case] needle: &str,
+    mut rename_arguments: HashMap<String, serde_json::Value>,
+) {
+    rename_arguments.insert(String::from("new_name"), serde_json::json!(new_name));
+    let adapter = adapter_unused();
+
+    let failure = execute_request(&adapter, &request_with_args(rename_arguments))
+        .expect_err("invalid identifier should fail");
+    assert!(
+        failure
+            .to_string()
+            .contains("is not a valid Python identifier")
+            && failure.to_string().contains(needle),
+        "expected identifier diagnostic mentioning '{needle}', got: {failure}"
+    );
+    assert_eq!(failure.reason_code, Some(ReasonCode::IncompletePayload));
+}
+
+#[rstest]
+fn extract_method_rejects_keyword_new_name() {
+    let adapter = adapter_unused();
+    let request = extract_request(&[
+        ("start_offset", serde_json::json!(24)),
+        ("end_offset", serde_json::json!(29)),
+        ("new_name", serde_json::json!("def")),
+    ]);
+
+    let failure = execute_request(&adapter, &request).expect_err("keyword name should fail");
+    assert!(
+        failure
+            .to_string()
+            .contains("'def' is a reserved Python keyword"),
+        "expected keyword diagnostic, got: {failure}"
+    );
+}
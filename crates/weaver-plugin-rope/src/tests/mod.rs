@@ -3,8 +3,16 @@
 mod behaviour;
 mod contract_behaviour;
 mod contract_fixtures;
-
-use std::{collections::HashMap, path::PathBuf};
+mod identifier;
+mod persistent;
+mod python_binary;
+mod traceback;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use cap_std::{ambient_authority, fs::Dir};
 use mockall::mock;
@@ -14,30 +22,58 @@ use weaver_plugins::{
     protocol::{FilePayload, PluginOutput, PluginRequest},
 };
 
-use crate::{PluginFailure, RopeAdapter, RopeAdapterError, execute_request, run_with_adapter};
+use crate::{
+    ChangedFiles,
+    PluginFailure,
+    RopeAdapter,
+    RopeAdapterError,
+    execute_request,
+    run_with_adapter,
+    serve_with_adapter,
+};
 
 mock! {
     Adapter {}
     impl RopeAdapter for Adapter {
         fn rename(
             &self,
-            file: &FilePayload,
+            files: &[FilePayload],
             offset: usize,
             new_name: &str,
+        ) -> Result<ChangedFiles, RopeAdapterError>;
+
+        fn extract_method(
+            &self,
+            file: &FilePayload,
+            range: std::ops::Range<usize>,
+            new_name: &str,
         ) -> Result<String, RopeAdapterError>;
     }
 }
 
-/// Builds a `MockAdapter` that expects a single rename call returning `result`.
+/// Builds a `MockAdapter` that expects a single rename call returning `result`
+/// as the new content of `src/main.py`.
 fn adapter_returning(result: Result<String, RopeAdapterError>) -> MockAdapter {
+    adapter_returning_changes(result.map(|content| changed_files(&[("src/main.py", &content)])))
+}
+
+/// Builds a `MockAdapter` that expects a single rename call returning `result`.
+fn adapter_returning_changes(result: Result<ChangedFiles, RopeAdapterError>) -> MockAdapter {
     let mut adapter = MockAdapter::new();
     adapter
         .expect_rename()
         .once()
-        .return_once(move |_file, _offset, _new_name| result);
+        .return_once(move |_files, _offset, _new_name| result);
     adapter
 }
 
+fn changed_files(entries: &[(&str, &str)]) -> ChangedFiles {
+    entries
+        .iter()
+        .map(|(path, content)| (String::from(*path), String::from(*content)))
+        .collect()
+}
+
 /// Builds a `MockAdapter` where rename is never expected.
 fn adapter_unused() -> MockAdapter { MockAdapter::new() }
 
@@ -172,6 +208,95 @@ fn rename_argument_validation(
     }
 }
 
+fn coordinate_request(arguments: &[(&str, serde_json::Value)]) -> PluginRequest {
+    let mut map: HashMap<String, serde_json::Value> = arguments
+        .iter()
+        .map(|(key, value)| (String::from(*key), value.clone()))
+        .collect();
+    map.insert(String::from("uri"), serde_json::json!("src/main.py"));
+    map.insert(String::from("new_name"), serde_json::json!("new_name"));
+    PluginRequest::with_arguments(
+        "rename-symbol",
+        vec![FilePayload::new(
+            PathBuf::from("src/main.py"),
+            "café = 1\ndef old_name():\n    return café\n",
+        )],
+        map,
+    )
+}
+
+/// Builds a `MockAdapter` that expects a rename at exactly `offset`.
+fn adapter_expecting_offset(offset: usize) -> MockAdapter {
+    let mut adapter = MockAdapter::new();
+    adapter
+        .expect_rename()
+        .withf(move |_files, actual, _new_name| *actual == offset)
+        .once()
+        .return_once(|_files, _offset, _new_name| {
+            Ok(changed_files(&[(
+                "src/main.py",
+                "café = 1\ndef new_name():\n    return café\n",
+            )]))
+        });
+    adapter
+}
+
+#[rstest]
+#[case::line_column(vec![("line", serde_json::json!(2)), ("column", serde_json::json!(5))], 14)]
+#[case::string_coordinates(
+    vec![("line", serde_json::json!("2")), ("column", serde_json::json!("5"))],
+    14
+)]
+#[case::multibyte_column(
+    vec![("line", serde_json::json!(1)), ("column", serde_json::json!(6))],
+    6
+)]
+#[case::explicit_offset_wins(
+    vec![
+        ("position", serde_json::json!(15)),
+        ("line", serde_json::json!(1)),
+        ("column", serde_json::json!(1)),
+    ],
+    15
+)]
+fn rename_resolves_line_and_column(
+    #[case] arguments: Vec<(&str, serde_json::Value)>,
+    #[case] expected_offset: usize,
+) {
+    let adapter = adapter_expecting_offset(expected_offset);
+    let response = execute_request(&adapter, &coordinate_request(&arguments))
+        .expect("coordinates should resolve");
+    assert!(response.is_success());
+}
+
+#[rstest]
+#[case::line_past_end(
+    vec![("line", serde_json::json!(9)), ("column", serde_json::json!(1))],
+    "line 9 is outside the file, which has 3 line(s)"
+)]
+#[case::column_past_end(
+    vec![("line", serde_json::json!(1)), ("column", serde_json::json!(10))],
+    "column 10 is outside line 1"
+)]
+#[case::zero_line(
+    vec![("line", serde_json::json!(0)), ("column", serde_json::json!(1))],
+    "line must be >= 1"
+)]
+#[case::missing_column(vec![("line", serde_json::json!(1))], "'line' and 'column'")]
+fn rename_rejects_invalid_coordinates(
+    #[case] arguments: Vec<(&str, serde_json::Value)>,
+    #[case] needle: &str,
+) {
+    let adapter = adapter_unused();
+    let failure = execute_request(&adapter, &coordinate_request(&arguments))
+        .expect_err("invalid coordinates should fail");
+    assert!(
+        failure.to_string().contains(needle),
+        "expected error mentioning '{needle}', got: {failure}"
+    );
+    assert_eq!(failure.reason_code, Some(ReasonCode::IncompletePayload));
+}
+
 #[rstest]
 #[case::unsupported_operation("extract_method")]
 #[case::old_rename_rejected("rename")]
@@ -188,6 +313,15 @@ fn unsupported_operations_rejected_with_operation_not_supported(#[case] operatio
     assert_eq!(failure.reason_code, Some(ReasonCode::OperationNotSupported));
 }
 
+#[test]
+fn ping_succeeds_without_touching_rope() {
+    let response =
+        execute_request(&adapter_unused(), &PluginRequest::ping()).expect("ping should succeed");
+
+    assert!(response.is_success());
+    assert_eq!(response.output(), &PluginOutput::Empty);
+}
+
 enum FailureScenario {
     NoChange,
     AdapterError,
@@ -226,6 +360,206 @@ fn rename_failure_includes_reason_code(
     }
 }
 
+const EXTRACT_SOURCE: &str = "def main():\n    total = 1 + 2\n    return total\n";
+
+fn extract_request(arguments: &[(&str, serde_json::Value)]) -> PluginRequest {
+    PluginRequest::with_arguments(
+        "extract-method",
+        vec![FilePayload::new(
+            PathBuf::from("src/main.py"),
+            EXTRACT_SOURCE,
+        )],
+        arguments
+            .iter()
+            .map(|(key, value)| (String::from(*key), value.clone()))
+            .collect(),
+    )
+}
+
+fn valid_extract_request() -> PluginRequest {
+    extract_request(&[
+        ("start_offset", serde_json::json!(24)),
+        ("end_offset", serde_json::json!(29)),
+        ("new_name", serde_json::json!("add")),
+    ])
+}
+
+/// Builds a `MockAdapter` that expects a single extract-method call.
+fn extract_adapter_returning(result: Result<String, RopeAdapterError>) -> MockAdapter {
+    let mut adapter = MockAdapter::new();
+    adapter
+        .expect_extract_method()
+        .withf(|_file, range, new_name| *range == (24..29) && new_name == "add")
+        .once()
+        .return_once(move |_file, _range, _new_name| result);
+    adapter
+}
+
+#[rstest]
+fn extract_method_success_returns_diff_output() {
+    let modified = concat!(
+        "def main():\n",
+        "    total = add()\n",
+        "    return total\n",
+        "\n",
+        "def add():\n",
+        "    return 1 + 2\n",
+    );
+    let adapter = extract_adapter_returning(Ok(String::from(modified)));
+
+    let response =
+        execute_request(&adapter, &valid_extract_request()).expect("extraction should succeed");
+    let PluginOutput::Diff { content } = response.output() else {
+        panic!("expected diff output, got {:?}", response.output());
+    };
+    assert!(content.starts_with("diff --git a/src/main.py b/src/main.py\n"));
+    assert!(content.contains("def add():"));
+}
+
+#[rstest]
+fn extract_method_surfaces_rope_error_verbatim() {
+    let rope_message =
+        "rope.base.exceptions.RefactoringError: Bad region selected for extract method";
+    let adapter = extract_adapter_returning(Err(RopeAdapterError::EngineFailed {
+        message: String::from(rope_message),
+    }));
+
+    let failure = execute_request(&adapter, &valid_extract_request())
+        .expect_err("invalid region should fail");
+    assert!(
+        failure.to_string().contains(rope_message),
+        "expected rope message, got: {failure}"
+    );
+}
+
+#[rstest]
+fn extract_method_without_changes_fails() {
+    let adapter = extract_adapter_returning(Ok(String::from(EXTRACT_SOURCE)));
+
+    let failure = execute_request(&adapter, &valid_extract_request())
+        .expect_err("unchanged content should fail");
+    assert!(
+        failure.to_string().contains("no content changes"),
+        "expected no-change diagnostic, got: {failure}"
+    );
+}
+
+#[rstest]
+#[case::missing_start(
+    vec![("end_offset", serde_json::json!(29)), ("new_name", serde_json::json!("add"))],
+    "start_offset"
+)]
+#[case::non_numeric_end(
+    vec![
+        ("start_offset", serde_json::json!(24)),
+        ("end_offset", serde_json::json!(true)),
+        ("new_name", serde_json::json!("add")),
+    ],
+    "end_offset argument must be a string or number"
+)]
+#[case::empty_range(
+    vec![
+        ("start_offset", serde_json::json!(24)),
+        ("end_offset", serde_json::json!(24)),
+        ("new_name", serde_json::json!("add")),
+    ],
+    "must be less than end_offset"
+)]
+#[case::missing_new_name(
+    vec![("start_offset", serde_json::json!(24)), ("end_offset", serde_json::json!(29))],
+    "new_name"
+)]
+#[case::range_past_end_of_file(
+    vec![
+        ("start_offset", serde_json::json!(24)),
+        ("end_offset", serde_json::json!(500)),
+        ("new_name", serde_json::json!("add")),
+    ],
+    "exceeds file length"
+)]
+fn extract_method_argument_validation(
+    #[case] arguments: Vec<(&str, serde_json::Value)>,
+    #[case] needle: &str,
+) {
+    let adapter = adapter_unused();
+    let failure = execute_request(&adapter, &extract_request(&arguments))
+        .expect_err("invalid extract-method arguments should fail");
+    assert!(
+        failure.to_string().contains(needle),
+        "expected error mentioning '{needle}', got: {failure}"
+    );
+    assert_eq!(failure.reason_code, Some(ReasonCode::IncompletePayload));
+}
+
+fn multi_file_request(arguments: HashMap<String, serde_json::Value>) -> PluginRequest {
+    PluginRequest::with_arguments(
+        "rename-symbol",
+        vec![
+            FilePayload::new(
+                PathBuf::from("src/main.py"),
+                "def old_name():\n    return 1\n",
+            ),
+            FilePayload::new(
+                PathBuf::from("src/consumer.py"),
+                "from main import old_name\n\nold_name()\n",
+            ),
+            FilePayload::new(PathBuf::from("src/unrelated.py"), "VALUE = 1\n"),
+        ],
+        arguments,
+    )
+}
+
+#[rstest]
+fn rename_emits_one_patch_per_changed_file(rename_arguments: HashMap<String, serde_json::Value>) {
+    let mut adapter = MockAdapter::new();
+    adapter
+        .expect_rename()
+        .withf(|files, _offset, _new_name| {
+            files.len() == 3
+                && files
+                    .first()
+                    .is_some_and(|file| file.path() == Path::new("src/main.py"))
+        })
+        .once()
+        .return_once(|_files, _offset, _new_name| {
+            Ok(changed_files(&[
+                (
+                    "src/consumer.py",
+                    "from main import new_name\n\nnew_name()\n",
+                ),
+                ("src/main.py", "def new_name():\n    return 1\n"),
+                ("src/unrelated.py", "VALUE = 1\n"),
+            ]))
+        });
+
+    let response = execute_request(&adapter, &multi_file_request(rename_arguments))
+        .expect("multi-file rename should succeed");
+    let PluginOutput::Diff { content } = response.output() else {
+        panic!("expected diff output, got {:?}", response.output());
+    };
+    assert_eq!(content.matches("diff --git ").count(), 2);
+    assert!(content.contains("diff --git a/src/consumer.py b/src/consumer.py\n"));
+    assert!(content.contains("diff --git a/src/main.py b/src/main.py\n"));
+    assert!(!content.contains("src/unrelated.py"));
+}
+
+#[rstest]
+fn rename_rejects_changes_to_unsupplied_files(
+    rename_arguments: HashMap<String, serde_json::Value>,
+) {
+    let adapter = adapter_returning_changes(Ok(changed_files(&[
+        ("src/main.py", "def new_name():\n    return 1\n"),
+        ("src/other.py", "new_name()\n"),
+    ])));
+
+    let failure = execute_request(&adapter, &request_with_args(rename_arguments))
+        .expect_err("changes to unknown files should fail");
+    assert!(
+        failure.to_string().contains("src/other.py"),
+        "expected error naming the unknown file, got: {failure}"
+    );
+}
+
 #[rstest]
 fn missing_arguments_include_incomplete_payload_reason_code() {
     let adapter = adapter_unused();
@@ -289,3 +623,49 @@ fn run_with_adapter_dispatch_layer(
     let response = dispatch_stdin(&input, &adapter);
     assert_eq!(response.is_success(), expect_success);
 }
+
+#[rstest]
+#[case::whole_seconds(Duration::from_secs(30), "timed out after 30s")]
+#[case::sub_second(Duration::from_millis(250), "timed out after 250ms")]
+fn adapter_timeout_is_reported_as_diagnostic(#[case] limit: Duration, #[case] expected: &str) {
+    let adapter = adapter_returning(Err(RopeAdapterError::Timeout { limit }));
+    let response = dispatch_stdin(format!("{}\n", valid_request_json()).as_bytes(), &adapter);
+
+    assert!(!response.is_success());
+    assert!(
+        response
+            .diagnostics()
+            .first()
+            .is_some_and(|diagnostic| diagnostic.message().contains(expected)),
+        "expected timeout diagnostic, got: {:?}",
+        response.diagnostics()
+    );
+}
+
+#[rstest]
+fn serve_with_adapter_answers_each_request_line() {
+    let mut adapter = MockAdapter::new();
+    adapter.expect_rename().times(2).returning(|_, _, _| {
+        Ok(changed_files(&[(
+            "src/main.py",
+            "def new_name():\n    return 1\n",
+        )]))
+    });
+    let input = format!("{request}\n{request}\n", request = valid_request_json());
+    let mut stdin = std::io::Cursor::new(input.into_bytes());
+    let mut stdout = Vec::new();
+
+    serve_with_adapter(&mut stdin, &mut stdout, &adapter).expect("serve should succeed");
+
+    let output = String::from_utf8(stdout).expect("utf8 stdout");
+    let responses: Vec<weaver_plugins::protocol::PluginResponse> = output
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("parse response"))
+        .collect();
+    assert_eq!(responses.len(), 2);
+    assert!(
+        responses
+            .iter()
+            .all(weaver_plugins::protocol::PluginResponse::is_success)
+    );
+}
@@ -0,0 +1,120 @@
+//! Tests for the persistent rope server adapter.
+
+use std::{path::PathBuf, time::Duration};
+
+use weaver_plugins::protocol::FilePayload;
+
+use super::python_binary::{PythonBinaryOverride, install_fake_python};
+use crate::{PersistentRopeAdapter, RopeAdapter, RopeAdapterError};
+
+fn main_py() -> [FilePayload; 1] {
+    [FilePayload::new(
+        PathBuf::from("src/main.py"),
+        "def old_name():\n    return 1\n",
+    )]
+}
+
+#[test]
+fn persistent_adapter_reuses_one_server_process() {
+    // Each reply carries the number of requests this process has served.
+    let (_scripts, wrapper) = install_fake_python(concat!(
+        "count=0\n",
+        "while read -r line; do\n",
+        "  count=$((count + 1))\n",
+        "  printf '{\"ok\": true, \"changes\": {\"src/main.py\": \"%s\"}}\\n' \"$count\"\n",
+        "done",
+    ));
+    let _guard = PythonBinaryOverride::set(wrapper.to_str().expect("utf-8 temp path"));
+    let adapter = PersistentRopeAdapter::new();
+
+    let first = adapter.rename(&main_py(), 4, "a").expect("first call");
+    let second = adapter.rename(&main_py(), 4, "b").expect("second call");
+
+    assert_eq!(first.get("src/main.py").map(String::as_str), Some("1"));
+    assert_eq!(second.get("src/main.py").map(String::as_str), Some("2"));
+}
+
+#[test]
+fn persistent_adapter_surfaces_server_errors() {
+    let (_scripts, wrapper) = install_fake_python(concat!(
+        "while read -r line; do\n",
+        "  printf '{\"ok\": false, \"error\": \"BadIdentifierError: bad name\"}\\n'\n",
+        "done",
+    ));
+    let _guard = PythonBinaryOverride::set(wrapper.to_str().expect("utf-8 temp path"));
+
+    let result = PersistentRopeAdapter::new().rename(&main_py(), 4, "b");
+
+    assert!(
+        matches!(&result, Err(RopeAdapterError::EngineFailed { message }) if message.contains("bad name")),
+        "expected engine failure, got {result:?}"
+    );
+}
+
+#[test]
+fn persistent_adapter_reports_server_exit_with_stderr() {
+    let (_scripts, wrapper) =
+        install_fake_python("echo 'ModuleNotFoundError: No module named rope' >&2\nexit 1");
+    let _guard = PythonBinaryOverride::set(wrapper.to_str().expect("utf-8 temp path"));
+
+    let result = PersistentRopeAdapter::new().rename(&main_py(), 4, "b");
+
+    assert!(
+        matches!(&result, Err(RopeAdapterError::EngineFailed { message }) if message.contains("No module named rope")),
+        "expected engine failure, got {result:?}"
+    );
+}
+
+#[test]
+fn persistent_adapter_kills_unresponsive_server_and_restarts() {
+    // The first server reads a request and never replies; its replacement
+    // answers normally, which proves the hung process was discarded.
+    let (_scripts, wrapper) = install_fake_python(concat!(
+        "if [ -e \"$0.hung\" ]; then\n",
+        "  while read -r line; do\n",
+        "    printf '{\"ok\": true, \"changes\": {}}\\n'\n",
+        "  done\n",
+        "else\n",
+        "  : > \"$0.hung\"\n",
+        "  read -r line\n",
+        "  exec sleep 30\n",
+        "fi",
+    ));
+    let _guard = PythonBinaryOverride::set(wrapper.to_str().expect("utf-8 temp path"));
+    let adapter = PersistentRopeAdapter::with_timeout(Duration::from_secs(1));
+
+    let timed_out = adapter.rename(&main_py(), 4, "b");
+    let restarted = adapter.rename(&main_py(), 4, "b");
+
+    assert!(
+        matches!(timed_out, Err(RopeAdapterError::Timeout { limit }) if limit == Duration::from_secs(1)),
+        "expected timeout error, got {timed_out:?}"
+    );
+    assert!(
+        restarted.is_ok_and(|changed| changed.is_empty()),
+        "restarted server should reply"
+    );
+}
+
+#[test]
+fn persistent_adapter_passes_character_offsets_to_rope() {
+    // Echo the selection the server receives back as the file content.
+    let (_scripts, wrapper) = install_fake_python(concat!(
+        "while read -r line; do\n",
+        "  start=$(printf '%s' \"$line\" | sed 's/.*\"start\":\\([0-9]*\\).*/\\1/')\n",
+        "  end=$(printf '%s' \"$line\" | sed 's/.*\"end\":\\([0-9]*\\).*/\\1/')\n",
+        "  printf '{\"ok\": true, \"changes\": {\"src/main.py\": \"%s..%s\"}}\\n' ",
+        "\"$start\" \"$end\"\n",
+        "done",
+    ));
+    let _guard = PythonBinaryOverride::set(wrapper.to_str().expect("utf-8 temp path"));
+    let content = "# café ☕\nvalue = 1\n";
+    let start = content.find("value").expect("selection present");
+    let file = FilePayload::new(PathBuf::from("src/main.py"), content);
+
+    let modified = PersistentRopeAdapter::new()
+        .extract_method(&file, start..start + "value = 1".len(), "helper")
+        .expect("server should reply");
+
+    assert_eq!(modified, "9..18");
+}
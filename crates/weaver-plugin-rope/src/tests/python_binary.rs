@@ -0,0 +1,152 @@
+//! Tests for the Python interpreter invocation used by the rope adapter.
+
+use std::{
+    ffi::OsString,
+    os::unix::fs::PermissionsExt,
+    path::PathBuf,
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+use cap_std::{ambient_authority, fs::Dir};
+use rstest::rstest;
+use weaver_plugins::protocol::FilePayload;
+
+use crate::{
+    PythonRopeAdapter,
+    RopeAdapter,
+    RopeAdapterError,
+    python::{PYTHON_BINARY_ENV, resolve_python_binary},
+};
+
+/// Serializes access to `WEAVER_PYTHON_BINARY` across parallel test threads.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+/// Sets or clears `WEAVER_PYTHON_BINARY` for the lifetime of the guard.
+///
+/// Only the Python adapter tests touch the variable, so holding the module
+/// lock and restoring the prior value on drop keeps other tests unaffected.
+pub(super) struct PythonBinaryOverride {
+    previous: Option<OsString>,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl PythonBinaryOverride {
+    pub(super) fn set(value: &str) -> Self { Self::apply(Some(value)) }
+
+    /// Sets the variable to `value`, or removes it when `value` is `None`.
+    fn apply(value: Option<&str>) -> Self {
+        let lock = ENV_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let previous = std::env::var_os(PYTHON_BINARY_ENV);
+        match value {
+            Some(binary) => unsafe { std::env::set_var(PYTHON_BINARY_ENV, binary) },
+            None => unsafe { std::env::remove_var(PYTHON_BINARY_ENV) },
+        }
+        Self {
+            previous,
+            _lock: lock,
+        }
+    }
+}
+
+impl Drop for PythonBinaryOverride {
+    fn drop(&mut self) {
+        match self.previous.take() {
+            Some(value) => unsafe { std::env::set_var(PYTHON_BINARY_ENV, value) },
+            None => unsafe { std::env::remove_var(PYTHON_BINARY_ENV) },
+        }
+    }
+}
+
+#[rstest]
+#[case::unset_falls_back(None, "python3")]
+#[case::empty_falls_back(Some(""), "python3")]
+#[case::blank_falls_back(Some("   "), "python3")]
+#[case::trims_whitespace(Some("  /opt/venv/bin/python \n"), "/opt/venv/bin/python")]
+fn resolve_python_binary_honours_override(#[case] value: Option<&str>, #[case] expected: &str) {
+    let _guard = PythonBinaryOverride::apply(value);
+    assert_eq!(resolve_python_binary(), expected);
+}
+
+/// Writes an executable shell script standing in for the Python interpreter.
+pub(super) fn install_fake_python(body: &str) -> (tempfile::TempDir, PathBuf) {
+    let scripts = tempfile::tempdir().expect("script directory should be created");
+    let scripts_dir =
+        Dir::open_ambient_dir(scripts.path(), ambient_authority()).expect("script dir opens");
+    scripts_dir
+        .write("fake-python", format!("#!/bin/sh\n{body}\n"))
+        .expect("wrapper script should be written");
+    scripts_dir
+        .set_permissions(
+            "fake-python",
+            cap_std::fs::Permissions::from_std(std::fs::Permissions::from_mode(0o755)),
+        )
+        .expect("wrapper script should be executable");
+    let wrapper = scripts.path().join("fake-python");
+    (scripts, wrapper)
+}
+
+fn main_py() -> [FilePayload; 1] {
+    [FilePayload::new(
+        PathBuf::from("src/main.py"),
+        "def old_name():\n    return 1\n",
+    )]
+}
+
+#[test]
+fn python_adapter_invokes_configured_interpreter() {
+    let (_scripts, wrapper) = install_fake_python(
+        "printf '%s' '{\"src/main.py\": \"def wrapped():\\n    return 1\\n\"}'",
+    );
+    let _guard = PythonBinaryOverride::set(wrapper.to_str().expect("utf-8 temp path"));
+
+    let changed = PythonRopeAdapter::default()
+        .rename(&main_py(), 4, "wrapped")
+        .expect("wrapper interpreter should succeed");
+
+    assert_eq!(
+        changed.get("src/main.py").map(String::as_str),
+        Some("def wrapped():\n    return 1\n")
+    );
+}
+
+#[test]
+fn python_adapter_passes_character_offsets_to_rope() {
+    // Echo the selection rope receives back as the file content.
+    let (_scripts, wrapper) =
+        install_fake_python("printf '{\"src/main.py\": \"%s..%s\"}' \"$5\" \"$6\"");
+    let _guard = PythonBinaryOverride::set(wrapper.to_str().expect("utf-8 temp path"));
+    let content = "# café ☕\nvalue = 1\n";
+    let start = content.find("value").expect("selection present");
+    let file = FilePayload::new(PathBuf::from("src/main.py"), content);
+
+    let modified = PythonRopeAdapter::default()
+        .extract_method(&file, start..start + "value = 1".len(), "helper")
+        .expect("wrapper interpreter should succeed");
+
+    assert_eq!(modified, "9..18");
+}
+
+#[test]
+fn python_adapter_kills_interpreter_after_timeout() {
+    let (_scripts, wrapper) = install_fake_python("exec sleep 30");
+    let _guard = PythonBinaryOverride::set(wrapper.to_str().expect("utf-8 temp path"));
+
+    let started = Instant::now();
+    let result =
+        PythonRopeAdapter::with_timeout(Duration::from_secs(1)).rename(&main_py(), 4, "wrapped");
+
+    assert!(
+        matches!(result, Err(RopeAdapterError::Timeout { limit }) if limit == Duration::from_secs(1)),
+        "expected timeout error, got {result:?}"
+    );
+    assert!(started.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn python_adapter_defaults_to_thirty_second_timeout() {
+    assert_eq!(
+        PythonRopeAdapter::default().timeout(),
+        Duration::from_secs(30)
+    );
+}
@@ -0,0 +1,120 @@
+//! Tests for locating rope failures within the request files.
+
+use std::path::PathBuf;
+
+use rstest::rstest;
+use weaver_plugins::{capability::ReasonCode, protocol::FilePayload};
+
+use super::{adapter_returning, dispatch_stdin, valid_request_json};
+use crate::{
+    RopeAdapterError,
+    traceback::{SourceLocation, locate_failure, summary_line},
+};
+
+const ROPE_SYNTAX_ERROR: &str = concat!(
+    "Traceback (most recent call last):\n",
+    "  File \"<string>\", line 9, in <module>\n",
+    "  File \"/usr/lib/python3/site-packages/rope/base/pyobjectsdef.py\", line 210, in _init\n",
+    "    raise exceptions.ModuleSyntaxError(...)\n",
+    "rope.base.exceptions.ModuleSyntaxError: ",
+    "Syntax error in file <src/main.py> line <3>: invalid syntax",
+);
+
+const USER_FRAME_TRACEBACK: &str = concat!(
+    "Traceback (most recent call last):\n",
+    "  File \"/tmp/.tmpA1b2/src/main.py\", line 2, in <module>\n",
+    "  File \"/tmp/.tmpA1b2/src/consumer.py\", line 7, in helper\n",
+    "  File \"/usr/lib/python3/site-packages/rope/base/worder.py\", line 88, in get\n",
+    "rope.base.exceptions.RefactoringError: Unresolvable name selected",
+);
+
+fn request_files() -> Vec<FilePayload> {
+    vec![
+        FilePayload::new(PathBuf::from("src/main.py"), "def old_name():\n"),
+        FilePayload::new(PathBuf::from("src/consumer.py"), "old_name()\n"),
+    ]
+}
+
+fn location(path: &str, line: u32) -> SourceLocation {
+    SourceLocation {
+        path: PathBuf::from(path),
+        line,
+    }
+}
+
+#[rstest]
+#[case::rope_syntax_error(ROPE_SYNTAX_ERROR, Some(location("src/main.py", 3)))]
+#[case::innermost_user_frame(USER_FRAME_TRACEBACK, Some(location("src/consumer.py", 7)))]
+#[case::syntax_error_frame(
+    "  File \"/tmp/ws/src/main.py\", line 4\n    def (:\nSyntaxError: invalid syntax",
+    Some(location("src/main.py", 4))
+)]
+#[case::only_library_frames(
+    "  File \"/usr/lib/rope/base/main.py\", line 4, in run\nValueError: boom",
+    None
+)]
+#[case::unsupplied_syntax_error_file(
+    "Syntax error in file <src/other.py> line <2>: invalid syntax",
+    None
+)]
+#[case::unstructured("rope failed", None)]
+fn locate_failure_finds_request_files(
+    #[case] stderr: &str,
+    #[case] expected: Option<SourceLocation>,
+) {
+    assert_eq!(locate_failure(stderr, &request_files()), expected);
+}
+
+#[rstest]
+fn summary_line_is_final_exception_line() {
+    assert_eq!(
+        summary_line(USER_FRAME_TRACEBACK),
+        Some("rope.base.exceptions.RefactoringError: Unresolvable name selected")
+    );
+    assert_eq!(summary_line("\n  \n"), None);
+}
+
+#[rstest]
+fn located_engine_failure_is_reported_with_file_and_line() {
+    let adapter = adapter_returning(Err(RopeAdapterError::EngineFailed {
+        message: String::from(ROPE_SYNTAX_ERROR),
+    }));
+    let response = dispatch_stdin(format!("{}\n", valid_request_json()).as_bytes(), &adapter);
+
+    let diagnostic = response
+        .diagnostics()
+        .first()
+        .expect("failure should carry a diagnostic");
+    let json = serde_json::to_value(diagnostic).expect("diagnostic should serialize");
+    assert_eq!(
+        json.get("file").and_then(serde_json::Value::as_str),
+        Some("src/main.py")
+    );
+    assert_eq!(
+        json.get("line").and_then(serde_json::Value::as_u64),
+        Some(3)
+    );
+    assert_eq!(diagnostic.reason_code(), Some(ReasonCode::SymbolNotFound));
+    assert!(
+        !diagnostic.message().contains("Traceback"),
+        "expected condensed message, got: {}",
+        diagnostic.message()
+    );
+}
+
+#[rstest]
+fn unlocated_engine_failure_keeps_raw_message() {
+    let adapter = adapter_returning(Err(RopeAdapterError::EngineFailed {
+        message: String::from("rope failed\nwith detail"),
+    }));
+    let response = dispatch_stdin(format!("{}\n", valid_request_json()).as_bytes(), &adapter);
+
+    let diagnostic = response
+        .diagnostics()
+        .first()
+        .expect("failure should carry a diagnostic");
+    let json = serde_json::to_value(diagnostic).expect("diagnostic should serialize");
+    assert!(json.get("file").is_none());
+    assert!(json.get("line").is_none());
+    assert!(diagnostic.message().contains("rope failed\nwith detail"));
+}
@@ -0,0 +1,83 @@
+//! Source-location extraction from rope and Python failure output.
+//!
+//! Rope reports failures as raw interpreter stderr. Two shapes commonly name
+//! the offending user file: rope's own `ModuleSyntaxError` text
+//! (`Syntax error in file <src/main.py> line <3>: ...`) and ordinary Python
+//! traceback frames (`File "/tmp/.../src/main.py", line 3, in <module>`).
+//! Frames are only trusted when they resolve to a file supplied in the
+//! request, because most of a rope traceback points into rope's internals or
+//! the adapter's temporary workspace.
+
+use std::path::PathBuf;
+
+use weaver_plugins::protocol::FilePayload;
+
+use crate::path_to_slash;
+
+/// File and one-based line that a rope failure refers to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SourceLocation {
+    /// Request-relative path of the offending file.
+    pub(crate) path: PathBuf,
+    /// One-based line number within `path`.
+    pub(crate) line: u32,
+}
+
+/// Finds the request file and line that `stderr` blames, if any.
+///
+/// Rope's explicit syntax-error report wins over traceback frames. Among
+/// frames, the innermost one that refers to a supplied file is chosen.
+pub(crate) fn locate_failure(stderr: &str, files: &[FilePayload]) -> Option<SourceLocation> {
+    stderr
+        .lines()
+        .find_map(parse_rope_syntax_error)
+        .and_then(|(path, line)| resolve(files, path, line))
+        .or_else(|| {
+            stderr
+                .lines()
+                .rev()
+                .filter_map(parse_traceback_frame)
+                .find_map(|(path, line)| resolve(files, path, line))
+        })
+}
+
+/// Returns the final non-empty line of `stderr`, which for a Python
+/// traceback is the exception type and message.
+pub(crate) fn summary_line(stderr: &str) -> Option<&str> {
+    stderr.lines().map(str::trim).rfind(|line| !line.is_empty())
+}
+
+/// Parses `Syntax error in file <path> line <n>` as emitted by rope.
+fn parse_rope_syntax_error(line: &str) -> Option<(&str, u32)> {
+    let (_, located) = line.split_once("Syntax error in file <")?;
+    let (path, numbered) = located.split_once("> line <")?;
+    let (number, _) = numbered.split_once('>')?;
+    Some((path, number.parse().ok()?))
+}
+
+/// Parses a `File "<path>", line <n>` traceback frame header.
+fn parse_traceback_frame(line: &str) -> Option<(&str, u32)> {
+    let quoted = line.trim_start().strip_prefix("File \"")?;
+    let (path, numbered) = quoted.split_once("\", line ")?;
+    let digits: String = numbered.chars().take_while(char::is_ascii_digit).collect();
+    Some((path, digits.parse().ok()?))
+}
+
+/// Maps a path reported by Python onto the matching request payload.
+///
+/// Reported paths may be absolute inside the temporary workspace, so a
+/// payload matches when the reported path equals it or ends with it as a
+/// whole path suffix.
+fn resolve(files: &[FilePayload], reported: &str, line: u32) -> Option<SourceLocation> {
+    let normalized = reported.replace('\\', "/");
+    files
+        .iter()
+        .find(|payload| {
+            let relative = path_to_slash(payload.path());
+            normalized == relative || normalized.ends_with(&format!("/{relative}"))
+        })
+        .map(|payload| SourceLocation {
+            path: payload.path().to_path_buf(),
+            line,
+        })
+}
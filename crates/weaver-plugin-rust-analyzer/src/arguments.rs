@@ -1,7 +1,13 @@
-//! Argument parsing for `rename-symbol` plugin requests.
+//! Argument parsing for `rename-symbol`, `prepare-rename`, `replace-body`, and
+//! `extract-predicate` plugin requests.
 //!
 //! Validates and extracts the `uri`, `position`, and `new_name` fields from a
-//! rename-symbol plugin request.
+//! rename-symbol plugin request, together with the optional `target_path`
+//! that selects the rename target when several files are supplied. A
+//! prepare-rename request carries only `uri` and `position`, and a
+//! replace-body request adds `new_body` to those. An extract-predicate
+//! request selects a span with `start_offset` and `end_offset` and names the
+//! new function with `new_name`.
 
 use std::collections::HashMap;
 
@@ -10,6 +16,7 @@ pub(crate) struct RenameSymbolArgs {
     uri: String,
     offset: usize,
     new_name: String,
+    target_path: Option<String>,
 }
 
 impl RenameSymbolArgs {
@@ -21,6 +28,63 @@ impl RenameSymbolArgs {
 
     /// Returns the new symbol name.
     pub(crate) fn new_name(&self) -> &str { &self.new_name }
+
+    /// Returns the workspace-relative path of the rename target, if given.
+    pub(crate) fn target_path(&self) -> Option<&str> { self.target_path.as_deref() }
+}
+
+/// Validated prepare-rename arguments extracted from a plugin request.
+pub(crate) struct PrepareRenameArgs {
+    uri: String,
+    offset: usize,
+}
+
+impl PrepareRenameArgs {
+    /// Returns the request URI.
+    pub(crate) fn uri(&self) -> &str { &self.uri }
+
+    /// Returns the byte offset parsed from the `position` field.
+    pub(crate) const fn offset(&self) -> usize { self.offset }
+}
+
+/// Validated replace-body arguments extracted from a plugin request.
+pub(crate) struct ReplaceBodyArgs {
+    uri: String,
+    offset: usize,
+    new_body: String,
+}
+
+impl ReplaceBodyArgs {
+    /// Returns the request URI.
+    pub(crate) fn uri(&self) -> &str { &self.uri }
+
+    /// Returns the byte offset parsed from the `position` field.
+    pub(crate) const fn offset(&self) -> usize { self.offset }
+
+    /// Returns the replacement body block, braces included.
+    pub(crate) fn new_body(&self) -> &str { &self.new_body }
+}
+
+/// Validated extract-predicate arguments extracted from a plugin request.
+pub(crate) struct ExtractPredicateArgs {
+    uri: String,
+    start_offset: usize,
+    end_offset: usize,
+    new_name: String,
+}
+
+impl ExtractPredicateArgs {
+    /// Returns the request URI.
+    pub(crate) fn uri(&self) -> &str { &self.uri }
+
+    /// Returns the byte offset where the selected expression starts.
+    pub(crate) const fn start_offset(&self) -> usize { self.start_offset }
+
+    /// Returns the byte offset just past the selected expression.
+    pub(crate) const fn end_offset(&self) -> usize { self.end_offset }
+
+    /// Returns the name of the predicate function to create.
+    pub(crate) fn new_name(&self) -> &str { &self.new_name }
 }
 
 /// Parses and validates rename-symbol arguments from the request map.
@@ -32,20 +96,84 @@ impl RenameSymbolArgs {
 pub(crate) fn parse_rename_symbol_arguments(
     arguments: &HashMap<String, serde_json::Value>,
 ) -> Result<RenameSymbolArgs, String> {
-    let uri = parse_uri(arguments)?;
-    let offset = parse_position(arguments)?;
-    let new_name = parse_new_name(arguments)?;
+    let uri = parse_uri(arguments, "rename-symbol")?;
+    let offset = parse_offset(arguments, "position", "rename-symbol")?;
+    let new_name = parse_new_name(arguments, "rename-symbol")?;
+    let target_path = parse_target_path(arguments)?;
     Ok(RenameSymbolArgs {
         uri,
         offset,
         new_name,
+        target_path,
+    })
+}
+
+/// Parses and validates prepare-rename arguments from the request map.
+///
+/// # Errors
+///
+/// Returns a human-readable error message if `uri` or `position` is missing,
+/// has the wrong type, or is empty.
+pub(crate) fn parse_prepare_rename_arguments(
+    arguments: &HashMap<String, serde_json::Value>,
+) -> Result<PrepareRenameArgs, String> {
+    let uri = parse_uri(arguments, "prepare-rename")?;
+    let offset = parse_offset(arguments, "position", "prepare-rename")?;
+    Ok(PrepareRenameArgs { uri, offset })
+}
+
+/// Parses and validates replace-body arguments from the request map.
+///
+/// # Errors
+///
+/// Returns a human-readable error message if any required field is missing,
+/// has the wrong type, or if `new_body` is not a brace-delimited block.
+pub(crate) fn parse_replace_body_arguments(
+    arguments: &HashMap<String, serde_json::Value>,
+) -> Result<ReplaceBodyArgs, String> {
+    let uri = parse_uri(arguments, "replace-body")?;
+    let offset = parse_offset(arguments, "position", "replace-body")?;
+    let new_body = parse_new_body(arguments)?;
+    Ok(ReplaceBodyArgs {
+        uri,
+        offset,
+        new_body,
+    })
+}
+
+/// Parses and validates extract-predicate arguments from the request map.
+///
+/// # Errors
+///
+/// Returns a human-readable error message if any required field is missing,
+/// has the wrong type, or if the offsets do not describe a non-empty span.
+pub(crate) fn parse_extract_predicate_arguments(
+    arguments: &HashMap<String, serde_json::Value>,
+) -> Result<ExtractPredicateArgs, String> {
+    let uri = parse_uri(arguments, "extract-predicate")?;
+    let start_offset = parse_offset(arguments, "start_offset", "extract-predicate")?;
+    let end_offset = parse_offset(arguments, "end_offset", "extract-predicate")?;
+    if start_offset >= end_offset {
+        return Err(format!(
+            "start_offset ({start_offset}) must be less than end_offset ({end_offset})"
+        ));
+    }
+    let new_name = parse_new_name(arguments, "extract-predicate")?;
+    Ok(ExtractPredicateArgs {
+        uri,
+        start_offset,
+        end_offset,
+        new_name,
     })
 }
 
-fn parse_uri(arguments: &HashMap<String, serde_json::Value>) -> Result<String, String> {
+fn parse_uri(
+    arguments: &HashMap<String, serde_json::Value>,
+    operation: &str,
+) -> Result<String, String> {
     let uri_value = arguments
         .get("uri")
-        .ok_or_else(|| String::from("rename-symbol operation requires 'uri' argument"))?;
+        .ok_or_else(|| format!("{operation} operation requires 'uri' argument"))?;
     let uri = uri_value
         .as_str()
         .ok_or_else(|| String::from("uri argument must be a string"))?;
@@ -55,21 +183,28 @@ fn parse_uri(arguments: &HashMap<String, serde_json::Value>) -> Result<String, S
     Ok(String::from(uri))
 }
 
-fn parse_position(arguments: &HashMap<String, serde_json::Value>) -> Result<usize, String> {
-    let position_value = arguments
-        .get("position")
-        .ok_or_else(|| String::from("rename-symbol operation requires 'position' argument"))?;
-    let position_string = json_value_to_string(position_value)
-        .ok_or_else(|| String::from("position argument must be a string or number"))?;
-    position_string
+fn parse_offset(
+    arguments: &HashMap<String, serde_json::Value>,
+    key: &str,
+    operation: &str,
+) -> Result<usize, String> {
+    let offset_value = arguments
+        .get(key)
+        .ok_or_else(|| format!("{operation} operation requires '{key}' argument"))?;
+    let offset_string = json_value_to_string(offset_value)
+        .ok_or_else(|| format!("{key} argument must be a string or number"))?;
+    offset_string
         .parse::<usize>()
-        .map_err(|error| format!("position must be a non-negative integer: {error}"))
+        .map_err(|error| format!("{key} must be a non-negative integer: {error}"))
 }
 
-fn parse_new_name(arguments: &HashMap<String, serde_json::Value>) -> Result<String, String> {
+fn parse_new_name(
+    arguments: &HashMap<String, serde_json::Value>,
+    operation: &str,
+) -> Result<String, String> {
     let new_name_value = arguments
         .get("new_name")
-        .ok_or_else(|| String::from("rename-symbol operation requires 'new_name' argument"))?;
+        .ok_or_else(|| format!("{operation} operation requires 'new_name' argument"))?;
     let new_name = new_name_value
         .as_str()
         .ok_or_else(|| String::from("new_name argument must be a string"))?;
@@ -79,6 +214,37 @@ fn parse_new_name(arguments: &HashMap<String, serde_json::Value>) -> Result<Stri
     Ok(String::from(new_name))
 }
 
+fn parse_new_body(arguments: &HashMap<String, serde_json::Value>) -> Result<String, String> {
+    let new_body_value = arguments
+        .get("new_body")
+        .ok_or_else(|| String::from("replace-body operation requires 'new_body' argument"))?;
+    let new_body = new_body_value
+        .as_str()
+        .ok_or_else(|| String::from("new_body argument must be a string"))?;
+    let block = new_body.trim();
+    if !(block.starts_with('{') && block.ends_with('}')) {
+        return Err(String::from(
+            "new_body argument must be a block enclosed in braces",
+        ));
+    }
+    Ok(String::from(block))
+}
+
+fn parse_target_path(
+    arguments: &HashMap<String, serde_json::Value>,
+) -> Result<Option<String>, String> {
+    let Some(target_value) = arguments.get("target_path") else {
+        return Ok(None);
+    };
+    let target_path = target_value
+        .as_str()
+        .ok_or_else(|| String::from("target_path argument must be a string"))?;
+    if target_path.trim().is_empty() {
+        return Err(String::from("target_path argument must not be empty"));
+    }
+    Ok(Some(String::from(target_path)))
+}
+
 fn json_value_to_string(value: &serde_json::Value) -> Option<String> {
     match value {
         serde_json::Value::String(text) => Some(text.to_owned()),
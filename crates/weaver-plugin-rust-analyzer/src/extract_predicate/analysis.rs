@@ -0,0 +1,237 @@
+//! Syntactic analysis of the expression selected for extraction.
+//!
+//! The selection must be exactly one boolean expression node inside a
+//! function. Its free variables are resolved against the enclosing function's
+//! parameters and the typed `let` bindings that precede it, which supplies the
+//! parameter list of the extracted predicate without type inference.
+
+use tree_sitter::Node;
+use weaver_plugins::capability::ReasonCode;
+
+use crate::failure::PluginFailure;
+
+/// Binary operators whose result is always `bool`.
+const BOOLEAN_OPERATORS: &[&str] = &["&&", "||", "==", "!=", "<", "<=", ">", ">="];
+
+/// Types that are cheap to copy and are therefore passed by value.
+const COPY_PRIMITIVES: &[&str] = &[
+    "bool", "char", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128",
+    "usize", "f32", "f64",
+];
+
+/// A variable the extracted predicate must take as a parameter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Binding {
+    pub(super) name: String,
+    pub(super) ty: String,
+}
+
+impl Binding {
+    /// Returns true when the binding should be borrowed rather than moved.
+    fn borrowed(&self) -> bool {
+        !(self.ty.starts_with('&') || COPY_PRIMITIVES.contains(&self.ty.as_str()))
+    }
+
+    /// Renders the binding as a predicate parameter.
+    pub(super) fn parameter(&self) -> String {
+        let borrow = if self.borrowed() { "&" } else { "" };
+        format!("{}: {borrow}{}", self.name, self.ty)
+    }
+
+    /// Renders the binding as an argument at the call site.
+    pub(super) fn argument(&self) -> String {
+        let borrow = if self.borrowed() { "&" } else { "" };
+        format!("{borrow}{}", self.name)
+    }
+}
+
+/// Locates the boolean expression spanning exactly `start..end`.
+pub(super) fn select_boolean_expression(
+    root: Node<'_>,
+    start: usize,
+    end: usize,
+) -> Result<Node<'_>, PluginFailure> {
+    let node = root
+        .descendant_for_byte_range(start, end)
+        .filter(|node| node.start_byte() == start && node.end_byte() == end)
+        .ok_or_else(|| {
+            PluginFailure::with_reason(
+                format!("byte range {start}..{end} does not select a single expression"),
+                ReasonCode::SymbolNotFound,
+            )
+        })?;
+    if !is_boolean_expression(node) {
+        return Err(PluginFailure::with_reason(
+            format!(
+                "byte range {start}..{end} selects a {} node, not a boolean expression",
+                node.kind()
+            ),
+            ReasonCode::SymbolNotFound,
+        ));
+    }
+    Ok(node)
+}
+
+fn is_boolean_expression(node: Node<'_>) -> bool {
+    match node.kind() {
+        "boolean_literal" => true,
+        "binary_expression" => node
+            .child_by_field_name("operator")
+            .is_some_and(|operator| BOOLEAN_OPERATORS.contains(&operator.kind())),
+        "unary_expression" => node.child(0).is_some_and(|operator| operator.kind() == "!"),
+        "parenthesized_expression" => node.named_child(0).is_some_and(is_boolean_expression),
+        _ => false,
+    }
+}
+
+/// Returns the innermost `fn` item containing `node`.
+pub(super) fn enclosing_function(node: Node<'_>) -> Option<Node<'_>> {
+    let mut current = node.parent();
+    while let Some(candidate) = current {
+        if candidate.kind() == "function_item" {
+            return Some(candidate);
+        }
+        current = candidate.parent();
+    }
+    None
+}
+
+/// Returns true when `function` is an associated function of an `impl` or
+/// `trait` block, so the predicate must be called through `Self::`.
+pub(super) fn is_associated(function: Node<'_>) -> bool {
+    function
+        .parent()
+        .filter(|parent| parent.kind() == "declaration_list")
+        .and_then(|list| list.parent())
+        .is_some_and(|owner| matches!(owner.kind(), "impl_item" | "trait_item"))
+}
+
+/// Resolves the local variables used by `expression`, in order of first use.
+///
+/// Identifiers that name neither a parameter nor an earlier `let` binding are
+/// assumed to be items in scope, such as constants, and are left alone.
+pub(super) fn free_variables(
+    source: &str,
+    function: Node<'_>,
+    expression: Node<'_>,
+) -> Result<Vec<Binding>, PluginFailure> {
+    let mut bindings: Vec<Binding> = Vec::new();
+    let mut stack = vec![expression];
+    let mut identifiers = Vec::new();
+    while let Some(node) = stack.pop() {
+        if node.kind() == "self" {
+            return Err(PluginFailure::with_reason(
+                "extracting an expression that uses self is not supported",
+                ReasonCode::OperationNotSupported,
+            ));
+        }
+        if node.kind() == "identifier" {
+            identifiers.push(node);
+        }
+        let mut cursor = node.walk();
+        let children: Vec<_> = node.children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+
+    for identifier in identifiers {
+        let name = text(source, identifier);
+        if bindings.iter().any(|binding| binding.name == name) {
+            continue;
+        }
+        if let Some(binding) = resolve(source, function, expression, name)? {
+            bindings.push(binding);
+        }
+    }
+    Ok(bindings)
+}
+
+/// Finds the type of `name`, preferring the latest `let` in scope before
+/// `expression` over the function's parameters.
+fn resolve(
+    source: &str,
+    function: Node<'_>,
+    expression: Node<'_>,
+    name: &str,
+) -> Result<Option<Binding>, PluginFailure> {
+    let before = expression.start_byte();
+    let in_scope = |declaration: Node<'_>| {
+        declaration
+            .parent()
+            .is_some_and(|block| block.byte_range().contains(&before))
+    };
+    let mut latest_let = None;
+    if let Some(body) = function.child_by_field_name("body") {
+        let mut stack = vec![body];
+        while let Some(node) = stack.pop() {
+            if node.start_byte() >= before {
+                continue;
+            }
+            if node.kind() == "let_declaration"
+                && node.end_byte() <= before
+                && in_scope(node)
+                && binds(source, node, name)
+                && latest_let.is_none_or(|seen: Node<'_>| seen.start_byte() < node.start_byte())
+            {
+                latest_let = Some(node);
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.named_children(&mut cursor));
+        }
+    }
+    if let Some(declaration) = latest_let {
+        let ty = declaration.child_by_field_name("type").ok_or_else(|| {
+            PluginFailure::with_reason(
+                format!("cannot determine the type of '{name}'; annotate its let binding"),
+                ReasonCode::OperationNotSupported,
+            )
+        })?;
+        return Ok(Some(binding(source, name, ty)));
+    }
+
+    let Some(parameters) = function.child_by_field_name("parameters") else {
+        return Ok(None);
+    };
+    let mut cursor = parameters.walk();
+    let parameter = parameters
+        .named_children(&mut cursor)
+        .filter(|candidate| candidate.kind() == "parameter")
+        .find(|candidate| binds(source, *candidate, name));
+    Ok(parameter
+        .and_then(|found| found.child_by_field_name("type"))
+        .map(|ty| binding(source, name, ty)))
+}
+
+/// Returns true when `node`'s pattern is the plain identifier `name`.
+fn binds(source: &str, node: Node<'_>, name: &str) -> bool {
+    node.child_by_field_name("pattern")
+        .is_some_and(|pattern| pattern.kind() == "identifier" && text(source, pattern) == name)
+}
+
+fn binding(source: &str, name: &str, ty: Node<'_>) -> Binding {
+    Binding {
+        name: String::from(name),
+        ty: String::from(text(source, ty)),
+    }
+}
+
+/// Returns true when the file already defines a function called `name`.
+pub(super) fn defines_function(source: &str, root: Node<'_>, name: &str) -> bool {
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "function_item"
+            && node
+                .child_by_field_name("name")
+                .is_some_and(|ident| text(source, ident) == name)
+        {
+            return true;
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    false
+}
+
+/// Returns the source text covered by `node`.
+pub(super) fn text<'src>(source: &'src str, node: Node<'_>) -> &'src str {
+    source.get(node.byte_range()).unwrap_or_default()
+}
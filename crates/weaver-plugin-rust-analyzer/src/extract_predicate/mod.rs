@@ -0,0 +1,161 @@
+//! Execution of `extract-predicate` plugin requests.
+//!
+//! An extract-predicate request selects a boolean expression by byte range
+//! and names a new function. The expression moves into a predicate returning
+//! `bool`, placed directly after the enclosing function, and the selection is
+//! replaced with a call to it. The rewrite is purely syntactic, so it needs
+//! no rust-analyzer server and no adapter.
+
+mod analysis;
+
+use weaver_plugins::{
+    capability::ReasonCode,
+    protocol::{PluginOutput, PluginRequest, PluginResponse},
+};
+use weaver_syntax::{Parser, SupportedLanguage};
+
+use self::analysis::{
+    Binding,
+    defines_function,
+    enclosing_function,
+    free_variables,
+    is_associated,
+    select_boolean_expression,
+    text,
+};
+use crate::{
+    arguments::parse_extract_predicate_arguments,
+    ensure_uri_matches,
+    failure::PluginFailure,
+    patch::build_search_replace_patch,
+    path_utils::validate_relative_path,
+};
+
+/// Extracts the selected boolean expression into a named predicate.
+pub(crate) fn execute_extract_predicate(
+    request: &PluginRequest,
+) -> Result<PluginResponse, PluginFailure> {
+    let arguments = parse_extract_predicate_arguments(request.arguments())
+        .map_err(|message| PluginFailure::with_reason(message, ReasonCode::IncompletePayload))?;
+    let file = match request.files() {
+        [single] => single,
+        other => {
+            return Err(PluginFailure::with_reason(
+                format!(
+                    "extract-predicate operation requires exactly one file payload, got {}",
+                    other.len()
+                ),
+                ReasonCode::IncompletePayload,
+            ));
+        }
+    };
+    validate_relative_path(file.path()).map_err(|error| {
+        PluginFailure::with_reason(error.to_string(), ReasonCode::IncompletePayload)
+    })?;
+    ensure_uri_matches(arguments.uri(), file)?;
+    let name = arguments.new_name();
+    if !is_identifier(name) {
+        return Err(PluginFailure::with_reason(
+            format!("new_name '{name}' is not a valid Rust identifier"),
+            ReasonCode::IncompletePayload,
+        ));
+    }
+
+    let source = file.content();
+    let modified = extract(
+        source,
+        arguments.start_offset(),
+        arguments.end_offset(),
+        name,
+    )?;
+    Ok(PluginResponse::success(PluginOutput::Diff {
+        content: build_search_replace_patch(file.path(), source, &modified)?,
+    }))
+}
+
+/// Rewrites `source` so `start..end` calls a new predicate called `name`.
+fn extract(source: &str, start: usize, end: usize, name: &str) -> Result<String, PluginFailure> {
+    let parsed = Parser::new(SupportedLanguage::Rust)
+        .and_then(|mut parser| parser.parse(source))
+        .map_err(|error| PluginFailure::plain(format!("failed to parse Rust source: {error}")))?;
+    let root = parsed.root_node();
+    if defines_function(source, root, name) {
+        return Err(PluginFailure::with_reason(
+            format!("a function named '{name}' already exists in this file"),
+            ReasonCode::NameConflict,
+        ));
+    }
+
+    let expression = select_boolean_expression(root, start, end)?;
+    let function = enclosing_function(expression).ok_or_else(|| {
+        PluginFailure::with_reason(
+            format!("byte range {start}..{end} is not inside a function"),
+            ReasonCode::SymbolNotFound,
+        )
+    })?;
+    let bindings = free_variables(source, function, expression)?;
+
+    let body = match expression.kind() {
+        "parenthesized_expression" => expression.named_child(0).unwrap_or(expression),
+        _ => expression,
+    };
+    let indent = line_indent(source, function.start_byte());
+    let parameters = join(&bindings, Binding::parameter);
+    let predicate = format!(
+        "\n\n{indent}fn {name}({parameters}) -> bool {{\n{indent}    {}\n{indent}}}",
+        text(source, body)
+    );
+    let receiver = if is_associated(function) {
+        "Self::"
+    } else {
+        ""
+    };
+    let call = format!("{receiver}{name}({})", join(&bindings, Binding::argument));
+
+    let insert_at = function.end_byte();
+    let (Some(before), Some(between), Some(after)) = (
+        source.get(..start),
+        source.get(end..insert_at),
+        source.get(insert_at..),
+    ) else {
+        return Err(PluginFailure::plain(format!(
+            "byte range {start}..{end} is not a valid span of the file"
+        )));
+    };
+    Ok(format!("{before}{call}{between}{predicate}{after}"))
+}
+
+/// Returns the whitespace that precedes `offset` on its line.
+fn line_indent(source: &str, offset: usize) -> &str {
+    let prefix = source.get(..offset).unwrap_or_default();
+    let line = prefix.rsplit('\n').next().unwrap_or_default();
+    if line.chars().all(char::is_whitespace) {
+        line
+    } else {
+        ""
+    }
+}
+
+fn join(bindings: &[Binding], render: fn(&Binding) -> String) -> String {
+    bindings.iter().map(render).collect::<Vec<_>>().join(", ")
+}
+
+/// Strict and reserved Rust keywords, which cannot name a function without
+/// the raw identifier prefix.
+const RUST_KEYWORDS: &[&str] = &[
+    "Self", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if",
+    "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first == '_' || first.is_ascii_alphabetic())
+        && chars.all(|rest| rest == '_' || rest.is_ascii_alphanumeric())
+        && name != "_"
+        && !RUST_KEYWORDS.contains(&name)
+}
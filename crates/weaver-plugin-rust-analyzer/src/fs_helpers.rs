@@ -61,6 +61,35 @@ pub(crate) fn write_workspace_file(
     Ok(absolute_path)
 }
 
+/// Removes a workspace-relative file previously staged with
+/// [`write_workspace_file`]. Missing files are ignored.
+///
+/// # Errors
+///
+/// Returns [`RustAnalyzerAdapterError`] when the path is invalid or the
+/// removal fails for a reason other than the file already being absent.
+pub(crate) fn remove_workspace_file(
+    workspace_root: &Path,
+    relative_path: &Path,
+) -> Result<(), RustAnalyzerAdapterError> {
+    validate_relative_path(relative_path)?;
+    let (absolute_path, workspace_relative_path) =
+        resolve_workspace_path(workspace_root, relative_path)?;
+    let workspace_dir = Dir::open_ambient_dir(workspace_root, cap_std::ambient_authority())
+        .map_err(|source| RustAnalyzerAdapterError::WorkspaceWrite {
+            path: workspace_root.to_path_buf(),
+            source,
+        })?;
+    match workspace_dir.remove_file(&workspace_relative_path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(source) => Err(RustAnalyzerAdapterError::WorkspaceWrite {
+            path: absolute_path,
+            source,
+        }),
+    }
+}
+
 fn resolve_workspace_path(
     workspace_root: &Path,
     relative_path: &Path,
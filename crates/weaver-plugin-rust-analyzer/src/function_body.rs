@@ -0,0 +1,102 @@
+//! Syntactic lookup of function bodies for `replace-body` requests.
+//!
+//! rust-analyzer has no LSP request that returns a function's body, so the
+//! adapter parses the file with Tree-sitter instead. The lookup is purely
+//! syntactic: it finds the innermost `fn` item whose span contains the offset
+//! and reports the span of its body block.
+
+use std::ops::Range;
+
+use weaver_syntax::{Parser, SupportedLanguage};
+
+use crate::{ByteOffset, RustAnalyzerAdapterError};
+
+/// Tree-sitter node kind for a `fn` item with a body.
+const FUNCTION_ITEM: &str = "function_item";
+
+/// Returns the body range of the innermost function enclosing `offset`.
+///
+/// Bodiless declarations, such as trait method signatures, are not
+/// `function_item` nodes and therefore never match.
+pub(crate) fn locate_function_body(
+    content: &str,
+    offset: ByteOffset,
+) -> Result<Option<Range<ByteOffset>>, RustAnalyzerAdapterError> {
+    let position = offset.as_usize();
+    if position > content.len() {
+        return Ok(None);
+    }
+    let mut parser = Parser::new(SupportedLanguage::Rust)
+        .map_err(|source| RustAnalyzerAdapterError::Parse { source })?;
+    let parsed = parser
+        .parse(content)
+        .map_err(|source| RustAnalyzerAdapterError::Parse { source })?;
+
+    let mut node = parsed
+        .root_node()
+        .descendant_for_byte_range(position, position);
+    while let Some(current) = node {
+        if current.kind() == FUNCTION_ITEM {
+            return Ok(current
+                .child_by_field_name("body")
+                .map(|body| ByteOffset::new(body.start_byte())..ByteOffset::new(body.end_byte())));
+        }
+        node = current.parent();
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for locating function bodies.
+
+    use rstest::rstest;
+
+    use super::*;
+
+    const SOURCE: &str = concat!(
+        "struct Weaver;\n",
+        "\n",
+        "impl Weaver {\n",
+        "    fn weave(&self) -> i32 {\n",
+        "        1\n",
+        "    }\n",
+        "}\n",
+        "\n",
+        "trait Loom {\n",
+        "    fn shuttle(&self);\n",
+        "}\n",
+    );
+
+    fn body_at(needle: &str) -> Option<String> {
+        let offset = SOURCE.find(needle).expect("needle should be present");
+        locate_function_body(SOURCE, ByteOffset::new(offset))
+            .expect("source should parse")
+            .map(|range| {
+                let span = SOURCE.get(range.start.as_usize()..range.end.as_usize());
+                String::from(span.expect("body range should be a valid span"))
+            })
+    }
+
+    #[rstest]
+    #[case::name("weave")]
+    #[case::signature("-> i32")]
+    #[case::body("1\n")]
+    fn finds_the_method_body_from_any_position_in_the_method(#[case] needle: &str) {
+        assert_eq!(body_at(needle).as_deref(), Some("{\n        1\n    }"));
+    }
+
+    #[rstest]
+    #[case::struct_item("Weaver;")]
+    #[case::trait_signature("shuttle")]
+    fn positions_outside_a_function_body_have_no_body(#[case] needle: &str) {
+        assert_eq!(body_at(needle), None);
+    }
+
+    #[test]
+    fn offsets_past_the_end_have_no_body() {
+        let located = locate_function_body(SOURCE, ByteOffset::new(SOURCE.len() + 1))
+            .expect("source should parse");
+        assert_eq!(located, None);
+    }
+}
@@ -5,32 +5,51 @@
 //! executes a refactoring operation, and writes one JSONL response to stdout.
 
 mod arguments;
+mod extract_predicate;
 mod failure;
 mod fs_helpers;
+mod function_body;
 
 #[cfg(test)]
 mod tests;
 
 mod lsp;
+mod organize_imports;
+mod patch;
 mod path_utils;
+mod prepare_rename;
+mod replace_body;
 
 use std::{
+    collections::BTreeMap,
     io::{BufRead, Write},
+    ops::Range,
     path::{Path, PathBuf},
 };
 
-pub(crate) use fs_helpers::write_workspace_file;
+pub(crate) use fs_helpers::{remove_workspace_file, write_workspace_file};
 pub use lsp::RustAnalyzerLspAdapter;
 use path_utils::{normalize_request_uri, path_to_slash, validate_relative_path};
 use thiserror::Error;
 use weaver_plugins::{
     capability::ReasonCode,
-    protocol::{FilePayload, PluginOutput, PluginRequest, PluginResponse},
+    protocol::{
+        FilePayload,
+        PING_OPERATION,
+        PluginOutput,
+        PluginRequest,
+        PluginResponse,
+        build_multi_file_patch,
+    },
 };
 
 use crate::{
     arguments::parse_rename_symbol_arguments,
+    extract_predicate::execute_extract_predicate,
     failure::{PluginFailure, failure_response},
+    organize_imports::execute_organize_imports,
+    prepare_rename::execute_prepare_rename,
+    replace_body::execute_replace_body,
 };
 
 /// UTF-8 byte offset into a source document.
@@ -47,19 +66,65 @@ impl ByteOffset {
     pub const fn as_usize(self) -> usize { self.0 }
 }
 
+/// Post-refactoring content of every supplied file, keyed by
+/// slash-separated workspace-relative path.
+pub type ChangedFiles = BTreeMap<String, String>;
+
 /// Refactoring adapter abstraction used to keep behaviour deterministic in tests.
 pub trait RustAnalyzerAdapter {
-    /// Executes a rename operation and returns the modified file content.
+    /// Executes a rename operation and returns the content of every file.
+    ///
+    /// The symbol at `offset` is resolved in the first entry of `files`. The
+    /// remaining files are staged alongside it so references elsewhere in the
+    /// crate are renamed too.
     ///
     /// # Errors
     ///
     /// Returns an error if the adapter cannot complete the operation.
     fn rename(
         &self,
-        file: &FilePayload,
+        files: &[FilePayload],
         offset: ByteOffset,
         new_name: &str,
-    ) -> Result<String, RustAnalyzerAdapterError>;
+    ) -> Result<ChangedFiles, RustAnalyzerAdapterError>;
+
+    /// Sorts and merges the imports of `file` and returns its new content.
+    ///
+    /// Returns the original content unchanged when there is nothing to
+    /// reorganize.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the adapter cannot complete the operation.
+    fn organize_imports(&self, file: &FilePayload) -> Result<String, RustAnalyzerAdapterError>;
+
+    /// Checks whether the symbol at `offset` in `file` can be renamed.
+    ///
+    /// Returns the byte range of the renameable symbol, or `None` when the
+    /// position does not name one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the adapter cannot complete the operation.
+    fn prepare_rename(
+        &self,
+        file: &FilePayload,
+        offset: ByteOffset,
+    ) -> Result<Option<Range<ByteOffset>>, RustAnalyzerAdapterError>;
+
+    /// Locates the body block of the innermost function enclosing `offset`.
+    ///
+    /// Returns the byte range of the body, braces included, or `None` when
+    /// `offset` is not inside a function that has a body.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the adapter cannot complete the operation.
+    fn function_body(
+        &self,
+        file: &FilePayload,
+        offset: ByteOffset,
+    ) -> Result<Option<Range<ByteOffset>>, RustAnalyzerAdapterError>;
 }
 
 /// Errors raised while dispatching plugin requests.
@@ -125,6 +190,13 @@ pub enum RustAnalyzerAdapterError {
         /// Parsing or protocol error details.
         message: String,
     },
+    /// The Rust source could not be parsed.
+    #[error("failed to parse Rust source: {source}")]
+    Parse {
+        /// Underlying parser error.
+        #[source]
+        source: weaver_syntax::SyntaxError,
+    },
     /// Request path was invalid for sandboxed execution.
     #[error("invalid file path for rust-analyzer operation: {message}")]
     InvalidPath {
@@ -168,7 +240,7 @@ pub fn run_with_adapter<R: RustAnalyzerAdapter>(
 ///
 /// Returns an error if the response cannot be written.
 pub fn run(stdin: &mut impl BufRead, stdout: &mut impl Write) -> Result<(), PluginDispatchError> {
-    run_with_adapter(stdin, stdout, &RustAnalyzerLspAdapter)
+    run_with_adapter(stdin, stdout, &RustAnalyzerLspAdapter::new())
 }
 
 fn read_request(stdin: &mut impl BufRead) -> Result<PluginRequest, PluginFailure> {
@@ -181,8 +253,12 @@ fn read_request(stdin: &mut impl BufRead) -> Result<PluginRequest, PluginFailure
         return Err(PluginFailure::plain("plugin request was empty"));
     }
 
-    serde_json::from_str(line.trim())
-        .map_err(|error| PluginFailure::plain(format!("invalid plugin request JSON: {error}")))
+    let mut request: PluginRequest = serde_json::from_str(line.trim())
+        .map_err(|error| PluginFailure::plain(format!("invalid plugin request JSON: {error}")))?;
+    request.load_file_references().map_err(|error| {
+        PluginFailure::plain(format!("failed to read referenced file content: {error}"))
+    })?;
+    Ok(request)
 }
 
 fn execute_request<R: RustAnalyzerAdapter>(
@@ -190,7 +266,12 @@ fn execute_request<R: RustAnalyzerAdapter>(
     request: &PluginRequest,
 ) -> Result<PluginResponse, PluginFailure> {
     match request.operation() {
+        PING_OPERATION => Ok(PluginResponse::success(PluginOutput::Empty)),
         "rename-symbol" => execute_rename(adapter, request),
+        "organize-imports" => execute_organize_imports(adapter, request),
+        "prepare-rename" => execute_prepare_rename(adapter, request),
+        "replace-body" => execute_replace_body(adapter, request),
+        "extract-predicate" => execute_extract_predicate(request),
         other => Err(PluginFailure::with_reason(
             format!("unsupported refactoring operation '{other}'"),
             ReasonCode::OperationNotSupported,
@@ -206,83 +287,105 @@ fn execute_rename<R: RustAnalyzerAdapter>(
         .map_err(|message| PluginFailure::with_reason(message, ReasonCode::IncompletePayload))?;
 
     let files = request.files();
-    let file = match files {
-        [single] => single,
-        other => {
-            return Err(PluginFailure::with_reason(
-                format!(
-                    "rename-symbol operation requires exactly one file payload, got {}",
-                    other.len()
-                ),
-                ReasonCode::IncompletePayload,
-            ));
-        }
-    };
-
-    validate_relative_path(file.path()).map_err(|error| {
-        PluginFailure::with_reason(error.to_string(), ReasonCode::IncompletePayload)
-    })?;
-
-    let request_path = path_to_slash(file.path()).map_err(|error| {
-        PluginFailure::with_reason(error.to_string(), ReasonCode::IncompletePayload)
-    })?;
-    let uri_path = normalize_request_uri(arguments.uri()).map_err(|error| {
-        PluginFailure::with_reason(error.to_string(), ReasonCode::IncompletePayload)
-    })?;
-    if uri_path != request_path {
+    if files.is_empty() {
         return Err(PluginFailure::with_reason(
-            format!(
-                "uri argument '{}' does not match file payload '{}'",
-                arguments.uri(),
-                request_path,
-            ),
+            "rename-symbol operation requires at least one file payload",
             ReasonCode::IncompletePayload,
         ));
     }
+    for payload in files {
+        validate_relative_path(payload.path()).map_err(|error| {
+            PluginFailure::with_reason(error.to_string(), ReasonCode::IncompletePayload)
+        })?;
+    }
+    let file = select_rename_target(files, arguments.target_path())?;
 
-    let modified = adapter
+    ensure_uri_matches(arguments.uri(), file)?;
+
+    let changed = adapter
         .rename(
-            file,
+            &target_first(files, file),
             ByteOffset::new(arguments.offset()),
             arguments.new_name(),
         )
         .map_err(|error| PluginFailure::plain(error.to_string()))?;
 
-    if modified == file.content() {
+    let patch = build_multi_file_patch(files, &changed, |path| path_to_slash(path).ok()).map_err(
+        |error| {
+            PluginFailure::plain(format!(
+                "rust-analyzer modified '{}', which was not supplied in the request",
+                error.path()
+            ))
+        },
+    )?;
+    if patch.is_empty() {
         return Err(PluginFailure::with_reason(
             "rename-symbol operation produced no content changes",
             ReasonCode::SymbolNotFound,
         ));
     }
 
-    let patch = build_search_replace_patch(file.path(), file.content(), &modified)?;
     Ok(PluginResponse::success(PluginOutput::Diff {
         content: patch,
     }))
 }
 
-fn build_search_replace_patch(
-    path: &Path,
-    original: &str,
-    modified: &str,
-) -> Result<String, PluginFailure> {
-    let unix_path = path_to_slash(path).map_err(|error| PluginFailure::plain(error.to_string()))?;
-    let sep_after_original = if original.ends_with('\n') { "" } else { "\n" };
-    let sep_after_modified = if modified.ends_with('\n') { "" } else { "\n" };
-
-    Ok(format!(
-        concat!(
-            "diff --git a/{unix_path} b/{unix_path}\n",
-            "<<<<<<< SEARCH\n",
-            "{original}{sep_a}",
-            "=======\n",
-            "{modified}{sep_b}",
-            ">>>>>>> REPLACE\n",
-        ),
-        unix_path = unix_path,
-        original = original,
-        sep_a = sep_after_original,
-        modified = modified,
-        sep_b = sep_after_modified,
-    ))
+/// Checks that the `uri` argument names the same file as `file`.
+fn ensure_uri_matches(uri: &str, file: &FilePayload) -> Result<(), PluginFailure> {
+    let request_path = path_to_slash(file.path()).map_err(|error| {
+        PluginFailure::with_reason(error.to_string(), ReasonCode::IncompletePayload)
+    })?;
+    let uri_path = normalize_request_uri(uri).map_err(|error| {
+        PluginFailure::with_reason(error.to_string(), ReasonCode::IncompletePayload)
+    })?;
+    if uri_path != request_path {
+        return Err(PluginFailure::with_reason(
+            format!("uri argument '{uri}' does not match file payload '{request_path}'"),
+            ReasonCode::IncompletePayload,
+        ));
+    }
+    Ok(())
+}
+
+/// Chooses the payload the rename position refers to.
+///
+/// A lone payload is the target by default; with several payloads the
+/// `target_path` argument must name one of them.
+fn select_rename_target<'a>(
+    files: &'a [FilePayload],
+    target_path: Option<&str>,
+) -> Result<&'a FilePayload, PluginFailure> {
+    let incomplete =
+        |message: String| PluginFailure::with_reason(message, ReasonCode::IncompletePayload);
+    let Some(target) = target_path else {
+        return match files {
+            [single] => Ok(single),
+            other => Err(incomplete(format!(
+                "rename-symbol operation with {} file payloads requires a 'target_path' argument",
+                other.len()
+            ))),
+        };
+    };
+
+    let wanted = path_to_slash(Path::new(target)).map_err(|error| incomplete(error.to_string()))?;
+    files
+        .iter()
+        .find(|payload| path_to_slash(payload.path()).is_ok_and(|path| path == wanted))
+        .ok_or_else(|| {
+            incomplete(format!(
+                "target_path '{target}' does not match any file payload"
+            ))
+        })
+}
+
+/// Orders `files` so the rename target comes first, as adapters expect.
+fn target_first(files: &[FilePayload], target: &FilePayload) -> Vec<FilePayload> {
+    std::iter::once(target)
+        .chain(
+            files
+                .iter()
+                .filter(|payload| !std::ptr::eq(*payload, target)),
+        )
+        .cloned()
+        .collect()
 }
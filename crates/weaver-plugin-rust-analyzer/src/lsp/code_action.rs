@@ -0,0 +1,169 @@
+//! Source code actions requested through `textDocument/codeAction`.
+//!
+//! rust-analyzer may return a code action without its workspace edit and
+//! expect the client to fill it in with `codeAction/resolve`, so both shapes
+//! are handled before the edit is applied.
+
+use lsp_types::{CodeAction, CodeActionKind, CodeActionOrCommand, Uri, WorkspaceEdit};
+use serde_json::json;
+
+use super::{
+    session::RustAnalyzerProcess,
+    text_edits::{PositionEncoding, byte_offset_to_lsp_position},
+};
+use crate::{ByteOffset, RustAnalyzerAdapterError};
+
+/// Document addressed by a code-action request.
+#[derive(Clone, Copy)]
+pub(super) struct CodeActionTarget<'a> {
+    /// URI under which the document was opened.
+    pub(super) uri: &'a Uri,
+    /// Text of the document, used to compute the whole-file range.
+    pub(super) content: &'a str,
+}
+
+/// Requests the organize-imports edit for the whole of `target`.
+///
+/// Returns `Ok(None)` when rust-analyzer offers no organize-imports action,
+/// which happens when the imports are already in order.
+pub(super) fn request_organize_imports_edit(
+    process: &mut RustAnalyzerProcess,
+    target: CodeActionTarget<'_>,
+    encoding: PositionEncoding,
+) -> Result<Option<WorkspaceEdit>, RustAnalyzerAdapterError> {
+    let end = byte_offset_to_lsp_position(
+        target.content,
+        ByteOffset::new(target.content.len()),
+        encoding,
+    )?;
+    let result = process.request(
+        "textDocument/codeAction",
+        json!({
+            "textDocument": {
+                "uri": target.uri.as_str(),
+            },
+            "range": {
+                "start": { "line": 0, "character": 0 },
+                "end": end,
+            },
+            "context": {
+                "diagnostics": [],
+                "only": [CodeActionKind::SOURCE_ORGANIZE_IMPORTS.as_str()],
+            },
+        }),
+    )?;
+
+    let Some(action) = select_organize_imports_action(result)? else {
+        return Ok(None);
+    };
+    let resolved = if action.edit.is_some() {
+        action
+    } else {
+        resolve_code_action(process, &action)?
+    };
+
+    resolved
+        .edit
+        .map(Some)
+        .ok_or_else(|| RustAnalyzerAdapterError::InvalidOutput {
+            message: String::from("organize-imports code action resolved without a workspace edit"),
+        })
+}
+
+/// Picks the first organize-imports code action from a code-action response.
+fn select_organize_imports_action(
+    result: serde_json::Value,
+) -> Result<Option<CodeAction>, RustAnalyzerAdapterError> {
+    let actions: Option<Vec<CodeActionOrCommand>> =
+        serde_json::from_value(result).map_err(|source| {
+            RustAnalyzerAdapterError::InvalidOutput {
+                message: format!("failed to deserialize code action response: {source}"),
+            }
+        })?;
+
+    Ok(actions
+        .into_iter()
+        .flatten()
+        .find_map(|candidate| match candidate {
+            CodeActionOrCommand::CodeAction(action)
+                if action.kind.as_ref().is_some_and(is_organize_imports_kind) =>
+            {
+                Some(action)
+            }
+            _ => None,
+        }))
+}
+
+/// Matches `source.organizeImports` and its hierarchical sub-kinds.
+fn is_organize_imports_kind(kind: &CodeActionKind) -> bool {
+    let expected = CodeActionKind::SOURCE_ORGANIZE_IMPORTS;
+    kind.as_str()
+        .strip_prefix(expected.as_str())
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+fn resolve_code_action(
+    process: &mut RustAnalyzerProcess,
+    action: &CodeAction,
+) -> Result<CodeAction, RustAnalyzerAdapterError> {
+    let params =
+        serde_json::to_value(action).map_err(|source| RustAnalyzerAdapterError::InvalidOutput {
+            message: format!("failed to serialize code action for resolution: {source}"),
+        })?;
+    let result = process.request("codeAction/resolve", params)?;
+
+    serde_json::from_value(result).map_err(|source| RustAnalyzerAdapterError::InvalidOutput {
+        message: format!("failed to deserialize resolved code action: {source}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for choosing the organize-imports code action.
+
+    use lsp_types::CodeActionKind;
+    use rstest::rstest;
+    use serde_json::json;
+
+    use super::{is_organize_imports_kind, select_organize_imports_action};
+
+    #[rstest]
+    #[case::exact("source.organizeImports", true)]
+    #[case::sub_kind("source.organizeImports.rust", true)]
+    #[case::other_source("source.fixAll", false)]
+    #[case::shared_prefix("source.organizeImportsLater", false)]
+    fn recognises_organize_imports_kinds(#[case] kind: &str, #[case] expected: bool) {
+        assert_eq!(
+            is_organize_imports_kind(&CodeActionKind::from(String::from(kind))),
+            expected
+        );
+    }
+
+    #[test]
+    fn selects_first_organize_imports_action() {
+        let response = json!([
+            { "title": "Run", "command": "rust-analyzer.run" },
+            { "title": "Fix all", "kind": "source.fixAll" },
+            { "title": "Organize imports", "kind": "source.organizeImports" },
+        ]);
+
+        let action = select_organize_imports_action(response)
+            .expect("response should parse")
+            .expect("organize-imports action should be selected");
+        assert_eq!(action.title, "Organize imports");
+    }
+
+    #[rstest]
+    #[case::null(serde_json::Value::Null)]
+    #[case::empty(json!([]))]
+    #[case::commands_only(json!([{ "title": "Run", "command": "rust-analyzer.run" }]))]
+    fn returns_none_without_organize_imports_action(#[case] response: serde_json::Value) {
+        let action = select_organize_imports_action(response).expect("response should parse");
+        assert!(action.is_none());
+    }
+
+    #[test]
+    fn rejects_malformed_responses() {
+        assert!(select_organize_imports_action(json!({ "unexpected": true })).is_err());
+    }
+}
@@ -0,0 +1,138 @@
+//! Staging of request files and text-document synchronization.
+//!
+//! Request files are written into the session workspace so rust-analyzer can
+//! load them as part of the stub crate, then announced through the
+//! `textDocument/did*` notifications so the server's in-memory view matches
+//! the request rather than whatever it last read from disk.
+
+use std::path::Path;
+
+use lsp_types::{
+    DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams,
+    TextDocumentContentChangeEvent,
+    TextDocumentIdentifier,
+    TextDocumentItem,
+    Uri,
+    VersionedTextDocumentIdentifier,
+};
+use serde::Serialize;
+use serde_json::json;
+use weaver_plugins::protocol::FilePayload;
+
+use super::{
+    session::RustAnalyzerProcess,
+    text_edits::path_to_file_uri,
+    workspace_edit::SourceDocument,
+};
+use crate::{RustAnalyzerAdapterError, path_utils::path_to_slash, write_workspace_file};
+
+/// `FileChangeType::DELETED` from the LSP specification.
+const FILE_CHANGE_DELETED: u8 = 3;
+
+/// Request files written into a workspace, in request order.
+pub(super) struct StagedDocuments<'a> {
+    /// Staged URI and original content of each file.
+    pub(super) documents: Vec<SourceDocument<'a>>,
+    /// Slash-separated request path of each entry in `documents`.
+    pub(super) keys: Vec<String>,
+}
+
+impl StagedDocuments<'_> {
+    /// Returns the URI of the first document, which is the operation target.
+    pub(super) fn target_uri(&self) -> Result<&Uri, RustAnalyzerAdapterError> {
+        self.documents
+            .first()
+            .map(|document| &document.uri)
+            .ok_or_else(|| RustAnalyzerAdapterError::InvalidPath {
+                message: String::from("no file payload supplied"),
+            })
+    }
+}
+
+/// Writes every request file under `workspace_root`.
+pub(super) fn stage_documents<'a>(
+    workspace_root: &Path,
+    files: &'a [FilePayload],
+) -> Result<StagedDocuments<'a>, RustAnalyzerAdapterError> {
+    let mut documents = Vec::with_capacity(files.len());
+    let mut keys = Vec::with_capacity(files.len());
+    for file in files {
+        let absolute_path = write_workspace_file(workspace_root, file.path(), file.content())?;
+        keys.push(path_to_slash(file.path())?);
+        documents.push(SourceDocument {
+            uri: path_to_file_uri(&absolute_path)?,
+            content: file.content(),
+        });
+    }
+    Ok(StagedDocuments { documents, keys })
+}
+
+/// Announces a newly opened document with its full text.
+pub(super) fn open_document(
+    process: &mut RustAnalyzerProcess,
+    file_uri: &Uri,
+    content: &str,
+) -> Result<(), RustAnalyzerAdapterError> {
+    let did_open = DidOpenTextDocumentParams {
+        text_document: TextDocumentItem {
+            uri: file_uri.clone(),
+            language_id: String::from("rust"),
+            version: 1,
+            text: content.to_owned(),
+        },
+    };
+    notify_with(process, "textDocument/didOpen", &did_open)
+}
+
+/// Applies an incremental change to an already open document.
+pub(super) fn change_document(
+    process: &mut RustAnalyzerProcess,
+    file_uri: &Uri,
+    version: i32,
+    change: TextDocumentContentChangeEvent,
+) -> Result<(), RustAnalyzerAdapterError> {
+    let did_change = DidChangeTextDocumentParams {
+        text_document: VersionedTextDocumentIdentifier {
+            uri: file_uri.clone(),
+            version,
+        },
+        content_changes: vec![change],
+    };
+    notify_with(process, "textDocument/didChange", &did_change)
+}
+
+/// Closes a document and reports its file as deleted from the workspace.
+pub(super) fn forget_document(
+    process: &mut RustAnalyzerProcess,
+    file_uri: &Uri,
+) -> Result<(), RustAnalyzerAdapterError> {
+    let did_close = DidCloseTextDocumentParams {
+        text_document: TextDocumentIdentifier {
+            uri: file_uri.clone(),
+        },
+    };
+    notify_with(process, "textDocument/didClose", &did_close)?;
+    process.notify(
+        "workspace/didChangeWatchedFiles",
+        Some(json!({
+            "changes": [{
+                "uri": file_uri.as_str(),
+                "type": FILE_CHANGE_DELETED,
+            }],
+        })),
+    )
+}
+
+fn notify_with(
+    process: &mut RustAnalyzerProcess,
+    method: &str,
+    params: &impl Serialize,
+) -> Result<(), RustAnalyzerAdapterError> {
+    let value =
+        serde_json::to_value(params).map_err(|source| RustAnalyzerAdapterError::InvalidOutput {
+            message: format!("failed to serialize {method} params: {source}"),
+        })?;
+    process.notify(method, Some(value))
+}
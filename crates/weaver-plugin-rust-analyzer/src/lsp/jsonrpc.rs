@@ -1,6 +1,15 @@
 //! JSON-RPC helpers for the rust-analyzer adapter.
+//!
+//! Server output is read on a dedicated thread and handed over through a
+//! channel, so waiting for a response can be bounded by a wall-clock deadline
+//! even when the server stops writing altogether.
 
-use std::io::{BufRead, Write};
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
 
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -15,19 +24,48 @@ pub(super) struct JsonRpcRequestSpec<'a> {
     pub method: &'a str,
     /// Request parameters payload.
     pub params: serde_json::Value,
+    /// How long to wait for the matching response.
+    pub timeout: Duration,
+}
+
+/// One framed message read from the server, or the error that ended reading.
+pub(super) type IncomingMessage = Result<String, RustAnalyzerAdapterError>;
+
+/// Starts a thread that reads framed LSP messages from `output`.
+///
+/// The thread stops after forwarding the first read error, which includes
+/// the server closing its output, or once the receiver is dropped.
+pub(super) fn spawn_message_reader(
+    output: impl Read + Send + 'static,
+) -> Receiver<IncomingMessage> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut reader = BufReader::new(output);
+        loop {
+            let message = read_lsp_message(&mut reader);
+            let failed = message.is_err();
+            if sender.send(message).is_err() || failed {
+                break;
+            }
+        }
+    });
+    receiver
 }
 
 /// Sends a JSON-RPC request and waits for the matching response ID.
+///
+/// Returns [`RustAnalyzerAdapterError::ResponseTimeout`] when no response
+/// arrives within `spec.timeout`.
 pub(super) fn send_request(
     writer: &mut impl Write,
-    reader: &mut impl BufRead,
-    spec: JsonRpcRequestSpec<'_>,
+    incoming: &Receiver<IncomingMessage>,
+    spec: &JsonRpcRequestSpec<'_>,
 ) -> Result<serde_json::Value, RustAnalyzerAdapterError> {
     let request = JsonRpcRequest {
         jsonrpc: "2.0",
         id: spec.id,
         method: spec.method,
-        params: Some(spec.params),
+        params: Some(&spec.params),
     };
 
     let payload = serde_json::to_string(&request).map_err(|source| {
@@ -39,7 +77,7 @@ pub(super) fn send_request(
         }
     })?;
     write_lsp_message(writer, &payload)?;
-    read_response_for_id(reader, writer, spec.id)
+    read_response_for_id(incoming, writer, spec)
 }
 
 /// Sends a JSON-RPC notification.
@@ -63,32 +101,42 @@ pub(super) fn send_notification(
 }
 
 fn read_response_for_id(
-    reader: &mut impl BufRead,
+    incoming: &Receiver<IncomingMessage>,
     writer: &mut impl Write,
-    expected_id: i64,
+    spec: &JsonRpcRequestSpec<'_>,
 ) -> Result<serde_json::Value, RustAnalyzerAdapterError> {
-    const MAX_RESPONSE_ATTEMPTS: usize = 128;
-
-    let mut attempts = 0_usize;
-    while attempts < MAX_RESPONSE_ATTEMPTS {
-        attempts += 1;
-        let message = read_lsp_message(reader)?;
+    let started = Instant::now();
+    loop {
+        let remaining = spec.timeout.saturating_sub(started.elapsed());
+        let message = match incoming.recv_timeout(remaining) {
+            Ok(message) => message?,
+            Err(RecvTimeoutError::Timeout) => {
+                return Err(RustAnalyzerAdapterError::ResponseTimeout {
+                    message: format!(
+                        "no response to '{}' request id {} within {} ms",
+                        spec.method,
+                        spec.id,
+                        spec.timeout.as_millis()
+                    ),
+                });
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err(RustAnalyzerAdapterError::EngineFailed {
+                    message: format!(
+                        "rust-analyzer output closed while waiting for request id {}",
+                        spec.id
+                    ),
+                });
+            }
+        };
         let rpc = parse_jsonrpc_message(&message)?;
         if acknowledge_server_request_if_needed(writer, &rpc)? {
             continue;
         }
-        if rpc.id != Some(expected_id) {
-            continue;
+        if rpc.id == Some(spec.id) {
+            return response_result(rpc);
         }
-        return response_result(rpc);
     }
-
-    Err(RustAnalyzerAdapterError::ResponseTimeout {
-        message: format!(
-            "response read loop exhausted while waiting for request id {expected_id} after \
-             {MAX_RESPONSE_ATTEMPTS} attempts"
-        ),
-    })
 }
 
 fn parse_jsonrpc_message(message: &str) -> Result<JsonRpcMessage, RustAnalyzerAdapterError> {
@@ -244,7 +292,7 @@ struct JsonRpcRequest<'a> {
     id: i64,
     method: &'a str,
     #[serde(skip_serializing_if = "Option::is_none")]
-    params: Option<serde_json::Value>,
+    params: Option<&'a serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
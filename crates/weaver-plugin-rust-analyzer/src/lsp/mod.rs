@@ -1,60 +1,141 @@
 //! rust-analyzer LSP adapter implementation.
 //!
-//! The adapter starts a short-lived rust-analyzer process, executes one
-//! rename request over JSON-RPC 2.0 / LSP framing, and returns the modified
-//! file content for diff generation.
-
+//! By default the adapter starts a short-lived rust-analyzer process for each
+//! operation. [`RustAnalyzerLspAdapter::persistent`] instead keeps one server
+//! alive across operations so its indexing cost is paid once. A rename sends
+//! `textDocument/rename`, repeating it while rust-analyzer is still indexing,
+//! and returns the modified content of every supplied file. Organizing imports
+//! requests the `source.organizeImports` code action for a single file, and
+//! rename validation sends `textDocument/prepareRename`. All exchanges use
+//! JSON-RPC 2.0 / LSP framing. Locating a function body for `replace-body`
+//! needs no server and is answered by a syntactic parse instead.
+
+mod code_action;
+mod documents;
 mod jsonrpc;
+mod persistent;
+mod prepare_rename;
+mod readiness;
+mod session;
 mod text_edits;
+mod workspace_edit;
 
 use std::{
-    io::{BufReader, BufWriter},
-    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+    ops::Range,
+    sync::{Mutex, PoisonError},
+    time::Duration,
 };
 
-use lsp_types::{DidOpenTextDocumentParams, TextDocumentItem, Uri, WorkspaceEdit};
+use lsp_types::{Uri, WorkspaceEdit};
 use serde_json::json;
 use tempfile::TempDir;
 use weaver_plugins::protocol::FilePayload;
 
 use self::{
-    jsonrpc::{JsonRpcRequestSpec, send_notification, send_request},
+    code_action::{CodeActionTarget, request_organize_imports_edit},
+    documents::{StagedDocuments, open_document, stage_documents},
+    persistent::PersistentSession,
+    prepare_rename::{RenameSite, request_prepare_rename},
+    readiness::{Readiness, ReadinessPolicy, await_ready},
+    session::{RustAnalyzerProcess, with_session},
     text_edits::{
         PositionEncoding,
-        apply_workspace_edit,
         byte_offset_to_lsp_position,
-        ensure_response_is_object,
         parse_workspace_edit,
         path_to_file_uri,
         write_stub_cargo_toml,
     },
+    workspace_edit::{apply_workspace_edit, has_changes},
+};
+use crate::{
+    ByteOffset,
+    ChangedFiles,
+    RustAnalyzerAdapter,
+    RustAnalyzerAdapterError,
+    function_body::locate_function_body,
 };
-use crate::{ByteOffset, RustAnalyzerAdapter, RustAnalyzerAdapterError, write_workspace_file};
 
-const RUST_ANALYZER_BINARY: &str = "rust-analyzer";
-const RUST_ANALYZER_BINARY_ENV: &str = "WEAVER_RUST_ANALYZER_BINARY";
-const INITIALIZE_REQUEST_ID: i64 = 1;
-const RENAME_REQUEST_ID: i64 = 2;
-const SHUTDOWN_REQUEST_ID: i64 = 3;
+/// How long an empty rename is retried in case rust-analyzer is indexing.
+const RENAME_READINESS: ReadinessPolicy = ReadinessPolicy {
+    timeout: Duration::from_secs(3),
+    interval: Duration::from_millis(250),
+};
 
-/// Adapter implementation that delegates rename operations to rust-analyzer.
-pub struct RustAnalyzerLspAdapter;
+/// Adapter implementation that delegates refactorings to rust-analyzer.
+#[derive(Default)]
+pub struct RustAnalyzerLspAdapter {
+    mode: SessionMode,
+}
 
-struct PreparedWorkspace {
-    workspace: TempDir,
-    file_uri: Uri,
-    workspace_uri: Uri,
+#[derive(Default)]
+enum SessionMode {
+    /// Start and shut down a server for every operation.
+    #[default]
+    OneShot,
+    /// Reuse one lazily started server; `None` until first use or after a
+    /// failed operation discarded it.
+    Persistent(Mutex<Option<PersistentSession>>),
 }
 
-struct RustAnalyzerProcess {
-    child: Child,
-    reader: BufReader<ChildStdout>,
-    writer: BufWriter<ChildStdin>,
+impl RustAnalyzerLspAdapter {
+    /// Creates an adapter that runs a fresh rust-analyzer for every operation.
+    ///
+    /// This suits the stateless plugin protocol, where the process exits after
+    /// a single request.
+    #[must_use]
+    pub fn new() -> Self { Self::default() }
+
+    /// Creates an adapter that keeps one rust-analyzer alive across
+    /// operations.
+    ///
+    /// The server starts on first use and indexes a workspace that persists
+    /// for the adapter's lifetime, so later operations skip start-up and
+    /// indexing. A failed operation kills the server and the next operation
+    /// starts a new one. Dropping the adapter shuts the server down cleanly.
+    #[must_use]
+    pub const fn persistent() -> Self {
+        Self {
+            mode: SessionMode::Persistent(Mutex::new(None)),
+        }
+    }
+
+    /// Runs `operation` with `files` staged and their first entry as target.
+    fn run<T>(
+        &self,
+        files: &[FilePayload],
+        operation: impl FnOnce(
+            &mut RustAnalyzerProcess,
+            &StagedDocuments<'_>,
+            PositionEncoding,
+        ) -> Result<T, RustAnalyzerAdapterError>,
+    ) -> Result<T, RustAnalyzerAdapterError> {
+        match &self.mode {
+            SessionMode::OneShot => run_one_shot(files, operation),
+            SessionMode::Persistent(slot) => {
+                let mut guard = slot.lock().unwrap_or_else(PoisonError::into_inner);
+                let mut session = match guard.take().and_then(PersistentSession::into_running) {
+                    Some(session) => session,
+                    None => PersistentSession::start()?,
+                };
+
+                match session.run(files, operation) {
+                    Ok(value) => {
+                        *guard = Some(session);
+                        Ok(value)
+                    }
+                    Err(error) => {
+                        session.terminate();
+                        Err(error)
+                    }
+                }
+            }
+        }
+    }
 }
 
 #[derive(Clone, Copy)]
 struct RenameInputs<'a> {
-    file: &'a FilePayload,
+    target: &'a FilePayload,
     offset: ByteOffset,
     new_name: &'a str,
 }
@@ -62,269 +143,166 @@ struct RenameInputs<'a> {
 impl RustAnalyzerAdapter for RustAnalyzerLspAdapter {
     fn rename(
         &self,
-        file: &FilePayload,
+        files: &[FilePayload],
         offset: ByteOffset,
         new_name: &str,
-    ) -> Result<String, RustAnalyzerAdapterError> {
-        let prepared = prepare_workspace(file)?;
-        let mut process = start_rust_analyzer(&prepared)?;
+    ) -> Result<ChangedFiles, RustAnalyzerAdapterError> {
+        let target = files
+            .first()
+            .ok_or_else(|| RustAnalyzerAdapterError::InvalidPath {
+                message: String::from("no file payload supplied"),
+            })?;
         let rename_inputs = RenameInputs {
-            file,
+            target,
             offset,
             new_name,
         };
-        let rename_result = run_rename_session(&mut process, &prepared, rename_inputs);
 
-        match rename_result {
-            Ok(updated_content) => {
-                close_session(process)?;
-                Ok(updated_content)
-            }
-            Err(error) => {
-                terminate_session(process);
-                Err(error)
-            }
-        }
+        self.run(files, |process, staged, position_encoding| {
+            run_rename_session(process, staged, position_encoding, rename_inputs)
+        })
     }
-}
 
-fn run_rename_session(
-    process: &mut RustAnalyzerProcess,
-    prepared: &PreparedWorkspace,
-    rename_inputs: RenameInputs<'_>,
-) -> Result<String, RustAnalyzerAdapterError> {
-    let position_encoding = initialize_session(process, &prepared.workspace_uri)?;
-    open_document(process, &prepared.file_uri, rename_inputs.file.content())?;
+    fn organize_imports(&self, file: &FilePayload) -> Result<String, RustAnalyzerAdapterError> {
+        self.run(
+            std::slice::from_ref(file),
+            |process, staged, position_encoding| {
+                run_organize_imports_session(process, staged, file, position_encoding)
+            },
+        )
+    }
 
-    let position = byte_offset_to_lsp_position(
-        rename_inputs.file.content(),
-        rename_inputs.offset,
-        position_encoding,
-    )?;
-    let workspace_edit = request_rename_edit(
-        process,
-        &prepared.file_uri,
-        position,
-        rename_inputs.new_name,
-    )?;
-    apply_workspace_edit(
-        rename_inputs.file.content(),
-        workspace_edit,
-        &prepared.file_uri,
-        position_encoding,
-    )
+    fn prepare_rename(
+        &self,
+        file: &FilePayload,
+        offset: ByteOffset,
+    ) -> Result<Option<Range<ByteOffset>>, RustAnalyzerAdapterError> {
+        self.run(
+            std::slice::from_ref(file),
+            |process, staged, position_encoding| {
+                let site = RenameSite {
+                    uri: staged.target_uri()?,
+                    content: file.content(),
+                    offset,
+                };
+                request_prepare_rename(process, site, position_encoding)
+            },
+        )
+    }
+
+    fn function_body(
+        &self,
+        file: &FilePayload,
+        offset: ByteOffset,
+    ) -> Result<Option<Range<ByteOffset>>, RustAnalyzerAdapterError> {
+        locate_function_body(file.content(), offset)
+    }
 }
 
-fn prepare_workspace(file: &FilePayload) -> Result<PreparedWorkspace, RustAnalyzerAdapterError> {
+/// Runs `operation` in a temporary workspace with a dedicated server.
+///
+/// Only the target document is opened; the remaining files are read from
+/// disk by rust-analyzer as part of the stub crate.
+fn run_one_shot<T>(
+    files: &[FilePayload],
+    operation: impl FnOnce(
+        &mut RustAnalyzerProcess,
+        &StagedDocuments<'_>,
+        PositionEncoding,
+    ) -> Result<T, RustAnalyzerAdapterError>,
+) -> Result<T, RustAnalyzerAdapterError> {
     let workspace =
         TempDir::new().map_err(|source| RustAnalyzerAdapterError::WorkspaceCreate { source })?;
     write_stub_cargo_toml(workspace.path())?;
-    let absolute_file_path = write_workspace_file(workspace.path(), file.path(), file.content())?;
-
-    let file_uri = path_to_file_uri(&absolute_file_path)?;
+    let staged = stage_documents(workspace.path(), files)?;
     let workspace_uri = path_to_file_uri(workspace.path())?;
 
-    Ok(PreparedWorkspace {
-        workspace,
-        file_uri,
-        workspace_uri,
-    })
-}
-
-fn start_rust_analyzer(
-    prepared: &PreparedWorkspace,
-) -> Result<RustAnalyzerProcess, RustAnalyzerAdapterError> {
-    let binary = resolve_rust_analyzer_binary();
-    let mut child = Command::new(binary)
-        .current_dir(prepared.workspace.path())
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::null())
-        .spawn()
-        .map_err(|source| RustAnalyzerAdapterError::Spawn { source })?;
-
-    let stdin = child
-        .stdin
-        .take()
-        .ok_or_else(|| RustAnalyzerAdapterError::EngineFailed {
-            message: String::from("rust-analyzer stdin pipe was unavailable"),
-        })?;
-    let stdout = child
-        .stdout
-        .take()
-        .ok_or_else(|| RustAnalyzerAdapterError::EngineFailed {
-            message: String::from("rust-analyzer stdout pipe was unavailable"),
-        })?;
-
-    Ok(RustAnalyzerProcess {
-        child,
-        reader: BufReader::new(stdout),
-        writer: BufWriter::new(stdin),
-    })
+    with_session(
+        workspace.path(),
+        &workspace_uri,
+        |process, position_encoding| {
+            let target =
+                staged
+                    .documents
+                    .first()
+                    .ok_or_else(|| RustAnalyzerAdapterError::InvalidPath {
+                        message: String::from("no file payload supplied"),
+                    })?;
+            open_document(process, &target.uri, target.content)?;
+            operation(process, &staged, position_encoding)
+        },
+    )
 }
 
-fn initialize_session(
+fn run_rename_session(
     process: &mut RustAnalyzerProcess,
-    workspace_uri: &Uri,
-) -> Result<PositionEncoding, RustAnalyzerAdapterError> {
-    let initialize_result = send_request(
-        &mut process.writer,
-        &mut process.reader,
-        JsonRpcRequestSpec {
-            id: INITIALIZE_REQUEST_ID,
-            method: "initialize",
-            params: json!({
-                "processId": std::process::id(),
-                "rootUri": workspace_uri.as_str(),
-                "workspaceFolders": [{
-                    "uri": workspace_uri.as_str(),
-                    "name": "workspace",
-                }],
-                "capabilities": {
-                    "general": {
-                        "positionEncodings": ["utf-8", "utf-16"],
-                    },
-                },
-            }),
-        },
+    staged: &StagedDocuments<'_>,
+    position_encoding: PositionEncoding,
+    rename_inputs: RenameInputs<'_>,
+) -> Result<ChangedFiles, RustAnalyzerAdapterError> {
+    let position = byte_offset_to_lsp_position(
+        rename_inputs.target.content(),
+        rename_inputs.offset,
+        position_encoding,
     )?;
-    let position_encoding = parse_position_encoding(&initialize_result)?;
+    let target_uri = staged.target_uri()?;
+    let workspace_edit = await_ready(RENAME_READINESS, || {
+        request_rename_edit(process, target_uri, position, rename_inputs.new_name)
+    })?;
+    let updated = apply_workspace_edit(&staged.documents, workspace_edit, position_encoding)?;
 
-    send_notification(&mut process.writer, "initialized", Some(json!({})))?;
-    Ok(position_encoding)
+    Ok(staged.keys.iter().cloned().zip(updated).collect())
 }
 
-fn open_document(
+fn run_organize_imports_session(
     process: &mut RustAnalyzerProcess,
-    file_uri: &Uri,
-    content: &str,
-) -> Result<(), RustAnalyzerAdapterError> {
-    let did_open = DidOpenTextDocumentParams {
-        text_document: TextDocumentItem {
-            uri: file_uri.clone(),
-            language_id: String::from("rust"),
-            version: 1,
-            text: content.to_owned(),
-        },
+    staged: &StagedDocuments<'_>,
+    file: &FilePayload,
+    position_encoding: PositionEncoding,
+) -> Result<String, RustAnalyzerAdapterError> {
+    let target = CodeActionTarget {
+        uri: staged.target_uri()?,
+        content: file.content(),
+    };
+    let Some(workspace_edit) = request_organize_imports_edit(process, target, position_encoding)?
+    else {
+        return Ok(String::from(file.content()));
     };
 
-    send_notification(
-        &mut process.writer,
-        "textDocument/didOpen",
-        Some(serde_json::to_value(did_open).map_err(|source| {
-            RustAnalyzerAdapterError::InvalidOutput {
-                message: format!("failed to serialize didOpen params: {source}"),
-            }
-        })?),
-    )
+    apply_workspace_edit(&staged.documents, workspace_edit, position_encoding)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| RustAnalyzerAdapterError::InvalidOutput {
+            message: String::from("organize-imports produced no document content"),
+        })
 }
 
+/// Sends one rename request, treating an empty reply as possibly not yet
+/// indexed.
 fn request_rename_edit(
     process: &mut RustAnalyzerProcess,
     file_uri: &Uri,
     position: lsp_types::Position,
     new_name: &str,
-) -> Result<WorkspaceEdit, RustAnalyzerAdapterError> {
-    let result = send_request(
-        &mut process.writer,
-        &mut process.reader,
-        JsonRpcRequestSpec {
-            id: RENAME_REQUEST_ID,
-            method: "textDocument/rename",
-            params: json!({
-                "textDocument": {
-                    "uri": file_uri.as_str(),
-                },
-                "position": position,
-                "newName": new_name,
-            }),
-        },
-    )?;
-
-    parse_workspace_edit(result)
-}
-
-fn shutdown_session(process: &mut RustAnalyzerProcess) -> Result<(), RustAnalyzerAdapterError> {
-    send_request(
-        &mut process.writer,
-        &mut process.reader,
-        JsonRpcRequestSpec {
-            id: SHUTDOWN_REQUEST_ID,
-            method: "shutdown",
-            params: serde_json::Value::Null,
-        },
-    )?;
-
-    send_notification(&mut process.writer, "exit", None)
-}
-
-fn close_session(mut process: RustAnalyzerProcess) -> Result<(), RustAnalyzerAdapterError> {
-    if let Err(error) = shutdown_session(&mut process) {
-        terminate_session(process);
-        return Err(error);
-    }
-
-    finish_session(process)
-}
-
-fn terminate_session(mut process: RustAnalyzerProcess) {
-    drop(process.writer);
-    drop(process.reader);
-    force_terminate_process(&mut process.child);
-}
-
-fn finish_session(mut process: RustAnalyzerProcess) -> Result<(), RustAnalyzerAdapterError> {
-    drop(process.writer);
-    drop(process.reader);
-
-    let status = match process.child.wait() {
-        Ok(status) => status,
-        Err(source) => {
-            force_terminate_process(&mut process.child);
-            return Err(RustAnalyzerAdapterError::EngineFailed {
-                message: format!("failed to wait for rust-analyzer process: {source}"),
-            });
-        }
-    };
-
-    if !status.success() {
-        return Err(RustAnalyzerAdapterError::EngineFailed {
-            message: format!("rust-analyzer exited with status {status}"),
-        });
-    }
-
-    Ok(())
-}
-
-fn force_terminate_process(child: &mut Child) {
-    child.kill().ok();
-    child.wait().ok();
-}
-
-fn parse_position_encoding(
-    initialize_result: &serde_json::Value,
-) -> Result<PositionEncoding, RustAnalyzerAdapterError> {
-    ensure_response_is_object(initialize_result, "initialize")?;
-
-    let negotiated = initialize_result
-        .get("capabilities")
-        .and_then(serde_json::Value::as_object)
-        .and_then(|capabilities| capabilities.get("positionEncoding"))
-        .and_then(serde_json::Value::as_str);
-
-    match negotiated {
-        Some("utf-8") => Ok(PositionEncoding::Utf8),
-        Some("utf-16") | None => Ok(PositionEncoding::Utf16),
-        Some(other) => Err(RustAnalyzerAdapterError::InvalidOutput {
-            message: format!("unsupported server position encoding '{other}'"),
+) -> Result<Readiness<WorkspaceEdit>, RustAnalyzerAdapterError> {
+    let result = process.request(
+        "textDocument/rename",
+        json!({
+            "textDocument": {
+                "uri": file_uri.as_str(),
+            },
+            "position": position,
+            "newName": new_name,
         }),
+    )?;
+    if result.is_null() {
+        return Ok(Readiness::Pending(WorkspaceEdit::default()));
     }
-}
 
-fn resolve_rust_analyzer_binary() -> String {
-    std::env::var(RUST_ANALYZER_BINARY_ENV)
-        .ok()
-        .map(|candidate| candidate.trim().to_owned())
-        .filter(|candidate| !candidate.is_empty())
-        .unwrap_or_else(|| String::from(RUST_ANALYZER_BINARY))
+    let workspace_edit = parse_workspace_edit(result)?;
+    Ok(if has_changes(&workspace_edit) {
+        Readiness::Ready(workspace_edit)
+    } else {
+        Readiness::Pending(workspace_edit)
+    })
 }
@@ -0,0 +1,158 @@
+//! Long-lived rust-analyzer session reused across adapter operations.
+//!
+//! Starting rust-analyzer and letting it index the workspace dominates the
+//! latency of a one-shot request. A persistent session initializes the server
+//! once inside a stable temporary workspace and keeps it running. Each
+//! operation rewrites the supplied files, brings the server's open documents
+//! in line with them, and reuses the position encoding negotiated at start-up.
+//! Documents that are already open receive range-based `didChange` events
+//! covering only the text that differs from the last version sent.
+
+use std::{collections::BTreeMap, path::Path};
+
+use lsp_types::Uri;
+use tempfile::TempDir;
+use weaver_plugins::protocol::FilePayload;
+
+use super::{
+    documents::{
+        StagedDocuments,
+        change_document,
+        forget_document,
+        open_document,
+        stage_documents,
+    },
+    session::{RustAnalyzerProcess, close_session, start_session, terminate_session},
+    text_edits::{PositionEncoding, incremental_change, path_to_file_uri, write_stub_cargo_toml},
+};
+use crate::{RustAnalyzerAdapterError, remove_workspace_file};
+
+/// A running rust-analyzer together with the workspace it has indexed.
+///
+/// Dropping the session shuts the server down gracefully.
+pub(super) struct PersistentSession {
+    workspace: TempDir,
+    /// Always `Some` until the session is closed or terminated.
+    process: Option<RustAnalyzerProcess>,
+    position_encoding: PositionEncoding,
+    /// Open documents keyed by slash-separated request path.
+    open_documents: BTreeMap<String, OpenDocument>,
+}
+
+struct OpenDocument {
+    uri: Uri,
+    version: i32,
+    /// Text as of `version`, which incremental changes are computed against.
+    content: String,
+}
+
+impl PersistentSession {
+    /// Creates a workspace and starts an initialized server inside it.
+    pub(super) fn start() -> Result<Self, RustAnalyzerAdapterError> {
+        let workspace = TempDir::new()
+            .map_err(|source| RustAnalyzerAdapterError::WorkspaceCreate { source })?;
+        write_stub_cargo_toml(workspace.path())?;
+        let workspace_uri = path_to_file_uri(workspace.path())?;
+        let (process, position_encoding) = start_session(workspace.path(), &workspace_uri)?;
+
+        Ok(Self {
+            workspace,
+            process: Some(process),
+            position_encoding,
+            open_documents: BTreeMap::new(),
+        })
+    }
+
+    /// Returns the session if its server is still running.
+    pub(super) fn into_running(mut self) -> Option<Self> {
+        self.process
+            .as_mut()
+            .is_some_and(RustAnalyzerProcess::is_running)
+            .then_some(self)
+    }
+
+    /// Synchronizes `files` with the server and runs `operation` against them.
+    pub(super) fn run<'a, T>(
+        &mut self,
+        files: &'a [FilePayload],
+        operation: impl FnOnce(
+            &mut RustAnalyzerProcess,
+            &StagedDocuments<'a>,
+            PositionEncoding,
+        ) -> Result<T, RustAnalyzerAdapterError>,
+    ) -> Result<T, RustAnalyzerAdapterError> {
+        let staged = self.synchronize(files)?;
+        let position_encoding = self.position_encoding;
+        operation(self.process_mut()?, &staged, position_encoding)
+    }
+
+    /// Kills the server without a graceful shutdown.
+    pub(super) fn terminate(mut self) {
+        if let Some(process) = self.process.take() {
+            terminate_session(process);
+        }
+    }
+
+    /// Writes `files` into the workspace, opens or updates their documents,
+    /// and forgets documents left over from earlier operations so the server
+    /// does not resolve references against stale modules.
+    fn synchronize<'a>(
+        &mut self,
+        files: &'a [FilePayload],
+    ) -> Result<StagedDocuments<'a>, RustAnalyzerAdapterError> {
+        let staged = stage_documents(self.workspace.path(), files)?;
+        let process = self.process.as_mut().ok_or_else(session_closed)?;
+
+        let stale: Vec<String> = self
+            .open_documents
+            .keys()
+            .filter(|key| !staged.keys.contains(key))
+            .cloned()
+            .collect();
+        for key in stale {
+            if let Some(document) = self.open_documents.remove(&key) {
+                remove_workspace_file(self.workspace.path(), Path::new(&key))?;
+                forget_document(process, &document.uri)?;
+            }
+        }
+
+        for (key, document) in staged.keys.iter().zip(&staged.documents) {
+            if let Some(open) = self.open_documents.get_mut(key) {
+                let change =
+                    incremental_change(&open.content, document.content, self.position_encoding)?;
+                open.version += 1;
+                change_document(process, &open.uri, open.version, change)?;
+                document.content.clone_into(&mut open.content);
+            } else {
+                open_document(process, &document.uri, document.content)?;
+                self.open_documents.insert(
+                    key.clone(),
+                    OpenDocument {
+                        uri: document.uri.clone(),
+                        version: 1,
+                        content: document.content.to_owned(),
+                    },
+                );
+            }
+        }
+        Ok(staged)
+    }
+
+    fn process_mut(&mut self) -> Result<&mut RustAnalyzerProcess, RustAnalyzerAdapterError> {
+        self.process.as_mut().ok_or_else(session_closed)
+    }
+}
+
+impl Drop for PersistentSession {
+    fn drop(&mut self) {
+        if let Some(process) = self.process.take() {
+            close_session(process).ok();
+        }
+    }
+}
+
+fn session_closed() -> RustAnalyzerAdapterError {
+    RustAnalyzerAdapterError::EngineFailed {
+        message: String::from("persistent rust-analyzer session is closed"),
+    }
+}
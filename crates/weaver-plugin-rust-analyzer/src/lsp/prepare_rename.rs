@@ -0,0 +1,135 @@
+//! Rename validation through `textDocument/prepareRename`.
+//!
+//! The server reports whether a position names a renameable symbol and the
+//! span that symbol occupies, without computing any edits. A `null` reply
+//! means the position cannot be renamed.
+
+use std::ops::Range;
+
+use lsp_types::{PrepareRenameResponse, Uri};
+use serde_json::{Value, json};
+
+use super::{
+    session::RustAnalyzerProcess,
+    text_edits::{PositionEncoding, byte_offset_to_lsp_position, lsp_position_to_byte_offset},
+};
+use crate::{ByteOffset, RustAnalyzerAdapterError};
+
+/// Position whose renameability is checked.
+#[derive(Clone, Copy)]
+pub(super) struct RenameSite<'a> {
+    /// URI under which the document was opened.
+    pub(super) uri: &'a Uri,
+    /// Text of the document, used to convert between offsets and positions.
+    pub(super) content: &'a str,
+    /// UTF-8 byte offset of the symbol to check.
+    pub(super) offset: ByteOffset,
+}
+
+/// Asks the server for the renameable range at `site`.
+pub(super) fn request_prepare_rename(
+    process: &mut RustAnalyzerProcess,
+    site: RenameSite<'_>,
+    encoding: PositionEncoding,
+) -> Result<Option<Range<ByteOffset>>, RustAnalyzerAdapterError> {
+    let position = byte_offset_to_lsp_position(site.content, site.offset, encoding)?;
+    let result = process.request(
+        "textDocument/prepareRename",
+        json!({
+            "textDocument": {
+                "uri": site.uri.as_str(),
+            },
+            "position": position,
+        }),
+    )?;
+    parse_prepare_rename(result, site.content, encoding)
+}
+
+/// Converts a `prepareRename` reply into a byte range within `content`.
+fn parse_prepare_rename(
+    result: Value,
+    content: &str,
+    encoding: PositionEncoding,
+) -> Result<Option<Range<ByteOffset>>, RustAnalyzerAdapterError> {
+    if result.is_null() {
+        return Ok(None);
+    }
+
+    let response: PrepareRenameResponse = serde_json::from_value(result).map_err(|source| {
+        RustAnalyzerAdapterError::InvalidOutput {
+            message: format!("invalid prepareRename response: {source}"),
+        }
+    })?;
+    let range = match response {
+        PrepareRenameResponse::Range(range)
+        | PrepareRenameResponse::RangeWithPlaceholder { range, .. } => range,
+        PrepareRenameResponse::DefaultBehavior { .. } => {
+            return Err(RustAnalyzerAdapterError::InvalidOutput {
+                message: String::from(
+                    "prepareRename deferred to client default behaviour, which is not supported",
+                ),
+            });
+        }
+    };
+
+    let start = lsp_position_to_byte_offset(content, range.start, encoding)?;
+    let end = lsp_position_to_byte_offset(content, range.end, encoding)?;
+    Ok(Some(ByteOffset::new(start)..ByteOffset::new(end)))
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for `prepareRename` reply parsing.
+
+    use serde_json::{Value, json};
+
+    use super::parse_prepare_rename;
+    use crate::{ByteOffset, RustAnalyzerAdapterError, lsp::text_edits::PositionEncoding};
+
+    const CONTENT: &str = "fn old_name() {}\n";
+
+    fn span() -> Value {
+        json!({
+            "start": { "line": 0, "character": 3 },
+            "end": { "line": 0, "character": 11 },
+        })
+    }
+
+    #[test]
+    fn parses_bare_range() {
+        let range = parse_prepare_rename(span(), CONTENT, PositionEncoding::Utf8)
+            .expect("range should parse");
+
+        assert_eq!(range, Some(ByteOffset::new(3)..ByteOffset::new(11)));
+    }
+
+    #[test]
+    fn parses_range_with_placeholder() {
+        let reply = json!({ "range": span(), "placeholder": "old_name" });
+
+        let range = parse_prepare_rename(reply, CONTENT, PositionEncoding::Utf16)
+            .expect("range should parse");
+
+        assert_eq!(range, Some(ByteOffset::new(3)..ByteOffset::new(11)));
+    }
+
+    #[test]
+    fn null_reply_means_not_renameable() {
+        let range = parse_prepare_rename(Value::Null, CONTENT, PositionEncoding::Utf8)
+            .expect("null should parse");
+
+        assert_eq!(range, None);
+    }
+
+    #[test]
+    fn rejects_default_behaviour() {
+        let reply = json!({ "defaultBehavior": true });
+
+        let result = parse_prepare_rename(reply, CONTENT, PositionEncoding::Utf8);
+
+        assert!(matches!(
+            result,
+            Err(RustAnalyzerAdapterError::InvalidOutput { .. })
+        ));
+    }
+}
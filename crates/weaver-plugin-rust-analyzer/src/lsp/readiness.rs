@@ -0,0 +1,120 @@
+//! Bounded waiting for rust-analyzer to finish loading a workspace.
+//!
+//! rust-analyzer answers requests while it is still indexing, and a rename
+//! issued too early comes back empty even though the symbol exists. The
+//! request is therefore repeated for a short while. An empty reply cannot be
+//! told apart from a rename that genuinely changes nothing, so once the budget
+//! is spent the last reply is returned as-is and the caller reports it like
+//! any other no-op.
+
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::RustAnalyzerAdapterError;
+
+/// Retry budget and cadence for requests that depend on indexing.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct ReadinessPolicy {
+    /// Total time to keep retrying before accepting a pending answer.
+    pub(super) timeout: Duration,
+    /// Pause between attempts.
+    pub(super) interval: Duration,
+}
+
+/// Result of one attempt at a readiness-dependent request.
+pub(super) enum Readiness<T> {
+    /// The server produced a usable result.
+    Ready(T),
+    /// The server replied, but its answer may only mean it is still indexing.
+    Pending(T),
+}
+
+/// Repeats `attempt` until it is ready or `policy.timeout` elapses.
+///
+/// Errors from `attempt` are returned immediately. Once time runs out the
+/// last pending answer is returned, so a genuinely empty result is reported
+/// by the caller rather than as a timeout.
+pub(super) fn await_ready<T>(
+    policy: ReadinessPolicy,
+    mut attempt: impl FnMut() -> Result<Readiness<T>, RustAnalyzerAdapterError>,
+) -> Result<T, RustAnalyzerAdapterError> {
+    let started = Instant::now();
+    loop {
+        let pending = match attempt()? {
+            Readiness::Ready(value) => return Ok(value),
+            Readiness::Pending(value) => value,
+        };
+
+        let Some(remaining) = policy.timeout.checked_sub(started.elapsed()) else {
+            return Ok(pending);
+        };
+        thread::sleep(policy.interval.min(remaining));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for the readiness retry loop.
+
+    use std::{cell::Cell, time::Duration};
+
+    use super::{Readiness, ReadinessPolicy, await_ready};
+    use crate::RustAnalyzerAdapterError;
+
+    const QUICK: ReadinessPolicy = ReadinessPolicy {
+        timeout: Duration::from_millis(200),
+        interval: Duration::from_millis(5),
+    };
+
+    #[test]
+    fn returns_once_attempt_is_ready() {
+        let attempts = Cell::new(0);
+
+        let value = await_ready(QUICK, || {
+            attempts.set(attempts.get() + 1);
+            Ok(if attempts.get() < 3 {
+                Readiness::Pending("empty")
+            } else {
+                Readiness::Ready("edit")
+            })
+        })
+        .expect("third attempt should succeed");
+
+        assert_eq!(value, "edit");
+        assert_eq!(attempts.get(), 3);
+    }
+
+    #[test]
+    fn returns_last_pending_answer_when_budget_runs_out() {
+        let attempts = Cell::new(0);
+
+        let value = await_ready(QUICK, || {
+            attempts.set(attempts.get() + 1);
+            Ok(Readiness::Pending(attempts.get()))
+        })
+        .expect("pending answer should be returned");
+
+        assert!(value > 1, "expected retries before giving up");
+        assert_eq!(value, attempts.get());
+    }
+
+    #[test]
+    fn propagates_errors_without_retrying() {
+        let attempts = Cell::new(0);
+
+        let result = await_ready::<()>(QUICK, || {
+            attempts.set(attempts.get() + 1);
+            Err(RustAnalyzerAdapterError::EngineFailed {
+                message: String::from("no references found"),
+            })
+        });
+
+        assert!(matches!(
+            result,
+            Err(RustAnalyzerAdapterError::EngineFailed { .. })
+        ));
+        assert_eq!(attempts.get(), 1);
+    }
+}
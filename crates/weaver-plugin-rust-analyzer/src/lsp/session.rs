@@ -0,0 +1,322 @@
+//! rust-analyzer process lifecycle shared by every adapter operation.
+//!
+//! A session spawns rust-analyzer inside a prepared workspace, negotiates a
+//! position encoding during `initialize`, runs its requests, and then shuts
+//! the server down. Failures terminate the process instead of attempting a
+//! graceful shutdown against a server in an unknown state.
+
+use std::{
+    io::BufWriter,
+    path::Path,
+    process::{Child, ChildStdin, Command, Stdio},
+    sync::mpsc::Receiver,
+    time::Duration,
+};
+
+use lsp_types::Uri;
+use serde_json::json;
+
+use super::{
+    jsonrpc::{
+        IncomingMessage,
+        JsonRpcRequestSpec,
+        send_notification,
+        send_request,
+        spawn_message_reader,
+    },
+    text_edits::{PositionEncoding, ensure_response_is_object},
+};
+use crate::RustAnalyzerAdapterError;
+
+const RUST_ANALYZER_BINARY: &str = "rust-analyzer";
+const RUST_ANALYZER_BINARY_ENV: &str = "WEAVER_RUST_ANALYZER_BINARY";
+/// Upper bound on the wait for any single response from rust-analyzer.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Pipes connected to a running rust-analyzer process.
+pub(super) struct RustAnalyzerProcess {
+    child: Child,
+    incoming: Receiver<IncomingMessage>,
+    writer: BufWriter<ChildStdin>,
+    next_request_id: i64,
+    request_timeout: Duration,
+}
+
+impl RustAnalyzerProcess {
+    /// Sends a request with a fresh ID and waits for its result.
+    ///
+    /// A server that does not respond in time is killed, because a late
+    /// reply would otherwise be mistaken for the response to a later request.
+    pub(super) fn request(
+        &mut self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, RustAnalyzerAdapterError> {
+        let id = self.next_request_id;
+        self.next_request_id += 1;
+        let result = send_request(
+            &mut self.writer,
+            &self.incoming,
+            &JsonRpcRequestSpec {
+                id,
+                method,
+                params,
+                timeout: self.request_timeout,
+            },
+        );
+        if matches!(
+            result,
+            Err(RustAnalyzerAdapterError::ResponseTimeout { .. })
+        ) {
+            force_terminate_process(&mut self.child);
+        }
+        result
+    }
+
+    /// Sends a notification, which has no response.
+    pub(super) fn notify(
+        &mut self,
+        method: &str,
+        params: Option<serde_json::Value>,
+    ) -> Result<(), RustAnalyzerAdapterError> {
+        send_notification(&mut self.writer, method, params)
+    }
+
+    /// Returns `true` while the server process has not exited.
+    pub(super) fn is_running(&mut self) -> bool { matches!(self.child.try_wait(), Ok(None)) }
+}
+
+/// Runs `operation` against a freshly initialized rust-analyzer session.
+///
+/// The server is shut down cleanly when `operation` succeeds and terminated
+/// when any step fails.
+pub(super) fn with_session<T>(
+    workspace_root: &Path,
+    workspace_uri: &Uri,
+    operation: impl FnOnce(
+        &mut RustAnalyzerProcess,
+        PositionEncoding,
+    ) -> Result<T, RustAnalyzerAdapterError>,
+) -> Result<T, RustAnalyzerAdapterError> {
+    let (mut process, position_encoding) = start_session(workspace_root, workspace_uri)?;
+    let result = operation(&mut process, position_encoding);
+
+    match result {
+        Ok(value) => {
+            close_session(process)?;
+            Ok(value)
+        }
+        Err(error) => {
+            terminate_session(process);
+            Err(error)
+        }
+    }
+}
+
+/// Spawns rust-analyzer and completes the `initialize` handshake.
+pub(super) fn start_session(
+    workspace_root: &Path,
+    workspace_uri: &Uri,
+) -> Result<(RustAnalyzerProcess, PositionEncoding), RustAnalyzerAdapterError> {
+    let mut process = start_rust_analyzer(workspace_root)?;
+    match initialize_session(&mut process, workspace_uri) {
+        Ok(position_encoding) => Ok((process, position_encoding)),
+        Err(error) => {
+            terminate_session(process);
+            Err(error)
+        }
+    }
+}
+
+fn start_rust_analyzer(
+    workspace_root: &Path,
+) -> Result<RustAnalyzerProcess, RustAnalyzerAdapterError> {
+    let mut command = Command::new(resolve_rust_analyzer_binary());
+    command.current_dir(workspace_root);
+    spawn_process(command)
+}
+
+/// Spawns `command` with piped stdio and starts reading its output.
+fn spawn_process(mut command: Command) -> Result<RustAnalyzerProcess, RustAnalyzerAdapterError> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|source| RustAnalyzerAdapterError::Spawn { source })?;
+
+    let stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| RustAnalyzerAdapterError::EngineFailed {
+            message: String::from("rust-analyzer stdin pipe was unavailable"),
+        })?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| RustAnalyzerAdapterError::EngineFailed {
+            message: String::from("rust-analyzer stdout pipe was unavailable"),
+        })?;
+
+    Ok(RustAnalyzerProcess {
+        child,
+        incoming: spawn_message_reader(stdout),
+        writer: BufWriter::new(stdin),
+        next_request_id: 1,
+        request_timeout: REQUEST_TIMEOUT,
+    })
+}
+
+fn initialize_session(
+    process: &mut RustAnalyzerProcess,
+    workspace_uri: &Uri,
+) -> Result<PositionEncoding, RustAnalyzerAdapterError> {
+    let initialize_result = process.request(
+        "initialize",
+        json!({
+            "processId": std::process::id(),
+            "rootUri": workspace_uri.as_str(),
+            "workspaceFolders": [{
+                "uri": workspace_uri.as_str(),
+                "name": "workspace",
+            }],
+            "capabilities": {
+                "general": {
+                    "positionEncodings": ["utf-8", "utf-16"],
+                },
+                "textDocument": {
+                    "codeAction": {
+                        "codeActionLiteralSupport": {
+                            "codeActionKind": {
+                                "valueSet": ["source.organizeImports"],
+                            },
+                        },
+                        "resolveSupport": {
+                            "properties": ["edit"],
+                        },
+                    },
+                    "rename": {
+                        "prepareSupport": true,
+                    },
+                },
+            },
+        }),
+    )?;
+    let position_encoding = parse_position_encoding(&initialize_result)?;
+
+    process.notify("initialized", Some(json!({})))?;
+    Ok(position_encoding)
+}
+
+fn shutdown_session(process: &mut RustAnalyzerProcess) -> Result<(), RustAnalyzerAdapterError> {
+    process.request("shutdown", serde_json::Value::Null)?;
+    process.notify("exit", None)
+}
+
+/// Shuts the server down gracefully, killing it if the shutdown fails.
+pub(super) fn close_session(
+    mut process: RustAnalyzerProcess,
+) -> Result<(), RustAnalyzerAdapterError> {
+    if let Err(error) = shutdown_session(&mut process) {
+        terminate_session(process);
+        return Err(error);
+    }
+
+    finish_session(process)
+}
+
+/// Kills the server without attempting a graceful shutdown.
+pub(super) fn terminate_session(mut process: RustAnalyzerProcess) {
+    drop(process.writer);
+    drop(process.incoming);
+    force_terminate_process(&mut process.child);
+}
+
+fn finish_session(mut process: RustAnalyzerProcess) -> Result<(), RustAnalyzerAdapterError> {
+    drop(process.writer);
+    drop(process.incoming);
+
+    let status = match process.child.wait() {
+        Ok(status) => status,
+        Err(source) => {
+            force_terminate_process(&mut process.child);
+            return Err(RustAnalyzerAdapterError::EngineFailed {
+                message: format!("failed to wait for rust-analyzer process: {source}"),
+            });
+        }
+    };
+
+    if !status.success() {
+        return Err(RustAnalyzerAdapterError::EngineFailed {
+            message: format!("rust-analyzer exited with status {status}"),
+        });
+    }
+
+    Ok(())
+}
+
+fn force_terminate_process(child: &mut Child) {
+    child.kill().ok();
+    child.wait().ok();
+}
+
+fn parse_position_encoding(
+    initialize_result: &serde_json::Value,
+) -> Result<PositionEncoding, RustAnalyzerAdapterError> {
+    ensure_response_is_object(initialize_result, "initialize")?;
+
+    let negotiated = initialize_result
+        .get("capabilities")
+        .and_then(serde_json::Value::as_object)
+        .and_then(|capabilities| capabilities.get("positionEncoding"))
+        .and_then(serde_json::Value::as_str);
+
+    match negotiated {
+        Some("utf-8") => Ok(PositionEncoding::Utf8),
+        Some("utf-16") | None => Ok(PositionEncoding::Utf16),
+        Some(other) => Err(RustAnalyzerAdapterError::InvalidOutput {
+            message: format!("unsupported server position encoding '{other}'"),
+        }),
+    }
+}
+
+fn resolve_rust_analyzer_binary() -> String {
+    std::env::var(RUST_ANALYZER_BINARY_ENV)
+        .ok()
+        .map(|candidate| candidate.trim().to_owned())
+        .filter(|candidate| !candidate.is_empty())
+        .unwrap_or_else(|| String::from(RUST_ANALYZER_BINARY))
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for request deadlines against an unresponsive server.
+
+    use std::{process::Command, time::Duration};
+
+    use serde_json::json;
+
+    use super::spawn_process;
+    use crate::RustAnalyzerAdapterError;
+
+    #[test]
+    fn request_times_out_and_kills_silent_server() {
+        // `sleep` accepts the request on stdin but never writes a reply.
+        let mut command = Command::new("sleep");
+        command.arg("30");
+        let mut process = spawn_process(command).expect("fake server should start");
+        process.request_timeout = Duration::from_millis(100);
+
+        let result = process.request("initialize", json!({}));
+
+        assert!(
+            matches!(
+                &result,
+                Err(RustAnalyzerAdapterError::ResponseTimeout { message })
+                    if message.contains("'initialize' request id 1 within 100 ms")
+            ),
+            "expected response timeout, got {result:?}"
+        );
+        assert!(!process.is_running());
+    }
+}
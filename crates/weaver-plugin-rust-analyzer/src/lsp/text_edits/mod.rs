@@ -1,17 +1,8 @@
-//! Workspace edit and position conversion helpers.
+//! Workspace edit, position conversion, and document change helpers.
 
 use std::path::Path;
 
-use lsp_types::{
-    AnnotatedTextEdit,
-    DocumentChangeOperation,
-    DocumentChanges,
-    OneOf,
-    Position,
-    TextEdit,
-    Uri,
-    WorkspaceEdit,
-};
+use lsp_types::{Position, Range, TextDocumentContentChangeEvent, Uri, WorkspaceEdit};
 
 use crate::{ByteOffset, RustAnalyzerAdapterError, write_workspace_file};
 
@@ -52,7 +43,12 @@ pub(super) fn ensure_response_is_object(
         })
 }
 
-/// Converts a byte offset into an LSP UTF-16 position.
+/// Converts a UTF-8 byte offset into an LSP position in `encoding`.
+///
+/// Columns count bytes under [`PositionEncoding::Utf8`] and UTF-16 code
+/// units under [`PositionEncoding::Utf16`], where a character outside the
+/// Basic Multilingual Plane counts as two. An offset inside a multi-byte
+/// character is rejected with an error naming that character.
 pub(super) fn byte_offset_to_lsp_position(
     content: &str,
     offset: ByteOffset,
@@ -69,7 +65,10 @@ pub(super) fn byte_offset_to_lsp_position(
     }
     if !content.is_char_boundary(byte_offset) {
         return Err(RustAnalyzerAdapterError::InvalidOutput {
-            message: format!("offset {byte_offset} is not at a UTF-8 character boundary"),
+            message: format!(
+                "offset {byte_offset} falls inside {}",
+                describe_char_at(content, byte_offset)
+            ),
         });
     }
 
@@ -98,146 +97,8 @@ pub(super) fn byte_offset_to_lsp_position(
     Ok(Position { line, character })
 }
 
-/// Applies a workspace edit to the original content and returns the updated text.
-pub(super) fn apply_workspace_edit(
-    original: &str,
-    workspace_edit: WorkspaceEdit,
-    file_uri: &Uri,
-    encoding: PositionEncoding,
-) -> Result<String, RustAnalyzerAdapterError> {
-    let mut edits = collect_text_edits(workspace_edit, file_uri)?;
-    if edits.is_empty() {
-        return Ok(String::from(original));
-    }
-
-    let mut ranges = edits
-        .drain(..)
-        .map(|edit| {
-            let start = lsp_position_to_byte_offset(original, edit.range.start, encoding)?;
-            let end = lsp_position_to_byte_offset(original, edit.range.end, encoding)?;
-            if end < start {
-                return Err(RustAnalyzerAdapterError::InvalidOutput {
-                    message: format!("edit range end precedes start (start={start}, end={end})"),
-                });
-            }
-            Ok((start, end, edit.new_text))
-        })
-        .collect::<Result<Vec<(usize, usize, String)>, RustAnalyzerAdapterError>>()?;
-
-    ranges.sort_by_key(|range| std::cmp::Reverse(range.0));
-
-    let mut updated = String::from(original);
-    for (start, end, replacement) in ranges {
-        if end > updated.len() || start > end {
-            return Err(RustAnalyzerAdapterError::InvalidOutput {
-                message: format!("edit range [{start}, {end}) is out of bounds"),
-            });
-        }
-        if !updated.is_char_boundary(start) || !updated.is_char_boundary(end) {
-            return Err(RustAnalyzerAdapterError::InvalidOutput {
-                message: format!("edit range [{start}, {end}) is not UTF-8 aligned"),
-            });
-        }
-
-        updated.replace_range(start..end, &replacement);
-    }
-
-    Ok(updated)
-}
-
-fn collect_text_edits(
-    workspace_edit: WorkspaceEdit,
-    file_uri: &Uri,
-) -> Result<Vec<TextEdit>, RustAnalyzerAdapterError> {
-    let mut edits = Vec::new();
-
-    if let Some(changes) = workspace_edit.changes
-        && let Some(file_edits) = changes.get(file_uri)
-    {
-        edits.extend(file_edits.clone());
-    }
-
-    if let Some(document_changes) = workspace_edit.document_changes {
-        collect_document_changes(&mut edits, document_changes, file_uri)?;
-    }
-
-    Ok(edits)
-}
-
-fn collect_document_changes(
-    target: &mut Vec<TextEdit>,
-    document_changes: DocumentChanges,
-    file_uri: &Uri,
-) -> Result<(), RustAnalyzerAdapterError> {
-    match document_changes {
-        DocumentChanges::Edits(text_document_edits) => {
-            for document_edit in text_document_edits {
-                append_document_edits(
-                    target,
-                    &document_edit.text_document.uri,
-                    document_edit.edits,
-                    file_uri,
-                );
-            }
-            Ok(())
-        }
-        DocumentChanges::Operations(operations) => {
-            for operation in operations {
-                collect_operation(target, operation, file_uri)?;
-            }
-            Ok(())
-        }
-    }
-}
-
-fn collect_operation(
-    target: &mut Vec<TextEdit>,
-    operation: DocumentChangeOperation,
-    file_uri: &Uri,
-) -> Result<(), RustAnalyzerAdapterError> {
-    match operation {
-        DocumentChangeOperation::Edit(document_edit) => {
-            append_document_edits(
-                target,
-                &document_edit.text_document.uri,
-                document_edit.edits,
-                file_uri,
-            );
-            Ok(())
-        }
-        DocumentChangeOperation::Op(resource_operation) => {
-            Err(RustAnalyzerAdapterError::InvalidOutput {
-                message: format!(
-                    concat!(
-                        "workspace edit includes unsupported resource operation: ",
-                        "{:?}"
-                    ),
-                    resource_operation
-                ),
-            })
-        }
-    }
-}
-
-fn append_document_edits(
-    target: &mut Vec<TextEdit>,
-    uri: &Uri,
-    edits: Vec<OneOf<TextEdit, AnnotatedTextEdit>>,
-    requested_uri: &Uri,
-) {
-    if uri != requested_uri {
-        return;
-    }
-
-    for edit in edits {
-        match edit {
-            OneOf::Left(text_edit) => target.push(text_edit),
-            OneOf::Right(annotated_text_edit) => target.push(annotated_text_edit.text_edit),
-        }
-    }
-}
-
-fn lsp_position_to_byte_offset(
+/// Converts an LSP position within `content` to a UTF-8 byte offset.
+pub(super) fn lsp_position_to_byte_offset(
     content: &str,
     position: Position,
     encoding: PositionEncoding,
@@ -281,7 +142,10 @@ fn utf8_position_to_byte_offset(
     }
     if !content.is_char_boundary(byte_offset) {
         return Err(RustAnalyzerAdapterError::InvalidOutput {
-            message: format!("position {position:?} splits a UTF-8 code point"),
+            message: format!(
+                "UTF-8 position {position:?} falls inside {}",
+                describe_char_at(content, byte_offset)
+            ),
         });
     }
     Ok(byte_offset)
@@ -305,7 +169,10 @@ fn utf16_position_to_byte_offset(
         })?;
         if utf16_units > position.character {
             return Err(RustAnalyzerAdapterError::InvalidOutput {
-                message: format!("position {position:?} splits a UTF-16 code unit sequence"),
+                message: format!(
+                    "UTF-16 position {position:?} falls between the surrogates of {}",
+                    describe_char(line_start + index, character)
+                ),
             });
         }
     }
@@ -317,6 +184,27 @@ fn utf16_position_to_byte_offset(
     })
 }
 
+/// Describes the character whose encoding contains `byte_offset`.
+fn describe_char_at(content: &str, byte_offset: usize) -> String {
+    content
+        .char_indices()
+        .take_while(|(index, _)| *index < byte_offset)
+        .last()
+        .map_or_else(
+            || String::from("the start of the document"),
+            |(index, character)| describe_char(index, character),
+        )
+}
+
+/// Names a character and the byte range it occupies, for error messages.
+fn describe_char(index: usize, character: char) -> String {
+    format!(
+        "'{character}' (U+{:04X}) at bytes {index}..{}",
+        u32::from(character),
+        index + character.len_utf8()
+    )
+}
+
 fn find_line_start_offset(
     content: &str,
     target_line: u32,
@@ -338,6 +226,58 @@ fn find_line_start_offset(
     })
 }
 
+/// Builds a range-based `didChange` event that turns `previous` into
+/// `updated`.
+///
+/// The range covers only the span between the longest common prefix and the
+/// longest common suffix of the two texts, expressed against `previous` in
+/// the negotiated `encoding`. Both ends fall on character boundaries, so the
+/// range never splits a UTF-16 surrogate pair. Identical texts produce an
+/// empty insertion.
+pub(super) fn incremental_change(
+    previous: &str,
+    updated: &str,
+    encoding: PositionEncoding,
+) -> Result<TextDocumentContentChangeEvent, RustAnalyzerAdapterError> {
+    let prefix = common_prefix_len(previous, updated);
+    let previous_tail = slice_checked(previous, prefix.., "previous tail")?;
+    let updated_tail = slice_checked(updated, prefix.., "updated tail")?;
+    let suffix = common_suffix_len(previous_tail, updated_tail);
+    let previous_end = previous.len() - suffix;
+    let updated_end = updated.len() - suffix;
+
+    let range = Range {
+        start: byte_offset_to_lsp_position(previous, ByteOffset::new(prefix), encoding)?,
+        end: byte_offset_to_lsp_position(previous, ByteOffset::new(previous_end), encoding)?,
+    };
+    let text = slice_checked(updated, prefix..updated_end, "changed text")?;
+    Ok(TextDocumentContentChangeEvent {
+        range: Some(range),
+        range_length: None,
+        text: text.to_owned(),
+    })
+}
+
+/// Returns the byte length of the longest shared prefix, at a character
+/// boundary.
+fn common_prefix_len(left: &str, right: &str) -> usize {
+    left.char_indices()
+        .zip(right.chars())
+        .find(|((_, left_char), right_char)| left_char != right_char)
+        .map_or_else(|| left.len().min(right.len()), |((index, _), _)| index)
+}
+
+/// Returns the byte length of the longest shared suffix, at a character
+/// boundary.
+fn common_suffix_len(left: &str, right: &str) -> usize {
+    left.chars()
+        .rev()
+        .zip(right.chars().rev())
+        .take_while(|(left_char, right_char)| left_char == right_char)
+        .map(|(character, _)| character.len_utf8())
+        .sum()
+}
+
 /// Writes a minimal `Cargo.toml` so rust-analyzer can open the workspace.
 pub(super) fn write_stub_cargo_toml(workspace_root: &Path) -> Result<(), RustAnalyzerAdapterError> {
     let content = concat!(
@@ -379,3 +319,6 @@ where
             message: format!("invalid UTF-8 slice for {slice_name}: {range_debug}"),
         })
 }
+
+#[cfg(test)]
+mod tests;
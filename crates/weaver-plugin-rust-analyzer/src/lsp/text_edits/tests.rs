@@ -0,0 +1,152 @@
+//! Unit tests for position conversion and incremental document changes.
+
+use lsp_types::{Position, TextDocumentContentChangeEvent};
+use rstest::rstest;
+
+use super::{
+    PositionEncoding,
+    byte_offset_to_lsp_position,
+    incremental_change,
+    lsp_position_to_byte_offset,
+};
+use crate::{ByteOffset, RustAnalyzerAdapterError};
+
+/// A crab emoji (four UTF-8 bytes, two UTF-16 units) precedes `target` on
+/// the second line, after a first line holding another astral character.
+const ASTRAL: &str = "// \u{1f600}\nlet s = \"\u{1f980}\"; target();\n";
+/// Byte offset of `target` in [`ASTRAL`].
+const TARGET_OFFSET: usize = 24;
+/// Byte offset where the crab emoji starts in [`ASTRAL`].
+const CRAB_OFFSET: usize = 17;
+
+#[rstest]
+#[case::utf8(PositionEncoding::Utf8, 16)]
+#[case::utf16(PositionEncoding::Utf16, 14)]
+fn target_after_astral_character_lands_on_its_column(
+    #[case] encoding: PositionEncoding,
+    #[case] character: u32,
+) {
+    let position = byte_offset_to_lsp_position(ASTRAL, ByteOffset::new(TARGET_OFFSET), encoding)
+        .expect("position should resolve");
+
+    assert_eq!(position, Position::new(1, character));
+    assert_eq!(
+        lsp_position_to_byte_offset(ASTRAL, position, encoding).expect("offset should resolve"),
+        TARGET_OFFSET
+    );
+}
+
+#[rstest]
+fn columns_straddling_astral_character_round_trip(
+    #[values(PositionEncoding::Utf8, PositionEncoding::Utf16)] encoding: PositionEncoding,
+    #[values(CRAB_OFFSET, CRAB_OFFSET + 4)] offset: usize,
+) {
+    let position = byte_offset_to_lsp_position(ASTRAL, ByteOffset::new(offset), encoding)
+        .expect("position should resolve");
+
+    assert_eq!(
+        lsp_position_to_byte_offset(ASTRAL, position, encoding).expect("offset should resolve"),
+        offset
+    );
+}
+
+#[rstest]
+fn offset_inside_astral_character_is_rejected(
+    #[values(PositionEncoding::Utf8, PositionEncoding::Utf16)] encoding: PositionEncoding,
+    #[values(1, 2, 3)] bytes_in: usize,
+) {
+    let offset = ByteOffset::new(CRAB_OFFSET + bytes_in);
+
+    let result = byte_offset_to_lsp_position(ASTRAL, offset, encoding);
+
+    assert!(
+        matches!(
+            &result,
+            Err(RustAnalyzerAdapterError::InvalidOutput { message })
+                if message.contains("U+1F980") && message.contains("bytes 17..21")
+        ),
+        "expected mid-codepoint error, got {result:?}"
+    );
+}
+
+#[rstest]
+#[case::utf8_byte(PositionEncoding::Utf8, 10)]
+#[case::utf8_last_byte(PositionEncoding::Utf8, 12)]
+#[case::utf16_low_surrogate(PositionEncoding::Utf16, 10)]
+fn position_inside_astral_character_is_rejected(
+    #[case] encoding: PositionEncoding,
+    #[case] character: u32,
+) {
+    let result = lsp_position_to_byte_offset(ASTRAL, Position::new(1, character), encoding);
+
+    assert!(
+        matches!(
+            &result,
+            Err(RustAnalyzerAdapterError::InvalidOutput { message })
+                if message.contains("U+1F980")
+        ),
+        "expected mid-codepoint error, got {result:?}"
+    );
+}
+
+/// Applies `change` to `content` the way a language server would.
+fn apply(
+    content: &str,
+    change: &TextDocumentContentChangeEvent,
+    encoding: PositionEncoding,
+) -> String {
+    let range = change.range.expect("incremental changes carry a range");
+    let start =
+        lsp_position_to_byte_offset(content, range.start, encoding).expect("start should resolve");
+    let end =
+        lsp_position_to_byte_offset(content, range.end, encoding).expect("end should resolve");
+    let mut applied = String::from(content.get(..start).expect("prefix slice"));
+    applied.push_str(&change.text);
+    applied.push_str(content.get(end..).expect("suffix slice"));
+    applied
+}
+
+#[rstest]
+#[case::rename("fn old_name() {}\n", "fn new_name() {}\n")]
+#[case::insert_line("fn a() {}\n", "fn a() {}\nfn b() {}\n")]
+#[case::delete_line("use x;\nuse y;\nfn a() {}\n", "use x;\nfn a() {}\n")]
+#[case::repeated_text("aaaa\n", "aaaaaa\n")]
+#[case::replace_all("abc", "xyz")]
+#[case::from_empty("", "fn main() {}\n")]
+#[case::to_empty("fn main() {}\n", "")]
+#[case::multibyte("let s = \"caf\u{e9}\";\n", "let s = \"caf\u{e8}\u{1f600}\";\n")]
+#[case::surrogate_pair("// \u{1f600}\u{1f601}\n", "// \u{1f600}\u{1f602}\n")]
+fn applying_change_reproduces_updated_source(
+    #[case] previous: &str,
+    #[case] updated: &str,
+    #[values(PositionEncoding::Utf8, PositionEncoding::Utf16)] encoding: PositionEncoding,
+) {
+    let change = incremental_change(previous, updated, encoding).expect("change should build");
+
+    assert_eq!(apply(previous, &change, encoding), updated);
+}
+
+#[test]
+fn change_covers_only_the_differing_span() {
+    let change = incremental_change(
+        "fn main() {\n    old();\n}\n",
+        "fn main() {\n    new();\n}\n",
+        PositionEncoding::Utf16,
+    )
+    .expect("change should build");
+
+    let range = change.range.expect("range");
+    assert_eq!((range.start.line, range.start.character), (1, 4));
+    assert_eq!((range.end.line, range.end.character), (1, 7));
+    assert_eq!(change.text, "new");
+}
+
+#[test]
+fn identical_content_produces_empty_insertion() {
+    let change = incremental_change("fn a() {}\n", "fn a() {}\n", PositionEncoding::Utf8)
+        .expect("change should build");
+
+    let range = change.range.expect("range");
+    assert_eq!(range.start, range.end);
+    assert!(change.text.is_empty());
+}
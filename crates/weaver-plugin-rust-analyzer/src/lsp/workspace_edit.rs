@@ -0,0 +1,309 @@
+//! Application of LSP workspace edits to the documents supplied in a request.
+//!
+//! A rename can touch every file that references the symbol, so edits are
+//! grouped by document URI and each URI is mapped back to the original
+//! content the plugin staged for rust-analyzer. Edits addressed to a URI that
+//! was not staged are rejected because the plugin cannot describe a change to
+//! content it never received.
+
+use lsp_types::{
+    AnnotatedTextEdit,
+    DocumentChangeOperation,
+    DocumentChanges,
+    OneOf,
+    TextEdit,
+    Uri,
+    WorkspaceEdit,
+};
+
+use super::text_edits::{PositionEncoding, lsp_position_to_byte_offset};
+use crate::RustAnalyzerAdapterError;
+
+/// Original text of a document that a workspace edit may modify.
+pub(super) struct SourceDocument<'a> {
+    /// URI under which the document was staged for rust-analyzer.
+    pub(super) uri: Uri,
+    /// Content supplied in the plugin request.
+    pub(super) content: &'a str,
+}
+
+/// Applies a workspace edit to `documents` and returns their updated text.
+///
+/// The result has one entry per document, in the same order; documents the
+/// edit does not touch are returned unchanged.
+pub(super) fn apply_workspace_edit(
+    documents: &[SourceDocument<'_>],
+    workspace_edit: WorkspaceEdit,
+    encoding: PositionEncoding,
+) -> Result<Vec<String>, RustAnalyzerAdapterError> {
+    let edits = collect_text_edits(workspace_edit)?;
+    if let Some((unknown, _)) = edits
+        .iter()
+        .find(|(uri, _)| !documents.iter().any(|document| document.uri == *uri))
+    {
+        return Err(RustAnalyzerAdapterError::InvalidOutput {
+            message: format!(
+                "workspace edit modifies '{}', which was not supplied in the request",
+                unknown.as_str()
+            ),
+        });
+    }
+
+    documents
+        .iter()
+        .map(|document| {
+            let document_edits = edits
+                .iter()
+                .filter(|(uri, _)| *uri == document.uri)
+                .map(|(_, edit)| edit.clone())
+                .collect();
+            apply_text_edits(document.content, document_edits, encoding)
+        })
+        .collect()
+}
+
+/// Returns `true` when `workspace_edit` contains at least one change.
+///
+/// Resource operations count as changes so that they reach
+/// [`apply_workspace_edit`] and are rejected there.
+pub(super) fn has_changes(workspace_edit: &WorkspaceEdit) -> bool {
+    let has_text_changes = workspace_edit
+        .changes
+        .as_ref()
+        .is_some_and(|changes| changes.values().any(|edits| !edits.is_empty()));
+    let has_document_changes = match &workspace_edit.document_changes {
+        Some(DocumentChanges::Edits(document_edits)) => document_edits
+            .iter()
+            .any(|document_edit| !document_edit.edits.is_empty()),
+        Some(DocumentChanges::Operations(operations)) => !operations.is_empty(),
+        None => false,
+    };
+    has_text_changes || has_document_changes
+}
+
+/// Applies edits addressed to a single document.
+fn apply_text_edits(
+    original: &str,
+    edits: Vec<TextEdit>,
+    encoding: PositionEncoding,
+) -> Result<String, RustAnalyzerAdapterError> {
+    if edits.is_empty() {
+        return Ok(String::from(original));
+    }
+
+    let mut ranges = edits
+        .into_iter()
+        .map(|edit| {
+            let start = lsp_position_to_byte_offset(original, edit.range.start, encoding)?;
+            let end = lsp_position_to_byte_offset(original, edit.range.end, encoding)?;
+            if end < start {
+                return Err(RustAnalyzerAdapterError::InvalidOutput {
+                    message: format!("edit range end precedes start (start={start}, end={end})"),
+                });
+            }
+            Ok((start, end, edit.new_text))
+        })
+        .collect::<Result<Vec<(usize, usize, String)>, RustAnalyzerAdapterError>>()?;
+
+    ranges.sort_by_key(|range| std::cmp::Reverse(range.0));
+
+    let mut updated = String::from(original);
+    for (start, end, replacement) in ranges {
+        if end > updated.len() || start > end {
+            return Err(RustAnalyzerAdapterError::InvalidOutput {
+                message: format!("edit range [{start}, {end}) is out of bounds"),
+            });
+        }
+        if !updated.is_char_boundary(start) || !updated.is_char_boundary(end) {
+            return Err(RustAnalyzerAdapterError::InvalidOutput {
+                message: format!("edit range [{start}, {end}) is not UTF-8 aligned"),
+            });
+        }
+
+        updated.replace_range(start..end, &replacement);
+    }
+
+    Ok(updated)
+}
+
+/// Flattens both workspace-edit representations into `(uri, edit)` pairs.
+fn collect_text_edits(
+    workspace_edit: WorkspaceEdit,
+) -> Result<Vec<(Uri, TextEdit)>, RustAnalyzerAdapterError> {
+    let mut edits = Vec::new();
+
+    if let Some(changes) = workspace_edit.changes {
+        for (uri, file_edits) in changes {
+            edits.extend(file_edits.into_iter().map(|edit| (uri.clone(), edit)));
+        }
+    }
+
+    if let Some(document_changes) = workspace_edit.document_changes {
+        collect_document_changes(&mut edits, document_changes)?;
+    }
+
+    Ok(edits)
+}
+
+fn collect_document_changes(
+    target: &mut Vec<(Uri, TextEdit)>,
+    document_changes: DocumentChanges,
+) -> Result<(), RustAnalyzerAdapterError> {
+    match document_changes {
+        DocumentChanges::Edits(text_document_edits) => {
+            for document_edit in text_document_edits {
+                append_document_edits(
+                    target,
+                    &document_edit.text_document.uri,
+                    document_edit.edits,
+                );
+            }
+            Ok(())
+        }
+        DocumentChanges::Operations(operations) => {
+            for operation in operations {
+                collect_operation(target, operation)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn collect_operation(
+    target: &mut Vec<(Uri, TextEdit)>,
+    operation: DocumentChangeOperation,
+) -> Result<(), RustAnalyzerAdapterError> {
+    match operation {
+        DocumentChangeOperation::Edit(document_edit) => {
+            append_document_edits(
+                target,
+                &document_edit.text_document.uri,
+                document_edit.edits,
+            );
+            Ok(())
+        }
+        DocumentChangeOperation::Op(resource_operation) => {
+            Err(RustAnalyzerAdapterError::InvalidOutput {
+                message: format!(
+                    concat!(
+                        "workspace edit includes unsupported resource operation: ",
+                        "{:?}"
+                    ),
+                    resource_operation
+                ),
+            })
+        }
+    }
+}
+
+fn append_document_edits(
+    target: &mut Vec<(Uri, TextEdit)>,
+    uri: &Uri,
+    edits: Vec<OneOf<TextEdit, AnnotatedTextEdit>>,
+) {
+    for edit in edits {
+        let text_edit = match edit {
+            OneOf::Left(text_edit) => text_edit,
+            OneOf::Right(annotated_text_edit) => annotated_text_edit.text_edit,
+        };
+        target.push((uri.clone(), text_edit));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for mapping workspace edits back to request documents.
+
+    use std::collections::HashMap;
+
+    use lsp_types::{Position, Range, TextEdit, Uri, WorkspaceEdit};
+
+    use super::{SourceDocument, apply_workspace_edit, has_changes};
+    use crate::{RustAnalyzerAdapterError, lsp::text_edits::PositionEncoding};
+
+    fn uri(path: &str) -> Uri { format!("file:///ws/{path}").parse().expect("static URI") }
+
+    fn replace(line: u32, start: u32, end: u32, text: &str) -> TextEdit {
+        TextEdit {
+            range: Range {
+                start: Position {
+                    line,
+                    character: start,
+                },
+                end: Position {
+                    line,
+                    character: end,
+                },
+            },
+            new_text: String::from(text),
+        }
+    }
+
+    fn edit(entries: Vec<(Uri, Vec<TextEdit>)>) -> WorkspaceEdit {
+        WorkspaceEdit {
+            changes: Some(entries.into_iter().collect::<HashMap<_, _>>()),
+            ..WorkspaceEdit::default()
+        }
+    }
+
+    #[test]
+    fn applies_edits_to_each_document_in_order() {
+        let documents = [
+            SourceDocument {
+                uri: uri("src/lib.rs"),
+                content: "fn old() {}\n",
+            },
+            SourceDocument {
+                uri: uri("src/main.rs"),
+                content: "fn main() { old(); }\n",
+            },
+            SourceDocument {
+                uri: uri("src/other.rs"),
+                content: "const X: i32 = 1;\n",
+            },
+        ];
+        let workspace_edit = edit(vec![
+            (uri("src/main.rs"), vec![replace(0, 12, 15, "new")]),
+            (uri("src/lib.rs"), vec![replace(0, 3, 6, "new")]),
+        ]);
+
+        let updated = apply_workspace_edit(&documents, workspace_edit, PositionEncoding::Utf16)
+            .expect("edits should apply");
+
+        assert_eq!(
+            updated,
+            vec![
+                String::from("fn new() {}\n"),
+                String::from("fn main() { new(); }\n"),
+                String::from("const X: i32 = 1;\n"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_edits_to_unstaged_documents() {
+        let documents = [SourceDocument {
+            uri: uri("src/lib.rs"),
+            content: "fn old() {}\n",
+        }];
+        let workspace_edit = edit(vec![(uri("src/extra.rs"), vec![replace(0, 0, 0, "x")])]);
+
+        let result = apply_workspace_edit(&documents, workspace_edit, PositionEncoding::Utf8);
+
+        assert!(matches!(
+            result,
+            Err(RustAnalyzerAdapterError::InvalidOutput { message })
+                if message.contains("src/extra.rs")
+        ));
+    }
+
+    #[test]
+    fn detects_empty_workspace_edits() {
+        assert!(!has_changes(&WorkspaceEdit::default()));
+        assert!(!has_changes(&edit(vec![(uri("src/lib.rs"), Vec::new())])));
+        assert!(has_changes(&edit(vec![(
+            uri("src/lib.rs"),
+            vec![replace(0, 0, 0, "x")]
+        )])));
+    }
+}
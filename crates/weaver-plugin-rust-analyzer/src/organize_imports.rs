@@ -0,0 +1,52 @@
+//! Execution of `organize-imports` plugin requests.
+//!
+//! An organize-imports request carries exactly one file and no arguments.
+//! The adapter sorts and merges its imports, and the result is returned as a
+//! diff.
+
+use weaver_plugins::{
+    capability::ReasonCode,
+    protocol::{PluginOutput, PluginRequest, PluginResponse},
+};
+
+use crate::{
+    RustAnalyzerAdapter,
+    failure::PluginFailure,
+    patch::build_search_replace_patch,
+    path_utils::validate_relative_path,
+};
+
+/// Sorts and merges the imports of a single file and returns the diff.
+pub(crate) fn execute_organize_imports<R: RustAnalyzerAdapter>(
+    adapter: &R,
+    request: &PluginRequest,
+) -> Result<PluginResponse, PluginFailure> {
+    let file = match request.files() {
+        [single] => single,
+        other => {
+            return Err(PluginFailure::with_reason(
+                format!(
+                    "organize-imports operation requires exactly one file payload, got {}",
+                    other.len()
+                ),
+                ReasonCode::IncompletePayload,
+            ));
+        }
+    };
+    validate_relative_path(file.path()).map_err(|error| {
+        PluginFailure::with_reason(error.to_string(), ReasonCode::IncompletePayload)
+    })?;
+
+    let modified = adapter
+        .organize_imports(file)
+        .map_err(|error| PluginFailure::plain(error.to_string()))?;
+    if modified == file.content() {
+        return Err(PluginFailure::plain(
+            "organize-imports operation produced no content changes",
+        ));
+    }
+
+    Ok(PluginResponse::success(PluginOutput::Diff {
+        content: build_search_replace_patch(file.path(), file.content(), &modified)?,
+    }))
+}
@@ -0,0 +1,17 @@
+//! SEARCH/REPLACE patch generation for plugin diff responses.
+
+use std::path::Path;
+
+use weaver_plugins::protocol::search_replace_patch;
+
+use crate::{failure::PluginFailure, path_utils::path_to_slash};
+
+/// Builds a single-file SEARCH/REPLACE patch for a request payload path.
+pub(crate) fn build_search_replace_patch(
+    path: &Path,
+    original: &str,
+    modified: &str,
+) -> Result<String, PluginFailure> {
+    let unix_path = path_to_slash(path).map_err(|error| PluginFailure::plain(error.to_string()))?;
+    Ok(search_replace_patch(&unix_path, original, modified))
+}
@@ -0,0 +1,70 @@
+//! Execution of `prepare-rename` plugin requests.
+//!
+//! A prepare-rename request asks whether the symbol at a position can be
+//! renamed without computing any edits. Success yields analysis output
+//! describing the renameable span so callers can validate a rename cheaply
+//! before requesting the diff.
+
+use serde_json::json;
+use weaver_plugins::{
+    capability::ReasonCode,
+    protocol::{PluginOutput, PluginRequest, PluginResponse},
+};
+
+use crate::{
+    ByteOffset,
+    RustAnalyzerAdapter,
+    arguments::parse_prepare_rename_arguments,
+    ensure_uri_matches,
+    failure::PluginFailure,
+    path_utils::validate_relative_path,
+};
+
+/// Validates a rename position and reports the range it would cover.
+pub(crate) fn execute_prepare_rename<R: RustAnalyzerAdapter>(
+    adapter: &R,
+    request: &PluginRequest,
+) -> Result<PluginResponse, PluginFailure> {
+    let arguments = parse_prepare_rename_arguments(request.arguments())
+        .map_err(|message| PluginFailure::with_reason(message, ReasonCode::IncompletePayload))?;
+    let file = match request.files() {
+        [single] => single,
+        other => {
+            return Err(PluginFailure::with_reason(
+                format!(
+                    "prepare-rename operation requires exactly one file payload, got {}",
+                    other.len()
+                ),
+                ReasonCode::IncompletePayload,
+            ));
+        }
+    };
+    validate_relative_path(file.path()).map_err(|error| {
+        PluginFailure::with_reason(error.to_string(), ReasonCode::IncompletePayload)
+    })?;
+    ensure_uri_matches(arguments.uri(), file)?;
+
+    let range = adapter
+        .prepare_rename(file, ByteOffset::new(arguments.offset()))
+        .map_err(|error| PluginFailure::plain(error.to_string()))?
+        .ok_or_else(|| {
+            PluginFailure::with_reason(
+                format!("no renameable symbol at byte offset {}", arguments.offset()),
+                ReasonCode::SymbolNotFound,
+            )
+        })?;
+    let (start, end) = (range.start.as_usize(), range.end.as_usize());
+    let symbol = file.content().get(start..end).ok_or_else(|| {
+        PluginFailure::plain(format!(
+            "prepare-rename range {start}..{end} is not a valid span of '{}'",
+            file.path().display()
+        ))
+    })?;
+
+    Ok(PluginResponse::success(PluginOutput::Analysis {
+        data: json!({
+            "range": { "start": start, "end": end },
+            "symbol": symbol,
+        }),
+    }))
+}
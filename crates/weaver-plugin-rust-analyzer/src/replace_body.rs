@@ -0,0 +1,81 @@
+//! Execution of `replace-body` plugin requests.
+//!
+//! A replace-body request names a function by a position anywhere inside it
+//! and supplies a replacement block. Only the body block is rewritten, so the
+//! signature, attributes, and doc comments are left untouched, and the result
+//! is returned as a diff.
+
+use weaver_plugins::{
+    capability::ReasonCode,
+    protocol::{PluginOutput, PluginRequest, PluginResponse},
+};
+
+use crate::{
+    ByteOffset,
+    RustAnalyzerAdapter,
+    arguments::parse_replace_body_arguments,
+    ensure_uri_matches,
+    failure::PluginFailure,
+    patch::build_search_replace_patch,
+    path_utils::validate_relative_path,
+};
+
+/// Replaces the body of the function enclosing the requested position.
+pub(crate) fn execute_replace_body<R: RustAnalyzerAdapter>(
+    adapter: &R,
+    request: &PluginRequest,
+) -> Result<PluginResponse, PluginFailure> {
+    let arguments = parse_replace_body_arguments(request.arguments())
+        .map_err(|message| PluginFailure::with_reason(message, ReasonCode::IncompletePayload))?;
+    let file = match request.files() {
+        [single] => single,
+        other => {
+            return Err(PluginFailure::with_reason(
+                format!(
+                    "replace-body operation requires exactly one file payload, got {}",
+                    other.len()
+                ),
+                ReasonCode::IncompletePayload,
+            ));
+        }
+    };
+    validate_relative_path(file.path()).map_err(|error| {
+        PluginFailure::with_reason(error.to_string(), ReasonCode::IncompletePayload)
+    })?;
+    ensure_uri_matches(arguments.uri(), file)?;
+
+    let body = adapter
+        .function_body(file, ByteOffset::new(arguments.offset()))
+        .map_err(|error| PluginFailure::plain(error.to_string()))?
+        .ok_or_else(|| {
+            PluginFailure::with_reason(
+                format!(
+                    "byte offset {} is not inside a function with a body",
+                    arguments.offset()
+                ),
+                ReasonCode::SymbolNotFound,
+            )
+        })?;
+    let (start, end) = (body.start.as_usize(), body.end.as_usize());
+    let original = file.content();
+    let (Some(before), Some(_), Some(after)) = (
+        original.get(..start),
+        original.get(start..end),
+        original.get(end..),
+    ) else {
+        return Err(PluginFailure::plain(format!(
+            "function body range {start}..{end} is not a valid span of '{}'",
+            file.path().display()
+        )));
+    };
+    let modified = format!("{before}{}{after}", arguments.new_body());
+    if modified == original {
+        return Err(PluginFailure::plain(
+            "replace-body operation produced no content changes",
+        ));
+    }
+
+    Ok(PluginResponse::success(PluginOutput::Diff {
+        content: build_search_replace_patch(file.path(), original, &modified)?,
+    }))
+}
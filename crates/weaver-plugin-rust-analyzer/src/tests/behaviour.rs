@@ -1,6 +1,6 @@
 //! Behaviour-driven tests for rust-analyzer plugin request dispatch.
 
-use std::{collections::HashMap, path::PathBuf};
+use std::{collections::HashMap, ops::Range, path::PathBuf};
 
 use mockall::mock;
 use rstest::fixture;
@@ -13,6 +13,7 @@ use weaver_test_macros::allow_fixture_expansion_lints;
 
 use crate::{
     ByteOffset,
+    ChangedFiles,
     RustAnalyzerAdapter,
     RustAnalyzerAdapterError,
     execute_request,
@@ -43,10 +44,21 @@ mock! {
     impl RustAnalyzerAdapter for BehaviourAdapter {
         fn rename(
             &self,
-            file: &FilePayload,
+            files: &[FilePayload],
             offset: ByteOffset,
             new_name: &str,
-        ) -> Result<String, RustAnalyzerAdapterError>;
+        ) -> Result<ChangedFiles, RustAnalyzerAdapterError>;
+        fn organize_imports(&self, file: &FilePayload) -> Result<String, RustAnalyzerAdapterError>;
+        fn prepare_rename(
+            &self,
+            file: &FilePayload,
+            offset: ByteOffset,
+        ) -> Result<Option<Range<ByteOffset>>, RustAnalyzerAdapterError>;
+        fn function_body(
+            &self,
+            file: &FilePayload,
+            offset: ByteOffset,
+        ) -> Result<Option<Range<ByteOffset>>, RustAnalyzerAdapterError>;
     }
 }
 
@@ -60,12 +72,25 @@ fn should_invoke_rename(request: &PluginRequest) -> bool {
 
 fn configure_adapter_for_mode(adapter: &mut MockBehaviourAdapter, mode: AdapterMode) {
     adapter.expect_rename().once().returning(
-        move |file: &FilePayload, _offset: ByteOffset, _new_name: &str| match mode {
-            AdapterMode::Success => Ok(file.content().replace("old_name", "new_name")),
-            AdapterMode::NoChange => Ok(file.content().to_owned()),
-            AdapterMode::Fails => Err(RustAnalyzerAdapterError::EngineFailed {
-                message: String::from("rust-analyzer adapter failed"),
-            }),
+        move |files: &[FilePayload], _offset: ByteOffset, _new_name: &str| {
+            let rewrite = |content: &str| match mode {
+                AdapterMode::NoChange => content.to_owned(),
+                _ => content.replace("old_name", "new_name"),
+            };
+            match mode {
+                AdapterMode::Fails => Err(RustAnalyzerAdapterError::EngineFailed {
+                    message: String::from("rust-analyzer adapter failed"),
+                }),
+                AdapterMode::Success | AdapterMode::NoChange => Ok(files
+                    .iter()
+                    .map(|file| {
+                        (
+                            file.path().to_string_lossy().into_owned(),
+                            rewrite(file.content()),
+                        )
+                    })
+                    .collect()),
+            }
         },
     );
 }
@@ -3,7 +3,14 @@
 use rstest::rstest;
 use weaver_plugins::{
     capability::ReasonCode,
-    protocol::{DiagnosticSeverity, PluginResponse},
+    protocol::{
+        ContentReference,
+        DiagnosticSeverity,
+        FilePayload,
+        PluginOutput,
+        PluginRequest,
+        PluginResponse,
+    },
 };
 
 use super::support::{
@@ -72,6 +79,42 @@ fn run_with_adapter_dispatch_layer(
     }
 }
 
+#[test]
+fn ping_succeeds_without_touching_rust_analyzer() {
+    let input = format!(
+        "{}\n",
+        serde_json::to_string(&PluginRequest::ping()).expect("serialize request")
+    );
+    let response = dispatch_stdin(input.as_bytes(), &adapter_unused());
+
+    assert!(response.is_success());
+    assert_eq!(response.output(), &PluginOutput::Empty);
+}
+
+#[test]
+fn unreadable_content_reference_fails_before_the_adapter_runs() {
+    let payload = FilePayload::by_reference(
+        "src/main.rs".into(),
+        ContentReference::new("/nonexistent/weaver-plugin/0".into(), 12),
+    );
+    let request = PluginRequest::new("rename-symbol", vec![payload]);
+    let input = format!(
+        "{}\n",
+        serde_json::to_string(&request).expect("serialize request")
+    );
+
+    let response = dispatch_stdin(input.as_bytes(), &adapter_unused());
+
+    assert!(!response.is_success());
+    assert!(
+        response.diagnostics().iter().any(|diagnostic| diagnostic
+            .message()
+            .contains("failed to read referenced file content")),
+        "unexpected diagnostics: {:?}",
+        response.diagnostics(),
+    );
+}
+
 #[rstest]
 #[case::missing_position(
     {
@@ -0,0 +1,159 @@
+//! Tests for the `extract-predicate` operation.
+
+use std::{collections::HashMap, path::PathBuf};
+
+use rstest::rstest;
+use serde_json::json;
+use weaver_plugins::{
+    capability::ReasonCode,
+    protocol::{FilePayload, PluginOutput, PluginRequest},
+};
+
+use super::support::adapter_unused;
+use crate::{execute_request, failure::PluginFailure};
+
+const CHECK: &str = "fn check(x: i32, y: i32) -> bool {\n    x > 0 && y < 10\n}\n";
+const PREDICATE: &str = "x > 0 && y < 10";
+
+fn extract_request(source: &str, selection: &str, new_name: &str) -> PluginRequest {
+    let start = source.find(selection).expect("selection should be present");
+    let arguments = HashMap::from([
+        (String::from("uri"), json!("file:///src/lib.rs")),
+        (String::from("start_offset"), json!(start)),
+        (String::from("end_offset"), json!(start + selection.len())),
+        (String::from("new_name"), json!(new_name)),
+    ]);
+    PluginRequest::with_arguments(
+        "extract-predicate",
+        vec![FilePayload::new(PathBuf::from("src/lib.rs"), source)],
+        arguments,
+    )
+}
+
+/// Runs the extraction and returns the REPLACE half of the diff.
+fn extracted(source: &str, selection: &str, new_name: &str) -> String {
+    let response = execute_request(
+        &adapter_unused(),
+        &extract_request(source, selection, new_name),
+    )
+    .expect("extract-predicate should succeed");
+    let PluginOutput::Diff { content } = response.output() else {
+        panic!("expected diff output, got {:?}", response.output());
+    };
+    let (_, replace) = content
+        .split_once("=======\n")
+        .expect("diff should have a REPLACE section");
+    String::from(replace.trim_end_matches(">>>>>>> REPLACE\n"))
+}
+
+fn extract_error(source: &str, selection: &str, new_name: &str) -> PluginFailure {
+    execute_request(
+        &adapter_unused(),
+        &extract_request(source, selection, new_name),
+    )
+    .expect_err("extract-predicate should fail")
+}
+
+#[test]
+fn extracts_comparison_into_named_predicate() {
+    assert_eq!(
+        extracted(CHECK, PREDICATE, "is_valid"),
+        concat!(
+            "fn check(x: i32, y: i32) -> bool {\n",
+            "    is_valid(x, y)\n",
+            "}\n",
+            "\n",
+            "fn is_valid(x: i32, y: i32) -> bool {\n",
+            "    x > 0 && y < 10\n",
+            "}\n",
+        )
+    );
+}
+
+#[test]
+fn associated_functions_call_the_predicate_through_self() {
+    let source = concat!(
+        "impl Gate {\n",
+        "    fn open(&self, name: String) -> bool {\n",
+        "        let limit: usize = 3;\n",
+        "        !(name.len() > limit)\n",
+        "    }\n",
+        "}\n",
+    );
+
+    assert_eq!(
+        extracted(source, "!(name.len() > limit)", "is_short"),
+        concat!(
+            "impl Gate {\n",
+            "    fn open(&self, name: String) -> bool {\n",
+            "        let limit: usize = 3;\n",
+            "        Self::is_short(&name, limit)\n",
+            "    }\n",
+            "\n",
+            "    fn is_short(name: &String, limit: usize) -> bool {\n",
+            "        !(name.len() > limit)\n",
+            "    }\n",
+            "}\n",
+        )
+    );
+}
+
+#[rstest]
+#[case::arithmetic(
+    "fn f(x: i32) -> i32 {\n    x + 1\n}\n",
+    "x + 1",
+    "not a boolean expression"
+)]
+#[case::partial_node(CHECK, "x > 0 && y", "does not select a single expression")]
+#[case::outside_function("const OK: bool = 1 < 2;\n", "1 < 2", "is not inside a function")]
+fn rejects_selections_that_are_not_boolean_expressions_in_functions(
+    #[case] source: &str,
+    #[case] selection: &str,
+    #[case] needle: &str,
+) {
+    let error = extract_error(source, selection, "is_valid");
+    assert!(
+        error.message().contains(needle),
+        "expected error mentioning '{needle}', got: {error}"
+    );
+    assert_eq!(error.reason_code(), Some(ReasonCode::SymbolNotFound));
+}
+
+#[test]
+fn rejects_untyped_let_bindings() {
+    let source = "fn f() -> bool {\n    let x = 1;\n    x > 0\n}\n";
+
+    let error = extract_error(source, "x > 0", "is_positive");
+    assert!(
+        error.message().contains("cannot determine the type of 'x'"),
+        "expected type diagnostic, got: {error}"
+    );
+    assert_eq!(error.reason_code(), Some(ReasonCode::OperationNotSupported));
+}
+
+#[test]
+fn rejects_names_that_already_exist() {
+    let error = extract_error(CHECK, PREDICATE, "check");
+    assert!(
+        error
+            .message()
+            .contains("a function named 'check' already exists"),
+        "expected conflict diagnostic, got: {error}"
+    );
+    assert_eq!(error.reason_code(), Some(ReasonCode::NameConflict));
+}
+
+#[rstest]
+#[case::invalid_name("is-valid", "not a valid Rust identifier")]
+#[case::strict_keyword("fn", "not a valid Rust identifier")]
+#[case::self_keyword("self", "not a valid Rust identifier")]
+#[case::reserved_keyword("yield", "not a valid Rust identifier")]
+#[case::empty_name(" ", "new_name argument must not be empty")]
+fn rejects_invalid_names(#[case] new_name: &str, #[case] needle: &str) {
+    let error = extract_error(CHECK, PREDICATE, new_name);
+    assert!(
+        error.message().contains(needle),
+        "expected error mentioning '{needle}', got: {error}"
+    );
+    assert_eq!(error.reason_code(), Some(ReasonCode::IncompletePayload));
+}
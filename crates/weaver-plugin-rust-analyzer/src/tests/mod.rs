@@ -5,6 +5,12 @@ mod behaviour;
 mod contract_behaviour;
 mod contract_fixtures;
 mod dispatch_layer;
+mod extract_predicate;
+mod multi_file;
+mod organize_imports;
+mod persistent;
+mod prepare_rename;
+mod replace_body;
 mod support;
 
 use std::path::PathBuf;
@@ -0,0 +1,147 @@
+//! Tests for rename requests that supply several file payloads.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use rstest::rstest;
+use weaver_plugins::{
+    capability::ReasonCode,
+    protocol::{FilePayload, PluginOutput, PluginRequest},
+};
+
+use super::support::{MockAdapter, adapter_unused, changed_files, rename_arguments};
+use crate::{ByteOffset, execute_request};
+
+const LIB_SOURCE: &str = "pub fn old_name() -> i32 {\n    1\n}\n";
+const MAIN_SOURCE: &str = "fn main() {\n    crate_name::old_name();\n}\n";
+const UNRELATED_SOURCE: &str = "pub const VALUE: i32 = 1;\n";
+
+fn crate_request(target_path: Option<&str>) -> PluginRequest {
+    let mut arguments = rename_arguments();
+    arguments.insert(
+        String::from("uri"),
+        serde_json::Value::String(String::from("file:///src/lib.rs")),
+    );
+    if let Some(path) = target_path {
+        arguments.insert(
+            String::from("target_path"),
+            serde_json::Value::String(String::from(path)),
+        );
+    }
+    PluginRequest::with_arguments(
+        "rename-symbol",
+        vec![
+            FilePayload::new(PathBuf::from("src/main.rs"), MAIN_SOURCE),
+            FilePayload::new(PathBuf::from("src/lib.rs"), LIB_SOURCE),
+            FilePayload::new(PathBuf::from("src/unrelated.rs"), UNRELATED_SOURCE),
+        ],
+        arguments,
+    )
+}
+
+#[test]
+fn rename_targets_named_file_and_emits_one_patch_per_changed_file() {
+    let mut adapter = MockAdapter::new();
+    adapter
+        .expect_rename()
+        .withf(|files, offset, _new_name| {
+            files.len() == 3
+                && files
+                    .first()
+                    .is_some_and(|file| file.path() == Path::new("src/lib.rs"))
+                && *offset == ByteOffset::new(3)
+        })
+        .once()
+        .return_once(|_files, _offset, _new_name| {
+            Ok(changed_files(&[
+                ("src/lib.rs", "pub fn new_name() -> i32 {\n    1\n}\n"),
+                (
+                    "src/main.rs",
+                    "fn main() {\n    crate_name::new_name();\n}\n",
+                ),
+                ("src/unrelated.rs", UNRELATED_SOURCE),
+            ]))
+        });
+
+    let response = execute_request(&adapter, &crate_request(Some("src/lib.rs")))
+        .expect("multi-file rename should succeed");
+    let PluginOutput::Diff { content } = response.output() else {
+        panic!("expected diff output, got {:?}", response.output());
+    };
+    assert_eq!(content.matches("diff --git ").count(), 2);
+    assert!(content.contains("diff --git a/src/lib.rs b/src/lib.rs\n"));
+    assert!(content.contains("diff --git a/src/main.rs b/src/main.rs\n"));
+    assert!(!content.contains("src/unrelated.rs"));
+}
+
+#[rstest]
+#[case::missing_target_path(None, "requires a 'target_path' argument")]
+#[case::unknown_target_path(Some("src/other.rs"), "does not match any file payload")]
+#[case::target_disagrees_with_uri(Some("src/main.rs"), "does not match file payload")]
+fn multi_file_rename_requires_matching_target(
+    #[case] target_path: Option<&str>,
+    #[case] needle: &str,
+) {
+    let adapter = adapter_unused();
+    let error = execute_request(&adapter, &crate_request(target_path))
+        .expect_err("ambiguous target should fail before adapter invocation");
+    assert!(
+        error.message().contains(needle),
+        "expected error mentioning '{needle}', got: {error}"
+    );
+    assert_eq!(error.reason_code(), Some(ReasonCode::IncompletePayload));
+}
+
+#[test]
+fn rename_rejects_changes_to_unsupplied_files() {
+    let mut adapter = MockAdapter::new();
+    adapter
+        .expect_rename()
+        .once()
+        .return_once(|_files, _offset, _new_name| {
+            Ok(changed_files(&[
+                ("src/lib.rs", "pub fn new_name() -> i32 {\n    1\n}\n"),
+                ("src/extra.rs", "new_name();\n"),
+            ]))
+        });
+
+    let error = execute_request(&adapter, &crate_request(Some("src/lib.rs")))
+        .expect_err("changes to unknown files should fail");
+    assert!(
+        error.message().contains("src/extra.rs"),
+        "expected error naming the unknown file, got: {error}"
+    );
+}
+
+#[test]
+fn rename_rejects_empty_file_list() {
+    let adapter = adapter_unused();
+    let request = PluginRequest::with_arguments("rename-symbol", Vec::new(), rename_arguments());
+    let error = execute_request(&adapter, &request).expect_err("no payloads should fail");
+    assert!(
+        error.message().contains("at least one file payload"),
+        "expected missing-payload error, got: {error}"
+    );
+    assert_eq!(error.reason_code(), Some(ReasonCode::IncompletePayload));
+}
+
+#[test]
+fn target_path_must_be_a_non_empty_string() {
+    let adapter = adapter_unused();
+    let mut request_arguments: HashMap<String, serde_json::Value> = rename_arguments();
+    request_arguments.insert(String::from("target_path"), serde_json::json!(7));
+    let request = PluginRequest::with_arguments(
+        "rename-symbol",
+        vec![FilePayload::new(PathBuf::from("src/main.rs"), LIB_SOURCE)],
+        request_arguments,
+    );
+    let error = execute_request(&adapter, &request).expect_err("numeric target should fail");
+    assert!(
+        error
+            .message()
+            .contains("target_path argument must be a string"),
+        "expected type error, got: {error}"
+    );
+}
@@ -0,0 +1,97 @@
+//! Tests for the `organize-imports` operation.
+
+use std::path::PathBuf;
+
+use rstest::rstest;
+use weaver_plugins::{
+    capability::ReasonCode,
+    protocol::{FilePayload, PluginOutput, PluginRequest},
+};
+
+use super::support::{MockAdapter, adapter_unused};
+use crate::{RustAnalyzerAdapterError, execute_request};
+
+const UNSORTED: &str = "use std::io;\nuse std::fmt;\n\nfn main() {}\n";
+const SORTED: &str = "use std::{fmt, io};\n\nfn main() {}\n";
+
+fn organize_request(files: Vec<FilePayload>) -> PluginRequest {
+    PluginRequest::new("organize-imports", files)
+}
+
+fn main_rs() -> FilePayload { FilePayload::new(PathBuf::from("src/main.rs"), UNSORTED) }
+
+fn adapter_organizing(result: Result<String, RustAnalyzerAdapterError>) -> MockAdapter {
+    let mut adapter = MockAdapter::new();
+    adapter
+        .expect_organize_imports()
+        .withf(|file| file.path() == PathBuf::from("src/main.rs").as_path())
+        .once()
+        .return_once(move |_file| result);
+    adapter
+}
+
+#[test]
+fn organize_imports_returns_diff_for_reordered_imports() {
+    let adapter = adapter_organizing(Ok(String::from(SORTED)));
+
+    let response = execute_request(&adapter, &organize_request(vec![main_rs()]))
+        .expect("organize-imports should succeed");
+
+    let PluginOutput::Diff { content } = response.output() else {
+        panic!("expected diff output, got {:?}", response.output());
+    };
+    assert!(content.starts_with("diff --git a/src/main.rs b/src/main.rs\n"));
+    assert!(content.contains(SORTED));
+}
+
+#[test]
+fn organize_imports_without_changes_fails() {
+    let adapter = adapter_organizing(Ok(String::from(UNSORTED)));
+
+    let error = execute_request(&adapter, &organize_request(vec![main_rs()]))
+        .expect_err("unchanged content should fail");
+    assert!(
+        error.message().contains("no content changes"),
+        "expected no-change error, got: {error}"
+    );
+}
+
+#[test]
+fn organize_imports_surfaces_adapter_errors() {
+    let adapter = adapter_organizing(Err(RustAnalyzerAdapterError::EngineFailed {
+        message: String::from("server crashed"),
+    }));
+
+    let error = execute_request(&adapter, &organize_request(vec![main_rs()]))
+        .expect_err("adapter error should fail");
+    assert!(
+        error.message().contains("server crashed"),
+        "expected adapter message, got: {error}"
+    );
+    assert_eq!(error.reason_code(), None);
+}
+
+#[rstest]
+#[case::no_files(Vec::new(), "exactly one file payload, got 0")]
+#[case::two_files(
+    vec![main_rs(), FilePayload::new(PathBuf::from("src/lib.rs"), UNSORTED)],
+    "exactly one file payload, got 2"
+)]
+#[case::absolute_path(
+    vec![FilePayload::new(PathBuf::from("/etc/main.rs"), UNSORTED)],
+    "absolute"
+)]
+fn organize_imports_rejects_invalid_payloads(
+    #[case] files: Vec<FilePayload>,
+    #[case] needle: &str,
+) {
+    let adapter = adapter_unused();
+
+    let error = execute_request(&adapter, &organize_request(files))
+        .expect_err("invalid payload should fail before adapter invocation");
+    assert!(
+        error.message().contains(needle),
+        "expected error mentioning '{needle}', got: {error}"
+    );
+    assert_eq!(error.reason_code(), Some(ReasonCode::IncompletePayload));
+}
@@ -0,0 +1,278 @@
+//! Tests for one-shot and persistent rust-analyzer sessions.
+//!
+//! A small Python language server stands in for rust-analyzer. Its rename
+//! replies encode how many renames the process has served, the version of the
+//! target document, and how many documents are open, so tests can observe
+//! whether a server was reused and how documents were synchronized. Renaming
+//! to `slow` simulates a server that is still indexing by answering the first
+//! two attempts with no edits, while renaming to `unchanged` never produces
+//! edits. `prepareRename` succeeds only at the function name.
+
+use std::{
+    ffi::OsString,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+use cap_std::{ambient_authority, fs::Dir};
+use weaver_plugins::{capability::ReasonCode, protocol::FilePayload};
+
+use super::support::{rename_arguments, request_with_args};
+use crate::{
+    ByteOffset,
+    RustAnalyzerAdapter,
+    RustAnalyzerAdapterError,
+    RustAnalyzerLspAdapter,
+    execute_request,
+};
+
+const BINARY_ENV: &str = "WEAVER_RUST_ANALYZER_BINARY";
+
+const FAKE_SERVER: &str = r"import json, sys
+MARKER = sys.argv[1] if len(sys.argv) > 1 else None
+stdin, stdout = sys.stdin.buffer, sys.stdout.buffer
+renames, documents, pending = 0, {}, 2
+def read():
+    length = None
+    while True:
+        line = stdin.readline()
+        if not line:
+            sys.exit(0)
+        if not line.strip():
+            return json.loads(stdin.read(length))
+        name, _, value = line.partition(b':')
+        if name.strip().lower() == b'content-length':
+            length = int(value)
+def reply(message, **fields):
+    body = json.dumps(dict(jsonrpc='2.0', id=message['id'], **fields)).encode()
+    stdout.write(b'Content-Length: %d\r\n\r\n' % len(body) + body)
+    stdout.flush()
+while True:
+    message = read()
+    method, params = message.get('method'), message.get('params') or {}
+    if method == 'initialize':
+        reply(message, result={'capabilities': {'positionEncoding': 'utf-8'}})
+    elif method in ('textDocument/didOpen', 'textDocument/didChange'):
+        documents[params['textDocument']['uri']] = params['textDocument']['version']
+    elif method == 'textDocument/didClose':
+        documents.pop(params['textDocument']['uri'], None)
+    elif method == 'textDocument/rename' and params['newName'] == 'boom':
+        reply(message, error={'code': -32603, 'message': 'rename exploded'})
+    elif method == 'textDocument/rename' and params['newName'] == 'unchanged':
+        reply(message, result={'changes': {}})
+    elif method == 'textDocument/rename' and params['newName'] == 'slow' and pending > 0:
+        pending -= 1
+        reply(message, result=None if pending else {'changes': {}})
+    elif method == 'textDocument/rename':
+        renames += 1
+        uri = params['textDocument']['uri']
+        text = '%s_%d_%d_%d' % (params['newName'], renames, documents.get(uri, 0), len(documents))
+        span = {'start': {'line': 0, 'character': 3}, 'end': {'line': 0, 'character': 11}}
+        reply(message, result={'changes': {uri: [{'range': span, 'newText': text}]}})
+    elif method == 'textDocument/prepareRename':
+        span = {'start': {'line': 0, 'character': 3}, 'end': {'line': 0, 'character': 11}}
+        found = params['position'] == {'line': 0, 'character': 3}
+        reply(message, result={'range': span, 'placeholder': 'old_name'} if found else None)
+    elif method == 'shutdown':
+        reply(message, result=None)
+    elif method == 'exit':
+        if MARKER:
+            open(MARKER, 'w').write('exit')
+        sys.exit(0)
+";
+
+/// Serializes access to the rust-analyzer binary override across tests.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+/// Points the adapter at the fake server for the lifetime of the guard.
+struct FakeServer {
+    scripts: tempfile::TempDir,
+    previous: Option<OsString>,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl FakeServer {
+    fn install() -> Self {
+        let lock = ENV_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let scripts = tempfile::tempdir().expect("script directory should be created");
+        let scripts_dir =
+            Dir::open_ambient_dir(scripts.path(), ambient_authority()).expect("script dir opens");
+        scripts_dir
+            .write("server.py", FAKE_SERVER)
+            .expect("fake server should be written");
+        let wrapper = format!(
+            "#!/bin/sh\nexec python3 '{}' '{}'\n",
+            scripts.path().join("server.py").display(),
+            marker_path(scripts.path()).display(),
+        );
+        scripts_dir
+            .write("rust-analyzer", wrapper)
+            .expect("wrapper should be written");
+        scripts_dir
+            .set_permissions(
+                "rust-analyzer",
+                cap_std::fs::Permissions::from_std(std::fs::Permissions::from_mode(0o755)),
+            )
+            .expect("wrapper should be executable");
+
+        let previous = std::env::var_os(BINARY_ENV);
+        unsafe { std::env::set_var(BINARY_ENV, scripts.path().join("rust-analyzer")) };
+        Self {
+            scripts,
+            previous,
+            _lock: lock,
+        }
+    }
+
+    /// Returns `true` once a server received the `exit` notification.
+    fn exited_cleanly(&self) -> bool { marker_path(self.scripts.path()).exists() }
+}
+
+impl Drop for FakeServer {
+    fn drop(&mut self) {
+        match self.previous.take() {
+            Some(value) => unsafe { std::env::set_var(BINARY_ENV, value) },
+            None => unsafe { std::env::remove_var(BINARY_ENV) },
+        }
+    }
+}
+
+fn marker_path(scripts: &Path) -> PathBuf { scripts.join("exit-marker") }
+
+fn payload(path: &str) -> FilePayload {
+    FilePayload::new(PathBuf::from(path), "fn old_name() -> i32 {\n    1\n}\n")
+}
+
+fn renamed(
+    adapter: &RustAnalyzerLspAdapter,
+    files: &[FilePayload],
+    new_name: &str,
+) -> Result<String, RustAnalyzerAdapterError> {
+    let mut changed = adapter.rename(files, ByteOffset::new(3), new_name)?;
+    let content = changed
+        .remove("src/main.rs")
+        .expect("target file should be reported");
+    Ok(content
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix("fn "))
+        .and_then(|line| line.split('(').next())
+        .expect("renamed function header")
+        .to_owned())
+}
+
+#[test]
+fn one_shot_adapter_starts_a_server_per_operation() {
+    let server = FakeServer::install();
+    let adapter = RustAnalyzerLspAdapter::new();
+    let files = [payload("src/main.rs"), payload("src/lib.rs")];
+
+    assert_eq!(
+        renamed(&adapter, &files, "a").expect("first rename"),
+        "a_1_1_1"
+    );
+    assert_eq!(
+        renamed(&adapter, &files, "b").expect("second rename"),
+        "b_1_1_1"
+    );
+    assert!(server.exited_cleanly());
+}
+
+#[test]
+fn persistent_adapter_reuses_server_and_synchronizes_documents() {
+    let _server = FakeServer::install();
+    let adapter = RustAnalyzerLspAdapter::persistent();
+
+    let both = [payload("src/main.rs"), payload("src/lib.rs")];
+    assert_eq!(
+        renamed(&adapter, &both, "a").expect("first rename"),
+        "a_1_1_2"
+    );
+    // The second request drops `src/lib.rs`, so it is closed, and re-sends
+    // `src/main.rs` as a new version of the already open document.
+    let main_only = [payload("src/main.rs")];
+    assert_eq!(
+        renamed(&adapter, &main_only, "b").expect("second rename"),
+        "b_2_2_1"
+    );
+}
+
+#[test]
+fn persistent_adapter_restarts_after_a_failed_operation() {
+    let _server = FakeServer::install();
+    let adapter = RustAnalyzerLspAdapter::persistent();
+    let files = [payload("src/main.rs")];
+
+    let failure = renamed(&adapter, &files, "boom");
+    assert!(
+        matches!(&failure, Err(RustAnalyzerAdapterError::EngineFailed { message }) if message.contains("rename exploded")),
+        "expected engine failure, got {failure:?}"
+    );
+    assert_eq!(
+        renamed(&adapter, &files, "a").expect("rename after restart"),
+        "a_1_1_1"
+    );
+}
+
+#[test]
+fn dropping_persistent_adapter_shuts_server_down() {
+    let server = FakeServer::install();
+    let adapter = RustAnalyzerLspAdapter::persistent();
+    renamed(&adapter, &[payload("src/main.rs")], "a").expect("rename");
+    assert!(!server.exited_cleanly());
+
+    drop(adapter);
+
+    assert!(server.exited_cleanly());
+}
+
+#[test]
+fn rename_retries_while_server_is_indexing() {
+    let _server = FakeServer::install();
+    let adapter = RustAnalyzerLspAdapter::new();
+
+    let result = renamed(&adapter, &[payload("src/main.rs")], "slow");
+
+    assert_eq!(result.expect("rename after indexing"), "slow_1_1_1");
+}
+
+#[test]
+fn empty_rename_reports_no_content_changes_after_short_retry() {
+    let _server = FakeServer::install();
+    let adapter = RustAnalyzerLspAdapter::new();
+    let mut arguments = rename_arguments();
+    arguments.insert(
+        String::from("new_name"),
+        serde_json::Value::String(String::from("unchanged")),
+    );
+
+    let started = Instant::now();
+    let failure = execute_request(&adapter, &request_with_args(arguments))
+        .expect_err("an empty rename should fail");
+
+    assert!(
+        failure.message().contains("produced no content changes"),
+        "expected no-op failure, got: {failure}"
+    );
+    assert_eq!(failure.reason_code(), Some(ReasonCode::SymbolNotFound));
+    assert!(started.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn prepare_rename_reports_symbol_range() {
+    let _server = FakeServer::install();
+    let adapter = RustAnalyzerLspAdapter::new();
+    let file = payload("src/main.rs");
+
+    let on_name = adapter
+        .prepare_rename(&file, ByteOffset::new(3))
+        .expect("prepare-rename at the function name");
+    let on_keyword = adapter
+        .prepare_rename(&file, ByteOffset::new(0))
+        .expect("prepare-rename at the keyword");
+
+    assert_eq!(on_name, Some(ByteOffset::new(3)..ByteOffset::new(11)));
+    assert_eq!(on_keyword, None);
+}
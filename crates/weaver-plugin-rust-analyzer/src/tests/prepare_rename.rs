@@ -0,0 +1,151 @@
+//! Tests for the `prepare-rename` operation.
+
+use std::{collections::HashMap, ops::Range, path::PathBuf};
+
+use rstest::rstest;
+use serde_json::{Value, json};
+use weaver_plugins::{
+    capability::ReasonCode,
+    protocol::{FilePayload, PluginOutput, PluginRequest},
+};
+
+use super::support::{MockAdapter, adapter_unused};
+use crate::{ByteOffset, RustAnalyzerAdapterError, execute_request};
+
+const SOURCE: &str = "fn old_name() -> i32 {\n    1\n}\n";
+
+fn prepare_arguments() -> HashMap<String, Value> {
+    HashMap::from([
+        (String::from("uri"), json!("file:///src/main.rs")),
+        (String::from("position"), json!(3)),
+    ])
+}
+
+fn prepare_request(files: Vec<FilePayload>, arguments: HashMap<String, Value>) -> PluginRequest {
+    PluginRequest::with_arguments("prepare-rename", files, arguments)
+}
+
+fn main_rs() -> FilePayload { FilePayload::new(PathBuf::from("src/main.rs"), SOURCE) }
+
+fn adapter_preparing(
+    result: Result<Option<Range<ByteOffset>>, RustAnalyzerAdapterError>,
+) -> MockAdapter {
+    let mut adapter = MockAdapter::new();
+    adapter
+        .expect_prepare_rename()
+        .withf(|file, offset| {
+            file.path() == PathBuf::from("src/main.rs").as_path() && *offset == ByteOffset::new(3)
+        })
+        .once()
+        .return_once(move |_file, _offset| result);
+    adapter
+}
+
+#[test]
+fn prepare_rename_reports_renameable_range() {
+    let adapter = adapter_preparing(Ok(Some(ByteOffset::new(3)..ByteOffset::new(11))));
+
+    let response = execute_request(
+        &adapter,
+        &prepare_request(vec![main_rs()], prepare_arguments()),
+    )
+    .expect("prepare-rename should succeed");
+
+    let PluginOutput::Analysis { data } = response.output() else {
+        panic!("expected analysis output, got {:?}", response.output());
+    };
+    assert_eq!(
+        data,
+        &json!({
+            "range": { "start": 3, "end": 11 },
+            "symbol": "old_name",
+        })
+    );
+}
+
+#[test]
+fn prepare_rename_without_symbol_reports_symbol_not_found() {
+    let adapter = adapter_preparing(Ok(None));
+
+    let error = execute_request(
+        &adapter,
+        &prepare_request(vec![main_rs()], prepare_arguments()),
+    )
+    .expect_err("missing symbol should fail");
+    assert!(
+        error
+            .message()
+            .contains("no renameable symbol at byte offset 3"),
+        "expected missing-symbol error, got: {error}"
+    );
+    assert_eq!(error.reason_code(), Some(ReasonCode::SymbolNotFound));
+}
+
+#[test]
+fn prepare_rename_surfaces_adapter_errors() {
+    let adapter = adapter_preparing(Err(RustAnalyzerAdapterError::EngineFailed {
+        message: String::from("server crashed"),
+    }));
+
+    let error = execute_request(
+        &adapter,
+        &prepare_request(vec![main_rs()], prepare_arguments()),
+    )
+    .expect_err("adapter error should fail");
+    assert!(
+        error.message().contains("server crashed"),
+        "expected adapter message, got: {error}"
+    );
+    assert_eq!(error.reason_code(), None);
+}
+
+#[test]
+fn prepare_rename_rejects_ranges_outside_the_file() {
+    let adapter = adapter_preparing(Ok(Some(ByteOffset::new(3)..ByteOffset::new(64))));
+
+    let error = execute_request(
+        &adapter,
+        &prepare_request(vec![main_rs()], prepare_arguments()),
+    )
+    .expect_err("out-of-bounds range should fail");
+    assert!(
+        error.message().contains("3..64"),
+        "expected range error, got: {error}"
+    );
+}
+
+#[rstest]
+#[case::no_files(Vec::new(), prepare_arguments(), "exactly one file payload, got 0")]
+#[case::two_files(
+    vec![main_rs(), FilePayload::new(PathBuf::from("src/lib.rs"), SOURCE)],
+    prepare_arguments(),
+    "exactly one file payload, got 2"
+)]
+#[case::missing_position(
+    vec![main_rs()],
+    HashMap::from([(String::from("uri"), json!("file:///src/main.rs"))]),
+    "prepare-rename operation requires 'position' argument"
+)]
+#[case::uri_mismatch(
+    vec![main_rs()],
+    HashMap::from([
+        (String::from("uri"), json!("file:///src/other.rs")),
+        (String::from("position"), json!(3)),
+    ]),
+    "does not match file payload"
+)]
+fn prepare_rename_rejects_invalid_requests(
+    #[case] files: Vec<FilePayload>,
+    #[case] arguments: HashMap<String, Value>,
+    #[case] needle: &str,
+) {
+    let adapter = adapter_unused();
+
+    let error = execute_request(&adapter, &prepare_request(files, arguments))
+        .expect_err("invalid request should fail before adapter invocation");
+    assert!(
+        error.message().contains(needle),
+        "expected error mentioning '{needle}', got: {error}"
+    );
+    assert_eq!(error.reason_code(), Some(ReasonCode::IncompletePayload));
+}
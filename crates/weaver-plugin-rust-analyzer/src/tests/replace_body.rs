@@ -0,0 +1,168 @@
+//! Tests for the `replace-body` operation.
+
+use std::{collections::HashMap, ops::Range, path::PathBuf};
+
+use rstest::rstest;
+use serde_json::{Value, json};
+use weaver_plugins::{
+    capability::ReasonCode,
+    protocol::{FilePayload, PluginOutput, PluginRequest},
+};
+
+use super::support::{MockAdapter, adapter_unused};
+use crate::{ByteOffset, RustAnalyzerAdapterError, execute_request};
+
+const SOURCE: &str = "/// Answers.\nfn answer() -> i32 {\n    1\n}\n";
+const REPLACED: &str = "/// Answers.\nfn answer() -> i32 {\n    42\n}\n";
+
+/// Byte range of `{\n    1\n}` in [`SOURCE`].
+fn body_range() -> Range<ByteOffset> { ByteOffset::new(32)..ByteOffset::new(41) }
+
+fn replace_arguments() -> HashMap<String, Value> {
+    HashMap::from([
+        (String::from("uri"), json!("file:///src/main.rs")),
+        (String::from("position"), json!(16)),
+        (String::from("new_body"), json!("{\n    42\n}")),
+    ])
+}
+
+fn replace_request(files: Vec<FilePayload>, arguments: HashMap<String, Value>) -> PluginRequest {
+    PluginRequest::with_arguments("replace-body", files, arguments)
+}
+
+fn main_rs() -> FilePayload { FilePayload::new(PathBuf::from("src/main.rs"), SOURCE) }
+
+fn adapter_locating(
+    result: Result<Option<Range<ByteOffset>>, RustAnalyzerAdapterError>,
+) -> MockAdapter {
+    let mut adapter = MockAdapter::new();
+    adapter
+        .expect_function_body()
+        .withf(|file, offset| {
+            file.path() == PathBuf::from("src/main.rs").as_path() && *offset == ByteOffset::new(16)
+        })
+        .once()
+        .return_once(move |_file, _offset| result);
+    adapter
+}
+
+#[test]
+fn replace_body_rewrites_only_the_body() {
+    let adapter = adapter_locating(Ok(Some(body_range())));
+
+    let response = execute_request(
+        &adapter,
+        &replace_request(vec![main_rs()], replace_arguments()),
+    )
+    .expect("replace-body should succeed");
+
+    let PluginOutput::Diff { content } = response.output() else {
+        panic!("expected diff output, got {:?}", response.output());
+    };
+    assert_eq!(
+        content,
+        &format!(
+            concat!(
+                "diff --git a/src/main.rs b/src/main.rs\n",
+                "<<<<<<< SEARCH\n{}",
+                "=======\n{}",
+                ">>>>>>> REPLACE\n",
+            ),
+            SOURCE, REPLACED,
+        )
+    );
+}
+
+#[test]
+fn replace_body_outside_a_function_reports_symbol_not_found() {
+    let adapter = adapter_locating(Ok(None));
+
+    let error = execute_request(
+        &adapter,
+        &replace_request(vec![main_rs()], replace_arguments()),
+    )
+    .expect_err("non-function target should fail");
+    assert!(
+        error
+            .message()
+            .contains("byte offset 16 is not inside a function with a body"),
+        "expected non-function diagnostic, got: {error}"
+    );
+    assert_eq!(error.reason_code(), Some(ReasonCode::SymbolNotFound));
+}
+
+#[test]
+fn replace_body_with_identical_body_fails() {
+    let adapter = adapter_locating(Ok(Some(body_range())));
+    let mut arguments = replace_arguments();
+    arguments.insert(String::from("new_body"), json!("{\n    1\n}"));
+
+    let error = execute_request(&adapter, &replace_request(vec![main_rs()], arguments))
+        .expect_err("unchanged body should fail");
+    assert!(
+        error.message().contains("no content changes"),
+        "expected no-change error, got: {error}"
+    );
+}
+
+#[test]
+fn replace_body_surfaces_adapter_errors() {
+    let adapter = adapter_locating(Err(RustAnalyzerAdapterError::EngineFailed {
+        message: String::from("server crashed"),
+    }));
+
+    let error = execute_request(
+        &adapter,
+        &replace_request(vec![main_rs()], replace_arguments()),
+    )
+    .expect_err("adapter error should fail");
+    assert!(
+        error.message().contains("server crashed"),
+        "expected adapter message, got: {error}"
+    );
+    assert_eq!(error.reason_code(), None);
+}
+
+#[rstest]
+#[case::no_files(Vec::new(), replace_arguments(), "exactly one file payload, got 0")]
+#[case::missing_new_body(
+    vec![main_rs()],
+    HashMap::from([
+        (String::from("uri"), json!("file:///src/main.rs")),
+        (String::from("position"), json!(16)),
+    ]),
+    "replace-body operation requires 'new_body' argument"
+)]
+#[case::unbraced_new_body(
+    vec![main_rs()],
+    HashMap::from([
+        (String::from("uri"), json!("file:///src/main.rs")),
+        (String::from("position"), json!(16)),
+        (String::from("new_body"), json!("42")),
+    ]),
+    "new_body argument must be a block enclosed in braces"
+)]
+#[case::uri_mismatch(
+    vec![main_rs()],
+    HashMap::from([
+        (String::from("uri"), json!("file:///src/other.rs")),
+        (String::from("position"), json!(16)),
+        (String::from("new_body"), json!("{ 42 }")),
+    ]),
+    "does not match file payload"
+)]
+fn replace_body_rejects_invalid_requests(
+    #[case] files: Vec<FilePayload>,
+    #[case] arguments: HashMap<String, Value>,
+    #[case] needle: &str,
+) {
+    let adapter = adapter_unused();
+
+    let error = execute_request(&adapter, &replace_request(files, arguments))
+        .expect_err("invalid request should fail before adapter invocation");
+    assert!(
+        error.message().contains(needle),
+        "expected error mentioning '{needle}', got: {error}"
+    );
+    assert_eq!(error.reason_code(), Some(ReasonCode::IncompletePayload));
+}
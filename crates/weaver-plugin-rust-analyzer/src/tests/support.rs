@@ -1,31 +1,43 @@
 //! Shared test helpers for rust-analyzer plugin unit tests.
 
-use std::{collections::HashMap, path::PathBuf};
+use std::{collections::HashMap, ops::Range, path::PathBuf};
 
 use mockall::mock;
 use url::Url;
 use weaver_plugins::protocol::{FilePayload, PluginRequest};
 
-use crate::{ByteOffset, RustAnalyzerAdapter, RustAnalyzerAdapterError};
+use crate::{ByteOffset, ChangedFiles, RustAnalyzerAdapter, RustAnalyzerAdapterError};
 
 mock! {
     pub(crate) Adapter {}
     impl RustAnalyzerAdapter for Adapter {
         fn rename(
             &self,
-            file: &FilePayload,
+            files: &[FilePayload],
             offset: ByteOffset,
             new_name: &str,
-        ) -> Result<String, RustAnalyzerAdapterError>;
+        ) -> Result<ChangedFiles, RustAnalyzerAdapterError>;
+        fn organize_imports(&self, file: &FilePayload) -> Result<String, RustAnalyzerAdapterError>;
+        fn prepare_rename(
+            &self,
+            file: &FilePayload,
+            offset: ByteOffset,
+        ) -> Result<Option<Range<ByteOffset>>, RustAnalyzerAdapterError>;
+        fn function_body(
+            &self,
+            file: &FilePayload,
+            offset: ByteOffset,
+        ) -> Result<Option<Range<ByteOffset>>, RustAnalyzerAdapterError>;
     }
 }
 
-/// Builds a `MockAdapter` that expects a single rename call returning `result`.
+/// Builds a `MockAdapter` that expects a single rename call returning `result`
+/// as the new content of `src/main.rs`.
 pub(crate) fn adapter_returning(result: Result<String, RustAnalyzerAdapterError>) -> MockAdapter {
     adapter_returning_with_path(result, None)
 }
 
-/// Builds a `MockAdapter` that can also assert the forwarded payload path.
+/// Builds a `MockAdapter` that can also assert the forwarded target path.
 pub(crate) fn adapter_returning_with_path(
     result: Result<String, RustAnalyzerAdapterError>,
     expected_payload_path: Option<&str>,
@@ -35,17 +47,30 @@ pub(crate) fn adapter_returning_with_path(
     adapter
         .expect_rename()
         .once()
-        .return_once(move |file, offset, new_name| {
+        .return_once(move |files, offset, new_name| {
             if let Some(path) = &expected_path_string {
-                assert_eq!(file.path(), PathBuf::from(path).as_path());
+                let target = files.first().map(FilePayload::path);
+                assert_eq!(target, Some(PathBuf::from(path).as_path()));
             }
             assert_eq!(offset, ByteOffset::new(3));
             assert_eq!(new_name, "new_name");
-            result
+            let key = files
+                .first()
+                .and_then(|file| file.path().to_str())
+                .map_or_else(String::new, str::to_owned);
+            result.map(|content| changed_files(&[(&key, &content)]))
         });
     adapter
 }
 
+/// Builds a `ChangedFiles` map from `(path, content)` pairs.
+pub(crate) fn changed_files(entries: &[(&str, &str)]) -> ChangedFiles {
+    entries
+        .iter()
+        .map(|(path, content)| (String::from(*path), String::from(*content)))
+        .collect()
+}
+
 /// Builds a `MockAdapter` where rename is never expected.
 pub(crate) fn adapter_unused() -> MockAdapter { MockAdapter::new() }
 
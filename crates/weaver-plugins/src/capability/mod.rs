@@ -75,6 +75,28 @@ impl CapabilityId {
             Self::ExtractPredicate => "extract-predicate",
         }
     }
+
+    /// Returns the contract that validates requests and responses for this
+    /// capability, or `None` when no contract has been defined yet.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weaver_plugins::capability::CapabilityId;
+    ///
+    /// assert!(CapabilityId::RenameSymbol.contract().is_some());
+    /// assert!(CapabilityId::ExtractMethod.contract().is_none());
+    /// ```
+    #[must_use]
+    pub const fn contract(self) -> Option<&'static dyn CapabilityContract> {
+        match self {
+            Self::RenameSymbol => Some(&RenameSymbolContract),
+            Self::ExtricateSymbol
+            | Self::ExtractMethod
+            | Self::ReplaceBody
+            | Self::ExtractPredicate => None,
+        }
+    }
 }
 
 impl std::fmt::Display for CapabilityId {
@@ -24,6 +24,7 @@ use serde::{Deserialize, Serialize};
 /// assert_eq!(code.as_str(), "symbol_not_found");
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum ReasonCode {
     /// The symbol at the requested position could not be resolved.
@@ -97,17 +97,20 @@ fn extract_non_empty_string_field(
     let value = args.get(field).ok_or_else(|| PluginError::InvalidOutput {
         name: String::from("rename-symbol"),
         message: format!("rename-symbol contract requires '{field}' argument"),
+        stderr: String::new(),
     })?;
 
     let s = value.as_str().ok_or_else(|| PluginError::InvalidOutput {
         name: String::from("rename-symbol"),
         message: format!("rename-symbol contract requires '{field}' to be a string"),
+        stderr: String::new(),
     })?;
 
     if s.trim().is_empty() {
         return Err(PluginError::InvalidOutput {
             name: String::from("rename-symbol"),
             message: format!("rename-symbol contract requires '{field}' to be non-empty"),
+            stderr: String::new(),
         });
     }
 
@@ -130,6 +133,7 @@ fn validate_success_output(response: &PluginResponse) -> Result<(), PluginError>
                 "rename-symbol contract requires successful responses to contain diff output, got \
                  {other:?}",
             ),
+            stderr: String::new(),
         }),
     }
 }
@@ -161,6 +165,7 @@ impl CapabilityContract for RenameSymbolContract {
                     "rename-symbol contract expects operation '{expected}', got '{}'",
                     request.operation(),
                 ),
+                stderr: String::new(),
             });
         }
         RenameSymbolRequest::extract(request).map(|_| ())
@@ -54,6 +54,15 @@ fn capability_id_serde_round_trip(#[case] json: &str, #[case] expected: Capabili
     assert_eq!(back, json);
 }
 
+#[test]
+fn capability_id_contract_lookup() {
+    let contract = CapabilityId::RenameSymbol
+        .contract()
+        .expect("rename-symbol has a contract");
+    assert_eq!(contract.capability_id(), CapabilityId::RenameSymbol);
+    assert!(CapabilityId::ExtractPredicate.contract().is_none());
+}
+
 // ---------------------------------------------------------------------------
 // ContractVersion
 // ---------------------------------------------------------------------------
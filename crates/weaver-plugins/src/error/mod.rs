@@ -8,6 +8,8 @@ use std::{path::PathBuf, sync::Arc};
 
 use thiserror::Error;
 
+use crate::manifest::PluginKind;
+
 /// Errors arising from plugin operations.
 #[derive(Debug, Error)]
 pub enum PluginError {
@@ -42,12 +44,51 @@ pub enum PluginError {
     },
 
     /// The plugin exited with a non-zero status code.
-    #[error("plugin '{name}' exited with non-zero status {status}")]
+    #[error(
+        "plugin '{name}' exited with non-zero status {status}{}",
+        stderr_suffix(stderr)
+    )]
     NonZeroExit {
         /// Plugin name.
         name: String,
         /// Process exit status.
         status: i32,
+        /// Tail of the plugin's stderr, empty when it wrote nothing.
+        stderr: String,
+    },
+
+    /// The plugin responded with an incompatible protocol version.
+    #[error(
+        "plugin '{name}' speaks protocol version {found}, but the broker requires version \
+         {expected}"
+    )]
+    ProtocolVersion {
+        /// Plugin name.
+        name: String,
+        /// Protocol version the broker speaks.
+        expected: u32,
+        /// Protocol version declared in the plugin response.
+        found: u32,
+    },
+
+    /// The plugin returned output that its kind is not allowed to produce.
+    #[error("{kind} plugin '{name}' returned {output} output, which {kind}s cannot produce")]
+    OutputKindMismatch {
+        /// Plugin name.
+        name: String,
+        /// Kind declared in the plugin manifest.
+        kind: PluginKind,
+        /// Output variant returned by the plugin.
+        output: &'static str,
+    },
+
+    /// The plugin answered a health-check ping with a failure response.
+    #[error("plugin '{name}' failed its health check: {message}")]
+    HealthCheck {
+        /// Plugin name.
+        name: String,
+        /// Diagnostics reported by the plugin.
+        message: String,
     },
 
     /// The plugin request could not be serialized to JSON.
@@ -65,12 +106,17 @@ pub enum PluginError {
     },
 
     /// The plugin produced output that does not conform to the protocol.
-    #[error("plugin '{name}' wrote invalid output: {message}")]
+    #[error(
+        "plugin '{name}' wrote invalid output: {message}{}",
+        stderr_suffix(stderr)
+    )]
     InvalidOutput {
         /// Plugin name.
         name: String,
         /// Description of the protocol violation.
         message: String,
+        /// Tail of the plugin's stderr, empty when unavailable or blank.
+        stderr: String,
     },
 
     /// An I/O error occurred while communicating with the plugin process.
@@ -99,6 +145,16 @@ pub enum PluginError {
         message: String,
     },
 
+    /// A manifest file or manifests directory could not be read.
+    #[error("failed to read manifest path '{}': {source}", path.display())]
+    ManifestIo {
+        /// Path that could not be read.
+        path: PathBuf,
+        /// Underlying I/O error.
+        #[source]
+        source: Arc<std::io::Error>,
+    },
+
     /// The plugin executable was not found on the filesystem.
     #[error("plugin '{name}' executable not found: {path}")]
     ExecutableNotFound {
@@ -109,5 +165,14 @@ pub enum PluginError {
     },
 }
 
+/// Formats captured stderr for appending to an error message.
+fn stderr_suffix(stderr: &str) -> String {
+    if stderr.is_empty() {
+        String::new()
+    } else {
+        format!("; stderr: {stderr}")
+    }
+}
+
 #[cfg(test)]
 mod tests;
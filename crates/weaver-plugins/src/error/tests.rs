@@ -36,6 +36,7 @@ fn not_found_error_message_includes_name() {
     PluginError::InvalidOutput {
         name: "noisy".into(),
         message: "plugin produced no output on stdout".into(),
+        stderr: String::new(),
     },
     "noisy",
     "no output on stdout"
@@ -69,6 +70,7 @@ fn error_message_includes_name_and_detail(
     PluginError::NonZeroExit {
         name: "buggy".into(),
         status: 127,
+        stderr: String::new(),
     },
     "127"
 )]
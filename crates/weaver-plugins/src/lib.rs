@@ -42,7 +42,7 @@
 //! let mut registry = PluginRegistry::new();
 //! registry.register(manifest).expect("registration succeeds");
 //!
-//! let runner = PluginRunner::new(registry, SandboxExecutor);
+//! let runner = PluginRunner::new(registry, SandboxExecutor::default());
 //! // runner.execute("rope", &request) would spawn the plugin in a sandbox.
 //! ```
 
@@ -95,13 +95,16 @@ pub use self::{
     error::PluginError,
     manifest::{PluginKind, PluginManifest, PluginMetadata},
     protocol::{
+        ContentReference,
         DiagnosticSeverity,
         FilePayload,
+        PING_OPERATION,
+        PROTOCOL_VERSION,
         PluginDiagnostic,
         PluginOutput,
         PluginRequest,
         PluginResponse,
     },
-    registry::PluginRegistry,
+    registry::{ManifestLoadFailure, ManifestLoadReport, PluginRegistry},
     runner::{PluginExecutor, PluginRunner},
 };
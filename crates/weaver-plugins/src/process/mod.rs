@@ -0,0 +1,331 @@
+//! Process-based plugin execution using the Weaver sandbox.
+//!
+//! [`SandboxExecutor`] implements the [`PluginExecutor`] trait by spawning a
+//! sandboxed child process, writing the request to stdin as a single JSONL
+//! line, reading the response from stdout, and enforcing the manifest timeout
+//! through the sandbox's time limit. This module is the primary integration
+//! point with the `weaver-sandbox` crate.
+
+mod spill;
+
+use std::{
+    io::{self, Read, Write},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+use tracing::{debug, warn};
+use weaver_sandbox::{SandboxError, SandboxProfile, process::Stdio};
+
+use crate::{
+    error::PluginError,
+    manifest::PluginManifest,
+    protocol::{PluginRequest, PluginResponse},
+    runner::PluginExecutor,
+};
+
+/// Tracing target for plugin process operations.
+const PLUGIN_TARGET: &str = "weaver_plugins::process";
+
+/// Maximum number of stderr bytes attached to a plugin error.
+const STDERR_TAIL_BYTES: usize = 2048;
+
+/// Executes plugins by spawning sandboxed child processes.
+///
+/// The executor builds a [`SandboxProfile`] from the manifest, spawns the
+/// plugin command with stdin and stdout piped, writes the JSONL request,
+/// reads the JSONL response, and kills the plugin if it outlives the
+/// manifest's `timeout_secs`.
+///
+/// File content is sent inline by default. With
+/// [`SandboxExecutor::with_inline_limit`], files larger than the limit are
+/// written to a temporary directory the plugin may read and passed by
+/// reference instead.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::path::PathBuf;
+///
+/// use weaver_plugins::{
+///     PluginKind,
+///     PluginManifest,
+///     PluginMetadata,
+///     PluginRequest,
+///     process::SandboxExecutor,
+///     runner::PluginExecutor,
+/// };
+///
+/// let executor = SandboxExecutor::default().with_inline_limit(1024 * 1024);
+/// let meta = PluginMetadata::new("example", "0.1.0", PluginKind::Actuator);
+/// let manifest = PluginManifest::new(
+///     meta,
+///     vec!["python".into()],
+///     PathBuf::from("/usr/bin/example-plugin"),
+/// );
+/// let request = PluginRequest::new("rename", vec![]);
+/// // let response = executor.execute(&manifest, &request);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SandboxExecutor {
+    inline_limit: Option<u64>,
+}
+
+impl SandboxExecutor {
+    /// Passes files larger than `bytes` to plugins by reference.
+    #[must_use]
+    pub const fn with_inline_limit(mut self, bytes: u64) -> Self {
+        self.inline_limit = Some(bytes);
+        self
+    }
+}
+
+impl PluginExecutor for SandboxExecutor {
+    fn execute(
+        &self,
+        manifest: &PluginManifest,
+        request: &PluginRequest,
+    ) -> Result<PluginResponse, PluginError> {
+        let spill = match self.inline_limit {
+            Some(limit) => spill::spill_large_files(manifest.name(), request, limit)?,
+            None => None,
+        };
+        let Some(spilled) = spill else {
+            return execute_in_sandbox(manifest, request, build_profile(manifest));
+        };
+        let profile = build_profile(manifest).allow_read_path(spilled.dir());
+        execute_in_sandbox(manifest, spilled.request(), profile)
+    }
+}
+
+/// Builds the sandbox profile for a plugin.
+fn build_profile(manifest: &PluginManifest) -> SandboxProfile {
+    SandboxProfile::new().allow_executable(manifest.executable())
+}
+
+/// Spawns the plugin process, writes the request, reads the response.
+///
+/// The profile carries the manifest timeout as its time limit, so the
+/// sandbox kills and reaps a plugin that outlives its budget while the
+/// response is still being awaited. The request is written from a scoped
+/// thread so a plugin that never reads stdin cannot block the deadline.
+fn execute_in_sandbox(
+    manifest: &PluginManifest,
+    request: &PluginRequest,
+    profile: SandboxProfile,
+) -> Result<PluginResponse, PluginError> {
+    let name = manifest.name();
+    let timeout_secs = manifest.timeout_secs();
+    let sandbox =
+        weaver_sandbox::Sandbox::new(profile.with_time_limit(Duration::from_secs(timeout_secs)));
+
+    let mut command = weaver_sandbox::SandboxCommand::new(manifest.executable());
+    command.args(manifest.args());
+    command.stdin(Stdio::piped());
+    command.stdout(Stdio::piped());
+    command.stderr(Stdio::piped());
+
+    debug!(
+        target: PLUGIN_TARGET,
+        plugin = name,
+        executable = %manifest.executable().display(),
+        timeout_secs,
+        "spawning plugin process"
+    );
+
+    let mut child = sandbox.spawn(command).map_err(|err| PluginError::Sandbox {
+        name: name.to_owned(),
+        message: err.to_string(),
+    })?;
+
+    let stdin = child.stdin.take().ok_or_else(|| PluginError::SpawnFailed {
+        name: name.to_owned(),
+        message: String::from("failed to capture stdin"),
+        source: None,
+    })?;
+
+    let (written, waited) = thread::scope(|scope| {
+        let writer = scope.spawn(|| write_request(name, stdin, request));
+        let waited = child.wait_with_output();
+        let written = writer.join().unwrap_or_else(|_| {
+            Err(PluginError::Io {
+                name: name.to_owned(),
+                source: Arc::new(io::Error::other("request writer thread panicked")),
+            })
+        });
+        (written, waited)
+    });
+    let output = waited.map_err(|error| wait_error(name, timeout_secs, error))?;
+    written?;
+
+    let stderr = capture_stderr(name, Some(output.stderr.as_slice()));
+    let Some(response_line) = first_line(name, &output.stdout) else {
+        return Err(PluginError::InvalidOutput {
+            name: name.to_owned(),
+            message: String::from("plugin produced no output on stdout"),
+            stderr,
+        });
+    };
+    handle_exited(name, output.status, &stderr)?;
+    parse_response(name, &response_line)
+}
+
+/// Maps a failed wait into a plugin error, reporting deadline expiry as
+/// [`PluginError::Timeout`].
+fn wait_error(name: &str, timeout_secs: u64, error: SandboxError) -> PluginError {
+    match error {
+        SandboxError::TimedOut { .. } => {
+            warn!(
+                target: PLUGIN_TARGET,
+                plugin = name,
+                timeout_secs,
+                "plugin timed out and was killed"
+            );
+            PluginError::Timeout {
+                name: name.to_owned(),
+                timeout_secs,
+                message: String::from("terminated timed-out process"),
+            }
+        }
+        other => PluginError::Sandbox {
+            name: name.to_owned(),
+            message: other.to_string(),
+        },
+    }
+}
+
+/// Writes the serialized request to the plugin's stdin and closes it.
+fn write_request(
+    name: &str,
+    mut stdin: impl Write,
+    request: &PluginRequest,
+) -> Result<(), PluginError> {
+    let json = serde_json::to_string(request).map_err(PluginError::SerializeRequest)?;
+
+    debug!(
+        target: PLUGIN_TARGET,
+        plugin = name,
+        request_bytes = json.len(),
+        "writing request to plugin stdin"
+    );
+
+    stdin
+        .write_all(json.as_bytes())
+        .map_err(|err| PluginError::Io {
+            name: name.to_owned(),
+            source: Arc::new(err),
+        })?;
+
+    stdin.write_all(b"\n").map_err(|err| PluginError::Io {
+        name: name.to_owned(),
+        source: Arc::new(err),
+    })?;
+
+    stdin.flush().map_err(|err| PluginError::Io {
+        name: name.to_owned(),
+        source: Arc::new(err),
+    })?;
+
+    // Stdin is dropped here, closing the pipe to signal no more input.
+    Ok(())
+}
+
+/// Returns the first JSONL line of the plugin's stdout.
+///
+/// Returns `None` when the plugin closed stdout without writing anything.
+fn first_line(name: &str, stdout: &[u8]) -> Option<String> {
+    debug!(
+        target: PLUGIN_TARGET,
+        plugin = name,
+        bytes_read = stdout.len(),
+        "read response from plugin stdout"
+    );
+    if stdout.is_empty() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(stdout);
+    Some(
+        text.split_inclusive('\n')
+            .next()
+            .unwrap_or_default()
+            .to_owned(),
+    )
+}
+
+/// Drains stderr to avoid blocking the child on a full pipe buffer.
+///
+/// The full output is logged for diagnostics and its trimmed tail, capped at
+/// [`STDERR_TAIL_BYTES`], is returned for attaching to plugin errors.
+fn capture_stderr<R: Read>(name: &str, stderr_handle: Option<R>) -> String {
+    let Some(mut reader) = stderr_handle else {
+        return String::new();
+    };
+    let mut bytes = Vec::new();
+    if let Err(error) = reader.read_to_end(&mut bytes) {
+        debug!(
+            target: PLUGIN_TARGET,
+            plugin = name,
+            %error,
+            "failed to read plugin stderr"
+        );
+    }
+    let buffer = String::from_utf8_lossy(&bytes);
+    let trimmed = buffer.trim();
+    if !trimmed.is_empty() {
+        debug!(
+            target: PLUGIN_TARGET,
+            plugin = name,
+            stderr = %trimmed,
+            "plugin stderr output"
+        );
+    }
+    stderr_tail(trimmed)
+}
+
+/// Keeps at most [`STDERR_TAIL_BYTES`] from the end of `text`, marking any
+/// truncation with a leading ellipsis.
+fn stderr_tail(text: &str) -> String {
+    if text.len() <= STDERR_TAIL_BYTES {
+        return text.to_owned();
+    }
+    let mut start = text.len() - STDERR_TAIL_BYTES;
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    let tail = text.get(start..).unwrap_or_default();
+    format!("…{tail}")
+}
+
+/// Handles a child process that has exited.
+fn handle_exited(
+    name: &str,
+    status: std::process::ExitStatus,
+    stderr: &str,
+) -> Result<(), PluginError> {
+    debug!(
+        target: PLUGIN_TARGET,
+        plugin = name,
+        ?status,
+        "plugin process exited"
+    );
+    if status.success() {
+        return Ok(());
+    }
+    Err(PluginError::NonZeroExit {
+        name: name.to_owned(),
+        status: status.code().unwrap_or(-1),
+        stderr: stderr.to_owned(),
+    })
+}
+
+/// Parses a JSONL response line into a [`PluginResponse`].
+fn parse_response(name: &str, line: &str) -> Result<PluginResponse, PluginError> {
+    serde_json::from_str(line.trim()).map_err(|err| PluginError::DeserializeResponse {
+        message: format!("plugin '{name}' produced invalid JSON: {err}"),
+        source: Some(err),
+    })
+}
+
+#[cfg(test)]
+mod tests;
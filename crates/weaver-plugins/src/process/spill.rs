@@ -0,0 +1,91 @@
+//! By-reference transfer of large file payloads.
+//!
+//! Embedding a very large file in the single-line JSONL request is costly to
+//! serialise and parse. When the executor has an inline limit, every payload
+//! over it is written to a private temporary directory that the sandbox may
+//! read, and the request carries a [`ContentReference`] instead. The directory
+//! is removed when the [`SpilledRequest`] is dropped, whether or not the
+//! plugin succeeded.
+
+use std::{fs, io, path::Path, sync::Arc};
+
+use tempfile::TempDir;
+use tracing::debug;
+
+use super::PLUGIN_TARGET;
+use crate::{
+    error::PluginError,
+    protocol::{ContentReference, FilePayload, PluginRequest},
+};
+
+/// A request whose large payloads were moved into a temporary directory.
+pub(super) struct SpilledRequest {
+    dir: TempDir,
+    request: PluginRequest,
+}
+
+impl SpilledRequest {
+    /// Returns the directory holding the spilled content.
+    pub(super) fn dir(&self) -> &Path { self.dir.path() }
+
+    /// Returns the request with large payloads replaced by references.
+    pub(super) const fn request(&self) -> &PluginRequest { &self.request }
+}
+
+/// Moves payloads larger than `limit` bytes out of `request`.
+///
+/// Returns `None` when no payload exceeds the limit, so small requests are
+/// sent inline without touching the filesystem.
+///
+/// # Errors
+///
+/// Returns [`PluginError::Io`] if the temporary directory or a content file
+/// cannot be written.
+pub(super) fn spill_large_files(
+    name: &str,
+    request: &PluginRequest,
+    limit: u64,
+) -> Result<Option<SpilledRequest>, PluginError> {
+    let spill = write_spill(request, limit).map_err(|error| PluginError::Io {
+        name: name.to_owned(),
+        source: Arc::new(error),
+    })?;
+    if let Some(spilled) = &spill {
+        debug!(
+            target: PLUGIN_TARGET,
+            plugin = name,
+            dir = %spilled.dir().display(),
+            "passing large files by reference"
+        );
+    }
+    Ok(spill)
+}
+
+fn write_spill(request: &PluginRequest, limit: u64) -> io::Result<Option<SpilledRequest>> {
+    let exceeds = |payload: &FilePayload| content_length(payload) > limit;
+    if !request.files().iter().any(exceeds) {
+        return Ok(None);
+    }
+
+    let dir = tempfile::Builder::new()
+        .prefix("weaver-plugin-")
+        .tempdir()?;
+    let mut spilled = request.clone();
+    for (index, payload) in spilled.files_mut().iter_mut().enumerate() {
+        if !exceeds(payload) {
+            continue;
+        }
+        let stored = dir.path().join(index.to_string());
+        fs::write(&stored, payload.content())?;
+        let reference = ContentReference::new(stored, content_length(payload));
+        *payload = FilePayload::by_reference(payload.path().to_path_buf(), reference);
+    }
+    Ok(Some(SpilledRequest {
+        dir,
+        request: spilled,
+    }))
+}
+
+fn content_length(payload: &FilePayload) -> u64 {
+    u64::try_from(payload.content().len()).unwrap_or(u64::MAX)
+}
@@ -0,0 +1,117 @@
+//! Unit tests for plugin process output handling.
+//!
+//! Spawning through the sandbox requires a single-threaded caller, which the
+//! test harness cannot provide, so these tests drive a plain child process
+//! through the same stderr capture and exit handling the executor uses.
+
+use std::process::{Command, Stdio};
+
+use rstest::rstest;
+
+use super::*;
+
+/// Runs a shell stub that reports progress and a failure on stderr before
+/// exiting with status 3.
+fn run_failing_stub() -> (std::process::ExitStatus, String) {
+    let mut child = Command::new("/bin/sh")
+        .args([
+            "-c",
+            "echo 'loading workspace' >&2; echo 'fatal: missing pyproject.toml' >&2; exit 3",
+        ])
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("spawn stub plugin");
+    let stderr = capture_stderr("stub", child.stderr.take());
+    let status = child.wait().expect("wait for stub plugin");
+    (status, stderr)
+}
+
+#[test]
+fn non_zero_exit_carries_plugin_stderr() {
+    let (status, stderr) = run_failing_stub();
+
+    let error = handle_exited("stub", status, &stderr).expect_err("stub exits non-zero");
+
+    match &error {
+        PluginError::NonZeroExit {
+            status: code,
+            stderr: tail,
+            ..
+        } => {
+            assert_eq!(*code, 3);
+            assert_eq!(tail, "loading workspace\nfatal: missing pyproject.toml");
+        }
+        other => panic!("expected NonZeroExit, got: {other}"),
+    }
+    let message = error.to_string();
+    assert!(
+        message.ends_with("; stderr: loading workspace\nfatal: missing pyproject.toml"),
+        "expected stderr in message: {message}"
+    );
+}
+
+#[test]
+fn missing_stderr_pipe_yields_empty_tail() {
+    assert_eq!(capture_stderr::<&[u8]>("stub", None), "");
+}
+
+#[rstest]
+#[case::short("only line", "only line")]
+#[case::blank_edges("\n  spaced out  \n", "spaced out")]
+fn short_stderr_is_kept_whole(#[case] raw: &str, #[case] expected: &str) {
+    assert_eq!(capture_stderr("stub", Some(raw.as_bytes())), expected);
+}
+
+#[test]
+fn long_stderr_keeps_only_the_tail() {
+    let noise = "é".repeat(STDERR_TAIL_BYTES);
+    let text = format!("{noise}\nfinal error");
+
+    let tail = stderr_tail(&text);
+
+    assert!(tail.starts_with('…'), "missing truncation marker: {tail}");
+    assert!(tail.ends_with("final error"));
+    assert!(tail.len() <= STDERR_TAIL_BYTES + '…'.len_utf8());
+}
+
+fn two_file_request() -> crate::protocol::PluginRequest {
+    crate::protocol::PluginRequest::new(
+        "rename",
+        vec![
+            crate::protocol::FilePayload::new("src/small.py".into(), "pass\n"),
+            crate::protocol::FilePayload::new("src/large.py".into(), "x = 1\n".repeat(64)),
+        ],
+    )
+}
+
+#[test]
+fn files_within_the_inline_limit_stay_inline() {
+    let spill = spill::spill_large_files("stub", &two_file_request(), 4096).expect("spill");
+
+    assert!(spill.is_none(), "no file exceeds the limit");
+}
+
+#[test]
+fn files_over_the_inline_limit_are_passed_by_reference() {
+    let original = two_file_request();
+    let spilled = spill::spill_large_files("stub", &original, 64)
+        .expect("spill")
+        .expect("large file exceeds the limit");
+
+    let [small, large] = spilled.request().files() else {
+        panic!("expected two files");
+    };
+    assert!(small.content_ref().is_none());
+    let reference = large.content_ref().expect("large file is referenced");
+    assert!(reference.path().starts_with(spilled.dir()));
+    assert_eq!(reference.length(), 384);
+
+    let mut loaded = spilled.request().clone();
+    loaded.load_file_references().expect("load references");
+    assert_eq!(loaded, original);
+
+    let dir = spilled.dir().to_path_buf();
+    drop(spilled);
+    assert!(!dir.exists(), "spill directory should be removed on drop");
+}
@@ -4,13 +4,45 @@
 //! one [`PluginRequest`] line to the plugin's stdin and closes it. The plugin
 //! writes one [`PluginResponse`] line to stdout and exits. Plugin stderr is
 //! captured for diagnostic logging but is not part of the protocol.
+//! Diagnostics meant for the operator travel in the response instead: a
+//! successful response may carry `warning` and `info` diagnostics alongside
+//! its output, which the broker forwards without failing the operation.
+//!
+//! Both messages carry a `protocol_version`. The version is bumped only for
+//! breaking schema changes; additive fields keep the current version, so
+//! peers must ignore fields they do not recognise. Messages that omit the
+//! field are treated as version 1, the version that predates it.
+
+mod patch;
+mod payload;
+#[cfg(feature = "schema")]
+mod schema;
 
-use std::{collections::HashMap, path::PathBuf};
+use std::{collections::HashMap, fmt, path::PathBuf};
 
+pub use patch::{UnsuppliedFileError, build_multi_file_patch, search_replace_patch};
+pub use payload::{ContentReference, FilePayload};
+#[cfg(feature = "schema")]
+pub use schema::json_schema;
 use serde::{Deserialize, Serialize};
 
 use crate::capability::ReasonCode;
 
+/// Protocol version spoken by this crate.
+///
+/// The runner rejects responses that declare a different version with
+/// [`PluginError::ProtocolVersion`](crate::PluginError::ProtocolVersion).
+pub const PROTOCOL_VERSION: u32 = 1;
+
+const fn default_protocol_version() -> u32 { 1 }
+
+/// Operation name of the health-check request every plugin must answer.
+///
+/// Plugins reply to a ping with a successful [`PluginOutput::Empty`] response
+/// without inspecting files or arguments, so the broker can confirm that an
+/// executable starts and speaks the protocol before sending real work.
+pub const PING_OPERATION: &str = "ping";
+
 /// Request sent from the `weaverd` broker to a plugin on stdin.
 ///
 /// Serialized as a single JSONL line terminated by a newline character.
@@ -33,8 +65,12 @@ use crate::capability::ReasonCode;
 /// assert_eq!(request.files().len(), 1);
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PluginRequest {
+    #[serde(default = "default_protocol_version")]
+    protocol_version: u32,
     operation: String,
+    #[serde(default)]
     files: Vec<FilePayload>,
     #[serde(default)]
     arguments: HashMap<String, serde_json::Value>,
@@ -45,12 +81,17 @@ impl PluginRequest {
     #[must_use]
     pub fn new(operation: impl Into<String>, files: Vec<FilePayload>) -> Self {
         Self {
+            protocol_version: PROTOCOL_VERSION,
             operation: operation.into(),
             files,
             arguments: HashMap::new(),
         }
     }
 
+    /// Creates a [`PING_OPERATION`] health-check request.
+    #[must_use]
+    pub fn ping() -> Self { Self::new(PING_OPERATION, Vec::new()) }
+
     /// Creates a request with arguments.
     #[must_use]
     pub fn with_arguments(
@@ -59,12 +100,17 @@ impl PluginRequest {
         arguments: HashMap<String, serde_json::Value>,
     ) -> Self {
         Self {
+            protocol_version: PROTOCOL_VERSION,
             operation: operation.into(),
             files,
             arguments,
         }
     }
 
+    /// Returns the protocol version the request was written for.
+    #[must_use]
+    pub const fn protocol_version(&self) -> u32 { self.protocol_version }
+
     /// Returns the operation name.
     #[must_use]
     pub const fn operation(&self) -> &str { self.operation.as_str() }
@@ -76,42 +122,35 @@ impl PluginRequest {
     /// Returns the arguments map.
     #[must_use]
     pub const fn arguments(&self) -> &HashMap<String, serde_json::Value> { &self.arguments }
-}
-
-/// File content passed to the plugin in the request body.
-///
-/// Contains the absolute path and the full text content of the file so
-/// the sandboxed plugin does not need filesystem access.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-pub struct FilePayload {
-    path: PathBuf,
-    content: String,
-}
 
-impl FilePayload {
-    /// Creates a file payload.
-    #[must_use]
-    pub fn new(path: PathBuf, content: impl Into<String>) -> Self {
-        Self {
-            path,
-            content: content.into(),
-        }
+    /// Reads the content of every by-reference file payload into the request.
+    ///
+    /// Plugins call this once after parsing a request so the rest of their
+    /// code can use [`FilePayload::content`] however the broker sent each
+    /// file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a referenced file cannot be read, is not valid
+    /// UTF-8, or does not match its declared length.
+    pub fn load_file_references(&mut self) -> std::io::Result<()> {
+        self.files
+            .iter_mut()
+            .try_for_each(FilePayload::load_reference)
     }
 
-    /// Returns the file path.
-    #[must_use]
-    pub fn path(&self) -> &std::path::Path { &self.path }
-
-    /// Returns the file content.
-    #[must_use]
-    pub const fn content(&self) -> &str { self.content.as_str() }
+    /// Returns the file payloads for in-place rewriting by the broker.
+    pub(crate) fn files_mut(&mut self) -> &mut [FilePayload] { &mut self.files }
 }
 
 /// Response sent from a plugin to the `weaverd` broker on stdout.
 ///
 /// Serialized as a single JSONL line terminated by a newline character.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PluginResponse {
+    #[serde(default = "default_protocol_version")]
+    protocol_version: u32,
     success: bool,
     output: PluginOutput,
     #[serde(default)]
@@ -123,25 +162,82 @@ impl PluginResponse {
     #[must_use]
     pub const fn success(output: PluginOutput) -> Self {
         Self {
+            protocol_version: PROTOCOL_VERSION,
             success: true,
             output,
             diagnostics: Vec::new(),
         }
     }
 
+    /// Attaches diagnostics to the response.
+    ///
+    /// A successful response may carry `Warning` and `Info` diagnostics
+    /// alongside its output; the broker reports them without failing the
+    /// operation. An `Error` diagnostic makes [`Self::is_success`] return
+    /// `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use weaver_plugins::protocol::{
+    ///     DiagnosticSeverity,
+    ///     PluginDiagnostic,
+    ///     PluginOutput,
+    ///     PluginResponse,
+    /// };
+    ///
+    /// let response =
+    ///     PluginResponse::success(PluginOutput::Empty).with_diagnostics(vec![PluginDiagnostic::new(
+    ///         DiagnosticSeverity::Warning,
+    ///         "docstring left unchanged",
+    ///     )]);
+    /// assert!(response.is_success());
+    /// ```
+    #[must_use]
+    pub fn with_diagnostics(mut self, diagnostics: Vec<PluginDiagnostic>) -> Self {
+        self.diagnostics = diagnostics;
+        self
+    }
+
     /// Creates a failed response with diagnostics.
     #[must_use]
     pub const fn failure(diagnostics: Vec<PluginDiagnostic>) -> Self {
         Self {
+            protocol_version: PROTOCOL_VERSION,
             success: false,
             output: PluginOutput::Empty,
             diagnostics,
         }
     }
 
+    /// Overrides the declared protocol version.
+    ///
+    /// Plugins built against this crate never need this; it exists so
+    /// brokers and tests can model peers that speak another version.
+    #[must_use]
+    pub const fn with_protocol_version(mut self, protocol_version: u32) -> Self {
+        self.protocol_version = protocol_version;
+        self
+    }
+
+    /// Returns the protocol version the plugin declared, or 1 if it omitted
+    /// the field.
+    #[must_use]
+    pub const fn protocol_version(&self) -> u32 { self.protocol_version }
+
     /// Returns whether the plugin completed successfully.
+    ///
+    /// `Warning` and `Info` diagnostics do not affect the result, but a
+    /// response that declares success while carrying an `Error` diagnostic
+    /// is treated as a failure.
     #[must_use]
-    pub const fn is_success(&self) -> bool { self.success }
+    pub fn is_success(&self) -> bool {
+        self.success
+            && !self
+                .diagnostics
+                .iter()
+                .any(|diagnostic| diagnostic.severity() == DiagnosticSeverity::Error)
+    }
 
     /// Returns the plugin output.
     #[must_use]
@@ -158,6 +254,7 @@ impl PluginResponse {
 /// broker can distinguish between diff output (from actuator plugins) and
 /// structured analysis data (from sensor plugins).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(tag = "kind", rename_all = "snake_case")]
 pub enum PluginOutput {
     /// A unified diff produced by an actuator plugin.
@@ -176,6 +273,7 @@ pub enum PluginOutput {
 
 /// A diagnostic message emitted by a plugin.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PluginDiagnostic {
     severity: DiagnosticSeverity,
     message: String,
@@ -234,8 +332,22 @@ impl PluginDiagnostic {
     pub const fn reason_code(&self) -> Option<ReasonCode> { self.reason_code }
 }
 
+impl fmt::Display for PluginDiagnostic {
+    /// Formats the diagnostic as `severity: message`, followed by the file
+    /// and line in parentheses when they are known.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.severity, self.message)?;
+        match (&self.file, self.line) {
+            (Some(file), Some(line)) => write!(f, " ({}:{line})", file.display()),
+            (Some(file), None) => write!(f, " ({})", file.display()),
+            (None, _) => Ok(()),
+        }
+    }
+}
+
 /// Severity level for plugin diagnostics.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum DiagnosticSeverity {
     /// A fatal error that prevented the plugin from completing.
@@ -246,5 +358,15 @@ pub enum DiagnosticSeverity {
     Info,
 }
 
+impl fmt::Display for DiagnosticSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Info => "info",
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,166 @@
+//! SEARCH/REPLACE patch construction for actuator diff output.
+//!
+//! Actuator plugins return [`PluginOutput::Diff`](super::PluginOutput::Diff)
+//! content in the SEARCH/REPLACE format the broker's `apply-patch` parser
+//! accepts. Each file section replaces the whole original content with the
+//! modified content, so the broker's matcher locates it unambiguously.
+
+use std::{collections::BTreeMap, path::Path};
+
+use thiserror::Error;
+
+use super::FilePayload;
+
+/// Error returned when a changed path has no matching request payload.
+///
+/// A patch can only describe edits to files whose original content the
+/// plugin received, so an engine touching any other file is rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("'{path}' was modified but not supplied in the request")]
+pub struct UnsuppliedFileError {
+    path: String,
+}
+
+impl UnsuppliedFileError {
+    /// Returns the slash-separated path of the unsupplied file.
+    #[must_use]
+    pub fn path(&self) -> &str { &self.path }
+}
+
+/// Builds a single-file SEARCH/REPLACE patch.
+///
+/// `unix_path` is the slash-separated workspace path written to the
+/// `diff --git` header. A trailing newline is added to either side that
+/// lacks one so the section markers stay on their own lines.
+#[must_use]
+pub fn search_replace_patch(unix_path: &str, original: &str, modified: &str) -> String {
+    let sep_after_original = if original.ends_with('\n') { "" } else { "\n" };
+    let sep_after_modified = if modified.ends_with('\n') { "" } else { "\n" };
+
+    format!(
+        concat!(
+            "diff --git a/{unix_path} b/{unix_path}\n",
+            "<<<<<<< SEARCH\n",
+            "{original}{sep_a}",
+            "=======\n",
+            "{modified}{sep_b}",
+            ">>>>>>> REPLACE\n",
+        ),
+        unix_path = unix_path,
+        original = original,
+        sep_a = sep_after_original,
+        modified = modified,
+        sep_b = sep_after_modified,
+    )
+}
+
+/// Concatenates one SEARCH/REPLACE patch per file whose content changed.
+///
+/// `changed` maps slash-separated paths to their modified content.
+/// `slash_path` converts each payload path into the same form, returning
+/// `None` for paths that cannot be represented; such payloads never match.
+/// Files whose content is unchanged are omitted, so an empty string means
+/// the edit was a no-op.
+///
+/// # Errors
+///
+/// Returns [`UnsuppliedFileError`] if a changed path does not correspond to
+/// any payload in `files`.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use weaver_plugins::protocol::{FilePayload, build_multi_file_patch};
+///
+/// let files = vec![FilePayload::new("src/lib.rs".into(), "old\n")];
+/// let changed = BTreeMap::from([(String::from("src/lib.rs"), String::from("new\n"))]);
+///
+/// let patch = build_multi_file_patch(&files, &changed, |path| path.to_str().map(str::to_owned))
+///     .expect("every changed file was supplied");
+/// assert!(patch.starts_with("diff --git a/src/lib.rs b/src/lib.rs\n"));
+/// ```
+pub fn build_multi_file_patch(
+    files: &[FilePayload],
+    changed: &BTreeMap<String, String>,
+    slash_path: impl Fn(&Path) -> Option<String>,
+) -> Result<String, UnsuppliedFileError> {
+    let mut patch = String::new();
+    for (path, modified) in changed {
+        let original = files
+            .iter()
+            .find(|payload| slash_path(payload.path()).is_some_and(|slash| slash == *path))
+            .ok_or_else(|| UnsuppliedFileError { path: path.clone() })?;
+        if original.content() != modified {
+            patch.push_str(&search_replace_patch(path, original.content(), modified));
+        }
+    }
+    Ok(patch)
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for SEARCH/REPLACE patch construction.
+
+    use std::path::PathBuf;
+
+    use rstest::rstest;
+
+    use super::*;
+
+    fn slash_path(path: &Path) -> Option<String> { path.to_str().map(str::to_owned) }
+
+    fn changed(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|&(path, content)| (path.to_owned(), content.to_owned()))
+            .collect()
+    }
+
+    #[rstest]
+    #[case::with_trailing_newlines("a\n", "b\n")]
+    #[case::without_trailing_newlines("a", "b")]
+    fn single_file_patch_keeps_markers_on_their_own_lines(
+        #[case] original: &str,
+        #[case] modified: &str,
+    ) {
+        let patch = search_replace_patch("src/lib.rs", original, modified);
+
+        assert_eq!(
+            patch,
+            concat!(
+                "diff --git a/src/lib.rs b/src/lib.rs\n",
+                "<<<<<<< SEARCH\n",
+                "a\n",
+                "=======\n",
+                "b\n",
+                ">>>>>>> REPLACE\n",
+            )
+        );
+    }
+
+    #[test]
+    fn multi_file_patch_skips_unchanged_files() {
+        let files = vec![
+            FilePayload::new(PathBuf::from("a.py"), "old\n"),
+            FilePayload::new(PathBuf::from("b.py"), "same\n"),
+        ];
+        let edits = changed(&[("a.py", "new\n"), ("b.py", "same\n")]);
+
+        let patch = build_multi_file_patch(&files, &edits, slash_path).expect("patch");
+
+        assert_eq!(patch, search_replace_patch("a.py", "old\n", "new\n"));
+    }
+
+    #[test]
+    fn multi_file_patch_rejects_unsupplied_paths() {
+        let files = vec![FilePayload::new(PathBuf::from("a.py"), "old\n")];
+        let edits = changed(&[("elsewhere.py", "new\n")]);
+
+        let error = build_multi_file_patch(&files, &edits, slash_path)
+            .expect_err("unsupplied path should be rejected");
+
+        assert_eq!(error.path(), "elsewhere.py");
+    }
+}
@@ -0,0 +1,135 @@
+//! File payloads carried by plugin requests.
+//!
+//! Content is embedded in the request line by default. Large files can
+//! instead be passed by reference: the broker writes the content to a file the
+//! sandbox may read and the payload carries that file's path and length. A
+//! by-reference payload omits `content`, so a plugin that predates references
+//! fails to parse it rather than silently seeing an empty file.
+
+use std::{
+    fs,
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// File content passed to the plugin in the request body.
+///
+/// Contains the workspace path of the file and either its full text content
+/// or a [`ContentReference`] to it, so the sandboxed plugin does not need
+/// access to the workspace.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FilePayload {
+    path: PathBuf,
+    #[serde(flatten)]
+    content: FileContent,
+}
+
+/// Wire representation of a payload's content.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+enum FileContent {
+    Inline { content: String },
+    Reference { content_ref: ContentReference },
+}
+
+/// Location of file content the broker wrote outside the request line.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ContentReference {
+    path: PathBuf,
+    length: u64,
+}
+
+impl ContentReference {
+    /// Creates a reference to `length` bytes of content stored at `path`.
+    #[must_use]
+    pub const fn new(path: PathBuf, length: u64) -> Self { Self { path, length } }
+
+    /// Returns the path of the file holding the content.
+    #[must_use]
+    pub fn path(&self) -> &Path { &self.path }
+
+    /// Returns the content length in bytes.
+    #[must_use]
+    pub const fn length(&self) -> u64 { self.length }
+
+    /// Reads the referenced content, checking it against the declared length.
+    fn read(&self) -> io::Result<String> {
+        let content = fs::read_to_string(&self.path)?;
+        let actual = u64::try_from(content.len()).unwrap_or(u64::MAX);
+        if actual != self.length {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "content reference '{}' holds {actual} bytes but declares {}",
+                    self.path.display(),
+                    self.length
+                ),
+            ));
+        }
+        Ok(content)
+    }
+}
+
+impl FilePayload {
+    /// Creates a file payload with inline content.
+    #[must_use]
+    pub fn new(path: PathBuf, content: impl Into<String>) -> Self {
+        Self {
+            path,
+            content: FileContent::Inline {
+                content: content.into(),
+            },
+        }
+    }
+
+    /// Creates a file payload whose content is stored at `reference`.
+    #[must_use]
+    pub const fn by_reference(path: PathBuf, reference: ContentReference) -> Self {
+        Self {
+            path,
+            content: FileContent::Reference {
+                content_ref: reference,
+            },
+        }
+    }
+
+    /// Returns the file path.
+    #[must_use]
+    pub fn path(&self) -> &Path { &self.path }
+
+    /// Returns the file content.
+    ///
+    /// By-reference payloads return an empty string until
+    /// [`PluginRequest::load_file_references`](super::PluginRequest::load_file_references)
+    /// has read their content.
+    #[must_use]
+    pub const fn content(&self) -> &str {
+        match &self.content {
+            FileContent::Inline { content } => content.as_str(),
+            FileContent::Reference { .. } => "",
+        }
+    }
+
+    /// Returns the content reference, if the content has not been inlined.
+    #[must_use]
+    pub const fn content_ref(&self) -> Option<&ContentReference> {
+        match &self.content {
+            FileContent::Inline { .. } => None,
+            FileContent::Reference { content_ref } => Some(content_ref),
+        }
+    }
+
+    /// Replaces a content reference with the content it points to.
+    pub(super) fn load_reference(&mut self) -> io::Result<()> {
+        if let FileContent::Reference { content_ref } = &self.content {
+            let content = content_ref.read()?;
+            self.content = FileContent::Inline { content };
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+//! JSON Schema export for the plugin protocol.
+//!
+//! Plugins written in other languages cannot share these Rust types, so the
+//! schemas give integrators a machine-readable description of the exact
+//! request and response lines. The schemas are derived from the same serde
+//! attributes that drive serialization, so they cannot drift from the wire
+//! format.
+
+use schemars::schema_for;
+use serde_json::{Value, json};
+
+use super::{PROTOCOL_VERSION, PluginOutput, PluginRequest, PluginResponse};
+
+/// Returns JSON Schemas for the plugin protocol messages.
+///
+/// The result is an object carrying the `protocol_version` the schemas
+/// describe and a `schemas` map holding a standalone schema for each of
+/// [`PluginRequest`], [`PluginResponse`], and [`PluginOutput`], keyed by type
+/// name.
+///
+/// # Example
+///
+/// ```
+/// let schema = weaver_plugins::protocol::json_schema();
+/// let request = &schema["schemas"]["PluginRequest"];
+/// assert!(request["properties"]["operation"].is_object());
+/// ```
+#[must_use]
+pub fn json_schema() -> Value {
+    json!({
+        "protocol_version": PROTOCOL_VERSION,
+        "schemas": {
+            "PluginRequest": schema_for!(PluginRequest),
+            "PluginResponse": schema_for!(PluginResponse),
+            "PluginOutput": schema_for!(PluginOutput),
+        },
+    })
+}
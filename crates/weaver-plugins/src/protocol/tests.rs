@@ -56,6 +56,72 @@ fn file_payload_accessors() {
     let payload = FilePayload::new(PathBuf::from("/a/b.py"), "content");
     assert_eq!(payload.path(), std::path::Path::new("/a/b.py"));
     assert_eq!(payload.content(), "content");
+    assert!(payload.content_ref().is_none());
+}
+
+#[test]
+fn inline_payload_keeps_wire_format() {
+    let payload = FilePayload::new(PathBuf::from("src/b.py"), "pass\n");
+    let json = serde_json::to_value(&payload).expect("serialise");
+    assert_eq!(
+        json,
+        serde_json::json!({"path": "src/b.py", "content": "pass\n"})
+    );
+}
+
+#[test]
+fn reference_payload_omits_inline_content() {
+    let reference = ContentReference::new(PathBuf::from("/tmp/spill/0"), 5);
+    let payload = FilePayload::by_reference(PathBuf::from("src/b.py"), reference.clone());
+
+    let json = serde_json::to_value(&payload).expect("serialise");
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "path": "src/b.py",
+            "content_ref": {"path": "/tmp/spill/0", "length": 5}
+        })
+    );
+    let back: FilePayload = serde_json::from_value(json).expect("deserialise");
+    assert_eq!(back.content_ref(), Some(&reference));
+    assert_eq!(back.content(), "");
+}
+
+fn request_referencing(content: &str, length: u64) -> (tempfile::TempDir, PluginRequest) {
+    let dir = tempfile::TempDir::new().expect("temp dir");
+    let stored = dir.path().join("0");
+    std::fs::write(&stored, content).expect("write content");
+    let payload = FilePayload::by_reference(
+        PathBuf::from("src/b.py"),
+        ContentReference::new(stored, length),
+    );
+    (dir, PluginRequest::new("rename", vec![payload]))
+}
+
+#[test]
+fn load_file_references_inlines_content() {
+    let (_dir, mut request) = request_referencing("pass\n", 5);
+
+    request.load_file_references().expect("load references");
+
+    let [file] = request.files() else {
+        panic!("expected one file");
+    };
+    assert_eq!(file.content(), "pass\n");
+    assert!(file.content_ref().is_none());
+}
+
+#[test]
+fn load_file_references_rejects_length_mismatch() {
+    let (_dir, mut request) = request_referencing("pass\n", 9);
+
+    let error = request.load_file_references().expect_err("length mismatch");
+
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert!(
+        error.to_string().contains("holds 5 bytes but declares 9"),
+        "unexpected error: {error}"
+    );
 }
 
 // ---------------------------------------------------------------------------
@@ -107,6 +173,45 @@ fn analysis_output_round_trip() {
     assert_eq!(back.output(), &PluginOutput::Analysis { data });
 }
 
+#[test]
+fn success_with_warning_round_trips_as_success() {
+    let warning = PluginDiagnostic::new(DiagnosticSeverity::Warning, "docstring left unchanged")
+        .with_file(PathBuf::from("src/main.py"))
+        .with_line(3);
+    let response = PluginResponse::success(PluginOutput::Diff {
+        content: "--- a/f\n+++ b/f\n".into(),
+    })
+    .with_diagnostics(vec![
+        warning,
+        PluginDiagnostic::new(DiagnosticSeverity::Info, "renamed 2 occurrences"),
+    ]);
+
+    let json = serde_json::to_string(&response).expect("serialise");
+    let back: PluginResponse = serde_json::from_str(&json).expect("deserialise");
+
+    assert!(back.is_success());
+    assert_eq!(back, response);
+    let rendered: Vec<String> = back.diagnostics().iter().map(ToString::to_string).collect();
+    assert_eq!(
+        rendered,
+        [
+            "warning: docstring left unchanged (src/main.py:3)",
+            "info: renamed 2 occurrences",
+        ]
+    );
+}
+
+#[test]
+fn success_with_error_diagnostic_is_not_success() {
+    let response =
+        PluginResponse::success(PluginOutput::Empty).with_diagnostics(vec![PluginDiagnostic::new(
+            DiagnosticSeverity::Error,
+            "index is stale",
+        )]);
+
+    assert!(!response.is_success());
+}
+
 // ---------------------------------------------------------------------------
 // PluginResponse diagnostics defaults
 // ---------------------------------------------------------------------------
@@ -123,6 +228,39 @@ fn diagnostics_defaults_to_empty_when_omitted(#[case] json: &str) {
     );
 }
 
+// ---------------------------------------------------------------------------
+// Protocol version
+// ---------------------------------------------------------------------------
+
+#[test]
+fn messages_declare_the_current_protocol_version() {
+    let request = serde_json::to_value(PluginRequest::new("rename", vec![])).expect("serialise");
+    let response =
+        serde_json::to_value(PluginResponse::success(PluginOutput::Empty)).expect("serialise");
+    let version = serde_json::Value::from(PROTOCOL_VERSION);
+    assert_eq!(request.get("protocol_version"), Some(&version));
+    assert_eq!(response.get("protocol_version"), Some(&version));
+}
+
+#[test]
+fn protocol_version_defaults_to_one_when_omitted() {
+    let request: PluginRequest =
+        serde_json::from_str(r#"{"operation":"rename","files":[]}"#).expect("deserialise");
+    let response: PluginResponse =
+        serde_json::from_str(r#"{"success":true,"output":{"kind":"empty"}}"#).expect("deserialise");
+    assert_eq!(request.protocol_version(), 1);
+    assert_eq!(response.protocol_version(), 1);
+}
+
+#[test]
+fn minimal_ping_request_deserialises() {
+    let request: PluginRequest =
+        serde_json::from_str(r#"{"operation":"ping"}"#).expect("deserialise");
+    assert_eq!(request, PluginRequest::ping());
+    assert_eq!(request.operation(), PING_OPERATION);
+    assert!(request.files().is_empty());
+}
+
 // ---------------------------------------------------------------------------
 // PluginOutput tagged serialization
 // ---------------------------------------------------------------------------
@@ -211,3 +349,48 @@ fn diagnostic_deserialises_without_reason_code() {
     assert!(diag.reason_code().is_none());
     assert_eq!(diag.message(), "oops");
 }
+
+// ---------------------------------------------------------------------------
+// JSON Schema export
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "schema")]
+fn schema_at(pointer: &str) -> serde_json::Value {
+    json_schema()
+        .pointer(pointer)
+        .cloned()
+        .unwrap_or_else(|| panic!("schema should contain {pointer}"))
+}
+
+#[cfg(feature = "schema")]
+#[test]
+fn request_schema_describes_operation_and_files() {
+    assert_eq!(schema_at("/protocol_version"), PROTOCOL_VERSION);
+
+    let request = schema_at("/schemas/PluginRequest");
+    for property in ["operation", "files"] {
+        assert!(
+            request
+                .pointer(&format!("/properties/{property}"))
+                .is_some_and(serde_json::Value::is_object),
+            "missing request property {property}: {request}"
+        );
+    }
+    assert_eq!(
+        request.get("required"),
+        Some(&serde_json::json!(["operation"]))
+    );
+}
+
+#[cfg(feature = "schema")]
+#[test]
+fn output_schema_lists_every_kind() {
+    let rendered = schema_at("/schemas/PluginOutput").to_string();
+    for kind in ["diff", "analysis", "empty"] {
+        assert!(
+            rendered.contains(&format!("\"const\":\"{kind}\"")),
+            "missing output kind {kind}: {rendered}"
+        );
+    }
+    assert!(schema_at("/schemas/PluginResponse/properties/output").is_object());
+}
@@ -0,0 +1,145 @@
+//! Manifest discovery from a directory of JSON and TOML files.
+//!
+//! Operators can drop one manifest per file into a directory instead of
+//! registering plugins programmatically. Each file is loaded independently,
+//! so a malformed manifest is reported without preventing the rest of the
+//! directory from being registered.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use super::PluginRegistry;
+use crate::{error::PluginError, manifest::PluginManifest};
+
+/// Outcome of loading manifests from a directory.
+///
+/// Successful registrations are listed by plugin name; every file that could
+/// not be read, parsed, validated, or registered is listed with its error.
+#[derive(Debug, Default)]
+pub struct ManifestLoadReport {
+    loaded: Vec<String>,
+    failures: Vec<ManifestLoadFailure>,
+}
+
+impl ManifestLoadReport {
+    /// Returns the names of the plugins that were registered.
+    #[must_use]
+    pub fn loaded(&self) -> &[String] { &self.loaded }
+
+    /// Returns the files that failed to load.
+    #[must_use]
+    pub fn failures(&self) -> &[ManifestLoadFailure] { &self.failures }
+
+    /// Returns `true` when every manifest in the directory was registered.
+    #[must_use]
+    pub const fn is_clean(&self) -> bool { self.failures.is_empty() }
+}
+
+/// A manifest file that could not be registered.
+#[derive(Debug)]
+pub struct ManifestLoadFailure {
+    path: PathBuf,
+    error: PluginError,
+}
+
+impl ManifestLoadFailure {
+    /// Returns the path of the manifest file.
+    #[must_use]
+    pub fn path(&self) -> &Path { &self.path }
+
+    /// Returns the reason the manifest was rejected.
+    #[must_use]
+    pub const fn error(&self) -> &PluginError { &self.error }
+}
+
+/// Supported manifest encodings, selected by file extension.
+#[derive(Debug, Clone, Copy)]
+enum ManifestFormat {
+    Json,
+    Toml,
+}
+
+impl ManifestFormat {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+
+    fn parse(self, text: &str) -> Result<PluginManifest, String> {
+        match self {
+            Self::Json => serde_json::from_str(text).map_err(|error| error.to_string()),
+            Self::Toml => toml::from_str(text).map_err(|error| error.to_string()),
+        }
+    }
+}
+
+impl PluginRegistry {
+    /// Registers every `*.json` and `*.toml` manifest found in `dir`.
+    ///
+    /// Files are processed in path order so duplicate names resolve
+    /// deterministically: the first file wins and later ones are reported as
+    /// failures. Subdirectories and files with other extensions are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PluginError::ManifestIo`] if the directory itself cannot be
+    /// listed. Problems with individual files are recorded in the returned
+    /// report instead.
+    pub fn load_from_dir(&mut self, dir: &Path) -> Result<ManifestLoadReport, PluginError> {
+        let mut paths = manifest_paths(dir)?;
+        paths.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+        let mut report = ManifestLoadReport::default();
+        for (path, format) in paths {
+            match self.load_manifest(&path, format) {
+                Ok(name) => report.loaded.push(name),
+                Err(error) => report.failures.push(ManifestLoadFailure { path, error }),
+            }
+        }
+        Ok(report)
+    }
+
+    fn load_manifest(
+        &mut self,
+        path: &Path,
+        format: ManifestFormat,
+    ) -> Result<String, PluginError> {
+        let text = fs::read_to_string(path).map_err(|source| manifest_io(path, source))?;
+        let manifest = format
+            .parse(&text)
+            .map_err(|message| PluginError::Manifest {
+                message: format!("failed to parse '{}': {message}", path.display()),
+            })?;
+        let name = manifest.name().to_owned();
+        self.register(manifest)?;
+        Ok(name)
+    }
+}
+
+fn manifest_paths(dir: &Path) -> Result<Vec<(PathBuf, ManifestFormat)>, PluginError> {
+    let entries = fs::read_dir(dir).map_err(|source| manifest_io(dir, source))?;
+    let mut paths = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|source| manifest_io(dir, source))?.path();
+        if !path.is_file() {
+            continue;
+        }
+        if let Some(format) = ManifestFormat::from_path(&path) {
+            paths.push((path, format));
+        }
+    }
+    Ok(paths)
+}
+
+fn manifest_io(path: &Path, source: std::io::Error) -> PluginError {
+    PluginError::ManifestIo {
+        path: path.to_path_buf(),
+        source: Arc::new(source),
+    }
+}
@@ -2,10 +2,14 @@
 //!
 //! The [`PluginRegistry`] stores validated plugin manifests keyed by name and
 //! provides lookup methods filtered by kind, language, or both. Duplicate
-//! registrations for the same plugin name are rejected.
+//! registrations for the same plugin name are rejected. Manifests can also be
+//! discovered from a directory with [`PluginRegistry::load_from_dir`].
+
+mod discovery;
 
 use std::collections::HashMap;
 
+pub use self::discovery::{ManifestLoadFailure, ManifestLoadReport};
 use crate::{
     capability::CapabilityId,
     error::PluginError,
@@ -117,6 +121,14 @@ impl PluginRegistry {
             .collect()
     }
 
+    /// Returns the names of all registered plugins in sorted order.
+    #[must_use]
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.manifests.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
     /// Returns the number of registered plugins.
     #[must_use]
     pub fn len(&self) -> usize { self.manifests.len() }
@@ -141,6 +141,11 @@ fn len_reflects_registration_count(populated_registry: PluginRegistry) {
     assert!(!populated_registry.is_empty());
 }
 
+#[rstest]
+fn names_are_sorted(populated_registry: PluginRegistry) {
+    assert_eq!(populated_registry.names(), ["jedi", "rope", "srgn"]);
+}
+
 // ---------------------------------------------------------------------------
 // Capability-based lookup
 // ---------------------------------------------------------------------------
@@ -226,3 +231,88 @@ fn find_for_language_and_capability_is_case_insensitive(capability_registry: Plu
     assert_eq!(results.len(), 1);
     assert_eq!(results.first().expect("one plugin").name(), "ra");
 }
+
+// ---------------------------------------------------------------------------
+// Directory discovery
+// ---------------------------------------------------------------------------
+
+const ROPE_TOML: &str = r#"
+name = "rope"
+version = "1.0.0"
+kind = "actuator"
+languages = ["Python"]
+executable = "/usr/bin/weaver-plugin-rope"
+capabilities = ["rename-symbol"]
+"#;
+
+fn write_manifest(dir: &std::path::Path, file: &str, contents: &str) {
+    std::fs::write(dir.join(file), contents).expect("write manifest");
+}
+
+#[test]
+fn load_from_dir_registers_valid_and_reports_malformed() {
+    let dir = tempfile::tempdir().expect("create manifests dir");
+    write_manifest(dir.path(), "rope.toml", ROPE_TOML);
+    write_manifest(dir.path(), "broken.json", r#"{"name": "jedi", "kind": }"#);
+    write_manifest(dir.path(), "README.md", "not a manifest");
+
+    let mut r = PluginRegistry::new();
+    let report = r.load_from_dir(dir.path()).expect("directory is readable");
+
+    assert_eq!(report.loaded(), ["rope"]);
+    assert!(!report.is_clean());
+    let [failure] = report.failures() else {
+        panic!("expected one failure, got: {:?}", report.failures());
+    };
+    assert_eq!(failure.path(), dir.path().join("broken.json"));
+    assert!(matches!(failure.error(), PluginError::Manifest { .. }));
+
+    let rope = r.get("rope").expect("rope registered");
+    assert_eq!(rope.languages(), ["python"]);
+    assert_eq!(rope.timeout_secs(), 30);
+}
+
+#[test]
+fn load_from_dir_reports_invalid_and_duplicate_manifests() {
+    let dir = tempfile::tempdir().expect("create manifests dir");
+    write_manifest(dir.path(), "a-rope.toml", ROPE_TOML);
+    write_manifest(
+        dir.path(),
+        "b-rope.json",
+        r#"{"name": "rope", "version": "2.0.0", "kind": "actuator",
+            "languages": ["python"], "executable": "/usr/bin/rope2"}"#,
+    );
+    write_manifest(
+        dir.path(),
+        "c-relative.json",
+        r#"{"name": "srgn", "version": "1.0", "kind": "actuator",
+            "languages": ["rust"], "executable": "bin/srgn"}"#,
+    );
+
+    let mut r = PluginRegistry::new();
+    let report = r.load_from_dir(dir.path()).expect("directory is readable");
+
+    assert_eq!(report.loaded(), ["rope"]);
+    let messages: Vec<String> = report
+        .failures()
+        .iter()
+        .map(|failure| failure.error().to_string())
+        .collect();
+    let [duplicate, relative] = messages.as_slice() else {
+        panic!("expected two failures, got: {messages:?}");
+    };
+    assert!(duplicate.contains("already registered"), "{duplicate}");
+    assert!(relative.contains("absolute path"), "{relative}");
+    assert_eq!(r.get("rope").map(PluginManifest::version), Some("1.0.0"));
+}
+
+#[test]
+fn load_from_missing_dir_is_an_error() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let missing = dir.path().join("absent");
+
+    let mut r = PluginRegistry::new();
+    let error = r.load_from_dir(&missing).expect_err("missing directory");
+
+    assert!(matches!(error, PluginError::ManifestIo { ref path, .. } if *path == missing));
+}
@@ -10,8 +10,8 @@
 
 use crate::{
     error::PluginError,
-    manifest::PluginManifest,
-    protocol::{PluginRequest, PluginResponse},
+    manifest::{PluginKind, PluginManifest},
+    protocol::{PROTOCOL_VERSION, PluginDiagnostic, PluginOutput, PluginRequest, PluginResponse},
     registry::PluginRegistry,
 };
 
@@ -139,7 +139,10 @@ impl<E: PluginExecutor> PluginRunner<E> {
     /// # Errors
     ///
     /// Returns [`PluginError::NotFound`] if no plugin with the given name is
-    /// registered, or any error produced by the executor.
+    /// registered, [`PluginError::ProtocolVersion`] if the response declares
+    /// a protocol version other than [`PROTOCOL_VERSION`],
+    /// [`PluginError::OutputKindMismatch`] if a sensor returns a diff or an
+    /// actuator returns analysis data, or any error produced by the executor.
     pub fn execute(
         &self,
         plugin_name: &str,
@@ -152,9 +155,67 @@ impl<E: PluginExecutor> PluginRunner<E> {
                 name: plugin_name.to_owned(),
             })?;
 
-        self.executor.execute(manifest, request)
+        let response = self.executor.execute(manifest, request)?;
+        if response.protocol_version() != PROTOCOL_VERSION {
+            return Err(PluginError::ProtocolVersion {
+                name: plugin_name.to_owned(),
+                expected: PROTOCOL_VERSION,
+                found: response.protocol_version(),
+            });
+        }
+        check_output_kind(manifest, response.output())?;
+        Ok(response)
+    }
+
+    /// Verifies that a plugin starts and answers a
+    /// [`PING_OPERATION`](crate::protocol::PING_OPERATION) request.
+    ///
+    /// Registration only validates the manifest, so a missing or broken
+    /// executable would otherwise go unnoticed until the first real request.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from [`Self::execute`], or
+    /// [`PluginError::HealthCheck`] if the plugin answers the ping with a
+    /// failure response.
+    pub fn health_check(&self, plugin_name: &str) -> Result<(), PluginError> {
+        let response = self.execute(plugin_name, &PluginRequest::ping())?;
+        if response.is_success() {
+            return Ok(());
+        }
+        let diagnostics: Vec<&str> = response
+            .diagnostics()
+            .iter()
+            .map(PluginDiagnostic::message)
+            .collect();
+        let message = if diagnostics.is_empty() {
+            String::from("plugin reported failure without diagnostics")
+        } else {
+            diagnostics.join("; ")
+        };
+        Err(PluginError::HealthCheck {
+            name: plugin_name.to_owned(),
+            message,
+        })
     }
 }
 
+/// Rejects output that the plugin's declared kind cannot produce.
+///
+/// Sensors observe and actuators modify, so a sensor diff or an actuator
+/// analysis indicates a misdeclared manifest. Empty output suits either kind.
+fn check_output_kind(manifest: &PluginManifest, output: &PluginOutput) -> Result<(), PluginError> {
+    let mismatch = match (manifest.kind(), output) {
+        (PluginKind::Sensor, PluginOutput::Diff { .. }) => "diff",
+        (PluginKind::Actuator, PluginOutput::Analysis { .. }) => "analysis",
+        _ => return Ok(()),
+    };
+    Err(PluginError::OutputKindMismatch {
+        name: manifest.name().to_owned(),
+        kind: manifest.kind(),
+        output: mismatch,
+    })
+}
+
 #[cfg(test)]
 mod tests;
@@ -8,8 +8,17 @@ use super::*;
 use crate::{
     error::PluginError,
     manifest::{PluginKind, PluginManifest, PluginMetadata},
-    protocol::PluginRequest,
+    protocol::{
+        DiagnosticSeverity,
+        PING_OPERATION,
+        PROTOCOL_VERSION,
+        PluginDiagnostic,
+        PluginOutput,
+        PluginRequest,
+        PluginResponse,
+    },
     registry::PluginRegistry,
+    runner::MockPluginExecutor,
     tests::{diff_executor, non_zero_exit_executor},
 };
 
@@ -23,6 +32,20 @@ fn manifest() -> PluginManifest {
     PluginManifest::new(meta, vec!["python".into()], PathBuf::from("/usr/bin/rope"))
 }
 
+#[fixture]
+fn registry_with_jedi() -> PluginRegistry {
+    let meta = PluginMetadata::new("jedi", "1.0", PluginKind::Sensor);
+    let mut r = PluginRegistry::new();
+    if let Err(error) = r.register(PluginManifest::new(
+        meta,
+        vec!["python".into()],
+        PathBuf::from("/usr/bin/jedi"),
+    )) {
+        panic!("register jedi: {error}");
+    }
+    r
+}
+
 #[fixture]
 fn registry_with_rope(manifest: PluginManifest) -> PluginRegistry {
     let mut r = PluginRegistry::new();
@@ -62,6 +85,140 @@ fn execute_propagates_executor_error(registry_with_rope: PluginRegistry) {
     assert!(matches!(err, PluginError::NonZeroExit { .. }));
 }
 
+fn executor_returning(response: PluginResponse) -> MockPluginExecutor {
+    let mut mock = MockPluginExecutor::new();
+    mock.expect_execute()
+        .returning(move |_manifest, _request| Ok(response.clone()));
+    mock
+}
+
+#[rstest]
+fn execute_accepts_matching_protocol_version(registry_with_rope: PluginRegistry) {
+    let runner = PluginRunner::new(registry_with_rope, diff_executor());
+    let request = PluginRequest::new("rename", vec![]);
+    let response = runner.execute("rope", &request).expect("execute");
+    assert_eq!(response.protocol_version(), PROTOCOL_VERSION);
+}
+
+#[rstest]
+fn execute_accepts_response_without_protocol_version(registry_with_rope: PluginRegistry) {
+    let legacy: PluginResponse =
+        serde_json::from_str(r#"{"success":true,"output":{"kind":"empty"}}"#)
+            .expect("deserialise legacy response");
+    let runner = PluginRunner::new(registry_with_rope, executor_returning(legacy));
+    let request = PluginRequest::new("rename", vec![]);
+    let response = runner.execute("rope", &request).expect("execute");
+    assert!(response.is_success());
+}
+
+#[rstest]
+fn execute_rejects_mismatched_protocol_version(registry_with_rope: PluginRegistry) {
+    let future = PluginResponse::success(crate::protocol::PluginOutput::Empty)
+        .with_protocol_version(PROTOCOL_VERSION + 1);
+    let runner = PluginRunner::new(registry_with_rope, executor_returning(future));
+    let request = PluginRequest::new("rename", vec![]);
+    let err = runner.execute("rope", &request).expect_err("should fail");
+    assert!(
+        matches!(
+            err,
+            PluginError::ProtocolVersion { ref name, expected, found }
+                if name == "rope" && expected == PROTOCOL_VERSION && found == PROTOCOL_VERSION + 1
+        ),
+        "unexpected error: {err}"
+    );
+}
+
+fn analysis_response() -> PluginResponse {
+    PluginResponse::success(PluginOutput::Analysis {
+        data: serde_json::json!({"symbols": 3}),
+    })
+}
+
+#[rstest]
+fn execute_returns_sensor_analysis(registry_with_jedi: PluginRegistry) {
+    let runner = PluginRunner::new(registry_with_jedi, executor_returning(analysis_response()));
+    let request = PluginRequest::new("analyze", vec![]);
+    let response = runner.execute("jedi", &request).expect("execute");
+    assert_eq!(response, analysis_response());
+}
+
+#[rstest]
+fn execute_rejects_sensor_returning_diff(registry_with_jedi: PluginRegistry) {
+    let runner = PluginRunner::new(registry_with_jedi, diff_executor());
+    let request = PluginRequest::new("analyze", vec![]);
+    let err = runner.execute("jedi", &request).expect_err("should fail");
+    assert!(
+        matches!(
+            err,
+            PluginError::OutputKindMismatch { ref name, kind: PluginKind::Sensor, output: "diff" }
+                if name == "jedi"
+        ),
+        "unexpected error: {err}"
+    );
+    assert_eq!(
+        err.to_string(),
+        "sensor plugin 'jedi' returned diff output, which sensors cannot produce"
+    );
+}
+
+#[rstest]
+fn execute_rejects_actuator_returning_analysis(registry_with_rope: PluginRegistry) {
+    let runner = PluginRunner::new(registry_with_rope, executor_returning(analysis_response()));
+    let request = PluginRequest::new("rename", vec![]);
+    let err = runner.execute("rope", &request).expect_err("should fail");
+    assert!(
+        matches!(
+            err,
+            PluginError::OutputKindMismatch { ref name, kind: PluginKind::Actuator, output: "analysis" }
+                if name == "rope"
+        ),
+        "unexpected error: {err}"
+    );
+}
+
+#[rstest]
+fn health_check_passes_for_healthy_plugin(registry_with_rope: PluginRegistry) {
+    let mut mock = MockPluginExecutor::new();
+    mock.expect_execute()
+        .withf(|_manifest, request| request.operation() == PING_OPERATION)
+        .times(1)
+        .returning(|_manifest, _request| Ok(PluginResponse::success(PluginOutput::Empty)));
+    let runner = PluginRunner::new(registry_with_rope, mock);
+    runner.health_check("rope").expect("rope is healthy");
+}
+
+#[rstest]
+fn health_check_reports_unresponsive_plugin(registry_with_rope: PluginRegistry) {
+    let mut mock = MockPluginExecutor::new();
+    mock.expect_execute().returning(|manifest, _request| {
+        Err(PluginError::Timeout {
+            name: manifest.name().to_owned(),
+            timeout_secs: 30,
+            message: String::from("process killed"),
+        })
+    });
+    let runner = PluginRunner::new(registry_with_rope, mock);
+    let err = runner.health_check("rope").expect_err("should fail");
+    assert!(
+        matches!(err, PluginError::Timeout { .. }),
+        "unexpected error: {err}"
+    );
+}
+
+#[rstest]
+fn health_check_rejects_failed_ping(registry_with_rope: PluginRegistry) {
+    let failure = PluginResponse::failure(vec![PluginDiagnostic::new(
+        DiagnosticSeverity::Error,
+        "unsupported refactoring operation 'ping'",
+    )]);
+    let runner = PluginRunner::new(registry_with_rope, executor_returning(failure));
+    let err = runner.health_check("rope").expect_err("should fail");
+    assert_eq!(
+        err.to_string(),
+        "plugin 'rope' failed its health check: unsupported refactoring operation 'ping'"
+    );
+}
+
 #[rstest]
 fn registry_accessor(registry_with_rope: PluginRegistry) {
     let runner = PluginRunner::new(registry_with_rope, diff_executor());
@@ -43,6 +43,7 @@ pub(crate) fn non_zero_exit_executor() -> MockPluginExecutor {
         Err(PluginError::NonZeroExit {
             name: manifest.name().to_owned(),
             status: 1,
+            stderr: String::new(),
         })
     });
     mock
@@ -0,0 +1,61 @@
+//! Timeout enforcement for sandboxed plugin execution.
+//!
+//! The sandbox refuses to spawn from a multi-threaded process and libtest runs
+//! every test on a worker thread, so this target opts out of the harness and
+//! drives a real plugin process from `main`.
+
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use weaver_plugins::{
+    PluginError,
+    PluginKind,
+    PluginManifest,
+    PluginMetadata,
+    PluginRequest,
+    process::SandboxExecutor,
+    runner::PluginExecutor,
+};
+
+/// Upper bound on how long the timed-out execution may take, leaving room for
+/// the kill and reap after the one-second budget.
+const MAX_ELAPSED: Duration = Duration::from_secs(10);
+
+/// Stub plugin that spins in a shell builtin loop, never writing a response,
+/// until it is killed.
+fn stalled_plugin() -> PluginManifest {
+    PluginManifest::new(
+        PluginMetadata::new("stalled", "0.1.0", PluginKind::Sensor),
+        vec![String::from("python")],
+        PathBuf::from("/bin/sh"),
+    )
+    .with_args(vec![
+        String::from("-c"),
+        String::from("while :; do :; done"),
+    ])
+    .with_timeout_secs(1)
+}
+
+fn plugin_exceeding_its_budget_is_killed() -> Result<(), String> {
+    let started = Instant::now();
+    let outcome = SandboxExecutor::default().execute(&stalled_plugin(), &PluginRequest::ping());
+    let elapsed = started.elapsed();
+
+    match outcome {
+        Err(PluginError::Timeout {
+            name, timeout_secs, ..
+        }) if name == "stalled" && timeout_secs == 1 => {}
+        Err(other) => return Err(format!("expected a timeout, got: {other}")),
+        Ok(response) => return Err(format!("expected a timeout, got: {response:?}")),
+    }
+    if elapsed < Duration::from_secs(1) || elapsed > MAX_ELAPSED {
+        return Err(format!(
+            "timeout fired after {elapsed:?}, not shortly after 1s"
+        ));
+    }
+    Ok(())
+}
+
+fn main() -> Result<(), String> { plugin_exceeding_its_budget_is_killed() }
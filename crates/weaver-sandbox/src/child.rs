@@ -0,0 +1,117 @@
+//! Handle to a running sandboxed process with an optional deadline.
+
+use std::{
+    io::{self, Read},
+    ops::{Deref, DerefMut},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use birdcage::process::{Child, Output};
+
+use crate::error::SandboxError;
+
+/// Interval between exit polls while a deadline is being enforced.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Handle to a running sandboxed process.
+///
+/// The handle dereferences to the underlying `birdcage` child, so its pipes
+/// and process controls remain available. When the profile carries a time
+/// limit, [`SandboxChild::wait_with_output`] kills the process once the limit
+/// elapses instead of waiting indefinitely.
+pub struct SandboxChild {
+    child: Child,
+    limit: Option<Duration>,
+    started: Instant,
+}
+
+impl SandboxChild {
+    pub(crate) fn new(child: Child, limit: Option<Duration>) -> Self {
+        Self {
+            child,
+            limit,
+            started: Instant::now(),
+        }
+    }
+
+    /// Returns the wall-clock limit applied to the process, if any.
+    #[must_use]
+    pub const fn time_limit(&self) -> Option<Duration> { self.limit }
+
+    /// Waits for the process to exit and collects its output.
+    ///
+    /// Without a time limit this behaves like the `birdcage` method of the
+    /// same name. With a limit, the process is killed and reaped once the
+    /// limit has elapsed since spawning, and [`SandboxError::TimedOut`] is
+    /// returned; any output produced before the deadline is discarded.
+    pub fn wait_with_output(mut self) -> Result<Output, SandboxError> {
+        let Some(limit) = self.limit else {
+            return self.child.wait_with_output().map_err(wait_error);
+        };
+
+        let stdout = self.child.stdout.take().map(read_in_background);
+        let stderr = self.child.stderr.take().map(read_in_background);
+        let deadline = self.started + limit;
+        let status = loop {
+            if let Some(status) = self.child.try_wait().map_err(wait_error)? {
+                break status;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                self.terminate()?;
+                // Killing the process closes its pipes, so the readers finish.
+                // Joining them keeps the caller single-threaded for the next
+                // spawn.
+                collect(stdout)?;
+                collect(stderr)?;
+                return Err(SandboxError::TimedOut { limit });
+            }
+            thread::sleep(POLL_INTERVAL.min(deadline - now));
+        };
+
+        Ok(Output {
+            status,
+            stdout: collect(stdout)?,
+            stderr: collect(stderr)?,
+        })
+    }
+
+    fn terminate(&mut self) -> Result<(), SandboxError> {
+        self.child.kill().map_err(wait_error)?;
+        self.child.wait().map(drop).map_err(wait_error)
+    }
+}
+
+impl Deref for SandboxChild {
+    type Target = Child;
+
+    fn deref(&self) -> &Self::Target { &self.child }
+}
+
+impl DerefMut for SandboxChild {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.child }
+}
+
+fn read_in_background<R>(mut pipe: R) -> JoinHandle<io::Result<Vec<u8>>>
+where
+    R: Read + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        pipe.read_to_end(&mut buffer)?;
+        Ok(buffer)
+    })
+}
+
+fn collect(reader: Option<JoinHandle<io::Result<Vec<u8>>>>) -> Result<Vec<u8>, SandboxError> {
+    let Some(handle) = reader else {
+        return Ok(Vec::new());
+    };
+    handle
+        .join()
+        .unwrap_or_else(|_| Err(io::Error::other("output reader thread panicked")))
+        .map_err(wait_error)
+}
+
+const fn wait_error(source: io::Error) -> SandboxError { SandboxError::Wait { source } }
@@ -1,6 +1,6 @@
 //! Domain errors raised by the sandbox wrapper.
 
-use std::{io, path::PathBuf};
+use std::{io, path::PathBuf, time::Duration};
 
 use birdcage::error::Error as BirdcageError;
 use thiserror::Error;
@@ -32,6 +32,19 @@ pub enum SandboxError {
     #[error("failed to determine thread count: {source}")]
     ThreadCountUnavailable { source: io::Error },
 
+    /// The sandboxed process outlived the profile's time limit and was killed.
+    #[error("sandboxed process exceeded its time limit of {limit:?} and was killed")]
+    TimedOut { limit: Duration },
+
+    /// The address-space limit could not be applied to the spawned process,
+    /// which was killed instead of being left unbounded.
+    #[error("failed to apply a memory limit of {limit} bytes: {source}")]
+    MemoryLimit { limit: u64, source: io::Error },
+
+    /// Waiting for, or collecting output from, the sandboxed process failed.
+    #[error("failed to wait for sandboxed process: {source}")]
+    Wait { source: io::Error },
+
     /// The underlying sandbox library rejected activation.
     #[error("birdcage activation failed: {0}")]
     Activation(#[from] BirdcageError),
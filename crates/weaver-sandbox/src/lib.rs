@@ -10,6 +10,8 @@
 //! - Networking is disabled unless explicitly enabled.
 //! - Environment variables are stripped unless whitelisted.
 //! - Executables must be whitelisted and provided as absolute paths.
+//! - Processes run without time or memory limits unless the profile sets them with
+//!   [`SandboxProfile::with_time_limit`] and [`SandboxProfile::with_memory_limit`].
 //! - Standard library locations on Linux are whitelisted by default to keep dynamically linked
 //!   binaries functional without exposing the wider filesystem.
 //!
@@ -39,13 +41,19 @@
 //! [`SandboxError::MultiThreaded`] rather than panicking on the internal
 //! assertion used by `birdcage`.
 
+mod child;
 pub(crate) mod env_guard;
 mod error;
+mod limits;
 mod profile;
 mod runtime;
 mod sandbox;
 
 pub use birdcage::process;
+pub use child::SandboxChild;
 pub use error::SandboxError;
 pub use profile::{EnvironmentPolicy, NetworkPolicy, SandboxProfile};
-pub use sandbox::{Sandbox, SandboxChild, SandboxCommand, SandboxOutput};
+pub use sandbox::{Sandbox, SandboxCommand, SandboxOutput};
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,91 @@
+//! Resource limits applied to sandboxed children.
+//!
+//! `birdcage` exposes no hook that runs in the child before `exec`, and
+//! lowering a limit on the daemon itself would constrain the daemon's own
+//! allocations while it spawns. The process `birdcage` returns is the sandbox's
+//! init, which inherits the daemon's address space while it sets up the
+//! namespaces, so limiting it would starve that setup. Limits are therefore
+//! applied with `prlimit` to the command init launches, as soon as it appears.
+
+#[cfg(target_os = "linux")]
+use std::{fs, io, thread, time::Duration};
+
+use crate::error::SandboxError;
+
+/// Interval between checks for the command launched by the sandbox's init.
+#[cfg(target_os = "linux")]
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Caps the address space of the command launched by sandbox init `init` at
+/// `bytes`.
+///
+/// Both the soft and hard limits are lowered so the sandboxed process cannot
+/// raise its own soft limit again. If init exits without launching the
+/// command, there is nothing to limit and the failure is left for the caller
+/// to observe when it waits.
+#[cfg(target_os = "linux")]
+pub(crate) fn limit_address_space(init: u32, bytes: u64) -> Result<(), SandboxError> {
+    let failed = |source| SandboxError::MemoryLimit {
+        limit: bytes,
+        source,
+    };
+    loop {
+        let children = launched_children(init).map_err(failed)?;
+        if !children.is_empty() {
+            return children
+                .into_iter()
+                .try_for_each(|pid| set_address_space(pid, bytes))
+                .map_err(failed);
+        }
+        if has_exited(init) {
+            return Ok(());
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn launched_children(init: u32) -> io::Result<Vec<libc::pid_t>> {
+    let children = fs::read_to_string(format!("/proc/{init}/task/{init}/children"))?;
+    children
+        .split_whitespace()
+        .map(|pid| {
+            pid.parse()
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+        })
+        .collect()
+}
+
+/// Returns true once `pid` is a zombie or has been reaped.
+#[cfg(target_os = "linux")]
+fn has_exited(pid: u32) -> bool {
+    let Ok(stat) = fs::read_to_string(format!("/proc/{pid}/stat")) else {
+        return true;
+    };
+    // The state follows the parenthesised command name, which may itself
+    // contain spaces or parentheses.
+    stat.rsplit_once(')')
+        .and_then(|(_, fields)| fields.split_whitespace().next())
+        .is_none_or(|state| matches!(state, "Z" | "X"))
+}
+
+#[cfg(target_os = "linux")]
+fn set_address_space(pid: libc::pid_t, bytes: u64) -> io::Result<()> {
+    let limit = libc::rlimit {
+        rlim_cur: bytes,
+        rlim_max: bytes,
+    };
+    // SAFETY: `limit` is a valid `rlimit` for the duration of the call and the
+    // previous limit is not requested, so a null output pointer is permitted.
+    let result =
+        unsafe { libc::prlimit(pid, libc::RLIMIT_AS, &raw const limit, std::ptr::null_mut()) };
+    if result != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Address-space limits are only enforced on Linux; elsewhere the profile's
+/// memory limit is accepted but has no effect.
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn limit_address_space(_init: u32, _bytes: u64) -> Result<(), SandboxError> { Ok(()) }
@@ -1,6 +1,6 @@
 //! Sandbox policy definition and builder helpers.
 
-use std::{collections::BTreeSet, path::PathBuf};
+use std::{collections::BTreeSet, path::PathBuf, time::Duration};
 
 use once_cell::sync::OnceCell;
 
@@ -12,8 +12,13 @@ pub enum EnvironmentPolicy {
     /// Remove all environment variables before launching the child.
     #[default]
     Isolated,
-    /// Allow only the named environment variables to be inherited.
-    AllowList(BTreeSet<String>),
+    /// Allow only the listed environment variables to be inherited.
+    AllowList {
+        /// Variables inherited by exact name.
+        names: BTreeSet<String>,
+        /// Prefixes matched against the parent environment at spawn time.
+        prefixes: BTreeSet<String>,
+    },
     /// Inherit the full environment unchanged.
     InheritAll,
 }
@@ -44,6 +49,8 @@ pub struct SandboxProfile {
     executable_paths_canon: OnceCell<Vec<PathBuf>>,
     environment: EnvironmentPolicy,
     network: NetworkPolicy,
+    time_limit: Option<Duration>,
+    memory_limit: Option<u64>,
 }
 
 impl SandboxProfile {
@@ -69,6 +76,8 @@ impl SandboxProfile {
             executable_paths_canon: OnceCell::new(),
             environment: EnvironmentPolicy::default(),
             network: NetworkPolicy::default(),
+            time_limit: None,
+            memory_limit: None,
         }
     }
 
@@ -80,6 +89,12 @@ impl SandboxProfile {
     }
 
     /// Grants read-only access to the provided path.
+    ///
+    /// This maps onto `birdcage`'s read exception: the sandboxed process may
+    /// open and list the path but every attempt to create, modify, or remove
+    /// files beneath it is refused. Prefer this over
+    /// [`SandboxProfile::allow_read_write_path`] for workspaces a plugin only
+    /// inspects.
     #[must_use]
     pub fn allow_read_path(mut self, path: impl Into<PathBuf>) -> Self {
         self.read_only_paths.push(path.into());
@@ -87,6 +102,9 @@ impl SandboxProfile {
     }
 
     /// Grants read-write access to the provided path.
+    ///
+    /// This maps onto `birdcage`'s write-and-read exception and should be
+    /// reserved for scratch or output locations the process must modify.
     #[must_use]
     pub fn allow_read_write_path(mut self, path: impl Into<PathBuf>) -> Self {
         self.read_write_paths.push(path.into());
@@ -105,6 +123,28 @@ impl SandboxProfile {
         self
     }
 
+    /// Whitelists every environment variable whose name starts with `prefix`.
+    ///
+    /// The prefix is resolved against the parent environment when the sandbox
+    /// spawns, so variables set after the profile is built are still matched.
+    /// Like [`SandboxProfile::allow_environment_variable`], this is a no-op
+    /// under [`EnvironmentPolicy::InheritAll`].
+    ///
+    /// ```
+    /// use weaver_sandbox::SandboxProfile;
+    ///
+    /// let profile = SandboxProfile::new()
+    ///     .allow_environment_prefix("CARGO_")
+    ///     .allow_environment_variable("RUSTUP_HOME");
+    /// # let _ = profile;
+    /// ```
+    #[must_use]
+    pub fn allow_environment_prefix(mut self, prefix: impl Into<String>) -> Self {
+        let current = std::mem::take(&mut self.environment);
+        self.environment = current.with_allowed_prefix(prefix.into());
+        self
+    }
+
     /// Inherit all environment variables from the parent process.
     #[must_use]
     pub fn allow_full_environment(mut self) -> Self {
@@ -119,6 +159,42 @@ impl SandboxProfile {
         self
     }
 
+    /// Bounds the wall-clock time a sandboxed process may run.
+    ///
+    /// Once the limit elapses after spawning,
+    /// [`SandboxChild::wait_with_output`](crate::SandboxChild::wait_with_output)
+    /// kills the process and reports [`SandboxError::TimedOut`](crate::SandboxError::TimedOut).
+    /// Profiles have no limit by default.
+    #[must_use]
+    pub fn with_time_limit(mut self, limit: Duration) -> Self {
+        self.time_limit = Some(limit);
+        self
+    }
+
+    /// Caps the address space of the sandboxed process at `bytes`.
+    ///
+    /// On Linux the spawned child receives an `RLIMIT_AS` limit of `bytes`, so
+    /// allocations beyond it fail and a memory-hungry plugin dies instead of
+    /// exhausting the host. The limit is applied as soon as the sandbox has
+    /// launched the command, so it is best-effort for the first instants of
+    /// the command's life, and it is accepted but not enforced on other
+    /// platforms. Address space includes mapped libraries and reserved stacks,
+    /// so leave generous headroom above the expected working set.
+    #[must_use]
+    pub fn with_memory_limit(mut self, bytes: u64) -> Self {
+        self.memory_limit = Some(bytes);
+        self
+    }
+
+    /// Returns the read-only paths as configured, before canonicalisation.
+    pub(crate) fn read_only_paths(&self) -> &[PathBuf] { &self.read_only_paths }
+
+    /// Returns the read-write paths as configured, before canonicalisation.
+    pub(crate) fn read_write_paths(&self) -> &[PathBuf] { &self.read_write_paths }
+
+    /// Returns the executable paths as configured, before canonicalisation.
+    pub(crate) fn executable_paths(&self) -> &[PathBuf] { &self.executable_paths }
+
     pub(crate) fn read_only_paths_canonicalised(
         &self,
     ) -> Result<&Vec<PathBuf>, crate::SandboxError> {
@@ -140,6 +216,14 @@ impl SandboxProfile {
     /// Returns the configured environment policy.
     pub(crate) fn environment_policy(&self) -> &EnvironmentPolicy { &self.environment }
 
+    /// Returns the configured time limit, if any.
+    #[must_use]
+    pub fn time_limit(&self) -> Option<Duration> { self.time_limit }
+
+    /// Returns the configured memory limit in bytes, if any.
+    #[must_use]
+    pub fn memory_limit(&self) -> Option<u64> { self.memory_limit }
+
     /// Returns the network policy.
     #[must_use]
     pub fn network_policy(&self) -> NetworkPolicy { self.network }
@@ -167,28 +251,61 @@ impl NetworkPolicy {
 
 impl EnvironmentPolicy {
     pub(crate) fn with_allowed(self, key: String) -> Self {
+        self.extend_allow_list(|names, _| {
+            let _ = names.insert(key);
+        })
+    }
+
+    pub(crate) fn with_allowed_prefix(self, prefix: String) -> Self {
+        self.extend_allow_list(|_, prefixes| {
+            let _ = prefixes.insert(prefix);
+        })
+    }
+
+    fn extend_allow_list(
+        self,
+        update: impl FnOnce(&mut BTreeSet<String>, &mut BTreeSet<String>),
+    ) -> Self {
         match self {
             Self::Isolated => {
-                let mut allow = BTreeSet::new();
-                allow.insert(key);
-                Self::AllowList(allow)
+                let mut names = BTreeSet::new();
+                let mut prefixes = BTreeSet::new();
+                update(&mut names, &mut prefixes);
+                Self::AllowList { names, prefixes }
             }
-            Self::AllowList(mut keys) => {
-                let _ = keys.insert(key);
-                Self::AllowList(keys)
+            Self::AllowList {
+                mut names,
+                mut prefixes,
+            } => {
+                update(&mut names, &mut prefixes);
+                Self::AllowList { names, prefixes }
             }
             Self::InheritAll => Self::InheritAll,
         }
     }
 
+    /// Builds the `birdcage` exceptions for this policy.
+    ///
+    /// Prefixes are expanded against the current process environment, so this
+    /// must run at spawn time. Variables with non-UTF-8 names cannot be
+    /// expressed as `birdcage` exceptions and are never matched.
     pub(crate) fn to_exceptions(&self) -> Vec<birdcage::Exception> {
         match self {
             Self::Isolated => Vec::new(),
-            Self::AllowList(keys) => keys
-                .iter()
-                .cloned()
-                .map(birdcage::Exception::Environment)
-                .collect(),
+            Self::AllowList { names, prefixes } => {
+                let mut allowed = names.clone();
+                if !prefixes.is_empty() {
+                    allowed.extend(
+                        std::env::vars_os()
+                            .filter_map(|(key, _)| key.into_string().ok())
+                            .filter(|key| prefixes.iter().any(|prefix| key.starts_with(prefix))),
+                    );
+                }
+                allowed
+                    .into_iter()
+                    .map(birdcage::Exception::Environment)
+                    .collect()
+            }
             Self::InheritAll => vec![birdcage::Exception::FullEnvironment],
         }
     }
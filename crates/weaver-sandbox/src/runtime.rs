@@ -7,8 +7,11 @@ use std::{io, path::PathBuf};
 pub fn linux_runtime_roots() -> Vec<PathBuf> {
     #[cfg(target_os = "linux")]
     {
-        use std::{fs, path::Path};
+        use std::path::Path;
 
+        // Keep the paths as listed rather than canonicalised: on merged-`/usr`
+        // hosts `/lib64` is a symlink the dynamic loader is resolved through,
+        // and `birdcage` only recreates symlinks it is given.
         let candidates = [
             "/lib",
             "/lib64",
@@ -21,14 +24,9 @@ pub fn linux_runtime_roots() -> Vec<PathBuf> {
         ];
         candidates
             .iter()
-            .filter_map(|path| {
-                let candidate = Path::new(path);
-                if candidate.exists() {
-                    fs::canonicalize(candidate).ok()
-                } else {
-                    None
-                }
-            })
+            .map(Path::new)
+            .filter(|candidate| candidate.exists())
+            .map(Path::to_path_buf)
             .collect()
     }
 
@@ -10,20 +10,20 @@ use birdcage::{
     Birdcage,
     Exception,
     Sandbox as BirdcageTrait,
-    process::{Child, Command, Output},
+    process::{Command, Output},
 };
 
 use crate::{
+    child::SandboxChild,
     env_guard::EnvGuard,
     error::SandboxError,
+    limits::limit_address_space,
     profile::{NetworkPolicy, SandboxProfile},
-    runtime::thread_count,
+    runtime::{linux_runtime_roots, thread_count},
 };
 
 /// Builder for sandboxed commands.
 pub type SandboxCommand = Command;
-/// Handle to a running sandboxed process.
-pub type SandboxChild = Child;
 /// Captured output from a sandboxed process.
 pub type SandboxOutput = Output;
 
@@ -67,7 +67,9 @@ impl Sandbox {
     /// The command's program path must be absolute and whitelisted on the
     /// profile. When more than one thread exists in the current process the
     /// function returns [`SandboxError::MultiThreaded`] to avoid triggering the
-    /// single-thread assertion enforced by `birdcage`.
+    /// single-thread assertion enforced by `birdcage`. The returned child
+    /// carries the profile's time limit, if one was configured, and has its
+    /// memory limit applied before this function returns.
     pub fn spawn(&self, command: SandboxCommand) -> Result<SandboxChild, SandboxError> {
         self.ensure_single_threaded()?;
         let program = Self::canonical_program(Path::new(command.get_program()))?;
@@ -81,9 +83,18 @@ impl Sandbox {
             sandbox.add_exception(exception)?;
         }
 
-        let child = sandbox.spawn(command)?;
+        let mut child = sandbox.spawn(command)?;
         drop(env_guard);
-        Ok(child)
+        if let Some(bytes) = self.profile.memory_limit()
+            && let Err(error) = limit_address_space(child.id(), bytes)
+        {
+            // The profile promised a bounded child, so never leave one running
+            // without its limit.
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(error);
+        }
+        Ok(SandboxChild::new(child, self.profile.time_limit()))
     }
 
     fn ensure_single_threaded(&self) -> Result<(), SandboxError> {
@@ -109,17 +120,27 @@ impl Sandbox {
 
     fn collect_exceptions(&self, _program: &Path) -> Result<Vec<Exception>, SandboxError> {
         let mut exceptions = Vec::new();
-        let read_only = self.profile.read_only_paths_canonicalised()?;
-        let read_write = self.profile.read_write_paths_canonicalised()?;
-        let executables = self.profile.executable_paths_canonicalised()?;
-
-        for path in read_only {
-            exceptions.push(Exception::Read(path.clone()));
+        // Canonicalising validates every path, but `birdcage` receives them as
+        // configured: it resolves them itself and recreates the symlinks along
+        // the way, such as a merged-`/usr` `/lib64` the dynamic loader needs.
+        self.profile.read_only_paths_canonicalised()?;
+        self.profile.read_write_paths_canonicalised()?;
+        self.profile.executable_paths_canonicalised()?;
+
+        let runtime_roots = linux_runtime_roots();
+        for path in self.profile.read_only_paths() {
+            // The dynamic loader maps shared libraries executable, which the
+            // `noexec` mount behind a plain read exception refuses.
+            if runtime_roots.contains(path) {
+                exceptions.push(Exception::ExecuteAndRead(path.clone()));
+            } else {
+                exceptions.push(Exception::Read(path.clone()));
+            }
         }
-        for path in read_write {
+        for path in self.profile.read_write_paths() {
             exceptions.push(Exception::WriteAndRead(path.clone()));
         }
-        for path in executables {
+        for path in self.profile.executable_paths() {
             exceptions.push(Exception::ExecuteAndRead(path.clone()));
         }
 
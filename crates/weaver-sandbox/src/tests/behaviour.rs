@@ -1,20 +1,23 @@
 #![cfg(target_os = "linux")]
 //! Behavioural tests for sandbox spawning using `rstest-bdd`.
 
-use std::cell::RefCell;
+use std::{
+    cell::RefCell,
+    time::{Duration, Instant},
+};
 
 use rstest::fixture;
 use rstest_bdd_macros::{given, scenario, then, when};
+use weaver_test_macros::allow_fixture_expansion_lints;
 
-use crate::tests::support::TestWorld;
+use crate::{SandboxError, tests::support::TestWorld};
 
+#[allow_fixture_expansion_lints]
 #[fixture]
-fn world() -> RefCell<TestWorld> {
-    RefCell::new(TestWorld::new())
-}
+fn world() -> RefCell<TestWorld> { RefCell::new(TestWorld::new()) }
 
 #[given("a sandbox world with fixture files")]
-fn given_world(_world: &RefCell<TestWorld>) {}
+fn given_world(world: &RefCell<TestWorld>) { let _ = world; }
 
 #[given("the command cats the allowed file")]
 fn given_allowed_cat(world: &RefCell<TestWorld>) {
@@ -30,6 +33,45 @@ fn given_forbidden_cat(world: &RefCell<TestWorld>) {
     w.configure_cat(&target);
 }
 
+#[given("the command appends to the allowed file")]
+fn given_allowed_append(world: &RefCell<TestWorld>) {
+    let mut w = world.borrow_mut();
+    let target = w.allowed_file.clone();
+    w.configure_append(&target);
+}
+
+#[given("the sandbox grants read-write access to the fixture file")]
+fn given_profile_allows_fixture_writes(world: &RefCell<TestWorld>) {
+    let mut w = world.borrow_mut();
+    let allowed = w.allowed_file.clone();
+    w.profile = w.profile.clone().allow_read_write_path(&allowed);
+}
+
+#[given("the command sleeps for {seconds} seconds")]
+fn given_sleep(world: &RefCell<TestWorld>, seconds: u64) {
+    world
+        .borrow_mut()
+        .configure_sleep(Duration::from_secs(seconds));
+}
+
+#[given("the sandbox limits the command to {millis} milliseconds")]
+fn given_time_limit(world: &RefCell<TestWorld>, millis: u64) {
+    let mut w = world.borrow_mut();
+    w.profile = w
+        .profile
+        .clone()
+        .with_time_limit(Duration::from_millis(millis));
+}
+
+#[given("the command allocates memory without bound")]
+fn given_memory_hog(world: &RefCell<TestWorld>) { world.borrow_mut().configure_memory_hog(); }
+
+#[given("the sandbox limits the command to {mebibytes} MiB of memory")]
+fn given_memory_limit(world: &RefCell<TestWorld>, mebibytes: u64) {
+    let mut w = world.borrow_mut();
+    w.profile = w.profile.clone().with_memory_limit(mebibytes << 20);
+}
+
 #[given("the sandbox allows the command and fixture file")]
 fn given_profile_allows_fixture(world: &RefCell<TestWorld>) {
     let mut w = world.borrow_mut();
@@ -38,7 +80,7 @@ fn given_profile_allows_fixture(world: &RefCell<TestWorld>) {
         .as_ref()
         .expect("command not configured")
         .get_program()
-        .to_path_buf();
+        .to_owned();
     let allowed = w.allowed_file.clone();
     w.profile = w
         .profile
@@ -56,12 +98,26 @@ fn given_environment_variables(world: &RefCell<TestWorld>) {
 
 #[given("the sandbox allows only KEEP_ME to be inherited")]
 fn given_environment_allowlist(world: &RefCell<TestWorld>) {
+    let mut world = world.borrow_mut();
+    world.configure_env_reader();
+    world.profile = world.profile.clone().allow_environment_variable("KEEP_ME");
+}
+
+#[given("environment variables WEAVER_PREFIX_MARKER and DROP_ME are set")]
+fn given_prefixed_environment_variables(world: &RefCell<TestWorld>) {
+    let mut world = world.borrow_mut();
+    world.set_env_var("WEAVER_PREFIX_MARKER", "matched");
+    world.set_env_var("DROP_ME", "remove-me");
+}
+
+#[given("the sandbox allows variables prefixed with WEAVER_PREFIX_")]
+fn given_environment_prefix(world: &RefCell<TestWorld>) {
     let mut world = world.borrow_mut();
     world.configure_env_reader();
     world.profile = world
         .profile
         .clone()
-        .allow_environment_variable("KEEP_ME");
+        .allow_environment_prefix("WEAVER_PREFIX_");
 }
 
 #[given("the sandbox uses the default environment isolation")]
@@ -78,8 +134,13 @@ fn given_environment_full_inheritance(world: &RefCell<TestWorld>) {
 }
 
 #[when("the sandbox launches the command")]
-fn when_launch(world: &RefCell<TestWorld>) {
+fn when_launch(world: &RefCell<TestWorld>) { world.borrow_mut().launch(); }
+
+#[when("the sandbox launches the command and times the wait")]
+fn when_launch_timed(world: &RefCell<TestWorld>) {
+    let started = Instant::now();
     world.borrow_mut().launch();
+    world.borrow_mut().elapsed = Some(started.elapsed());
 }
 
 #[then("the sandboxed process succeeds")]
@@ -111,6 +172,32 @@ fn then_process_fails(world: &RefCell<TestWorld>) {
     );
 }
 
+#[then("the sandbox reports a timeout within {seconds} seconds")]
+fn then_timed_out(world: &RefCell<TestWorld>, seconds: u64) {
+    let world = world.borrow();
+    assert!(
+        matches!(world.wait_error, Some(SandboxError::TimedOut { .. })),
+        "expected a timeout, got {:?}",
+        world.wait_error
+    );
+    let elapsed = world.elapsed.expect("wait was not timed");
+    assert!(
+        elapsed < Duration::from_secs(seconds),
+        "the process was not killed promptly: {elapsed:?}"
+    );
+}
+
+#[then("stderr contains {text}")]
+fn then_stderr_contains(world: &RefCell<TestWorld>, text: String) {
+    let world = world.borrow();
+    let output = world.output.as_ref().expect("process output missing");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(text.trim_matches('"')),
+        "stderr did not contain expected text. stderr={stderr:?}"
+    );
+}
+
 #[then("stdout contains {text}")]
 fn then_stdout_contains(world: &RefCell<TestWorld>, text: String) {
     let world = world.borrow();
@@ -133,6 +220,16 @@ fn then_stdout_absent(world: &RefCell<TestWorld>, text: String) {
     );
 }
 
+#[then("the sandboxed process reports an error")]
+fn then_process_reports_error(world: &RefCell<TestWorld>) {
+    let world = world.borrow();
+    let output = world.output.as_ref().expect("process output missing");
+    assert!(
+        !output.stderr.is_empty(),
+        "a denied write should be reported on stderr"
+    );
+}
+
 #[then("environment markers are cleaned up")]
 fn then_environment_cleaned(world: &RefCell<TestWorld>) {
     world.borrow_mut().restore_env();
@@ -149,6 +246,34 @@ fn then_environment_cleaned(world: &RefCell<TestWorld>) {
 }
 
 #[scenario(path = "tests/features/sandbox.feature")]
-fn sandbox_behaviour(world: RefCell<TestWorld>) {
-    let _ = world;
-}
+fn sandbox_behaviour(world: RefCell<TestWorld>) { let _ = world; }
+
+#[scenario(
+    path = "tests/features/sandbox.feature",
+    name = "Read-only file cannot be written"
+)]
+fn read_only_file_cannot_be_written(world: RefCell<TestWorld>) { let _ = world; }
+
+#[scenario(
+    path = "tests/features/sandbox.feature",
+    name = "Read-write file can be written"
+)]
+fn read_write_file_can_be_written(world: RefCell<TestWorld>) { let _ = world; }
+
+#[scenario(
+    path = "tests/features/sandbox.feature",
+    name = "Environment variables matching an allowed prefix are inherited"
+)]
+fn environment_prefix_is_inherited(world: RefCell<TestWorld>) { let _ = world; }
+
+#[scenario(
+    path = "tests/features/sandbox.feature",
+    name = "Commands exceeding the time limit are killed"
+)]
+fn commands_exceeding_time_limit_are_killed(world: RefCell<TestWorld>) { let _ = world; }
+
+#[scenario(
+    path = "tests/features/sandbox.feature",
+    name = "Commands exceeding the memory limit fail"
+)]
+fn commands_exceeding_memory_limit_fail(world: RefCell<TestWorld>) { let _ = world; }
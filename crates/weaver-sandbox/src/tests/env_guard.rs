@@ -2,8 +2,7 @@
 
 use std::env;
 
-use crate::env_guard::EnvGuard;
-use crate::tests::support::lock_env;
+use crate::{env_guard::EnvGuard, tests::support::lock_env};
 
 #[test]
 fn restores_modified_and_removed_environment_variables() {
@@ -2,5 +2,6 @@
 
 mod behaviour;
 mod env_guard;
+mod sandbox_unit;
 mod support;
 mod unit;
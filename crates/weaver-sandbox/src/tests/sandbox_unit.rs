@@ -1,10 +1,15 @@
 //! Unit tests covering sandbox spawn preflight errors.
 
-use std::io;
-use std::path::PathBuf;
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
 
-use crate::sandbox::{Sandbox, SandboxCommand};
-use crate::{SandboxError, SandboxProfile};
+use crate::{
+    SandboxError,
+    SandboxProfile,
+    sandbox::{Sandbox, SandboxCommand},
+};
 
 fn sandbox_with_forced_thread_count<F>(profile: SandboxProfile, counter: F) -> Sandbox
 where
@@ -13,21 +18,22 @@ where
     Sandbox::with_thread_counter_for_tests(profile, Box::new(counter))
 }
 
-fn spawn_expect_error(program: &PathBuf, profile: SandboxProfile) -> SandboxError {
+fn spawn_expect_error(program: &Path, profile: SandboxProfile) -> SandboxError {
     let mut command = SandboxCommand::new(program);
     command.arg("hello");
 
-    Sandbox::new(profile)
+    sandbox_with_forced_thread_count(profile, || Ok(1))
         .spawn(command)
-        .expect_err("spawn should fail")
+        .err()
+        .expect("spawn should fail")
 }
 
 #[test]
 fn rejects_relative_program_paths() {
-    let sandbox = Sandbox::new(SandboxProfile::new());
+    let sandbox = sandbox_with_forced_thread_count(SandboxProfile::new(), || Ok(1));
     let command = SandboxCommand::new("relative/bin");
 
-    let err = sandbox.spawn(command).expect_err("spawn should fail");
+    let err = sandbox.spawn(command).err().expect("spawn should fail");
     match err {
         SandboxError::ProgramNotAbsolute(path) => {
             assert_eq!(path, PathBuf::from("relative/bin"));
@@ -51,21 +57,23 @@ fn rejects_unwhitelisted_programs() {
     let program = PathBuf::from("/bin/echo");
     let error = spawn_expect_error(&program, SandboxProfile::new());
     match error {
-        SandboxError::ExecutableNotAuthorised { program: p } => assert_eq!(p, program),
+        SandboxError::ExecutableNotAuthorised { program: p } => {
+            let canonical = program.canonicalize().expect("echo should resolve");
+            assert_eq!(p, canonical);
+        }
         other => panic!("unexpected error: {other:?}"),
     }
 }
 
 #[test]
 fn rejects_multithreaded_processes() {
-    let sandbox = sandbox_with_forced_thread_count(SandboxProfile::new(), || {
-        Ok(4)
-    });
+    let sandbox = sandbox_with_forced_thread_count(SandboxProfile::new(), || Ok(4));
     let command = SandboxCommand::new("/usr/bin/true");
 
     let err = sandbox
         .spawn(command)
-        .expect_err("spawn should fail for multi-threaded processes");
+        .err()
+        .expect("spawn should fail for multi-threaded processes");
     match err {
         SandboxError::MultiThreaded { thread_count } => assert_eq!(thread_count, 4),
         other => panic!("expected MultiThreaded error, got: {other:?}"),
@@ -75,13 +83,14 @@ fn rejects_multithreaded_processes() {
 #[test]
 fn rejects_when_thread_count_unavailable() {
     let sandbox = sandbox_with_forced_thread_count(SandboxProfile::new(), || {
-        Err(io::Error::new(io::ErrorKind::Other, "thread count failed"))
+        Err(io::Error::other("thread count failed"))
     });
     let command = SandboxCommand::new("/usr/bin/true");
 
     let err = sandbox
         .spawn(command)
-        .expect_err("spawn should fail when thread count is unavailable");
+        .err()
+        .expect("spawn should fail when thread count is unavailable");
     match err {
         SandboxError::ThreadCountUnavailable { .. } => {}
         other => panic!("expected ThreadCountUnavailable error, got: {other:?}"),
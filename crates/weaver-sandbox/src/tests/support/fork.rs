@@ -0,0 +1,146 @@
+//! Single-threaded sandbox launches for behavioural tests.
+//!
+//! `birdcage` asserts that it spawns from a single-threaded process, but
+//! libtest runs every test on a worker thread. Forking copies only the calling
+//! thread, so each launch runs in a forked child that spawns the sandbox,
+//! waits for the command, and reports the outcome to the test over a pipe.
+
+use std::{
+    io::{self, PipeReader, PipeWriter, Read, Write},
+    os::unix::process::ExitStatusExt,
+    panic::{self, AssertUnwindSafe},
+    process::ExitStatus,
+    time::Duration,
+};
+
+use crate::{
+    error::SandboxError,
+    sandbox::{Sandbox, SandboxCommand, SandboxOutput},
+};
+
+const COMPLETED: u8 = 0;
+const REFUSED: u8 = 1;
+const TIMED_OUT: u8 = 2;
+const WAIT_FAILED: u8 = 3;
+
+/// Outcome of launching a command and waiting for it to finish.
+#[derive(Debug)]
+pub(crate) enum LaunchOutcome {
+    /// The command ran to completion.
+    Completed(SandboxOutput),
+    /// The sandbox refused to spawn the command.
+    Refused(String),
+    /// Waiting for the command failed, including by exceeding a time limit.
+    WaitFailed(SandboxError),
+}
+
+/// Launches `command` in `sandbox` from a forked, single-threaded child.
+pub(crate) fn launch_in_fork(sandbox: &Sandbox, command: SandboxCommand) -> LaunchOutcome {
+    let (reader, writer) = io::pipe().expect("failed to create outcome pipe");
+    // SAFETY: the child only runs the launch and then calls `_exit`, so it
+    // never returns into the test harness that was copied from the parent.
+    match unsafe { libc::fork() } {
+        -1 => panic!("fork failed: {}", io::Error::last_os_error()),
+        0 => {
+            drop(reader);
+            report_and_exit(writer, sandbox, command)
+        }
+        pid => {
+            drop(writer);
+            let outcome = read_outcome(reader);
+            let mut status = 0;
+            // SAFETY: `pid` is the child forked above and is reaped only here.
+            unsafe { libc::waitpid(pid, &raw mut status, 0) };
+            outcome
+        }
+    }
+}
+
+fn report_and_exit(mut writer: PipeWriter, sandbox: &Sandbox, command: SandboxCommand) -> ! {
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| launch(sandbox, command)))
+        .unwrap_or_else(|_| LaunchOutcome::Refused(String::from("the launch panicked")));
+    let code = i32::from(writer.write_all(&encode(&outcome)).is_err());
+    // SAFETY: `_exit` skips the copied harness's destructors and atexit hooks,
+    // which belong to the parent process.
+    unsafe { libc::_exit(code) }
+}
+
+fn launch(sandbox: &Sandbox, command: SandboxCommand) -> LaunchOutcome {
+    match sandbox.spawn(command) {
+        Ok(child) => match child.wait_with_output() {
+            Ok(output) => LaunchOutcome::Completed(output),
+            Err(error) => LaunchOutcome::WaitFailed(error),
+        },
+        Err(error) => LaunchOutcome::Refused(error.to_string()),
+    }
+}
+
+fn encode(outcome: &LaunchOutcome) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    match outcome {
+        LaunchOutcome::Completed(output) => {
+            bytes.push(COMPLETED);
+            bytes.extend_from_slice(&output.status.into_raw().to_le_bytes());
+            bytes.extend_from_slice(&(output.stdout.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(&output.stdout);
+            bytes.extend_from_slice(&output.stderr);
+        }
+        LaunchOutcome::Refused(message) => {
+            bytes.push(REFUSED);
+            bytes.extend_from_slice(message.as_bytes());
+        }
+        LaunchOutcome::WaitFailed(SandboxError::TimedOut { limit }) => {
+            bytes.push(TIMED_OUT);
+            bytes.extend_from_slice(&limit.as_secs().to_le_bytes());
+            bytes.extend_from_slice(&limit.subsec_nanos().to_le_bytes());
+        }
+        LaunchOutcome::WaitFailed(error) => {
+            bytes.push(WAIT_FAILED);
+            bytes.extend_from_slice(error.to_string().as_bytes());
+        }
+    }
+    bytes
+}
+
+fn read_outcome(mut reader: PipeReader) -> LaunchOutcome {
+    let mut bytes = Vec::new();
+    reader
+        .read_to_end(&mut bytes)
+        .expect("failed to read launch outcome");
+    let (&tag, body) = bytes
+        .split_first()
+        .expect("forked launch exited without reporting an outcome");
+    match tag {
+        COMPLETED => {
+            let (status, rest) = body.split_at(4);
+            let (length, streams) = rest.split_at(8);
+            let length = usize::try_from(u64::from_le_bytes(fixed(length)))
+                .expect("stdout length fits in memory");
+            let (stdout, stderr) = streams.split_at(length);
+            LaunchOutcome::Completed(SandboxOutput {
+                status: ExitStatus::from_raw(i32::from_le_bytes(fixed(status))),
+                stdout: stdout.to_vec(),
+                stderr: stderr.to_vec(),
+            })
+        }
+        REFUSED => LaunchOutcome::Refused(String::from_utf8_lossy(body).into_owned()),
+        TIMED_OUT => {
+            let (seconds, nanos) = body.split_at(8);
+            let limit = Duration::new(
+                u64::from_le_bytes(fixed(seconds)),
+                u32::from_le_bytes(fixed(nanos)),
+            );
+            LaunchOutcome::WaitFailed(SandboxError::TimedOut { limit })
+        }
+        WAIT_FAILED => LaunchOutcome::WaitFailed(SandboxError::Wait {
+            source: io::Error::other(String::from_utf8_lossy(body).into_owned()),
+        }),
+        other => panic!("unknown launch outcome tag {other}"),
+    }
+}
+
+fn fixed<const N: usize>(bytes: &[u8]) -> [u8; N] {
+    bytes
+        .try_into()
+        .expect("launch outcome field has a fixed width")
+}
@@ -1,30 +1,40 @@
 //! Shared fixtures for sandbox behavioural tests.
 
-use std::fs;
-use std::io::Write;
-use std::path::{Path, PathBuf};
-use std::sync::MutexGuard;
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::MutexGuard,
+    time::Duration,
+};
+
 use tempfile::TempDir;
 
-use crate::error::SandboxError;
-use crate::env_guard::EnvGuard;
-use crate::process::Stdio;
-use crate::profile::SandboxProfile;
-use crate::sandbox::{Sandbox, SandboxChild, SandboxCommand, SandboxOutput};
+use crate::{
+    env_guard::EnvGuard,
+    error::SandboxError,
+    process::Stdio,
+    profile::SandboxProfile,
+    sandbox::{Sandbox, SandboxCommand, SandboxOutput},
+};
 
 mod env;
+#[cfg(target_os = "linux")]
+mod fork;
 pub(crate) use env::lock_env;
+#[cfg(target_os = "linux")]
+use fork::{LaunchOutcome, launch_in_fork};
 
 #[derive(Debug)]
 struct EnvHandle {
-    guard: MutexGuard<'static, ()>,
+    _guard: MutexGuard<'static, ()>,
     snapshot: EnvGuard,
 }
 
 impl EnvHandle {
     fn acquire() -> Self {
         Self {
-            guard: lock_env(),
+            _guard: lock_env(),
             snapshot: EnvGuard::capture(),
         }
     }
@@ -50,8 +60,10 @@ pub struct TestWorld {
     pub profile: SandboxProfile,
     pub command: Option<SandboxCommand>,
     pub output: Option<SandboxOutput>,
-    pub launch_error: Option<SandboxError>,
-    pub temp_dir: TempDir,
+    pub launch_error: Option<String>,
+    pub wait_error: Option<SandboxError>,
+    pub elapsed: Option<Duration>,
+    _temp_dir: TempDir,
     pub allowed_file: PathBuf,
     pub forbidden_file: PathBuf,
     env: Option<EnvHandle>,
@@ -71,7 +83,9 @@ impl TestWorld {
             command: None,
             output: None,
             launch_error: None,
-            temp_dir,
+            wait_error: None,
+            elapsed: None,
+            _temp_dir: temp_dir,
             allowed_file,
             forbidden_file,
             env: None,
@@ -89,14 +103,54 @@ impl TestWorld {
         self.command = Some(command);
     }
 
-    pub fn configure_env_reader(&mut self) {
-        let mut command = SandboxCommand::new(resolve_binary(&["/usr/bin/env", "/bin/env"]));
+    /// Configures `tee -a` to append its empty standard input to `target`.
+    ///
+    /// `tee` exits non-zero when it cannot open the file for writing, which
+    /// makes it a minimal probe for write access.
+    pub fn configure_append(&mut self, target: &Path) {
+        let mut command = SandboxCommand::new(resolve_binary(&["/usr/bin/tee", "/bin/tee"]));
+        command.arg("-a").arg(target);
+        command.stdin(Stdio::null());
+        command.stdout(Stdio::piped());
+        command.stderr(Stdio::piped());
+
+        self.profile = self.profile.clone().allow_executable(command.get_program());
+
+        self.command = Some(command);
+    }
+
+    pub fn configure_sleep(&mut self, duration: Duration) {
+        let mut command = SandboxCommand::new(resolve_binary(&["/bin/sleep", "/usr/bin/sleep"]));
+        command.arg(duration.as_secs().to_string());
         command.stdout(Stdio::piped());
 
+        self.profile = self.profile.clone().allow_executable(command.get_program());
+
+        self.command = Some(command);
+    }
+
+    /// Configures `tail` to read `/dev/zero`, which never yields a newline and
+    /// so grows its buffer until allocation fails.
+    pub fn configure_memory_hog(&mut self) {
+        let mut command = SandboxCommand::new(resolve_binary(&["/usr/bin/tail", "/bin/tail"]));
+        command.arg("/dev/zero");
+        command.stdout(Stdio::piped());
+        command.stderr(Stdio::piped());
+
         self.profile = self
             .profile
             .clone()
-            .allow_executable(command.get_program());
+            .allow_executable(command.get_program())
+            .allow_read_path("/dev/zero");
+
+        self.command = Some(command);
+    }
+
+    pub fn configure_env_reader(&mut self) {
+        let mut command = SandboxCommand::new(resolve_binary(&["/usr/bin/env", "/bin/env"]));
+        command.stdout(Stdio::piped());
+
+        self.profile = self.profile.clone().allow_executable(command.get_program());
 
         self.command = Some(command);
     }
@@ -111,35 +165,29 @@ impl TestWorld {
             .set_var(key, value);
     }
 
-    pub fn restore_env(&mut self) {
-        self.env = None;
-    }
+    pub fn restore_env(&mut self) { self.env = None; }
 
+    /// Launches the configured command and records how it finished.
+    ///
+    /// The launch runs in a forked child so `birdcage` sees a single-threaded
+    /// process despite the test harness's worker threads.
+    #[cfg(target_os = "linux")]
     pub fn launch(&mut self) {
         let profile = self.profile.clone();
         let Some(command) = self.command.take() else {
             panic!("command not configured");
         };
 
-        let sandbox = Sandbox::new(profile);
-        match sandbox.spawn(command) {
-            Ok(child) => self.capture_output(child),
-            Err(error) => self.launch_error = Some(error),
+        match launch_in_fork(&Sandbox::new(profile), command) {
+            LaunchOutcome::Completed(output) => self.output = Some(output),
+            LaunchOutcome::Refused(message) => self.launch_error = Some(message),
+            LaunchOutcome::WaitFailed(error) => self.wait_error = Some(error),
         }
     }
-
-    pub fn capture_output(&mut self, mut child: SandboxChild) {
-        let output = child
-            .wait_with_output()
-            .unwrap_or_else(|error| panic!("failed to read child output: {error}"));
-        self.output = Some(output);
-    }
 }
 
 impl Drop for TestWorld {
-    fn drop(&mut self) {
-        self.restore_env();
-    }
+    fn drop(&mut self) { self.restore_env(); }
 }
 
 #[cfg(target_os = "linux")]
@@ -2,7 +2,11 @@
 
 use std::path::PathBuf;
 
-use crate::profile::{EnvironmentPolicy, NetworkPolicy, SandboxProfile};
+use crate::{
+    env_guard::EnvGuard,
+    profile::{EnvironmentPolicy, NetworkPolicy, SandboxProfile},
+    tests::support::lock_env,
+};
 
 #[test]
 fn profile_whitelists_linux_runtime_roots() {
@@ -24,14 +28,64 @@ fn environment_allowlist_deduplicates_entries() {
         .allow_environment_variable("KEEP_ME");
 
     match profile.environment_policy() {
-        EnvironmentPolicy::AllowList(keys) => {
-            assert_eq!(keys.len(), 1);
-            assert!(keys.contains("KEEP_ME"));
-        },
+        EnvironmentPolicy::AllowList { names, .. } => {
+            assert_eq!(names.len(), 1);
+            assert!(names.contains("KEEP_ME"));
+        }
         other => panic!("unexpected environment policy: {other:?}"),
     }
 }
 
+#[test]
+fn environment_allowlist_keeps_names_and_prefixes_apart() {
+    let profile = SandboxProfile::new()
+        .allow_environment_prefix("CARGO_")
+        .allow_environment_variable("KEEP_ME");
+
+    match profile.environment_policy() {
+        EnvironmentPolicy::AllowList { names, prefixes } => {
+            assert!(names.contains("KEEP_ME"));
+            assert!(prefixes.contains("CARGO_"));
+            assert!(!names.contains("CARGO_"));
+        }
+        other => panic!("unexpected environment policy: {other:?}"),
+    }
+}
+
+#[test]
+fn environment_prefixes_expand_against_the_parent_environment() {
+    const MATCHED: &str = "WEAVER_UNIT_PREFIX_MATCHED";
+    let _lock = lock_env();
+    let snapshot = EnvGuard::capture();
+    let policy = SandboxProfile::new()
+        .allow_environment_prefix("WEAVER_UNIT_PREFIX_")
+        .allow_environment_variable("KEEP_ME")
+        .environment_policy()
+        .clone();
+
+    // SAFETY: `lock_env` serialises environment mutation across tests and
+    // the snapshot is restored before the lock is released.
+    unsafe { std::env::set_var(MATCHED, "set after the profile was built") };
+    let allowed: Vec<String> = policy
+        .to_exceptions()
+        .into_iter()
+        .filter_map(|exception| match exception {
+            birdcage::Exception::Environment(name) => Some(name),
+            _ => None,
+        })
+        .collect();
+    snapshot.restore();
+
+    assert!(allowed.iter().any(|name| name == MATCHED));
+    assert!(allowed.iter().any(|name| name == "KEEP_ME"));
+    assert!(
+        allowed
+            .iter()
+            .all(|name| name == "KEEP_ME" || name.starts_with("WEAVER_UNIT_PREFIX_")),
+        "unexpected variables inherited: {allowed:?}"
+    );
+}
+
 #[test]
 fn network_is_denied_by_default() {
     let profile = SandboxProfile::new();
@@ -72,14 +126,18 @@ fn read_write_paths_are_recorded() {
         .allow_read_path(PathBuf::from("/tmp"))
         .allow_read_write_path(PathBuf::from("/var/tmp"));
 
-    assert!(profile
-        .read_only_paths()
-        .iter()
-        .any(|path| path.ends_with("tmp")));
-    assert!(profile
-        .read_write_paths()
-        .iter()
-        .any(|path| path.ends_with("tmp")));
+    assert!(
+        profile
+            .read_only_paths()
+            .iter()
+            .any(|path| path.ends_with("tmp"))
+    );
+    assert!(
+        profile
+            .read_write_paths()
+            .iter()
+            .any(|path| path.ends_with("tmp"))
+    );
 }
 
 #[test]
@@ -8,6 +8,37 @@ Feature: Sandbox process isolation
     Then the sandboxed process succeeds
     And stdout contains "allowed file content"
 
+  Scenario: Read-only file cannot be written
+    Given a sandbox world with fixture files
+    And the command appends to the allowed file
+    And the sandbox allows the command and fixture file
+    When the sandbox launches the command
+    Then the sandboxed process fails
+    And the sandboxed process reports an error
+
+  Scenario: Read-write file can be written
+    Given a sandbox world with fixture files
+    And the command appends to the allowed file
+    And the sandbox grants read-write access to the fixture file
+    When the sandbox launches the command
+    Then the sandboxed process succeeds
+
+  Scenario: Commands exceeding the time limit are killed
+    Given a sandbox world with fixture files
+    And the command sleeps for 30 seconds
+    And the sandbox limits the command to 200 milliseconds
+    When the sandbox launches the command and times the wait
+    Then the sandbox reports a timeout within 5 seconds
+
+  Scenario: Commands exceeding the memory limit fail
+    Given a sandbox world with fixture files
+    And the command allocates memory without bound
+    And the sandbox limits the command to 64 MiB of memory
+    And the sandbox limits the command to 10000 milliseconds
+    When the sandbox launches the command
+    Then the sandboxed process fails
+    And stderr contains "memory exhausted"
+
   Scenario: Disallowed file access is blocked
     Given a sandbox world with fixture files
     And the command cats the forbidden file
@@ -24,6 +55,16 @@ Feature: Sandbox process isolation
     And stdout does not contain "DROP_ME"
     And environment markers are cleaned up
 
+  Scenario: Environment variables matching an allowed prefix are inherited
+    Given a sandbox world with fixture files
+    And environment variables WEAVER_PREFIX_MARKER and DROP_ME are set
+    And the sandbox allows variables prefixed with WEAVER_PREFIX_
+    When the sandbox launches the command
+    Then the sandboxed process succeeds
+    And stdout contains "WEAVER_PREFIX_MARKER=matched"
+    And stdout does not contain "DROP_ME"
+    And environment markers are cleaned up
+
   Scenario: Environment variables are isolated by default
     Given a sandbox world with fixture files
     And environment variables KEEP_ME and DROP_ME are set
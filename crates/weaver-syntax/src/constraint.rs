@@ -0,0 +1,316 @@
+//! Constraints that restrict what a metavariable may capture.
+//!
+//! A constraint is attached inline, as in `$NAME:identifier` or
+//! `$NAME{regex: "^test_"}`, or programmatically through
+//! [`Pattern::with_constraint`]. The matcher checks
+//! every constraint on a metavariable once it has bound a capture, and
+//! rejects the candidate match if any constraint fails.
+//!
+//! [`Pattern::with_constraint`]: crate::Pattern::with_constraint
+
+use std::{iter::Peekable, str::CharIndices};
+
+use regex::Regex;
+
+use crate::{error::SyntaxError, language::SupportedLanguage, matcher::CapturedValue};
+
+type Chars<'s> = Peekable<CharIndices<'s>>;
+
+/// Key introducing a regular-expression constraint in an inline block.
+const REGEX_KEY: &str = "regex";
+
+/// A condition that a metavariable capture must satisfy for a match to
+/// succeed.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum MetaVarConstraint {
+    /// The captured text must match the regular expression.
+    Regex(Regex),
+    /// Every captured node must have this Tree-sitter node kind.
+    Kind(String),
+}
+
+impl MetaVarConstraint {
+    /// Creates a constraint requiring the captured text to match `pattern`.
+    ///
+    /// The expression is unanchored; use `^` and `$` to constrain the whole
+    /// capture.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pattern` is not a valid regular expression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weaver_syntax::MetaVarConstraint;
+    ///
+    /// let constraint = MetaVarConstraint::regex("^test_")?;
+    /// # Ok::<(), weaver_syntax::SyntaxError>(())
+    /// ```
+    pub fn regex(pattern: &str) -> Result<Self, SyntaxError> {
+        Regex::new(pattern).map(Self::Regex).map_err(|error| {
+            SyntaxError::invalid_constraint(format!("invalid regex '{pattern}': {error}"))
+        })
+    }
+
+    /// Creates a constraint requiring captured nodes to have the named
+    /// Tree-sitter node kind, such as `identifier`.
+    ///
+    /// The kind is checked against the pattern's grammar when the constraint
+    /// is attached.
+    #[must_use]
+    pub fn kind(kind: impl Into<String>) -> Self { Self::Kind(kind.into()) }
+
+    /// Returns whether `value` satisfies this constraint.
+    #[must_use]
+    pub fn is_satisfied_by(&self, value: &CapturedValue<'_>) -> bool {
+        match (self, value) {
+            (Self::Regex(regex), _) => regex.is_match(value.text()),
+            (Self::Kind(kind), CapturedValue::Single(node)) => node.node().kind() == kind,
+            (Self::Kind(kind), CapturedValue::Multiple(nodes)) => {
+                nodes.nodes().iter().all(|node| node.node().kind() == kind)
+            }
+        }
+    }
+
+    /// Checks that this constraint can ever hold in `language`.
+    pub(crate) fn validate_for(&self, language: SupportedLanguage) -> Result<(), SyntaxError> {
+        match self {
+            Self::Regex(_) => Ok(()),
+            Self::Kind(kind) => {
+                let grammar = language.tree_sitter_language();
+                if grammar.id_for_node_kind(kind, true) == 0 {
+                    return Err(SyntaxError::invalid_constraint(format!(
+                        "unknown {language} node kind '{kind}'"
+                    )));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Parses the constraints that directly follow a metavariable name.
+///
+/// An optional `:kind` filter may be followed by an optional `{...}` block.
+/// Text that does not introduce either is left in `chars` untouched.
+pub(crate) fn parse_inline_constraints(
+    chars: &mut Chars<'_>,
+) -> Result<Vec<MetaVarConstraint>, SyntaxError> {
+    let mut constraints: Vec<_> = parse_kind_filter(chars)
+        .map(MetaVarConstraint::Kind)
+        .into_iter()
+        .collect();
+    constraints.extend(parse_constraint_block(chars)?.unwrap_or_default());
+    Ok(constraints)
+}
+
+/// Parses a `:kind` filter.
+///
+/// The kind must start immediately after the colon with a lowercase letter,
+/// so Rust paths such as `$T::new` and annotations such as `$X: i32` keep
+/// their meaning.
+fn parse_kind_filter(chars: &mut Chars<'_>) -> Option<String> {
+    let mut lookahead = chars.clone();
+    lookahead.next_if(|(_, c)| *c == ':')?;
+    let (_, first) = lookahead.next_if(|(_, c)| c.is_ascii_lowercase())?;
+
+    let mut kind = String::from(first);
+    while let Some((_, c)) =
+        lookahead.next_if(|(_, c)| c.is_ascii_lowercase() || c.is_ascii_digit() || *c == '_')
+    {
+        kind.push(c);
+    }
+
+    *chars = lookahead;
+    Some(kind)
+}
+
+/// Parses a `{key: "value", ...}` constraint block.
+///
+/// Returns `Ok(None)` and leaves `chars` untouched when the upcoming text is
+/// not a constraint block, so source such as the Rust struct literal
+/// `$T{ field: 1 }` keeps its meaning.
+fn parse_constraint_block(
+    chars: &mut Chars<'_>,
+) -> Result<Option<Vec<MetaVarConstraint>>, SyntaxError> {
+    let mut lookahead = chars.clone();
+    if lookahead.next_if(|(_, c)| *c == '{').is_none() {
+        return Ok(None);
+    }
+    skip_whitespace(&mut lookahead);
+    if !starts_constraint_entry(lookahead.clone()) {
+        return Ok(None);
+    }
+
+    let mut constraints = Vec::new();
+    loop {
+        constraints.push(parse_entry(&mut lookahead)?);
+        skip_whitespace(&mut lookahead);
+        match lookahead.next() {
+            Some((_, ',')) => skip_whitespace(&mut lookahead),
+            Some((_, '}')) => break,
+            Some((offset, other)) => {
+                return Err(SyntaxError::invalid_constraint(format!(
+                    "expected ',' or '}}' at offset {offset}, found '{other}'"
+                )));
+            }
+            None => {
+                return Err(SyntaxError::invalid_constraint(
+                    "unterminated constraint block",
+                ));
+            }
+        }
+    }
+
+    *chars = lookahead;
+    Ok(Some(constraints))
+}
+
+/// Returns whether `chars` begins with a known constraint key and a colon.
+fn starts_constraint_entry(mut chars: Chars<'_>) -> bool {
+    let key = read_key(&mut chars);
+    skip_whitespace(&mut chars);
+    key == REGEX_KEY && chars.next_if(|(_, c)| *c == ':').is_some()
+}
+
+fn parse_entry(chars: &mut Chars<'_>) -> Result<MetaVarConstraint, SyntaxError> {
+    let key = read_key(chars);
+    skip_whitespace(chars);
+    if chars.next_if(|(_, c)| *c == ':').is_none() {
+        return Err(SyntaxError::invalid_constraint(format!(
+            "expected ':' after constraint key '{key}'"
+        )));
+    }
+    skip_whitespace(chars);
+    let value = parse_string(chars)?;
+
+    match key.as_str() {
+        REGEX_KEY => MetaVarConstraint::regex(&value),
+        other => Err(SyntaxError::invalid_constraint(format!(
+            "unknown constraint '{other}'"
+        ))),
+    }
+}
+
+fn read_key(chars: &mut Chars<'_>) -> String {
+    let mut key = String::new();
+    while let Some((_, c)) = chars.next_if(|(_, c)| c.is_ascii_alphabetic() || *c == '_') {
+        key.push(c);
+    }
+    key
+}
+
+/// Reads a double-quoted string.
+///
+/// `\"` yields a literal quote. Any other backslash sequence is kept verbatim
+/// so regular-expression escapes such as `\d` survive unchanged.
+fn parse_string(chars: &mut Chars<'_>) -> Result<String, SyntaxError> {
+    if chars.next_if(|(_, c)| *c == '"').is_none() {
+        return Err(SyntaxError::invalid_constraint(
+            "constraint value must be a double-quoted string",
+        ));
+    }
+
+    let mut value = String::new();
+    while let Some((_, c)) = chars.next() {
+        match (c, chars.peek().map(|(_, next)| *next)) {
+            ('"', _) => return Ok(value),
+            ('\\', Some(escaped)) => {
+                chars.next();
+                if escaped != '"' {
+                    value.push('\\');
+                }
+                value.push(escaped);
+            }
+            (other, _) => value.push(other),
+        }
+    }
+
+    Err(SyntaxError::invalid_constraint(
+        "unterminated constraint string",
+    ))
+}
+
+fn skip_whitespace(chars: &mut Chars<'_>) {
+    while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for inline constraint parsing.
+
+    use rstest::rstest;
+
+    use super::*;
+
+    fn parse(text: &str) -> Result<Vec<MetaVarConstraint>, SyntaxError> {
+        parse_inline_constraints(&mut text.char_indices().peekable())
+    }
+
+    fn describe(constraints: &[MetaVarConstraint]) -> Vec<String> {
+        constraints
+            .iter()
+            .map(|constraint| match constraint {
+                MetaVarConstraint::Regex(regex) => format!("regex {}", regex.as_str()),
+                MetaVarConstraint::Kind(kind) => format!("kind {kind}"),
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[case(r#"{regex: "^get_"}"#, vec!["regex ^get_"])]
+    #[case(r#"{ regex : "^a" , regex: "b$" }"#, vec!["regex ^a", "regex b$"])]
+    #[case(r#"{regex: "say \"hi\""}"#, vec![r#"regex say "hi""#])]
+    #[case(r#"{regex: "^\d+\\$"}"#, vec![r"regex ^\d+\\$"])]
+    #[case(":identifier = 1", vec!["kind identifier"])]
+    #[case(r#":type_identifier{regex: "^T"}"#, vec!["kind type_identifier", "regex ^T"])]
+    fn parses_inline_constraints(#[case] text: &str, #[case] expected: Vec<&str>) {
+        let constraints = parse(text).expect("parse");
+        assert_eq!(describe(&constraints), expected);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("()")]
+    #[case("{ field: 1 }")]
+    #[case("{}")]
+    #[case("::new()")]
+    #[case(": i32")]
+    #[case(":Vec")]
+    fn leaves_other_text_alone(#[case] text: &str) {
+        let mut chars = text.char_indices().peekable();
+        let parsed = parse_inline_constraints(&mut chars).expect("parse");
+        assert!(parsed.is_empty());
+        assert_eq!(chars.next().map(|(_, c)| c), text.chars().next());
+    }
+
+    #[rstest]
+    #[case(SupportedLanguage::Rust, "identifier", true)]
+    #[case(SupportedLanguage::Rust, "tuple_pattern", true)]
+    #[case(SupportedLanguage::Python, "tuple_pattern", true)]
+    #[case(SupportedLanguage::Rust, "no_such_kind", false)]
+    fn validates_kinds_against_grammar(
+        #[case] language: SupportedLanguage,
+        #[case] kind: &str,
+        #[case] valid: bool,
+    ) {
+        let result = MetaVarConstraint::kind(kind).validate_for(language);
+        assert_eq!(result.is_ok(), valid, "{kind} in {language}: {result:?}");
+    }
+
+    #[rstest]
+    #[case(r#"{regex: "("}"#, "invalid regex")]
+    #[case(r#"{regex: "^a""#, "unterminated constraint block")]
+    #[case(r#"{regex: "^a}"#, "unterminated constraint string")]
+    #[case("{regex: ^a}", "double-quoted string")]
+    #[case(r#"{regex: "^a", kind: "x"}"#, "unknown constraint 'kind'")]
+    fn rejects_malformed_blocks(#[case] text: &str, #[case] needle: &str) {
+        let error = parse(text).expect_err("malformed block should fail");
+        assert!(
+            error.to_string().contains(needle),
+            "expected '{needle}' in: {error}"
+        );
+    }
+}
@@ -0,0 +1,121 @@
+//! Unified diff rendering for rewrite results.
+//!
+//! Rewrites are reported as `diff --git` unified hunks so they can flow into
+//! patch-based tooling without re-deriving the change from the two sources.
+
+use std::path::Path;
+
+use similar::TextDiff;
+
+/// Lines of unchanged context shown around each hunk.
+const CONTEXT_LINES: usize = 3;
+
+/// Renders a unified diff turning `original` into `modified` at `path`.
+///
+/// Returns an empty string when the two sources are identical. A final line
+/// without a trailing newline is marked with `\ No newline at end of file`,
+/// so adding or removing the last newline shows up as a change.
+pub(crate) fn unified_diff(path: &Path, original: &str, modified: &str) -> String {
+    if original == modified {
+        return String::new();
+    }
+
+    let slash_path = slash_path(path);
+    let old_header = format!("a/{slash_path}");
+    let new_header = format!("b/{slash_path}");
+    let hunks = TextDiff::from_lines(original, modified)
+        .unified_diff()
+        .context_radius(CONTEXT_LINES)
+        .header(&old_header, &new_header)
+        .to_string();
+
+    format!("diff --git {old_header} {new_header}\n{hunks}")
+}
+
+/// Joins the components of `path` with forward slashes, as patch headers
+/// expect regardless of platform.
+fn slash_path(path: &Path) -> String {
+    path.iter()
+        .map(|component| component.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    //! Golden tests for unified diff rendering.
+
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn identical_sources_produce_no_diff() {
+        assert_eq!(unified_diff(Path::new("src/lib.rs"), "a\n", "a\n"), "");
+    }
+
+    #[rstest]
+    #[case::trailing_newlines(
+        "fn a() {}\nfn b() {}\n",
+        "fn a() {}\nfn c() {}\n",
+        concat!(
+            "diff --git a/src/lib.rs b/src/lib.rs\n",
+            "--- a/src/lib.rs\n",
+            "+++ b/src/lib.rs\n",
+            "@@ -1,2 +1,2 @@\n",
+            " fn a() {}\n",
+            "-fn b() {}\n",
+            "+fn c() {}\n",
+        )
+    )]
+    #[case::no_trailing_newlines(
+        "fn a() {}\nfn b() {}",
+        "fn a() {}\nfn c() {}",
+        concat!(
+            "diff --git a/src/lib.rs b/src/lib.rs\n",
+            "--- a/src/lib.rs\n",
+            "+++ b/src/lib.rs\n",
+            "@@ -1,2 +1,2 @@\n",
+            " fn a() {}\n",
+            "-fn b() {}\n",
+            "\\ No newline at end of file\n",
+            "+fn c() {}\n",
+            "\\ No newline at end of file\n",
+        )
+    )]
+    #[case::newline_added(
+        "fn a() {}",
+        "fn a() {}\n",
+        concat!(
+            "diff --git a/src/lib.rs b/src/lib.rs\n",
+            "--- a/src/lib.rs\n",
+            "+++ b/src/lib.rs\n",
+            "@@ -1 +1 @@\n",
+            "-fn a() {}\n",
+            "\\ No newline at end of file\n",
+            "+fn a() {}\n",
+        )
+    )]
+    fn renders_golden_diffs(#[case] original: &str, #[case] modified: &str, #[case] golden: &str) {
+        assert_eq!(
+            unified_diff(Path::new("src/lib.rs"), original, modified),
+            golden
+        );
+    }
+
+    #[test]
+    fn separates_distant_changes_into_hunks() {
+        let original = (1..=12)
+            .map(|line| format!("line {line}\n"))
+            .collect::<Vec<_>>()
+            .concat();
+        let modified = original
+            .replace("line 2\n", "line two\n")
+            .replace("line 11\n", "line eleven\n");
+
+        let diff = unified_diff(Path::new("notes.txt"), &original, &modified);
+
+        let headers: Vec<_> = diff.lines().filter(|line| line.starts_with("@@")).collect();
+        assert_eq!(headers, ["@@ -1,5 +1,5 @@", "@@ -8,5 +8,5 @@"]);
+    }
+}
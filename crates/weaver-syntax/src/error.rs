@@ -61,6 +61,13 @@ pub enum SyntaxError {
         message: String,
     },
 
+    /// A metavariable constraint is malformed or cannot be applied.
+    #[error("invalid metavariable constraint: {message}")]
+    InvalidConstraint {
+        /// Description of the constraint error.
+        message: String,
+    },
+
     /// Rewrite operation failed.
     #[error("rewrite failed: {message}")]
     RewriteError {
@@ -133,6 +140,14 @@ impl SyntaxError {
         }
     }
 
+    /// Creates an invalid constraint error.
+    #[must_use]
+    pub fn invalid_constraint(message: impl Into<String>) -> Self {
+        Self::InvalidConstraint {
+            message: message.into(),
+        }
+    }
+
     /// Creates a rewrite error.
     #[must_use]
     pub fn rewrite(message: impl Into<String>) -> Self {
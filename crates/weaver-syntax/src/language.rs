@@ -16,16 +16,19 @@ pub enum SupportedLanguage {
     /// Rust source files (`.rs`).
     #[default]
     Rust,
-    /// Python source files (`.py`).
+    /// Python source files (`.py`, `.pyi`).
     Python,
-    /// TypeScript source files (`.ts`, `.tsx`).
+    /// TypeScript source files (`.ts`, `.tsx`, `.mts`, `.cts`).
     TypeScript,
+    /// JavaScript source files (`.js`, `.jsx`, `.mjs`, `.cjs`).
+    JavaScript,
 }
 
 impl SupportedLanguage {
     /// Detects the language from a file extension.
     ///
-    /// Returns `None` if the extension is not recognised.
+    /// Returns `None` if the extension is not recognised. JSX files map to
+    /// [`Self::JavaScript`], whereas TSX files stay with [`Self::TypeScript`].
     ///
     /// # Examples
     ///
@@ -41,11 +44,33 @@ impl SupportedLanguage {
     #[must_use]
     pub fn from_extension(ext: &str) -> Option<Self> {
         let normalised = ext.to_ascii_lowercase();
-        match normalised.as_str() {
-            "rs" => Some(Self::Rust),
-            "py" | "pyi" => Some(Self::Python),
-            "ts" | "tsx" | "mts" | "cts" => Some(Self::TypeScript),
-            _ => None,
+        Self::all()
+            .iter()
+            .copied()
+            .find(|language| language.extensions().contains(&normalised.as_str()))
+    }
+
+    /// Returns the lower-case file extensions, without the leading dot, that
+    /// route to this language.
+    ///
+    /// [`Self::from_extension`] and [`Self::from_path`] are derived from this
+    /// table, so consumers presenting the supported set stay consistent with
+    /// detection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weaver_syntax::SupportedLanguage;
+    ///
+    /// assert_eq!(SupportedLanguage::Python.extensions(), ["py", "pyi"]);
+    /// ```
+    #[must_use]
+    pub const fn extensions(self) -> &'static [&'static str] {
+        match self {
+            Self::Rust => &["rs"],
+            Self::Python => &["py", "pyi"],
+            Self::TypeScript => &["ts", "tsx", "mts", "cts"],
+            Self::JavaScript => &["js", "jsx", "mjs", "cjs"],
         }
     }
 
@@ -82,6 +107,8 @@ impl SupportedLanguage {
             Self::Python => tree_sitter_python::LANGUAGE.into(),
             // Use a TSX-capable grammar so `.tsx` is parsed correctly.
             Self::TypeScript => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            // The JavaScript grammar parses JSX natively.
+            Self::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
         }
     }
 
@@ -94,12 +121,15 @@ impl SupportedLanguage {
             Self::Rust => "rust",
             Self::Python => "python",
             Self::TypeScript => "typescript",
+            Self::JavaScript => "javascript",
         }
     }
 
-    /// Returns all supported languages.
+    /// Returns all supported languages in a stable order.
     #[must_use]
-    pub const fn all() -> &'static [Self] { &[Self::Rust, Self::Python, Self::TypeScript] }
+    pub const fn all() -> &'static [Self] {
+        &[Self::Rust, Self::Python, Self::TypeScript, Self::JavaScript]
+    }
 }
 
 impl fmt::Display for SupportedLanguage {
@@ -126,6 +156,7 @@ impl FromStr for SupportedLanguage {
             "rust" | "rs" => Ok(Self::Rust),
             "python" | "py" => Ok(Self::Python),
             "typescript" | "ts" => Ok(Self::TypeScript),
+            "javascript" | "js" => Ok(Self::JavaScript),
             other => Err(LanguageParseError(other.to_owned())),
         }
     }
@@ -147,6 +178,10 @@ mod tests {
     #[case("tsx", SupportedLanguage::TypeScript)]
     #[case("mts", SupportedLanguage::TypeScript)]
     #[case("cts", SupportedLanguage::TypeScript)]
+    #[case("js", SupportedLanguage::JavaScript)]
+    #[case("jsx", SupportedLanguage::JavaScript)]
+    #[case("mjs", SupportedLanguage::JavaScript)]
+    #[case("cjs", SupportedLanguage::JavaScript)]
     fn from_extension_recognises_supported_languages(
         #[case] ext: &str,
         #[case] expected: SupportedLanguage,
@@ -164,6 +199,8 @@ mod tests {
     #[rstest]
     #[case("src/main.rs", SupportedLanguage::Rust)]
     #[case("script.py", SupportedLanguage::Python)]
+    #[case("src/App.jsx", SupportedLanguage::JavaScript)]
+    #[case("src/App.tsx", SupportedLanguage::TypeScript)]
     fn from_path_extracts_extension(#[case] path_str: &str, #[case] expected: SupportedLanguage) {
         assert_eq!(
             SupportedLanguage::from_path(Path::new(path_str)),
@@ -171,6 +208,41 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case("src/App.tsx", Some(SupportedLanguage::TypeScript))]
+    #[case("stubs/os.pyi", Some(SupportedLanguage::Python))]
+    #[case("SRC/LIB.RS", Some(SupportedLanguage::Rust))]
+    #[case("notes.txt", None)]
+    fn from_path_routes_by_extension_table(
+        #[case] path_str: &str,
+        #[case] expected: Option<SupportedLanguage>,
+    ) {
+        assert_eq!(SupportedLanguage::from_path(Path::new(path_str)), expected);
+    }
+
+    #[test]
+    fn every_listed_extension_routes_back_to_its_language() {
+        for &language in SupportedLanguage::all() {
+            assert!(
+                !language.extensions().is_empty(),
+                "{language} has no extensions"
+            );
+            for extension in language.extensions() {
+                assert_eq!(
+                    SupportedLanguage::from_extension(extension),
+                    Some(language),
+                    "extension {extension}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn extensions_include_tsx_and_pyi() {
+        assert!(SupportedLanguage::TypeScript.extensions().contains(&"tsx"));
+        assert!(SupportedLanguage::Python.extensions().contains(&"pyi"));
+    }
+
     #[test]
     fn from_path_returns_none_for_no_extension() {
         assert_eq!(SupportedLanguage::from_path(Path::new("Makefile")), None);
@@ -180,6 +252,8 @@ mod tests {
     #[case("rust", SupportedLanguage::Rust)]
     #[case("Python", SupportedLanguage::Python)]
     #[case("TYPESCRIPT", SupportedLanguage::TypeScript)]
+    #[case("javascript", SupportedLanguage::JavaScript)]
+    #[case("js", SupportedLanguage::JavaScript)]
     fn from_str_parses_language_names(#[case] input: &str, #[case] expected: SupportedLanguage) {
         assert_eq!(SupportedLanguage::from_str(input), Ok(expected));
     }
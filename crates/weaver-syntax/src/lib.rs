@@ -13,6 +13,9 @@
 //! - Rust (`.rs`)
 //! - Python (`.py`, `.pyi`)
 //! - TypeScript (`.ts`, `.tsx`, `.mts`, `.cts`)
+//! - JavaScript (`.js`, `.jsx`, `.mjs`, `.cjs`)
+//!
+//! JSX files are parsed as JavaScript; TSX files are parsed as TypeScript.
 //!
 //! # Pattern Language
 //!
@@ -22,6 +25,7 @@
 //! - `$VAR` - Matches any single AST node and captures it
 //! - `$_` - Matches any single AST node without capturing (wildcard)
 //! - `$$$VAR` - Matches zero or more AST nodes
+//! - `$VAR{regex: "^get_"}` - Matches a single node whose text matches the regex
 //!
 //! # Example: Pattern Matching
 //!
@@ -82,6 +86,8 @@
 //! # Ok::<(), weaver_syntax::SyntaxError>(())
 //! ```
 
+mod constraint;
+mod diff;
 mod error;
 mod language;
 mod matcher;
@@ -92,13 +98,26 @@ mod position;
 mod rewriter;
 mod syntactic_lock;
 
+pub use constraint::MetaVarConstraint;
 pub use error::SyntaxError;
 pub use language::{LanguageParseError, SupportedLanguage};
-pub use matcher::{CapturedNode, CapturedNodes, CapturedValue, MatchResult, Matcher};
+pub use matcher::{
+    CaptureRecord,
+    CapturedNode,
+    CapturedNodes,
+    CapturedValue,
+    ContextLine,
+    LineColumn,
+    MatchExcerpt,
+    MatchRecord,
+    MatchResult,
+    Matcher,
+    matches_to_json,
+};
 pub use parser::{ParseResult, Parser, SyntaxErrorInfo};
 pub use pattern::{MetaVarKind, MetaVariable, Pattern};
-pub use rewriter::{RewriteResult, RewriteRule, Rewriter};
-pub use syntactic_lock::{OwnedFile, TreeSitterSyntacticLock, ValidationFailure};
+pub use rewriter::{MAX_RULE_PASSES, RewriteResult, RewriteRule, Rewriter};
+pub use syntactic_lock::{FileValidation, OwnedFile, TreeSitterSyntacticLock, ValidationFailure};
 
 #[cfg(test)]
 mod tests;
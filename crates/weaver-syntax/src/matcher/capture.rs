@@ -35,6 +35,9 @@ pub struct CapturedNodes<'a> {
 
 impl<'a> CapturedNodes<'a> {
     /// Returns the captured nodes in order.
+    ///
+    /// Only named nodes are listed, so the commas separating `$$$ARGS` in
+    /// `foo($$$ARGS)` are omitted; they remain part of [`Self::text`].
     #[must_use]
     pub fn nodes(&self) -> &[CapturedNode<'a>] { &self.nodes }
 
@@ -126,6 +129,8 @@ impl<'a> Captures<'a> {
 
     pub(super) fn into_inner(self) -> HashMap<String, CapturedValue<'a>> { self.inner }
 
+    pub(super) fn get(&self, name: &str) -> Option<&CapturedValue<'a>> { self.inner.get(name) }
+
     pub(super) fn capture_single(&mut self, name: &str, node: tree_sitter::Node<'a>) -> bool {
         if name == "_" {
             return true;
@@ -149,6 +154,7 @@ impl<'a> Captures<'a> {
 
         let captured_nodes: Vec<_> = nodes
             .iter()
+            .filter(|node| node.is_named())
             .map(|node| CapturedNode {
                 node: *node,
                 text: slice_source_range(self.source, node.byte_range()),
@@ -177,6 +183,11 @@ impl<'a> Captures<'a> {
         self.insert_consistent(name, value)
     }
 
+    /// Binds `name` to `next`, or checks it against an earlier binding.
+    ///
+    /// A metavariable repeated within one pattern must bind equal source
+    /// text each time it appears, so `$A + $A` matches `x + x` but not
+    /// `x + y`.
     fn insert_consistent(&mut self, name: &str, next: CapturedValue<'a>) -> bool {
         let Some(existing) = self.inner.get(name) else {
             self.inner.insert(name.to_owned(), next);
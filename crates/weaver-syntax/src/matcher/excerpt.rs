@@ -0,0 +1,179 @@
+//! Source excerpts surrounding a match.
+//!
+//! Structural search results are easier to read alongside the lines around
+//! them, as `grep --context` shows. [`MatchExcerpt`] holds the lines spanned
+//! by a match plus a requested number of leading and trailing lines, each
+//! tagged with its one-based line number.
+
+/// One source line in a [`MatchExcerpt`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContextLine<'a> {
+    number: u32,
+    text: &'a str,
+}
+
+impl<'a> ContextLine<'a> {
+    /// Returns the one-based line number.
+    #[must_use]
+    pub const fn number(&self) -> u32 { self.number }
+
+    /// Returns the line text without its line terminator.
+    #[must_use]
+    pub const fn text(&self) -> &'a str { self.text }
+}
+
+/// A match together with the source lines surrounding it.
+///
+/// Returned by [`MatchResult::context`](super::MatchResult::context). Near
+/// the start or end of the file fewer lines than requested are available, so
+/// [`Self::before`] and [`Self::after`] may be shorter than asked for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchExcerpt<'a> {
+    text: &'a str,
+    before: Vec<ContextLine<'a>>,
+    matched: Vec<ContextLine<'a>>,
+    after: Vec<ContextLine<'a>>,
+}
+
+impl<'a> MatchExcerpt<'a> {
+    /// Builds the excerpt for a match spanning the zero-based rows
+    /// `start_row..=end_row` of `source`.
+    pub(super) fn new(
+        source: &'a str,
+        text: &'a str,
+        rows: (usize, usize),
+        (before, after): (usize, usize),
+    ) -> Self {
+        let (start_row, end_row) = rows;
+        let first = start_row.saturating_sub(before);
+        let last = end_row.saturating_add(after);
+        let mut excerpt = Self {
+            text,
+            before: Vec::new(),
+            matched: Vec::new(),
+            after: Vec::new(),
+        };
+        for (row, line) in source
+            .lines()
+            .enumerate()
+            .take(last.saturating_add(1))
+            .skip(first)
+        {
+            let context_line = ContextLine {
+                number: u32::try_from(row.saturating_add(1)).unwrap_or(u32::MAX),
+                text: line,
+            };
+            let section = if row < start_row {
+                &mut excerpt.before
+            } else if row <= end_row {
+                &mut excerpt.matched
+            } else {
+                &mut excerpt.after
+            };
+            section.push(context_line);
+        }
+        excerpt
+    }
+
+    /// Returns the matched text.
+    #[must_use]
+    pub const fn text(&self) -> &'a str { self.text }
+
+    /// Returns the lines preceding the match, in source order.
+    #[must_use]
+    pub fn before(&self) -> &[ContextLine<'a>] { &self.before }
+
+    /// Returns the whole lines the match spans.
+    #[must_use]
+    pub fn matched_lines(&self) -> &[ContextLine<'a>] { &self.matched }
+
+    /// Returns the lines following the match, in source order.
+    #[must_use]
+    pub fn after(&self) -> &[ContextLine<'a>] { &self.after }
+
+    /// Iterates over every line of the excerpt in source order.
+    pub fn lines(&self) -> impl Iterator<Item = &ContextLine<'a>> {
+        self.before.iter().chain(&self.matched).chain(&self.after)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for match context excerpts.
+
+    use rstest::rstest;
+
+    use crate::{Parser, Pattern, SupportedLanguage};
+
+    const SOURCE: &str = "fn first() {}\nfn second() {}\nfn third() {}\nfn fourth() {}\n";
+
+    fn numbers(lines: &[super::ContextLine<'_>]) -> Vec<u32> {
+        lines.iter().map(super::ContextLine::number).collect()
+    }
+
+    fn excerpt_numbers(name: &str, before: usize, after: usize) -> (Vec<u32>, Vec<u32>, Vec<u32>) {
+        let mut parser = Parser::new(SupportedLanguage::Rust).expect("parser");
+        let parsed = parser.parse(SOURCE).expect("parse");
+        let pattern = Pattern::compile(&format!("fn {name}() {{}}"), SupportedLanguage::Rust)
+            .expect("pattern");
+        let found = pattern.find_first(&parsed).expect("match");
+        let context = found.context(before, after);
+        assert_eq!(context.text(), found.text());
+        (
+            numbers(context.before()),
+            numbers(context.matched_lines()),
+            numbers(context.after()),
+        )
+    }
+
+    #[test]
+    fn match_on_first_line_has_no_leading_context() {
+        let (before, matched, after) = excerpt_numbers("first", 2, 1);
+
+        assert!(before.is_empty());
+        assert_eq!(matched, [1]);
+        assert_eq!(after, [2]);
+    }
+
+    #[rstest]
+    #[case::middle("third", (1, 1), (vec![2], vec![4]))]
+    #[case::last_line("fourth", (1, 3), (vec![3], vec![]))]
+    #[case::no_context("second", (0, 0), (vec![], vec![]))]
+    fn context_is_clamped_to_the_file(
+        #[case] name: &str,
+        #[case] (before, after): (usize, usize),
+        #[case] expected: (Vec<u32>, Vec<u32>),
+    ) {
+        let (leading, _, trailing) = excerpt_numbers(name, before, after);
+
+        assert_eq!((leading, trailing), expected);
+    }
+
+    #[test]
+    fn multi_line_match_spans_every_line() {
+        let source = "// lead\nfn main() {\n    run();\n}\n// tail\n";
+        let mut parser = Parser::new(SupportedLanguage::Rust).expect("parser");
+        let parsed = parser.parse(source).expect("parse");
+        let pattern =
+            Pattern::compile("fn $NAME() { $$$BODY }", SupportedLanguage::Rust).expect("pattern");
+        let found = pattern.find_first(&parsed).expect("match");
+
+        let context = found.context(usize::MAX, usize::MAX);
+
+        let lines: Vec<_> = context
+            .lines()
+            .map(|line| (line.number(), line.text()))
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                (1, "// lead"),
+                (2, "fn main() {"),
+                (3, "    run();"),
+                (4, "}"),
+                (5, "// tail"),
+            ]
+        );
+        assert_eq!(numbers(context.matched_lines()), [2, 3, 4]);
+    }
+}
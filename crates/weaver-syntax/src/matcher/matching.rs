@@ -1,7 +1,9 @@
 //! Matching algorithms for the [`Matcher`] implementation.
 
+use std::ops::Range;
+
 use crate::{
-    matcher::{MatchResult, capture::Captures, context::MatchContext},
+    matcher::{MatchResult, capture::Captures, context::MatchContext, relations::match_at},
     metavariables::metavar_name_from_placeholder,
     parser::ParseResult,
     pattern::{MetaVarKind, MetaVariable, Pattern},
@@ -17,12 +19,30 @@ fn is_block_node_kind(kind: &str) -> bool {
 ///
 /// Returns matches in traversal order (pre-order) and borrows from `parsed`.
 pub(super) fn find_all<'a>(pattern: &Pattern, parsed: &'a ParseResult) -> Vec<MatchResult<'a>> {
+    find_in_range(pattern, parsed, 0..parsed.source().len())
+}
+
+/// Finds all matches of `pattern` whose node touches the byte `range`.
+///
+/// Subtrees lying wholly outside `range` are skipped without being matched.
+/// A node touches the range when the two overlap or share a boundary, so a
+/// match straddling either end of the range is still returned.
+pub(super) fn find_in_range<'a>(
+    pattern: &Pattern,
+    parsed: &'a ParseResult,
+    range: Range<usize>,
+) -> Vec<MatchResult<'a>> {
     let ctx = MatchContext::new(pattern, parsed.source());
     let mut results = Vec::new();
-    find_matches_recursive(parsed.root_node(), &ctx, &mut results);
+    find_matches_recursive(parsed.root_node(), &ctx, &range, &mut results);
     results
 }
 
+/// Returns whether `node` overlaps `range` or shares one of its boundaries.
+fn touches_range(node: tree_sitter::Node<'_>, range: &Range<usize>) -> bool {
+    node.start_byte() <= range.end && node.end_byte() >= range.start
+}
+
 /// Finds the first match of `pattern` in `parsed` via depth-first traversal.
 ///
 /// Returns the earliest match in traversal order (pre-order) and borrows from
@@ -36,14 +56,18 @@ pub(super) fn find_first<'a>(
 }
 
 /// Recursively traverses the source AST in pre-order, collecting all matches
-/// of the pattern. Creates a fresh capture state for each candidate node.
+/// of the pattern among nodes touching `range`.
 fn find_matches_recursive<'a>(
     source_node: tree_sitter::Node<'a>,
     ctx: &MatchContext<'a, '_>,
+    range: &Range<usize>,
     results: &mut Vec<MatchResult<'a>>,
 ) {
-    let mut captures = Captures::new(ctx.source);
-    if nodes_match(source_node, ctx.pattern_root, ctx, &mut captures) {
+    if !touches_range(source_node, range) {
+        return;
+    }
+
+    if let Some(captures) = match_at(source_node, ctx) {
         results.push(MatchResult {
             node: source_node,
             source: ctx.source,
@@ -53,18 +77,17 @@ fn find_matches_recursive<'a>(
 
     let mut cursor = source_node.walk();
     for child in source_node.children(&mut cursor) {
-        find_matches_recursive(child, ctx, results);
+        find_matches_recursive(child, ctx, range, results);
     }
 }
 
 /// Recursively traverses the source AST in pre-order, returning the first match
-/// of the pattern. Creates a fresh capture state for each candidate node.
+/// of the pattern.
 fn find_first_recursive<'a>(
     source_node: tree_sitter::Node<'a>,
     ctx: &MatchContext<'a, '_>,
 ) -> Option<MatchResult<'a>> {
-    let mut captures = Captures::new(ctx.source);
-    if nodes_match(source_node, ctx.pattern_root, ctx, &mut captures) {
+    if let Some(captures) = match_at(source_node, ctx) {
         return Some(MatchResult {
             node: source_node,
             source: ctx.source,
@@ -85,6 +108,10 @@ fn find_first_recursive<'a>(
 /// Extracts a metavariable reference from a pattern node by checking placeholder
 /// text, recursing through single-child wrapper nodes, and skipping ERROR/block
 /// nodes.
+///
+/// A spread (`$$$NAME`) only collapses through wrappers without punctuation of
+/// their own. Between delimiters, as in `foo($$$ARGS)`, the delimited node is
+/// matched structurally so the spread binds each node inside it.
 fn find_metavariable_in_pattern<'p>(
     pattern_node: tree_sitter::Node<'p>,
     ctx: &MatchContext<'_, 'p>,
@@ -105,29 +132,31 @@ fn find_metavariable_in_pattern<'p>(
     let mut cursor = pattern_node.walk();
     let mut children = pattern_node.named_children(&mut cursor);
     let child = children.next()?;
-    if children.next().is_none() {
-        return find_metavariable_in_pattern(child, ctx);
+    if children.next().is_some() {
+        return None;
     }
 
-    None
+    find_metavariable_in_pattern(child, ctx)
+        .filter(|metavar| metavar.kind == MetaVarKind::Single || pattern_node.child_count() == 1)
 }
 
 /// Checks whether `source_node` matches `pattern_node`, handling metavariables,
 /// kind comparison, leaf text comparison, and delegating to child matching.
 /// Updates `captures` if the match succeeds.
-fn nodes_match<'a>(
+pub(super) fn nodes_match<'a>(
     source_node: tree_sitter::Node<'a>,
     pattern_node: tree_sitter::Node<'_>,
     ctx: &MatchContext<'a, '_>,
     captures: &mut Captures<'a>,
 ) -> bool {
     if let Some(metavar) = find_metavariable_in_pattern(pattern_node, ctx) {
-        return match metavar.kind {
+        let bound = match metavar.kind {
             MetaVarKind::Single => captures.capture_single(&metavar.name, source_node),
             MetaVarKind::Multiple => {
                 captures.capture_multiple(&metavar.name, &[source_node], source_node.start_byte())
             }
         };
+        return bound && satisfies_constraints(metavar, ctx, captures);
     }
 
     if source_node.kind() != pattern_node.kind() {
@@ -152,6 +181,24 @@ fn nodes_match<'a>(
     match_children(source_node, pattern_node, ctx, captures)
 }
 
+/// Checks the constraints on `metavar` against the value it has captured.
+fn satisfies_constraints(
+    metavar: &MetaVariable,
+    ctx: &MatchContext<'_, '_>,
+    captures: &Captures<'_>,
+) -> bool {
+    let constraints = ctx.pattern.constraints(&metavar.name);
+    if constraints.is_empty() {
+        return true;
+    }
+
+    captures.get(&metavar.name).is_some_and(|value| {
+        constraints
+            .iter()
+            .all(|constraint| constraint.is_satisfied_by(value))
+    })
+}
+
 /// Collects all children of `node` into a Vec.
 /// Used by `match_children` and `SequenceMatcher` for backtracking over child
 /// sequences.
@@ -286,7 +333,9 @@ impl<'a, 'p> SequenceMatcher<'a, 'p, '_> {
             };
 
             let mut trial = captures.clone();
-            if !trial.capture_multiple(&metavar.name, candidate, empty_anchor_byte) {
+            if !trial.capture_multiple(&metavar.name, candidate, empty_anchor_byte)
+                || !satisfies_constraints(metavar, self.ctx, &trial)
+            {
                 continue;
             }
 
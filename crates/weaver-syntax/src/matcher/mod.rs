@@ -6,11 +6,16 @@
 
 mod capture;
 mod context;
+mod excerpt;
 mod matching;
+mod record;
+mod relations;
 
 use std::{collections::HashMap, ops::Range};
 
 pub use capture::{CapturedNode, CapturedNodes, CapturedValue};
+pub use excerpt::{ContextLine, MatchExcerpt};
+pub use record::{CaptureRecord, LineColumn, MatchRecord, matches_to_json};
 
 use crate::{parser::ParseResult, pattern::Pattern, position::point_to_one_based};
 
@@ -47,10 +52,44 @@ impl<'a> MatchResult<'a> {
     #[must_use]
     pub fn end_position(&self) -> (u32, u32) { point_to_one_based(self.node.end_position()) }
 
+    /// Returns the match with up to `before` leading and `after` trailing
+    /// source lines.
+    ///
+    /// Requests reaching past the start or end of the file are clamped, so a
+    /// match on the first line has no leading lines.
+    #[must_use]
+    pub fn context(&self, before: usize, after: usize) -> MatchExcerpt<'a> {
+        let start = self.node.start_position();
+        let end = self.node.end_position();
+        // A node ending just after a newline does not extend onto the next line.
+        let end_row = if end.column == 0 && end.row > start.row {
+            end.row - 1
+        } else {
+            end.row
+        };
+        MatchExcerpt::new(
+            self.source,
+            self.text(),
+            (start.row, end_row),
+            (before, after),
+        )
+    }
+
     /// Gets a captured metavariable by name.
     #[must_use]
     pub fn capture(&self, name: &str) -> Option<&CapturedValue<'a>> { self.captures.get(name) }
 
+    /// Gets the nodes captured by a multiple metavariable (`$$$NAME`).
+    ///
+    /// Returns `None` when `name` was not captured or was bound by a
+    /// single-node metavariable. An empty spread yields an empty slice.
+    #[must_use]
+    pub fn capture_list(&self, name: &str) -> Option<&[CapturedNode<'a>]> {
+        self.capture(name)
+            .and_then(CapturedValue::as_multiple)
+            .map(CapturedNodes::nodes)
+    }
+
     /// Returns all captured metavariables.
     #[must_use]
     pub const fn captures(&self) -> &HashMap<String, CapturedValue<'a>> { &self.captures }
@@ -72,6 +111,23 @@ impl<'p> Matcher<'p> {
         matching::find_all(self.pattern, parsed)
     }
 
+    /// Finds the matches of the pattern that touch a byte range of the
+    /// parsed source.
+    ///
+    /// Subtrees lying wholly outside `range` are skipped, which keeps searches
+    /// of a small edited region cheap on large files. A match is returned when
+    /// its node overlaps `range` or shares one of its boundaries, so matches
+    /// straddling either end are included. An empty range finds matches at
+    /// that position.
+    #[must_use]
+    pub fn find_in_range<'a>(
+        &self,
+        parsed: &'a ParseResult,
+        range: Range<usize>,
+    ) -> Vec<MatchResult<'a>> {
+        matching::find_in_range(self.pattern, parsed, range)
+    }
+
     /// Finds the first match of the pattern in the parsed source.
     #[must_use]
     pub fn find_first<'a>(&self, parsed: &'a ParseResult) -> Option<MatchResult<'a>> {
@@ -86,6 +142,17 @@ impl Pattern {
         Matcher::new(self).find_all(parsed)
     }
 
+    /// Finds the matches of this pattern that touch a byte range of the
+    /// parsed source; see [`Matcher::find_in_range`].
+    #[must_use]
+    pub fn find_in_range<'a>(
+        &self,
+        parsed: &'a ParseResult,
+        range: Range<usize>,
+    ) -> Vec<MatchResult<'a>> {
+        Matcher::new(self).find_in_range(parsed, range)
+    }
+
     /// Finds the first match of this pattern in the parsed source.
     #[must_use]
     pub fn find_first<'a>(&self, parsed: &'a ParseResult) -> Option<MatchResult<'a>> {
@@ -0,0 +1,148 @@
+//! Serializable match records for tooling integration.
+//!
+//! [`MatchResult`] borrows from the parsed tree, so it cannot be handed to
+//! other processes directly. [`MatchRecord`] is an owned, file-agnostic
+//! snapshot of a match that serializes to JSON: callers that know which file
+//! was searched attach the path themselves.
+
+use std::{collections::BTreeMap, ops::Range};
+
+use serde::Serialize;
+
+use super::{CapturedValue, MatchResult};
+use crate::error::SyntaxError;
+
+/// One-based line and column of a match boundary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct LineColumn {
+    /// One-based line number.
+    pub line: u32,
+    /// One-based column, counted in bytes.
+    pub column: u32,
+}
+
+impl From<(u32, u32)> for LineColumn {
+    fn from((line, column): (u32, u32)) -> Self { Self { line, column } }
+}
+
+/// Text bound to a metavariable.
+///
+/// Single captures (`$NAME`) serialize as a string; spread captures
+/// (`$$$NAME`) serialize as an array holding the text of each captured node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum CaptureRecord {
+    /// Text of a single-node capture.
+    Single(String),
+    /// Text of each node of a spread capture, in source order.
+    Multiple(Vec<String>),
+}
+
+impl From<&CapturedValue<'_>> for CaptureRecord {
+    fn from(value: &CapturedValue<'_>) -> Self {
+        match value {
+            CapturedValue::Single(node) => Self::Single(node.text().to_owned()),
+            CapturedValue::Multiple(nodes) => Self::Multiple(
+                nodes
+                    .nodes()
+                    .iter()
+                    .map(|node| node.text().to_owned())
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Owned, serializable snapshot of a [`MatchResult`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MatchRecord {
+    /// Byte range of the match in the searched source.
+    pub byte_range: Range<usize>,
+    /// Position of the first character of the match.
+    pub start: LineColumn,
+    /// Position just past the last character of the match.
+    pub end: LineColumn,
+    /// Matched source text.
+    pub text: String,
+    /// Captured metavariables keyed by name, in name order.
+    pub captures: BTreeMap<String, CaptureRecord>,
+}
+
+impl From<&MatchResult<'_>> for MatchRecord {
+    fn from(result: &MatchResult<'_>) -> Self {
+        Self {
+            byte_range: result.byte_range(),
+            start: result.start_position().into(),
+            end: result.end_position().into(),
+            text: result.text().to_owned(),
+            captures: result
+                .captures()
+                .iter()
+                .map(|(name, value)| (name.clone(), CaptureRecord::from(value)))
+                .collect(),
+        }
+    }
+}
+
+/// Serializes `matches` to a JSON array of [`MatchRecord`] objects.
+///
+/// # Errors
+///
+/// Returns an error if serialization fails.
+pub fn matches_to_json(matches: &[MatchResult<'_>]) -> Result<String, SyntaxError> {
+    let records: Vec<MatchRecord> = matches.iter().map(MatchRecord::from).collect();
+    serde_json::to_string(&records).map_err(|error| {
+        SyntaxError::internal_error(format!("failed to serialize matches: {error}"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for JSON match records.
+
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::{Parser, Pattern, SupportedLanguage};
+
+    fn matches_json(source: &str, pattern_str: &str) -> Value {
+        let mut parser = Parser::new(SupportedLanguage::Rust).expect("parser");
+        let parsed = parser.parse(source).expect("parse");
+        let pattern = Pattern::compile(pattern_str, SupportedLanguage::Rust).expect("pattern");
+        let json = matches_to_json(&pattern.find_all(&parsed)).expect("serialize");
+        serde_json::from_str(&json).expect("valid JSON")
+    }
+
+    #[test]
+    fn single_capture_serializes_as_text() {
+        let value = matches_json("fn hello() {}\n", "fn $NAME() {}");
+
+        assert_eq!(
+            value,
+            json!([{
+                "byte_range": {"start": 0, "end": 13},
+                "start": {"line": 1, "column": 1},
+                "end": {"line": 1, "column": 14},
+                "text": "fn hello() {}",
+                "captures": {"NAME": "hello"},
+            }])
+        );
+    }
+
+    #[test]
+    fn spread_capture_serializes_as_array() {
+        let value = matches_json("fn main() { foo(a, b, c); }", "foo($$$ARGS)");
+
+        assert_eq!(
+            value.pointer("/0/captures/ARGS"),
+            Some(&json!(["a", "b", "c"]))
+        );
+    }
+
+    #[test]
+    fn no_matches_serialize_as_empty_array() {
+        let value = matches_json("fn main() {}", "struct $NAME {}");
+
+        assert_eq!(value, json!([]));
+    }
+}
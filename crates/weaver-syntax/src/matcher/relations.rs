@@ -0,0 +1,112 @@
+//! Evaluation of relational constraints against the source tree.
+//!
+//! A candidate node that matches a pattern structurally is only accepted once
+//! every relation on the pattern holds, which means searching its ancestors
+//! for `inside` and its descendants for `has`.
+
+use super::{capture::Captures, context::MatchContext, matching::nodes_match};
+use crate::pattern::Relation;
+
+/// Matches the context's pattern at `node`, returning the captures when both
+/// the structure and every relation of the pattern match.
+pub(super) fn match_at<'a>(
+    node: tree_sitter::Node<'a>,
+    ctx: &MatchContext<'a, '_>,
+) -> Option<Captures<'a>> {
+    let mut captures = Captures::new(ctx.source);
+    let matched =
+        nodes_match(node, ctx.pattern_root, ctx, &mut captures) && satisfies_relations(node, ctx);
+    matched.then_some(captures)
+}
+
+fn satisfies_relations(node: tree_sitter::Node<'_>, ctx: &MatchContext<'_, '_>) -> bool {
+    ctx.pattern
+        .relations()
+        .iter()
+        .all(|relation| match relation {
+            Relation::Inside(outer) => {
+                has_matching_ancestor(node, &MatchContext::new(outer, ctx.source))
+            }
+            Relation::Has(inner) => {
+                has_matching_descendant(node, &MatchContext::new(inner, ctx.source))
+            }
+        })
+}
+
+fn has_matching_ancestor(node: tree_sitter::Node<'_>, ctx: &MatchContext<'_, '_>) -> bool {
+    std::iter::successors(node.parent(), tree_sitter::Node::parent)
+        .any(|ancestor| match_at(ancestor, ctx).is_some())
+}
+
+fn has_matching_descendant(node: tree_sitter::Node<'_>, ctx: &MatchContext<'_, '_>) -> bool {
+    let mut cursor = node.walk();
+    let children: Vec<_> = node.children(&mut cursor).collect();
+    children
+        .into_iter()
+        .any(|child| match_at(child, ctx).is_some() || has_matching_descendant(child, ctx))
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for `inside` and `has` relations.
+
+    use rstest::rstest;
+
+    use crate::{Parser, Pattern, SupportedLanguage};
+
+    fn rust(source: &str) -> Pattern {
+        Pattern::compile(source, SupportedLanguage::Rust).expect("pattern")
+    }
+
+    fn count_matches(pattern: &Pattern, source: &str) -> usize {
+        let mut parser = Parser::new(SupportedLanguage::Rust).expect("parser");
+        let parsed = parser.parse(source).expect("parse");
+        pattern.find_all(&parsed).len()
+    }
+
+    #[rstest]
+    #[case("fn main() { loop { break; } }", 1)]
+    #[case("fn main() { for x in xs { break; } }", 0)]
+    #[case("fn main() { loop { if done { break; } } }", 1)]
+    #[case("fn main() { loop { break; } while go { break; } }", 1)]
+    fn inside_requires_matching_ancestor(#[case] source: &str, #[case] expected: usize) {
+        let pattern = rust("break;")
+            .inside(rust("loop { $$$BODY }"))
+            .expect("relation");
+
+        assert_eq!(count_matches(&pattern, source), expected);
+    }
+
+    #[rstest]
+    #[case("fn a() { unsafe { run(); } }\nfn b() { run(); }", 1)]
+    #[case("fn a() { if x { unsafe { run(); } } }", 1)]
+    #[case("fn a() { run(); }", 0)]
+    fn has_requires_matching_descendant(#[case] source: &str, #[case] expected: usize) {
+        let pattern = rust("fn $NAME() { $$$BODY }")
+            .has(rust("unsafe { $$$INNER }"))
+            .expect("relation");
+
+        assert_eq!(count_matches(&pattern, source), expected);
+    }
+
+    #[test]
+    fn relations_compose() {
+        let pattern = rust("break;")
+            .inside(rust("loop { $$$BODY }"))
+            .and_then(|pattern| pattern.inside(rust("fn worker() { $$$BODY }")))
+            .expect("relation");
+
+        let source = "fn worker() { loop { break; } }\nfn idle() { loop { break; } }";
+        assert_eq!(count_matches(&pattern, source), 1);
+    }
+
+    #[test]
+    fn rejects_patterns_for_other_languages() {
+        let python = Pattern::compile("while $COND: $$$BODY", SupportedLanguage::Python);
+        let error = rust("break;")
+            .inside(python.expect("pattern"))
+            .expect_err("language mismatch should fail");
+
+        assert!(error.to_string().contains("targets python"));
+    }
+}
@@ -6,7 +6,7 @@ use rstest::*;
 use weaver_test_macros::allow_fixture_expansion_lints;
 
 use super::*;
-use crate::{language::SupportedLanguage, parser::Parser};
+use crate::{constraint::MetaVarConstraint, language::SupportedLanguage, parser::Parser};
 
 /// Fixture providing a Rust parser.
 #[allow_fixture_expansion_lints]
@@ -203,3 +203,192 @@ fn operator_tokens_must_match(mut rust_parser: Parser) {
     );
     assert!(pattern.find_first(&source).is_none());
 }
+
+const TEST_FUNCTIONS: &str = "fn test_alpha() {}\nfn helper() {}\nfn test_beta() {}\n";
+
+fn captured_names(pattern: &Pattern, source: &crate::parser::ParseResult) -> Vec<String> {
+    pattern
+        .find_all(source)
+        .iter()
+        .filter_map(|m| m.capture("NAME").map(|value| value.text().to_owned()))
+        .collect()
+}
+
+#[rstest]
+fn inline_regex_constraint_filters_captures(mut rust_parser: Parser) {
+    let (source, pattern) = parse_and_pattern(
+        &mut rust_parser,
+        TEST_FUNCTIONS,
+        r#"fn $NAME{regex: "^test_"}() {}"#,
+    );
+
+    assert_eq!(
+        captured_names(&pattern, &source),
+        ["test_alpha", "test_beta"]
+    );
+}
+
+#[rstest]
+fn builder_regex_constraint_filters_captures(mut rust_parser: Parser) {
+    let (source, pattern) = parse_and_pattern(&mut rust_parser, TEST_FUNCTIONS, "fn $NAME() {}");
+    let constraint = result_or_panic(MetaVarConstraint::regex("^test_"), "regex");
+    let constrained = result_or_panic(pattern.with_constraint("NAME", constraint), "constraint");
+
+    assert_eq!(
+        captured_names(&constrained, &source),
+        ["test_alpha", "test_beta"]
+    );
+}
+
+#[rstest]
+fn regex_constraint_applies_to_multiple_captures(mut rust_parser: Parser) {
+    let (source, pattern) = parse_and_pattern(
+        &mut rust_parser,
+        "fn first() { let a = 1; }\nfn second() { helper(); }\n",
+        r#"fn $NAME() { $$$BODY{regex: "^let "} }"#,
+    );
+
+    assert_eq!(captured_names(&pattern, &source), ["first"]);
+}
+
+#[rstest]
+#[case("fn main() { x + x; }", "$A + $A", true)]
+#[case("fn main() { x + y; }", "$A + $A", false)]
+#[case("fn main() { a.len() == a.len(); }", "$X == $X", true)]
+#[case("fn main() { a.len() == b.len(); }", "$X == $X", false)]
+fn repeated_metavariables_must_bind_equal_text(
+    mut rust_parser: Parser,
+    #[case] source_code: &str,
+    #[case] pattern_str: &str,
+    #[case] should_match: bool,
+) {
+    let (source, pattern) = parse_and_pattern(&mut rust_parser, source_code, pattern_str);
+
+    assert_eq!(pattern.find_first(&source).is_some(), should_match);
+}
+
+#[rstest]
+#[case("fn main() { let x = 1; }", true)]
+#[case("fn main() { let (a, b) = (1, 2); }", false)]
+fn kind_filter_rejects_other_node_kinds(
+    mut rust_parser: Parser,
+    #[case] source_code: &str,
+    #[case] should_match: bool,
+) {
+    let (source, pattern) =
+        parse_and_pattern(&mut rust_parser, source_code, "let $NAME:identifier = $_;");
+
+    assert_eq!(pattern.find_first(&source).is_some(), should_match);
+}
+
+#[rstest]
+fn builder_kind_filter_rejects_other_node_kinds(mut rust_parser: Parser) {
+    let (source, pattern) = parse_and_pattern(
+        &mut rust_parser,
+        "fn main() { let (a, b) = (1, 2); let c = 3; }",
+        "let $NAME = $_;",
+    );
+    let constrained = result_or_panic(pattern.with_kind("NAME", "identifier"), "kind");
+
+    let names: Vec<_> = constrained
+        .find_all(&source)
+        .iter()
+        .filter_map(|m| m.capture("NAME").map(|value| value.text().to_owned()))
+        .collect();
+    assert_eq!(names, ["c"]);
+}
+
+const THREE_FUNCTIONS: &str =
+    "fn alpha() { let a = 1; }\nfn beta() { let b = 2; }\nfn gamma() {}\n";
+
+fn matched_texts(matches: &[MatchResult<'_>]) -> Vec<String> {
+    matches.iter().map(|m| m.text().to_owned()).collect()
+}
+
+#[rstest]
+fn find_in_range_skips_nodes_outside_range(mut rust_parser: Parser) {
+    let (source, pattern) = parse_and_pattern(&mut rust_parser, THREE_FUNCTIONS, "let $V = $E;");
+    let beta = result_or_panic(
+        THREE_FUNCTIONS.find("fn beta").ok_or("missing beta"),
+        "locate",
+    );
+
+    let matches = Matcher::new(&pattern).find_in_range(&source, beta..THREE_FUNCTIONS.len());
+
+    assert_eq!(matched_texts(&matches), ["let b = 2;"]);
+}
+
+#[rstest]
+fn find_in_range_returns_matches_straddling_boundary(mut rust_parser: Parser) {
+    let (source, pattern) =
+        parse_and_pattern(&mut rust_parser, THREE_FUNCTIONS, "fn $NAME() { $$$BODY }");
+    let inside_alpha = result_or_panic(THREE_FUNCTIONS.find("= 1").ok_or("missing"), "locate");
+
+    let matches = Matcher::new(&pattern).find_in_range(&source, inside_alpha..inside_alpha);
+
+    assert_eq!(matched_texts(&matches), ["fn alpha() { let a = 1; }"]);
+}
+
+#[rstest]
+fn find_in_range_over_whole_source_matches_find_all(mut rust_parser: Parser) {
+    let (source, pattern) =
+        parse_and_pattern(&mut rust_parser, THREE_FUNCTIONS, "fn $NAME() { $$$BODY }");
+
+    let ranged = Matcher::new(&pattern).find_in_range(&source, 0..THREE_FUNCTIONS.len());
+
+    assert_eq!(
+        matched_texts(&ranged),
+        matched_texts(&pattern.find_all(&source))
+    );
+    assert_eq!(ranged.len(), 3);
+}
+
+#[rstest]
+fn capture_list_exposes_each_spread_node(mut rust_parser: Parser) {
+    let (source, pattern) = parse_and_pattern(
+        &mut rust_parser,
+        "fn main() { foo(a, b, c); }",
+        "foo($$$ARGS)",
+    );
+
+    let m = first_rust_match(&pattern, &source);
+    let Some(arguments) = m.capture_list("ARGS") else {
+        panic!("ARGS should be captured as a list");
+    };
+
+    let texts: Vec<_> = arguments.iter().map(CapturedNode::text).collect();
+    assert_eq!(texts, ["a", "b", "c"]);
+    assert!(
+        arguments
+            .iter()
+            .all(|argument| argument.node().kind() == "identifier")
+    );
+}
+
+#[rstest]
+fn capture_list_is_none_for_single_captures(mut rust_parser: Parser) {
+    let (source, pattern) = parse_and_pattern(&mut rust_parser, "fn hello() {}", "fn $NAME() {}");
+
+    let m = first_rust_match(&pattern, &source);
+    assert!(m.capture("NAME").is_some());
+    assert!(m.capture_list("NAME").is_none());
+}
+
+#[rstest]
+#[case::call_argument("fn main() { print(a); }", "print($X)", "(a)")]
+#[case::macro_argument("fn main() { dbg!(x); }", "dbg!($X)", "(x)")]
+#[case::macro_token_run("fn main() { dbg!(x + 1); }", "dbg!($X)", "(x + 1)")]
+#[case::spread_call_arguments("fn main() { print(a, b); }", "print($$$X)", "a, b")]
+#[case::spread_macro_tokens("fn main() { dbg!(x + 1); }", "dbg!($$$X)", "x + 1")]
+fn only_spreads_capture_inside_delimiters(
+    mut rust_parser: Parser,
+    #[case] source: &str,
+    #[case] pattern_str: &str,
+    #[case] expected: &str,
+) {
+    let (parsed, pattern) = parse_and_pattern(&mut rust_parser, source, pattern_str);
+
+    let m = first_rust_match(&pattern, &parsed);
+    let capture = m.capture("X").expect("should capture X");
+    assert_eq!(capture.text(), expected);
+}
@@ -6,6 +6,8 @@
 
 use std::ops::Range;
 
+use tree_sitter::InputEdit;
+
 use crate::{error::SyntaxError, language::SupportedLanguage, position::point_to_one_based};
 
 /// Result of parsing source code.
@@ -54,6 +56,68 @@ impl ParseResult {
     /// Returns the root node of the syntax tree.
     #[must_use]
     pub fn root_node(&self) -> tree_sitter::Node<'_> { self.tree.root_node() }
+
+    /// Applies an edit and incrementally re-parses the updated source.
+    ///
+    /// Tree-sitter reuses the subtrees the edit did not touch, so repeated
+    /// searches over a buffer that changes in small steps avoid a full parse.
+    /// The resulting tree is the same as a fresh parse of `new_source`.
+    ///
+    /// `edit` must describe how the current source became `new_source`:
+    ///
+    /// - `start_byte..old_end_byte` is the replaced span of the current source, and
+    ///   `start_byte..new_end_byte` is its replacement in `new_source`;
+    /// - all offsets are UTF-8 byte offsets on character boundaries;
+    /// - the positions are the zero-based rows and byte columns of those offsets in their
+    ///   respective sources.
+    ///
+    /// Byte offsets are checked against both sources. Positions are not, and
+    /// wrong positions yield a tree with wrong row and column information.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, leaving this result unchanged, if the byte offsets
+    /// are out of bounds or if the text outside the edited span differs
+    /// between the current source and `new_source`.
+    pub fn edit(&mut self, edit: InputEdit, new_source: &str) -> Result<(), SyntaxError> {
+        self.check_edit(&edit, new_source)?;
+
+        let mut old_tree = self.tree.clone();
+        old_tree.edit(&edit);
+        let mut parser = Parser::new(self.language)?;
+        self.tree = parser
+            .inner
+            .parse(new_source, Some(&old_tree))
+            .ok_or_else(|| SyntaxError::parse(self.language, "incremental parsing failed"))?;
+        new_source.clone_into(&mut self.source);
+        Ok(())
+    }
+
+    fn check_edit(&self, edit: &InputEdit, new_source: &str) -> Result<(), SyntaxError> {
+        let old_source = self.source.as_str();
+        let prefix = edit.start_byte;
+        let same_prefix = old_source
+            .get(..prefix)
+            .zip(new_source.get(..prefix))
+            .is_some_and(|(old, new)| old == new);
+        let same_suffix = old_source
+            .get(edit.old_end_byte..)
+            .zip(new_source.get(edit.new_end_byte..))
+            .is_some_and(|(old, new)| old == new);
+        let ordered = prefix <= edit.old_end_byte && prefix <= edit.new_end_byte;
+
+        if ordered && same_prefix && same_suffix {
+            return Ok(());
+        }
+        Err(SyntaxError::parse(
+            self.language,
+            format!(
+                "edit replacing bytes {prefix}..{} with {prefix}..{} does not describe the change \
+                 to the new source",
+                edit.old_end_byte, edit.new_end_byte
+            ),
+        ))
+    }
 }
 
 /// Information about a syntax error found during parsing.
@@ -97,12 +161,7 @@ impl SyntaxErrorInfo {
             },
         );
 
-        let message = if node.is_missing() {
-            format!("missing {}", node.kind())
-        } else {
-            "syntax error".to_owned()
-        };
-
+        let message = describe_error_node(node, source);
         let (line, column) = point_to_one_based(start);
 
         Self {
@@ -115,6 +174,39 @@ impl SyntaxErrorInfo {
     }
 }
 
+/// Maximum number of characters of source quoted in an error message.
+const MESSAGE_SNIPPET_CHARS: usize = 30;
+
+/// Describes an `ERROR` or `MISSING` node in terms a reader can act on.
+///
+/// Missing nodes name the token or construct Tree-sitter expected. Error
+/// nodes quote the first line of the unparseable text, or report the end of
+/// input when the error runs to the end of the file. Both name the enclosing
+/// construct when there is one.
+fn describe_error_node(node: tree_sitter::Node<'_>, source: &str) -> String {
+    let problem = if node.is_missing() {
+        if node.is_named() {
+            format!("missing {}", node.kind())
+        } else {
+            format!("missing `{}`", node.kind())
+        }
+    } else if node.end_byte() >= source.trim_end().len() {
+        String::from("unexpected end of input")
+    } else {
+        let text = source.get(node.byte_range()).unwrap_or_default();
+        let first_line = text.lines().next().unwrap_or_default().trim();
+        let snippet: String = first_line.chars().take(MESSAGE_SNIPPET_CHARS).collect();
+        format!("unexpected `{snippet}`")
+    };
+
+    let enclosing = node
+        .parent()
+        .filter(|parent| parent.parent().is_some())
+        .map(|parent| format!(" in {}", parent.kind().replace('_', " ")))
+        .unwrap_or_default();
+    format!("syntax error: {problem}{enclosing}")
+}
+
 /// Tree-sitter parser wrapper for a specific language.
 ///
 /// Each parser instance is configured for a single language. Create multiple
@@ -215,6 +307,10 @@ mod tests {
         SupportedLanguage::TypeScript,
         "function hello(): string { return 'hi'; }"
     )]
+    #[case(
+        SupportedLanguage::JavaScript,
+        "function hello() { return <b>hi</b>; }"
+    )]
     fn parser_parses_valid_source(#[case] language: SupportedLanguage, #[case] source: &str) {
         let mut parser = Parser::new(language).expect("parser init");
         let result = parser.parse(source).expect("parse");
@@ -227,6 +323,7 @@ mod tests {
     #[case(SupportedLanguage::Rust, "fn broken() {")]
     #[case(SupportedLanguage::Python, "def broken(")]
     #[case(SupportedLanguage::TypeScript, "function broken( {")]
+    #[case(SupportedLanguage::JavaScript, "function broken( {")]
     fn parser_detects_syntax_errors(#[case] language: SupportedLanguage, #[case] source: &str) {
         let mut parser = Parser::new(language).expect("parser init");
         let result = parser.parse(source).expect("parse");
@@ -247,4 +344,27 @@ mod tests {
         assert!(first_error.line >= 1);
         assert!(first_error.column >= 1);
     }
+
+    #[rstest]
+    #[case("fn broken() {", "syntax error: unexpected end of input")]
+    #[case(
+        "fn main() { let x = 1 }",
+        "syntax error: missing `;` in let declaration"
+    )]
+    #[case(
+        "fn main() { let = 1; }",
+        "syntax error: unexpected `=` in let declaration"
+    )]
+    #[case("fn main() { @@ }", "syntax error: unexpected `@@` in block")]
+    fn syntax_error_messages_describe_the_problem(#[case] source: &str, #[case] expected: &str) {
+        let mut parser = Parser::new(SupportedLanguage::Rust).expect("parser init");
+        let result = parser.parse(source).expect("parse");
+
+        let messages: Vec<_> = result
+            .errors()
+            .into_iter()
+            .map(|error| error.message)
+            .collect();
+        assert_eq!(messages, [expected]);
+    }
 }
@@ -9,11 +9,21 @@
 //! - `$VAR` - Matches any single AST node and captures it as `VAR`
 //! - `$_` - Matches any single AST node without capturing (wildcard)
 //! - `$$$VAR` - Matches zero or more AST nodes and captures them as `VAR`
+//! - `$VAR:identifier` - Matches like `$VAR`, but only nodes of the given Tree-sitter kind
+//! - `$VAR{regex: "^get_"}` - Matches like `$VAR`, but only when the captured text matches the
+//!   regular expression
 //!
 //! Metavariable names must start with an uppercase letter or underscore,
 //! followed by uppercase letters, digits, or underscores.
 
+mod relation;
+
+use std::collections::HashMap;
+
+pub(crate) use relation::Relation;
+
 use crate::{
+    constraint::{MetaVarConstraint, parse_inline_constraints},
     error::SyntaxError,
     language::SupportedLanguage,
     metavariables::{extract_metavar_name, placeholder_for_metavar},
@@ -31,10 +41,15 @@ pub struct Pattern {
     source: String,
     language: SupportedLanguage,
     metavariables: Vec<MetaVariable>,
+    constraints: Constraints,
+    relations: Vec<Relation>,
     parsed: ParseResult,
     wrapped_in_function: bool,
 }
 
+/// Constraints keyed by the name of the metavariable they restrict.
+type Constraints = HashMap<String, Vec<MetaVarConstraint>>;
+
 /// A metavariable in a pattern.
 ///
 /// Metavariables are placeholders that match AST nodes during pattern matching.
@@ -68,6 +83,7 @@ impl Pattern {
     ///
     /// Returns an error if:
     /// - The pattern contains invalid metavariable syntax
+    /// - An inline constraint is malformed, names an unknown node kind, or is attached to `$_`
     /// - The pattern cannot be parsed by the language grammar
     ///
     /// # Examples
@@ -81,7 +97,11 @@ impl Pattern {
     /// ```
     pub fn compile(source: &str, language: SupportedLanguage) -> Result<Self, SyntaxError> {
         let raw = RawSource(source);
-        let metavariables = extract_metavariables(raw)?;
+        let (metavariables, constraints) = extract_metavariables(raw)?;
+        constraints
+            .values()
+            .flatten()
+            .try_for_each(|constraint| constraint.validate_for(language))?;
         let normalized = normalize_metavariables(raw)?;
 
         let mut parser = Parser::new(language)?;
@@ -104,11 +124,79 @@ impl Pattern {
             source: source.to_owned(),
             language,
             metavariables,
+            constraints,
+            relations: Vec::new(),
             parsed,
             wrapped_in_function,
         })
     }
 
+    /// Restricts what metavariable `name` may capture.
+    ///
+    /// This is the programmatic form of an inline `$NAME{regex: "..."}`
+    /// block. A metavariable may carry several constraints; a match succeeds
+    /// only when its capture satisfies all of them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pattern has no metavariable called `name`, if
+    /// `name` is the `_` wildcard, which never captures, or if the constraint
+    /// names a node kind the pattern's grammar does not define.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weaver_syntax::{MetaVarConstraint, Pattern, SupportedLanguage};
+    ///
+    /// let pattern = Pattern::compile("fn $NAME() {}", SupportedLanguage::Rust)?
+    ///     .with_constraint("NAME", MetaVarConstraint::regex("^test_")?)?;
+    /// # Ok::<(), weaver_syntax::SyntaxError>(())
+    /// ```
+    pub fn with_constraint(
+        mut self,
+        name: &str,
+        constraint: MetaVarConstraint,
+    ) -> Result<Self, SyntaxError> {
+        ensure_constrainable(name)?;
+        constraint.validate_for(self.language)?;
+        if !self
+            .metavariables
+            .iter()
+            .any(|metavar| metavar.name == name)
+        {
+            return Err(SyntaxError::invalid_constraint(format!(
+                "pattern has no metavariable '${name}'"
+            )));
+        }
+        self.constraints
+            .entry(name.to_owned())
+            .or_default()
+            .push(constraint);
+        Ok(self)
+    }
+
+    /// Restricts metavariable `name` to nodes of the given Tree-sitter kind.
+    ///
+    /// This is the programmatic form of an inline `$NAME:kind` filter.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as
+    /// [`Pattern::with_constraint`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weaver_syntax::{Pattern, SupportedLanguage};
+    ///
+    /// let pattern = Pattern::compile("let $NAME = $_;", SupportedLanguage::Rust)?
+    ///     .with_kind("NAME", "identifier")?;
+    /// # Ok::<(), weaver_syntax::SyntaxError>(())
+    /// ```
+    pub fn with_kind(self, name: &str, kind: &str) -> Result<Self, SyntaxError> {
+        self.with_constraint(name, MetaVarConstraint::kind(kind))
+    }
+
     /// Returns the original pattern source.
     #[must_use]
     pub fn source(&self) -> &str { &self.source }
@@ -123,6 +211,12 @@ impl Pattern {
     #[must_use]
     pub fn metavariables(&self) -> &[MetaVariable] { &self.metavariables }
 
+    /// Returns the constraints attached to metavariable `name`.
+    #[must_use]
+    pub fn constraints(&self, name: &str) -> &[MetaVarConstraint] {
+        self.constraints.get(name).map_or(&[], Vec::as_slice)
+    }
+
     /// Returns the parsed syntax tree of the pattern.
     #[must_use]
     pub const fn parsed(&self) -> &ParseResult { &self.parsed }
@@ -154,7 +248,7 @@ fn wrap_pattern_for_parse(language: SupportedLanguage, pattern: &NormalizedSourc
             )
         }
         SupportedLanguage::Python => python_pattern_wrapper(pattern),
-        SupportedLanguage::TypeScript => {
+        SupportedLanguage::TypeScript | SupportedLanguage::JavaScript => {
             format!("function __weaver_pattern_wrapper__() {{ {s} }}")
         }
     }
@@ -199,6 +293,7 @@ struct MetavarReference {
     dollars: usize,
     name: String,
     offset: usize,
+    constraints: Vec<MetaVarConstraint>,
 }
 
 #[derive(Debug)]
@@ -237,22 +332,28 @@ where
             )));
         }
 
+        let constraints = parse_inline_constraints(&mut chars)?;
         handler(MetavarEvent::Metavar(MetavarReference {
             dollars: dollar_count,
             name,
             offset,
+            constraints,
         }));
     }
 
     Ok(())
 }
 
-/// Extracts metavariables from a pattern source string.
+/// Extracts metavariables and their inline constraints from a pattern source
+/// string.
 ///
 /// Scans the source for `$VAR` and `$$$VAR` patterns and returns
 /// information about each metavariable found.
-fn extract_metavariables(source: RawSource<'_>) -> Result<Vec<MetaVariable>, SyntaxError> {
+fn extract_metavariables(
+    source: RawSource<'_>,
+) -> Result<(Vec<MetaVariable>, Constraints), SyntaxError> {
     let mut metavariables = Vec::new();
+    let mut constraints = Constraints::new();
     visit_metavariables(source, |event| {
         let MetavarEvent::Metavar(metavar) = event else {
             return;
@@ -264,6 +365,12 @@ fn extract_metavariables(source: RawSource<'_>) -> Result<Vec<MetaVariable>, Syn
             MetaVarKind::Single
         };
 
+        if !metavar.constraints.is_empty() {
+            constraints
+                .entry(metavar.name.clone())
+                .or_default()
+                .extend(metavar.constraints);
+        }
         metavariables.push(MetaVariable {
             name: metavar.name,
             kind,
@@ -271,94 +378,20 @@ fn extract_metavariables(source: RawSource<'_>) -> Result<Vec<MetaVariable>, Syn
         });
     })?;
 
-    Ok(metavariables)
+    constraints
+        .keys()
+        .try_for_each(|name| ensure_constrainable(name))?;
+    Ok((metavariables, constraints))
 }
 
-#[cfg(test)]
-mod tests {
-    //! Unit tests for pattern metavariable extraction and validation.
-
-    use super::*;
-
-    #[test]
-    fn extract_single_metavariable() {
-        let metavars = extract_metavariables(RawSource("$VAR")).expect("extract");
-        assert_eq!(metavars.len(), 1);
-        assert_eq!(metavars.first().map(|m| m.name.as_str()), Some("VAR"));
-        assert_eq!(metavars.first().map(|m| m.kind), Some(MetaVarKind::Single));
-    }
-
-    #[test]
-    fn extract_multiple_metavariable() {
-        let metavars = extract_metavariables(RawSource("$$$ARGS")).expect("extract");
-        assert_eq!(metavars.len(), 1);
-        assert_eq!(metavars.first().map(|m| m.name.as_str()), Some("ARGS"));
-        assert_eq!(
-            metavars.first().map(|m| m.kind),
-            Some(MetaVarKind::Multiple)
-        );
-    }
-
-    #[test]
-    fn extract_multiple_metavariables() {
-        let metavars = extract_metavariables(RawSource("$FUNC($ARG1, $ARG2)")).expect("extract");
-        assert_eq!(metavars.len(), 3);
-
-        let names: Vec<_> = metavars.iter().map(|m| m.name.as_str()).collect();
-        assert_eq!(names, vec!["FUNC", "ARG1", "ARG2"]);
-    }
-
-    #[test]
-    fn extract_wildcard() {
-        let metavars = extract_metavariables(RawSource("$_")).expect("extract");
-        assert_eq!(metavars.len(), 1);
-        assert_eq!(metavars.first().map(|m| m.name.as_str()), Some("_"));
-    }
-
-    #[test]
-    fn compile_rust_pattern() {
-        let pattern = Pattern::compile("fn $NAME() {}", SupportedLanguage::Rust).expect("compile");
-        assert_eq!(pattern.language(), SupportedLanguage::Rust);
-        assert!(pattern.has_metavariables());
-
-        let metavars = pattern.metavariables();
-        assert_eq!(metavars.len(), 1);
-        assert_eq!(metavars.first().map(|m| m.name.as_str()), Some("NAME"));
-    }
-
-    #[test]
-    fn compile_python_pattern() {
-        let pattern =
-            Pattern::compile("def $FUNC($$$ARGS):", SupportedLanguage::Python).expect("compile");
-        assert_eq!(pattern.language(), SupportedLanguage::Python);
-
-        let metavars = pattern.metavariables();
-        assert_eq!(metavars.len(), 2);
-    }
-
-    #[test]
-    fn pattern_without_metavariables() {
-        let pattern = Pattern::compile("fn main() {}", SupportedLanguage::Rust).expect("compile");
-        assert!(!pattern.has_metavariables());
-    }
-
-    #[test]
-    fn compile_rejects_patterns_with_syntax_errors() {
-        let result = Pattern::compile("fn (", SupportedLanguage::Rust);
-        assert!(result.is_err());
-    }
-
-    #[test]
-    fn wrap_rust_pattern_adds_statement_semicolon() {
-        let src = NormalizedSource("dbg!($EXPR)".to_owned());
-        let wrapped = wrap_pattern_for_parse(SupportedLanguage::Rust, &src);
-        assert_eq!(wrapped, "fn __weaver_pattern_wrapper__() { dbg!($EXPR); }");
-    }
-
-    #[test]
-    fn wrap_python_empty_pattern_uses_pass() {
-        let src = NormalizedSource(" \n".to_owned());
-        let wrapped = wrap_pattern_for_parse(SupportedLanguage::Python, &src);
-        assert_eq!(wrapped, "def __weaver_pattern_wrapper__():\n    pass\n");
+fn ensure_constrainable(name: &str) -> Result<(), SyntaxError> {
+    if name == "_" {
+        return Err(SyntaxError::invalid_constraint(
+            "the '$_' wildcard never captures, so it cannot be constrained",
+        ));
     }
+    Ok(())
 }
+
+#[cfg(test)]
+mod tests;
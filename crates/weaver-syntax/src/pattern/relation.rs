@@ -0,0 +1,69 @@
+//! Relational constraints between a pattern and its surrounding syntax.
+//!
+//! A relation restricts where a pattern may match by requiring another
+//! pattern to match an enclosing or enclosed node, as in "a `break` that is
+//! inside a `loop`".
+
+use super::Pattern;
+use crate::error::SyntaxError;
+
+/// A requirement on the syntax surrounding a candidate match.
+#[derive(Debug)]
+pub(crate) enum Relation {
+    /// Some strict ancestor of the match must match the pattern.
+    Inside(Pattern),
+    /// Some strict descendant of the match must match the pattern.
+    Has(Pattern),
+}
+
+impl Pattern {
+    /// Restricts this pattern to matches nested inside a node matching
+    /// `outer`.
+    ///
+    /// Any enclosing node qualifies, not only the direct parent. Captures made
+    /// by `outer` are not merged into the match.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `outer` targets a different language.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weaver_syntax::{Pattern, SupportedLanguage};
+    ///
+    /// let outer = Pattern::compile("loop { $$$BODY }", SupportedLanguage::Rust)?;
+    /// let pattern = Pattern::compile("break;", SupportedLanguage::Rust)?.inside(outer)?;
+    /// # Ok::<(), weaver_syntax::SyntaxError>(())
+    /// ```
+    pub fn inside(self, outer: Self) -> Result<Self, SyntaxError> {
+        self.with_relation(Relation::Inside(outer))
+    }
+
+    /// Restricts this pattern to matches containing a node matching `inner`.
+    ///
+    /// Any nested node qualifies, not only direct children. Captures made by
+    /// `inner` are not merged into the match.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `inner` targets a different language.
+    pub fn has(self, inner: Self) -> Result<Self, SyntaxError> {
+        self.with_relation(Relation::Has(inner))
+    }
+
+    /// Returns the relations a match of this pattern must satisfy.
+    pub(crate) fn relations(&self) -> &[Relation] { &self.relations }
+
+    fn with_relation(mut self, relation: Relation) -> Result<Self, SyntaxError> {
+        let (Relation::Inside(related) | Relation::Has(related)) = &relation;
+        if related.language != self.language {
+            return Err(SyntaxError::invalid_constraint(format!(
+                "related pattern targets {}, but this pattern targets {}",
+                related.language, self.language
+            )));
+        }
+        self.relations.push(relation);
+        Ok(self)
+    }
+}
@@ -0,0 +1,159 @@
+//! Unit tests for pattern metavariable extraction and validation.
+
+use rstest::rstest;
+
+use super::*;
+
+fn extract_metavariables_only(source: RawSource<'_>) -> Result<Vec<MetaVariable>, SyntaxError> {
+    extract_metavariables(source).map(|(metavariables, _)| metavariables)
+}
+
+#[test]
+fn extract_single_metavariable() {
+    let metavars = extract_metavariables_only(RawSource("$VAR")).expect("extract");
+    assert_eq!(metavars.len(), 1);
+    assert_eq!(metavars.first().map(|m| m.name.as_str()), Some("VAR"));
+    assert_eq!(metavars.first().map(|m| m.kind), Some(MetaVarKind::Single));
+}
+
+#[test]
+fn extract_multiple_metavariable() {
+    let metavars = extract_metavariables_only(RawSource("$$$ARGS")).expect("extract");
+    assert_eq!(metavars.len(), 1);
+    assert_eq!(metavars.first().map(|m| m.name.as_str()), Some("ARGS"));
+    assert_eq!(
+        metavars.first().map(|m| m.kind),
+        Some(MetaVarKind::Multiple)
+    );
+}
+
+#[test]
+fn extract_multiple_metavariables() {
+    let metavars = extract_metavariables_only(RawSource("$FUNC($ARG1, $ARG2)")).expect("extract");
+    assert_eq!(metavars.len(), 3);
+
+    let names: Vec<_> = metavars.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(names, vec!["FUNC", "ARG1", "ARG2"]);
+}
+
+#[test]
+fn extract_wildcard() {
+    let metavars = extract_metavariables_only(RawSource("$_")).expect("extract");
+    assert_eq!(metavars.len(), 1);
+    assert_eq!(metavars.first().map(|m| m.name.as_str()), Some("_"));
+}
+
+#[test]
+fn compile_rust_pattern() {
+    let pattern = Pattern::compile("fn $NAME() {}", SupportedLanguage::Rust).expect("compile");
+    assert_eq!(pattern.language(), SupportedLanguage::Rust);
+    assert!(pattern.has_metavariables());
+
+    let metavars = pattern.metavariables();
+    assert_eq!(metavars.len(), 1);
+    assert_eq!(metavars.first().map(|m| m.name.as_str()), Some("NAME"));
+}
+
+#[test]
+fn compile_python_pattern() {
+    let pattern =
+        Pattern::compile("def $FUNC($$$ARGS):", SupportedLanguage::Python).expect("compile");
+    assert_eq!(pattern.language(), SupportedLanguage::Python);
+
+    let metavars = pattern.metavariables();
+    assert_eq!(metavars.len(), 2);
+}
+
+#[test]
+fn pattern_without_metavariables() {
+    let pattern = Pattern::compile("fn main() {}", SupportedLanguage::Rust).expect("compile");
+    assert!(!pattern.has_metavariables());
+}
+
+#[test]
+fn compile_rejects_patterns_with_syntax_errors() {
+    let result = Pattern::compile("fn (", SupportedLanguage::Rust);
+    assert!(result.is_err());
+}
+
+#[test]
+fn wrap_rust_pattern_adds_statement_semicolon() {
+    let src = NormalizedSource("dbg!($EXPR)".to_owned());
+    let wrapped = wrap_pattern_for_parse(SupportedLanguage::Rust, &src);
+    assert_eq!(wrapped, "fn __weaver_pattern_wrapper__() { dbg!($EXPR); }");
+}
+
+#[test]
+fn wrap_python_empty_pattern_uses_pass() {
+    let src = NormalizedSource(" \n".to_owned());
+    let wrapped = wrap_pattern_for_parse(SupportedLanguage::Python, &src);
+    assert_eq!(wrapped, "def __weaver_pattern_wrapper__():\n    pass\n");
+}
+
+#[test]
+fn inline_constraints_are_recorded_and_stripped() {
+    let pattern = Pattern::compile(r#"fn $NAME{regex: "^test_"}() {}"#, SupportedLanguage::Rust)
+        .expect("compile");
+
+    assert_eq!(pattern.metavariables().len(), 1);
+    assert_eq!(pattern.constraints("NAME").len(), 1);
+    assert!(pattern.constraints("OTHER").is_empty());
+}
+
+#[test]
+fn with_constraint_appends_to_inline_constraints() {
+    let pattern = Pattern::compile(r#"fn $NAME{regex: "^test_"}() {}"#, SupportedLanguage::Rust)
+        .and_then(|pattern| pattern.with_constraint("NAME", MetaVarConstraint::regex("_slow$")?))
+        .expect("compile");
+
+    assert_eq!(pattern.constraints("NAME").len(), 2);
+}
+
+#[rstest]
+#[case(r#"fn $_{regex: "^a"}() {}"#, "cannot be constrained")]
+#[case(r#"fn $NAME{regex: "("}() {}"#, "invalid regex")]
+#[case("let $NAME:no_such_kind = 1;", "unknown rust node kind 'no_such_kind'")]
+fn compile_rejects_invalid_constraints(#[case] source: &str, #[case] needle: &str) {
+    let error = Pattern::compile(source, SupportedLanguage::Rust)
+        .expect_err("constraint should be rejected");
+    assert!(
+        error.to_string().contains(needle),
+        "expected '{needle}' in: {error}"
+    );
+}
+
+#[rstest]
+#[case("OTHER", "has no metavariable '$OTHER'")]
+#[case("_", "cannot be constrained")]
+fn with_constraint_rejects_unconstrainable_names(#[case] name: &str, #[case] needle: &str) {
+    let constraint = MetaVarConstraint::regex("^a").expect("regex");
+    let error = Pattern::compile("fn $NAME($_) {}", SupportedLanguage::Rust)
+        .expect("compile")
+        .with_constraint(name, constraint)
+        .expect_err("constraint should be rejected");
+    assert!(
+        error.to_string().contains(needle),
+        "expected '{needle}' in: {error}"
+    );
+}
+
+#[test]
+fn kind_filter_is_recorded_and_stripped() {
+    let pattern =
+        Pattern::compile("let $NAME:identifier = $_;", SupportedLanguage::Rust).expect("compile");
+
+    assert_eq!(pattern.metavariables().len(), 2);
+    assert!(matches!(
+        pattern.constraints("NAME"),
+        [MetaVarConstraint::Kind(kind)] if kind == "identifier"
+    ));
+}
+
+#[test]
+fn with_kind_rejects_unknown_kinds() {
+    let error = Pattern::compile("let $NAME = $_;", SupportedLanguage::Rust)
+        .expect("compile")
+        .with_kind("NAME", "no_such_kind")
+        .expect_err("unknown kind should be rejected");
+    assert!(error.to_string().contains("unknown rust node kind"));
+}
@@ -4,9 +4,10 @@
 //! replacing matched code structures with new code, with support for
 //! metavariable substitution in the replacement.
 
-use std::collections::HashSet;
+use std::{collections::HashSet, path::Path};
 
 use crate::{
+    diff::unified_diff,
     error::SyntaxError,
     language::SupportedLanguage,
     matcher::MatchResult,
@@ -15,6 +16,10 @@ use crate::{
     pattern::Pattern,
 };
 
+/// Maximum number of times [`Rewriter::apply_all`] re-applies one rule to its
+/// own output before giving up.
+pub const MAX_RULE_PASSES: usize = 16;
+
 /// A structural rewrite rule.
 ///
 /// Combines a pattern to match with a replacement template. Metavariables
@@ -100,6 +105,7 @@ impl Rewriter {
         let matches = rule.pattern.find_all(&parsed);
         if matches.is_empty() {
             return Ok(RewriteResult {
+                original: source.to_owned(),
                 output: source.to_owned(),
                 num_replacements: 0,
             });
@@ -108,6 +114,7 @@ impl Rewriter {
         let output = Self::apply_replacements(source, &matches, &rule.replacement)?;
 
         Ok(RewriteResult {
+            original: source.to_owned(),
             output,
             num_replacements: matches.len(),
         })
@@ -115,11 +122,17 @@ impl Rewriter {
 
     /// Applies multiple rewrite rules in sequence.
     ///
-    /// Each rule is applied to the result of the previous rule.
+    /// Rules run in order, each parsing and rewriting the output of the
+    /// previous one. A rule is re-applied to its own output until it stops
+    /// changing the source, so a later match exposed by an earlier
+    /// replacement is also rewritten. The returned result compares the
+    /// original source with the final output and counts every replacement.
     ///
     /// # Errors
     ///
-    /// Returns an error if parsing fails during any rule application.
+    /// Returns an error if parsing fails during any rule application, or if a
+    /// rule still changes the source after [`MAX_RULE_PASSES`] passes, which
+    /// happens when its replacement always re-matches its pattern.
     pub fn apply_all(
         &self,
         rules: &[RewriteRule],
@@ -129,17 +142,40 @@ impl Rewriter {
         let mut total_replacements: usize = 0;
 
         for rule in rules {
-            let result = self.apply(rule, &current)?;
-            total_replacements = total_replacements.saturating_add(result.num_replacements);
-            current = result.output;
+            let (output, replacements) = self.apply_until_settled(rule, current)?;
+            total_replacements = total_replacements.saturating_add(replacements);
+            current = output;
         }
 
         Ok(RewriteResult {
+            original: source.to_owned(),
             output: current,
             num_replacements: total_replacements,
         })
     }
 
+    /// Re-applies `rule` until a pass leaves the source unchanged.
+    fn apply_until_settled(
+        &self,
+        rule: &RewriteRule,
+        mut current: String,
+    ) -> Result<(String, usize), SyntaxError> {
+        let mut replacements: usize = 0;
+        for _ in 0..MAX_RULE_PASSES {
+            let result = self.apply(rule, &current)?;
+            if result.output == current {
+                return Ok((current, replacements));
+            }
+            replacements = replacements.saturating_add(result.num_replacements);
+            current = result.output;
+        }
+        Err(SyntaxError::rewrite(format!(
+            "rule '{}' still matches after {MAX_RULE_PASSES} passes; its replacement re-matches \
+             the pattern",
+            rule.pattern.source()
+        )))
+    }
+
     /// Applies replacements to source code based on matches.
     fn apply_replacements(
         source: &str,
@@ -177,6 +213,8 @@ impl Rewriter {
 /// Result of a rewrite operation.
 #[derive(Debug, Clone)]
 pub struct RewriteResult {
+    /// The source code before any rewrite was applied.
+    original: String,
     /// The transformed source code.
     output: String,
     /// Number of replacements made.
@@ -195,6 +233,19 @@ impl RewriteResult {
     /// Returns whether any replacements were made.
     #[must_use]
     pub const fn has_changes(&self) -> bool { self.num_replacements > 0 }
+
+    /// Renders the rewrite as a `diff --git` unified diff for `path`.
+    ///
+    /// `path` should be relative to the workspace root, as it is written into
+    /// the `a/` and `b/` headers. Hunks carry three lines of context, and a
+    /// final line lacking a trailing newline is marked with
+    /// `\ No newline at end of file`. Returns an empty string when the
+    /// rewrite left the source text unchanged, even if replacements were
+    /// made.
+    #[must_use]
+    pub fn unified_diff(&self, path: &Path) -> String {
+        unified_diff(path, &self.original, &self.output)
+    }
 }
 
 /// Counts consecutive dollar signs starting from the current position.
@@ -289,64 +340,45 @@ fn substitute_metavariables(template: &str, match_result: &MatchResult<'_>) -> S
 
         let dollars = count_dollars(&mut chars);
         let name = extract_metavar_name(&mut chars);
+        if dollars == 3 && is_empty_spread(&name, match_result) {
+            drop_dangling_separator(&mut out, &mut chars);
+            continue;
+        }
         try_substitute_metavar(&mut out, dollars, &name, match_result);
     }
 
     out
 }
 
-#[cfg(test)]
-mod tests {
-    //! Unit tests for pattern-based source code rewriting.
-
-    use super::*;
-
-    #[test]
-    fn rewrite_simple_replacement() {
-        let pattern =
-            Pattern::compile("let $VAR = $VAL", SupportedLanguage::Rust).expect("pattern");
-        let rule = RewriteRule::new(pattern, "const $VAR: _ = $VAL").expect("rule");
-
-        let rewriter = Rewriter::new(SupportedLanguage::Rust);
-        let result = rewriter
-            .apply(&rule, "fn main() { let x = 1; }")
-            .expect("rewrite");
-
-        assert!(result.has_changes());
-        assert!(result.output().contains("const"));
-    }
-
-    #[test]
-    fn rewrite_no_match_returns_unchanged() {
-        let pattern =
-            Pattern::compile("struct $NAME {}", SupportedLanguage::Rust).expect("pattern");
-        let rule = RewriteRule::new(pattern, "enum $NAME {}").expect("rule");
-
-        let rewriter = Rewriter::new(SupportedLanguage::Rust);
-        let source = "fn main() {}";
-        let result = rewriter.apply(&rule, source).expect("rewrite");
-
-        assert!(!result.has_changes());
-        assert_eq!(result.output(), source);
-    }
-
-    #[test]
-    fn rewrite_rule_validates_metavariables() {
-        let pattern = Pattern::compile("fn $NAME() {}", SupportedLanguage::Rust).expect("pattern");
-        let result = RewriteRule::new(pattern, "fn $UNDEFINED() {}");
-
-        assert!(result.is_err());
-    }
+/// Returns whether `name` is a `$$$` capture that bound no nodes.
+///
+/// The `$$$_` wildcard never captures, so it always renders as empty.
+fn is_empty_spread(name: &str, match_result: &MatchResult<'_>) -> bool {
+    name == "_" || match_result.capture_list(name).is_some_and(<[_]>::is_empty)
+}
 
-    #[test]
-    fn extract_replacement_vars_finds_all() {
-        let vars = extract_replacement_vars("$A + $B = $RESULT");
-        assert_eq!(vars, vec!["A", "B", "RESULT"]);
+/// Removes the comma that would separate an empty spread from its neighbours.
+///
+/// A comma already written before the spread is dropped, as in
+/// `trace(ctx, $$$ARGS)`; otherwise a comma following the spread in the
+/// template is skipped, as in `trace($$$ARGS, ctx)`. Whitespace around the
+/// dropped comma goes with it.
+fn drop_dangling_separator(
+    out: &mut String,
+    chars: &mut std::iter::Peekable<std::str::CharIndices<'_>>,
+) {
+    if let Some(before) = out.trim_end().strip_suffix(',') {
+        out.truncate(before.len());
+        return;
     }
 
-    #[test]
-    fn extract_replacement_vars_handles_multiple_prefix() {
-        let vars = extract_replacement_vars("f($$$ARGS)");
-        assert_eq!(vars, vec!["ARGS"]);
+    let mut lookahead = chars.clone();
+    while lookahead.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    if lookahead.next_if(|(_, c)| *c == ',').is_some() {
+        while lookahead.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+        *chars = lookahead;
     }
 }
+
+#[cfg(test)]
+mod tests;
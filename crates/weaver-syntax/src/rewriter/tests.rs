@@ -0,0 +1,144 @@
+//! Unit tests for pattern-based source code rewriting.
+
+use rstest::rstest;
+
+use super::*;
+
+#[test]
+fn rewrite_simple_replacement() {
+    let pattern = Pattern::compile("let $VAR = $VAL", SupportedLanguage::Rust).expect("pattern");
+    let rule = RewriteRule::new(pattern, "const $VAR: _ = $VAL").expect("rule");
+
+    let rewriter = Rewriter::new(SupportedLanguage::Rust);
+    let result = rewriter
+        .apply(&rule, "fn main() { let x = 1; }")
+        .expect("rewrite");
+
+    assert!(result.has_changes());
+    assert!(result.output().contains("const"));
+}
+
+#[test]
+fn rewrite_no_match_returns_unchanged() {
+    let pattern = Pattern::compile("struct $NAME {}", SupportedLanguage::Rust).expect("pattern");
+    let rule = RewriteRule::new(pattern, "enum $NAME {}").expect("rule");
+
+    let rewriter = Rewriter::new(SupportedLanguage::Rust);
+    let source = "fn main() {}";
+    let result = rewriter.apply(&rule, source).expect("rewrite");
+
+    assert!(!result.has_changes());
+    assert_eq!(result.output(), source);
+}
+
+#[test]
+fn rewrite_rule_validates_metavariables() {
+    let pattern = Pattern::compile("fn $NAME() {}", SupportedLanguage::Rust).expect("pattern");
+    let result = RewriteRule::new(pattern, "fn $UNDEFINED() {}");
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn extract_replacement_vars_finds_all() {
+    let vars = extract_replacement_vars("$A + $B = $RESULT");
+    assert_eq!(vars, vec!["A", "B", "RESULT"]);
+}
+
+#[test]
+fn extract_replacement_vars_handles_multiple_prefix() {
+    let vars = extract_replacement_vars("f($$$ARGS)");
+    assert_eq!(vars, vec!["ARGS"]);
+}
+
+fn rust_rule(source: &str, replacement: &str) -> RewriteRule {
+    let pattern = Pattern::compile(source, SupportedLanguage::Rust).expect("pattern");
+    RewriteRule::new(pattern, replacement).expect("rule")
+}
+
+#[test]
+fn apply_all_chains_complementary_rules() {
+    let rules = [
+        rust_rule("struct $NAME {}", "enum $NAME {}"),
+        rust_rule("enum $NAME {}", "union $NAME {}"),
+    ];
+
+    let rewriter = Rewriter::new(SupportedLanguage::Rust);
+    let result = rewriter
+        .apply_all(&rules, "struct A {}\nenum B {}\n")
+        .expect("rewrite");
+
+    assert_eq!(result.output(), "union A {}\nunion B {}\n");
+    assert_eq!(result.num_replacements(), 3);
+    assert!(result.has_changes());
+}
+
+#[test]
+fn apply_all_without_matches_reports_no_changes() {
+    let rules = [rust_rule("struct $NAME {}", "enum $NAME {}")];
+
+    let rewriter = Rewriter::new(SupportedLanguage::Rust);
+    let source = "fn main() {}";
+    let result = rewriter.apply_all(&rules, source).expect("rewrite");
+
+    assert!(!result.has_changes());
+    assert_eq!(result.output(), source);
+}
+
+#[test]
+fn apply_all_rejects_rules_that_always_rematch() {
+    let rules = [rust_rule("fn $NAME() {}", "fn $NAME() {} // checked")];
+
+    let rewriter = Rewriter::new(SupportedLanguage::Rust);
+    let error = rewriter
+        .apply_all(&rules, "fn main() {}\n")
+        .expect_err("self-matching rule should be bounded");
+
+    assert!(
+        error
+            .to_string()
+            .contains(&format!("still matches after {MAX_RULE_PASSES} passes")),
+        "unexpected error: {error}"
+    );
+}
+
+fn python_rule(source: &str, replacement: &str) -> RewriteRule {
+    let pattern = Pattern::compile(source, SupportedLanguage::Python).expect("pattern");
+    RewriteRule::new(pattern, replacement).expect("rule")
+}
+
+fn rewrite_python(rule: &RewriteRule, source: &str) -> String {
+    let rewriter = Rewriter::new(SupportedLanguage::Python);
+    let result = rewriter.apply(rule, source).expect("rewrite");
+    result.output().to_owned()
+}
+
+#[rstest]
+#[case::zero_arguments("log()\n", "trace()\n")]
+#[case::one_argument("log(a)\n", "trace(a)\n")]
+#[case::three_arguments("log(a, b, c)\n", "trace(a, b, c)\n")]
+fn spread_replacement_preserves_arguments(#[case] source: &str, #[case] expected: &str) {
+    let rule = python_rule("log($$$ARGS)", "trace($$$ARGS)");
+
+    assert_eq!(rewrite_python(&rule, source), expected);
+}
+
+#[rstest]
+#[case::leading_context("trace(ctx, $$$ARGS)")]
+#[case::trailing_context("trace($$$ARGS, ctx)")]
+#[case::wildcard("trace(ctx, $$$_)")]
+fn empty_spread_leaves_no_dangling_comma(#[case] replacement: &str) {
+    let rule = python_rule("log($$$ARGS)", replacement);
+
+    assert_eq!(rewrite_python(&rule, "log()\n"), "trace(ctx)\n");
+}
+
+#[test]
+fn non_empty_spread_keeps_neighbouring_arguments() {
+    let rule = python_rule("log($$$ARGS)", "trace(ctx, $$$ARGS)");
+
+    assert_eq!(
+        rewrite_python(&rule, "log(a, b, c)\n"),
+        "trace(ctx, a, b, c)\n"
+    );
+}
@@ -0,0 +1,300 @@
+//! Tree-sitter based syntactic validation for the Double-Lock harness.
+//!
+//! This module provides [`TreeSitterSyntacticLock`], which validates that
+//! modified files produce valid syntax trees. It integrates with the
+//! safety harness in `weaverd` to prevent syntactically invalid code from
+//! being committed.
+
+use std::{
+    collections::HashMap,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    error::SyntaxError,
+    language::SupportedLanguage,
+    parser::{ParseResult, Parser},
+};
+
+/// Tree-sitter based syntactic validation.
+///
+/// This validator parses modified files using Tree-sitter and reports any
+/// syntax errors found. Files with unrecognised extensions are skipped
+/// (passed through), allowing non-code files to coexist in the codebase.
+///
+/// # Thread Safety
+///
+/// This type is thread-safe and can be shared across threads. Internal
+/// parser state is protected by a mutex.
+pub struct TreeSitterSyntacticLock {
+    /// Cached parsers for each language.
+    parsers: Mutex<HashMap<SupportedLanguage, Arc<Mutex<Parser>>>>,
+}
+
+/// Owned file content for syntactic lock validation.
+///
+/// This is a small convenience wrapper for callers that naturally work with
+/// `PathBuf` and `String` values (e.g. staging edits before validation).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedFile {
+    /// Path to the file (used for language detection).
+    pub path: PathBuf,
+    /// File content to validate.
+    pub content: String,
+}
+
+impl TreeSitterSyntacticLock {
+    /// Creates a new syntactic lock.
+    ///
+    /// Parsers for each language are created lazily on first use.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            parsers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Validates a single file's content.
+    ///
+    /// Returns a list of syntax errors found in the file. An empty list
+    /// indicates the file is syntactically valid, or that its extension maps
+    /// to no supported language and it was skipped.
+    ///
+    /// This is a thin wrapper over [`Self::validate_files`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the parser for the detected language cannot be
+    /// initialised, or if the internal parser lock is poisoned.
+    pub fn validate_file(
+        &self,
+        path: &Path,
+        content: &str,
+    ) -> Result<Vec<ValidationFailure>, SyntaxError> {
+        let mut results = self.validate_files(&[(path, content)])?;
+        Ok(results
+            .pop()
+            .map(|(_, outcome)| outcome.into_failures())
+            .unwrap_or_default())
+    }
+
+    /// Validates a single file using owned inputs.
+    ///
+    /// This is a convenience wrapper around [`Self::validate_file`] for call
+    /// sites that already have owned `PathBuf`/`String` values.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Self::validate_file`], such as parser
+    /// initialisation failures or internal lock poisoning.
+    pub fn validate_owned_file(
+        &self,
+        file: OwnedFile,
+    ) -> Result<Vec<ValidationFailure>, SyntaxError> {
+        let OwnedFile { path, content } = file;
+        self.validate_file(&path, &content)
+    }
+
+    /// Validates a batch of files, reporting an outcome for each.
+    ///
+    /// Files are grouped by language and each language's parser is locked
+    /// once for the whole group, so a batch costs one lock acquisition per
+    /// language rather than one per file. Outcomes are returned in the order
+    /// of `files`. Files whose extension maps to no supported language are
+    /// reported as [`FileValidation::Skipped`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a parser cannot be initialised, if parsing fails
+    /// outright, or if an internal parser lock is poisoned.
+    pub fn validate_files(
+        &self,
+        files: &[(&Path, &str)],
+    ) -> Result<Vec<(PathBuf, FileValidation)>, SyntaxError> {
+        let mut outcomes: Vec<_> = files.iter().map(|_| FileValidation::Skipped).collect();
+        for (language, indices) in group_by_language(files) {
+            let parser = self.parser_for(language)?;
+            let mut parser_guard = parser
+                .lock()
+                .map_err(|_| SyntaxError::internal_error("parser lock poisoned"))?;
+
+            check_group(&mut parser_guard, files, &indices, &mut outcomes)?;
+        }
+
+        Ok(files
+            .iter()
+            .map(|(path, _)| path.to_path_buf())
+            .zip(outcomes)
+            .collect())
+    }
+
+    /// Validates multiple files using owned inputs.
+    ///
+    /// Returns all validation failures across all files, skipping files with
+    /// unsupported extensions.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Self::validate_files`], such as parser
+    /// initialisation failures.
+    pub fn validate_owned_files<I>(&self, files: I) -> Result<Vec<ValidationFailure>, SyntaxError>
+    where
+        I: IntoIterator<Item = OwnedFile>,
+    {
+        let owned: Vec<_> = files.into_iter().collect();
+        let borrowed: Vec<_> = owned
+            .iter()
+            .map(|file| (file.path.as_path(), file.content.as_str()))
+            .collect();
+        Ok(self
+            .validate_files(&borrowed)?
+            .into_iter()
+            .flat_map(|(_, outcome)| outcome.into_failures())
+            .collect())
+    }
+
+    /// Returns the cached parser for `language`, creating it on first use.
+    fn parser_for(&self, language: SupportedLanguage) -> Result<Arc<Mutex<Parser>>, SyntaxError> {
+        let mut parsers = self
+            .parsers
+            .lock()
+            .map_err(|_| SyntaxError::internal_error("parser map lock poisoned"))?;
+
+        if let Some(parser) = parsers.get(&language) {
+            return Ok(parser.clone());
+        }
+        let parser = Arc::new(Mutex::new(Parser::new(language)?));
+        parsers.insert(language, parser.clone());
+        Ok(parser)
+    }
+
+    /// Checks if a file would be validated by this lock.
+    ///
+    /// Returns `true` if the file has a recognised extension that maps
+    /// to a supported language.
+    #[must_use]
+    pub fn supports_file(path: &Path) -> bool { SupportedLanguage::from_path(path).is_some() }
+}
+
+impl Default for TreeSitterSyntacticLock {
+    fn default() -> Self { Self::new() }
+}
+
+impl std::fmt::Debug for TreeSitterSyntacticLock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TreeSitterSyntacticLock")
+            .field("languages", &SupportedLanguage::all())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Groups the indices of `files` by detected language, omitting files whose
+/// extension maps to no supported language.
+fn group_by_language(files: &[(&Path, &str)]) -> HashMap<SupportedLanguage, Vec<usize>> {
+    let mut groups: HashMap<_, Vec<_>> = HashMap::new();
+    for (index, (path, _)) in files.iter().enumerate() {
+        if let Some(language) = SupportedLanguage::from_path(path) {
+            groups.entry(language).or_default().push(index);
+        }
+    }
+    groups
+}
+
+/// Parses the files at `indices` with one parser, recording their outcomes.
+fn check_group(
+    parser: &mut Parser,
+    files: &[(&Path, &str)],
+    indices: &[usize],
+    outcomes: &mut [FileValidation],
+) -> Result<(), SyntaxError> {
+    for &index in indices {
+        let (Some((path, content)), Some(outcome)) = (files.get(index), outcomes.get_mut(index))
+        else {
+            continue;
+        };
+        let result = parser.parse(content)?;
+        *outcome = FileValidation::Checked(failures_for(path, &result));
+    }
+    Ok(())
+}
+
+/// Converts the syntax errors in `result` into failures attributed to `path`.
+fn failures_for(path: &Path, result: &ParseResult) -> Vec<ValidationFailure> {
+    result
+        .errors()
+        .into_iter()
+        .map(|e| ValidationFailure {
+            path: path.to_path_buf(),
+            byte_range: e.byte_range,
+            line: e.line,
+            column: e.column,
+            message: e.message,
+        })
+        .collect()
+}
+
+/// Outcome of validating one file in a batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileValidation {
+    /// The file was parsed. The list is empty when the file is valid.
+    Checked(Vec<ValidationFailure>),
+    /// The file's extension maps to no supported language, so it was not
+    /// parsed.
+    Skipped,
+}
+
+impl FileValidation {
+    /// Returns the failures found, treating a skipped file as valid.
+    #[must_use]
+    pub fn into_failures(self) -> Vec<ValidationFailure> {
+        match self {
+            Self::Checked(failures) => failures,
+            Self::Skipped => Vec::new(),
+        }
+    }
+}
+
+/// A validation failure from the syntactic lock.
+///
+/// This struct is designed to be compatible with `VerificationFailure` in
+/// `weaverd::safety_harness` for easy integration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationFailure {
+    /// Path to the affected file.
+    pub path: std::path::PathBuf,
+    /// Byte range of the offending `ERROR` or `MISSING` node. Missing nodes
+    /// have an empty range at the point where the token was expected.
+    pub byte_range: Range<usize>,
+    /// Line number (one-based).
+    pub line: u32,
+    /// Column number (one-based).
+    pub column: u32,
+    /// Human-readable description of the problem.
+    pub message: String,
+}
+
+impl ValidationFailure {
+    /// Returns the one-based `(line, column)` where the failure starts.
+    ///
+    /// Columns count bytes, matching Tree-sitter positions.
+    #[must_use]
+    pub const fn line_column(&self) -> (u32, u32) { (self.line, self.column) }
+}
+
+impl std::fmt::Display for ValidationFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}",
+            self.path.display(),
+            self.line,
+            self.column,
+            self.message
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,139 @@
+//! Unit tests for syntactic validation using Tree-sitter locks.
+
+use std::path::PathBuf;
+
+use rstest::rstest;
+
+use super::*;
+
+#[rstest]
+#[case("test.rs", "fn main() { println!(\"hello\"); }", true)]
+#[case("test.rs", "fn broken() {", false)]
+#[case("script.py", "def hello():\n    print('hello')", true)]
+#[case("script.py", "def broken(", false)]
+#[case(
+    "app.ts",
+    "function greet(name: string): void { console.log(name); }",
+    true
+)]
+#[case("broken.tsx", "function broken( {", false)]
+#[case("data.json", "{invalid json without quotes}", true)]
+fn validate_file_cases(#[case] filename: &str, #[case] content: &str, #[case] should_pass: bool) {
+    let lock = TreeSitterSyntacticLock::new();
+    let path = PathBuf::from(filename);
+
+    let failures = lock.validate_file(&path, content).expect("validate");
+    if should_pass {
+        assert!(failures.is_empty(), "Expected no failures for {filename}");
+    } else {
+        assert!(!failures.is_empty(), "Expected failures for {filename}");
+    }
+}
+
+#[test]
+fn failure_has_location_info() {
+    let lock = TreeSitterSyntacticLock::new();
+    let path = PathBuf::from("test.rs");
+    let content = "fn test() {\n    let x = \n}";
+
+    let failures = lock.validate_file(&path, content).expect("validate");
+    assert!(!failures.is_empty());
+
+    let first = failures.first().expect("failure");
+    assert!(first.line >= 1);
+    assert!(first.column >= 1);
+    assert!(!first.message.is_empty());
+}
+
+#[test]
+fn failure_reports_range_and_line_for_unclosed_function() {
+    let lock = TreeSitterSyntacticLock::new();
+    let content = "// header\nfn broken() {";
+
+    let failures = lock
+        .validate_file(Path::new("test.rs"), content)
+        .expect("validate");
+
+    let [failure] = failures.as_slice() else {
+        panic!("expected one failure, got {failures:?}");
+    };
+    assert_eq!(failure.line_column(), (2, 1));
+    assert_eq!(failure.byte_range, 10..content.len());
+    assert_eq!(failure.message, "syntax error: unexpected end of input");
+}
+
+#[test]
+fn validate_files_reports_an_outcome_per_file_in_order() {
+    let lock = TreeSitterSyntacticLock::new();
+    let files = [
+        (Path::new("valid.rs"), "fn main() {}"),
+        (Path::new("data.json"), "{not validated}"),
+        (Path::new("broken.py"), "def broken("),
+        (Path::new("invalid.rs"), "fn broken() {"),
+    ];
+
+    let results = lock.validate_files(&files).expect("validate");
+
+    let paths: Vec<_> = results.iter().map(|(path, _)| path.as_path()).collect();
+    assert_eq!(paths, files.map(|(path, _)| path));
+    let outcomes: Vec<_> = results
+        .into_iter()
+        .map(|(_, outcome)| match outcome {
+            FileValidation::Checked(failures) => Some(failures.len()),
+            FileValidation::Skipped => None,
+        })
+        .collect();
+    assert_eq!(outcomes, [Some(0), None, Some(1), Some(1)]);
+}
+
+#[test]
+fn validate_files_accepts_empty_batch() {
+    let lock = TreeSitterSyntacticLock::new();
+
+    assert!(lock.validate_files(&[]).expect("validate").is_empty());
+}
+
+#[rstest]
+#[case("main.rs", true)]
+#[case("script.py", true)]
+#[case("app.ts", true)]
+#[case("view.tsx", true)]
+#[case("data.json", false)]
+#[case("README.md", false)]
+fn supports_file_detects_extensions(#[case] path: &str, #[case] expected: bool) {
+    assert_eq!(
+        TreeSitterSyntacticLock::supports_file(Path::new(path)),
+        expected
+    );
+}
+
+#[test]
+fn validate_owned_file_accepts_pathbuf_and_string() {
+    let lock = TreeSitterSyntacticLock::new();
+    let file = OwnedFile {
+        path: PathBuf::from("test.rs"),
+        content: "fn main() {}".to_owned(),
+    };
+
+    let failures = lock.validate_owned_file(file).expect("validate");
+    assert!(failures.is_empty());
+}
+
+#[test]
+fn validate_owned_files_collects_failures() {
+    let lock = TreeSitterSyntacticLock::new();
+    let files = vec![
+        OwnedFile {
+            path: PathBuf::from("ok.rs"),
+            content: "fn main() {}".to_owned(),
+        },
+        OwnedFile {
+            path: PathBuf::from("broken.rs"),
+            content: "fn broken() {".to_owned(),
+        },
+    ];
+
+    let failures = lock.validate_owned_files(files).expect("validate");
+    assert!(!failures.is_empty());
+    assert!(failures.iter().any(|f| f.path.ends_with("broken.rs")));
+}
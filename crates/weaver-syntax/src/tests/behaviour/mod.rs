@@ -154,7 +154,12 @@ fn when_validate_all_files(world: &RefCell<TestWorld>) {
         .iter()
         .map(|(p, c)| (p.as_path(), c.as_str()))
         .collect();
-    let failures = lock.validate_files(files).expect("validate");
+    let failures = lock
+        .validate_files(&files)
+        .expect("validate")
+        .into_iter()
+        .flat_map(|(_, outcome)| outcome.into_failures())
+        .collect();
     w.validation_failures = failures;
 }
 
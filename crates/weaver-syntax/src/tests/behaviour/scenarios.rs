@@ -52,6 +52,18 @@ fn invalid_python_validation(world: RefCell<TestWorld>) { drop(world); }
 )]
 fn invalid_typescript_validation(world: RefCell<TestWorld>) { drop(world); }
 
+#[scenario(
+    path = "tests/features/weaver_syntax.feature",
+    name = "Valid JSX code passes syntactic validation"
+)]
+fn valid_jsx_validation(world: RefCell<TestWorld>) { drop(world); }
+
+#[scenario(
+    path = "tests/features/weaver_syntax.feature",
+    name = "TypeScript annotations fail validation in JavaScript files"
+)]
+fn typescript_annotations_in_javascript_fail(world: RefCell<TestWorld>) { drop(world); }
+
 #[scenario(
     path = "tests/features/weaver_syntax.feature",
     name = "Unknown file extensions are skipped"
@@ -1,8 +1,9 @@
 //! Unit tests for weaver-syntax.
 
-use std::path::Path;
+use std::{ops::Range, path::Path};
 
 use rstest::rstest;
+use tree_sitter::{InputEdit, Point};
 
 use crate::{Parser, Pattern, RewriteRule, Rewriter, SupportedLanguage, TreeSitterSyntacticLock};
 
@@ -21,6 +22,9 @@ use crate::{Parser, Pattern, RewriteRule, Rewriter, SupportedLanguage, TreeSitte
 #[case(SupportedLanguage::Python, "def broken(", true)]
 #[case(SupportedLanguage::TypeScript, "function test(): void {}", false)]
 #[case(SupportedLanguage::TypeScript, "function test( {", true)]
+#[case(SupportedLanguage::JavaScript, "function test() {}", false)]
+#[case(SupportedLanguage::JavaScript, "const el = <div>{name}</div>;", false)]
+#[case(SupportedLanguage::JavaScript, "function test( {", true)]
 fn parser_detects_errors(
     #[case] language: SupportedLanguage,
     #[case] source: &str,
@@ -41,6 +45,12 @@ fn parser_detects_errors(
 #[case("test.py", "def valid(): pass", true)]
 #[case("test.py", "def invalid(", false)]
 #[case("test.ts", "const x: number = 1;", true)]
+#[case("test.tsx", "const x: number = 1;", true)]
+#[case("test.js", "const x = 1;", true)]
+#[case("test.js", "const x: number = 1;", false)]
+#[case("test.jsx", "const el = <div>{x}</div>;", true)]
+#[case("test.mjs", "export default function () {}", true)]
+#[case("test.cjs", "module.exports = {", false)]
 #[case("data.json", "{not validated}", true)] // Unknown extension passes
 fn syntactic_lock_validates_correctly(
     #[case] filename: &str,
@@ -106,6 +116,100 @@ fn pattern_returns_empty_for_no_match() {
     assert!(matches.is_empty());
 }
 
+/// Returns the zero-based row and byte column of `offset` in `source`.
+fn point_at(source: &str, offset: usize) -> Point {
+    let before = source.get(..offset).expect("offset in bounds");
+    let row = before.matches('\n').count();
+    let column = before
+        .rfind('\n')
+        .map_or(offset, |newline| offset - newline - 1);
+    Point::new(row, column)
+}
+
+/// Replaces `old_range` of `source`, returning the new source and the
+/// matching edit.
+fn replace(source: &str, old_range: Range<usize>, text: &str) -> (String, InputEdit) {
+    let mut new_source = source.to_owned();
+    new_source.replace_range(old_range.clone(), text);
+    let new_end_byte = old_range.start + text.len();
+    let edit = InputEdit {
+        start_byte: old_range.start,
+        old_end_byte: old_range.end,
+        new_end_byte,
+        start_position: point_at(source, old_range.start),
+        old_end_position: point_at(source, old_range.end),
+        new_end_position: point_at(&new_source, new_end_byte),
+    };
+    (new_source, edit)
+}
+
+#[test]
+fn edited_tree_matches_fresh_parse() {
+    let mut parser = Parser::new(SupportedLanguage::Rust).expect("parser init");
+    let mut source: String = (0..500)
+        .map(|index| format!("fn function_{index}() -> u32 {{ {index} }}\n"))
+        .collect::<Vec<_>>()
+        .concat();
+    let mut result = parser.parse(&source).expect("parse");
+
+    let edits = [
+        ("function_250", "renamed"),
+        ("{ 10 }", "{ 10 + 1 }"),
+        ("fn function_499() -> u32 { 499 }\n", ""),
+        ("fn function_0", "pub fn function_0"),
+    ];
+    for (old, new) in edits {
+        let start = source.find(old).expect("edit target");
+        let (new_source, edit) = replace(&source, start..start + old.len(), new);
+        result.edit(edit, &new_source).expect("edit");
+        source = new_source;
+
+        let fresh = parser.parse(&source).expect("fresh parse");
+        assert_eq!(result.source(), fresh.source());
+        assert_eq!(result.root_node().to_sexp(), fresh.root_node().to_sexp());
+        assert_eq!(
+            result.root_node().end_position(),
+            fresh.root_node().end_position()
+        );
+    }
+}
+
+#[test]
+fn edit_can_introduce_and_clear_errors() {
+    let mut parser = Parser::new(SupportedLanguage::Rust).expect("parser init");
+    let mut result = parser.parse("fn main() {}").expect("parse");
+
+    let (broken, breaking_edit) = replace("fn main() {}", 11..12, "");
+    result.edit(breaking_edit, &broken).expect("edit");
+    assert!(result.has_errors());
+
+    let (fixed, closing_edit) = replace(&broken, 11..11, "}");
+    result.edit(closing_edit, &fixed).expect("edit");
+    assert!(!result.has_errors());
+}
+
+#[rstest]
+#[case::out_of_bounds(4..40, "x")]
+#[case::mismatched_text(0..2, "pub fn")]
+fn edit_rejects_inconsistent_edits(#[case] old_range: Range<usize>, #[case] text: &str) {
+    let mut parser = Parser::new(SupportedLanguage::Rust).expect("parser init");
+    let mut result = parser.parse("fn main() {}").expect("parse");
+    let edit = InputEdit {
+        start_byte: old_range.start,
+        old_end_byte: old_range.end,
+        new_end_byte: old_range.start + text.len(),
+        start_position: Point::new(0, old_range.start),
+        old_end_position: Point::new(0, old_range.end),
+        new_end_position: Point::new(0, old_range.start + text.len()),
+    };
+
+    let error = result
+        .edit(edit, "fn other() {}")
+        .expect_err("inconsistent edit should fail");
+    assert!(error.to_string().contains("does not describe the change"));
+    assert_eq!(result.source(), "fn main() {}");
+}
+
 // =============================================================================
 // Rewriter Tests
 // =============================================================================
@@ -136,6 +240,61 @@ fn rewriter_returns_unchanged_for_no_match() {
     assert_eq!(result.output(), source);
 }
 
+#[test]
+fn rewriter_transforms_javascript() {
+    let pattern = Pattern::compile(
+        "function $NAME() { $$$BODY }",
+        SupportedLanguage::JavaScript,
+    )
+    .expect("pattern");
+    let rule = RewriteRule::new(pattern, "async function $NAME() { $$$BODY }").expect("rule");
+
+    let rewriter = Rewriter::new(SupportedLanguage::JavaScript);
+    let result = rewriter
+        .apply(&rule, "function render() { return <b>hi</b>; }")
+        .expect("rewrite");
+
+    assert_eq!(
+        result.output(),
+        "async function render() { return <b>hi</b>; }"
+    );
+}
+
+#[test]
+fn rewriter_renders_unified_diff() {
+    let pattern = Pattern::compile("fn $NAME() {}", SupportedLanguage::Rust).expect("pattern");
+    let rule = RewriteRule::new(pattern, "pub fn $NAME() {}").expect("rule");
+
+    let rewriter = Rewriter::new(SupportedLanguage::Rust);
+    let result = rewriter
+        .apply(&rule, "// helpers\nfn helper() {}\n")
+        .expect("rewrite");
+
+    assert_eq!(
+        result.unified_diff(Path::new("src/helpers.rs")),
+        concat!(
+            "diff --git a/src/helpers.rs b/src/helpers.rs\n",
+            "--- a/src/helpers.rs\n",
+            "+++ b/src/helpers.rs\n",
+            "@@ -1,2 +1,2 @@\n",
+            " // helpers\n",
+            "-fn helper() {}\n",
+            "+pub fn helper() {}\n",
+        )
+    );
+}
+
+#[test]
+fn rewriter_without_changes_renders_empty_diff() {
+    let pattern = Pattern::compile("struct $NAME {}", SupportedLanguage::Rust).expect("pattern");
+    let rule = RewriteRule::new(pattern, "enum $NAME {}").expect("rule");
+
+    let rewriter = Rewriter::new(SupportedLanguage::Rust);
+    let result = rewriter.apply(&rule, "fn main() {}").expect("rewrite");
+
+    assert!(result.unified_diff(Path::new("src/main.rs")).is_empty());
+}
+
 #[test]
 fn rewrite_rule_validates_metavariables() {
     let pattern = Pattern::compile("fn $NAME() {}", SupportedLanguage::Rust).expect("pattern");
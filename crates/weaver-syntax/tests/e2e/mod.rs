@@ -8,7 +8,8 @@ use std::path::Path;
 use rstest::{fixture, rstest};
 
 use weaver_syntax::{
-    Parser, Pattern, RewriteRule, Rewriter, SupportedLanguage, TreeSitterSyntacticLock,
+    FileValidation, Parser, Pattern, RewriteRule, Rewriter, SupportedLanguage,
+    TreeSitterSyntacticLock,
 };
 
 // =============================================================================
@@ -190,10 +191,14 @@ fn syntactic_lock_handles_multiple_languages() {
         (Path::new("app.ts"), "function main(): void {}"),
     ];
 
-    let failures = lock
-        .validate_files(files)
+    let results = lock
+        .validate_files(&files)
         .unwrap_or_else(|err| panic!("validate: {err}"));
-    assert!(failures.is_empty());
+    assert!(
+        results
+            .into_iter()
+            .all(|(_, outcome)| outcome == FileValidation::Checked(Vec::new()))
+    );
 }
 
 // =============================================================================
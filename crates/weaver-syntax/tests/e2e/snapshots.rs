@@ -34,7 +34,9 @@ fn snapshot_validation_failure_format() {
 
 #[test]
 fn snapshot_language_detection() {
-    let extensions = ["rs", "py", "pyi", "ts", "tsx", "json", "md", "toml"];
+    let extensions = [
+        "rs", "py", "pyi", "ts", "tsx", "js", "jsx", "mjs", "cjs", "json", "md", "toml",
+    ];
     let results: Vec<_> = extensions
         .iter()
         .map(|ext| {
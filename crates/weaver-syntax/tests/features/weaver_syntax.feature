@@ -36,6 +36,16 @@ Feature: Syntactic analysis and pattern matching
     When the syntactic lock validates the file
     Then validation fails
 
+  Scenario: Valid JSX code passes syntactic validation
+    Given a file "App.jsx" with content "const App = () => <div>hello</div>;"
+    When the syntactic lock validates the file
+    Then validation passes with no failures
+
+  Scenario: TypeScript annotations fail validation in JavaScript files
+    Given a file "main.js" with content "function main(x: number) {}"
+    When the syntactic lock validates the file
+    Then validation fails
+
   Scenario: Unknown file extensions are skipped
     Given a file "data.json" with content "{invalid json"
     When the syntactic lock validates the file
@@ -0,0 +1,75 @@
+//! Failure handling for `act apply-patch`.
+//!
+//! Maps harness and patch errors onto [`ApplyPatchFailure`], and writes each
+//! failure as a JSON error envelope on stderr alongside the exit status the
+//! client should report.
+
+use std::io::Write;
+
+use super::{
+    ApplyPatchError,
+    ApplyPatchFailure,
+    payloads::{GenericErrorEnvelope, VerificationErrorEnvelope},
+};
+use crate::{
+    dispatch::{errors::DispatchError, response::ResponseWriter, router::DispatchResult},
+    safety_harness::{SafetyHarnessError, VerificationFailure},
+};
+
+pub(super) fn map_harness_error(error: SafetyHarnessError) -> ApplyPatchFailure {
+    match error {
+        SafetyHarnessError::SemanticBackendUnavailable { message } => {
+            ApplyPatchFailure::BackendUnavailable(message)
+        }
+        SafetyHarnessError::SyntacticBackendUnavailable { message } => {
+            ApplyPatchFailure::BackendUnavailable(message)
+        }
+        other => ApplyPatchFailure::Io(other.to_string()),
+    }
+}
+
+pub(super) fn map_patch_error(error: ApplyPatchError) -> ApplyPatchFailure {
+    match error {
+        error @ ApplyPatchError::Io { .. } => ApplyPatchFailure::Io(error.to_string()),
+        other => ApplyPatchFailure::Patch(other),
+    }
+}
+
+/// Generic helper to write serializable error payloads to stderr.
+fn write_error_payload<W: Write, T: serde::Serialize>(
+    writer: &mut ResponseWriter<W>,
+    payload: &T,
+    status: i32,
+) -> Result<DispatchResult, DispatchError> {
+    let json = serde_json::to_string(payload)?;
+    writer.write_stderr(json)?;
+    Ok(DispatchResult::with_status(status))
+}
+
+pub(super) fn write_patch_error<W: Write>(
+    writer: &mut ResponseWriter<W>,
+    error: ApplyPatchError,
+) -> Result<DispatchResult, DispatchError> {
+    let json = error.to_json()?;
+    writer.write_stderr(json)?;
+    Ok(DispatchResult::with_status(error.exit_status()))
+}
+
+pub(super) fn write_verification_error<W: Write>(
+    writer: &mut ResponseWriter<W>,
+    phase: &str,
+    failures: Vec<VerificationFailure>,
+) -> Result<DispatchResult, DispatchError> {
+    let payload = VerificationErrorEnvelope::from_failures(phase, failures);
+    write_error_payload(writer, &payload, 1)
+}
+
+pub(super) fn write_backend_error<W: Write>(
+    writer: &mut ResponseWriter<W>,
+    kind: &'static str,
+    message: String,
+    status: i32,
+) -> Result<DispatchResult, DispatchError> {
+    let payload = GenericErrorEnvelope::new(kind, message);
+    write_error_payload(writer, &payload, status)
+}
@@ -19,6 +19,12 @@ pub(crate) enum ApplyPatchError {
     InvalidDiffHeader { line: String },
     #[error("modify operation missing SEARCH/REPLACE blocks")]
     MissingSearchReplace { path: FilePath },
+    #[error("modify operation mixes SEARCH/REPLACE blocks with unified diff hunks")]
+    MixedPatchFormats { path: FilePath },
+    #[error("invalid hunk header: {line}")]
+    InvalidHunkHeader { path: FilePath, line: String },
+    #[error("hunk {hunk_index} ended before the line counts in its header were reached")]
+    IncompleteHunk { path: FilePath, hunk_index: usize },
     #[error("create operation missing diff hunk content")]
     MissingHunk { path: FilePath },
     #[error("SEARCH block was not closed before end of patch")]
@@ -35,6 +41,29 @@ pub(crate) enum ApplyPatchError {
     DeleteMissing { path: FilePath },
     #[error("SEARCH block {block_index} did not match")]
     SearchBlockNotFound { path: FilePath, block_index: usize },
+    #[error(
+        "SEARCH block {block_index} matched {candidates} locations equally well; the first starts \
+         at line {line}"
+    )]
+    AmbiguousSearchBlock {
+        path: FilePath,
+        block_index: usize,
+        line: usize,
+        candidates: usize,
+    },
+    #[error(
+        "SEARCH block {block_index} did not match; the closest candidate starts at line {line} \
+         and matches {matched_lines} of {total_lines} lines"
+    )]
+    SearchBlockNearMiss {
+        path: FilePath,
+        block_index: usize,
+        line: usize,
+        matched_lines: usize,
+        total_lines: usize,
+    },
+    #[error("hunk {hunk_index} did not match")]
+    HunkNotFound { path: FilePath, hunk_index: usize },
     #[error("I/O error for {path}: {message} ({kind})")]
     Io {
         path: FilePath,
@@ -50,6 +79,7 @@ impl ApplyPatchError {
         let details = ApplyPatchErrorDetails {
             message: self.to_string(),
             path: self.path(),
+            line: self.line(),
             operation: self.operation().map(str::to_string),
         };
         let envelope = ApplyPatchErrorEnvelope {
@@ -63,6 +93,10 @@ impl ApplyPatchError {
     fn path(&self) -> Option<String> {
         match self {
             Self::MissingSearchReplace { path }
+            | Self::MixedPatchFormats { path }
+            | Self::InvalidHunkHeader { path, .. }
+            | Self::IncompleteHunk { path, .. }
+            | Self::HunkNotFound { path, .. }
             | Self::MissingHunk { path }
             | Self::UnclosedSearchBlock { path }
             | Self::UnclosedReplaceBlock { path }
@@ -71,6 +105,8 @@ impl ApplyPatchError {
             | Self::FileAlreadyExists { path }
             | Self::DeleteMissing { path }
             | Self::SearchBlockNotFound { path, .. }
+            | Self::AmbiguousSearchBlock { path, .. }
+            | Self::SearchBlockNearMiss { path, .. }
             | Self::Io { path, .. } => Some(path.clone().into_string()),
             Self::EmptyPatch
             | Self::BinaryPatch
@@ -80,12 +116,28 @@ impl ApplyPatchError {
         }
     }
 
+    /// Returns the line of the closest candidate for near-miss SEARCH blocks.
+    const fn line(&self) -> Option<usize> {
+        match self {
+            Self::AmbiguousSearchBlock { line, .. } | Self::SearchBlockNearMiss { line, .. } => {
+                Some(*line)
+            }
+            _ => None,
+        }
+    }
+
     fn operation(&self) -> Option<&'static str> {
         match self {
             Self::MissingSearchReplace { .. }
+            | Self::MixedPatchFormats { .. }
+            | Self::InvalidHunkHeader { .. }
+            | Self::IncompleteHunk { .. }
+            | Self::HunkNotFound { .. }
             | Self::UnclosedSearchBlock { .. }
             | Self::UnclosedReplaceBlock { .. }
             | Self::SearchBlockNotFound { .. }
+            | Self::AmbiguousSearchBlock { .. }
+            | Self::SearchBlockNearMiss { .. }
             | Self::FileNotFound { .. } => Some("modify"),
             Self::MissingHunk { .. } | Self::FileAlreadyExists { .. } => Some("create"),
             Self::DeleteMissing { .. } => Some("delete"),
@@ -114,5 +166,7 @@ struct ApplyPatchErrorDetails {
     #[serde(skip_serializing_if = "Option::is_none")]
     path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     operation: Option<String>,
 }
@@ -0,0 +1,136 @@
+//! Line ending detection and normalization for apply-patch content.
+//!
+//! Patches generated by plugins usually use LF line endings whatever the
+//! target file uses. Files whose dominant ending is CRLF are therefore matched
+//! and edited with LF endings and converted back before they are committed,
+//! so edited and untouched lines keep the file's own convention.
+
+/// Line ending style inferred from patch or file content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LineEnding {
+    /// Line feed (`\n`).
+    Lf,
+    /// Carriage return + line feed (`\r\n`).
+    CrLf,
+}
+
+impl LineEnding {
+    /// Returns the line ending used by most lines of `content`.
+    ///
+    /// Content without line breaks is treated as LF. When CRLF and bare LF
+    /// endings are equally common, CRLF wins.
+    pub(crate) fn detect(content: &str) -> Self {
+        let mut crlf = 0;
+        let mut lf = 0;
+        let bytes = content.as_bytes();
+        let mut idx = 0;
+        while idx < bytes.len() {
+            if bytes[idx] == b'\r' && bytes.get(idx + 1) == Some(&b'\n') {
+                crlf += 1;
+                idx += 2;
+                continue;
+            }
+            if bytes[idx] == b'\n' {
+                lf += 1;
+            }
+            idx += 1;
+        }
+
+        if crlf > 0 && crlf >= lf {
+            Self::CrLf
+        } else {
+            Self::Lf
+        }
+    }
+}
+
+/// Normalises line endings in text to the specified line ending style.
+///
+/// Converts CRLF to LF when targeting `LineEnding::Lf` and converts bare LF to
+/// CRLF when targeting `LineEnding::CrLf`. Other characters are preserved.
+pub(crate) fn normalise_line_endings(input: &str, line_ending: LineEnding) -> String {
+    match line_ending {
+        LineEnding::Lf => input.replace("\r\n", "\n"),
+        LineEnding::CrLf => normalise_line_endings_crlf(input),
+    }
+}
+
+enum CrlfAction {
+    EmitAndSkip(&'static str),
+    Emit(&'static str),
+    EmitChar(char),
+}
+
+/// Processes a single character for CRLF normalization and returns the action.
+fn process_char_for_crlf(ch: char, next_char: Option<char>) -> CrlfAction {
+    match (ch, next_char) {
+        ('\r', Some('\n')) => CrlfAction::EmitAndSkip("\r\n"),
+        ('\r', _) => CrlfAction::Emit("\r"),
+        ('\n', _) => CrlfAction::Emit("\r\n"),
+        (ch, _) => CrlfAction::EmitChar(ch),
+    }
+}
+
+/// Calculates the additional capacity needed for CRLF normalization.
+fn calculate_crlf_capacity(input: &str) -> usize {
+    let mut extra = 0;
+    let mut prev_cr = false;
+    for byte in input.as_bytes() {
+        let is_lf = *byte == b'\n';
+        if is_lf && !prev_cr {
+            extra += 1;
+        }
+        prev_cr = *byte == b'\r';
+    }
+    extra
+}
+
+fn normalise_line_endings_crlf(input: &str) -> String {
+    let extra = calculate_crlf_capacity(input);
+    // Phase 2: Build normalized output
+    let mut output = String::with_capacity(input.len() + extra);
+    let mut chars = input.chars().peekable();
+    while let Some(ch) = chars.next() {
+        let next = chars.peek().copied();
+        match process_char_for_crlf(ch, next) {
+            CrlfAction::EmitAndSkip(value) => {
+                output.push_str(value);
+                chars.next();
+            }
+            CrlfAction::Emit(value) => output.push_str(value),
+            CrlfAction::EmitChar(value) => output.push(value),
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for line ending detection and normalization.
+
+    use rstest::rstest;
+
+    use super::{LineEnding, normalise_line_endings};
+
+    #[rstest]
+    #[case::lf("alpha\nbeta\n", LineEnding::Lf)]
+    #[case::crlf("alpha\r\nbeta\r\n", LineEnding::CrLf)]
+    #[case::mostly_lf("alpha\r\nbeta\ngamma\n", LineEnding::Lf)]
+    #[case::mostly_crlf("alpha\r\nbeta\r\ngamma\n", LineEnding::CrLf)]
+    #[case::tie("alpha\r\nbeta\n", LineEnding::CrLf)]
+    #[case::single_line("alpha", LineEnding::Lf)]
+    fn detects_dominant_line_ending(#[case] content: &str, #[case] expected: LineEnding) {
+        assert_eq!(LineEnding::detect(content), expected);
+    }
+
+    #[rstest]
+    #[case::to_lf("a\r\nb\nc\r", LineEnding::Lf, "a\nb\nc\r")]
+    #[case::to_crlf("a\r\nb\nc\r", LineEnding::CrLf, "a\r\nb\r\nc\r")]
+    fn normalises_to_requested_ending(
+        #[case] input: &str,
+        #[case] line_ending: LineEnding,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(normalise_line_endings(input, line_ending), expected);
+    }
+}
@@ -1,36 +1,75 @@
-//! Search/replace matching helpers for apply-patch modifications.
+//! Search/replace and unified diff matching helpers for apply-patch
+//! modifications.
 
 use crate::dispatch::act::apply_patch::{
     errors::ApplyPatchError,
-    types::{FileContent, FilePath, LineEnding, SearchPattern, SearchReplaceBlock},
+    line_ending::{LineEnding, normalise_line_endings},
+    types::{
+        DiffHunk,
+        FileContent,
+        FilePath,
+        HunkLine,
+        MatchMode,
+        SearchPattern,
+        SearchReplaceBlock,
+    },
 };
 
+mod tolerant;
+
+use self::tolerant::{NearMiss, find_tolerant};
+
 /// Applies SEARCH/REPLACE blocks to the provided file content in order.
 ///
 /// Uses the patch path and block index to report errors when a block cannot be
-/// matched, normalizes replacement line endings to the dominant line ending in
-/// the original content, and updates the match cursor after each replacement.
+/// matched, normalizes SEARCH and replacement line endings to the dominant
+/// line ending in the original content, and updates the match cursor after
+/// each replacement. Content whose dominant ending is CRLF is matched with LF
+/// endings and converted back afterwards, so LF patches apply exactly. In
+/// [`MatchMode::Tolerant`], a block that matches neither exactly nor fuzzily
+/// is retried line by line, ignoring trailing whitespace and accepting the
+/// unique closest candidate.
 ///
 /// # Errors
 ///
 /// Returns `ApplyPatchError::SearchBlockNotFound` when a search block does not
-/// match the remaining content in sequence.
+/// match the remaining content in sequence. In tolerant mode, returns
+/// `ApplyPatchError::AmbiguousSearchBlock` or
+/// `ApplyPatchError::SearchBlockNearMiss` with the closest candidate's line
+/// when no single candidate is close enough.
 pub(crate) fn apply_search_replace(
     path: &FilePath,
     original: &FileContent,
     blocks: &[SearchReplaceBlock],
+    mode: MatchMode,
+) -> Result<FileContent, ApplyPatchError> {
+    with_lf_line_endings(original, |content| {
+        search_replace_in(path, content, blocks, mode)
+    })
+}
+
+fn search_replace_in(
+    path: &FilePath,
+    original: &FileContent,
+    blocks: &[SearchReplaceBlock],
+    mode: MatchMode,
 ) -> Result<FileContent, ApplyPatchError> {
     let mut content = FileContent::new(original.as_str());
     let mut cursor = 0;
-    let line_ending = dominant_line_ending(original.as_str());
+    let line_ending = LineEnding::detect(original.as_str());
 
     for (index, block) in blocks.iter().enumerate() {
-        let (start, end) = find_exact(&content, cursor, &block.search)
+        let miss = |near_miss: NearMiss| near_miss.into_error(path, index + 1);
+        let search = SearchPattern::new(normalise_line_endings(block.search.as_str(), line_ending));
+        let (start, end) = match find_exact(&content, cursor, &search)
             .or_else(|| find_fuzzy(&content, cursor, &block.search))
-            .ok_or_else(|| ApplyPatchError::SearchBlockNotFound {
-                path: path.clone(),
-                block_index: index + 1,
-            })?;
+        {
+            Some(span) => span,
+            None if mode == MatchMode::Tolerant => {
+                find_tolerant(content.as_str(), cursor, block.search.as_str()).map_err(miss)?
+            }
+            None => return Err(miss(NearMiss::NotFound)),
+        };
 
         let replacement = normalise_line_endings(block.replace.as_str(), line_ending);
         content.replace_range(start..end, &replacement);
@@ -40,6 +79,132 @@ pub(crate) fn apply_search_replace(
     Ok(content)
 }
 
+/// Applies unified diff hunks to the provided file content in order.
+///
+/// Each hunk is located by matching its context and removed lines against
+/// whole lines of the content, treating CRLF and LF endings as equal. The
+/// search starts at the line named in the hunk header and widens outwards, so
+/// hunks still apply when the file has drifted, but never reaches back before
+/// the end of the previous hunk. Context lines keep the file's own text, and
+/// added lines use the dominant line ending of the original content, which is
+/// restored afterwards when it is CRLF.
+///
+/// # Errors
+///
+/// Returns `ApplyPatchError::HunkNotFound` when a hunk's original lines do not
+/// appear in the remaining content.
+pub(crate) fn apply_hunks(
+    path: &FilePath,
+    original: &FileContent,
+    hunks: &[DiffHunk],
+) -> Result<FileContent, ApplyPatchError> {
+    with_lf_line_endings(original, |content| apply_hunks_in(path, content, hunks))
+}
+
+fn apply_hunks_in(
+    path: &FilePath,
+    original: &FileContent,
+    hunks: &[DiffHunk],
+) -> Result<FileContent, ApplyPatchError> {
+    let lines: Vec<&str> = original.as_str().split_inclusive('\n').collect();
+    let line_ending = LineEnding::detect(original.as_str());
+    let mut output = String::with_capacity(original.as_str().len());
+    let mut cursor = 0;
+
+    for (index, hunk) in hunks.iter().enumerate() {
+        let expected: Vec<&str> = hunk
+            .lines
+            .iter()
+            .filter_map(HunkLine::original_text)
+            .collect();
+        let start = locate_hunk(&lines, cursor, hunk.old_start, &expected).ok_or_else(|| {
+            ApplyPatchError::HunkNotFound {
+                path: path.clone(),
+                hunk_index: index + 1,
+            }
+        })?;
+        output.extend(lines[cursor..start].iter().copied());
+        let mut matched = lines[start..].iter();
+        for line in &hunk.lines {
+            match line {
+                HunkLine::Context(_) => output.extend(matched.next().copied()),
+                HunkLine::Removed(_) => {
+                    matched.next();
+                }
+                HunkLine::Added(text) => {
+                    output.push_str(&normalise_line_endings(text, line_ending));
+                }
+            }
+        }
+        cursor = start + expected.len();
+    }
+    output.extend(lines[cursor..].iter().copied());
+
+    Ok(FileContent::new(output))
+}
+
+/// Runs `apply` against `original` with LF line endings when CRLF dominates,
+/// converting the result back to CRLF.
+///
+/// Content that is already mostly LF is passed through untouched, so stray
+/// CRLF lines in it survive an edit elsewhere in the file.
+fn with_lf_line_endings(
+    original: &FileContent,
+    apply: impl FnOnce(&FileContent) -> Result<FileContent, ApplyPatchError>,
+) -> Result<FileContent, ApplyPatchError> {
+    match LineEnding::detect(original.as_str()) {
+        LineEnding::Lf => apply(original),
+        LineEnding::CrLf => {
+            let normalised =
+                FileContent::new(normalise_line_endings(original.as_str(), LineEnding::Lf));
+            let applied = apply(&normalised)?;
+            Ok(FileContent::new(normalise_line_endings(
+                applied.as_str(),
+                LineEnding::CrLf,
+            )))
+        }
+    }
+}
+
+/// Returns the line index at or after `cursor` where `expected` matches,
+/// preferring the match nearest to the hunk's one-based `old_start` line.
+fn locate_hunk(
+    lines: &[&str],
+    cursor: usize,
+    old_start: usize,
+    expected: &[&str],
+) -> Option<usize> {
+    let last_start = lines.len().checked_sub(expected.len())?;
+    if cursor > last_start {
+        return None;
+    }
+    // A hunk without original lines inserts after `old_start`.
+    let anchor = if expected.is_empty() {
+        old_start
+    } else {
+        old_start.saturating_sub(1)
+    };
+    let anchor = anchor.clamp(cursor, last_start);
+    let widest = (anchor - cursor).max(last_start - anchor);
+    (0..=widest)
+        .flat_map(|distance| [anchor.checked_sub(distance), anchor.checked_add(distance)])
+        .flatten()
+        .filter(|start| (cursor..=last_start).contains(start))
+        .find(|&start| {
+            lines[start..start + expected.len()]
+                .iter()
+                .zip(expected)
+                .all(|(actual, wanted)| same_line(actual, wanted))
+        })
+}
+
+/// Compares two lines, ignoring whether they end in CRLF or LF but not
+/// whether they end in a newline at all.
+fn same_line(actual: &str, wanted: &str) -> bool {
+    actual.trim_end_matches(['\r', '\n']) == wanted.trim_end_matches(['\r', '\n'])
+        && actual.ends_with('\n') == wanted.ends_with('\n')
+}
+
 fn find_exact(
     content: &FileContent,
     cursor: usize,
@@ -86,91 +251,6 @@ fn find_fuzzy(
 
 fn trim_fuzzy_whitespace(value: &str) -> &str { value.trim_matches(|ch| ch == ' ' || ch == '\t') }
 
-fn dominant_line_ending(content: &str) -> LineEnding {
-    let mut crlf = 0;
-    let mut lf = 0;
-    let bytes = content.as_bytes();
-    let mut idx = 0;
-    while idx < bytes.len() {
-        if bytes[idx] == b'\r' && bytes.get(idx + 1) == Some(&b'\n') {
-            crlf += 1;
-            idx += 2;
-            continue;
-        }
-        if bytes[idx] == b'\n' {
-            lf += 1;
-        }
-        idx += 1;
-    }
-
-    // Tie-breaker: when counts are equal and CRLF is present, prefer CRLF.
-    if crlf > 0 && crlf >= lf {
-        LineEnding::CrLf
-    } else {
-        LineEnding::Lf
-    }
-}
-
-/// Normalises line endings in text to the specified line ending style.
-///
-/// Converts CRLF to LF when targeting `LineEnding::Lf` and converts bare LF to
-/// CRLF when targeting `LineEnding::CrLf`. Other characters are preserved.
-pub(crate) fn normalise_line_endings(input: &str, line_ending: LineEnding) -> String {
-    match line_ending {
-        LineEnding::Lf => input.replace("\r\n", "\n"),
-        LineEnding::CrLf => normalise_line_endings_crlf(input),
-    }
-}
-
-enum CrlfAction {
-    EmitAndSkip(&'static str),
-    Emit(&'static str),
-    EmitChar(char),
-}
-
-/// Processes a single character for CRLF normalization and returns the action.
-fn process_char_for_crlf(ch: char, next_char: Option<char>) -> CrlfAction {
-    match (ch, next_char) {
-        ('\r', Some('\n')) => CrlfAction::EmitAndSkip("\r\n"),
-        ('\r', _) => CrlfAction::Emit("\r"),
-        ('\n', _) => CrlfAction::Emit("\r\n"),
-        (ch, _) => CrlfAction::EmitChar(ch),
-    }
-}
-
-/// Calculates the additional capacity needed for CRLF normalization.
-fn calculate_crlf_capacity(input: &str) -> usize {
-    let mut extra = 0;
-    let mut prev_cr = false;
-    for byte in input.as_bytes() {
-        let is_lf = *byte == b'\n';
-        if is_lf && !prev_cr {
-            extra += 1;
-        }
-        prev_cr = *byte == b'\r';
-    }
-    extra
-}
-
-fn normalise_line_endings_crlf(input: &str) -> String {
-    let extra = calculate_crlf_capacity(input);
-    // Phase 2: Build normalized output
-    let mut output = String::with_capacity(input.len() + extra);
-    let mut chars = input.chars().peekable();
-    while let Some(ch) = chars.next() {
-        let next = chars.peek().copied();
-        match process_char_for_crlf(ch, next) {
-            CrlfAction::EmitAndSkip(value) => {
-                output.push_str(value);
-                chars.next();
-            }
-            CrlfAction::Emit(value) => output.push_str(value),
-            CrlfAction::EmitChar(value) => output.push(value),
-        }
-    }
-    output
-}
-
 struct NormalizedContent {
     normalized: String,
     norm_to_orig: Vec<usize>,
@@ -228,81 +308,4 @@ fn map_crlf_indices(orig_to_norm: &mut [usize], next_idx: usize, norm_index: usi
 }
 
 #[cfg(test)]
-mod tests {
-    //! Tests for apply-patch matcher helpers.
-
-    use rstest::{fixture, rstest};
-    use weaver_test_macros::allow_fixture_expansion_lints;
-
-    use super::*;
-    use crate::dispatch::act::apply_patch::types::{
-        FileContent,
-        FilePath,
-        ReplacementText,
-        SearchPattern,
-        SearchReplaceBlock,
-    };
-
-    #[allow_fixture_expansion_lints]
-    #[fixture]
-    fn path() -> FilePath { FilePath::new("file.txt") }
-
-    #[rstest]
-    #[case::exact_match(
-        "alpha\nbeta\ngamma\n",
-        vec![SearchReplaceBlock {
-            search: SearchPattern::new("beta\n"),
-            replace: ReplacementText::new("delta\n"),
-        }],
-        "alpha\ndelta\ngamma\n",
-    )]
-    #[case::fuzzy_line_endings(
-        "alpha\r\nbeta\r\ngamma\r\n",
-        vec![SearchReplaceBlock {
-            search: SearchPattern::new("beta\n"),
-            replace: ReplacementText::new("delta\n"),
-        }],
-        "alpha\r\ndelta\r\ngamma\r\n",
-    )]
-    #[case::cursor_ordered(
-        "one two one two",
-        vec![
-            SearchReplaceBlock {
-                search: SearchPattern::new("one"),
-                replace: ReplacementText::new("ONE"),
-            },
-            SearchReplaceBlock {
-                search: SearchPattern::new("one"),
-                replace: ReplacementText::new("UNO"),
-            },
-        ],
-        "ONE two UNO two",
-    )]
-    fn apply_search_replace_succeeds(
-        path: FilePath,
-        #[case] original: &str,
-        #[case] blocks: Vec<SearchReplaceBlock>,
-        #[case] expected: &str,
-    ) {
-        let original = FileContent::new(original);
-        let result = apply_search_replace(&path, &original, &blocks).expect("apply");
-        assert_eq!(result.as_str(), expected);
-    }
-
-    #[rstest]
-    fn apply_search_replace_rejects_missing_block(path: FilePath) {
-        let blocks = vec![SearchReplaceBlock {
-            search: SearchPattern::new("missing"),
-            replace: ReplacementText::new("new"),
-        }];
-        let original = FileContent::new("content");
-        let error = apply_search_replace(&path, &original, &blocks).expect_err("error");
-        match error {
-            ApplyPatchError::SearchBlockNotFound { path, block_index } => {
-                assert_eq!(path.as_str(), "file.txt");
-                assert_eq!(block_index, 1);
-            }
-            other => panic!("unexpected error: {other:?}"),
-        }
-    }
-}
+mod tests;
@@ -0,0 +1,275 @@
+//! Tests for apply-patch matcher helpers.
+
+use rstest::{fixture, rstest};
+use weaver_test_macros::allow_fixture_expansion_lints;
+
+use super::*;
+use crate::dispatch::act::apply_patch::{
+    parser::parse_patch,
+    types::{
+        DiffHunk,
+        FileContent,
+        FilePath,
+        HunkLine,
+        MatchMode,
+        PatchOperation,
+        PatchText,
+        ReplacementText,
+        SearchPattern,
+        SearchReplaceBlock,
+    },
+};
+
+#[allow_fixture_expansion_lints]
+#[fixture]
+fn path() -> FilePath { FilePath::new("file.txt") }
+
+#[rstest]
+#[case::exact_match(
+    "alpha\nbeta\ngamma\n",
+    vec![SearchReplaceBlock {
+        search: SearchPattern::new("beta\n"),
+        replace: ReplacementText::new("delta\n"),
+    }],
+    "alpha\ndelta\ngamma\n",
+)]
+#[case::fuzzy_line_endings(
+    "alpha\r\nbeta\r\ngamma\r\n",
+    vec![SearchReplaceBlock {
+        search: SearchPattern::new("beta\n"),
+        replace: ReplacementText::new("delta\n"),
+    }],
+    "alpha\r\ndelta\r\ngamma\r\n",
+)]
+#[case::cursor_ordered(
+    "one two one two",
+    vec![
+        SearchReplaceBlock {
+            search: SearchPattern::new("one"),
+            replace: ReplacementText::new("ONE"),
+        },
+        SearchReplaceBlock {
+            search: SearchPattern::new("one"),
+            replace: ReplacementText::new("UNO"),
+        },
+    ],
+    "ONE two UNO two",
+)]
+fn apply_search_replace_succeeds(
+    path: FilePath,
+    #[case] original: &str,
+    #[case] blocks: Vec<SearchReplaceBlock>,
+    #[case] expected: &str,
+) {
+    let original = FileContent::new(original);
+    let result = apply_search_replace(&path, &original, &blocks, MatchMode::Strict).expect("apply");
+    assert_eq!(result.as_str(), expected);
+}
+
+#[rstest]
+fn apply_search_replace_rejects_missing_block(path: FilePath) {
+    let blocks = vec![SearchReplaceBlock {
+        search: SearchPattern::new("missing"),
+        replace: ReplacementText::new("new"),
+    }];
+    let original = FileContent::new("content");
+    let error =
+        apply_search_replace(&path, &original, &blocks, MatchMode::Strict).expect_err("error");
+    match error {
+        ApplyPatchError::SearchBlockNotFound { path, block_index } => {
+            assert_eq!(path.as_str(), "file.txt");
+            assert_eq!(block_index, 1);
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+fn replace_beta_hunk(old_start: usize) -> DiffHunk {
+    DiffHunk {
+        old_start,
+        lines: vec![
+            HunkLine::Context(String::from("alpha\n")),
+            HunkLine::Removed(String::from("beta\n")),
+            HunkLine::Added(String::from("delta\n")),
+        ],
+    }
+}
+
+#[rstest]
+#[case::single_hunk("alpha\nbeta\ngamma\n", replace_beta_hunk(1), "alpha\ndelta\ngamma\n")]
+#[case::drifted_start(
+    "intro\n\nalpha\nbeta\n",
+    replace_beta_hunk(1),
+    "intro\n\nalpha\ndelta\n"
+)]
+#[case::crlf_content(
+    "alpha\r\nbeta\r\ngamma\r\n",
+    replace_beta_hunk(1),
+    "alpha\r\ndelta\r\ngamma\r\n"
+)]
+#[case::nearest_match(
+    "alpha\nbeta\nalpha\nbeta\n",
+    replace_beta_hunk(3),
+    "alpha\nbeta\nalpha\ndelta\n"
+)]
+#[case::missing_final_newline(
+    "alpha\nbeta",
+    DiffHunk {
+        old_start: 2,
+        lines: vec![
+            HunkLine::Removed(String::from("beta")),
+            HunkLine::Added(String::from("beta\n")),
+        ],
+    },
+    "alpha\nbeta\n"
+)]
+#[case::insertion_into_empty_file(
+    "",
+    DiffHunk {
+        old_start: 0,
+        lines: vec![HunkLine::Added(String::from("alpha\n"))],
+    },
+    "alpha\n"
+)]
+fn apply_hunks_succeeds(
+    path: FilePath,
+    #[case] original: &str,
+    #[case] hunk: DiffHunk,
+    #[case] expected: &str,
+) {
+    let original = FileContent::new(original);
+    let result = apply_hunks(&path, &original, &[hunk]).expect("apply");
+    assert_eq!(result.as_str(), expected);
+}
+
+#[rstest]
+#[case::missing_context("alpha\ngamma\n")]
+#[case::missing_final_newline("alpha\nbeta")]
+fn apply_hunks_rejects_unmatched_hunk(path: FilePath, #[case] original: &str) {
+    let original = FileContent::new(original);
+    let error = apply_hunks(&path, &original, &[replace_beta_hunk(1)]).expect_err("error");
+    assert!(matches!(
+        error,
+        ApplyPatchError::HunkNotFound { hunk_index: 1, .. }
+    ));
+}
+
+fn block(search: &str, replace: &str) -> SearchReplaceBlock {
+    SearchReplaceBlock {
+        search: SearchPattern::new(search),
+        replace: ReplacementText::new(replace),
+    }
+}
+
+#[rstest]
+#[case::trailing_spaces(
+    "fn a() {   \n    body();  \n}\n",
+    "fn a() {\n    body();\n}\n",
+    "fn a() {\n    other();\n}\n"
+)]
+#[case::drifted_line(
+    "fn a() {\n    body(1);\n}\nfn b() {}\n",
+    "fn a() {\n    body(0);\n}\n",
+    "fn a() {\n    other();\n}\nfn b() {}\n"
+)]
+fn tolerant_mode_applies_near_matches(
+    path: FilePath,
+    #[case] original: &str,
+    #[case] search: &str,
+    #[case] expected: &str,
+) {
+    let original = FileContent::new(original);
+    let blocks = [block(search, "fn a() {\n    other();\n}\n")];
+
+    let strict = apply_search_replace(&path, &original, &blocks, MatchMode::Strict);
+    let tolerant = apply_search_replace(&path, &original, &blocks, MatchMode::Tolerant)
+        .expect("tolerant apply");
+
+    assert!(matches!(
+        strict,
+        Err(ApplyPatchError::SearchBlockNotFound { block_index: 1, .. })
+    ));
+    assert_eq!(tolerant.as_str(), expected);
+}
+
+#[rstest]
+fn tolerant_mode_reports_ambiguous_candidates(path: FilePath) {
+    let original = FileContent::new("one \ntwo \none \ntwo \n");
+    let blocks = [block("one\ntwo\n", "three\n")];
+
+    let error = apply_search_replace(&path, &original, &blocks, MatchMode::Tolerant)
+        .expect_err("ambiguous");
+
+    assert!(matches!(
+        error,
+        ApplyPatchError::AmbiguousSearchBlock {
+            block_index: 1,
+            line: 1,
+            candidates: 2,
+            ..
+        }
+    ));
+}
+
+#[rstest]
+fn tolerant_mode_reports_closest_near_miss(path: FilePath) {
+    let original = FileContent::new("alpha\nbeta\ngamma\ndelta\n");
+    let blocks = [block("gamma\nomega\n", "epsilon\n")];
+
+    let error = apply_search_replace(&path, &original, &blocks, MatchMode::Tolerant)
+        .expect_err("near miss");
+
+    assert!(matches!(
+        error,
+        ApplyPatchError::SearchBlockNearMiss {
+            block_index: 1,
+            line: 3,
+            matched_lines: 1,
+            total_lines: 2,
+            ..
+        }
+    ));
+}
+
+const CRLF_SOURCE: &str = "def old_name():\r\n    return 1\r\n\r\nprint(old_name())\r\n";
+
+#[rstest]
+#[case::lf_patch(
+    "def old_name():\n    return 1\n\nprint(old_name())\n",
+    "def new_name():\n    return 1\n\nprint(new_name())\n",
+    "def new_name():\r\n    return 1\r\n\r\nprint(new_name())\r\n"
+)]
+#[case::crlf_patch(
+    CRLF_SOURCE,
+    "def new_name():\r\n    return 1\r\n\r\nprint(new_name())\r\n",
+    "def new_name():\r\n    return 1\r\n\r\nprint(new_name())\r\n"
+)]
+#[case::indented_block(
+    "    return 1\n",
+    "    return 2\n",
+    "def old_name():\r\n    return 2\r\n\r\nprint(old_name())\r\n"
+)]
+fn crlf_file_round_trips_through_rename_patch(
+    #[case] search: &str,
+    #[case] replace: &str,
+    #[case] expected: &str,
+) {
+    let patch = format!(
+        "diff --git a/main.py b/main.py\n<<<<<<< SEARCH\n{search}=======\n{replace}>>>>>>> \
+         REPLACE\n"
+    );
+    let operations = parse_patch(&PatchText::from(patch.as_str())).expect("parse patch");
+    let [PatchOperation::Modify { path, blocks }] = operations.as_slice() else {
+        panic!("unexpected operations: {operations:?}");
+    };
+
+    let result = apply_search_replace(
+        path,
+        &FileContent::new(CRLF_SOURCE),
+        blocks,
+        MatchMode::Strict,
+    )
+    .expect("apply");
+
+    assert_eq!(result.as_str(), expected);
+}
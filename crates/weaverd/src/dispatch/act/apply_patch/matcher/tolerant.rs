@@ -0,0 +1,138 @@
+//! Tolerant SEARCH block matching for `act apply-patch --tolerant`.
+//!
+//! Candidates are windows of whole lines, starting at or after the cursor,
+//! with as many lines as the SEARCH block. Each window is scored by how many
+//! of its lines equal the corresponding SEARCH line once trailing whitespace
+//! and line endings are ignored. The best window is accepted when it is the
+//! only one with that score and more than half of its lines match, which lets
+//! a block through when one line in a larger block drifted.
+
+use crate::dispatch::act::apply_patch::{errors::ApplyPatchError, types::FilePath};
+
+/// Reason a tolerant match could not settle on a single location.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum NearMiss {
+    /// No line of the SEARCH block appears in the remaining content.
+    NotFound,
+    /// Several windows share the best score.
+    Ambiguous { line: usize, candidates: usize },
+    /// The best window matches too few lines to be trusted.
+    Partial {
+        line: usize,
+        matched_lines: usize,
+        total_lines: usize,
+    },
+}
+
+impl NearMiss {
+    pub(super) fn into_error(self, path: &FilePath, block_index: usize) -> ApplyPatchError {
+        let path = path.clone();
+        match self {
+            Self::NotFound => ApplyPatchError::SearchBlockNotFound { path, block_index },
+            Self::Ambiguous { line, candidates } => ApplyPatchError::AmbiguousSearchBlock {
+                path,
+                block_index,
+                line,
+                candidates,
+            },
+            Self::Partial {
+                line,
+                matched_lines,
+                total_lines,
+            } => ApplyPatchError::SearchBlockNearMiss {
+                path,
+                block_index,
+                line,
+                matched_lines,
+                total_lines,
+            },
+        }
+    }
+}
+
+/// Finds the byte span of the unique closest match for `search` in `content`
+/// at or after `cursor`.
+///
+/// The span covers whole lines, including the final line ending only when
+/// the SEARCH block itself ends with one. Reported line numbers are
+/// one-based.
+pub(super) fn find_tolerant(
+    content: &str,
+    cursor: usize,
+    search: &str,
+) -> Result<(usize, usize), NearMiss> {
+    let wanted: Vec<&str> = search.lines().map(str::trim_end).collect();
+    let lines = lines_from(content, cursor);
+    let Some(last_first) = lines.len().checked_sub(wanted.len()) else {
+        return Err(NearMiss::NotFound);
+    };
+    if wanted.is_empty() {
+        return Err(NearMiss::NotFound);
+    }
+
+    let scores: Vec<usize> = (0..=last_first)
+        .map(|first| score(&lines[first..first + wanted.len()], &wanted))
+        .collect();
+    let best = scores.iter().copied().max().unwrap_or(0);
+    if best == 0 {
+        return Err(NearMiss::NotFound);
+    }
+    let mut candidates = (0..=last_first).filter(|&first| scores[first] == best);
+    let Some(first) = candidates.next() else {
+        return Err(NearMiss::NotFound);
+    };
+    let line = line_number(content, lines[first].0);
+    let others = candidates.count();
+    if others > 0 {
+        return Err(NearMiss::Ambiguous {
+            line,
+            candidates: others + 1,
+        });
+    }
+    if best * 2 <= wanted.len() {
+        return Err(NearMiss::Partial {
+            line,
+            matched_lines: best,
+            total_lines: wanted.len(),
+        });
+    }
+
+    let (start, _) = lines[first];
+    let (last_start, last_line) = lines[first + wanted.len() - 1];
+    let end = if search.ends_with('\n') {
+        last_start + last_line.len()
+    } else {
+        last_start + last_line.trim_end_matches(['\r', '\n']).len()
+    };
+    Ok((start, end))
+}
+
+/// Returns the lines of `content` that start at or after `cursor`, paired
+/// with their byte offsets.
+fn lines_from(content: &str, cursor: usize) -> Vec<(usize, &str)> {
+    let mut offset = 0;
+    content
+        .split_inclusive('\n')
+        .filter_map(|line| {
+            let start = offset;
+            offset += line.len();
+            (start >= cursor).then_some((start, line))
+        })
+        .collect()
+}
+
+fn score(window: &[(usize, &str)], wanted: &[&str]) -> usize {
+    window
+        .iter()
+        .zip(wanted)
+        .filter(|((_, line), wanted_line)| line.trim_end() == **wanted_line)
+        .count()
+}
+
+fn line_number(content: &str, offset: usize) -> usize {
+    content.as_bytes()[..offset]
+        .iter()
+        .filter(|&&byte| byte == b'\n')
+        .count()
+        + 1
+}
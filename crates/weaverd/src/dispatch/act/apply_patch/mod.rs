@@ -1,9 +1,12 @@
 //! Handler for `act apply-patch`.
 //!
-//! Parses Git-style patch streams, applies SEARCH/REPLACE modifications, and
-//! executes the Double-Lock safety harness before committing changes.
+//! Parses Git-style patch streams, applies SEARCH/REPLACE blocks or unified
+//! diff hunks, and executes the Double-Lock safety harness before committing
+//! changes.
 
+mod error_output;
 mod errors;
+mod line_ending;
 mod matcher;
 mod parser;
 mod payloads;
@@ -21,16 +24,30 @@ use tracing::debug;
 
 pub(crate) use self::errors::ApplyPatchError;
 use self::{
-    matcher::apply_search_replace,
+    error_output::{
+        map_harness_error,
+        map_patch_error,
+        write_backend_error,
+        write_patch_error,
+        write_verification_error,
+    },
+    matcher::{apply_hunks, apply_search_replace},
     parser::parse_patch,
-    payloads::{ApplyPatchSummary, GenericErrorEnvelope, VerificationErrorEnvelope},
+    payloads::{ApplyPatchSummary, FileOutcome},
     semantic_lock::LspSemanticLockAdapter,
-    types::{FileContent, FilePath, PatchOperation, PatchText, SearchReplaceBlock},
-    workspace::{ValidatedPath, path_exists, read_patch_target, resolve_path},
+    types::{FileContent, FilePath, MatchMode, PatchOperation, PatchText},
+    workspace::{
+        ValidatedPath,
+        ensure_create_target_contained,
+        path_exists,
+        read_patch_target,
+        resolve_path,
+    },
 };
 use crate::{
     backends::{BackendKind, FusionBackends},
     dispatch::{
+        cancellation::AFTER_BACKEND_STARTUP,
         errors::DispatchError,
         request::CommandRequest,
         response::ResponseWriter,
@@ -39,7 +56,6 @@ use crate::{
     safety_harness::{
         ContentChange,
         ContentTransaction,
-        SafetyHarnessError,
         SemanticLock,
         SyntacticLock,
         TransactionOutcome,
@@ -50,6 +66,10 @@ use crate::{
 };
 
 /// Handles `act apply-patch` requests.
+///
+/// The optional `--tolerant` argument lets SEARCH blocks that differ from the
+/// target by trailing whitespace or a drifted line apply at their unique
+/// closest match.
 pub fn handle<W: Write>(
     request: &CommandRequest,
     writer: &mut ResponseWriter<W>,
@@ -59,16 +79,52 @@ pub fn handle<W: Write>(
     let patch = request.patch().ok_or_else(|| {
         DispatchError::invalid_arguments("apply-patch requires patch content in the request")
     })?;
+    let match_mode = parse_match_mode(&request.arguments)?;
+    execute_patch(patch, match_mode, false, writer, backends, workspace_root)
+}
+
+/// Verifies and applies a patch generated by another handler.
+///
+/// SEARCH blocks must match exactly. When `dry_run` is set the patch still
+/// passes through both locks, but nothing is written and the summary carries
+/// the patch as the would-be diff.
+pub(crate) fn handle_generated_patch<W: Write>(
+    patch: &str,
+    dry_run: bool,
+    writer: &mut ResponseWriter<W>,
+    backends: &mut FusionBackends<SemanticBackendProvider>,
+    workspace_root: &Path,
+) -> Result<DispatchResult, DispatchError> {
+    execute_patch(
+        patch,
+        MatchMode::Strict,
+        dry_run,
+        writer,
+        backends,
+        workspace_root,
+    )
+}
 
+fn execute_patch<W: Write>(
+    patch: &str,
+    match_mode: MatchMode,
+    dry_run: bool,
+    writer: &mut ResponseWriter<W>,
+    backends: &mut FusionBackends<SemanticBackendProvider>,
+    workspace_root: &Path,
+) -> Result<DispatchResult, DispatchError> {
     debug!(
         target: DISPATCH_TARGET,
         patch_bytes = patch.len(),
+        ?match_mode,
+        dry_run,
         "handling apply-patch"
     );
 
     backends
         .ensure_started(BackendKind::Semantic)
         .map_err(DispatchError::backend_startup)?;
+    writer.check_cancelled(AFTER_BACKEND_STARTUP)?;
 
     let semantic_lock = LspSemanticLockAdapter::new(backends.provider());
     let syntactic_lock = TreeSitterSyntacticLockAdapter::new();
@@ -76,7 +132,9 @@ pub fn handle<W: Write>(
         workspace_root.to_path_buf(),
         &syntactic_lock,
         &semantic_lock,
-    );
+    )
+    .with_match_mode(match_mode)
+    .with_dry_run(dry_run);
 
     match executor.execute(patch) {
         Ok(summary) => {
@@ -97,10 +155,22 @@ pub fn handle<W: Write>(
     }
 }
 
+fn parse_match_mode(arguments: &[String]) -> Result<MatchMode, DispatchError> {
+    match arguments {
+        [] => Ok(MatchMode::Strict),
+        [flag] if flag == "--tolerant" => Ok(MatchMode::Tolerant),
+        _ => Err(DispatchError::invalid_arguments(
+            "act apply-patch only accepts the optional --tolerant flag",
+        )),
+    }
+}
+
 pub(crate) struct ApplyPatchExecutor<'a> {
     workspace_root: PathBuf,
     syntactic_lock: &'a dyn SyntacticLock,
     semantic_lock: &'a dyn SemanticLock,
+    match_mode: MatchMode,
+    dry_run: bool,
 }
 
 /// Represents the kind of file system change to validate and construct.
@@ -119,9 +189,24 @@ impl<'a> ApplyPatchExecutor<'a> {
             workspace_root,
             syntactic_lock,
             semantic_lock,
+            match_mode: MatchMode::Strict,
+            dry_run: false,
         }
     }
 
+    /// Sets how closely SEARCH blocks must match their target files.
+    pub(crate) const fn with_match_mode(mut self, match_mode: MatchMode) -> Self {
+        self.match_mode = match_mode;
+        self
+    }
+
+    /// Validates the patch without committing it, returning the would-be
+    /// summary.
+    pub(crate) const fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
     pub(crate) fn execute(&self, patch: &str) -> Result<ApplyPatchSummary, ApplyPatchFailure> {
         let workspace_dir =
             Dir::open_ambient_dir(&self.workspace_root, cap_std::ambient_authority()).map_err(
@@ -135,42 +220,54 @@ impl<'a> ApplyPatchExecutor<'a> {
 
         let mut transaction = ContentTransaction::new(self.syntactic_lock, self.semantic_lock);
         transaction.add_changes(changes.iter().cloned());
+        let outcome = if self.dry_run {
+            transaction.validate_only(&workspace_dir, &self.workspace_root)
+        } else {
+            transaction.execute(&workspace_dir, &self.workspace_root)
+        };
 
-        match transaction.execute(&workspace_dir, &self.workspace_root) {
-            Ok(TransactionOutcome::Committed { files_modified }) => {
-                let files_deleted = changes
-                    .iter()
-                    .filter(|change| matches!(change, ContentChange::Delete { .. }))
-                    .count();
-                debug_assert!(
-                    files_modified >= files_deleted,
-                    concat!("files_modified ({}) smaller than files_deleted ", "({})"),
-                    files_modified,
-                    files_deleted,
-                );
-                Ok(ApplyPatchSummary {
-                    status: "ok",
-                    files_written: files_modified.saturating_sub(files_deleted),
-                    files_deleted,
-                })
-            }
+        let files_modified = match outcome {
+            Ok(TransactionOutcome::Committed { files_modified }) => files_modified,
+            Ok(TransactionOutcome::Validated { files }) => files.len(),
             Ok(TransactionOutcome::SyntacticLockFailed { failures }) => {
-                Err(ApplyPatchFailure::Verification {
+                return Err(ApplyPatchFailure::Verification {
                     phase: "SyntacticLock",
                     failures,
-                })
+                });
             }
             Ok(TransactionOutcome::SemanticLockFailed { failures }) => {
-                Err(ApplyPatchFailure::Verification {
+                return Err(ApplyPatchFailure::Verification {
                     phase: "SemanticLock",
                     failures,
-                })
+                });
             }
             Ok(TransactionOutcome::NoChanges) => {
-                Err(ApplyPatchFailure::Patch(ApplyPatchError::EmptyTransaction))
+                return Err(ApplyPatchFailure::Patch(ApplyPatchError::EmptyTransaction));
             }
-            Err(error) => Err(map_harness_error(error)),
-        }
+            Err(error) => return Err(map_harness_error(error)),
+        };
+        let files_deleted = changes
+            .iter()
+            .filter(|change| matches!(change, ContentChange::Delete { .. }))
+            .count();
+        debug_assert!(
+            files_modified >= files_deleted,
+            concat!("files_modified ({}) smaller than files_deleted ", "({})"),
+            files_modified,
+            files_deleted,
+        );
+        Ok(ApplyPatchSummary {
+            status: "ok",
+            files_written: files_modified.saturating_sub(files_deleted),
+            files_deleted,
+            files: operations
+                .iter()
+                .zip(&changes)
+                .map(|(operation, change)| FileOutcome::new(operation, change))
+                .collect(),
+            dry_run: self.dry_run,
+            diff: self.dry_run.then(|| patch.as_str().to_owned()),
+        })
     }
 
     fn build_changes(
@@ -182,7 +279,14 @@ impl<'a> ApplyPatchExecutor<'a> {
         for operation in operations {
             let change = match operation {
                 PatchOperation::Modify { path, blocks } => {
-                    self.build_modify_change(workspace_dir, path, blocks)?
+                    self.build_modify_change(workspace_dir, path, |original| {
+                        apply_search_replace(path, original, blocks, self.match_mode)
+                    })?
+                }
+                PatchOperation::ModifyHunks { path, hunks } => {
+                    self.build_modify_change(workspace_dir, path, |original| {
+                        apply_hunks(path, original, hunks)
+                    })?
                 }
                 PatchOperation::Create { path, content } => {
                     self.build_create_change(workspace_dir, path, content)?
@@ -198,12 +302,12 @@ impl<'a> ApplyPatchExecutor<'a> {
         &self,
         workspace_dir: &Dir,
         path: &FilePath,
-        blocks: &[SearchReplaceBlock],
+        modify: impl FnOnce(&FileContent) -> Result<FileContent, ApplyPatchError>,
     ) -> Result<ContentChange, ApplyPatchError> {
         let resolved = self.resolve_and_validate(workspace_dir, path)?;
         let original = read_patch_target(workspace_dir, &resolved.relative, path)?;
         let original = FileContent::new(original);
-        let modified = apply_search_replace(path, &original, blocks)?;
+        let modified = modify(&original)?;
         Ok(ContentChange::write(
             resolved.absolute,
             modified.into_string(),
@@ -247,6 +351,12 @@ impl<'a> ApplyPatchExecutor<'a> {
 
         match kind {
             ChangeKind::Create(content) => {
+                ensure_create_target_contained(
+                    workspace_dir,
+                    &self.workspace_root,
+                    &resolved.relative,
+                    path,
+                )?;
                 if path_exists(workspace_dir, &resolved.relative, path)? {
                     return Err(ApplyPatchError::FileAlreadyExists { path: path.clone() });
                 }
@@ -276,63 +386,5 @@ pub(crate) enum ApplyPatchFailure {
     Io(String),
 }
 
-fn map_harness_error(error: SafetyHarnessError) -> ApplyPatchFailure {
-    match error {
-        SafetyHarnessError::SemanticBackendUnavailable { message } => {
-            ApplyPatchFailure::BackendUnavailable(message)
-        }
-        SafetyHarnessError::SyntacticBackendUnavailable { message } => {
-            ApplyPatchFailure::BackendUnavailable(message)
-        }
-        other => ApplyPatchFailure::Io(other.to_string()),
-    }
-}
-
-fn map_patch_error(error: ApplyPatchError) -> ApplyPatchFailure {
-    match error {
-        error @ ApplyPatchError::Io { .. } => ApplyPatchFailure::Io(error.to_string()),
-        other => ApplyPatchFailure::Patch(other),
-    }
-}
-
-/// Generic helper to write serializable error payloads to stderr.
-fn write_error_payload<W: Write, T: serde::Serialize>(
-    writer: &mut ResponseWriter<W>,
-    payload: &T,
-    status: i32,
-) -> Result<DispatchResult, DispatchError> {
-    let json = serde_json::to_string(payload)?;
-    writer.write_stderr(json)?;
-    Ok(DispatchResult::with_status(status))
-}
-
-fn write_patch_error<W: Write>(
-    writer: &mut ResponseWriter<W>,
-    error: ApplyPatchError,
-) -> Result<DispatchResult, DispatchError> {
-    let json = error.to_json()?;
-    writer.write_stderr(json)?;
-    Ok(DispatchResult::with_status(error.exit_status()))
-}
-
-fn write_verification_error<W: Write>(
-    writer: &mut ResponseWriter<W>,
-    phase: &str,
-    failures: Vec<VerificationFailure>,
-) -> Result<DispatchResult, DispatchError> {
-    let payload = VerificationErrorEnvelope::from_failures(phase, failures);
-    write_error_payload(writer, &payload, 1)
-}
-
-fn write_backend_error<W: Write>(
-    writer: &mut ResponseWriter<W>,
-    kind: &'static str,
-    message: String,
-    status: i32,
-) -> Result<DispatchResult, DispatchError> {
-    let payload = GenericErrorEnvelope::new(kind, message);
-    write_error_payload(writer, &payload, status)
-}
-
 #[cfg(test)]
 mod tests;
@@ -1,13 +1,21 @@
 //! Patch parser for the apply-patch command.
+//!
+//! Each file section carries either SEARCH/REPLACE blocks or standard unified
+//! diff hunks. The format is detected per file, and a section mixing the two
+//! is rejected.
 
 use crate::dispatch::act::apply_patch::{
     errors::ApplyPatchError,
-    types::{DiffHeaderLine, FilePath, PatchOperation, PatchText},
+    types::{FilePath, PatchOperation, PatchText},
 };
 
+mod header;
 mod state;
 
-use self::state::{CreateContentCapture, SearchReplaceParser};
+use self::{
+    header::parse_header,
+    state::{CreateContentCapture, HunkCapture, SearchReplaceParser},
+};
 
 /// Line processing context containing type and position information.
 struct LineInfo {
@@ -90,13 +98,21 @@ fn parse_operation(chunk: &str) -> Result<PatchOperation, ApplyPatchError> {
     let mut mode = OperationMode::Unknown;
     let mut search_replace = SearchReplaceParser::new();
     let mut create_capture = CreateContentCapture::new();
+    let mut hunk_capture = HunkCapture::new();
 
     for line in chunk[offset..].split_inclusive('\n') {
         let line_start = offset;
         let line_end = offset + line.len();
+        if hunk_capture.capture_line(line) {
+            offset = line_end;
+            continue;
+        }
         let trimmed = trim_line(line);
         let line_type = classify_line(trimmed);
         let line_info = LineInfo::new(line_type, line_start, line_end);
+        if line_type == LineType::SearchMarker && hunk_capture.has_hunks() {
+            return Err(ApplyPatchError::MixedPatchFormats { path });
+        }
 
         if matches!(
             line_type,
@@ -114,6 +130,17 @@ fn parse_operation(chunk: &str) -> Result<PatchOperation, ApplyPatchError> {
         }
 
         mode = detect_mode_transition(trimmed, mode);
+        if line_type == LineType::HunkHeader
+            && matches!(mode, OperationMode::Unknown | OperationMode::Modify)
+        {
+            if search_replace.has_blocks() {
+                return Err(ApplyPatchError::MixedPatchFormats { path });
+            }
+            mode = mode.promote(OperationMode::Modify);
+            hunk_capture.handle_hunk_header(trimmed, &path)?;
+            offset = line_end;
+            continue;
+        }
         if matches!(
             line_type,
             LineType::HunkHeader | LineType::DiffHeader | LineType::CreateContent
@@ -126,6 +153,11 @@ fn parse_operation(chunk: &str) -> Result<PatchOperation, ApplyPatchError> {
     }
 
     search_replace.validate_complete(&path)?;
+    hunk_capture.validate_complete(&path)?;
+    if mode == OperationMode::Modify && hunk_capture.has_hunks() {
+        let hunks = hunk_capture.into_hunks();
+        return Ok(PatchOperation::ModifyHunks { path, hunks });
+    }
     construct_operation(mode, path, search_replace, create_capture)
 }
 
@@ -194,29 +226,6 @@ fn handle_mode_specific_capture(
     }
 }
 
-fn parse_header(chunk: &str) -> Result<(String, usize), ApplyPatchError> {
-    if chunk.is_empty() {
-        return Err(ApplyPatchError::MissingDiffHeader);
-    }
-
-    let mut lines = chunk.split_inclusive('\n');
-    let Some(line) = lines.next() else {
-        return Err(ApplyPatchError::MissingDiffHeader);
-    };
-    let line_end = line.len();
-    let trimmed = trim_line(line);
-    if !trimmed.starts_with("diff --git ") {
-        return Err(ApplyPatchError::InvalidDiffHeader {
-            line: trimmed.to_string(),
-        });
-    }
-
-    let header = DiffHeaderLine::new(trimmed);
-    let (_, b_path) = parse_diff_paths(header.as_str())?;
-    let path = strip_b_prefix(&b_path);
-    Ok((path, line_end))
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum LineType {
     SearchMarker,
@@ -271,90 +280,6 @@ fn construct_operation(
 
 fn trim_line(line: &str) -> &str { line.trim_end_matches(['\n', '\r']) }
 
-fn parse_diff_paths(line: &str) -> Result<(String, String), ApplyPatchError> {
-    let remainder =
-        line.strip_prefix("diff --git ")
-            .ok_or_else(|| ApplyPatchError::InvalidDiffHeader {
-                line: line.to_string(),
-            })?;
-    let mut chars = remainder.chars().peekable();
-    let mut tokens = Vec::new();
-
-    while tokens.len() < 2 {
-        let token = parse_next_token(&mut chars, line)?;
-        let Some(token) = token else {
-            break;
-        };
-        if !token.is_empty() {
-            tokens.push(token);
-        }
-    }
-
-    let [first, second] =
-        <[String; 2]>::try_from(tokens).map_err(|_| ApplyPatchError::InvalidDiffHeader {
-            line: line.to_string(),
-        })?;
-    Ok((first, second))
-}
-
-fn parse_next_token(
-    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
-    line: &str,
-) -> Result<Option<String>, ApplyPatchError> {
-    consume_whitespace(chars);
-    let next = match chars.peek().copied() {
-        Some(next) => next,
-        None => return Ok(None),
-    };
-    if next == '"' {
-        chars.next();
-        read_quoted_token(chars, line).map(Some)
-    } else {
-        Ok(Some(read_unquoted_token(chars)))
-    }
-}
-
-fn consume_whitespace(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) {
-    while matches!(chars.peek(), Some(ch) if ch.is_whitespace()) {
-        chars.next();
-    }
-}
-
-fn read_quoted_token(
-    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
-    line: &str,
-) -> Result<String, ApplyPatchError> {
-    let mut value = String::new();
-    for ch in chars.by_ref() {
-        if ch == '"' {
-            return Ok(value);
-        }
-        value.push(ch);
-    }
-    Err(ApplyPatchError::InvalidDiffHeader {
-        line: line.to_string(),
-    })
-}
-
-fn read_unquoted_token(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
-    let mut value = String::new();
-    while let Some(ch) = chars.peek().copied() {
-        if ch.is_whitespace() {
-            break;
-        }
-        value.push(ch);
-        chars.next();
-    }
-    value
-}
-
-fn strip_b_prefix(path: &str) -> String {
-    path.strip_prefix("b/")
-        .or_else(|| path.strip_prefix("b\\"))
-        .unwrap_or(path)
-        .to_string()
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum OperationMode {
     Unknown,
@@ -0,0 +1,111 @@
+//! Diff header parsing for apply-patch operations.
+
+use super::trim_line;
+use crate::dispatch::act::apply_patch::{errors::ApplyPatchError, types::DiffHeaderLine};
+
+pub(super) fn parse_header(chunk: &str) -> Result<(String, usize), ApplyPatchError> {
+    if chunk.is_empty() {
+        return Err(ApplyPatchError::MissingDiffHeader);
+    }
+
+    let mut lines = chunk.split_inclusive('\n');
+    let Some(line) = lines.next() else {
+        return Err(ApplyPatchError::MissingDiffHeader);
+    };
+    let line_end = line.len();
+    let trimmed = trim_line(line);
+    if !trimmed.starts_with("diff --git ") {
+        return Err(ApplyPatchError::InvalidDiffHeader {
+            line: trimmed.to_string(),
+        });
+    }
+
+    let header = DiffHeaderLine::new(trimmed);
+    let (_, b_path) = parse_diff_paths(header.as_str())?;
+    let path = strip_b_prefix(&b_path);
+    Ok((path, line_end))
+}
+
+fn parse_diff_paths(line: &str) -> Result<(String, String), ApplyPatchError> {
+    let remainder =
+        line.strip_prefix("diff --git ")
+            .ok_or_else(|| ApplyPatchError::InvalidDiffHeader {
+                line: line.to_string(),
+            })?;
+    let mut chars = remainder.chars().peekable();
+    let mut tokens = Vec::new();
+
+    while tokens.len() < 2 {
+        let token = parse_next_token(&mut chars, line)?;
+        let Some(token) = token else {
+            break;
+        };
+        if !token.is_empty() {
+            tokens.push(token);
+        }
+    }
+
+    let [first, second] =
+        <[String; 2]>::try_from(tokens).map_err(|_| ApplyPatchError::InvalidDiffHeader {
+            line: line.to_string(),
+        })?;
+    Ok((first, second))
+}
+
+fn parse_next_token(
+    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+    line: &str,
+) -> Result<Option<String>, ApplyPatchError> {
+    consume_whitespace(chars);
+    let next = match chars.peek().copied() {
+        Some(next) => next,
+        None => return Ok(None),
+    };
+    if next == '"' {
+        chars.next();
+        read_quoted_token(chars, line).map(Some)
+    } else {
+        Ok(Some(read_unquoted_token(chars)))
+    }
+}
+
+fn consume_whitespace(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) {
+    while matches!(chars.peek(), Some(ch) if ch.is_whitespace()) {
+        chars.next();
+    }
+}
+
+fn read_quoted_token(
+    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+    line: &str,
+) -> Result<String, ApplyPatchError> {
+    let mut value = String::new();
+    for ch in chars.by_ref() {
+        if ch == '"' {
+            return Ok(value);
+        }
+        value.push(ch);
+    }
+    Err(ApplyPatchError::InvalidDiffHeader {
+        line: line.to_string(),
+    })
+}
+
+fn read_unquoted_token(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
+    let mut value = String::new();
+    while let Some(ch) = chars.peek().copied() {
+        if ch.is_whitespace() {
+            break;
+        }
+        value.push(ch);
+        chars.next();
+    }
+    value
+}
+
+fn strip_b_prefix(path: &str) -> String {
+    path.strip_prefix("b/")
+        .or_else(|| path.strip_prefix("b\\"))
+        .unwrap_or(path)
+        .to_string()
+}
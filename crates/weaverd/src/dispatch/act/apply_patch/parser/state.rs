@@ -1,8 +1,17 @@
 //! Parser state helpers for apply-patch operations.
 
+use super::trim_line;
 use crate::dispatch::act::apply_patch::{
     errors::ApplyPatchError,
-    types::{FileContent, FilePath, ReplacementText, SearchPattern, SearchReplaceBlock},
+    types::{
+        DiffHunk,
+        FileContent,
+        FilePath,
+        HunkLine,
+        ReplacementText,
+        SearchPattern,
+        SearchReplaceBlock,
+    },
 };
 
 pub(super) struct SearchReplaceParser {
@@ -68,6 +77,8 @@ impl SearchReplaceParser {
         self.search_start.is_some() || self.replace_start.is_some()
     }
 
+    pub(super) fn has_blocks(&self) -> bool { !self.blocks.is_empty() }
+
     pub(super) fn into_blocks(self) -> Vec<SearchReplaceBlock> { self.blocks }
 }
 
@@ -129,3 +140,113 @@ fn split_line_content(line: &str) -> (&str, &str) {
 }
 
 fn strip_leading_plus(line: &str) -> &str { line.strip_prefix('+').unwrap_or(line) }
+
+/// Collects unified diff hunks for modify operations.
+///
+/// Hunk bodies are bounded by the line counts in their headers, so a body
+/// line that looks like patch syntax, such as `--- a/file`, is still captured
+/// as content while the hunk is open.
+pub(super) struct HunkCapture {
+    hunks: Vec<DiffHunk>,
+    remaining_old: usize,
+    remaining_new: usize,
+}
+
+impl HunkCapture {
+    pub(super) fn new() -> Self {
+        Self {
+            hunks: Vec::new(),
+            remaining_old: 0,
+            remaining_new: 0,
+        }
+    }
+
+    pub(super) fn has_hunks(&self) -> bool { !self.hunks.is_empty() }
+
+    /// Opens a hunk from an `@@ -start,count +start,count @@` header.
+    pub(super) fn handle_hunk_header(
+        &mut self,
+        header: &str,
+        path: &FilePath,
+    ) -> Result<(), ApplyPatchError> {
+        self.validate_complete(path)?;
+        let (old_start, old_count, new_count) =
+            parse_hunk_header(header).ok_or_else(|| ApplyPatchError::InvalidHunkHeader {
+                path: path.clone(),
+                line: header.to_owned(),
+            })?;
+        self.remaining_old = old_count;
+        self.remaining_new = new_count;
+        self.hunks.push(DiffHunk {
+            old_start,
+            lines: Vec::new(),
+        });
+        Ok(())
+    }
+
+    /// Captures a hunk body line, returning whether the line was consumed.
+    pub(super) fn capture_line(&mut self, line: &str) -> bool {
+        let Some(hunk) = self.hunks.last_mut() else {
+            return false;
+        };
+        if line.starts_with('\\') {
+            // `\ No newline at end of file` applies to the preceding line.
+            return hunk.lines.last_mut().map(strip_line_ending).is_some();
+        }
+        let hunk_line = match line.chars().next() {
+            Some('+') if self.remaining_new > 0 => HunkLine::Added(line[1..].to_owned()),
+            Some('-') if self.remaining_old > 0 => HunkLine::Removed(line[1..].to_owned()),
+            Some(' ') if self.remaining_old > 0 && self.remaining_new > 0 => {
+                HunkLine::Context(line[1..].to_owned())
+            }
+            // Some tools strip the leading space from empty context lines.
+            Some('\r' | '\n') | None if self.remaining_old > 0 && self.remaining_new > 0 => {
+                HunkLine::Context(line.to_owned())
+            }
+            _ => return false,
+        };
+        if !matches!(hunk_line, HunkLine::Added(_)) {
+            self.remaining_old -= 1;
+        }
+        if !matches!(hunk_line, HunkLine::Removed(_)) {
+            self.remaining_new -= 1;
+        }
+        hunk.lines.push(hunk_line);
+        true
+    }
+
+    pub(super) fn validate_complete(&self, path: &FilePath) -> Result<(), ApplyPatchError> {
+        if self.remaining_old > 0 || self.remaining_new > 0 {
+            return Err(ApplyPatchError::IncompleteHunk {
+                path: path.clone(),
+                hunk_index: self.hunks.len(),
+            });
+        }
+        Ok(())
+    }
+
+    pub(super) fn into_hunks(self) -> Vec<DiffHunk> { self.hunks }
+}
+
+/// Parses the original start line and both line counts from a hunk header.
+fn parse_hunk_header(header: &str) -> Option<(usize, usize, usize)> {
+    let ranges = header.strip_prefix("@@ ")?.split(" @@").next()?;
+    let (old, new) = ranges.split_once(' ')?;
+    let (old_start, old_count) = parse_hunk_range(old.strip_prefix('-')?)?;
+    let (_, new_count) = parse_hunk_range(new.strip_prefix('+')?)?;
+    Some((old_start, old_count, new_count))
+}
+
+/// Parses a `start,count` range, where a missing count means one line.
+fn parse_hunk_range(range: &str) -> Option<(usize, usize)> {
+    match range.split_once(',') {
+        Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+fn strip_line_ending(line: &mut HunkLine) {
+    let (HunkLine::Context(text) | HunkLine::Removed(text) | HunkLine::Added(text)) = line;
+    let content_len = trim_line(text).len();
+    text.truncate(content_len);
+}
@@ -3,7 +3,7 @@
 use rstest::rstest;
 
 use super::*;
-use crate::dispatch::act::apply_patch::types::PatchText;
+use crate::dispatch::act::apply_patch::types::{HunkLine, PatchText};
 
 #[test]
 fn parses_modify_operation() {
@@ -86,10 +86,73 @@ fn parses_delete_operation() {
     }
 }
 
+#[test]
+fn parses_unified_diff_hunk() {
+    let patch = concat!(
+        "diff --git a/src/lib.rs b/src/lib.rs\n",
+        "index 83db48f..bf269f4 100644\n",
+        "--- a/src/lib.rs\n",
+        "+++ b/src/lib.rs\n",
+        "@@ -1,3 +1,3 @@ fn main() {\n",
+        " fn main() {\n",
+        "-    old();\n",
+        "+    new();\n",
+        " }\n",
+    );
+    let ops = parse_patch(&PatchText::from(patch)).expect("parse patch");
+    assert_eq!(ops.len(), 1);
+    match &ops[0] {
+        PatchOperation::ModifyHunks { path, hunks } => {
+            assert_eq!(path.as_str(), "src/lib.rs");
+            assert_eq!(hunks.len(), 1);
+            assert_eq!(hunks[0].old_start, 1);
+            assert_eq!(
+                hunks[0].lines,
+                [
+                    HunkLine::Context(String::from("fn main() {\n")),
+                    HunkLine::Removed(String::from("    old();\n")),
+                    HunkLine::Added(String::from("    new();\n")),
+                    HunkLine::Context(String::from("}\n")),
+                ]
+            );
+        }
+        other => panic!("unexpected operation: {other:?}"),
+    }
+}
+
+#[test]
+fn unified_diff_hunk_keeps_header_like_lines_and_missing_newlines() {
+    let patch = concat!(
+        "diff --git a/notes.md b/notes.md\n",
+        "--- a/notes.md\n",
+        "+++ b/notes.md\n",
+        "@@ -1,2 +1,2 @@\n",
+        "--- a/rule\n",
+        "+++ b/rule\n",
+        "\n",
+        "\\ No newline at end of file\n",
+    );
+    let ops = parse_patch(&PatchText::from(patch)).expect("parse patch");
+    match &ops[0] {
+        PatchOperation::ModifyHunks { hunks, .. } => assert_eq!(
+            hunks[0].lines,
+            [
+                HunkLine::Removed(String::from("-- a/rule\n")),
+                HunkLine::Added(String::from("++ b/rule\n")),
+                HunkLine::Context(String::new()),
+            ]
+        ),
+        other => panic!("unexpected operation: {other:?}"),
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 enum ParseErrorCase {
     MissingDiffHeader,
     UnclosedSearchBlock,
+    MixedPatchFormats,
+    InvalidHunkHeader,
+    IncompleteHunk,
 }
 
 #[rstest]
@@ -102,6 +165,51 @@ enum ParseErrorCase {
     ),
     ParseErrorCase::UnclosedSearchBlock,
 )]
+#[case::hunk_after_search_replace(
+    concat!(
+        "diff --git a/src/lib.rs b/src/lib.rs\n",
+        "<<<<<<< SEARCH\n",
+        "fn main() {}\n",
+        "=======\n",
+        "fn main() { run(); }\n",
+        ">>>>>>> REPLACE\n",
+        "@@ -1 +1 @@\n",
+        "-fn run() {}\n",
+        "+fn run() { main(); }\n",
+    ),
+    ParseErrorCase::MixedPatchFormats,
+)]
+#[case::search_replace_after_hunk(
+    concat!(
+        "diff --git a/src/lib.rs b/src/lib.rs\n",
+        "@@ -1 +1 @@\n",
+        "-fn run() {}\n",
+        "+fn run() { main(); }\n",
+        "<<<<<<< SEARCH\n",
+        "fn main() {}\n",
+        "=======\n",
+        "fn main() { run(); }\n",
+        ">>>>>>> REPLACE\n",
+    ),
+    ParseErrorCase::MixedPatchFormats,
+)]
+#[case::invalid_hunk_header(
+    concat!(
+        "diff --git a/src/lib.rs b/src/lib.rs\n",
+        "@@ -one +1 @@\n",
+        "-fn run() {}\n",
+        "+fn run() { main(); }\n",
+    ),
+    ParseErrorCase::InvalidHunkHeader,
+)]
+#[case::incomplete_hunk(
+    concat!(
+        "diff --git a/src/lib.rs b/src/lib.rs\n",
+        "@@ -1,2 +1,2 @@\n",
+        " fn main() {}\n",
+    ),
+    ParseErrorCase::IncompleteHunk,
+)]
 fn rejects_invalid_patch(#[case] patch: &str, #[case] expected: ParseErrorCase) {
     let error = parse_patch(&PatchText::from(patch)).expect_err("should fail");
     match expected {
@@ -111,5 +219,17 @@ fn rejects_invalid_patch(#[case] patch: &str, #[case] expected: ParseErrorCase)
         ParseErrorCase::UnclosedSearchBlock => {
             assert!(matches!(error, ApplyPatchError::UnclosedSearchBlock { .. }));
         }
+        ParseErrorCase::MixedPatchFormats => {
+            assert!(matches!(error, ApplyPatchError::MixedPatchFormats { .. }));
+        }
+        ParseErrorCase::InvalidHunkHeader => {
+            assert!(matches!(error, ApplyPatchError::InvalidHunkHeader { .. }));
+        }
+        ParseErrorCase::IncompleteHunk => {
+            assert!(matches!(
+                error,
+                ApplyPatchError::IncompleteHunk { hunk_index: 1, .. }
+            ));
+        }
     }
 }
@@ -2,13 +2,47 @@
 
 use serde::Serialize;
 
-use crate::safety_harness::VerificationFailure;
+use super::types::PatchOperation;
+use crate::safety_harness::{ContentChange, VerificationFailure};
 
 #[derive(Debug, Serialize)]
 pub(crate) struct ApplyPatchSummary {
     pub(crate) status: &'static str,
     pub(crate) files_written: usize,
     pub(crate) files_deleted: usize,
+    /// Outcome of each file operation, in patch order.
+    pub(crate) files: Vec<FileOutcome>,
+    /// Set when the patch was verified but not written.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub(crate) dry_run: bool,
+    /// The verified patch, reported by dry runs in place of writing it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) diff: Option<String>,
+}
+
+/// Per-file entry in an apply-patch summary.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct FileOutcome {
+    /// Path as written in the patch, relative to the workspace root.
+    pub(crate) path: String,
+    /// One of `modify`, `create`, or `delete`.
+    pub(crate) operation: &'static str,
+    /// Size of the content written, or zero for deletions.
+    pub(crate) bytes_written: usize,
+}
+
+impl FileOutcome {
+    pub(crate) fn new(operation: &PatchOperation, change: &ContentChange) -> Self {
+        let bytes_written = match change {
+            ContentChange::Write { content, .. } => content.len(),
+            ContentChange::Delete { .. } => 0,
+        };
+        Self {
+            path: operation.path().as_str().to_owned(),
+            operation: operation.kind(),
+            bytes_written,
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -33,6 +67,12 @@ struct VerificationFailurePayload {
     #[serde(skip_serializing_if = "Option::is_none")]
     column: Option<u32>,
     message: String,
+    /// Whether the edit introduced the diagnostic; set by the semantic lock.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_new: Option<bool>,
+    /// Failing diagnostics the file had before the edit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    preexisting_count: Option<usize>,
 }
 
 impl VerificationErrorEnvelope {
@@ -44,6 +84,10 @@ impl VerificationErrorEnvelope {
                 line: failure.line(),
                 column: failure.column(),
                 message: failure.message().to_string(),
+                is_new: failure.baseline().map(|baseline| baseline.is_new()),
+                preexisting_count: failure
+                    .baseline()
+                    .map(|baseline| baseline.preexisting_count()),
             })
             .collect();
         Self {
@@ -5,7 +5,9 @@ use std::{collections::HashSet, path::Path, str::FromStr};
 use weaver_lsp_host::{Language, LspHost};
 
 use crate::{
+    dispatch::observe::arguments::lsp_language_for_path,
     safety_harness::{
+        BaselineComparison,
         SafetyHarnessError,
         SemanticLock,
         SemanticLockResult,
@@ -54,16 +56,6 @@ impl<'a> SemanticLock for LspSemanticLockAdapter<'a> {
     }
 }
 
-fn infer_language(path: &Path) -> Option<Language> {
-    let extension = path.extension()?.to_string_lossy().to_ascii_lowercase();
-    match extension.as_str() {
-        "rs" => Some(Language::Rust),
-        "py" => Some(Language::Python),
-        "ts" | "tsx" => Some(Language::TypeScript),
-        _ => None,
-    }
-}
-
 fn to_uri(path: &Path) -> Result<lsp_types::Uri, SafetyHarnessError> {
     let url = url::Url::from_file_path(path).map_err(|_| {
         SafetyHarnessError::SemanticBackendUnavailable {
@@ -98,7 +90,7 @@ fn collect_failures(
 ) -> Result<Vec<VerificationFailure>, SafetyHarnessError> {
     let mut failures = Vec::new();
     for (path, modified) in context.modified_files() {
-        let Some(language) = infer_language(path) else {
+        let Some(language) = lsp_language_for_path(path) else {
             // Skip files without a supported language to avoid noisy LSP errors.
             continue;
         };
@@ -182,12 +174,20 @@ fn fetch_diagnostics(
         .map_err(|e| lsp_error("diagnostics", e))
 }
 
+/// Reports the failing-severity diagnostics absent from the baseline.
+///
+/// Pre-existing diagnostics never fail the lock, so every failure is labelled
+/// as new, alongside how many failing diagnostics the file already had.
 fn filter_new_failures(
     path: &Path,
     baseline: Vec<lsp_types::Diagnostic>,
     updated: Vec<lsp_types::Diagnostic>,
 ) -> Vec<VerificationFailure> {
     let baseline_set = diagnostics_signature_set(&baseline);
+    let preexisting_count = baseline
+        .iter()
+        .filter(|diagnostic| is_high_severity(&diagnostic.severity))
+        .count();
     updated
         .into_iter()
         .filter(|diagnostic| is_high_severity(&diagnostic.severity))
@@ -196,6 +196,7 @@ fn filter_new_failures(
             let position = diagnostic.range.start;
             VerificationFailure::new(path.to_path_buf(), diagnostic.message)
                 .at_location(position.line + 1, position.character + 1)
+                .with_baseline(BaselineComparison::new(true, preexisting_count))
         })
         .collect()
 }
@@ -274,3 +275,57 @@ fn severity_code(severity: lsp_types::DiagnosticSeverity) -> u32 {
         _ => 0,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for semantic-lock diagnostic comparison.
+
+    use std::path::Path;
+
+    use lsp_types::{Diagnostic, DiagnosticSeverity, Position, Range};
+
+    use super::filter_new_failures;
+    use crate::safety_harness::BaselineComparison;
+
+    fn diagnostic(line: u32, severity: DiagnosticSeverity, message: &str) -> Diagnostic {
+        let start = Position::new(line, 4);
+        Diagnostic {
+            range: Range::new(start, start),
+            severity: Some(severity),
+            message: String::from(message),
+            ..Diagnostic::default()
+        }
+    }
+
+    #[test]
+    fn preexisting_warning_is_not_reported_as_failure() {
+        let warning = diagnostic(2, DiagnosticSeverity::WARNING, "unused import");
+        let hint = diagnostic(5, DiagnosticSeverity::HINT, "consider renaming");
+        let error = diagnostic(7, DiagnosticSeverity::ERROR, "undefined name 'woven'");
+
+        let failures = filter_new_failures(
+            Path::new("/workspace/main.py"),
+            vec![warning.clone(), hint],
+            vec![warning, error],
+        );
+
+        assert_eq!(failures.len(), 1, "failures: {failures:?}");
+        let failure = failures.first().expect("one failure");
+        assert_eq!(failure.message(), "undefined name 'woven'");
+        assert_eq!(failure.line(), Some(8));
+        assert_eq!(failure.baseline(), Some(BaselineComparison::new(true, 1)));
+    }
+
+    #[test]
+    fn unchanged_diagnostics_produce_no_failures() {
+        let error = diagnostic(1, DiagnosticSeverity::ERROR, "syntax error");
+
+        let failures = filter_new_failures(
+            Path::new("/workspace/main.py"),
+            vec![error.clone()],
+            vec![error],
+        );
+
+        assert!(failures.is_empty(), "failures: {failures:?}");
+    }
+}
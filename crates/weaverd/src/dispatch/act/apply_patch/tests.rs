@@ -4,10 +4,15 @@ use rstest::{fixture, rstest};
 use tempfile::TempDir;
 use weaver_test_macros::allow_fixture_expansion_lints;
 
-use super::{ApplyPatchExecutor, resolve_path};
+use super::{ApplyPatchExecutor, ensure_create_target_contained, parse_match_mode, resolve_path};
 use crate::{
-    dispatch::act::apply_patch::{ApplyPatchFailure, types::FilePath},
+    dispatch::act::apply_patch::{
+        ApplyPatchError,
+        ApplyPatchFailure,
+        types::{FilePath, MatchMode},
+    },
     safety_harness::{ConfigurableSemanticLock, ConfigurableSyntacticLock},
+    tests::support::fs as test_fs,
 };
 
 #[allow_fixture_expansion_lints]
@@ -31,6 +36,59 @@ fn resolve_path_rejects_parent_dir(temp_dir: Result<TempDir, String>) -> Result<
     Ok(())
 }
 
+#[cfg(unix)]
+#[rstest]
+#[case::dangling_target("escape.rs", "escape.rs", "../outside.rs")]
+#[case::absolute_target("escape.rs", "escape.rs", "/tmp/outside.rs")]
+#[case::parent_directory("out/new.rs", "out", "..")]
+fn create_guard_rejects_escaping_symlinks(
+    temp_dir: Result<TempDir, String>,
+    #[case] target: &str,
+    #[case] link: &str,
+    #[case] link_target: &str,
+) -> Result<(), String> {
+    let temp_dir = temp_dir?;
+    let workspace_dir =
+        cap_std::fs::Dir::open_ambient_dir(temp_dir.path(), cap_std::ambient_authority())
+            .map_err(|error| format!("open workspace dir: {error}"))?;
+    workspace_dir
+        .symlink_contents(link_target, link)
+        .map_err(|error| format!("create symlink: {error}"))?;
+    let result = ensure_create_target_contained(
+        &workspace_dir,
+        temp_dir.path(),
+        std::path::Path::new(target),
+        &FilePath::new(target),
+    );
+    assert!(
+        matches!(result, Err(ApplyPatchError::InvalidPath { .. })),
+        "escaping symlink should be rejected: {result:?}"
+    );
+    Ok(())
+}
+
+#[cfg(unix)]
+#[rstest]
+fn create_guard_allows_symlinks_inside_workspace(
+    temp_dir: Result<TempDir, String>,
+) -> Result<(), String> {
+    let temp_dir = temp_dir?;
+    let workspace_dir =
+        cap_std::fs::Dir::open_ambient_dir(temp_dir.path(), cap_std::ambient_authority())
+            .map_err(|error| format!("open workspace dir: {error}"))?;
+    workspace_dir
+        .symlink_contents("src/../lib.rs", "alias.rs")
+        .map_err(|error| format!("create symlink: {error}"))?;
+    let result = ensure_create_target_contained(
+        &workspace_dir,
+        temp_dir.path(),
+        std::path::Path::new("alias.rs"),
+        &FilePath::new("alias.rs"),
+    );
+    assert!(result.is_ok(), "contained symlink should pass: {result:?}");
+    Ok(())
+}
+
 #[rstest]
 fn executor_rejects_empty_patch(temp_dir: Result<TempDir, String>) -> Result<(), String> {
     let temp_dir = temp_dir?;
@@ -41,3 +99,150 @@ fn executor_rejects_empty_patch(temp_dir: Result<TempDir, String>) -> Result<(),
     assert!(matches!(error, ApplyPatchFailure::Patch(_)));
     Ok(())
 }
+
+#[rstest]
+fn executor_applies_unified_diff(temp_dir: Result<TempDir, String>) -> Result<(), String> {
+    let temp_dir = temp_dir?;
+    let target = temp_dir.path().join("lib.rs");
+    test_fs::write(&target, "fn main() {\n    old();\n}\n")
+        .map_err(|error| format!("write fixture: {error}"))?;
+    let syntactic = ConfigurableSyntacticLock::passing();
+    let semantic = ConfigurableSemanticLock::passing();
+    let executor = ApplyPatchExecutor::new(temp_dir.path().to_path_buf(), &syntactic, &semantic);
+    let patch = concat!(
+        "diff --git a/lib.rs b/lib.rs\n",
+        "--- a/lib.rs\n",
+        "+++ b/lib.rs\n",
+        "@@ -1,3 +1,3 @@\n",
+        " fn main() {\n",
+        "-    old();\n",
+        "+    new();\n",
+        " }\n",
+    );
+    let summary = executor
+        .execute(patch)
+        .map_err(|error| format!("apply patch: {error:?}"))?;
+    assert_eq!(summary.files_written, 1);
+    let content =
+        test_fs::read_to_string(&target).map_err(|error| format!("read fixture: {error}"))?;
+    assert_eq!(content, "fn main() {\n    new();\n}\n");
+    Ok(())
+}
+
+#[rstest]
+fn executor_reports_each_file(temp_dir: Result<TempDir, String>) -> Result<(), String> {
+    let temp_dir = temp_dir?;
+    test_fs::write(temp_dir.path().join("lib.rs"), "fn main() {}\n")
+        .map_err(|error| format!("write fixture: {error}"))?;
+    let syntactic = ConfigurableSyntacticLock::passing();
+    let semantic = ConfigurableSemanticLock::passing();
+    let executor = ApplyPatchExecutor::new(temp_dir.path().to_path_buf(), &syntactic, &semantic);
+    let patch = concat!(
+        "diff --git a/lib.rs b/lib.rs\n",
+        "<<<<<<< SEARCH\n",
+        "fn main() {}\n",
+        "=======\n",
+        "fn main() { run(); }\n",
+        ">>>>>>> REPLACE\n",
+        "diff --git a/src/new.rs b/src/new.rs\n",
+        "new file mode 100644\n",
+        "--- /dev/null\n",
+        "+++ b/src/new.rs\n",
+        "@@ -0,0 +1,1 @@\n",
+        "+fn run() {}\n",
+    );
+    let summary = executor
+        .execute(patch)
+        .map_err(|error| format!("apply patch: {error:?}"))?;
+    let payload = serde_json::to_value(&summary).map_err(|error| format!("serialize: {error}"))?;
+    assert_eq!(
+        payload,
+        serde_json::json!({
+            "status": "ok",
+            "files_written": 2,
+            "files_deleted": 0,
+            "files": [
+                {"path": "lib.rs", "operation": "modify", "bytes_written": 21},
+                {"path": "src/new.rs", "operation": "create", "bytes_written": 12},
+            ],
+        })
+    );
+    Ok(())
+}
+
+#[rstest]
+#[case::default(&[], Some(MatchMode::Strict))]
+#[case::tolerant(&["--tolerant"], Some(MatchMode::Tolerant))]
+#[case::unknown_flag(&["--fuzzy"], None)]
+#[case::repeated_flag(&["--tolerant", "--tolerant"], None)]
+fn parse_match_mode_accepts_only_tolerant_flag(
+    #[case] arguments: &[&str],
+    #[case] expected: Option<MatchMode>,
+) {
+    let arguments: Vec<String> = arguments.iter().map(|&arg| arg.to_owned()).collect();
+    assert_eq!(parse_match_mode(&arguments).ok(), expected);
+}
+
+#[rstest]
+fn tolerant_executor_ignores_trailing_whitespace(
+    temp_dir: Result<TempDir, String>,
+) -> Result<(), String> {
+    let temp_dir = temp_dir?;
+    let target = temp_dir.path().join("lib.rs");
+    test_fs::write(&target, "fn main() {  \n    old();\t\n}\n")
+        .map_err(|error| format!("write fixture: {error}"))?;
+    let syntactic = ConfigurableSyntacticLock::passing();
+    let semantic = ConfigurableSemanticLock::passing();
+    let executor = ApplyPatchExecutor::new(temp_dir.path().to_path_buf(), &syntactic, &semantic)
+        .with_match_mode(MatchMode::Tolerant);
+    let patch = concat!(
+        "diff --git a/lib.rs b/lib.rs\n",
+        "<<<<<<< SEARCH\n",
+        "fn main() {\n",
+        "    old();\n",
+        "=======\n",
+        "fn main() {\n",
+        "    new();\n",
+        ">>>>>>> REPLACE\n",
+    );
+    let summary = executor
+        .execute(patch)
+        .map_err(|error| format!("apply patch: {error:?}"))?;
+    assert_eq!(summary.files_written, 1);
+    let content =
+        test_fs::read_to_string(&target).map_err(|error| format!("read fixture: {error}"))?;
+    assert_eq!(content, "fn main() {\n    new();\n}\n");
+    Ok(())
+}
+
+#[rstest]
+fn dry_run_executor_reports_diff_without_writing(
+    temp_dir: Result<TempDir, String>,
+) -> Result<(), String> {
+    let temp_dir = temp_dir?;
+    let target = temp_dir.path().join("lib.rs");
+    test_fs::write(&target, "fn main() {}\n").map_err(|error| format!("write fixture: {error}"))?;
+    let syntactic = ConfigurableSyntacticLock::passing();
+    let semantic = ConfigurableSemanticLock::passing();
+    let executor = ApplyPatchExecutor::new(temp_dir.path().to_path_buf(), &syntactic, &semantic)
+        .with_dry_run(true);
+    let patch = concat!(
+        "diff --git a/lib.rs b/lib.rs\n",
+        "<<<<<<< SEARCH\n",
+        "fn main() {}\n",
+        "=======\n",
+        "fn main() { run(); }\n",
+        ">>>>>>> REPLACE\n",
+    );
+    let summary = executor
+        .execute(patch)
+        .map_err(|error| format!("apply patch: {error:?}"))?;
+    let payload = serde_json::to_value(&summary).map_err(|error| format!("serialize: {error}"))?;
+    assert_eq!(payload.get("dry_run"), Some(&serde_json::json!(true)));
+    assert_eq!(payload.get("diff"), Some(&serde_json::json!(patch)));
+    assert_eq!(summary.files_written, 1);
+    let content =
+        test_fs::read_to_string(&target).map_err(|error| format!("read fixture: {error}"))?;
+    assert_eq!(content, "fn main() {}\n");
+    Ok(())
+}
@@ -1,12 +1,15 @@
 //! Types used to model apply-patch operations.
 
-/// Line ending style inferred from patch or file content.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum LineEnding {
-    /// Line feed (`\n`).
-    Lf,
-    /// Carriage return + line feed (`\r\n`).
-    CrLf,
+/// How closely a SEARCH block must match the target content.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum MatchMode {
+    /// Exact match, falling back to one that trims surrounding spaces and
+    /// tabs and normalizes line endings.
+    #[default]
+    Strict,
+    /// Strict matching, then a line-by-line match that ignores trailing
+    /// whitespace and accepts the unique closest candidate.
+    Tolerant,
 }
 
 macro_rules! string_newtype {
@@ -87,6 +90,36 @@ pub(crate) struct SearchReplaceBlock {
     pub(crate) replace: ReplacementText,
 }
 
+/// Line of a unified diff hunk, keeping its line ending but not its marker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum HunkLine {
+    /// Unchanged line that must be present in the target file.
+    Context(String),
+    /// Line removed from the target file.
+    Removed(String),
+    /// Line added to the target file.
+    Added(String),
+}
+
+impl HunkLine {
+    /// Returns the text this line expects in the original file, if any.
+    pub(crate) fn original_text(&self) -> Option<&str> {
+        match self {
+            Self::Context(text) | Self::Removed(text) => Some(text),
+            Self::Added(_) => None,
+        }
+    }
+}
+
+/// Unified diff hunk for modify operations.
+#[derive(Debug, Clone)]
+pub(crate) struct DiffHunk {
+    /// One-based line number where the hunk starts in the original file, or
+    /// the line after which it inserts when it has no original lines.
+    pub(crate) old_start: usize,
+    pub(crate) lines: Vec<HunkLine>,
+}
+
 /// Parsed patch operation.
 #[derive(Debug, Clone)]
 pub(crate) enum PatchOperation {
@@ -94,6 +127,10 @@ pub(crate) enum PatchOperation {
         path: FilePath,
         blocks: Vec<SearchReplaceBlock>,
     },
+    ModifyHunks {
+        path: FilePath,
+        hunks: Vec<DiffHunk>,
+    },
     Create {
         path: FilePath,
         content: FileContent,
@@ -102,3 +139,24 @@ pub(crate) enum PatchOperation {
         path: FilePath,
     },
 }
+
+impl PatchOperation {
+    /// Returns the file this operation targets.
+    pub(crate) const fn path(&self) -> &FilePath {
+        match self {
+            Self::Modify { path, .. }
+            | Self::ModifyHunks { path, .. }
+            | Self::Create { path, .. }
+            | Self::Delete { path } => path,
+        }
+    }
+
+    /// Returns the operation name reported in responses.
+    pub(crate) const fn kind(&self) -> &'static str {
+        match self {
+            Self::Modify { .. } | Self::ModifyHunks { .. } => "modify",
+            Self::Create { .. } => "create",
+            Self::Delete { .. } => "delete",
+        }
+    }
+}
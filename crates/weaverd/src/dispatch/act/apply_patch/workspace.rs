@@ -1,6 +1,6 @@
 //! Workspace path validation and capability-based reads for apply-patch.
 
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
 use cap_std::fs::Dir;
 
@@ -85,6 +85,84 @@ pub(super) fn path_exists(
     }
 }
 
+/// Refuses a create target whose parent directory or final component is a
+/// symlink resolving outside the workspace.
+///
+/// [`resolve_path`] already rejects symlink components, but a dangling symlink
+/// reports as missing to [`path_exists`], so creates check their final target
+/// again immediately before the change is built.
+pub(super) fn ensure_create_target_contained(
+    workspace_dir: &Dir,
+    workspace_root: &Path,
+    relative: &Path,
+    path: &FilePath,
+) -> Result<(), ApplyPatchError> {
+    let parent = relative
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty());
+    for candidate in parent.into_iter().chain(std::iter::once(relative)) {
+        let Some(target) = symlink_target(workspace_dir, candidate, path)? else {
+            continue;
+        };
+        if !link_stays_within(workspace_root, candidate, &target) {
+            return Err(ApplyPatchError::InvalidPath {
+                path: path.clone(),
+                reason: format!(
+                    "'{}' is a symlink that resolves outside the workspace",
+                    candidate.display()
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Returns the raw target of the symlink at `relative`, if it is one.
+fn symlink_target(
+    workspace_dir: &Dir,
+    relative: &Path,
+    path: &FilePath,
+) -> Result<Option<PathBuf>, ApplyPatchError> {
+    let inspect_error = |err: std::io::Error| ApplyPatchError::InvalidPath {
+        path: path.clone(),
+        reason: format!("failed to inspect path component: {err}"),
+    };
+    match workspace_dir.symlink_metadata(relative) {
+        Ok(metadata) if metadata.file_type().is_symlink() => workspace_dir
+            .read_link_contents(relative)
+            .map(Some)
+            .map_err(inspect_error),
+        Ok(_) => Ok(None),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(inspect_error(err)),
+    }
+}
+
+/// Lexically resolves `target`, read from the symlink at `link`, and reports
+/// whether it stays inside the workspace.
+fn link_stays_within(workspace_root: &Path, link: &Path, target: &Path) -> bool {
+    if target.is_absolute() {
+        return target.starts_with(workspace_root)
+            && !target
+                .components()
+                .any(|component| component == Component::ParentDir);
+    }
+    let mut depth = link
+        .parent()
+        .map_or(0, |parent| parent.components().count());
+    for component in target.components() {
+        match component {
+            Component::ParentDir => match depth.checked_sub(1) {
+                Some(parent_depth) => depth = parent_depth,
+                None => return false,
+            },
+            Component::Normal(_) => depth += 1,
+            Component::CurDir | Component::RootDir | Component::Prefix(_) => {}
+        }
+    }
+    true
+}
+
 /// Reads the current patch target content from the workspace capability.
 ///
 /// Missing files become [`ApplyPatchError::FileNotFound`]; other read failures
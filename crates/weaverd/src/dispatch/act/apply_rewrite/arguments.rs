@@ -0,0 +1,81 @@
+//! Argument parsing for `act apply-rewrite`.
+
+use crate::dispatch::errors::DispatchError;
+
+/// Parsed arguments for a structural rewrite request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RewriteArgs {
+    pub(crate) pattern: String,
+    pub(crate) replacement: String,
+    pub(crate) file: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Flag {
+    Pattern,
+    Replacement,
+    File,
+}
+
+impl Flag {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "--pattern" => Some(Self::Pattern),
+            "--replacement" => Some(Self::Replacement),
+            "--file" => Some(Self::File),
+            _ => None,
+        }
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pattern => "--pattern",
+            Self::Replacement => "--replacement",
+            Self::File => "--file",
+        }
+    }
+}
+
+/// Parses `--pattern`, `--replacement`, and `--file` from the request
+/// arguments.
+///
+/// The replacement may be empty, which deletes every match; the pattern and
+/// file must not be.
+pub(crate) fn parse_rewrite_args(arguments: &[String]) -> Result<RewriteArgs, DispatchError> {
+    let mut pattern = None;
+    let mut replacement = None;
+    let mut file = None;
+    let mut iter = arguments.iter();
+    while let Some(arg) = iter.next() {
+        let flag = Flag::parse(arg).ok_or_else(|| {
+            DispatchError::invalid_arguments(format!(
+                "act apply-rewrite does not accept argument '{arg}'. Use --pattern <pattern>, \
+                 --replacement <template>, and --file <path>"
+            ))
+        })?;
+        let value = iter.next().cloned().ok_or_else(|| {
+            DispatchError::invalid_arguments(format!("{} requires a value", flag.as_str()))
+        })?;
+        match flag {
+            Flag::Pattern => pattern = Some(value),
+            Flag::Replacement => replacement = Some(value),
+            Flag::File => file = Some(value),
+        }
+    }
+
+    match (pattern, replacement, file) {
+        (Some(pattern), Some(replacement), Some(file))
+            if !pattern.trim().is_empty() && !file.is_empty() =>
+        {
+            Ok(RewriteArgs {
+                pattern,
+                replacement,
+                file,
+            })
+        }
+        _ => Err(DispatchError::invalid_arguments(
+            "act apply-rewrite requires non-empty --pattern and --file values and a --replacement \
+             value",
+        )),
+    }
+}
@@ -0,0 +1,103 @@
+//! Handler for `act apply-rewrite`.
+//!
+//! Compiles a structural rewrite rule with `weaver-syntax`, applies it to a
+//! single workspace file, and forwards the rewrite as a unified diff to
+//! `act apply-patch` so the Double-Lock safety harness verifies the change
+//! before anything is written.
+
+mod arguments;
+
+#[cfg(test)]
+mod tests;
+
+use std::{io::Write, path::Path};
+
+use tracing::debug;
+use weaver_syntax::{
+    Pattern,
+    RewriteResult,
+    RewriteRule,
+    Rewriter,
+    SupportedLanguage,
+    SyntaxError,
+};
+
+use self::arguments::{RewriteArgs, parse_rewrite_args};
+use crate::{
+    backends::FusionBackends,
+    dispatch::{
+        act::{
+            apply_patch,
+            target_file::{canonical_workspace_root, load_file_contents, resolve_file},
+        },
+        errors::DispatchError,
+        request::{CommandDescriptor, CommandRequest},
+        response::ResponseWriter,
+        router::{DISPATCH_TARGET, DispatchResult},
+    },
+    semantic_provider::SemanticBackendProvider,
+};
+
+/// Handles `act apply-rewrite` requests.
+///
+/// When the rule matches nothing, the file is left untouched and an
+/// apply-patch style summary reporting no written files is returned.
+pub fn handle<W: Write>(
+    request: &CommandRequest,
+    writer: &mut ResponseWriter<W>,
+    backends: &mut FusionBackends<SemanticBackendProvider>,
+    workspace_root: &Path,
+) -> Result<DispatchResult, DispatchError> {
+    let args = parse_rewrite_args(&request.arguments)?;
+    let canonical_workspace = canonical_workspace_root(workspace_root)?;
+    let resolved_file = resolve_file(&canonical_workspace, &args.file)?;
+    let language = SupportedLanguage::from_path(&resolved_file.path).ok_or_else(|| {
+        DispatchError::invalid_arguments(format!(
+            "act apply-rewrite does not support the language of '{}'",
+            args.file
+        ))
+    })?;
+    let original = load_file_contents(&resolved_file.path)?;
+    let result = rewrite_source(&args, language, &original)?;
+    let patch = result.unified_diff(&resolved_file.relative_path);
+
+    debug!(
+        target: DISPATCH_TARGET,
+        file = %resolved_file.relative_path.display(),
+        language = language.as_str(),
+        replacements = result.num_replacements(),
+        "handling apply-rewrite"
+    );
+
+    if patch.is_empty() {
+        writer.write_stdout(r#"{"status":"ok","files_written":0,"files_deleted":0,"files":[]}"#)?;
+        return Ok(DispatchResult::success());
+    }
+    let patch_request = CommandRequest {
+        command: CommandDescriptor {
+            domain: String::from("act"),
+            operation: String::from("apply-patch"),
+        },
+        arguments: Vec::new(),
+        patch: Some(patch),
+    };
+    apply_patch::handle(&patch_request, writer, backends, workspace_root)
+}
+
+/// Compiles the rewrite rule and applies it to `source`.
+fn rewrite_source(
+    args: &RewriteArgs,
+    language: SupportedLanguage,
+    source: &str,
+) -> Result<RewriteResult, DispatchError> {
+    let invalid_rule = |error: SyntaxError| {
+        DispatchError::invalid_arguments(format!("invalid rewrite rule: {error}"))
+    };
+    let pattern = Pattern::compile(&args.pattern, language).map_err(invalid_rule)?;
+    let rule = RewriteRule::new(pattern, args.replacement.as_str()).map_err(invalid_rule)?;
+    Rewriter::new(language)
+        .apply(&rule, source)
+        .map_err(|error| {
+            DispatchError::invalid_arguments(format!("cannot rewrite '{}': {error}", args.file))
+        })
+}
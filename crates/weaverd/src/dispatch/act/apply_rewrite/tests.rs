@@ -0,0 +1,134 @@
+//! Tests for the `act apply-rewrite` handler.
+
+use std::path::Path;
+
+use rstest::{fixture, rstest};
+use tempfile::TempDir;
+use weaver_syntax::SupportedLanguage;
+use weaver_test_macros::allow_fixture_expansion_lints;
+
+use super::{arguments::RewriteArgs, handle, rewrite_source};
+use crate::{
+    dispatch::{
+        act::{
+            apply_patch::ApplyPatchExecutor,
+            refactor::refactor_helpers::builders::build_backends,
+        },
+        errors::DispatchError,
+        request::{CommandDescriptor, CommandRequest},
+        response::ResponseWriter,
+        router::DispatchResult,
+    },
+    safety_harness::{ConfigurableSemanticLock, ConfigurableSyntacticLock},
+    tests::support::fs as test_fs,
+};
+
+const SOURCE: &str = "fn main() {\n    dbg!(compute());\n}\n";
+
+#[allow_fixture_expansion_lints]
+#[fixture]
+fn workspace() -> TempDir {
+    let workspace = TempDir::new().expect("workspace");
+    test_fs::write(workspace.path().join("main.rs"), SOURCE).expect("write main.rs");
+    test_fs::write(workspace.path().join("notes.txt"), "dbg!(x)\n").expect("write notes.txt");
+    workspace
+}
+
+fn rewrite_request(arguments: &[&str]) -> CommandRequest {
+    CommandRequest {
+        command: CommandDescriptor {
+            domain: String::from("act"),
+            operation: String::from("apply-rewrite"),
+        },
+        arguments: arguments
+            .iter()
+            .map(|&argument| argument.to_owned())
+            .collect(),
+        patch: None,
+    }
+}
+
+fn run_handle(
+    workspace: &TempDir,
+    arguments: &[&str],
+) -> (Result<DispatchResult, DispatchError>, String) {
+    let socket_dir = TempDir::new().expect("socket dir");
+    let mut backends = build_backends(&socket_dir.path().join("socket.sock"));
+    let mut output = Vec::new();
+    let mut writer = ResponseWriter::new(&mut output);
+    let result = handle(
+        &rewrite_request(arguments),
+        &mut writer,
+        &mut backends,
+        workspace.path(),
+    );
+    (result, String::from_utf8(output).expect("utf-8 output"))
+}
+
+#[rstest]
+#[case::no_arguments(&[])]
+#[case::missing_file(&["--pattern", "dbg!($E)", "--replacement", "$E"])]
+#[case::missing_replacement(&["--pattern", "dbg!($E)", "--file", "main.rs"])]
+#[case::empty_pattern(&["--pattern", " ", "--replacement", "", "--file", "main.rs"])]
+#[case::missing_value(&["--replacement", "$E", "--file", "main.rs", "--pattern"])]
+#[case::unknown_flag(&["--pattern", "dbg!($E)", "--replacement", "$E", "--path", "main.rs"])]
+#[case::parent_traversal(&["--pattern", "dbg!($E)", "--replacement", "$E", "--file", "../main.rs"])]
+#[case::unsupported_language(&["--pattern", "dbg!($E)", "--replacement", "$E", "--file", "notes.txt"])]
+#[case::undefined_metavariable(&["--pattern", "dbg!($E)", "--replacement", "$F", "--file", "main.rs"])]
+fn rejects_invalid_requests(workspace: TempDir, #[case] arguments: &[&str]) {
+    let (result, _) = run_handle(&workspace, arguments);
+
+    assert!(matches!(
+        result,
+        Err(DispatchError::InvalidArguments { .. })
+    ));
+}
+
+#[rstest]
+fn unmatched_pattern_leaves_file_untouched(workspace: TempDir) {
+    let (result, output) = run_handle(
+        &workspace,
+        &[
+            "--pattern",
+            "todo!()",
+            "--replacement",
+            "()",
+            "--file",
+            "main.rs",
+        ],
+    );
+
+    assert_eq!(result.expect("rewrite succeeds").status, 0);
+    assert!(
+        output.contains("files_written"),
+        "summary missing: {output}"
+    );
+    let content = test_fs::read_to_string(workspace.path().join("main.rs")).expect("read main.rs");
+    assert_eq!(content, SOURCE);
+}
+
+#[rstest]
+#[case::trailing_newline(SOURCE, "fn main() {\n    (compute());\n}\n")]
+#[case::no_trailing_newline("fn main() { dbg!(1); }", "fn main() { (1); }")]
+fn rewrite_diff_applies_through_apply_patch(#[case] original: &str, #[case] expected: &str) {
+    let workspace = TempDir::new().expect("workspace");
+    test_fs::create_dir_all(workspace.path().join("src")).expect("create src");
+    test_fs::write(workspace.path().join("src/main.rs"), original).expect("write fixture");
+    let syntactic = ConfigurableSyntacticLock::passing();
+    let semantic = ConfigurableSemanticLock::passing();
+    let executor = ApplyPatchExecutor::new(workspace.path().to_path_buf(), &syntactic, &semantic);
+    let args = RewriteArgs {
+        pattern: String::from("dbg!($E)"),
+        replacement: String::from("$E"),
+        file: String::from("src/main.rs"),
+    };
+
+    let result = rewrite_source(&args, SupportedLanguage::Rust, original).expect("rewrite");
+    let patch = result.unified_diff(Path::new("src/main.rs"));
+    let summary = executor.execute(&patch).expect("patch applies");
+
+    assert_eq!(summary.files_written, 1);
+    let content =
+        test_fs::read_to_string(workspace.path().join("src/main.rs")).expect("read fixture");
+    assert_eq!(content, expected);
+}
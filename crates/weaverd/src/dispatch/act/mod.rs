@@ -4,4 +4,6 @@
 //! Double-Lock safety harness before writing to disk.
 
 pub mod apply_patch;
+pub mod apply_rewrite;
 pub mod refactor;
+pub(crate) mod target_file;
@@ -4,23 +4,30 @@
 use super::{
     metrics::PositionMetrics,
     positions::{LineCol, parse_line_col},
-    requirements::{missing_requirements_error, validate_provider, validate_refactoring},
+    requirements::{missing_requirements_error, validate_refactoring},
 };
 use crate::dispatch::errors::DispatchError;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct RefactorArgs {
-    pub(crate) provider: String,
+    /// Explicit provider; `None` selects one from the file's language.
+    pub(crate) provider: Option<String>,
     pub(crate) refactoring: String,
     pub(crate) file: String,
     pub(crate) position: Option<LineCol>,
+    pub(crate) dry_run: bool,
     pub(crate) extra: Vec<String>,
 }
+impl RefactorArgs {
+    /// Returns the provider named in diagnostics, or `auto` when omitted.
+    pub(crate) fn provider_label(&self) -> &str { self.provider.as_deref().unwrap_or("auto") }
+}
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Flag {
     Provider,
     Refactoring,
     File,
     Position,
+    DryRun,
 }
 impl Flag {
     fn parse(s: &str) -> Option<Self> {
@@ -29,6 +36,7 @@ impl Flag {
             "--refactoring" => Some(Self::Refactoring),
             "--file" => Some(Self::File),
             "--position" => Some(Self::Position),
+            "--dry-run" => Some(Self::DryRun),
             _ => None,
         }
     }
@@ -38,6 +46,7 @@ impl Flag {
             Self::Refactoring => "--refactoring",
             Self::File => "--file",
             Self::Position => "--position",
+            Self::DryRun => "--dry-run",
         }
     }
 }
@@ -47,13 +56,11 @@ struct RefactorArgsBuilder {
     refactoring: Option<String>,
     file: Option<String>,
     position: Option<LineCol>,
+    dry_run: bool,
     extra: Vec<String>,
 }
 impl RefactorArgsBuilder {
     fn build(self) -> Result<RefactorArgs, DispatchError> {
-        let Some(provider) = self.provider else {
-            return Err(missing_requirements_error());
-        };
         let Some(refactoring) = self.refactoring else {
             return Err(missing_requirements_error());
         };
@@ -63,13 +70,13 @@ impl RefactorArgsBuilder {
         let position = self.position;
         validate_position_contract(position, &self.extra)?;
         validate_trailing_extra_arguments(&self.extra)?;
-        validate_provider(&provider)?;
         validate_refactoring(&refactoring)?;
         Ok(RefactorArgs {
-            provider,
+            provider: self.provider,
             refactoring,
             file,
             position,
+            dry_run: self.dry_run,
             extra: self.extra,
         })
     }
@@ -105,7 +112,7 @@ fn validate_trailing_extra_arguments(extra: &[String]) -> Result<(), DispatchErr
     Err(DispatchError::invalid_arguments(format!(
         "act refactor only accepts trailing KEY=VALUE arguments; invalid trailing arguments: \
          {offending_tokens}. Use only --provider <plugin>, --refactoring <operation>, --file \
-         <path>, --position <line:col>, and trailing KEY=VALUE arguments"
+         <path>, --position <line:col>, --dry-run, and trailing KEY=VALUE arguments"
     )))
 }
 pub(crate) fn parse_refactor_args(
@@ -141,6 +148,7 @@ fn apply_flag<'a>(
         Flag::Refactoring => builder.refactoring = Some(parse_flag_value(flag, iter)?),
         Flag::File => builder.file = Some(parse_flag_value(flag, iter)?),
         Flag::Position => builder.position = Some(parse_position_flag(flag, iter, metrics)?),
+        Flag::DryRun => builder.dry_run = true,
     }
     Ok(())
 }
@@ -183,218 +191,5 @@ fn has_deprecated_offset_argument(arguments: &[String]) -> bool {
         .any(|argument| argument.starts_with("offset="))
 }
 #[cfg(test)]
-mod tests {
-    //! Unit tests for act refactor argument parsing.
-    use rstest::rstest;
-
-    use super::{LineCol, parse_refactor_args};
-    use crate::dispatch::{act::refactor::metrics::NullPositionMetrics, errors::DispatchError};
-    fn invalid_arguments_message(error: DispatchError) -> String {
-        match error {
-            DispatchError::InvalidArguments { message } => message,
-            other => panic!("expected invalid arguments error, got: {other:?}"),
-        }
-    }
-    fn args(tokens: &[&str]) -> Vec<String> { tokens.iter().copied().map(String::from).collect() }
-    #[track_caller]
-    fn assert_invalid_args_contains(args: Vec<String>, expected_substrings: &[&str]) {
-        let metrics = NullPositionMetrics;
-        let message = invalid_arguments_message(
-            parse_refactor_args(&args, &metrics).expect_err("parse should fail"),
-        );
-        for expected in expected_substrings {
-            assert!(
-                message.contains(expected),
-                "missing {expected:?} from: {message}"
-            );
-        }
-    }
-    #[rstest]
-    #[case::missing_flag_value(
-        vec![
-            String::from("--provider"),
-            String::from("rope"),
-            String::from("--refactoring"),
-            String::from("rename"),
-            String::from("--file"),
-        ],
-        vec!["requires a value"],
-    )]
-    #[case::flag_as_value(
-        vec![
-            String::from("--provider"),
-            String::from("rope"),
-            String::from("--refactoring"),
-            String::from("rename"),
-            String::from("--file"),
-            String::from("--provider"),
-        ],
-        vec!["requires a value"],
-    )]
-    #[case::unsupported_provider(
-        vec![
-            String::from("--provider"),
-            String::from("missing-provider"),
-            String::from("--refactoring"),
-            String::from("rename"),
-            String::from("--file"),
-            String::from("src/main.py"),
-            String::from("--position"),
-            String::from("1:1"),
-        ],
-        vec!["does not support provider 'missing-provider'", "Providers: rope, rust-analyzer"],
-    )]
-    #[case::unsupported_refactoring(
-        vec![
-            String::from("--provider"),
-            String::from("rope"),
-            String::from("--refactoring"),
-            String::from("extract-method"),
-            String::from("--file"),
-            String::from("src/main.py"),
-            String::from("--position"),
-            String::from("1:1"),
-        ],
-        vec!["does not support refactoring 'extract-method'", "Refactorings: rename"],
-    )]
-    #[case::unexpected_top_level_flag(
-        vec![
-            String::from("--provider"),
-            String::from("rope"),
-            String::from("--refactoring"),
-            String::from("rename"),
-            String::from("--file"),
-            String::from("src/main.py"),
-            String::from("--position"),
-            String::from("1:1"),
-            String::from("--bogus"),
-        ],
-        vec!["invalid trailing arguments: '--bogus'", "trailing KEY=VALUE arguments"],
-    )]
-    #[case::malformed_trailing_arguments(
-        vec![
-            String::from("--provider"),
-            String::from("rope"),
-            String::from("--refactoring"),
-            String::from("rename"),
-            String::from("--file"),
-            String::from("src/main.py"),
-            String::from("--position"),
-            String::from("1:1"),
-            String::from("offset"),
-            String::from("=woven"),
-            String::from("new_name"),
-        ],
-        vec!["invalid trailing arguments: 'offset', '=woven', 'new_name'", "trailing KEY=VALUE arguments"],
-    )]
-    #[case::interleaved_trailing_arguments(
-        vec![
-            String::from("--provider"),
-            String::from("rope"),
-            String::from("new_name=woven"),
-            String::from("--refactoring"),
-            String::from("rename"),
-            String::from("--file"),
-            String::from("src/main.py"),
-        ],
-        vec!["interleaved KEY=VALUE arguments", "before flag '--refactoring'"],
-    )]
-    #[case::missing_position_value(
-        vec![
-            String::from("--provider"),
-            String::from("rope"),
-            String::from("--refactoring"),
-            String::from("rename"),
-            String::from("--file"),
-            String::from("src/main.py"),
-            String::from("--position"),
-        ],
-        vec!["--position requires a value"],
-    )]
-    #[case::invalid_position_format(
-        vec![
-            String::from("--provider"),
-            String::from("rope"),
-            String::from("--refactoring"),
-            String::from("rename"),
-            String::from("--file"),
-            String::from("src/main.py"),
-            String::from("--position"),
-            String::from("1"),
-        ],
-        vec!["position must be LINE:COL"],
-    )]
-    #[case::zero_position_column(
-        vec![
-            String::from("--provider"),
-            String::from("rope"),
-            String::from("--refactoring"),
-            String::from("rename"),
-            String::from("--file"),
-            String::from("src/main.py"),
-            String::from("--position"),
-            String::from("1:0"),
-        ],
-        vec!["column number must be >= 1"],
-    )]
-    fn invalid_arguments_are_rejected(
-        #[case] args: Vec<String>,
-        #[case] expected_substrings: Vec<&str>,
-    ) {
-        assert_invalid_args_contains(args, &expected_substrings);
-    }
-    #[test]
-    fn parses_complete_argument_set() {
-        let args = args(&[
-            "--provider",
-            "rope",
-            "--refactoring",
-            "rename",
-            "--file",
-            "src/main.py",
-            "--position",
-            "1:5",
-        ]);
-
-        let metrics = NullPositionMetrics;
-        let parsed = parse_refactor_args(&args, &metrics).expect("parse succeeds");
-        assert_eq!(parsed.provider, "rope");
-        assert_eq!(parsed.refactoring, "rename");
-        assert_eq!(parsed.file, "src/main.py");
-        assert_eq!(parsed.position, Some(LineCol { line: 1, column: 5 }));
-    }
-    #[rstest]
-    #[case::no_arguments(Vec::new())]
-    #[case::missing_provider(args(&["--refactoring", "rename", "--file", "src/main.py"]))]
-    #[case::missing_refactoring(args(&["--provider", "rope", "--file", "src/main.py"]))]
-    #[case::missing_file(args(&["--provider", "rope", "--refactoring", "rename"]))]
-    #[case::missing_position(args(&[
-        "--provider",
-        "rope",
-        "--refactoring",
-        "rename",
-        "--file",
-        "src/main.py",
-    ]))]
-    fn missing_required_flags_report_full_contract(#[case] args: Vec<String>) {
-        let metrics = NullPositionMetrics;
-        let message = invalid_arguments_message(
-            parse_refactor_args(&args, &metrics).expect_err("parse should fail"),
-        );
-
-        for required in [
-            "--provider <plugin>",
-            "--refactoring <operation>",
-            "--file <path>",
-            "--position <line:col>",
-        ] {
-            assert!(
-                message.contains(required),
-                "missing '{required}' from: {message}"
-            );
-        }
-        assert!(message.contains("Providers: rope, rust-analyzer"));
-        assert!(message.contains("Refactorings: rename"));
-        assert!(message.contains("Next command:"));
-    }
-}
+#[path = "arguments_tests.rs"]
+mod tests;
@@ -16,24 +16,6 @@ pub(super) fn manifest_supports_language(
         .any(|entry| entry == language.as_str())
 }
 
-/// Returns a ranking tuple for provider prioritization.
-pub(super) fn provider_rank(provider_name: &str, language: SupportedLanguage) -> (usize, &str) {
-    let preferred = preferred_provider(language);
-    let rank = if provider_name == preferred { 0 } else { 1 };
-    (rank, provider_name)
-}
-
-/// Returns the preferred provider for a given language.
-pub(super) fn preferred_provider(language: SupportedLanguage) -> &'static str {
-    match language {
-        SupportedLanguage::Python => "rope",
-        SupportedLanguage::Rust => "rust-analyzer",
-        // TODO: Implement TypeScript provider support - this placeholder will cause routing to fail
-        // for TypeScript files
-        SupportedLanguage::TypeScript => "typescript-unimplemented",
-    }
-}
-
 /// Creates an accepted candidate evaluation.
 pub(super) fn accepted_candidate(
     manifest: &PluginManifest,
@@ -23,6 +23,7 @@ use crate::{
         RefactorPluginRuntime,
         ResponseWriter,
         handle,
+        manifests::rust_analyzer_manifest,
         refactor_helpers::builders::{build_backends, command_request},
         resolution::{
             CandidateEvaluation,
@@ -32,7 +33,6 @@ use crate::{
             ResolutionRequest,
             SelectionMode,
         },
-        rust_analyzer_manifest,
     },
     tests::support::fs as test_fs,
 };
@@ -122,11 +122,27 @@ struct RenameContractCase {
     new_name: Option<&'static str>,
 }
 
+/// Outcome of dispatching a rename request through the handler.
+struct RenameRun {
+    captured: Option<PluginRequest>,
+    file_path: PathBuf,
+    response_stream: String,
+    status: i32,
+}
+
 /// Dispatches a rename request through the handler and returns the captured
 /// `PluginRequest` for inspection.
 fn dispatch_inspecting_rename(
     config: RenameDispatch<'_>,
 ) -> Result<(PluginRequest, PathBuf, String), String> {
+    let run = run_rename(config)?;
+    let captured = run
+        .captured
+        .ok_or_else(|| String::from("request should be captured"))?;
+    Ok((captured, run.file_path, run.response_stream))
+}
+
+fn run_rename(config: RenameDispatch<'_>) -> Result<RenameRun, String> {
     let workspace = TempDir::new().map_err(|e| format!("workspace: {e}"))?;
     let file_path = workspace.path().join(config.file);
     test_fs::write(&file_path, "hello world\n").map_err(|e| format!("write: {e}"))?;
@@ -156,7 +172,7 @@ fn dispatch_inspecting_rename(
     let mut backends = build_backends(&socket_path);
     let mut output = Vec::new();
     let mut writer = ResponseWriter::new(&mut output);
-    handle(
+    let result = handle(
         &request,
         &mut writer,
         RefactorContext {
@@ -170,10 +186,14 @@ fn dispatch_inspecting_rename(
     let captured = runtime
         .captured
         .into_inner()
-        .map_err(|error| format!("captured request lock poisoned: {error}"))?
-        .ok_or_else(|| String::from("request should be captured"))?;
+        .map_err(|error| format!("captured request lock poisoned: {error}"))?;
     let response_stream = String::from_utf8(output).map_err(|e| format!("response utf8: {e}"))?;
-    Ok((captured, file_path, response_stream))
+    Ok(RenameRun {
+        captured,
+        file_path,
+        response_stream,
+        status: result.status,
+    })
 }
 
 fn assert_rename_request(
@@ -317,6 +337,34 @@ fn handler_accepts_deprecated_offset_without_position(
     Ok(())
 }
 
+#[rstest]
+fn handler_rejects_rename_without_new_name_before_execution(
+    socket_dir: Result<TempDir, String>,
+) -> Result<(), String> {
+    let socket_dir = socket_dir?;
+    let run = run_rename(RenameDispatch {
+        file: "notes.py",
+        provider: "rope",
+        language: "python",
+        position: Some("1:5"),
+        extra_args: Vec::new(),
+        socket_dir: &socket_dir,
+    })?;
+
+    assert_eq!(run.status, 1);
+    assert!(run.captured.is_none(), "plugin must not be executed");
+    assert!(
+        run.response_stream.contains(
+            "request does not satisfy the rename-symbol contract: rename-symbol contract requires \
+             'new_name' argument"
+        ),
+        "unexpected response stream: {}",
+        run.response_stream
+    );
+    assert!(!run.response_stream.contains("CapabilityResolution"));
+    Ok(())
+}
+
 #[test]
 fn rust_analyzer_manifest_declares_rename_symbol_capability() {
     let manifest = rust_analyzer_manifest(std::path::PathBuf::from(
@@ -0,0 +1,53 @@
+//! Capability contract checks around refactor plugin execution.
+//!
+//! The request is checked before any provider is resolved or spawned, so
+//! malformed operator input fails fast. The response is checked before its
+//! output is forwarded to `act apply-patch`. Capabilities without a contract
+//! pass through unchecked.
+
+use weaver_plugins::{PluginError, PluginRequest, PluginResponse, capability::CapabilityId};
+
+/// Validates `request` against the contract for `capability`.
+///
+/// Returns an operator-facing description of the violation on failure.
+pub(super) fn check_request(
+    capability: CapabilityId,
+    request: &PluginRequest,
+) -> Result<(), String> {
+    let Some(contract) = capability.contract() else {
+        return Ok(());
+    };
+    contract.validate_request(request).map_err(|error| {
+        format!(
+            "request does not satisfy the {capability} contract: {}",
+            describe(error)
+        )
+    })
+}
+
+/// Validates `response` against the contract for `capability`.
+///
+/// Returns an operator-facing description of the violation on failure.
+pub(super) fn check_response(
+    capability: CapabilityId,
+    response: &PluginResponse,
+) -> Result<(), String> {
+    let Some(contract) = capability.contract() else {
+        return Ok(());
+    };
+    contract.validate_response(response).map_err(|error| {
+        format!(
+            "response does not satisfy the {capability} contract: {}",
+            describe(error)
+        )
+    })
+}
+
+/// Contracts report violations as invalid output attributed to the
+/// capability rather than a plugin, so only the message is shown.
+fn describe(error: PluginError) -> String {
+    match error {
+        PluginError::InvalidOutput { message, .. } => message,
+        other => other.to_string(),
+    }
+}
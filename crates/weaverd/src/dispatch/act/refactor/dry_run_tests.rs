@@ -0,0 +1,120 @@
+//! Tests for `act refactor --dry-run`, including plugin warnings reported
+//! alongside a successful dry run.
+
+use serial_test::serial;
+use tempfile::TempDir;
+use weaver_plugins::{DiagnosticSeverity, PluginDiagnostic, PluginOutput, PluginResponse};
+
+use super::refactor_helpers::{
+    builders::{build_backends, command_request},
+    resolutions::SelectedResolution,
+    rollback::{ExecuteResult, selected_runtime},
+};
+use crate::{
+    dispatch::act::refactor::{RefactorContext, ResponseWriter, handle, resolution::SelectionMode},
+    tests::support::{fs as test_fs, stream::stdout_payload},
+};
+
+const DIFF: &str = concat!(
+    "diff --git a/notes.txt b/notes.txt\n",
+    "<<<<<<< SEARCH\n",
+    "hello world\n",
+    "=======\n",
+    "hello woven\n",
+    ">>>>>>> REPLACE\n",
+);
+
+/// Runs a dry-run rename of `notes.txt` whose plugin returns `response`,
+/// returning the exit status, the file content afterwards, and the output.
+fn run_dry_run(response: PluginResponse) -> Result<(i32, String, String), String> {
+    let workspace = TempDir::new().map_err(|e| format!("workspace: {e}"))?;
+    let file_path = workspace.path().join("notes.txt");
+    test_fs::write(&file_path, "hello world\n").map_err(|e| format!("write file: {e}"))?;
+    let runtime = selected_runtime(
+        SelectedResolution {
+            capability: weaver_plugins::CapabilityId::RenameSymbol,
+            language: "python",
+            provider: "rope",
+            selection_mode: SelectionMode::ExplicitProvider,
+            requested_provider: Some("rope"),
+        },
+        ExecuteResult::Success(response),
+    );
+    let request = command_request(Vec::from(
+        [
+            "--provider",
+            "rope",
+            "--refactoring",
+            "rename",
+            "--file",
+            "notes.txt",
+            "--position",
+            "1:1",
+            "--dry-run",
+            "new_name=woven",
+        ]
+        .map(String::from),
+    ));
+    let socket_dir = TempDir::new().map_err(|e| format!("socket dir: {e}"))?;
+    let mut backends = build_backends(&socket_dir.path().join("socket.sock"));
+    let mut output = Vec::new();
+    let mut writer = ResponseWriter::new(&mut output);
+
+    let result = handle(
+        &request,
+        &mut writer,
+        RefactorContext {
+            backends: &mut backends,
+            workspace_root: workspace.path(),
+            runtime: &runtime,
+        },
+    )
+    .map_err(|e| format!("dispatch should complete: {e}"))?;
+
+    let content = test_fs::read_to_string(&file_path).map_err(|e| format!("read file: {e}"))?;
+    let output = String::from_utf8(output).map_err(|e| format!("output utf8: {e}"))?;
+    Ok((result.status, content, output))
+}
+
+fn diff_response() -> PluginResponse {
+    PluginResponse::success(PluginOutput::Diff {
+        content: String::from(DIFF),
+    })
+}
+
+#[test]
+// FIXME(`#148`): `#[serial]` required until global AtomicU64 metrics statics are
+// replaced with an encapsulated metrics actor or registry.
+#[serial]
+fn dry_run_returns_diff_without_changing_files() -> Result<(), String> {
+    let (status, content, output) = run_dry_run(diff_response())?;
+
+    assert_eq!(status, 0);
+    assert_eq!(content, "hello world\n");
+    let summary = stdout_payload(&output);
+    assert_eq!(summary["dry_run"], true);
+    assert_eq!(summary["diff"], DIFF);
+    assert_eq!(summary["files_written"], 1);
+    Ok(())
+}
+
+#[test]
+// FIXME(`#148`): `#[serial]` required until global AtomicU64 metrics statics are
+// replaced with an encapsulated metrics actor or registry.
+#[serial]
+fn plugin_warnings_are_reported_without_failing() -> Result<(), String> {
+    let response = diff_response().with_diagnostics(vec![PluginDiagnostic::new(
+        DiagnosticSeverity::Warning,
+        "occurrence in a string literal left unchanged",
+    )]);
+
+    let (status, _, output) = run_dry_run(response)?;
+
+    assert_eq!(status, 0);
+    assert!(
+        output.contains("act refactor warning: occurrence in a string literal left unchanged"),
+        "missing warning in: {output}"
+    );
+    assert_eq!(stdout_payload(&output)["dry_run"], true);
+    Ok(())
+}
@@ -0,0 +1,99 @@
+//! Plugin execution for `act refactor`.
+//!
+//! Runs the selected provider with progress reporting and cancellation
+//! checks, validates its response against the capability contract, and hands
+//! successful diffs on to the Double-Lock harness. Each run is wrapped in a
+//! `refactor_plugin` tracing span; see [`super::telemetry`].
+
+use std::io::Write;
+
+use weaver_plugins::{PluginRequest, capability::CapabilityId};
+
+use super::{
+    RefactorContext,
+    RefactorPluginRuntime,
+    arguments::RefactorArgs,
+    contracts,
+    progress::{PROGRESS_INTERVAL, run_with_progress},
+    response_handling::handle_successful_execution,
+    telemetry::{PluginSpanFields, in_plugin_span},
+};
+use crate::dispatch::{
+    cancellation::{AFTER_PLUGIN_EXECUTION, BEFORE_PLUGIN_EXECUTION},
+    errors::DispatchError,
+    response::ResponseWriter,
+    router::DispatchResult,
+};
+
+/// Parameters required for plugin execution.
+pub(super) struct ExecutionParams<'a> {
+    pub(super) runtime: &'a dyn RefactorPluginRuntime,
+    pub(super) selected_provider: &'a str,
+    pub(super) capability: CapabilityId,
+    pub(super) plugin_request: &'a PluginRequest,
+}
+
+/// Executes the plugin and handles the response.
+///
+/// Progress messages are sent to the client while the plugin runs. A
+/// cancelled request stops before the plugin is spawned, or discards its
+/// output once it has exited. The whole run, including the Double-Lock
+/// harness, is recorded in a `refactor_plugin` span.
+pub(super) fn execute_plugin_and_handle_response<W: Write>(
+    params: ExecutionParams<'_>,
+    args: &RefactorArgs,
+    writer: &mut ResponseWriter<W>,
+    context: &mut RefactorContext<'_>,
+) -> Result<DispatchResult, DispatchError> {
+    let fields = PluginSpanFields {
+        provider: params.selected_provider,
+        refactoring: &args.refactoring,
+        file: &args.file,
+    };
+    in_plugin_span(fields, || run_plugin(&params, args, writer, context))
+}
+
+fn run_plugin<W: Write>(
+    params: &ExecutionParams<'_>,
+    args: &RefactorArgs,
+    writer: &mut ResponseWriter<W>,
+    context: &mut RefactorContext<'_>,
+) -> Result<DispatchResult, DispatchError> {
+    writer.check_cancelled(BEFORE_PLUGIN_EXECUTION)?;
+    let outcome = run_with_progress(
+        writer.progress(),
+        params.selected_provider,
+        PROGRESS_INTERVAL,
+        || {
+            params
+                .runtime
+                .execute(params.selected_provider, params.plugin_request)
+        },
+    );
+    writer.check_cancelled(AFTER_PLUGIN_EXECUTION)?;
+    let checked = outcome
+        .map_err(|error| error.to_string())
+        .and_then(|response| {
+            contracts::check_response(params.capability, &response).map(|()| response)
+        });
+    match checked {
+        Ok(response) => handle_successful_execution(response, args.dry_run, writer, context),
+        Err(message) => {
+            write_execution_error(&message, params.selected_provider, args, writer)?;
+            Ok(DispatchResult::with_status(1))
+        }
+    }
+}
+
+/// Writes an error message for a failed or rejected plugin execution.
+pub(super) fn write_execution_error<W: Write>(
+    error: &dyn std::fmt::Display,
+    selected_provider: &str,
+    args: &RefactorArgs,
+    writer: &mut ResponseWriter<W>,
+) -> Result<(), DispatchError> {
+    writer.write_stderr(format!(
+        "act refactor failed: {error} (provider={}, refactoring={}, file={})\n",
+        selected_provider, args.refactoring, args.file
+    ))
+}
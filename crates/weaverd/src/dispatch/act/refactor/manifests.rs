@@ -2,22 +2,35 @@
 //!
 //! Keeping manifest construction here prevents the main refactor handler from
 //! overflowing the repository's 400-line limit while still exposing a small
-//! test seam for capability declarations.
+//! test seam for capability declarations. The daemon's registry combines the
+//! built-in providers with any manifests found in the directory named by
+//! `WEAVER_PLUGIN_MANIFEST_DIR`.
 
-use std::path::PathBuf;
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
 
+use tracing::warn;
 use weaver_plugins::{
     CapabilityId,
+    PluginError,
+    PluginRegistry,
     manifest::{PluginKind, PluginManifest, PluginMetadata},
 };
 
 use super::plugin_paths::{
     ROPE_PLUGIN_NAME,
+    ROPE_PLUGIN_PATH_ENV,
     ROPE_PLUGIN_VERSION,
     RUST_ANALYZER_PLUGIN_NAME,
+    RUST_ANALYZER_PLUGIN_PATH_ENV,
     RUST_ANALYZER_PLUGIN_TIMEOUT_SECS,
     RUST_ANALYZER_PLUGIN_VERSION,
+    resolve_rope_plugin_path,
+    resolve_rust_analyzer_plugin_path,
 };
+use crate::dispatch::router::DISPATCH_TARGET;
 
 struct BuiltInProviderSpec {
     name: &'static str,
@@ -59,6 +72,52 @@ pub(crate) fn rust_analyzer_manifest(executable: PathBuf) -> PluginManifest {
 /// argument validation.
 pub(crate) fn built_in_provider_names() -> &'static [&'static str] { BUILT_IN_PROVIDER_NAMES }
 
+/// Builds the plugin registry used by the daemon's refactor runtime.
+///
+/// The built-in providers are registered first, so a manifest in
+/// `manifest_dir` cannot replace them. Manifests that fail to load are logged
+/// and skipped rather than disabling refactoring altogether. Relative plugin
+/// and manifest directory paths are resolved against `workspace_root`.
+///
+/// # Errors
+///
+/// Returns an error if a built-in manifest fails registration.
+pub(crate) fn daemon_registry(
+    workspace_root: &Path,
+    manifest_dir: Option<OsString>,
+) -> Result<PluginRegistry, PluginError> {
+    let rope_executable =
+        resolve_rope_plugin_path(workspace_root, std::env::var_os(ROPE_PLUGIN_PATH_ENV));
+    let rust_analyzer_executable = resolve_rust_analyzer_plugin_path(
+        workspace_root,
+        std::env::var_os(RUST_ANALYZER_PLUGIN_PATH_ENV),
+    );
+    let mut registry = PluginRegistry::new();
+    registry.register(rope_manifest(rope_executable))?;
+    registry.register(rust_analyzer_manifest(rust_analyzer_executable))?;
+
+    if let Some(dir) = manifest_dir.map(|dir| workspace_root.join(dir)) {
+        match registry.load_from_dir(&dir) {
+            Ok(report) => {
+                for failure in report.failures() {
+                    warn!(
+                        target: DISPATCH_TARGET,
+                        path = %failure.path().display(),
+                        error = %failure.error(),
+                        "skipping plugin manifest"
+                    );
+                }
+            }
+            Err(error) => warn!(
+                target: DISPATCH_TARGET,
+                %error,
+                "cannot load plugin manifest directory"
+            ),
+        }
+    }
+    Ok(registry)
+}
+
 fn manifest_from_spec(spec: &BuiltInProviderSpec, executable: PathBuf) -> PluginManifest {
     let metadata = PluginMetadata::new(spec.name, spec.version, PluginKind::Actuator);
     let manifest = PluginManifest::new(
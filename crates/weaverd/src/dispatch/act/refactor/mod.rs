@@ -12,20 +12,19 @@
 use std::{io::Write, path::Path, sync::Arc};
 
 use arguments::parse_refactor_args;
-use manifests::{rope_manifest, rust_analyzer_manifest};
+use execution::{ExecutionParams, execute_plugin_and_handle_response, write_execution_error};
+use manifests::daemon_registry;
 use metrics::AtomicPositionMetrics;
 pub(crate) use metrics::{position_conversion_error_count, position_parse_error_count};
-use plugin_paths::{
-    ROPE_PLUGIN_PATH_ENV,
-    RUST_ANALYZER_PLUGIN_PATH_ENV,
-    resolve_rope_plugin_path,
-    resolve_rust_analyzer_plugin_path,
-};
+use plugin_paths::PLUGIN_MANIFEST_DIR_ENV;
+use refusal::refusal_message;
 use request_building::prepare_plugin_request;
 use resolution::{CapabilityResolutionEnvelope, ResolutionRequest, resolve_provider};
+use targets::{REFACTOR_FILE_LIMIT_ENV, file_limit};
 use tracing::debug;
 use weaver_plugins::{
     PluginError,
+    PluginKind,
     PluginRegistry,
     PluginRequest,
     PluginResponse,
@@ -35,7 +34,7 @@ use weaver_plugins::{
 };
 
 use crate::{
-    backends::{BackendKind, FusionBackends},
+    backends::FusionBackends,
     dispatch::{
         errors::DispatchError,
         request::CommandRequest,
@@ -47,6 +46,8 @@ use crate::{
 
 mod arguments;
 mod candidates;
+mod contracts;
+mod execution;
 mod manifests;
 mod metrics;
 mod plugin_paths;
@@ -54,8 +55,11 @@ mod plugin_paths;
 pub(super) mod refactor_helpers;
 mod refusal;
 mod requirements;
+mod targets;
+mod telemetry;
 
 mod positions;
+mod progress;
 mod request_building;
 mod resolution;
 mod response_handling;
@@ -83,26 +87,42 @@ pub(crate) struct SandboxRefactorRuntime {
 }
 
 impl SandboxRefactorRuntime {
-    /// Builds the runtime from environment configuration.
+    /// Builds the runtime from environment configuration, resolving relative
+    /// plugin paths against `workspace_root`.
     ///
     /// # Errors
     ///
     /// Returns an error description if plugin registration fails.
-    pub fn from_environment() -> Result<Self, String> {
-        let mut registry = PluginRegistry::new();
-        let rope_executable = resolve_rope_plugin_path(std::env::var_os(ROPE_PLUGIN_PATH_ENV));
-        registry
-            .register(rope_manifest(rope_executable))
-            .map_err(|error| format!("failed to initialize refactor runtime: {error}"))?;
-
-        let rust_analyzer_executable =
-            resolve_rust_analyzer_plugin_path(std::env::var_os(RUST_ANALYZER_PLUGIN_PATH_ENV));
-        registry
-            .register(rust_analyzer_manifest(rust_analyzer_executable))
-            .map_err(|error| format!("failed to initialize refactor runtime: {error}"))?;
+    pub fn from_environment(
+        workspace_root: &Path,
+        executor: SandboxExecutor,
+    ) -> Result<Self, String> {
+        daemon_plugin_registry(workspace_root)
+            .and_then(|registry| Self::from_registry(&registry, executor))
+            .map_err(|error| format!("failed to initialize refactor runtime: {error}"))
+    }
 
-        let runner = PluginRunner::new(registry.clone(), SandboxExecutor);
-        Ok(Self { registry, runner })
+    /// Builds the runtime from every actuator in `registry`.
+    ///
+    /// Sensor plugins are skipped because `act refactor` only routes to
+    /// providers that produce diffs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an actuator manifest fails registration.
+    pub fn from_registry(
+        registry: &PluginRegistry,
+        executor: SandboxExecutor,
+    ) -> Result<Self, PluginError> {
+        let mut actuators = PluginRegistry::new();
+        for manifest in registry.find_by_kind(PluginKind::Actuator) {
+            actuators.register(manifest.clone())?;
+        }
+        let runner = PluginRunner::new(actuators.clone(), executor);
+        Ok(Self {
+            registry: actuators,
+            runner,
+        })
     }
 }
 
@@ -123,6 +143,18 @@ impl RefactorPluginRuntime for SandboxRefactorRuntime {
     }
 }
 
+/// Builds the daemon's plugin registry from the built-in providers and the
+/// manifest directory named by `WEAVER_PLUGIN_MANIFEST_DIR`.
+///
+/// Relative paths in the environment are resolved against `workspace_root`.
+///
+/// # Errors
+///
+/// Returns an error if a built-in manifest fails registration.
+pub(crate) fn daemon_plugin_registry(workspace_root: &Path) -> Result<PluginRegistry, PluginError> {
+    daemon_registry(workspace_root, std::env::var_os(PLUGIN_MANIFEST_DIR_ENV))
+}
+
 /// Runtime that reports an initialization error on every execution attempt.
 struct NoopRefactorRuntime {
     message: String,
@@ -151,8 +183,11 @@ impl RefactorPluginRuntime for NoopRefactorRuntime {
 
 /// Constructs the default refactor plugin runtime for daemon dispatch.
 #[must_use]
-pub(crate) fn default_runtime() -> Arc<dyn RefactorPluginRuntime + Send + Sync> {
-    match SandboxRefactorRuntime::from_environment() {
+pub(crate) fn default_runtime(
+    workspace_root: &Path,
+    executor: SandboxExecutor,
+) -> Arc<dyn RefactorPluginRuntime + Send + Sync> {
+    match SandboxRefactorRuntime::from_environment(workspace_root, executor) {
         Ok(runtime) => Arc::new(runtime),
         Err(message) => Arc::new(NoopRefactorRuntime { message }),
     }
@@ -190,41 +225,25 @@ fn resolve_provider_with_fallback(
         Err(error) => {
             writer.write_stderr(format!(
                 "act refactor failed: {error} (provider={}, refactoring={}, file={})\n",
-                args.provider, args.refactoring, args.file
+                args.provider_label(),
+                args.refactoring,
+                args.file
             ))?;
             Ok(None)
         }
     }
 }
 
-/// Parameters required for plugin execution.
-struct ExecutionParams<'a> {
-    runtime: &'a dyn RefactorPluginRuntime,
-    selected_provider: &'a str,
-    plugin_request: &'a PluginRequest,
-}
-
-/// Starts the semantic backend and handles the plugin response.
-fn handle_successful_execution<W: Write>(
-    response: PluginResponse,
-    writer: &mut ResponseWriter<W>,
-    context: &mut RefactorContext<'_>,
-) -> Result<DispatchResult, DispatchError> {
-    context
-        .backends
-        .ensure_started(BackendKind::Semantic)
-        .map_err(DispatchError::backend_startup)?;
-    handle_plugin_response(response, writer, context.backends, context.workspace_root)
-}
-
 /// Handles `act refactor` requests.
 ///
-/// Expects `--provider <plugin>`, `--refactoring <operation>`, and
-/// `--file <path>` in the request arguments.
+/// Expects `--refactoring <operation>` and `--file <path>`; without
+/// `--provider <plugin>` the provider is inferred from the file extension.
+/// `--file` may be a glob, in which case every match is sent to the plugin.
 ///
 /// The handler reads the file content, executes the plugin, and forwards
 /// successful diff output through `act apply-patch` for Double-Lock
-/// verification and atomic commit.
+/// verification and atomic commit. With `--dry-run` the verified diff is
+/// returned in the summary instead of being committed.
 pub fn handle<W: Write>(
     request: &CommandRequest,
     writer: &mut ResponseWriter<W>,
@@ -241,20 +260,28 @@ pub fn handle<W: Write>(
 
     debug!(
         target: DISPATCH_TARGET,
-        provider = args.provider,
+        provider = args.provider_label(),
         refactoring = args.refactoring,
         file = args.file,
         "handling act refactor"
     );
 
-    let (plugin_request, capability, file_path) =
-        prepare_plugin_request(context.workspace_root, &args, &metrics)?;
+    let (plugin_request, capability, file_path) = prepare_plugin_request(
+        context.workspace_root,
+        &args,
+        &metrics,
+        file_limit(std::env::var_os(REFACTOR_FILE_LIMIT_ENV)),
+    )?;
     write_deprecated_offset_warning(&args, writer)?;
+    if let Err(violation) = contracts::check_request(capability, &plugin_request) {
+        write_execution_error(&violation, args.provider_label(), &args, writer)?;
+        return Ok(DispatchResult::with_status(1));
+    }
     let resolution_params = ResolutionParams {
         runtime: context.runtime,
         capability,
         file_path: file_path.as_path(),
-        provider_override: Some(args.provider.as_str()),
+        provider_override: args.provider.as_deref(),
     };
 
     let Some(resolution) = resolve_provider_with_fallback(resolution_params, &args, writer)? else {
@@ -263,12 +290,14 @@ pub fn handle<W: Write>(
 
     write_capability_resolution(writer, &resolution)?;
     let Some(selected_provider) = resolution.details().selected_provider() else {
+        write_refusal(writer, &resolution, &args)?;
         return Ok(DispatchResult::with_status(1));
     };
 
     let execution_params = ExecutionParams {
         runtime: context.runtime,
         selected_provider,
+        capability,
         plugin_request: &plugin_request,
     };
 
@@ -283,6 +312,22 @@ fn write_capability_resolution<W: Write>(
     writer.write_stderr(format!("{json}\n"))
 }
 
+fn write_refusal<W: Write>(
+    writer: &mut ResponseWriter<W>,
+    resolution: &CapabilityResolutionEnvelope,
+    args: &arguments::RefactorArgs,
+) -> Result<(), DispatchError> {
+    let Some(message) = refusal_message(resolution.details()) else {
+        return Ok(());
+    };
+    writer.write_stderr(format!(
+        "act refactor failed: {message} (provider={}, refactoring={}, file={})\n",
+        args.provider_label(),
+        args.refactoring,
+        args.file
+    ))
+}
+
 fn write_deprecated_offset_warning<W: Write>(
     args: &arguments::RefactorArgs,
     writer: &mut ResponseWriter<W>,
@@ -295,46 +340,15 @@ fn write_deprecated_offset_warning<W: Write>(
     Ok(())
 }
 
-use response_handling::handle_plugin_response;
 #[cfg(test)]
 mod behaviour;
 #[cfg(test)]
 mod contract_tests;
 #[cfg(test)]
+mod dry_run_tests;
+#[cfg(test)]
 mod resolution_tests;
 #[cfg(test)]
 mod rollback_tests;
 #[cfg(test)]
 mod tests;
-
-/// Executes the plugin and handles the response.
-fn execute_plugin_and_handle_response<W: Write>(
-    params: ExecutionParams<'_>,
-    args: &arguments::RefactorArgs,
-    writer: &mut ResponseWriter<W>,
-    context: &mut RefactorContext<'_>,
-) -> Result<DispatchResult, DispatchError> {
-    match params
-        .runtime
-        .execute(params.selected_provider, params.plugin_request)
-    {
-        Ok(response) => handle_successful_execution(response, writer, context),
-        Err(error) => {
-            write_execution_error(&error, params.selected_provider, args, writer)?;
-            Ok(DispatchResult::with_status(1))
-        }
-    }
-}
-
-/// Writes an error message for a failed plugin execution.
-fn write_execution_error<W: Write>(
-    error: &PluginError,
-    selected_provider: &str,
-    args: &arguments::RefactorArgs,
-    writer: &mut ResponseWriter<W>,
-) -> Result<(), DispatchError> {
-    writer.write_stderr(format!(
-        "act refactor failed: {error} (provider={}, refactoring={}, file={})\n",
-        selected_provider, args.refactoring, args.file
-    ))
-}
@@ -1,8 +1,9 @@
 //! Plugin path constants and resolution helpers for `act refactor`.
 
-use std::{ffi::OsString, path::PathBuf};
-
-use crate::dispatch::router::DISPATCH_TARGET;
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
 
 /// Environment variable overriding the rope plugin executable path.
 pub(super) const ROPE_PLUGIN_PATH_ENV: &str = "WEAVER_ROPE_PLUGIN_PATH";
@@ -24,34 +25,74 @@ pub(super) const RUST_ANALYZER_PLUGIN_VERSION: &str = "0.1.0";
 /// Timeout budget for rust-analyzer plugin execution.
 pub(super) const RUST_ANALYZER_PLUGIN_TIMEOUT_SECS: u64 = 60;
 
+/// Environment variable naming a directory of additional plugin manifests.
+pub(super) const PLUGIN_MANIFEST_DIR_ENV: &str = "WEAVER_PLUGIN_MANIFEST_DIR";
+
 /// Converts an optional executable override to an absolute rope plugin path.
-pub(super) fn resolve_rope_plugin_path(raw_override: Option<OsString>) -> PathBuf {
-    resolve_plugin_path(raw_override, DEFAULT_ROPE_PLUGIN_PATH)
+///
+/// Relative overrides are resolved against `workspace_root`.
+pub(super) fn resolve_rope_plugin_path(
+    workspace_root: &Path,
+    raw_override: Option<OsString>,
+) -> PathBuf {
+    resolve_plugin_path(workspace_root, raw_override, DEFAULT_ROPE_PLUGIN_PATH)
 }
 
 /// Converts an optional executable override to an absolute rust-analyzer path.
-pub(super) fn resolve_rust_analyzer_plugin_path(raw_override: Option<OsString>) -> PathBuf {
-    resolve_plugin_path(raw_override, DEFAULT_RUST_ANALYZER_PLUGIN_PATH)
+///
+/// Relative overrides are resolved against `workspace_root`.
+pub(super) fn resolve_rust_analyzer_plugin_path(
+    workspace_root: &Path,
+    raw_override: Option<OsString>,
+) -> PathBuf {
+    resolve_plugin_path(
+        workspace_root,
+        raw_override,
+        DEFAULT_RUST_ANALYZER_PLUGIN_PATH,
+    )
 }
 
-fn resolve_plugin_path(raw_override: Option<OsString>, default_path: &str) -> PathBuf {
+fn resolve_plugin_path(
+    workspace_root: &Path,
+    raw_override: Option<OsString>,
+    default_path: &str,
+) -> PathBuf {
     let candidate = raw_override
         .map(PathBuf::from)
         .unwrap_or_else(|| PathBuf::from(default_path));
-    if candidate.is_absolute() {
-        return candidate;
+    // Joining an absolute candidate replaces the root, so absolute overrides
+    // are kept as given.
+    workspace_root.join(candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for plugin executable path resolution.
+
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::rope(resolve_rope_plugin_path, "bin/rope")]
+    #[case::rust_analyzer(resolve_rust_analyzer_plugin_path, "bin/rust-analyzer-plugin")]
+    fn relative_overrides_resolve_against_workspace_root(
+        #[case] resolve: fn(&Path, Option<OsString>) -> PathBuf,
+        #[case] raw: &str,
+    ) {
+        let root = Path::new("/srv/workspace");
+        assert_ne!(std::env::current_dir().ok().as_deref(), Some(root));
+
+        assert_eq!(resolve(root, Some(OsString::from(raw))), root.join(raw));
     }
 
-    match std::env::current_dir() {
-        Ok(cwd) => cwd.join(candidate),
-        Err(error) => {
-            tracing::warn!(
-                target: DISPATCH_TARGET,
-                path = %candidate.display(),
-                %error,
-                "cannot resolve relative plugin path against working directory; using path as-is"
-            );
-            candidate
-        }
+    #[rstest]
+    #[case::absolute_override(Some("/opt/rope"), "/opt/rope")]
+    #[case::default_path(None, DEFAULT_ROPE_PLUGIN_PATH)]
+    fn absolute_paths_ignore_workspace_root(#[case] raw: Option<&str>, #[case] expected: &str) {
+        let resolved =
+            resolve_rope_plugin_path(Path::new("/srv/workspace"), raw.map(OsString::from));
+
+        assert_eq!(resolved, PathBuf::from(expected));
     }
 }
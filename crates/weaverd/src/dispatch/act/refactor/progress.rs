@@ -0,0 +1,141 @@
+//! Periodic progress reporting while a refactor plugin runs.
+//!
+//! Plugins such as rust-analyzer can take a long time to answer, and the
+//! handler's response is only sent once the plugin returns. While it waits the
+//! handler reports elapsed time through the connection's [`ProgressSink`] so
+//! the client can show that the operation is still alive.
+
+use std::{
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use tracing::debug;
+
+use crate::dispatch::{response::ProgressSink, router::DISPATCH_TARGET};
+
+/// Interval between progress messages while waiting on a plugin.
+pub(super) const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Runs `work`, reporting progress every `interval` until it returns.
+///
+/// Without a sink the work runs directly. Reporting stops early if the client
+/// stops accepting messages; the work itself is never interrupted.
+pub(super) fn run_with_progress<T>(
+    progress: Option<&ProgressSink>,
+    provider: &str,
+    interval: Duration,
+    work: impl FnOnce() -> T,
+) -> T {
+    let Some(progress) = progress else {
+        return work();
+    };
+    let (done, finished) = mpsc::channel::<()>();
+    thread::scope(|scope| {
+        scope.spawn(move || report_until_finished(progress, provider, interval, &finished));
+        let output = work();
+        drop(done);
+        output
+    })
+}
+
+fn report_until_finished(
+    progress: &ProgressSink,
+    provider: &str,
+    interval: Duration,
+    finished: &Receiver<()>,
+) {
+    let started = Instant::now();
+    while let Err(RecvTimeoutError::Timeout) = finished.recv_timeout(interval) {
+        let elapsed = started.elapsed().as_secs();
+        let message = format!("waiting for {provider} ({elapsed}s elapsed)");
+        if let Err(error) = progress.report(message, None) {
+            debug!(target: DISPATCH_TARGET, %error, "stopped reporting refactor progress");
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for refactor progress reporting.
+
+    use std::{
+        io::{self, Write},
+        sync::{Arc, Mutex},
+    };
+
+    use rstest::rstest;
+
+    use super::*;
+
+    /// Writer that lets the test read what the sink wrote.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl SharedBuffer {
+        fn lines(&self) -> Vec<serde_json::Value> {
+            let bytes = self.0.lock().expect("buffer lock").clone();
+            String::from_utf8(bytes)
+                .expect("valid utf8")
+                .lines()
+                .map(|line| serde_json::from_str(line).expect("valid json"))
+                .collect()
+        }
+    }
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().expect("buffer lock").write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> { Ok(()) }
+    }
+
+    #[rstest]
+    fn reports_progress_while_work_is_running() {
+        let buffer = SharedBuffer::default();
+        let sink = ProgressSink::new(buffer.clone());
+
+        let output = run_with_progress(
+            Some(&sink),
+            "rust-analyzer",
+            Duration::from_millis(5),
+            || {
+                thread::sleep(Duration::from_millis(60));
+                "done"
+            },
+        );
+
+        assert_eq!(output, "done");
+        let lines = buffer.lines();
+        assert!(!lines.is_empty(), "expected at least one progress message");
+        for line in lines {
+            assert_eq!(line["kind"], "progress");
+            assert!(
+                line["message"]
+                    .as_str()
+                    .is_some_and(|message| message.starts_with("waiting for rust-analyzer")),
+                "unexpected message: {line}"
+            );
+            assert!(line.get("fraction").is_none());
+        }
+    }
+
+    #[rstest]
+    fn fast_work_reports_no_progress() {
+        let buffer = SharedBuffer::default();
+        let sink = ProgressSink::new(buffer.clone());
+
+        let output = run_with_progress(Some(&sink), "rope", Duration::from_secs(60), || 7);
+
+        assert_eq!(output, 7);
+        assert!(buffer.lines().is_empty());
+    }
+
+    #[rstest]
+    fn runs_work_without_a_sink() {
+        assert_eq!(run_with_progress(None, "rope", PROGRESS_INTERVAL, || 3), 3);
+    }
+}
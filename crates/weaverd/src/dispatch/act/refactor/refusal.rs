@@ -1,10 +1,11 @@
-//! Refusal construction for capability resolution.
+//! Refusal construction and reporting for capability resolution.
 
 use weaver_plugins::capability::CapabilityId;
 use weaver_syntax::SupportedLanguage;
 
 use super::resolution::{
     CandidateEvaluation,
+    CandidateReason,
     CapabilityResolutionDetails,
     CapabilityResolutionEnvelope,
     RefusalReason,
@@ -12,6 +13,68 @@ use super::resolution::{
     SelectionMode,
 };
 
+/// Describes why routing was refused, for operators reading `stderr`.
+///
+/// Provider errors list the registered providers that declare the requested
+/// capability, and ambiguous automatic selections list the providers that
+/// matched, so the operator can pick a valid `--provider`. Returns `None`
+/// when the resolution selected a provider.
+pub(super) fn refusal_message(details: &CapabilityResolutionDetails) -> Option<String> {
+    let capability = details.capability;
+    let provider = details.requested_provider.as_deref().unwrap_or_default();
+    let language = details.language.as_deref().unwrap_or("unknown");
+    let message = match details.refusal_reason? {
+        RefusalReason::UnsupportedLanguage => {
+            String::from("cannot infer a supported language from the target file")
+        }
+        RefusalReason::ProviderNotFound => format!(
+            "provider '{provider}' is not registered (available providers for {capability}: {})",
+            candidate_list(details)
+        ),
+        RefusalReason::ProviderLacksCapability => format!(
+            "provider '{provider}' does not declare the {capability} capability (available \
+             providers for {capability}: {})",
+            candidate_list(details)
+        ),
+        RefusalReason::ExplicitProviderMismatch => {
+            format!("provider '{provider}' does not support {language}")
+        }
+        RefusalReason::NoMatchingProvider => format!(
+            "no registered provider supports {capability} for {language} (available providers for \
+             {capability}: {})",
+            candidate_list(details)
+        ),
+        RefusalReason::AmbiguousProvider => format!(
+            "several providers support {capability} for {language} ({}); choose one with \
+             --provider",
+            ambiguous_candidate_list(details)
+        ),
+    };
+    Some(message)
+}
+
+fn candidate_list(details: &CapabilityResolutionDetails) -> String {
+    if details.candidates.is_empty() {
+        return String::from("none");
+    }
+    details
+        .candidates
+        .iter()
+        .map(|candidate| candidate.provider.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn ambiguous_candidate_list(details: &CapabilityResolutionDetails) -> String {
+    details
+        .candidates
+        .iter()
+        .filter(|candidate| candidate.reason == CandidateReason::AmbiguousMatch)
+        .map(|candidate| candidate.provider.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// Groups routing metadata for refusal construction.
 #[derive(Debug)]
 pub(super) struct RoutingContext {
@@ -1,5 +1,5 @@
 //! Builds plugin requests for `act refactor` by resolving and loading the
-//! target file, constructing the request payload, and applying
+//! target files, constructing the request payload, and applying
 //! capability-specific argument mapping through `CapabilityMappingContext`.
 //!
 //! Position-aware mapping converts line and column pairs to provider byte
@@ -22,13 +22,12 @@ use super::{
         capability_for_operation,
         effective_operation as supported_effective_operation,
     },
+    targets::resolve_targets,
+};
+use crate::dispatch::{
+    act::target_file::{canonical_workspace_root, load_file_contents},
+    errors::DispatchError,
 };
-use crate::dispatch::{errors::DispatchError, filesystem};
-
-struct ResolvedFile {
-    path: PathBuf,
-    relative_path: PathBuf,
-}
 
 struct CapabilityMappingContext<'a> {
     capability: CapabilityId,
@@ -38,30 +37,44 @@ struct CapabilityMappingContext<'a> {
     metrics: &'a dyn PositionMetrics,
 }
 
-/// Resolves the target file, reads its content, builds the [`PluginRequest`],
-/// and maps the refactoring operation to the corresponding [`CapabilityId`].
+/// Resolves the target files, reads their content, builds the
+/// [`PluginRequest`], and maps the refactoring operation to the corresponding
+/// [`CapabilityId`].
+///
+/// `--file` may be a glob matching up to `file_limit` files; each match
+/// becomes one [`FilePayload`]. The first match in path order is the primary
+/// target: capability mapping such as `--position` conversion reads its
+/// content, and its path is returned for provider selection.
 pub(super) fn prepare_plugin_request(
     workspace_root: &Path,
     args: &arguments::RefactorArgs,
     metrics: &dyn PositionMetrics,
+    file_limit: usize,
 ) -> Result<(PluginRequest, CapabilityId, PathBuf), DispatchError> {
-    let canonical_workspace = workspace_root.canonicalize().map_err(|error| {
-        DispatchError::invalid_arguments(format!(
-            "cannot canonicalize workspace root '{}': {error}",
-            workspace_root.display()
-        ))
-    })?;
-    let resolved_file = resolve_file(&canonical_workspace, &args.file)?;
+    let canonical_workspace = canonical_workspace_root(workspace_root)?;
+    let targets = resolve_targets(&canonical_workspace, &args.file, file_limit)?;
     let mut plugin_args = build_plugin_args(args)?;
     let effective_operation = supported_effective_operation(&args.refactoring)?;
     let capability = capability_for_operation(effective_operation)?;
-    let file_content = load_file_contents(&resolved_file.path)?;
+    let files = targets
+        .iter()
+        .map(|target| {
+            load_file_contents(&target.path)
+                .map(|content| FilePayload::new(target.relative_path.clone(), content))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let (Some(primary), Some(primary_file)) = (targets.first(), files.first()) else {
+        return Err(DispatchError::invalid_arguments(format!(
+            "'{}' matches no files in the workspace",
+            args.file
+        )));
+    };
     apply_capability_argument_mapping(
         &mut plugin_args,
         CapabilityMappingContext {
             capability,
-            file_path: &resolved_file.path,
-            file_content: &file_content,
+            file_path: &primary.path,
+            file_content: primary_file.content(),
             position: args.position,
             metrics,
         },
@@ -70,12 +83,9 @@ pub(super) fn prepare_plugin_request(
         String::from("refactoring"),
         serde_json::Value::String(String::from(effective_operation)),
     );
-    let plugin_request = PluginRequest::with_arguments(
-        effective_operation,
-        vec![FilePayload::new(resolved_file.relative_path, file_content)],
-        plugin_args,
-    );
-    Ok((plugin_request, capability, resolved_file.path))
+    let primary_path = primary.path.clone();
+    let plugin_request = PluginRequest::with_arguments(effective_operation, files, plugin_args);
+    Ok((plugin_request, capability, primary_path))
 }
 
 fn build_plugin_args(
@@ -122,50 +132,6 @@ fn apply_capability_argument_mapping(
     Ok(())
 }
 
-fn contains_parent_traversal(path: &Path) -> bool {
-    path.components()
-        .any(|c| matches!(c, std::path::Component::ParentDir))
-}
-
-fn resolve_file(workspace_root: &Path, file: &str) -> Result<ResolvedFile, DispatchError> {
-    let path = Path::new(file);
-    if path.is_absolute() {
-        return Err(DispatchError::invalid_arguments(
-            "absolute file paths are not allowed; use a path relative to the workspace root",
-        ));
-    }
-    if contains_parent_traversal(path) {
-        return Err(DispatchError::invalid_arguments(
-            "path traversal is not allowed",
-        ));
-    }
-    let resolved = workspace_root.join(path);
-    let canonical_resolved = resolved.canonicalize().map_err(|error| {
-        DispatchError::invalid_arguments(format!("cannot resolve file '{}': {error}", file))
-    })?;
-    if !canonical_resolved.starts_with(workspace_root) {
-        return Err(DispatchError::invalid_arguments(
-            "path traversal is not allowed",
-        ));
-    }
-    let relative_path = canonical_resolved
-        .strip_prefix(workspace_root)
-        .map_err(|_| {
-            DispatchError::invalid_arguments("resolved file path escapes the workspace root")
-        })?
-        .to_path_buf();
-    Ok(ResolvedFile {
-        path: canonical_resolved,
-        relative_path,
-    })
-}
-
-fn load_file_contents(path: &Path) -> Result<String, DispatchError> {
-    filesystem::read_to_string(path).map_err(|error| {
-        DispatchError::invalid_arguments(format!("cannot read file '{}': {error}", path.display()))
-    })
-}
-
 #[tracing::instrument(
     level = "debug",
     skip(plugin_args, context),
@@ -1,7 +1,9 @@
 //! Canonical operator-facing requirements for `act refactor`.
 //!
-//! This module keeps the required flags, supported provider names, supported
+//! This module keeps the required flags, built-in provider names, supported
 //! refactorings, and actionable error text aligned from one source of truth.
+//! Provider names are not validated here: any registered actuator may be
+//! requested, and routing reports unknown providers.
 
 use weaver_plugins::CapabilityId;
 
@@ -9,11 +11,11 @@ use super::manifests::built_in_provider_names;
 use crate::dispatch::errors::DispatchError;
 
 const REQUIRED_FLAGS: &[&str] = &[
-    "--provider <plugin>",
     "--refactoring <operation>",
     "--file <path>",
     "--position <line:col>",
 ];
+const OPTIONAL_PROVIDER_FLAG: &str = "--provider <plugin>";
 const NEXT_COMMAND_SUFFIX: &str = "--file path/to/file.py --position 1:1 new_name=renamed_symbol";
 
 struct SupportedRefactoring {
@@ -52,7 +54,8 @@ supported_refactorings!({
     capability: CapabilityId::RenameSymbol
 });
 
-/// Returns the canonical built-in provider names accepted by `act refactor`.
+/// Returns the canonical built-in provider names listed in `act refactor`
+/// guidance.
 pub(crate) fn supported_provider_names() -> &'static [&'static str] { built_in_provider_names() }
 
 /// Returns the supported user-facing refactoring names accepted by
@@ -61,14 +64,6 @@ pub(crate) fn supported_refactoring_names() -> &'static [&'static str] {
     SUPPORTED_REFACTORING_NAMES
 }
 
-/// Validates an operator-supplied provider name.
-///
-/// Returns [`DispatchError::InvalidArguments`] when `provider` is not one of
-/// [`supported_provider_names`].
-pub(crate) fn validate_provider(provider: &str) -> Result<(), DispatchError> {
-    validate_value("provider", supported_provider_names(), provider)
-}
-
 /// Validates an operator-supplied refactoring name.
 ///
 /// Returns [`DispatchError::InvalidArguments`] when `refactoring` is not one of
@@ -123,11 +118,13 @@ fn validate_value(kind: &str, supported: &[&str], value: &str) -> Result<(), Dis
 /// Builds the deterministic missing-arguments error for `act refactor`.
 ///
 /// The returned [`DispatchError::InvalidArguments`] lists every required
-/// top-level flag, the supported provider and refactoring values, and a next
-/// command example. This is used when one or more required flags are absent.
+/// top-level flag, the optional `--provider` flag, the supported provider and
+/// refactoring values, and a next command example. This is used when one or more required flags are
+/// absent.
 pub(crate) fn missing_requirements_error() -> DispatchError {
     DispatchError::invalid_arguments(format!(
-        "act refactor requires {}\n\n{}",
+        "act refactor requires {} ({OPTIONAL_PROVIDER_FLAG} is optional; omit it to select the \
+         provider from the file extension)\n\n{}",
         format_required_flags(),
         guidance_lines()
     ))
@@ -185,7 +182,6 @@ mod tests {
         missing_requirements_error,
         supported_provider_names,
         supported_refactoring_names,
-        validate_provider,
         validate_refactoring,
     };
     use crate::dispatch::errors::DispatchError;
@@ -217,15 +213,6 @@ mod tests {
         assert!(message.contains("Next command:"));
     }
 
-    #[test]
-    fn invalid_provider_error_lists_supported_values() {
-        let message =
-            invalid_arguments_message(validate_provider("missing-provider").expect_err("invalid"));
-
-        assert!(message.contains("does not support provider 'missing-provider'"));
-        assert!(message.contains("Providers: rope, rust-analyzer"));
-    }
-
     #[test]
     fn invalid_refactoring_error_lists_supported_values() {
         let message =
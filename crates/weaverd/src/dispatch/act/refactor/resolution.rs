@@ -1,8 +1,10 @@
 //! Capability resolution for `act refactor`.
 //!
 //! The daemon uses this module to choose a plugin for `rename-symbol` based on
-//! the requested capability, inferred language, and any explicit provider
-//! override supplied by the operator.
+//! the requested capability, the language inferred from the target file's
+//! extension, and any explicit provider override supplied by the operator.
+//! Without an override, exactly one registered actuator must match; zero or
+//! several matches are refused with the candidates listed.
 
 use std::path::Path;
 
@@ -11,12 +13,7 @@ use weaver_plugins::{PluginRegistry, capability::CapabilityId, manifest::PluginM
 use weaver_syntax::SupportedLanguage;
 
 use super::{
-    candidates::{
-        accepted_candidate,
-        manifest_supports_language,
-        provider_rank,
-        rejected_candidate,
-    },
+    candidates::{accepted_candidate, manifest_supports_language, rejected_candidate},
     refusal::{RoutingContext, refused},
 };
 
@@ -82,7 +79,8 @@ impl CapabilityResolutionDetails {
 pub(crate) enum SelectionMode {
     /// The operator supplied `--provider`, so the daemon validates it.
     ExplicitProvider,
-    /// The daemon chose the provider from language policy.
+    /// `--provider` was omitted, so the daemon chose the only actuator that
+    /// supports the language inferred from the file extension.
     Automatic,
 }
 
@@ -110,6 +108,9 @@ pub(crate) enum RefusalReason {
     ExplicitProviderMismatch,
     /// No registered provider matched the inferred language and capability.
     NoMatchingProvider,
+    /// More than one registered actuator matched the inferred language and
+    /// capability, so the operator must choose one with `--provider`.
+    AmbiguousProvider,
 }
 
 /// Candidate-by-candidate explanation of the routing choice.
@@ -130,8 +131,8 @@ pub(crate) enum CandidateReason {
     NotRequested,
     /// The candidate supports the capability but not the inferred language.
     UnsupportedLanguage,
-    /// The candidate matched, but a higher-priority policy choice won.
-    NotSelectedByPolicy,
+    /// The candidate matched, but so did another provider, so none was chosen.
+    AmbiguousMatch,
     /// The requested provider exists but does not support the inferred language.
     ExplicitProviderMismatch,
 }
@@ -218,7 +219,7 @@ pub(crate) fn resolve_provider(
         );
     }
 
-    resolve_automatic_provider(request.capability, language, candidates)
+    resolve_automatic_provider(registry, request.capability, language, &candidates)
 }
 
 struct ExplicitProviderContext<'a> {
@@ -294,53 +295,53 @@ fn resolve_explicit_provider(
 }
 
 fn resolve_automatic_provider(
+    registry: &PluginRegistry,
     capability: CapabilityId,
     language: SupportedLanguage,
-    candidates: Vec<&PluginManifest>,
+    candidates: &[&PluginManifest],
 ) -> CapabilityResolutionEnvelope {
-    let matching: Vec<&PluginManifest> = candidates
-        .iter()
-        .copied()
-        .filter(|manifest| manifest_supports_language(manifest, language))
+    let matching: Vec<&str> = registry
+        .find_actuator_for_language(language.as_str())
+        .into_iter()
+        .filter(|manifest| manifest.capabilities().contains(&capability))
+        .map(PluginManifest::name)
         .collect();
-
-    if matching.is_empty() {
-        return refused(
-            RoutingContext {
-                capability,
-                language: Some(language),
-                requested_provider: None,
-                selection_mode: SelectionMode::Automatic,
-            },
-            RefusalReason::NoMatchingProvider,
-            candidates
-                .iter()
-                .map(|manifest| rejected_candidate(manifest, CandidateReason::UnsupportedLanguage))
-                .collect(),
-        );
-    }
-
-    // INVARIANT: `matching` is guaranteed non-empty by the early-return guard above.
-    // The fallback to "unreachable" exists solely to satisfy the type system.
-    let selected_name = matching
-        .iter()
-        .min_by_key(|manifest| provider_rank(manifest.name(), language))
-        .map(|manifest| manifest.name())
-        .unwrap_or("unreachable");
+    let selected_name = match matching.as_slice() {
+        [only] => Some(*only),
+        _ => None,
+    };
 
     let evaluations = candidates
         .iter()
         .map(|manifest| {
-            if !manifest_supports_language(manifest, language) {
+            if !matching.contains(&manifest.name()) {
                 rejected_candidate(manifest, CandidateReason::UnsupportedLanguage)
-            } else if manifest.name() == selected_name {
+            } else if selected_name.is_some() {
                 accepted_candidate(manifest, CandidateReason::MatchedLanguageAndCapability)
             } else {
-                rejected_candidate(manifest, CandidateReason::NotSelectedByPolicy)
+                rejected_candidate(manifest, CandidateReason::AmbiguousMatch)
             }
         })
         .collect();
 
+    let Some(selected_name) = selected_name else {
+        let refusal_reason = if matching.is_empty() {
+            RefusalReason::NoMatchingProvider
+        } else {
+            RefusalReason::AmbiguousProvider
+        };
+        return refused(
+            RoutingContext {
+                capability,
+                language: Some(language),
+                requested_provider: None,
+                selection_mode: SelectionMode::Automatic,
+            },
+            refusal_reason,
+            evaluations,
+        );
+    };
+
     CapabilityResolutionEnvelope::from_details(CapabilityResolutionDetails {
         capability,
         language: Some(String::from(language.as_str())),
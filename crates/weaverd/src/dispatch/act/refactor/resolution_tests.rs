@@ -2,21 +2,29 @@
 
 use std::path::{Path, PathBuf};
 
+use rstest::rstest;
 use weaver_plugins::{
     CapabilityId,
     PluginManifest,
     PluginRegistry,
     manifest::{PluginKind, PluginMetadata},
+    process::SandboxExecutor,
 };
 
-use crate::dispatch::act::refactor::resolution::{
-    CandidateReason,
-    CapabilityResolutionDetails,
-    RefusalReason,
-    ResolutionOutcome,
-    ResolutionRequest,
-    SelectionMode,
-    resolve_provider,
+use crate::dispatch::act::refactor::{
+    RefactorPluginRuntime,
+    SandboxRefactorRuntime,
+    manifests::daemon_registry,
+    refusal::refusal_message,
+    resolution::{
+        CandidateReason,
+        CapabilityResolutionDetails,
+        RefusalReason,
+        ResolutionOutcome,
+        ResolutionRequest,
+        SelectionMode,
+        resolve_provider,
+    },
 };
 
 fn registry() -> Result<PluginRegistry, String> {
@@ -36,9 +44,19 @@ fn registry() -> Result<PluginRegistry, String> {
     registry
         .register(rope)
         .map_err(|e| format!("register rope: {e}"))?;
+    // An actuator that declares no capabilities, as a manifest-directory
+    // provider for an unrelated operation would.
+    let srgn = PluginManifest::new(
+        PluginMetadata::new("srgn", "1.0.0", PluginKind::Actuator),
+        vec![String::from("python")],
+        PathBuf::from("/usr/bin/srgn"),
+    );
     registry
         .register(rust_analyzer)
         .map_err(|e| format!("register rust-analyzer: {e}"))?;
+    registry
+        .register(srgn)
+        .map_err(|e| format!("register srgn: {e}"))?;
     Ok(registry)
 }
 
@@ -72,7 +90,7 @@ fn assert_provider_refused(details: &CapabilityResolutionDetails, reason: Refusa
 }
 
 #[test]
-fn automatic_python_selection_prefers_rope() -> Result<(), String> {
+fn automatic_python_selection_picks_rope() -> Result<(), String> {
     let envelope = resolution_for("src/main.py", None)?;
     let details = envelope.details();
 
@@ -86,7 +104,7 @@ fn automatic_python_selection_prefers_rope() -> Result<(), String> {
 }
 
 #[test]
-fn automatic_rust_selection_prefers_rust_analyzer() -> Result<(), String> {
+fn automatic_rust_selection_picks_rust_analyzer() -> Result<(), String> {
     let envelope = resolution_for("src/main.rs", None)?;
     let details = envelope.details();
 
@@ -102,6 +120,64 @@ fn automatic_rust_selection_prefers_rust_analyzer() -> Result<(), String> {
     Ok(())
 }
 
+#[rstest]
+#[case::python("src/main.py", "rope", "python")]
+#[case::rust("src/main.rs", "rust-analyzer", "rust")]
+fn daemon_runtime_selects_built_in_provider_from_extension(
+    #[case] path: &str,
+    #[case] provider: &str,
+    #[case] language: &str,
+) -> Result<(), String> {
+    let registry = daemon_registry(Path::new("/"), None).map_err(|e| e.to_string())?;
+    let runtime = SandboxRefactorRuntime::from_registry(&registry, SandboxExecutor::default())
+        .map_err(|e| e.to_string())?;
+
+    let envelope = runtime
+        .resolve(ResolutionRequest::new(
+            CapabilityId::RenameSymbol,
+            Path::new(path),
+            None,
+        ))
+        .map_err(|e| e.to_string())?;
+
+    assert_provider_selected(
+        envelope.details(),
+        provider,
+        SelectionMode::Automatic,
+        language,
+    );
+    Ok(())
+}
+
+#[test]
+fn automatic_selection_refuses_several_matching_providers() -> Result<(), String> {
+    let mut reg = registry()?;
+    let custom = PluginManifest::new(
+        PluginMetadata::new("tree-sitter-rename", "0.2.0", PluginKind::Actuator),
+        vec![String::from("python")],
+        PathBuf::from("/opt/weaver/tree-sitter-rename"),
+    )
+    .with_capabilities(vec![CapabilityId::RenameSymbol]);
+    reg.register(custom)
+        .map_err(|e| format!("register custom: {e}"))?;
+
+    let envelope = resolve_provider(
+        &reg,
+        ResolutionRequest::new(CapabilityId::RenameSymbol, Path::new("src/main.py"), None),
+    );
+    let details = envelope.details();
+
+    assert_eq!(details.selection_mode, SelectionMode::Automatic);
+    assert_provider_refused(details, RefusalReason::AmbiguousProvider);
+    let message = refusal_message(details).ok_or("refusal should have a message")?;
+    assert_eq!(
+        message,
+        "several providers support rename-symbol for python (rope, tree-sitter-rename); choose \
+         one with --provider"
+    );
+    Ok(())
+}
+
 #[test]
 fn explicit_provider_mismatch_is_refused() -> Result<(), String> {
     let envelope = resolution_for("src/main.py", Some("rust-analyzer"))?;
@@ -129,6 +205,65 @@ fn unknown_explicit_provider_is_refused() -> Result<(), String> {
 
     assert_eq!(details.selection_mode, SelectionMode::ExplicitProvider);
     assert_provider_refused(details, RefusalReason::ProviderNotFound);
+    let message = refusal_message(details).ok_or("refusal should have a message")?;
+    assert_eq!(
+        message,
+        "provider 'missing-provider' is not registered (available providers for rename-symbol: \
+         rope, rust-analyzer)"
+    );
+    Ok(())
+}
+
+#[test]
+fn provider_without_capability_is_refused() -> Result<(), String> {
+    let envelope = resolution_for("src/main.py", Some("srgn"))?;
+    let details = envelope.details();
+
+    assert_provider_refused(details, RefusalReason::ProviderLacksCapability);
+    let message = refusal_message(details).ok_or("refusal should have a message")?;
+    assert!(
+        message.contains("'srgn' does not declare the rename-symbol capability"),
+        "unexpected message: {message}"
+    );
+    assert!(message.contains("rope, rust-analyzer"), "{message}");
+    Ok(())
+}
+
+#[test]
+fn runtime_routes_to_every_registered_actuator() -> Result<(), String> {
+    let mut reg = registry()?;
+    let custom = PluginManifest::new(
+        PluginMetadata::new("tree-sitter-rename", "0.2.0", PluginKind::Actuator),
+        vec![String::from("python")],
+        PathBuf::from("/opt/weaver/tree-sitter-rename"),
+    )
+    .with_capabilities(vec![CapabilityId::RenameSymbol]);
+    let sensor = PluginManifest::new(
+        PluginMetadata::new("jedi", "1.0.0", PluginKind::Sensor),
+        vec![String::from("python")],
+        PathBuf::from("/usr/bin/jedi"),
+    );
+    reg.register(custom)
+        .map_err(|e| format!("register custom: {e}"))?;
+    reg.register(sensor)
+        .map_err(|e| format!("register jedi: {e}"))?;
+    let runtime = SandboxRefactorRuntime::from_registry(&reg, SandboxExecutor::default())
+        .map_err(|e| e.to_string())?;
+
+    let resolve = |provider| {
+        runtime.resolve(ResolutionRequest::new(
+            CapabilityId::RenameSymbol,
+            Path::new("src/main.py"),
+            Some(provider),
+        ))
+    };
+    let selected = resolve("tree-sitter-rename").map_err(|e| e.to_string())?;
+    assert_eq!(
+        selected.details().selected_provider(),
+        Some("tree-sitter-rename")
+    );
+    let sensor_request = resolve("jedi").map_err(|e| e.to_string())?;
+    assert_provider_refused(sensor_request.details(), RefusalReason::ProviderNotFound);
     Ok(())
 }
 
@@ -4,20 +4,45 @@ use std::{io::Write, path::Path};
 
 use weaver_plugins::{PluginOutput, PluginResponse};
 
+use super::RefactorContext;
 use crate::{
-    backends::FusionBackends,
+    backends::{BackendKind, FusionBackends},
     dispatch::{
         act::apply_patch,
+        cancellation::AFTER_BACKEND_STARTUP,
         errors::DispatchError,
-        request::{CommandDescriptor, CommandRequest},
         response::ResponseWriter,
         router::DispatchResult,
     },
     semantic_provider::SemanticBackendProvider,
 };
 
-pub(super) fn handle_plugin_response<W: Write>(
+/// Starts the semantic backend and handles the plugin response.
+///
+/// With `dry_run` set the diff is verified but not written.
+pub(super) fn handle_successful_execution<W: Write>(
     response: PluginResponse,
+    dry_run: bool,
+    writer: &mut ResponseWriter<W>,
+    context: &mut RefactorContext<'_>,
+) -> Result<DispatchResult, DispatchError> {
+    context
+        .backends
+        .ensure_started(BackendKind::Semantic)
+        .map_err(DispatchError::backend_startup)?;
+    writer.check_cancelled(AFTER_BACKEND_STARTUP)?;
+    handle_plugin_response(
+        response,
+        dry_run,
+        writer,
+        context.backends,
+        context.workspace_root,
+    )
+}
+
+fn handle_plugin_response<W: Write>(
+    response: PluginResponse,
+    dry_run: bool,
     writer: &mut ResponseWriter<W>,
     backends: &mut FusionBackends<SemanticBackendProvider>,
     workspace_root: &Path,
@@ -36,10 +61,15 @@ pub(super) fn handle_plugin_response<W: Write>(
         writer.write_stderr(format!("act refactor failed: {message}\n"))?;
         return Ok(DispatchResult::with_status(1));
     }
+    // A successful response only carries warnings and informational notes,
+    // which are reported without failing the operation.
+    for diagnostic in response.diagnostics() {
+        writer.write_stderr(format!("act refactor {diagnostic}\n"))?;
+    }
 
     match response.output() {
         PluginOutput::Diff { content } => {
-            forward_diff_to_apply_patch(content, writer, backends, workspace_root)
+            apply_patch::handle_generated_patch(content, dry_run, writer, backends, workspace_root)
         }
         PluginOutput::Analysis { .. } | PluginOutput::Empty => {
             writer.write_stderr(
@@ -49,20 +79,3 @@ pub(super) fn handle_plugin_response<W: Write>(
         }
     }
 }
-
-fn forward_diff_to_apply_patch<W: Write>(
-    patch: &str,
-    writer: &mut ResponseWriter<W>,
-    backends: &mut FusionBackends<SemanticBackendProvider>,
-    workspace_root: &Path,
-) -> Result<DispatchResult, DispatchError> {
-    let patch_request = CommandRequest {
-        command: CommandDescriptor {
-            domain: String::from("act"),
-            operation: String::from("apply-patch"),
-        },
-        arguments: Vec::new(),
-        patch: Some(patch.to_owned()),
-    };
-    apply_patch::handle(&patch_request, writer, backends, workspace_root)
-}
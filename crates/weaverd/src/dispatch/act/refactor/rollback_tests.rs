@@ -118,6 +118,9 @@ fn successful_non_diff_response_leaves_target_file_unchanged() -> Result<(), Str
             data: serde_json::json!({ "unexpected": true }),
         }),
     )))?;
-    assert_rollback_invariants(&outcome, "did not return diff output");
+    assert_rollback_invariants(
+        &outcome,
+        "response does not satisfy the rename-symbol contract",
+    );
     Ok(())
 }
@@ -0,0 +1,161 @@
+//! Target file selection for `act refactor`.
+//!
+//! `--file` names either a single workspace-relative path or a glob such as
+//! `src/**/*.py`. A glob is expanded against the workspace root and every
+//! match passes through the same traversal guards as a single path, so a
+//! symlink pointing outside the workspace is rejected rather than sent to the
+//! plugin. Matches are returned in path order; the first is the primary
+//! target used for provider selection and position mapping.
+
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+use glob::{MatchOptions, Pattern};
+
+use crate::dispatch::{
+    act::target_file::{ResolvedFile, ensure_workspace_relative, resolve_file},
+    errors::DispatchError,
+    router::DISPATCH_TARGET,
+};
+
+/// Environment variable capping how many files one glob may match.
+pub(super) const REFACTOR_FILE_LIMIT_ENV: &str = "WEAVER_REFACTOR_FILE_LIMIT";
+/// Default cap on the number of files one glob may match.
+pub(super) const DEFAULT_REFACTOR_FILE_LIMIT: usize = 100;
+
+/// Characters that make a `--file` value a glob rather than a literal path.
+const GLOB_METACHARACTERS: [char; 3] = ['*', '?', '['];
+
+/// Reads the file limit from an optional environment override.
+///
+/// Values that are not positive integers are ignored with a warning so a
+/// typo cannot disable the guard.
+pub(super) fn file_limit(raw_override: Option<OsString>) -> usize {
+    let Some(raw) = raw_override else {
+        return DEFAULT_REFACTOR_FILE_LIMIT;
+    };
+    match raw
+        .to_str()
+        .and_then(|value| value.trim().parse::<usize>().ok())
+    {
+        Some(limit) if limit > 0 => limit,
+        _ => {
+            tracing::warn!(
+                target: DISPATCH_TARGET,
+                value = %raw.to_string_lossy(),
+                default = DEFAULT_REFACTOR_FILE_LIMIT,
+                "ignoring invalid {REFACTOR_FILE_LIMIT_ENV}; expected a positive integer"
+            );
+            DEFAULT_REFACTOR_FILE_LIMIT
+        }
+    }
+}
+
+/// Resolves `file` to one or more files inside `workspace_root`.
+///
+/// Literal paths resolve to exactly one file. Globs must match at least one
+/// regular file, at most `limit` files, and only files sharing one extension,
+/// because a single request is routed to one language's provider.
+pub(super) fn resolve_targets(
+    workspace_root: &Path,
+    file: &str,
+    limit: usize,
+) -> Result<Vec<ResolvedFile>, DispatchError> {
+    if !file.contains(GLOB_METACHARACTERS) {
+        return resolve_file(workspace_root, file).map(|resolved| vec![resolved]);
+    }
+    // Reject absolute and `..` patterns before expansion; each match is
+    // checked again once symlinks are resolved.
+    ensure_workspace_relative(Path::new(file))?;
+    let matches = expand_glob(workspace_root, file, limit)?;
+    let mut targets = matches
+        .iter()
+        .map(|relative| resolve_file(workspace_root, &relative.to_string_lossy()))
+        .collect::<Result<Vec<_>, _>>()?;
+    targets.sort_by(|left, right| left.relative_path.cmp(&right.relative_path));
+    targets.dedup_by(|left, right| left.path == right.path);
+    ensure_single_extension(file, &targets)?;
+    Ok(targets)
+}
+
+/// Expands `pattern` under `workspace_root`, returning workspace-relative
+/// paths of the regular files it matches.
+fn expand_glob(
+    workspace_root: &Path,
+    pattern: &str,
+    limit: usize,
+) -> Result<Vec<PathBuf>, DispatchError> {
+    let root = workspace_root.to_str().ok_or_else(|| {
+        DispatchError::invalid_arguments(format!(
+            "cannot expand '{pattern}': workspace root '{}' is not valid UTF-8",
+            workspace_root.display()
+        ))
+    })?;
+    let full_pattern = format!("{}/{pattern}", Pattern::escape(root));
+    let options = MatchOptions {
+        case_sensitive: true,
+        require_literal_separator: true,
+        require_literal_leading_dot: true,
+    };
+    let entries = glob::glob_with(&full_pattern, options).map_err(|error| {
+        DispatchError::invalid_arguments(format!("invalid glob pattern '{pattern}': {error}"))
+    })?;
+    let mut matches = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|error| {
+            DispatchError::invalid_arguments(format!(
+                "cannot expand '{pattern}' at '{}': {}",
+                error.path().display(),
+                error.error()
+            ))
+        })?;
+        if !path.is_file() {
+            continue;
+        }
+        if matches.len() == limit {
+            return Err(DispatchError::invalid_arguments(format!(
+                "'{pattern}' matches more than {limit} files; narrow the pattern or raise \
+                 {REFACTOR_FILE_LIMIT_ENV}"
+            )));
+        }
+        let relative = path.strip_prefix(workspace_root).map_err(|_| {
+            DispatchError::invalid_arguments("resolved file path escapes the workspace root")
+        })?;
+        matches.push(relative.to_path_buf());
+    }
+    if matches.is_empty() {
+        return Err(DispatchError::invalid_arguments(format!(
+            "'{pattern}' matches no files in the workspace"
+        )));
+    }
+    Ok(matches)
+}
+
+fn ensure_single_extension(pattern: &str, targets: &[ResolvedFile]) -> Result<(), DispatchError> {
+    let mut extensions = targets
+        .iter()
+        .map(|target| {
+            target
+                .relative_path
+                .extension()
+                .map(|extension| extension.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+    extensions.sort();
+    extensions.dedup();
+    if extensions.len() > 1 {
+        return Err(DispatchError::invalid_arguments(format!(
+            "'{pattern}' matches files with different extensions ({}); refactor one language at a \
+             time",
+            extensions.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "targets_tests.rs"]
+mod tests;
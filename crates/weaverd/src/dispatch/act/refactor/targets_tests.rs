@@ -0,0 +1,199 @@
+//! Unit tests for `act refactor` target selection and glob expansion.
+
+use std::{ffi::OsString, path::PathBuf};
+
+use rstest::{fixture, rstest};
+use tempfile::TempDir;
+use weaver_test_macros::allow_fixture_expansion_lints;
+
+use super::*;
+use crate::{
+    dispatch::act::{
+        refactor::{
+            arguments::parse_refactor_args,
+            metrics::NullPositionMetrics,
+            request_building::prepare_plugin_request,
+        },
+        target_file::canonical_workspace_root,
+    },
+    tests::support::fs as test_fs,
+};
+
+/// Workspace holding two Python files under `src/`, a nested Python file,
+/// and a text file.
+#[allow_fixture_expansion_lints]
+#[fixture]
+fn workspace() -> Result<TempDir, String> {
+    let workspace = TempDir::new().map_err(|error| format!("workspace: {error}"))?;
+    let root = workspace.path();
+    for directory in ["src", "src/nested"] {
+        test_fs::create_dir_all(root.join(directory)).map_err(|error| error.to_string())?;
+    }
+    for (path, content) in [
+        ("src/b.py", "def beta():\n    pass\n"),
+        ("src/a.py", "def alpha():\n    pass\n"),
+        ("src/nested/c.py", "def gamma():\n    pass\n"),
+        ("src/notes.txt", "notes\n"),
+    ] {
+        test_fs::write(root.join(path), content).map_err(|error| format!("{path}: {error}"))?;
+    }
+    Ok(workspace)
+}
+
+fn resolve(workspace: &TempDir, pattern: &str, limit: usize) -> Result<Vec<PathBuf>, String> {
+    let root = canonical_workspace_root(workspace.path()).map_err(|error| error.to_string())?;
+    resolve_targets(&root, pattern, limit)
+        .map(|targets| {
+            targets
+                .into_iter()
+                .map(|target| target.relative_path)
+                .collect()
+        })
+        .map_err(|error| error.to_string())
+}
+
+#[rstest]
+fn glob_matching_two_files_produces_one_payload_each(
+    workspace: Result<TempDir, String>,
+) -> Result<(), String> {
+    let workspace = workspace?;
+    let arguments: Vec<String> = [
+        "--refactoring",
+        "rename",
+        "--file",
+        "src/*.py",
+        "--position",
+        "1:5",
+        "new_name=renamed",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect();
+    let args =
+        parse_refactor_args(&arguments, &NullPositionMetrics).map_err(|error| error.to_string())?;
+
+    let (request, _, primary) = prepare_plugin_request(
+        workspace.path(),
+        &args,
+        &NullPositionMetrics,
+        DEFAULT_REFACTOR_FILE_LIMIT,
+    )
+    .map_err(|error| error.to_string())?;
+
+    let paths: Vec<PathBuf> = request
+        .files()
+        .iter()
+        .map(|file| file.path().to_path_buf())
+        .collect();
+    assert_eq!(
+        paths,
+        [PathBuf::from("src/a.py"), PathBuf::from("src/b.py")]
+    );
+    assert!(primary.ends_with("src/a.py"), "primary target: {primary:?}");
+    assert_eq!(
+        request.arguments().get("position"),
+        Some(&serde_json::json!("4"))
+    );
+    Ok(())
+}
+
+#[rstest]
+fn recursive_glob_matches_nested_files(workspace: Result<TempDir, String>) -> Result<(), String> {
+    let workspace = workspace?;
+
+    let paths = resolve(&workspace, "src/**/*.py", DEFAULT_REFACTOR_FILE_LIMIT)?;
+
+    assert_eq!(
+        paths,
+        [
+            PathBuf::from("src/a.py"),
+            PathBuf::from("src/b.py"),
+            PathBuf::from("src/nested/c.py"),
+        ]
+    );
+    Ok(())
+}
+
+#[rstest]
+#[case::parent_traversal("../*.py", "path traversal is not allowed")]
+#[case::nested_traversal("src/../../*.py", "path traversal is not allowed")]
+#[case::absolute("/tmp/*.py", "absolute file paths are not allowed")]
+fn glob_escaping_the_workspace_is_rejected(
+    workspace: Result<TempDir, String>,
+    #[case] pattern: &str,
+    #[case] expected: &str,
+) -> Result<(), String> {
+    let workspace = workspace?;
+
+    let error = resolve(&workspace, pattern, DEFAULT_REFACTOR_FILE_LIMIT)
+        .expect_err("escaping glob should be rejected");
+
+    assert!(error.contains(expected), "unexpected error: {error}");
+    Ok(())
+}
+
+#[cfg(unix)]
+#[rstest]
+fn glob_match_through_escaping_symlink_is_rejected(
+    workspace: Result<TempDir, String>,
+) -> Result<(), String> {
+    let workspace = workspace?;
+    let outside = TempDir::new().map_err(|error| format!("outside: {error}"))?;
+    let target = outside.path().join("secret.py");
+    test_fs::write(&target, "SECRET = 1\n").map_err(|error| error.to_string())?;
+    let src = cap_std::fs::Dir::open_ambient_dir(
+        workspace.path().join("src"),
+        cap_std::ambient_authority(),
+    )
+    .map_err(|error| format!("open src: {error}"))?;
+    src.symlink_contents(&target, "link.py")
+        .map_err(|error| format!("create symlink: {error}"))?;
+
+    let error = resolve(&workspace, "src/*.py", DEFAULT_REFACTOR_FILE_LIMIT)
+        .expect_err("symlink escaping the workspace should be rejected");
+
+    assert!(
+        error.contains("path traversal is not allowed"),
+        "unexpected error: {error}"
+    );
+    Ok(())
+}
+
+#[rstest]
+#[case::over_limit("src/**/*.py", 2, "matches more than 2 files")]
+#[case::no_matches("src/*.rs", DEFAULT_REFACTOR_FILE_LIMIT, "matches no files")]
+#[case::mixed_extensions("src/*", DEFAULT_REFACTOR_FILE_LIMIT, "different extensions (py, txt)")]
+fn glob_guards_reject_unsuitable_matches(
+    workspace: Result<TempDir, String>,
+    #[case] pattern: &str,
+    #[case] limit: usize,
+    #[case] expected: &str,
+) -> Result<(), String> {
+    let workspace = workspace?;
+
+    let error = resolve(&workspace, pattern, limit).expect_err("glob should be rejected");
+
+    assert!(error.contains(expected), "unexpected error: {error}");
+    Ok(())
+}
+
+#[rstest]
+fn literal_paths_resolve_without_expansion(
+    workspace: Result<TempDir, String>,
+) -> Result<(), String> {
+    let workspace = workspace?;
+
+    let paths = resolve(&workspace, "src/b.py", 1)?;
+
+    assert_eq!(paths, [PathBuf::from("src/b.py")]);
+    Ok(())
+}
+
+#[rstest]
+#[case::unset(None, DEFAULT_REFACTOR_FILE_LIMIT)]
+#[case::valid(Some(" 7 "), 7)]
+#[case::zero(Some("0"), DEFAULT_REFACTOR_FILE_LIMIT)]
+#[case::not_a_number(Some("many"), DEFAULT_REFACTOR_FILE_LIMIT)]
+fn file_limit_reads_positive_overrides(#[case] raw: Option<&str>, #[case] expected: usize) {
+    assert_eq!(file_limit(raw.map(OsString::from)), expected);
+}
@@ -0,0 +1,68 @@
+//! Tracing span recorded around `act refactor` plugin execution.
+//!
+//! Every plugin run is wrapped in a `refactor_plugin` span on the dispatch
+//! target carrying the provider, refactoring, and file, and is closed with the
+//! run's duration and exit status. The span and its closing event are ordinary
+//! `info`/`warn` records, so the daemon's configured log filter decides
+//! whether they are emitted.
+
+use std::time::Instant;
+
+use tracing::{field, info, info_span, warn};
+
+use crate::dispatch::{
+    errors::DispatchError,
+    router::{DISPATCH_TARGET, DispatchResult},
+};
+
+/// Request details recorded on the `refactor_plugin` span.
+pub(super) struct PluginSpanFields<'a> {
+    /// Provider selected to run the refactoring.
+    pub(super) provider: &'a str,
+    /// Refactoring operation requested by the client.
+    pub(super) refactoring: &'a str,
+    /// Target file as given on the command line.
+    pub(super) file: &'a str,
+}
+
+/// Runs `execute` inside a `refactor_plugin` span.
+///
+/// Once `execute` returns, the span records `duration_ms` and `status`, and
+/// an event reports the outcome: `info` when the exit status is zero and
+/// `warn` otherwise. Dispatch errors are recorded with their exit status and
+/// then returned unchanged.
+pub(super) fn in_plugin_span(
+    fields: PluginSpanFields<'_>,
+    execute: impl FnOnce() -> Result<DispatchResult, DispatchError>,
+) -> Result<DispatchResult, DispatchError> {
+    let span = info_span!(
+        target: DISPATCH_TARGET,
+        "refactor_plugin",
+        provider = fields.provider,
+        refactoring = fields.refactoring,
+        file = fields.file,
+        duration_ms = field::Empty,
+        status = field::Empty,
+    );
+    let started = Instant::now();
+    let result = span.in_scope(execute);
+    let duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    let status = match &result {
+        Ok(outcome) => outcome.status,
+        Err(error) => error.exit_status(),
+    };
+    span.record("duration_ms", duration_ms);
+    span.record("status", status);
+    span.in_scope(|| {
+        if status == 0 {
+            info!(target: DISPATCH_TARGET, status, duration_ms, "refactor plugin succeeded");
+        } else {
+            warn!(target: DISPATCH_TARGET, status, duration_ms, "refactor plugin failed");
+        }
+    });
+    result
+}
+
+#[cfg(test)]
+#[path = "telemetry_tests.rs"]
+mod tests;
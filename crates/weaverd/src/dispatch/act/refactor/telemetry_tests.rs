@@ -0,0 +1,236 @@
+//! Unit tests for the `refactor_plugin` tracing span.
+
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+use rstest::rstest;
+use tracing::{
+    Dispatch,
+    Event,
+    Level,
+    Subscriber,
+    dispatcher,
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+};
+use tracing_subscriber::{
+    EnvFilter,
+    Layer,
+    layer::Context,
+    prelude::*,
+    registry::{LookupSpan, Registry},
+};
+
+use super::*;
+
+type Fields = BTreeMap<String, String>;
+
+/// Span or event captured by [`SpanRecorder`].
+#[derive(Debug)]
+struct Captured {
+    name: String,
+    level: Level,
+    fields: Fields,
+}
+
+#[derive(Debug, Default)]
+struct Captures {
+    spans: Vec<Captured>,
+    events: Vec<Captured>,
+}
+
+/// Layer that keeps span fields as they are recorded and reports them, along
+/// with every event, once the span closes.
+struct SpanRecorder {
+    captures: Arc<Mutex<Captures>>,
+}
+
+struct FieldVisitor<'a>(&'a mut Fields);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().to_owned(), format!("{value:?}"));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_owned(), value.to_owned());
+    }
+}
+
+impl<S> Layer<S> for SpanRecorder
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut fields = Fields::new();
+        attrs.record(&mut FieldVisitor(&mut fields));
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(fields);
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id)
+            && let Some(fields) = span.extensions_mut().get_mut::<Fields>()
+        {
+            values.record(&mut FieldVisitor(fields));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut fields = Fields::new();
+        event.record(&mut FieldVisitor(&mut fields));
+        if let Ok(mut captures) = self.captures.lock() {
+            captures.events.push(Captured {
+                name: event.metadata().name().to_owned(),
+                level: *event.metadata().level(),
+                fields,
+            });
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let fields = span
+            .extensions()
+            .get::<Fields>()
+            .cloned()
+            .unwrap_or_default();
+        if let Ok(mut captures) = self.captures.lock() {
+            captures.spans.push(Captured {
+                name: span.name().to_owned(),
+                level: *span.metadata().level(),
+                fields,
+            });
+        }
+    }
+}
+
+/// Runs `execute` in a plugin span under a subscriber that filters the
+/// dispatch target at `level`, as the daemon's log filter would.
+fn capture(
+    level: &str,
+    execute: impl FnOnce() -> Result<DispatchResult, DispatchError>,
+) -> (Result<DispatchResult, DispatchError>, Captures) {
+    let captures = Arc::new(Mutex::new(Captures::default()));
+    let recorder = SpanRecorder {
+        captures: Arc::clone(&captures),
+    };
+    let subscriber = Registry::default()
+        .with(recorder.with_filter(EnvFilter::new(format!("{DISPATCH_TARGET}={level}"))));
+    let fields = PluginSpanFields {
+        provider: "rope",
+        refactoring: "rename",
+        file: "src/main.py",
+    };
+    let result = dispatcher::with_default(&Dispatch::new(subscriber), || {
+        in_plugin_span(fields, execute)
+    });
+    let captured = std::mem::take(
+        &mut *captures
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()),
+    );
+    (result, captured)
+}
+
+fn message(event: &Captured) -> &str { event.fields.get("message").map_or("", String::as_str) }
+
+#[rstest]
+#[case::success(
+    Ok(DispatchResult::success()),
+    0,
+    Level::INFO,
+    "refactor plugin succeeded"
+)]
+#[case::refused(
+    Ok(DispatchResult::with_status(1)),
+    1,
+    Level::WARN,
+    "refactor plugin failed"
+)]
+#[case::dispatch_error(
+    Err(DispatchError::invalid_arguments("bad")),
+    1,
+    Level::WARN,
+    "refactor plugin failed"
+)]
+fn records_plugin_span_with_outcome(
+    #[case] outcome: Result<DispatchResult, DispatchError>,
+    #[case] status: i32,
+    #[case] level: Level,
+    #[case] expected_message: &str,
+) {
+    let (result, captures) = capture("info", || outcome);
+    let status = status.to_string();
+
+    assert_eq!(
+        result
+            .map_or_else(|error| error.exit_status(), |outcome| outcome.status)
+            .to_string(),
+        status
+    );
+    let [span] = captures.spans.as_slice() else {
+        panic!("expected one span, got {:?}", captures.spans);
+    };
+    assert_eq!(span.name, "refactor_plugin");
+    assert_eq!(
+        span.fields.get("provider").map(String::as_str),
+        Some("rope")
+    );
+    assert_eq!(
+        span.fields.get("refactoring").map(String::as_str),
+        Some("rename")
+    );
+    assert_eq!(
+        span.fields.get("file").map(String::as_str),
+        Some("src/main.py")
+    );
+    assert_eq!(
+        span.fields.get("status").map(String::as_str),
+        Some(status.as_str())
+    );
+    assert!(
+        span.fields.contains_key("duration_ms"),
+        "span should record its duration: {:?}",
+        span.fields
+    );
+    let [event] = captures.events.as_slice() else {
+        panic!("expected one event, got {:?}", captures.events);
+    };
+    assert_eq!(event.level, level);
+    assert_eq!(message(event), expected_message);
+    assert_eq!(
+        event.fields.get("status").map(String::as_str),
+        Some(status.as_str())
+    );
+}
+
+#[test]
+fn log_filter_suppresses_span_below_its_level() {
+    let (result, captures) = capture("warn", || Ok(DispatchResult::with_status(1)));
+
+    assert_eq!(result.expect("dispatch").status, 1);
+    assert!(
+        captures.spans.is_empty(),
+        "span should be filtered: {:?}",
+        captures.spans
+    );
+    let [event] = captures.events.as_slice() else {
+        panic!("expected the failure event, got {:?}", captures.events);
+    };
+    assert_eq!(event.level, Level::WARN);
+}
+
+#[test]
+fn log_filter_can_disable_plugin_telemetry() {
+    let (result, captures) = capture("off", || Ok(DispatchResult::success()));
+
+    assert_eq!(result.expect("dispatch").status, 0);
+    assert!(captures.spans.is_empty());
+    assert!(captures.events.is_empty());
+}
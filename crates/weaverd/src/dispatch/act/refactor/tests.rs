@@ -5,7 +5,13 @@
 use rstest::{fixture, rstest};
 use serial_test::serial;
 use tempfile::TempDir;
-use weaver_plugins::{PluginError, PluginOutput, PluginRequest, PluginResponse};
+use weaver_plugins::{
+    PluginError,
+    PluginOutput,
+    PluginRequest,
+    PluginResponse,
+    process::SandboxExecutor,
+};
 use weaver_test_macros::allow_fixture_expansion_lints;
 
 use crate::{
@@ -25,8 +31,6 @@ use crate::{
             ResolutionRequest,
             SelectionMode,
         },
-        resolve_rope_plugin_path,
-        resolve_rust_analyzer_plugin_path,
     },
     tests::support::fs as test_fs,
 };
@@ -108,6 +112,7 @@ fn run_rename_handle(
         String::from(file),
         String::from("--position"),
         String::from("1:1"),
+        String::from("new_name=woven"),
     ]);
     let runtime = MockRuntime { resolution, result };
     let socket_path = socket_dir.path().join("socket.sock");
@@ -191,6 +196,7 @@ fn handle_non_diff_output_returns_status_one(
         String::from("notes.py"),
         String::from("--position"),
         String::from("1:1"),
+        String::from("new_name=woven"),
     ]);
     let runtime = MockRuntime {
         resolution: MockResolution::Success(automatic_selection("rope", "python")),
@@ -214,7 +220,7 @@ fn handle_non_diff_output_returns_status_one(
 
     assert_eq!(result.status, 1);
     let stderr = String::from_utf8(output).expect("stderr utf8");
-    assert!(stderr.contains("did not return diff output"));
+    assert!(stderr.contains("response does not satisfy the rename-symbol contract"));
 }
 
 #[rstest]
@@ -250,6 +256,7 @@ fn handle_diff_output_applies_patch_through_apply_patch_pipeline(socket_dir: Tem
         relative_file.clone(),
         String::from("--position"),
         String::from("1:1"),
+        String::from("new_name=woven"),
     ]);
     let socket_path = socket_dir.path().join("socket.sock");
     let mut backends = build_backends(&socket_path);
@@ -275,23 +282,10 @@ fn handle_diff_output_applies_patch_through_apply_patch_pipeline(socket_dir: Tem
     assert!(stderr.contains("\"kind\":\"stream\""));
 }
 
-#[test]
-fn resolve_rope_plugin_path_makes_relative_overrides_absolute() {
-    let path = resolve_rope_plugin_path(Some(std::ffi::OsString::from("bin/rope")));
-    assert!(path.is_absolute());
-}
-
-#[test]
-fn resolve_rust_analyzer_plugin_path_makes_relative_overrides_absolute() {
-    let path = resolve_rust_analyzer_plugin_path(Some(std::ffi::OsString::from(
-        "bin/rust-analyzer-plugin",
-    )));
-    assert!(path.is_absolute());
-}
-
 #[test]
 fn default_runtime_returns_shared_trait_object() {
-    let runtime = default_runtime();
+    let workspace = TempDir::new().expect("workspace");
+    let runtime = default_runtime(workspace.path(), SandboxExecutor::default());
     let request = PluginRequest::new("rename", Vec::new());
     let result = runtime.execute("rope", &request);
     assert!(result.is_err());
@@ -312,6 +306,7 @@ fn handle_returns_error_for_unsupported_refactoring(socket_dir: TempDir) {
         String::from("notes.py"),
         String::from("--position"),
         String::from("1:1"),
+        String::from("new_name=woven"),
     ]);
     let socket_path = socket_dir.path().join("socket.sock");
     let mut backends = build_backends(&socket_path);
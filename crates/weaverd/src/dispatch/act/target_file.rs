@@ -0,0 +1,120 @@
+//! Resolution of workspace-relative target files for dispatch handlers.
+//!
+//! Commands that operate on a single file, such as `act refactor`,
+//! `act apply-rewrite`, and `observe analyze`, accept a path relative to the
+//! workspace root. These helpers reject absolute paths and parent traversal,
+//! confirm that the canonical path stays inside the workspace, and read the
+//! file content.
+
+use std::path::{Path, PathBuf};
+
+use crate::dispatch::{errors::DispatchError, filesystem};
+
+/// A target file resolved against the canonical workspace root.
+pub(crate) struct ResolvedFile {
+    /// Canonical absolute path to the file.
+    pub(crate) path: PathBuf,
+    /// Path of the file relative to the workspace root.
+    pub(crate) relative_path: PathBuf,
+}
+
+/// Canonicalizes the workspace root so that resolved files can be checked
+/// for containment.
+pub(crate) fn canonical_workspace_root(workspace_root: &Path) -> Result<PathBuf, DispatchError> {
+    workspace_root.canonicalize().map_err(|error| {
+        DispatchError::invalid_arguments(format!(
+            "cannot canonicalize workspace root '{}': {error}",
+            workspace_root.display()
+        ))
+    })
+}
+
+fn contains_parent_traversal(path: &Path) -> bool {
+    path.components()
+        .any(|c| matches!(c, std::path::Component::ParentDir))
+}
+
+/// Rejects absolute paths and parent traversal before anything touches the
+/// filesystem.
+///
+/// Glob patterns are checked with this before expansion, and each match is
+/// then resolved with [`resolve_file`].
+pub(crate) fn ensure_workspace_relative(path: &Path) -> Result<(), DispatchError> {
+    if path.is_absolute() {
+        return Err(DispatchError::invalid_arguments(
+            "absolute file paths are not allowed; use a path relative to the workspace root",
+        ));
+    }
+    if contains_parent_traversal(path) {
+        return Err(DispatchError::invalid_arguments(
+            "path traversal is not allowed",
+        ));
+    }
+    Ok(())
+}
+
+/// Resolves `file` against the canonical `workspace_root`, rejecting paths
+/// that could escape it.
+pub(crate) fn resolve_file(
+    workspace_root: &Path,
+    file: &str,
+) -> Result<ResolvedFile, DispatchError> {
+    let path = Path::new(file);
+    ensure_workspace_relative(path)?;
+    let resolved = workspace_root.join(path);
+    let canonical_resolved = resolved.canonicalize().map_err(|error| {
+        DispatchError::invalid_arguments(format!("cannot resolve file '{}': {error}", file))
+    })?;
+    if !canonical_resolved.starts_with(workspace_root) {
+        return Err(DispatchError::invalid_arguments(
+            "path traversal is not allowed",
+        ));
+    }
+    let relative_path = canonical_resolved
+        .strip_prefix(workspace_root)
+        .map_err(|_| {
+            DispatchError::invalid_arguments("resolved file path escapes the workspace root")
+        })?
+        .to_path_buf();
+    Ok(ResolvedFile {
+        path: canonical_resolved,
+        relative_path,
+    })
+}
+
+/// Reads the content of a resolved target file.
+pub(crate) fn load_file_contents(path: &Path) -> Result<String, DispatchError> {
+    filesystem::read_to_string(path).map_err(|error| {
+        DispatchError::invalid_arguments(format!("cannot read file '{}': {error}", path.display()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for workspace-relative file resolution.
+
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::tests::support::fs as test_fs;
+
+    #[test]
+    fn files_resolve_against_the_workspace_root_not_the_working_directory() {
+        let workspace = TempDir::new().expect("workspace");
+        test_fs::write(workspace.path().join("main.py"), "pass\n").expect("write file");
+        let root = canonical_workspace_root(workspace.path()).expect("canonical root");
+        let cwd = std::env::current_dir().expect("working directory");
+        assert!(
+            !cwd.starts_with(&root),
+            "test requires a root outside the cwd"
+        );
+
+        let resolved = resolve_file(&root, "main.py").expect("file inside the workspace root");
+
+        assert_eq!(resolved.path, root.join("main.py"));
+        assert_eq!(resolved.relative_path, PathBuf::from("main.py"));
+        // `Cargo.toml` exists in the test working directory but not the root.
+        assert!(cwd.join("Cargo.toml").exists());
+        assert!(resolve_file(&root, "Cargo.toml").is_err());
+    }
+}
@@ -0,0 +1,171 @@
+//! Best-effort cancellation of in-flight requests.
+//!
+//! Once the handler has read a request it keeps watching the connection on a
+//! background thread. A client that sends [`CANCEL_REQUEST_LINE`] or closes
+//! its end of the socket marks the request as cancelled. Handlers poll the
+//! resulting [`CancellationToken`] between stages, such as after starting the
+//! semantic backend or around plugin execution, and abandon the request at the
+//! first checkpoint that observes the flag.
+//!
+//! Cancellation never interrupts a stage that is already running: a plugin
+//! process finishes or reaches its manifest timeout and is reaped by the
+//! sandbox as usual, after which its output is discarded.
+//!
+//! [`CANCEL_REQUEST_LINE`]: weaver_daemon_types::CANCEL_REQUEST_LINE
+
+use std::{
+    io::{self, BufRead, BufReader, Read},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+};
+
+use tracing::debug;
+use weaver_daemon_types::ClientControl;
+
+use super::{errors::DispatchError, router::DISPATCH_TARGET};
+use crate::transport::ConnectionStream;
+
+/// Checkpoint reached once the request holds the shared backends.
+pub(crate) const BEFORE_ROUTING: &str = "while waiting for the backends";
+/// Checkpoint reached once the semantic backend is running.
+pub(crate) const AFTER_BACKEND_STARTUP: &str = "after backend startup";
+/// Checkpoint reached just before a plugin process is spawned.
+pub(crate) const BEFORE_PLUGIN_EXECUTION: &str = "before plugin execution";
+/// Checkpoint reached once a plugin process has exited.
+pub(crate) const AFTER_PLUGIN_EXECUTION: &str = "after plugin execution";
+
+/// Longest control line the watcher buffers; longer lines are discarded.
+const CONTROL_LINE_LIMIT: u64 = 1024;
+
+/// Shared flag recording whether the client abandoned a request.
+///
+/// A default token is never cancelled, so handlers that run without a
+/// watched connection (tests, nested requests) pass every checkpoint.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Marks the request as cancelled.
+    pub fn cancel(&self) { self.cancelled.store(true, Ordering::Release); }
+
+    /// Returns whether the request has been cancelled.
+    pub fn is_cancelled(&self) -> bool { self.cancelled.load(Ordering::Acquire) }
+
+    /// Fails with [`DispatchError::Cancelled`] once the request is cancelled.
+    ///
+    /// `stage` names the checkpoint so logs show how far the request got.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DispatchError::Cancelled`] if the client has abandoned the
+    /// request.
+    pub fn check(&self, stage: &'static str) -> Result<(), DispatchError> {
+        if self.is_cancelled() {
+            return Err(DispatchError::cancelled(stage));
+        }
+        Ok(())
+    }
+}
+
+/// Watches a connection for cancellation while its request is handled.
+///
+/// Dropping the watch tells the background thread that the response is
+/// complete, so the client closing the connection afterwards is not reported
+/// as a cancellation. The thread exits on its next read, which the handler
+/// prompts by shutting down the write half of the socket.
+pub(super) struct ClientWatch {
+    token: CancellationToken,
+    finished: Arc<AtomicBool>,
+}
+
+impl ClientWatch {
+    /// Starts watching a clone of `stream` for a cancel message or EOF.
+    ///
+    /// Cancellation is best effort, so a socket that cannot be cloned, or a
+    /// thread that cannot be spawned, only loses cancellation support rather
+    /// than failing the request.
+    pub(super) fn start(stream: &ConnectionStream, endpoint: &str) -> Self {
+        let watch = Self {
+            token: CancellationToken::default(),
+            finished: Arc::new(AtomicBool::new(false)),
+        };
+        let spawned = stream.try_clone().and_then(|clone| {
+            let token = watch.token.clone();
+            let finished = Arc::clone(&watch.finished);
+            thread::Builder::new()
+                .name(String::from("weaverd-cancel-watch"))
+                .spawn(move || watch_connection(clone, &token, &finished))
+        });
+        if let Err(error) = spawned {
+            debug!(
+                target: DISPATCH_TARGET,
+                endpoint,
+                %error,
+                "cancellation unavailable for connection"
+            );
+        }
+        watch
+    }
+
+    /// Returns the token that records the client's cancellation.
+    pub(super) fn token(&self) -> CancellationToken { self.token.clone() }
+}
+
+impl Drop for ClientWatch {
+    fn drop(&mut self) { self.finished.store(true, Ordering::Release); }
+}
+
+/// Reads control lines from the client until it cancels or disconnects.
+fn watch_connection<R: Read>(stream: R, token: &CancellationToken, finished: &AtomicBool) {
+    let mut reader = BufReader::new(stream);
+    let mut line = Vec::new();
+    loop {
+        let outcome = (&mut reader)
+            .take(CONTROL_LINE_LIMIT)
+            .read_until(b'\n', &mut line);
+        if finished.load(Ordering::Acquire) {
+            return;
+        }
+        let reason = match outcome {
+            Ok(0) => "client closed the connection",
+            Ok(_) if is_cancel_line(&line) => "client sent a cancel message",
+            // Unrecognised or oversized lines are ignored; a partial line
+            // before EOF surfaces as `Ok(0)` on the next read.
+            Ok(_) => {
+                line.clear();
+                continue;
+            }
+            Err(error) if is_retryable(&error) => continue,
+            Err(_) => "connection failed",
+        };
+        debug!(target: DISPATCH_TARGET, reason, "cancelling in-flight request");
+        token.cancel();
+        return;
+    }
+}
+
+fn is_cancel_line(line: &[u8]) -> bool {
+    line.ends_with(b"\n")
+        && matches!(
+            serde_json::from_slice::<ClientControl>(line),
+            Ok(ClientControl::Cancel)
+        )
+}
+
+/// Socket read timeouts surface as `WouldBlock` on Unix and `TimedOut` on
+/// Windows; neither means the client went away.
+fn is_retryable(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut | io::ErrorKind::Interrupted
+    )
+}
+
+#[cfg(test)]
+#[path = "cancellation_tests.rs"]
+mod tests;
@@ -0,0 +1,101 @@
+//! Unit tests for request cancellation.
+
+use std::{
+    io::{self, Cursor, Read},
+    net::{Shutdown, TcpListener, TcpStream},
+    time::{Duration, Instant},
+};
+
+use rstest::rstest;
+use weaver_daemon_types::CANCEL_REQUEST_LINE;
+
+use super::*;
+
+/// Reader that times out once before yielding its data, like an idle socket.
+struct IdleThenReader {
+    idle: bool,
+    data: Cursor<Vec<u8>>,
+}
+
+impl IdleThenReader {
+    fn new(data: &[u8]) -> Self {
+        Self {
+            idle: true,
+            data: Cursor::new(data.to_vec()),
+        }
+    }
+}
+
+impl Read for IdleThenReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if std::mem::take(&mut self.idle) {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+        self.data.read(buf)
+    }
+}
+
+fn watch(input: impl Read, finished: bool) -> CancellationToken {
+    let token = CancellationToken::default();
+    watch_connection(input, &token, &AtomicBool::new(finished));
+    token
+}
+
+#[rstest]
+#[case::cancel_message(CANCEL_REQUEST_LINE.as_bytes())]
+#[case::disconnect(b"")]
+#[case::ignored_lines_then_disconnect(b"{\"kind\":\"ping\"}\nnot json\n")]
+fn cancel_messages_and_disconnects_cancel_the_request(#[case] input: &[u8]) {
+    assert!(watch(Cursor::new(input), false).is_cancelled());
+}
+
+#[test]
+fn read_timeouts_do_not_cancel_the_request() {
+    let token = watch(IdleThenReader::new(CANCEL_REQUEST_LINE.as_bytes()), false);
+
+    assert!(token.is_cancelled(), "the watcher should keep reading");
+}
+
+#[test]
+fn disconnect_after_the_response_is_not_a_cancellation() {
+    assert!(!watch(Cursor::new(b""), true).is_cancelled());
+}
+
+#[test]
+fn check_reports_the_cancelled_stage() {
+    let token = CancellationToken::default();
+    assert!(token.check(AFTER_BACKEND_STARTUP).is_ok());
+
+    token.cancel();
+    let error = token
+        .check(AFTER_BACKEND_STARTUP)
+        .expect_err("cancelled token should fail the checkpoint");
+    assert_eq!(
+        error.to_string(),
+        "request cancelled by client after backend startup"
+    );
+}
+
+#[test]
+fn client_disconnect_mid_request_cancels_the_token() {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).expect("bind");
+    let client = TcpStream::connect(listener.local_addr().expect("addr")).expect("connect");
+    let (server, _) = listener.accept().expect("accept");
+    let stream = ConnectionStream::Tcp(server);
+
+    let watch = ClientWatch::start(&stream, "tcp://127.0.0.1");
+    let token = watch.token();
+    assert!(!token.is_cancelled());
+    client.shutdown(Shutdown::Both).expect("client shutdown");
+    drop(client);
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !token.is_cancelled() {
+        assert!(
+            Instant::now() < deadline,
+            "disconnect was not observed in time"
+        );
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    drop(watch);
+}
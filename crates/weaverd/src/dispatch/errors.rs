@@ -4,11 +4,11 @@
 //! and command dispatch. Each variant maps to a specific failure mode and
 //! carries enough context to produce actionable error messages for clients.
 
-use std::{io, sync::Arc};
+use std::{io, sync::Arc, time::Duration};
 
 use thiserror::Error;
 
-use crate::backends::BackendStartupError;
+use crate::{backends::BackendStartupError, semantic_provider::LspHostPoisonedError};
 
 /// Errors surfaced during request parsing and dispatch.
 #[derive(Debug, Error)]
@@ -61,10 +61,26 @@ pub enum DispatchError {
     #[error("LSP error for {language}: {message}")]
     LspHost { language: String, message: String },
 
+    /// The language server did not publish diagnostics before the deadline.
+    #[error("no diagnostics published for {uri} within {timeout_ms} ms: {message}")]
+    DiagnosticsTimeout {
+        uri: String,
+        timeout_ms: u128,
+        message: String,
+    },
+
     /// File extension does not map to a supported language.
     #[error("unsupported language for extension: {extension}")]
     UnsupportedLanguage { extension: String },
 
+    /// The client cancelled the request or disconnected before it finished.
+    #[error("request cancelled by client {stage}")]
+    Cancelled { stage: &'static str },
+
+    /// The shared LSP host lock was poisoned by a panic in another request.
+    #[error("LSP host unavailable: {0}")]
+    LspHostPoisoned(#[from] LspHostPoisonedError),
+
     /// Internal error (e.g., lock poisoned).
     #[error("internal error: {message}")]
     Internal { message: String },
@@ -85,8 +101,13 @@ impl DispatchError {
             | Self::InvalidArguments { .. }
             | Self::BackendStartup(_)
             | Self::LspHost { .. }
-            | Self::UnsupportedLanguage { .. } => 1,
-            Self::Io(_) | Self::SerializeResponse(_) | Self::Internal { .. } => 2,
+            | Self::DiagnosticsTimeout { .. }
+            | Self::UnsupportedLanguage { .. }
+            | Self::Cancelled { .. } => 1,
+            Self::Io(_)
+            | Self::SerializeResponse(_)
+            | Self::LspHostPoisoned(_)
+            | Self::Internal { .. } => 2,
         }
     }
 
@@ -158,6 +179,20 @@ impl DispatchError {
         }
     }
 
+    /// Creates a diagnostics timeout error, keeping the last server error as
+    /// the message.
+    pub fn diagnostics_timeout(
+        uri: impl Into<String>,
+        timeout: Duration,
+        message: impl Into<String>,
+    ) -> Self {
+        Self::DiagnosticsTimeout {
+            uri: uri.into(),
+            timeout_ms: timeout.as_millis(),
+            message: message.into(),
+        }
+    }
+
     /// Creates an unsupported language error.
     pub fn unsupported_language(extension: impl Into<String>) -> Self {
         Self::UnsupportedLanguage {
@@ -165,6 +200,9 @@ impl DispatchError {
         }
     }
 
+    /// Creates a cancellation error naming the checkpoint that observed it.
+    pub fn cancelled(stage: &'static str) -> Self { Self::Cancelled { stage } }
+
     /// Creates an internal error.
     pub fn internal(message: impl Into<String>) -> Self {
         Self::Internal {
@@ -0,0 +1,58 @@
+//! Tests for abandoning requests whose client has cancelled or disconnected.
+
+use rstest::rstest;
+use tempfile::TempDir;
+
+use super::{
+    tests_helpers::{BackendManagerFixture, backend_manager, capture_events},
+    *,
+};
+use crate::dispatch::cancellation::CancellationToken;
+
+const REQUEST: &[u8] = b"{\"command\":{\"domain\":\"observe\",\"operation\":\"get-definition\"}}";
+
+#[rstest]
+fn cancelled_requests_are_not_routed_or_answered(
+    backend_manager: Result<BackendManagerFixture, String>,
+) -> Result<(), String> {
+    let temp_dir = TempDir::new().map_err(|error| format!("temporary directory: {error}"))?;
+    let handler = DispatchConnectionHandler::new(
+        backend_manager?.manager(),
+        temp_dir.path().join("workspace"),
+        "tcp://127.0.0.1:0",
+        temp_dir.path().to_path_buf(),
+    )
+    .map_err(|error| format!("handler: {error}"))?;
+    let request = CommandRequest::parse(REQUEST).map_err(|error| format!("parse: {error}"))?;
+    let token = CancellationToken::default();
+    token.cancel();
+
+    let mut output = Vec::new();
+    let events = capture_events(|| {
+        let mut writer = ResponseWriter::new(&mut output).with_cancellation(token);
+        handler.route_request(request, REQUEST.len(), &mut writer);
+    });
+
+    assert!(
+        output.is_empty(),
+        "a cancelled request must not be answered"
+    );
+    let cancelled = events
+        .iter()
+        .find(|event| {
+            event
+                .fields
+                .get("event")
+                .is_some_and(|value| value == "request_cancelled")
+        })
+        .ok_or_else(|| format!("expected a request_cancelled event; captured: {events:?}"))?;
+    assert_eq!(cancelled.level, tracing::Level::INFO);
+    assert!(
+        !events.iter().any(|event| event
+            .fields
+            .get("event")
+            .is_some_and(|value| value == "dispatch_failed")),
+        "cancellation must not be reported as a dispatch failure"
+    );
+    Ok(())
+}
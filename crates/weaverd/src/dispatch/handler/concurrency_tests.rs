@@ -0,0 +1,81 @@
+//! Tests for serving several dispatch clients at once.
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{SocketAddr, TcpStream},
+    sync::Arc,
+    thread,
+};
+
+use rstest::rstest;
+use tempfile::TempDir;
+use weaver_config::SocketEndpoint;
+
+use super::{
+    tests_helpers::{BackendManagerFixture, backend_manager},
+    *,
+};
+use crate::transport::SocketListener;
+
+const CLIENTS: usize = 6;
+const WORKERS: usize = 2;
+const REQUEST: &[u8] = b"{\"command\":{\"domain\":\"observe\",\"operation\":\"get-definition\"}}\n";
+
+/// Sends one request and returns every response line the daemon wrote.
+fn run_client(addr: SocketAddr) -> Result<Vec<serde_json::Value>, String> {
+    let mut client = TcpStream::connect(addr).map_err(|error| format!("connect: {error}"))?;
+    client
+        .write_all(REQUEST)
+        .map_err(|error| format!("write request: {error}"))?;
+    BufReader::new(client)
+        .lines()
+        .map(|line| {
+            let line = line.map_err(|error| format!("read: {error}"))?;
+            serde_json::from_str(&line).map_err(|error| format!("decode {line}: {error}"))
+        })
+        .collect()
+}
+
+#[rstest]
+fn concurrent_clients_each_receive_an_exit_message(
+    backend_manager: Result<BackendManagerFixture, String>,
+) -> Result<(), String> {
+    let backend_manager = backend_manager?.manager();
+    let temp_dir = TempDir::new().map_err(|error| format!("temporary directory: {error}"))?;
+    let handler: Arc<dyn ConnectionHandler> = Arc::new(
+        DispatchConnectionHandler::new(
+            backend_manager,
+            temp_dir.path().join("workspace"),
+            "tcp://127.0.0.1:0",
+            temp_dir.path().to_path_buf(),
+        )
+        .map_err(|error| format!("handler: {error}"))?,
+    );
+    let listener = SocketListener::bind(&SocketEndpoint::tcp("127.0.0.1", 0))
+        .map_err(|error| format!("bind: {error}"))?
+        .with_workers(WORKERS);
+    let addr = listener.local_addr().ok_or("listener address")?;
+    let handle = listener
+        .start(handler)
+        .map_err(|error| format!("start: {error}"))?;
+
+    let clients: Vec<_> = (0..CLIENTS)
+        .map(|_| thread::spawn(move || run_client(addr)))
+        .collect();
+    for client in clients {
+        let messages = client
+            .join()
+            .map_err(|error| format!("client join: {error:?}"))??;
+        let exits: Vec<_> = messages
+            .iter()
+            .filter(|message| message["kind"] == "exit")
+            .collect();
+        assert_eq!(exits, [&serde_json::json!({"kind": "exit", "status": 1})]);
+        assert_eq!(messages.last(), Some(exits[0]), "exit must end the stream");
+    }
+
+    handle.shutdown();
+    handle
+        .join()
+        .map_err(|error| format!("join listener: {error}"))
+}
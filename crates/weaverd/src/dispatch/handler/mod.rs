@@ -5,13 +5,16 @@
 //! parses them into typed commands, routes them to domain handlers, and streams
 //! responses back to the client.
 
-use std::path::PathBuf;
+use std::{net::Shutdown, path::PathBuf};
+
+use weaver_plugins::process::SandboxExecutor;
 
 use super::{
     backend_manager::BackendManager,
+    cancellation::{BEFORE_ROUTING, ClientWatch},
     errors::DispatchError,
     request::CommandRequest,
-    response::ResponseWriter,
+    response::{ProgressSink, ResponseWriter},
     router::{DISPATCH_TARGET, DomainRouter},
 };
 use crate::transport::{ConnectionHandler, ConnectionStream};
@@ -42,7 +45,18 @@ enum ReadRequestError {
 ///
 /// Each connection is handled synchronously: the handler reads a single JSONL
 /// request line, parses it, routes it to domain handlers, and writes the
-/// response stream before closing the connection.
+/// response stream before closing the connection. Domain handlers write into a
+/// buffer, but may send progress messages ahead of it through the writer's
+/// [`ProgressSink`].
+///
+/// While the request runs, a [`ClientWatch`] reads the rest of the connection
+/// so a client that sends a cancel message or disconnects stops the request
+/// at its next checkpoint. A cancelled request receives no response.
+///
+/// The socket listener may run the handler for several connections at once on
+/// its worker pool. Request parsing and response writing proceed in parallel,
+/// but routing holds the [`BackendManager`] lock, so commands that touch the
+/// shared backends execute one at a time in the order they acquire it.
 #[derive(Debug)]
 pub struct DispatchConnectionHandler {
     router: DomainRouter,
@@ -58,9 +72,26 @@ impl DispatchConnectionHandler {
         workspace_root: PathBuf,
         endpoint: impl Into<String>,
         runtime_dir: PathBuf,
+    ) -> Result<Self, DispatchError> {
+        Self::with_plugin_executor(
+            backends,
+            workspace_root,
+            endpoint,
+            runtime_dir,
+            SandboxExecutor::default(),
+        )
+    }
+
+    /// Creates a dispatch handler whose plugin runtimes use `executor`.
+    pub fn with_plugin_executor(
+        backends: BackendManager,
+        workspace_root: PathBuf,
+        endpoint: impl Into<String>,
+        runtime_dir: PathBuf,
+        executor: SandboxExecutor,
     ) -> Result<Self, DispatchError> {
         Ok(Self {
-            router: DomainRouter::new(workspace_root)?,
+            router: DomainRouter::with_plugin_executor(workspace_root, executor)?,
             backends,
             endpoint: endpoint.into(),
             runtime_dir,
@@ -85,7 +116,11 @@ impl DispatchConnectionHandler {
                 return;
             }
         };
-        let mut writer = ResponseWriter::new(&mut stream);
+        let progress = self.progress_sink(&stream);
+        let watch = ClientWatch::start(&stream, &self.endpoint);
+        let mut writer = ResponseWriter::new(&mut stream)
+            .with_progress(progress)
+            .with_cancellation(watch.token());
 
         let event = StructuredDispatchEvent::new(
             "dispatching_request",
@@ -97,6 +132,43 @@ impl DispatchConnectionHandler {
         emit_structured_event(&event, "dispatching request", false);
 
         self.route_request(request, request_bytes.len(), &mut writer);
+        drop(watch);
+        self.finish_connection(&stream);
+    }
+
+    /// Signals end-of-file to the client once the response is complete.
+    ///
+    /// The cancellation watcher holds a clone of the socket, so dropping the
+    /// handler's stream alone would leave the connection open until the
+    /// watcher's next read returns.
+    fn finish_connection(&self, stream: &ConnectionStream) {
+        if let Err(error) = stream.shutdown(Shutdown::Write) {
+            tracing::debug!(
+                target: DISPATCH_TARGET,
+                endpoint = %self.endpoint,
+                %error,
+                "failed to shut down connection after response"
+            );
+        }
+    }
+
+    /// Returns a sink that writes progress messages to a clone of `stream`.
+    ///
+    /// Progress is best effort, so a socket that cannot be cloned only loses
+    /// progress reporting rather than failing the request.
+    fn progress_sink(&self, stream: &ConnectionStream) -> Option<ProgressSink> {
+        match stream.try_clone() {
+            Ok(clone) => Some(ProgressSink::new(clone)),
+            Err(error) => {
+                tracing::debug!(
+                    target: DISPATCH_TARGET,
+                    endpoint = %self.endpoint,
+                    %error,
+                    "progress reporting unavailable for connection"
+                );
+                None
+            }
+        }
     }
 
     fn receive_request(
@@ -158,13 +230,23 @@ impl DispatchConnectionHandler {
         writer: &mut ResponseWriter<W>,
     ) {
         let mut response = Vec::new();
+        let progress = writer.progress().cloned();
+        let cancellation = writer.cancellation().clone();
         let route_result = self.backends.with_backends(|backends| {
-            let mut buffered_writer = ResponseWriter::new(&mut response);
-            self.router.route(&request, &mut buffered_writer, backends)
+            let mut buffered_writer = ResponseWriter::new(&mut response)
+                .with_progress(progress)
+                .with_cancellation(cancellation);
+            buffered_writer
+                .check_cancelled(BEFORE_ROUTING)
+                .and_then(|()| self.router.route(&request, &mut buffered_writer, backends))
         });
         let context = Self::request_context(&request, request_size);
 
         match route_result {
+            Ok(Err(DispatchError::Cancelled { stage })) => self.log_cancelled(&context, stage),
+            Ok(Ok(_)) if writer.cancellation().is_cancelled() => {
+                self.log_cancelled(&context, "after routing");
+            }
             Ok(Ok(result)) => {
                 if self.write_buffered_response(&context, writer, &response) {
                     self.write_exit_status(&context, result.status, writer);
@@ -191,6 +273,17 @@ impl DispatchConnectionHandler {
         }
     }
 
+    /// Records that the client abandoned the request; nothing is written
+    /// because the client is no longer waiting for a response.
+    fn log_cancelled(&self, context: &RouteContext<'_>, stage: &'static str) {
+        emit_structured_event(
+            &self.with_metadata(context, "request_cancelled"),
+            "request cancelled by client",
+            false,
+        );
+        tracing::info!(target: DISPATCH_TARGET, stage, "request cancelled by client");
+    }
+
     fn write_buffered_response<W: std::io::Write>(
         &self,
         context: &RouteContext<'_>,
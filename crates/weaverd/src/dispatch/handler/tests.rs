@@ -9,6 +9,10 @@ use std::{
 use rstest::rstest;
 use weaver_daemon_types::JSONL_REQUEST_MAX_LINE_BYTES;
 
+#[path = "cancellation_tests.rs"]
+mod cancellation_tests;
+#[path = "concurrency_tests.rs"]
+mod concurrency_tests;
 #[path = "read_error_event_tests.rs"]
 mod read_error_event_tests;
 #[path = "receive_request_tests.rs"]
@@ -82,11 +86,10 @@ fn handler_responds_to_not_implemented_operation(
     harness: Result<HandlerTestHarness, String>,
 ) -> Result<(), String> {
     let mut harness = harness?;
-    let lines = harness.send_and_collect(
-        b"{\"command\":{\"domain\":\"observe\",\"operation\":\"find-references\"}}\n",
-    )?;
+    let lines = harness
+        .send_and_collect(b"{\"command\":{\"domain\":\"observe\",\"operation\":\"grep\"}}\n")?;
 
-    // find-references is not yet implemented.
+    // grep is not yet implemented.
     assert!(lines.iter().any(|l| l.contains("not yet implemented")));
     assert!(lines.iter().any(|l| l.contains(r#""kind":"exit""#)));
 
@@ -120,7 +123,9 @@ fn handler_emits_known_operations_for_unknown_operation(
             "diagnostics",
             "call-hierarchy",
             "get-card",
-            "graph-slice"
+            "graph-slice",
+            "analyze",
+            "list-plugins"
         ])
     );
     assert!(lines.iter().any(|line| line.contains(r#""status":1"#)));
@@ -21,6 +21,11 @@
 //! {"kind":"exit","status":1}
 //! ```
 //!
+//! After the request line a client may send `{"kind":"cancel"}` to abandon
+//! the request; closing the connection has the same effect. Cancellation is
+//! best effort and takes hold at the next stage boundary (see
+//! `cancellation`).
+//!
 //! ## Domain Routing
 //!
 //! Requests are routed by domain (`observe`, `act`, `verify`) and then by
@@ -29,17 +34,21 @@
 
 pub mod act;
 mod backend_manager;
+mod cancellation;
 mod errors;
 mod filesystem;
 mod handler;
 pub mod observe;
+mod plugin_health;
 mod request;
 mod response;
 mod router;
+pub mod verify;
 
 #[doc(hidden)]
 pub use self::backend_manager::BackendManager;
 #[doc(hidden)]
 pub use self::handler::DispatchConnectionHandler;
+pub(crate) use self::plugin_health::check_plugin_health;
 #[cfg(test)]
 pub(crate) use self::response::{UNKNOWN_OPERATION_TYPE, parse_stderr_json_payload};
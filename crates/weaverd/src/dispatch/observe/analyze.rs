@@ -0,0 +1,278 @@
+//! Handler for the `observe analyze` operation.
+//!
+//! Runs a sensor plugin against a single workspace file and returns the
+//! plugin's analysis data to the client unchanged. Sensors never modify the
+//! workspace, so their output bypasses the Double-Lock harness; the plugin
+//! runner rejects any sensor that returns a diff instead of analysis data.
+
+use std::{collections::HashMap, io::Write, path::Path, sync::Arc};
+
+use tracing::debug;
+use weaver_plugins::{
+    PluginError,
+    PluginKind,
+    PluginOutput,
+    PluginRegistry,
+    PluginRequest,
+    PluginResponse,
+    process::SandboxExecutor,
+    protocol::FilePayload,
+    runner::PluginRunner,
+};
+
+use crate::dispatch::{
+    act::{
+        refactor::daemon_plugin_registry,
+        target_file::{canonical_workspace_root, load_file_contents, resolve_file},
+    },
+    cancellation::{AFTER_PLUGIN_EXECUTION, BEFORE_PLUGIN_EXECUTION},
+    errors::DispatchError,
+    request::CommandRequest,
+    response::ResponseWriter,
+    router::{DISPATCH_TARGET, DispatchResult},
+};
+
+/// Operation name sent to sensor plugins in the request envelope.
+const ANALYZE_OPERATION: &str = "analyze";
+
+/// Runtime abstraction for executing sensor plugins.
+pub(crate) trait AnalysisRuntime {
+    /// Executes the named sensor with the provided request.
+    fn execute(
+        &self,
+        provider: &str,
+        request: &PluginRequest,
+    ) -> Result<PluginResponse, PluginError>;
+}
+
+/// Sandbox-backed runtime that executes sensors from a registry.
+pub(crate) struct SandboxAnalysisRuntime {
+    runner: PluginRunner<SandboxExecutor>,
+}
+
+impl SandboxAnalysisRuntime {
+    /// Builds the runtime from every sensor in `registry`.
+    ///
+    /// Actuators are skipped so `observe analyze` cannot run a plugin that is
+    /// declared to modify the workspace.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a sensor manifest fails registration.
+    pub fn from_registry(
+        registry: &PluginRegistry,
+        executor: SandboxExecutor,
+    ) -> Result<Self, PluginError> {
+        let mut sensors = PluginRegistry::new();
+        for manifest in registry.find_by_kind(PluginKind::Sensor) {
+            sensors.register(manifest.clone())?;
+        }
+        Ok(Self {
+            runner: PluginRunner::new(sensors, executor),
+        })
+    }
+}
+
+impl AnalysisRuntime for SandboxAnalysisRuntime {
+    fn execute(
+        &self,
+        provider: &str,
+        request: &PluginRequest,
+    ) -> Result<PluginResponse, PluginError> {
+        self.runner.execute(provider, request)
+    }
+}
+
+/// Runtime that reports an initialization error on every execution attempt.
+struct NoopAnalysisRuntime {
+    message: String,
+}
+
+impl AnalysisRuntime for NoopAnalysisRuntime {
+    fn execute(
+        &self,
+        _provider: &str,
+        _request: &PluginRequest,
+    ) -> Result<PluginResponse, PluginError> {
+        Err(PluginError::Manifest {
+            message: self.message.clone(),
+        })
+    }
+}
+
+/// Constructs the default analysis runtime for daemon dispatch.
+#[must_use]
+pub(crate) fn default_runtime(
+    workspace_root: &Path,
+    executor: SandboxExecutor,
+) -> Arc<dyn AnalysisRuntime + Send + Sync> {
+    let runtime = daemon_plugin_registry(workspace_root)
+        .and_then(|registry| SandboxAnalysisRuntime::from_registry(&registry, executor));
+    match runtime {
+        Ok(runtime) => Arc::new(runtime),
+        Err(error) => Arc::new(NoopAnalysisRuntime {
+            message: format!("failed to initialize analysis runtime: {error}"),
+        }),
+    }
+}
+
+/// Parsed arguments for `observe analyze`.
+///
+/// ```text
+/// weaver observe analyze --provider <SENSOR> --file <PATH> [KEY=VALUE...]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AnalyzeArgs {
+    provider: String,
+    file: String,
+    extra: HashMap<String, serde_json::Value>,
+}
+
+impl AnalyzeArgs {
+    fn parse(arguments: &[String]) -> Result<Self, DispatchError> {
+        let mut provider = None;
+        let mut file = None;
+        let mut extra = HashMap::new();
+
+        let mut iter = arguments.iter();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--provider" => provider = Some(require_value(&mut iter, "--provider")?),
+                "--file" => file = Some(require_value(&mut iter, "--file")?),
+                other => {
+                    let (key, value) = parse_extra(other)?;
+                    extra.insert(key, value);
+                }
+            }
+        }
+
+        let (Some(provider), Some(file)) = (provider, file) else {
+            return Err(DispatchError::invalid_arguments(
+                "observe analyze requires --provider <SENSOR> and --file <PATH>",
+            ));
+        };
+        Ok(Self {
+            provider,
+            file,
+            extra,
+        })
+    }
+}
+
+fn require_value<'a>(
+    iter: &mut impl Iterator<Item = &'a String>,
+    flag: &str,
+) -> Result<String, DispatchError> {
+    iter.next()
+        .cloned()
+        .ok_or_else(|| DispatchError::invalid_arguments(format!("{flag} requires a value")))
+}
+
+/// Parses a `KEY=VALUE` sensor argument into a string-valued entry.
+fn parse_extra(arg: &str) -> Result<(String, serde_json::Value), DispatchError> {
+    let Some((key, value)) = arg.split_once('=') else {
+        return Err(DispatchError::invalid_arguments(format!(
+            "unknown argument: {arg}"
+        )));
+    };
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(DispatchError::invalid_arguments(format!(
+            "analyze argument has an empty key: '{arg}'"
+        )));
+    }
+    Ok((key.to_owned(), serde_json::Value::String(value.to_owned())))
+}
+
+/// Handles `observe analyze` requests.
+///
+/// Expects `--provider <sensor>` and `--file <path>`; any `KEY=VALUE`
+/// arguments are forwarded to the sensor as string arguments. Successful
+/// analysis data is written to stdout exactly as the sensor produced it.
+///
+/// # Errors
+///
+/// Returns [`DispatchError::InvalidArguments`] if the arguments are malformed
+/// or the file cannot be resolved inside the workspace.
+pub(crate) fn handle<W: Write>(
+    request: &CommandRequest,
+    writer: &mut ResponseWriter<W>,
+    workspace_root: &Path,
+    runtime: &dyn AnalysisRuntime,
+) -> Result<DispatchResult, DispatchError> {
+    let args = AnalyzeArgs::parse(&request.arguments)?;
+    debug!(
+        target: DISPATCH_TARGET,
+        provider = args.provider,
+        file = args.file,
+        "handling observe analyze"
+    );
+
+    let canonical_workspace = canonical_workspace_root(workspace_root)?;
+    let resolved_file = resolve_file(&canonical_workspace, &args.file)?;
+    let content = load_file_contents(&resolved_file.path)?;
+    let plugin_request = PluginRequest::with_arguments(
+        ANALYZE_OPERATION,
+        vec![FilePayload::new(resolved_file.relative_path, content)],
+        args.extra.clone(),
+    );
+
+    writer.check_cancelled(BEFORE_PLUGIN_EXECUTION)?;
+    let outcome = runtime.execute(&args.provider, &plugin_request);
+    writer.check_cancelled(AFTER_PLUGIN_EXECUTION)?;
+    match outcome {
+        Ok(response) => write_analysis(&response, &args, writer),
+        Err(error) => write_failure(&error, &args, writer),
+    }
+}
+
+fn write_analysis<W: Write>(
+    response: &PluginResponse,
+    args: &AnalyzeArgs,
+    writer: &mut ResponseWriter<W>,
+) -> Result<DispatchResult, DispatchError> {
+    if !response.is_success() {
+        let diagnostics: Vec<&str> = response
+            .diagnostics()
+            .iter()
+            .map(|diagnostic| diagnostic.message())
+            .collect();
+        let message = if diagnostics.is_empty() {
+            String::from("plugin reported failure without diagnostics")
+        } else {
+            diagnostics.join("; ")
+        };
+        return write_failure(&message, args, writer);
+    }
+    for diagnostic in response.diagnostics() {
+        writer.write_stderr(format!("observe analyze {diagnostic}\n"))?;
+    }
+
+    match response.output() {
+        PluginOutput::Analysis { data } => {
+            writer.write_stdout(serde_json::to_string(data)?)?;
+            Ok(DispatchResult::success())
+        }
+        PluginOutput::Diff { .. } | PluginOutput::Empty => write_failure(
+            &"plugin succeeded but did not return analysis output",
+            args,
+            writer,
+        ),
+    }
+}
+
+fn write_failure<W: Write>(
+    error: &dyn std::fmt::Display,
+    args: &AnalyzeArgs,
+    writer: &mut ResponseWriter<W>,
+) -> Result<DispatchResult, DispatchError> {
+    writer.write_stderr(format!(
+        "observe analyze failed: {error} (provider={}, file={})\n",
+        args.provider, args.file
+    ))?;
+    Ok(DispatchResult::with_status(1))
+}
+
+#[cfg(test)]
+#[path = "analyze_tests.rs"]
+mod tests;
@@ -0,0 +1,254 @@
+//! Unit tests for `observe::analyze`.
+
+use std::{path::PathBuf, sync::Mutex};
+
+use rstest::rstest;
+use tempfile::TempDir;
+use weaver_plugins::{
+    DiagnosticSeverity,
+    PluginDiagnostic,
+    PluginManifest,
+    PluginMetadata,
+    PluginRegistry,
+};
+
+use super::*;
+use crate::{
+    dispatch::request::CommandDescriptor,
+    tests::support::{fs as test_fs, stream::stdout_payload},
+};
+
+/// Runtime double that records the request and returns a fixed outcome.
+struct RecordingRuntime {
+    outcome: fn() -> Result<PluginResponse, PluginError>,
+    calls: Mutex<Vec<(String, PluginRequest)>>,
+}
+
+impl RecordingRuntime {
+    fn new(outcome: fn() -> Result<PluginResponse, PluginError>) -> Self {
+        Self {
+            outcome,
+            calls: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn calls(&self) -> Vec<(String, PluginRequest)> {
+        self.calls.lock().expect("calls lock").clone()
+    }
+}
+
+impl AnalysisRuntime for RecordingRuntime {
+    fn execute(
+        &self,
+        provider: &str,
+        request: &PluginRequest,
+    ) -> Result<PluginResponse, PluginError> {
+        self.calls
+            .lock()
+            .expect("calls lock")
+            .push((provider.to_owned(), request.clone()));
+        (self.outcome)()
+    }
+}
+
+fn analysis_data() -> serde_json::Value {
+    serde_json::json!({
+        "symbols": [{"name": "weave", "kind": "function", "line": 1}],
+        "metrics": {"complexity": 2.5}
+    })
+}
+
+fn analysis_outcome() -> Result<PluginResponse, PluginError> {
+    Ok(PluginResponse::success(PluginOutput::Analysis {
+        data: analysis_data(),
+    }))
+}
+
+fn analyze_request(arguments: &[&str]) -> CommandRequest {
+    CommandRequest {
+        command: CommandDescriptor {
+            domain: String::from("observe"),
+            operation: String::from("analyze"),
+        },
+        arguments: arguments
+            .iter()
+            .map(|&argument| argument.to_owned())
+            .collect(),
+        patch: None,
+    }
+}
+
+fn workspace() -> TempDir {
+    let workspace = TempDir::new().expect("workspace");
+    test_fs::write(
+        workspace.path().join("weave.py"),
+        "def weave():\n    pass\n",
+    )
+    .expect("write source");
+    workspace
+}
+
+fn run(
+    runtime: &dyn AnalysisRuntime,
+    arguments: &[&str],
+) -> (Result<DispatchResult, DispatchError>, String) {
+    let workspace = workspace();
+    let mut output = Vec::new();
+    let mut writer = ResponseWriter::new(&mut output);
+    let result = handle(
+        &analyze_request(arguments),
+        &mut writer,
+        workspace.path(),
+        runtime,
+    );
+    (result, String::from_utf8(output).expect("utf-8 output"))
+}
+
+#[test]
+fn forwards_analysis_data_unchanged() {
+    let runtime = RecordingRuntime::new(analysis_outcome);
+
+    let (result, output) = run(
+        &runtime,
+        &["--provider", "jedi", "--file", "weave.py", "depth=2"],
+    );
+
+    assert_eq!(result.expect("dispatch").status, 0);
+    assert_eq!(stdout_payload(&output), analysis_data());
+
+    let calls = runtime.calls();
+    let [(provider, request)] = calls.as_slice() else {
+        panic!("expected one execution, got {}", calls.len());
+    };
+    assert_eq!(provider, "jedi");
+    assert_eq!(request.operation(), "analyze");
+    assert_eq!(
+        request.arguments().get("depth"),
+        Some(&serde_json::json!("2"))
+    );
+    let [file] = request.files() else {
+        panic!("expected one file payload");
+    };
+    assert_eq!(file.path(), Path::new("weave.py"));
+    assert_eq!(file.content(), "def weave():\n    pass\n");
+}
+
+#[rstest]
+#[case::diff(|| Ok(PluginResponse::success(PluginOutput::Diff { content: String::from("--- a\n") })))]
+#[case::empty(|| Ok(PluginResponse::success(PluginOutput::Empty)))]
+fn rejects_success_without_analysis(#[case] outcome: fn() -> Result<PluginResponse, PluginError>) {
+    let runtime = RecordingRuntime::new(outcome);
+
+    let (result, output) = run(&runtime, &["--provider", "jedi", "--file", "weave.py"]);
+
+    assert_eq!(result.expect("dispatch").status, 1);
+    assert!(
+        output.contains(
+            "observe analyze failed: plugin succeeded but did not return analysis output \
+             (provider=jedi, file=weave.py)"
+        ),
+        "unexpected output: {output}"
+    );
+}
+
+#[test]
+fn forwards_sensor_warnings_without_failing() {
+    let runtime = RecordingRuntime::new(|| {
+        Ok(PluginResponse::success(PluginOutput::Analysis {
+            data: analysis_data(),
+        })
+        .with_diagnostics(vec![
+            PluginDiagnostic::new(DiagnosticSeverity::Warning, "type stubs missing")
+                .with_file(PathBuf::from("weave.py"))
+                .with_line(1),
+        ]))
+    });
+
+    let (result, output) = run(&runtime, &["--provider", "jedi", "--file", "weave.py"]);
+
+    assert_eq!(result.expect("dispatch").status, 0);
+    assert!(
+        output.contains("observe analyze warning: type stubs missing (weave.py:1)"),
+        "unexpected output: {output}"
+    );
+    assert_eq!(stdout_payload(&output), analysis_data());
+}
+
+#[test]
+fn reports_sensor_failure_diagnostics() {
+    let runtime = RecordingRuntime::new(|| {
+        Ok(PluginResponse::failure(vec![PluginDiagnostic::new(
+            DiagnosticSeverity::Error,
+            "syntax error on line 3",
+        )]))
+    });
+
+    let (result, output) = run(&runtime, &["--provider", "jedi", "--file", "weave.py"]);
+
+    assert_eq!(result.expect("dispatch").status, 1);
+    assert!(
+        output.contains("observe analyze failed: syntax error on line 3"),
+        "unexpected output: {output}"
+    );
+}
+
+#[test]
+fn reports_runner_errors() {
+    let runtime = RecordingRuntime::new(|| {
+        Err(PluginError::OutputKindMismatch {
+            name: String::from("jedi"),
+            kind: PluginKind::Sensor,
+            output: "diff",
+        })
+    });
+
+    let (result, output) = run(&runtime, &["--provider", "jedi", "--file", "weave.py"]);
+
+    assert_eq!(result.expect("dispatch").status, 1);
+    assert!(
+        output.contains("sensor plugin 'jedi' returned diff output, which sensors cannot produce"),
+        "unexpected output: {output}"
+    );
+}
+
+#[rstest]
+#[case::missing_provider(&["--file", "weave.py"])]
+#[case::missing_file(&["--provider", "jedi"])]
+#[case::missing_value(&["--provider"])]
+#[case::unknown_flag(&["--provider", "jedi", "--file", "weave.py", "--bogus"])]
+#[case::empty_key(&["--provider", "jedi", "--file", "weave.py", "=2"])]
+#[case::escaping_file(&["--provider", "jedi", "--file", "../weave.py"])]
+fn rejects_invalid_arguments_before_execution(#[case] arguments: &[&str]) {
+    let runtime = RecordingRuntime::new(analysis_outcome);
+
+    let (result, _output) = run(&runtime, arguments);
+
+    assert!(
+        matches!(result, Err(DispatchError::InvalidArguments { .. })),
+        "expected InvalidArguments"
+    );
+    assert!(runtime.calls().is_empty());
+}
+
+#[test]
+fn sandbox_runtime_excludes_actuators() {
+    let mut registry = PluginRegistry::new();
+    registry
+        .register(PluginManifest::new(
+            PluginMetadata::new("rope", "1.0", PluginKind::Actuator),
+            vec![String::from("python")],
+            PathBuf::from("/usr/bin/rope"),
+        ))
+        .expect("register rope");
+    let runtime = SandboxAnalysisRuntime::from_registry(&registry, SandboxExecutor::default())
+        .expect("runtime");
+
+    let error = runtime
+        .execute("rope", &PluginRequest::new(ANALYZE_OPERATION, Vec::new()))
+        .expect_err("actuators are not analysis providers");
+
+    assert!(
+        matches!(error, PluginError::NotFound { ref name } if name == "rope"),
+        "unexpected error: {error}"
+    );
+}
@@ -4,14 +4,19 @@
 //! parsing CLI arguments from the `CommandRequest::arguments` vector into
 //! strongly-typed values suitable for calling backend services.
 
+use std::path::Path;
+
 use lsp_types::{
     GotoDefinitionParams,
     Position,
+    ReferenceContext,
+    ReferenceParams,
     TextDocumentIdentifier,
     TextDocumentPositionParams,
     Uri,
 };
 use weaver_lsp_host::Language;
+use weaver_syntax::SupportedLanguage;
 
 use crate::dispatch::errors::DispatchError;
 
@@ -21,6 +26,7 @@ use crate::dispatch::errors::DispatchError;
 ///
 /// ```text
 /// weaver observe get-definition --uri file:///src/main.rs --position 10:5
+/// weaver observe get-definition --uri file:///src/main.rs --line 10 --column 5
 /// ```
 #[derive(Debug, Clone)]
 pub struct GetDefinitionArgs {
@@ -35,8 +41,8 @@ pub struct GetDefinitionArgs {
 impl GetDefinitionArgs {
     /// Parses arguments from a CLI argument list.
     ///
-    /// Expects `--uri <URI> --position <LINE:COL>` format. Arguments can appear
-    /// in any order. Both flags are required.
+    /// Expects `--uri <URI>` with either `--position <LINE:COL>` or both
+    /// `--line <LINE>` and `--column <COL>`. Arguments can appear in any order.
     ///
     /// # Errors
     ///
@@ -45,6 +51,8 @@ impl GetDefinitionArgs {
     pub fn parse(arguments: &[String]) -> Result<Self, DispatchError> {
         let mut uri: Option<Uri> = None;
         let mut position: Option<(u32, u32)> = None;
+        let mut line: Option<u32> = None;
+        let mut column: Option<u32> = None;
 
         let mut iter = arguments.iter().peekable();
         while let Some(arg) = iter.next() {
@@ -57,6 +65,14 @@ impl GetDefinitionArgs {
                     let value = require_arg_value(&mut iter, "--position")?;
                     position = Some(parse_position(value)?);
                 }
+                "--line" => {
+                    let value = require_arg_value(&mut iter, "--line")?;
+                    line = Some(parse_coordinate(value, "line")?);
+                }
+                "--column" => {
+                    let value = require_arg_value(&mut iter, "--column")?;
+                    column = Some(parse_coordinate(value, "column")?);
+                }
                 other => {
                     return Err(DispatchError::invalid_arguments(format!(
                         "unknown argument: {other}"
@@ -66,8 +82,7 @@ impl GetDefinitionArgs {
         }
 
         let uri = uri.ok_or_else(|| DispatchError::invalid_arguments("missing required --uri"))?;
-        let (line, column) = position
-            .ok_or_else(|| DispatchError::invalid_arguments("missing required --position"))?;
+        let (line, column) = resolve_position(position, line, column)?;
 
         Ok(Self { uri, line, column })
     }
@@ -77,20 +92,7 @@ impl GetDefinitionArgs {
     /// # Errors
     ///
     /// Returns `UnsupportedLanguage` if the file extension is not recognized.
-    pub fn language(&self) -> Result<Language, DispatchError> {
-        let path = self.uri.path().as_str();
-        let extension = path
-            .rsplit_once('.')
-            .and_then(|(_, ext)| if ext.is_empty() { None } else { Some(ext) })
-            .ok_or_else(|| DispatchError::unsupported_language("(no extension)"))?;
-
-        match extension.to_ascii_lowercase().as_str() {
-            "rs" => Ok(Language::Rust),
-            "py" => Ok(Language::Python),
-            "ts" | "tsx" => Ok(Language::TypeScript),
-            other => Err(DispatchError::unsupported_language(other)),
-        }
-    }
+    pub fn language(&self) -> Result<Language, DispatchError> { language_for_uri(&self.uri) }
 
     /// Converts to LSP `GotoDefinitionParams`.
     ///
@@ -112,6 +114,84 @@ impl GetDefinitionArgs {
     }
 }
 
+/// Parsed arguments for the `find-references` operation.
+///
+/// Accepts the same `--uri` and position flags as [`GetDefinitionArgs`],
+/// plus an optional `--include-declaration` switch.
+///
+/// # Example
+///
+/// ```text
+/// weaver observe find-references --uri file:///src/main.rs --position 10:5 --include-declaration
+/// ```
+#[derive(Debug, Clone)]
+pub struct FindReferencesArgs {
+    /// The symbol position to search from.
+    pub target: GetDefinitionArgs,
+    /// Whether the declaration itself is reported as a reference.
+    pub include_declaration: bool,
+}
+
+impl FindReferencesArgs {
+    /// Parses arguments from a CLI argument list.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidArguments` under the same conditions as
+    /// [`GetDefinitionArgs::parse`].
+    pub fn parse(arguments: &[String]) -> Result<Self, DispatchError> {
+        let (switches, rest): (Vec<String>, Vec<String>) = arguments
+            .iter()
+            .cloned()
+            .partition(|argument| argument == "--include-declaration");
+        Ok(Self {
+            target: GetDefinitionArgs::parse(&rest)?,
+            include_declaration: !switches.is_empty(),
+        })
+    }
+
+    /// Converts to LSP `ReferenceParams`, using the same 0-indexed position
+    /// as [`GetDefinitionArgs::into_params`].
+    #[must_use]
+    pub fn into_params(self) -> ReferenceParams {
+        let definition = self.target.into_params();
+        ReferenceParams {
+            text_document_position: definition.text_document_position_params,
+            work_done_progress_params: definition.work_done_progress_params,
+            partial_result_params: definition.partial_result_params,
+            context: ReferenceContext {
+                include_declaration: self.include_declaration,
+            },
+        }
+    }
+}
+
+/// Infers the language from a document URI's file extension.
+///
+/// # Errors
+///
+/// Returns `UnsupportedLanguage` if the file extension is not recognized.
+pub fn language_for_uri(uri: &Uri) -> Result<Language, DispatchError> {
+    let path = Path::new(uri.path().as_str());
+    lsp_language_for_path(path).ok_or_else(|| {
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+            .filter(|ext| !ext.is_empty())
+            .unwrap_or_else(|| String::from("(no extension)"));
+        DispatchError::unsupported_language(extension)
+    })
+}
+
+/// Infers the language server language for `path`.
+///
+/// Extensions are resolved through [`SupportedLanguage::from_path`], so the
+/// extension table lives in `weaver-syntax`. Returns `None` for unrecognised
+/// extensions and for languages without a language server.
+pub(crate) fn lsp_language_for_path(path: &Path) -> Option<Language> {
+    SupportedLanguage::from_path(path)?.as_str().parse().ok()
+}
+
 /// Extracts the next argument value or returns an error.
 fn require_arg_value<'a, I>(iter: &mut I, flag: &str) -> Result<&'a str, DispatchError>
 where
@@ -138,23 +218,45 @@ fn parse_position(value: &str) -> Result<(u32, u32), DispatchError> {
         )));
     }
 
-    let line: u32 = parts[0].parse().map_err(|_| {
-        DispatchError::invalid_arguments(format!("invalid line number: {}", parts[0]))
-    })?;
-    let column: u32 = parts[1].parse().map_err(|_| {
-        DispatchError::invalid_arguments(format!("invalid column number: {}", parts[1]))
-    })?;
+    let line = parse_coordinate(parts[0], "line")?;
+    let column = parse_coordinate(parts[1], "column")?;
+    Ok((line, column))
+}
 
-    if line == 0 {
-        return Err(DispatchError::invalid_arguments("line number must be >= 1"));
-    }
-    if column == 0 {
-        return Err(DispatchError::invalid_arguments(
-            "column number must be >= 1",
-        ));
+/// Parses a 1-indexed line or column number.
+fn parse_coordinate(value: &str, name: &str) -> Result<u32, DispatchError> {
+    let number: u32 = value
+        .parse()
+        .map_err(|_| DispatchError::invalid_arguments(format!("invalid {name} number: {value}")))?;
+    if number == 0 {
+        return Err(DispatchError::invalid_arguments(format!(
+            "{name} number must be >= 1"
+        )));
     }
+    Ok(number)
+}
 
-    Ok((line, column))
+/// Combines `--position` with the separate `--line` and `--column` flags,
+/// which are mutually exclusive.
+fn resolve_position(
+    position: Option<(u32, u32)>,
+    line: Option<u32>,
+    column: Option<u32>,
+) -> Result<(u32, u32), DispatchError> {
+    match (position, line, column) {
+        (Some(position), None, None) => Ok(position),
+        (None, Some(line), Some(column)) => Ok((line, column)),
+        (Some(_), ..) => Err(DispatchError::invalid_arguments(
+            "use either --position or --line and --column, not both",
+        )),
+        (None, Some(_), None) => Err(DispatchError::invalid_arguments(
+            "missing required --column",
+        )),
+        (None, None, Some(_)) => Err(DispatchError::invalid_arguments("missing required --line")),
+        (None, None, None) => Err(DispatchError::invalid_arguments(
+            "missing required --position (or --line and --column)",
+        )),
+    }
 }
 
 #[cfg(test)]
@@ -210,16 +312,31 @@ mod tests {
     #[case::malformed_position(&["--uri", "file:///main.rs", "--position", "10"], "LINE:COL")]
     #[case::zero_line(&["--uri", "file:///main.rs", "--position", "0:5"], "line")]
     #[case::unknown_argument(&["--uri", "file:///main.rs", "--position", "10:5", "--unknown"], "unknown")]
+    #[case::missing_column(&["--uri", "file:///main.rs", "--line", "10"], "--column")]
+    #[case::missing_line(&["--uri", "file:///main.rs", "--column", "5"], "--line")]
+    #[case::zero_column(&["--uri", "file:///main.rs", "--line", "1", "--column", "0"], "column")]
+    #[case::mixed_position_forms(&["--uri", "file:///main.rs", "--position", "1:1", "--line", "1"], "not both")]
     fn rejects_invalid_arguments(#[case] arg_list: &[&str], #[case] expected_substring: &str) {
         assert_invalid_arguments(arg_list, expected_substring);
     }
 
+    #[test]
+    fn parses_separate_line_and_column() {
+        let arguments = args(&["--column", "5", "--uri", "file:///main.rs", "--line", "10"]);
+        let parsed = GetDefinitionArgs::parse(&arguments).expect("should parse");
+
+        assert_eq!(parsed.line, 10);
+        assert_eq!(parsed.column, 5);
+    }
+
     #[rstest]
     #[case("file:///main.rs", Language::Rust)]
     #[case("file:///lib.rs", Language::Rust)]
     #[case("file:///script.py", Language::Python)]
     #[case("file:///app.ts", Language::TypeScript)]
     #[case("file:///component.tsx", Language::TypeScript)]
+    #[case("file:///stubs.pyi", Language::Python)]
+    #[case("file:///MAIN.RS", Language::Rust)]
     fn infers_language_from_extension(#[case] uri: &str, #[case] expected: Language) {
         let arguments = args(&["--uri", uri, "--position", "1:1"]);
         let parsed = GetDefinitionArgs::parse(&arguments).expect("should parse");
@@ -227,9 +344,11 @@ mod tests {
         assert_eq!(language, expected);
     }
 
-    #[test]
-    fn rejects_unsupported_extension() {
-        let arguments = args(&["--uri", "file:///main.xyz", "--position", "1:1"]);
+    #[rstest]
+    #[case::unknown("file:///main.xyz")]
+    #[case::no_language_server("file:///app.js")]
+    fn rejects_unsupported_extension(#[case] uri: &str) {
+        let arguments = args(&["--uri", uri, "--position", "1:1"]);
         let parsed = GetDefinitionArgs::parse(&arguments).expect("should parse");
         let error = parsed.language().expect_err("should fail");
 
@@ -246,4 +365,19 @@ mod tests {
         assert_eq!(params.text_document_position_params.position.line, 9);
         assert_eq!(params.text_document_position_params.position.character, 4);
     }
+
+    #[rstest]
+    #[case::excluded(&["--uri", "file:///main.rs", "--position", "10:5"], false)]
+    #[case::included(&["--include-declaration", "--uri", "file:///main.rs", "--line", "10", "--column", "5"], true)]
+    fn find_references_args_control_include_declaration(
+        #[case] arg_list: &[&str],
+        #[case] expected: bool,
+    ) {
+        let parsed = FindReferencesArgs::parse(&args(arg_list)).expect("should parse");
+        let params = parsed.into_params();
+
+        assert_eq!(params.context.include_declaration, expected);
+        assert_eq!(params.text_document_position.position.line, 9);
+        assert_eq!(params.text_document_position.position.character, 4);
+    }
 }
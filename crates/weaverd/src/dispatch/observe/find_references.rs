@@ -0,0 +1,82 @@
+//! Handler for the `observe find-references` operation.
+//!
+//! Mirrors `observe get-definition`: it parses the target position, ensures
+//! the semantic backend is running, asks the LSP host for references, and
+//! writes a `{"references":[...]}` payload.
+
+use std::io::Write;
+
+use tracing::debug;
+
+use super::{arguments::FindReferencesArgs, responses::ReferencesResponse};
+use crate::{
+    backends::{BackendKind, FusionBackends},
+    dispatch::{
+        cancellation::AFTER_BACKEND_STARTUP,
+        errors::DispatchError,
+        request::CommandRequest,
+        response::ResponseWriter,
+        router::{DISPATCH_TARGET, DispatchResult},
+    },
+    semantic_provider::SemanticBackendProvider,
+};
+
+/// Handles the `observe find-references` command.
+///
+/// The declaration is only reported when `--include-declaration` is passed.
+/// When the server finds no references, the payload carries an empty
+/// `references` array.
+///
+/// # Errors
+///
+/// Returns a `DispatchError` if:
+/// - Required arguments are missing or malformed
+/// - The file extension is not recognized
+/// - The semantic backend fails to start
+/// - The LSP host lock is poisoned
+/// - The LSP host returns an error
+pub fn handle<W: Write>(
+    request: &CommandRequest,
+    writer: &mut ResponseWriter<W>,
+    backends: &mut FusionBackends<SemanticBackendProvider>,
+) -> Result<DispatchResult, DispatchError> {
+    let args = FindReferencesArgs::parse(&request.arguments)?;
+    let language = args.target.language()?;
+
+    debug!(
+        target: DISPATCH_TARGET,
+        uri = %args.target.uri.as_str(),
+        line = args.target.line,
+        column = args.target.column,
+        include_declaration = args.include_declaration,
+        language = %language,
+        "handling find-references"
+    );
+
+    backends
+        .ensure_started(BackendKind::Semantic)
+        .map_err(DispatchError::backend_startup)?;
+    writer.check_cancelled(AFTER_BACKEND_STARTUP)?;
+
+    let params = args.into_params();
+    let locations = backends
+        .provider()
+        .with_lsp_host_mut(|lsp_host| {
+            lsp_host.initialize(language).map_err(|e| {
+                DispatchError::lsp_host(language.as_str(), format!("initialization failed: {e}"))
+            })?;
+            lsp_host.references(language, params).map_err(|e| {
+                DispatchError::lsp_host(language.as_str(), format!("references failed: {e}"))
+            })
+        })?
+        .ok_or_else(|| DispatchError::internal("LSP host not initialized after backend start"))??;
+
+    let json = serde_json::to_string(&ReferencesResponse::from(locations.as_slice()))?;
+    writer.write_stdout(json)?;
+
+    Ok(DispatchResult::success())
+}
+
+#[cfg(test)]
+#[path = "find_references_tests.rs"]
+mod tests;
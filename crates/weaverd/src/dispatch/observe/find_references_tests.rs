@@ -0,0 +1,129 @@
+//! Unit tests for `observe::find_references`.
+
+use lsp_types::{Location, Position, Range, Uri};
+use rstest::rstest;
+use weaver_lsp_host::{
+    Language,
+    ServerCapabilitySet,
+    test_support::{RecordingLanguageServer, RecordingServerHandle, ResponseSet},
+};
+
+use super::*;
+use crate::{
+    dispatch::{observe::test_support::semantic_backends_with_server, request::CommandDescriptor},
+    tests::support::stream::stdout_payload,
+};
+
+fn references_request(arguments: &[&str]) -> CommandRequest {
+    CommandRequest {
+        command: CommandDescriptor {
+            domain: String::from("observe"),
+            operation: String::from("find-references"),
+        },
+        arguments: arguments
+            .iter()
+            .map(|&argument| argument.to_owned())
+            .collect(),
+        patch: None,
+    }
+}
+
+fn location(uri: &str, line: u32, character: u32) -> Location {
+    let uri: Uri = uri.parse().expect("valid uri");
+    let position = Position { line, character };
+    Location {
+        uri,
+        range: Range {
+            start: position,
+            end: position,
+        },
+    }
+}
+
+fn run_with_references(
+    references: Vec<Location>,
+    arguments: &[&str],
+) -> (
+    Result<DispatchResult, DispatchError>,
+    String,
+    RecordingServerHandle,
+) {
+    let server = RecordingLanguageServer::new(
+        ServerCapabilitySet::new(false, true, false),
+        ResponseSet {
+            references,
+            ..ResponseSet::default()
+        },
+    );
+    let recorder = server.handle();
+    let (mut backends, _dir) =
+        semantic_backends_with_server(Language::Rust, server).expect("backends");
+    let mut output = Vec::new();
+    let mut writer = ResponseWriter::new(&mut output);
+    let result = handle(&references_request(arguments), &mut writer, &mut backends);
+    (
+        result,
+        String::from_utf8(output).expect("utf-8 output"),
+        recorder,
+    )
+}
+
+#[rstest]
+#[case::declaration_excluded(&["--uri", "file:///src/main.rs", "--position", "3:5"], false)]
+#[case::declaration_included(
+    &["--uri", "file:///src/main.rs", "--line", "3", "--column", "5", "--include-declaration"],
+    true
+)]
+fn forwards_reference_request_to_server(#[case] arguments: &[&str], #[case] include: bool) {
+    let references = vec![
+        location("file:///src/main.rs", 2, 4),
+        location("file:///src/lib.rs", 9, 0),
+    ];
+
+    let (result, output, server) = run_with_references(references, arguments);
+
+    assert_eq!(result.expect("reference lookup").status, 0);
+    assert_eq!(
+        stdout_payload(&output),
+        serde_json::json!({"references": [
+            {"uri": "file:///src/main.rs", "line": 3, "column": 5},
+            {"uri": "file:///src/lib.rs", "line": 10, "column": 1},
+        ]})
+    );
+    let requests = server.reference_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].context.include_declaration, include);
+    assert_eq!(
+        requests[0].text_document_position.position,
+        Position::new(2, 4)
+    );
+}
+
+#[rstest]
+fn writes_empty_references_when_nothing_is_found() {
+    let (result, output, _server) = run_with_references(
+        Vec::new(),
+        &["--uri", "file:///src/main.rs", "--position", "1:1"],
+    );
+
+    assert_eq!(result.expect("reference lookup").status, 0);
+    assert_eq!(
+        stdout_payload(&output),
+        serde_json::json!({"references": []})
+    );
+}
+
+#[rstest]
+fn rejects_unknown_flags_before_contacting_the_server() {
+    let (result, output, server) = run_with_references(
+        Vec::new(),
+        &["--uri", "file:///src/main.rs", "--position", "1:1", "--all"],
+    );
+
+    assert!(matches!(
+        result,
+        Err(DispatchError::InvalidArguments { .. })
+    ));
+    assert!(output.is_empty(), "nothing should be written: {output}");
+    assert!(server.calls().is_empty());
+}
@@ -13,6 +13,7 @@ use super::{arguments::GetDefinitionArgs, responses::extract_locations};
 use crate::{
     backends::{BackendKind, FusionBackends},
     dispatch::{
+        cancellation::AFTER_BACKEND_STARTUP,
         errors::DispatchError,
         request::CommandRequest,
         response::ResponseWriter,
@@ -25,12 +26,13 @@ use crate::{
 ///
 /// # Flow
 ///
-/// 1. Parse `--uri` and `--position` from the command arguments
+/// 1. Parse `--uri` and either `--position` or `--line` and `--column` from the command arguments
 /// 2. Infer the language from the URI's file extension
 /// 3. Ensure the semantic backend is started
 /// 4. Initialize the language server if not already initialized
 /// 5. Call `goto_definition` on the LSP host
-/// 6. Serialize the result locations as JSON to stdout
+/// 6. Serialize the result locations as JSON to stdout, writing an empty list when the server finds
+///    no definition
 ///
 /// # Errors
 ///
@@ -38,6 +40,7 @@ use crate::{
 /// - Required arguments are missing or malformed
 /// - The file extension is not recognized
 /// - The semantic backend fails to start
+/// - The LSP host lock is poisoned
 /// - The LSP host returns an error
 pub fn handle<W: Write>(
     request: &CommandRequest,
@@ -61,6 +64,7 @@ pub fn handle<W: Write>(
     backends
         .ensure_started(BackendKind::Semantic)
         .map_err(DispatchError::backend_startup)?;
+    writer.check_cancelled(AFTER_BACKEND_STARTUP)?;
 
     // 3. Get LSP host and perform definition lookup
     let params = args.into_params();
@@ -76,8 +80,7 @@ pub fn handle<W: Write>(
             lsp_host.goto_definition(language, params).map_err(|e| {
                 DispatchError::lsp_host(language.as_str(), format!("goto_definition failed: {e}"))
             })
-        })
-        .map_err(|_| DispatchError::internal("LSP host lock poisoned"))?
+        })?
         .ok_or_else(|| DispatchError::internal("LSP host not initialized after backend start"))??;
 
     // 4. Serialize response
@@ -88,13 +91,6 @@ pub fn handle<W: Write>(
     Ok(DispatchResult::success())
 }
 
-// Tests for get-definition handler.
-//
-// Integration tests are in the BDD test suite.
-// Unit tests for argument parsing are in the arguments module.
-// Unit tests for response serialization are in the responses module.
-
 #[cfg(test)]
-mod tests {
-    //! Unit tests for get_definition dispatch handler.
-}
+#[path = "get_definition_tests.rs"]
+mod tests;
@@ -0,0 +1,114 @@
+//! Unit tests for `observe::get_definition`.
+
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+use lsp_types::{GotoDefinitionResponse, Location, Position, Range, Uri};
+use rstest::rstest;
+use weaver_lsp_host::{Language, ServerCapabilitySet};
+
+use super::*;
+use crate::{
+    dispatch::{
+        observe::test_support::{StubLanguageServer, semantic_backends_with_server},
+        request::CommandDescriptor,
+    },
+    tests::support::stream::stdout_payload,
+};
+
+fn definition_request(arguments: &[&str]) -> CommandRequest {
+    CommandRequest {
+        command: CommandDescriptor {
+            domain: String::from("observe"),
+            operation: String::from("get-definition"),
+        },
+        arguments: arguments
+            .iter()
+            .map(|&argument| argument.to_owned())
+            .collect(),
+        patch: None,
+    }
+}
+
+fn stub_server() -> StubLanguageServer {
+    let (server, _hover_params) =
+        StubLanguageServer::missing_hover(ServerCapabilitySet::new(true, false, false));
+    server
+}
+
+fn run_handle(
+    backends: &mut FusionBackends<SemanticBackendProvider>,
+    arguments: &[&str],
+) -> (Result<DispatchResult, DispatchError>, String) {
+    let mut output = Vec::new();
+    let mut writer = ResponseWriter::new(&mut output);
+    let result = handle(&definition_request(arguments), &mut writer, backends);
+    (result, String::from_utf8(output).expect("utf-8 output"))
+}
+
+fn definition_at(uri: &str, line: u32, character: u32) -> GotoDefinitionResponse {
+    let uri: Uri = uri.parse().expect("valid uri");
+    let position = Position { line, character };
+    GotoDefinitionResponse::Scalar(Location {
+        uri,
+        range: Range {
+            start: position,
+            end: position,
+        },
+    })
+}
+
+#[rstest]
+#[case::position(&["--uri", "file:///src/main.rs", "--position", "3:5"])]
+#[case::line_and_column(&["--uri", "file:///src/main.rs", "--line", "3", "--column", "5"])]
+fn writes_definition_locations(#[case] arguments: &[&str]) {
+    let server = stub_server().with_definition(definition_at("file:///src/lib.rs", 9, 3));
+    let (mut backends, _dir) =
+        semantic_backends_with_server(Language::Rust, server).expect("backends");
+
+    let (result, output) = run_handle(&mut backends, arguments);
+
+    assert_eq!(result.expect("definition lookup").status, 0);
+    assert_eq!(
+        stdout_payload(&output),
+        serde_json::json!([{"uri": "file:///src/lib.rs", "line": 10, "column": 4}])
+    );
+}
+
+#[rstest]
+fn writes_empty_list_when_nothing_is_found() {
+    let (mut backends, _dir) =
+        semantic_backends_with_server(Language::Rust, stub_server()).expect("backends");
+
+    let (result, output) = run_handle(
+        &mut backends,
+        &["--uri", "file:///src/main.rs", "--position", "1:1"],
+    );
+
+    assert_eq!(result.expect("definition lookup").status, 0);
+    assert_eq!(stdout_payload(&output), serde_json::json!([]));
+}
+
+#[rstest]
+fn poisoned_lsp_host_lock_returns_structured_error() {
+    let (mut backends, _dir) =
+        semantic_backends_with_server(Language::Rust, stub_server()).expect("backends");
+    backends
+        .ensure_started(BackendKind::Semantic)
+        .expect("start semantic backend");
+    let poison = catch_unwind(AssertUnwindSafe(|| {
+        let _ = backends
+            .provider()
+            .with_lsp_host_mut::<_, ()>(|_| panic!("poison the LSP host lock"));
+    }));
+    assert!(poison.is_err(), "closure should panic");
+
+    let (result, output) = run_handle(
+        &mut backends,
+        &["--uri", "file:///src/main.rs", "--position", "1:1"],
+    );
+
+    let error = result.expect_err("poisoned lock should fail");
+    assert!(matches!(error, DispatchError::LspHostPoisoned(_)));
+    assert_eq!(error.exit_status(), 2);
+    assert!(output.is_empty(), "nothing should be written: {output}");
+}
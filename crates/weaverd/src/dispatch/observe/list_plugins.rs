@@ -0,0 +1,105 @@
+//! Handler for the `observe list-plugins` operation.
+//!
+//! Reports every plugin in the daemon's registry, built-in providers and
+//! manifests loaded from `WEAVER_PLUGIN_MANIFEST_DIR` alike, so operators can
+//! see which providers `act refactor` and `observe analyze` can route to. The
+//! listing describes manifests only; it does not start or health-check any
+//! plugin.
+
+use std::{io::Write, path::Path};
+
+use serde::Serialize;
+use tracing::debug;
+use weaver_plugins::{CapabilityId, PluginKind, PluginManifest, PluginRegistry};
+
+use crate::dispatch::{
+    act::refactor::daemon_plugin_registry,
+    errors::DispatchError,
+    request::CommandRequest,
+    response::ResponseWriter,
+    router::{DISPATCH_TARGET, DispatchResult},
+};
+
+/// JSON payload returned by `observe list-plugins`.
+#[derive(Debug, Serialize)]
+struct PluginListing<'a> {
+    /// Registered plugins in name order.
+    plugins: Vec<PluginSummary<'a>>,
+}
+
+/// Manifest fields reported for a single plugin.
+#[derive(Debug, Serialize)]
+struct PluginSummary<'a> {
+    name: &'a str,
+    version: &'a str,
+    kind: PluginKind,
+    languages: &'a [String],
+    capabilities: &'a [CapabilityId],
+    timeout_secs: u64,
+}
+
+impl<'a> From<&'a PluginManifest> for PluginSummary<'a> {
+    fn from(manifest: &'a PluginManifest) -> Self {
+        Self {
+            name: manifest.name(),
+            version: manifest.version(),
+            kind: manifest.kind(),
+            languages: manifest.languages(),
+            capabilities: manifest.capabilities(),
+            timeout_secs: manifest.timeout_secs(),
+        }
+    }
+}
+
+/// Builds the registry served by `observe list-plugins`.
+///
+/// A registry that cannot be built is kept as an error message and reported
+/// on every request, matching how the plugin runtimes surface the same
+/// failure.
+pub(crate) fn default_registry(workspace_root: &Path) -> Result<PluginRegistry, String> {
+    daemon_plugin_registry(workspace_root)
+        .map_err(|error| format!("failed to build plugin registry: {error}"))
+}
+
+/// Handles `observe list-plugins` requests.
+///
+/// Writes the registered manifests to stdout as a `plugins` array in name
+/// order, reporting each plugin's name, version, kind, languages,
+/// capabilities, and timeout.
+///
+/// # Errors
+///
+/// Returns [`DispatchError::InvalidArguments`] if any arguments are supplied,
+/// and [`DispatchError::Internal`] if the registry could not be built.
+pub(crate) fn handle<W: Write>(
+    request: &CommandRequest,
+    writer: &mut ResponseWriter<W>,
+    registry: Result<&PluginRegistry, &str>,
+) -> Result<DispatchResult, DispatchError> {
+    if let Some(argument) = request.arguments.first() {
+        return Err(DispatchError::invalid_arguments(format!(
+            "observe list-plugins takes no arguments, got '{argument}'"
+        )));
+    }
+    let registry = registry.map_err(DispatchError::internal)?;
+    debug!(
+        target: DISPATCH_TARGET,
+        plugins = registry.len(),
+        "handling observe list-plugins"
+    );
+
+    let listing = PluginListing {
+        plugins: registry
+            .names()
+            .into_iter()
+            .filter_map(|name| registry.get(name))
+            .map(PluginSummary::from)
+            .collect(),
+    };
+    writer.write_stdout(serde_json::to_string(&listing)?)?;
+    Ok(DispatchResult::success())
+}
+
+#[cfg(test)]
+#[path = "list_plugins_tests.rs"]
+mod tests;
@@ -0,0 +1,107 @@
+//! Unit tests for `observe::list_plugins`.
+
+use std::path::PathBuf;
+
+use weaver_plugins::PluginMetadata;
+
+use super::*;
+use crate::{dispatch::request::CommandDescriptor, tests::support::stream::stdout_payload};
+
+fn list_request(arguments: &[&str]) -> CommandRequest {
+    CommandRequest {
+        command: CommandDescriptor {
+            domain: String::from("observe"),
+            operation: String::from("list-plugins"),
+        },
+        arguments: arguments
+            .iter()
+            .map(|&argument| argument.to_owned())
+            .collect(),
+        patch: None,
+    }
+}
+
+fn run(
+    registry: Result<&PluginRegistry, &str>,
+    arguments: &[&str],
+) -> (Result<DispatchResult, DispatchError>, String) {
+    let mut output = Vec::new();
+    let mut writer = ResponseWriter::new(&mut output);
+    let result = handle(&list_request(arguments), &mut writer, registry);
+    (result, String::from_utf8(output).expect("utf-8 output"))
+}
+
+#[test]
+fn lists_rope_with_its_declared_capabilities() {
+    let registry = default_registry(Path::new("/")).expect("daemon registry");
+
+    let (result, output) = run(Ok(&registry), &[]);
+
+    assert_eq!(result.expect("dispatch").status, 0);
+    let payload = stdout_payload(&output);
+    let plugins = payload["plugins"].as_array().expect("plugins array");
+    let rope = plugins
+        .iter()
+        .find(|plugin| plugin["name"] == "rope")
+        .expect("rope should be listed");
+    assert_eq!(rope["kind"], "actuator");
+    assert_eq!(rope["languages"], serde_json::json!(["python"]));
+    assert_eq!(rope["capabilities"], serde_json::json!(["rename-symbol"]));
+    assert_eq!(rope["timeout_secs"], 30);
+    assert!(rope["version"].is_string());
+    assert!(
+        rope.get("executable").is_none(),
+        "listing should not expose executable paths: {rope}"
+    );
+}
+
+#[test]
+fn lists_plugins_in_name_order() {
+    let mut registry = PluginRegistry::new();
+    for name in ["zeta", "alpha"] {
+        let metadata = PluginMetadata::new(name, "0.1.0", PluginKind::Sensor);
+        let manifest = PluginManifest::new(
+            metadata,
+            vec![String::from("python")],
+            PathBuf::from("/usr/bin/sensor"),
+        );
+        registry.register(manifest).expect("register sensor");
+    }
+
+    let (result, output) = run(Ok(&registry), &[]);
+
+    assert_eq!(result.expect("dispatch").status, 0);
+    let payload = stdout_payload(&output);
+    let names: Vec<&str> = payload["plugins"]
+        .as_array()
+        .expect("plugins array")
+        .iter()
+        .filter_map(|plugin| plugin["name"].as_str())
+        .collect();
+    assert_eq!(names, ["alpha", "zeta"]);
+    assert_eq!(payload["plugins"][0]["capabilities"], serde_json::json!([]));
+}
+
+#[test]
+fn rejects_arguments() {
+    let registry = PluginRegistry::new();
+
+    let (result, _) = run(Ok(&registry), &["--kind", "sensor"]);
+
+    assert!(matches!(
+        result,
+        Err(DispatchError::InvalidArguments { .. })
+    ));
+}
+
+#[test]
+fn reports_registry_failure() {
+    let (result, output) = run(Err("failed to build plugin registry: boom"), &[]);
+
+    assert!(
+        matches!(&result, Err(DispatchError::Internal { message }) if message.contains("boom")),
+        "expected internal error, got {:?}",
+        result.as_ref().err()
+    );
+    assert!(output.is_empty());
+}
@@ -2,13 +2,17 @@
 //!
 //! This module contains operation handlers for querying the codebase,
 //! including definition lookup, reference finding, card retrieval,
-//! graph-slice traversal, and structural search.
+//! graph-slice traversal, structural search, sensor plugin analysis, and
+//! plugin registry listing.
 
+pub mod analyze;
 pub mod arguments;
 pub mod enrich;
+pub mod find_references;
 pub mod get_card;
 pub mod get_definition;
 pub mod graph_slice;
+pub mod list_plugins;
 pub mod responses;
 
 #[cfg(test)]
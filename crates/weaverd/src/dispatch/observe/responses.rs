@@ -62,6 +62,27 @@ pub fn extract_locations(response: GotoDefinitionResponse) -> Vec<DefinitionLoca
     }
 }
 
+/// Payload for `observe find-references`.
+///
+/// Serializes to the format the CLI renders:
+///
+/// ```json
+/// {"references":[{"uri":"file:///path.rs","line":42,"column":17}]}
+/// ```
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ReferencesResponse {
+    /// Locations where the symbol is referenced, in server order.
+    pub references: Vec<DefinitionLocation>,
+}
+
+impl From<&[Location]> for ReferencesResponse {
+    fn from(locations: &[Location]) -> Self {
+        Self {
+            references: locations.iter().map(DefinitionLocation::from).collect(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     //! Unit tests for LSP response conversion and extraction.
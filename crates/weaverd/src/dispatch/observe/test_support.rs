@@ -38,6 +38,7 @@ use crate::{backends::FusionBackends, semantic_provider::SemanticBackendProvider
 
 pub(crate) struct StubLanguageServer {
     capabilities: ServerCapabilitySet,
+    definition: Option<GotoDefinitionResponse>,
     hover: Option<Hover>,
     initialize_error: Option<String>,
     hover_error: Option<String>,
@@ -54,6 +55,7 @@ impl StubLanguageServer {
         let last_hover_params = Arc::new(Mutex::new(None));
         let server = Self {
             capabilities,
+            definition: None,
             hover,
             initialize_error,
             hover_error,
@@ -75,6 +77,12 @@ impl StubLanguageServer {
         Self::new(capabilities, None, None, None)
     }
 
+    /// Makes `goto_definition` return `definition` instead of an empty list.
+    pub(crate) fn with_definition(mut self, definition: GotoDefinitionResponse) -> Self {
+        self.definition = Some(definition);
+        self
+    }
+
     pub(crate) fn failing_initialize(
         capabilities: ServerCapabilitySet,
         message: impl Into<String>,
@@ -102,7 +110,10 @@ impl LanguageServer for StubLanguageServer {
         &mut self,
         _params: GotoDefinitionParams,
     ) -> Result<GotoDefinitionResponse, LanguageServerError> {
-        Ok(GotoDefinitionResponse::Array(Vec::new()))
+        Ok(self
+            .definition
+            .clone()
+            .unwrap_or_else(|| GotoDefinitionResponse::Array(Vec::new())))
     }
 
     fn references(
@@ -0,0 +1,62 @@
+//! Startup health checks for registered plugins.
+//!
+//! Registration only validates manifests, so a plugin whose executable is
+//! missing or broken would otherwise go unnoticed until the first request
+//! routed to it. The daemon pings every registered plugin once at startup and
+//! logs a warning for each one that fails. Unhealthy plugins stay registered,
+//! so a transient failure does not hide a provider for the daemon's lifetime.
+
+use std::path::Path;
+
+use tracing::{debug, warn};
+use weaver_plugins::{
+    PluginError,
+    process::SandboxExecutor,
+    runner::{PluginExecutor, PluginRunner},
+};
+
+use crate::dispatch::{act::refactor::daemon_plugin_registry, router::DISPATCH_TARGET};
+
+/// Pings every plugin in the daemon registry and warns about failures.
+///
+/// Must run before the listener starts worker threads, because the sandbox
+/// only spawns plugins from a single-threaded caller.
+pub(crate) fn check_plugin_health(workspace_root: &Path, executor: SandboxExecutor) {
+    match daemon_plugin_registry(workspace_root) {
+        Ok(registry) => {
+            report_unhealthy(&PluginRunner::new(registry, executor));
+        }
+        Err(error) => warn!(
+            target: DISPATCH_TARGET,
+            %error,
+            "skipping plugin health checks: failed to build plugin registry"
+        ),
+    }
+}
+
+/// Health-checks each plugin known to `runner` in name order.
+///
+/// Returns the plugins that failed alongside their errors, after logging a
+/// warning for each.
+fn report_unhealthy<E: PluginExecutor>(runner: &PluginRunner<E>) -> Vec<(String, PluginError)> {
+    let mut unhealthy = Vec::new();
+    for name in runner.registry().names() {
+        match runner.health_check(name) {
+            Ok(()) => debug!(target: DISPATCH_TARGET, plugin = name, "plugin passed health check"),
+            Err(error) => {
+                warn!(
+                    target: DISPATCH_TARGET,
+                    plugin = name,
+                    %error,
+                    "plugin failed its startup health check"
+                );
+                unhealthy.push((name.to_owned(), error));
+            }
+        }
+    }
+    unhealthy
+}
+
+#[cfg(test)]
+#[path = "plugin_health_tests.rs"]
+mod tests;
@@ -0,0 +1,80 @@
+//! Unit tests for startup plugin health checks.
+
+use std::path::PathBuf;
+
+use weaver_plugins::{
+    PluginKind,
+    PluginManifest,
+    PluginMetadata,
+    PluginOutput,
+    PluginRegistry,
+    PluginRequest,
+    PluginResponse,
+    protocol::PING_OPERATION,
+};
+
+use super::*;
+
+/// Executor double where `jedi` never answers and every other plugin is
+/// healthy.
+struct StubExecutor;
+
+impl PluginExecutor for StubExecutor {
+    fn execute(
+        &self,
+        manifest: &PluginManifest,
+        request: &PluginRequest,
+    ) -> Result<PluginResponse, PluginError> {
+        assert_eq!(request.operation(), PING_OPERATION);
+        if manifest.name() == "jedi" {
+            return Err(PluginError::Timeout {
+                name: manifest.name().to_owned(),
+                timeout_secs: 30,
+                message: String::from("process killed"),
+            });
+        }
+        Ok(PluginResponse::success(PluginOutput::Empty))
+    }
+}
+
+fn manifest(name: &str, kind: PluginKind) -> PluginManifest {
+    PluginManifest::new(
+        PluginMetadata::new(name, "1.0", kind),
+        vec![String::from("python")],
+        PathBuf::from(format!("/usr/bin/{name}")),
+    )
+}
+
+fn runner(manifests: Vec<PluginManifest>) -> PluginRunner<StubExecutor> {
+    let mut registry = PluginRegistry::new();
+    for manifest in manifests {
+        registry.register(manifest).expect("register manifest");
+    }
+    PluginRunner::new(registry, StubExecutor)
+}
+
+#[test]
+fn healthy_plugins_are_not_reported() {
+    let runner = runner(vec![manifest("rope", PluginKind::Actuator)]);
+
+    assert!(report_unhealthy(&runner).is_empty());
+}
+
+#[test]
+fn unresponsive_plugins_are_reported() {
+    let runner = runner(vec![
+        manifest("rope", PluginKind::Actuator),
+        manifest("jedi", PluginKind::Sensor),
+    ]);
+
+    let unhealthy = report_unhealthy(&runner);
+
+    let [(name, error)] = unhealthy.as_slice() else {
+        panic!("expected one unhealthy plugin, got {}", unhealthy.len());
+    };
+    assert_eq!(name, "jedi");
+    assert!(
+        matches!(error, PluginError::Timeout { .. }),
+        "unexpected error: {error}"
+    );
+}
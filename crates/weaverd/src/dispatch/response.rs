@@ -3,8 +3,15 @@
 //! This module provides the `DaemonMessage` type and `ResponseWriter` helper
 //! for streaming JSONL responses back to clients. The message format matches
 //! the protocol expected by `weaver-cli`.
+//!
+//! Handlers write their response into a buffer that is only sent once routing
+//! completes, so long-running handlers report liveness through a
+//! [`ProgressSink`] that writes straight to the client connection instead.
 
-use std::io::Write;
+use std::{
+    io::Write,
+    sync::{Arc, Mutex, PoisonError},
+};
 
 use serde::Serialize;
 #[cfg(test)]
@@ -12,7 +19,7 @@ use serde::de::DeserializeOwned;
 // Re-export the wire-protocol constant for internal and test use.
 pub use weaver_daemon_types::UNKNOWN_OPERATION_TYPE;
 
-use super::errors::DispatchError;
+use super::{cancellation::CancellationToken, errors::DispatchError};
 
 /// Target stream for output messages.
 #[derive(Debug, Clone, Copy, Serialize)]
@@ -28,7 +35,8 @@ pub enum StreamTarget {
 ///
 /// Each message is serialized as a single JSONL line. The client reads these
 /// lines until it receives an `Exit` message, which signals the end of the
-/// response stream.
+/// response stream. Clients ignore message kinds they do not recognise, so new
+/// kinds can be added without breaking older clients.
 #[derive(Debug, Serialize)]
 #[serde(tag = "kind", rename_all = "snake_case")]
 pub enum DaemonMessage {
@@ -39,6 +47,14 @@ pub enum DaemonMessage {
         /// Text payload to write.
         data: String,
     },
+    /// Status update for an operation that is still running.
+    Progress {
+        /// Human-readable description of the current step.
+        message: String,
+        /// Completed fraction between 0.0 and 1.0, when it is known.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        fraction: Option<f32>,
+    },
     /// Terminal message signalling completion with an exit status.
     Exit {
         /// Exit status code (0 for success, non-zero for failure).
@@ -63,16 +79,66 @@ impl DaemonMessage {
         }
     }
 
+    /// Creates a progress message with an optional completed fraction.
+    pub fn progress(message: impl Into<String>, fraction: Option<f32>) -> Self {
+        Self::Progress {
+            message: message.into(),
+            fraction,
+        }
+    }
+
     /// Creates an exit message with the given status code.
     pub fn exit(status: i32) -> Self { Self::Exit { status } }
 }
 
+/// Shared handle that writes progress messages directly to the client.
+///
+/// Unlike [`ResponseWriter`], which handlers may point at a buffer, the sink
+/// writes and flushes each message immediately so the client sees it while
+/// the handler is still running. Clones share the underlying stream and the
+/// lock keeps concurrent reports from interleaving.
+#[derive(Clone)]
+pub struct ProgressSink {
+    writer: Arc<Mutex<Box<dyn Write + Send>>>,
+}
+
+impl ProgressSink {
+    /// Creates a sink that writes progress messages to `writer`.
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self {
+            writer: Arc::new(Mutex::new(Box::new(writer))),
+        }
+    }
+
+    /// Writes a progress message and flushes it to the client.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization, writing, or flushing fails.
+    pub fn report(
+        &self,
+        message: impl Into<String>,
+        fraction: Option<f32>,
+    ) -> Result<(), DispatchError> {
+        let mut line = serde_json::to_vec(&DaemonMessage::progress(message, fraction))?;
+        line.push(b'\n');
+        let mut writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        writer.write_all(&line)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
 /// Writer that serializes daemon messages to a stream.
 ///
 /// The writer handles JSONL framing (appending newlines) and provides
-/// convenience methods for common message patterns.
+/// convenience methods for common message patterns. It may also carry a
+/// [`ProgressSink`] that handlers use to report progress before their
+/// response is written, and the request's [`CancellationToken`].
 pub struct ResponseWriter<W> {
     writer: W,
+    progress: Option<ProgressSink>,
+    cancellation: CancellationToken,
 }
 
 #[derive(Debug, Serialize)]
@@ -92,7 +158,45 @@ struct UnknownOperationDetails<'a> {
 
 impl<W: Write> ResponseWriter<W> {
     /// Creates a new response writer wrapping the given output stream.
-    pub fn new(writer: W) -> Self { Self { writer } }
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            progress: None,
+            cancellation: CancellationToken::default(),
+        }
+    }
+
+    /// Attaches a sink that handlers can use to report progress.
+    #[must_use]
+    pub fn with_progress(mut self, progress: Option<ProgressSink>) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Returns the progress sink, if the client connection supports one.
+    pub fn progress(&self) -> Option<&ProgressSink> { self.progress.as_ref() }
+
+    /// Attaches the token that records whether the client abandoned the
+    /// request.
+    #[must_use]
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    /// Returns the request's cancellation token.
+    pub fn cancellation(&self) -> &CancellationToken { &self.cancellation }
+
+    /// Fails with [`DispatchError::Cancelled`] if the client has abandoned the
+    /// request, so handlers can stop between stages.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DispatchError::Cancelled`] naming `stage` once the request is
+    /// cancelled.
+    pub fn check_cancelled(&self, stage: &'static str) -> Result<(), DispatchError> {
+        self.cancellation.check(stage)
+    }
 
     /// Writes a daemon message as a JSONL line.
     ///
@@ -204,80 +308,5 @@ where
 }
 
 #[cfg(test)]
-mod tests {
-    //! Unit tests for response writing and formatting.
-
-    use super::*;
-
-    #[test]
-    fn writes_exit_message() {
-        let mut output = Vec::new();
-        let mut writer = ResponseWriter::new(&mut output);
-        writer.write_exit(0).expect("write exit");
-
-        let response = String::from_utf8(output).expect("valid utf8");
-        assert!(response.contains(r#""kind":"exit""#));
-        assert!(response.contains(r#""status":0"#));
-        assert!(response.ends_with('\n'));
-    }
-
-    #[test]
-    fn writes_stdout_stream() {
-        let mut output = Vec::new();
-        let mut writer = ResponseWriter::new(&mut output);
-        writer.write_stdout("result data").expect("write stdout");
-
-        let response = String::from_utf8(output).expect("valid utf8");
-        assert!(response.contains(r#""stream":"stdout""#));
-        assert!(response.contains(r#""data":"result data""#));
-    }
-
-    #[test]
-    fn writes_stderr_stream() {
-        let mut output = Vec::new();
-        let mut writer = ResponseWriter::new(&mut output);
-        writer.write_stderr("error text").expect("write stderr");
-
-        let response = String::from_utf8(output).expect("valid utf8");
-        assert!(response.contains(r#""stream":"stderr""#));
-        assert!(response.contains(r#""data":"error text""#));
-    }
-
-    #[test]
-    fn write_error_includes_status() {
-        let mut output = Vec::new();
-        let mut writer = ResponseWriter::new(&mut output);
-        let error = DispatchError::unknown_domain("bogus");
-        writer.write_error(&error).expect("write error");
-
-        let response = String::from_utf8(output).expect("valid utf8");
-        assert!(response.contains("unknown domain"));
-        assert!(response.contains(r#""status":1"#));
-    }
-
-    #[test]
-    fn write_error_serializes_unknown_operation_payload() {
-        let mut output = Vec::new();
-        let mut writer = ResponseWriter::new(&mut output);
-        let error = DispatchError::unknown_operation(
-            "observe",
-            "bogus",
-            &["get-definition", "find-references"],
-        );
-        writer.write_error(&error).expect("write error");
-
-        let response = String::from_utf8(output).expect("valid utf8");
-        let payload = response
-            .lines()
-            .find_map(parse_stderr_json_payload::<serde_json::Value>)
-            .expect("unknown-operation payload");
-        assert_eq!(payload["type"], UNKNOWN_OPERATION_TYPE);
-        assert_eq!(payload["details"]["domain"], "observe");
-        assert_eq!(payload["details"]["operation"], "bogus");
-        assert_eq!(
-            payload["details"]["known_operations"],
-            serde_json::json!(["get-definition", "find-references"])
-        );
-        assert!(response.contains(r#""status":1"#));
-    }
-}
+#[path = "response_tests.rs"]
+mod tests;
@@ -0,0 +1,114 @@
+//! Unit tests for response writing and formatting.
+
+use super::*;
+
+#[test]
+fn writes_exit_message() {
+    let mut output = Vec::new();
+    let mut writer = ResponseWriter::new(&mut output);
+    writer.write_exit(0).expect("write exit");
+
+    let response = String::from_utf8(output).expect("valid utf8");
+    assert!(response.contains(r#""kind":"exit""#));
+    assert!(response.contains(r#""status":0"#));
+    assert!(response.ends_with('\n'));
+}
+
+#[test]
+fn writes_stdout_stream() {
+    let mut output = Vec::new();
+    let mut writer = ResponseWriter::new(&mut output);
+    writer.write_stdout("result data").expect("write stdout");
+
+    let response = String::from_utf8(output).expect("valid utf8");
+    assert!(response.contains(r#""stream":"stdout""#));
+    assert!(response.contains(r#""data":"result data""#));
+}
+
+#[test]
+fn writes_stderr_stream() {
+    let mut output = Vec::new();
+    let mut writer = ResponseWriter::new(&mut output);
+    writer.write_stderr("error text").expect("write stderr");
+
+    let response = String::from_utf8(output).expect("valid utf8");
+    assert!(response.contains(r#""stream":"stderr""#));
+    assert!(response.contains(r#""data":"error text""#));
+}
+
+#[test]
+fn writes_progress_message() {
+    let mut output = Vec::new();
+    {
+        let mut writer = ResponseWriter::new(&mut output);
+        writer
+            .write_message(&DaemonMessage::progress("indexing", Some(0.5)))
+            .expect("write progress");
+        writer
+            .write_message(&DaemonMessage::progress("waiting", None))
+            .expect("write progress");
+    }
+
+    let response = String::from_utf8(output).expect("valid utf8");
+    let lines: Vec<&str> = response.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            r#"{"kind":"progress","message":"indexing","fraction":0.5}"#,
+            r#"{"kind":"progress","message":"waiting"}"#,
+        ]
+    );
+}
+
+#[test]
+fn write_error_includes_status() {
+    let mut output = Vec::new();
+    let mut writer = ResponseWriter::new(&mut output);
+    let error = DispatchError::unknown_domain("bogus");
+    writer.write_error(&error).expect("write error");
+
+    let response = String::from_utf8(output).expect("valid utf8");
+    assert!(response.contains("unknown domain"));
+    assert!(response.contains(r#""status":1"#));
+}
+
+#[test]
+fn write_error_serializes_unknown_operation_payload() {
+    let mut output = Vec::new();
+    let mut writer = ResponseWriter::new(&mut output);
+    let error = DispatchError::unknown_operation(
+        "observe",
+        "bogus",
+        &["get-definition", "find-references"],
+    );
+    writer.write_error(&error).expect("write error");
+
+    let response = String::from_utf8(output).expect("valid utf8");
+    let payload = response
+        .lines()
+        .find_map(parse_stderr_json_payload::<serde_json::Value>)
+        .expect("unknown-operation payload");
+    assert_eq!(payload["type"], UNKNOWN_OPERATION_TYPE);
+    assert_eq!(payload["details"]["domain"], "observe");
+    assert_eq!(payload["details"]["operation"], "bogus");
+    assert_eq!(
+        payload["details"]["known_operations"],
+        serde_json::json!(["get-definition", "find-references"])
+    );
+    assert!(response.contains(r#""status":1"#));
+}
+
+#[test]
+fn check_cancelled_follows_the_attached_token() {
+    let token = CancellationToken::default();
+    let writer = ResponseWriter::new(Vec::new()).with_cancellation(token.clone());
+    assert!(writer.check_cancelled("before routing").is_ok());
+
+    token.cancel();
+    assert!(matches!(
+        writer.check_cancelled("before routing"),
+        Err(DispatchError::Cancelled {
+            stage: "before routing"
+        })
+    ));
+}
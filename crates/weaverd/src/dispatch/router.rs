@@ -12,6 +12,7 @@ use std::{
 };
 
 use tracing::debug;
+use weaver_plugins::{PluginRegistry, process::SandboxExecutor};
 
 use super::{
     act,
@@ -19,6 +20,7 @@ use super::{
     observe,
     request::CommandRequest,
     response::ResponseWriter,
+    verify,
 };
 use crate::{backends::FusionBackends, semantic_provider::SemanticBackendProvider};
 
@@ -94,6 +96,8 @@ impl DomainRoutingContext {
             "call-hierarchy",
             "get-card",
             "graph-slice",
+            "analyze",
+            "list-plugins",
         ],
     };
 
@@ -124,6 +128,8 @@ impl DomainRoutingContext {
 pub struct DomainRouter {
     workspace_root: PathBuf,
     refactor_runtime: Arc<dyn act::refactor::RefactorPluginRuntime + Send + Sync>,
+    analysis_runtime: Arc<dyn observe::analyze::AnalysisRuntime + Send + Sync>,
+    plugin_registry: Result<PluginRegistry, String>,
 }
 
 impl std::fmt::Debug for DomainRouter {
@@ -144,10 +150,25 @@ impl DomainRouter {
     /// against this root and rely on the resulting paths to construct
     /// canonical file URIs.
     pub fn new(workspace_root: PathBuf) -> Result<Self, DispatchError> {
+        Self::with_plugin_executor(workspace_root, SandboxExecutor::default())
+    }
+
+    /// Creates a domain router whose plugin runtimes use `executor`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DispatchError::InvalidArguments`] if `workspace_root` is not
+    /// an absolute path.
+    pub fn with_plugin_executor(
+        workspace_root: PathBuf,
+        executor: SandboxExecutor,
+    ) -> Result<Self, DispatchError> {
         validate_absolute_workspace_root(workspace_root.as_path())?;
         Ok(Self {
+            refactor_runtime: act::refactor::default_runtime(&workspace_root, executor),
+            analysis_runtime: observe::analyze::default_runtime(&workspace_root, executor),
+            plugin_registry: observe::list_plugins::default_registry(&workspace_root),
             workspace_root,
-            refactor_runtime: act::refactor::default_runtime(),
         })
     }
 
@@ -168,8 +189,13 @@ impl DomainRouter {
     ) -> Result<Self, DispatchError> {
         validate_absolute_workspace_root(workspace_root.as_path())?;
         Ok(Self {
-            workspace_root,
             refactor_runtime: runtime,
+            analysis_runtime: observe::analyze::default_runtime(
+                &workspace_root,
+                SandboxExecutor::default(),
+            ),
+            plugin_registry: observe::list_plugins::default_registry(&workspace_root),
+            workspace_root,
         })
     }
 
@@ -196,7 +222,7 @@ impl DomainRouter {
         match domain {
             Domain::Observe => self.route_observe(request, writer, backends),
             Domain::Act => self.route_act(request, writer, backends),
-            Domain::Verify => self.route_verify(request, writer),
+            Domain::Verify => self.route_verify(request, writer, backends),
         }
     }
 
@@ -209,8 +235,20 @@ impl DomainRouter {
         let operation = request.operation().to_ascii_lowercase();
         match operation.as_str() {
             "get-definition" => observe::get_definition::handle(request, writer, backends),
+            "find-references" => observe::find_references::handle(request, writer, backends),
             "get-card" => observe::get_card::handle(request, writer, backends),
             "graph-slice" => observe::graph_slice::handle(request, writer, backends),
+            "analyze" => observe::analyze::handle(
+                request,
+                writer,
+                &self.workspace_root,
+                self.analysis_runtime.as_ref(),
+            ),
+            "list-plugins" => observe::list_plugins::handle(
+                request,
+                writer,
+                self.plugin_registry.as_ref().map_err(String::as_str),
+            ),
             _ => Self::route_fallback(&DomainRoutingContext::OBSERVE, operation.as_str(), writer),
         }
     }
@@ -226,6 +264,9 @@ impl DomainRouter {
             "apply-patch" => {
                 act::apply_patch::handle(request, writer, backends, &self.workspace_root)
             }
+            "apply-rewrite" => {
+                act::apply_rewrite::handle(request, writer, backends, &self.workspace_root)
+            }
             "refactor" => act::refactor::handle(
                 request,
                 writer,
@@ -243,9 +284,13 @@ impl DomainRouter {
         &self,
         request: &CommandRequest,
         writer: &mut ResponseWriter<W>,
+        backends: &mut FusionBackends<SemanticBackendProvider>,
     ) -> Result<DispatchResult, DispatchError> {
         let operation = request.operation().to_ascii_lowercase();
-        Self::route_fallback(&DomainRoutingContext::VERIFY, operation.as_str(), writer)
+        match operation.as_str() {
+            "diagnostics" => verify::diagnostics::handle(request, writer, backends),
+            _ => Self::route_fallback(&DomainRoutingContext::VERIFY, operation.as_str(), writer),
+        }
     }
 
     /// Handles routing fallbacks for known-but-unimplemented and unknown operations.
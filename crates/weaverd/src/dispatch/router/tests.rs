@@ -59,15 +59,27 @@ fn invalid_arguments_message(domain: &str, operation: &str) -> Option<&'static s
         ("observe", "get-definition") => {
             Some("observe get-definition should fail with InvalidArguments (no args provided)")
         }
+        ("observe", "find-references") => {
+            Some("observe find-references should fail with InvalidArguments (no args provided)")
+        }
         ("observe", "get-card") => {
             Some("observe get-card should fail with InvalidArguments (no args provided)")
         }
         ("observe", "graph-slice") => {
             Some("observe graph-slice should fail with InvalidArguments (no args provided)")
         }
+        ("observe", "analyze") => {
+            Some("observe analyze should fail with InvalidArguments (no args provided)")
+        }
+        ("verify", "diagnostics") => {
+            Some("verify diagnostics should fail with InvalidArguments (no args provided)")
+        }
         ("act", "apply-patch") => {
             Some("act apply-patch should fail with InvalidArguments (missing patch)")
         }
+        ("act", "apply-rewrite") => {
+            Some("act apply-rewrite should fail with InvalidArguments (missing required flags)")
+        }
         ("act", "refactor") => {
             Some("act refactor should fail with InvalidArguments (missing required flags)")
         }
@@ -208,9 +220,9 @@ fn get_card_returns_structured_refusal(mut backends: FusionBackends<SemanticBack
 }
 
 #[rstest]
-fn find_references_not_implemented(mut backends: FusionBackends<SemanticBackendProvider>) {
+fn grep_not_implemented(mut backends: FusionBackends<SemanticBackendProvider>) {
     let router = build_router();
-    let request = make_request("observe", "find-references");
+    let request = make_request("observe", "grep");
     let mut output = Vec::new();
     let mut writer = ResponseWriter::new(&mut output);
     let result = router
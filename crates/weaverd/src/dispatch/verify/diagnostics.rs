@@ -0,0 +1,250 @@
+//! Handler for the `verify diagnostics` operation.
+//!
+//! The handler reads the target document from disk, opens it against the LSP
+//! host, and collects the diagnostics the language server publishes for it.
+//! Servers publish diagnostics asynchronously after `textDocument/didOpen`,
+//! and many reject diagnostic requests while they are still indexing, so
+//! server errors are retried until a deadline passes. Once it does, the last
+//! server error is reported as a `DiagnosticsTimeout`.
+
+use std::{
+    io::Write,
+    path::PathBuf,
+    thread,
+    time::{Duration, Instant},
+};
+
+use lsp_types::{
+    Diagnostic,
+    DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams,
+    TextDocumentIdentifier,
+    TextDocumentItem,
+    Uri,
+};
+use tracing::debug;
+use url::Url;
+use weaver_lsp_host::{Language, LspHost, LspHostError};
+
+use super::responses::DiagnosticsResponse;
+use crate::{
+    backends::{BackendKind, FusionBackends},
+    dispatch::{
+        cancellation::AFTER_BACKEND_STARTUP,
+        errors::DispatchError,
+        filesystem,
+        observe::arguments::language_for_uri,
+        request::CommandRequest,
+        response::ResponseWriter,
+        router::{DISPATCH_TARGET, DispatchResult},
+    },
+    semantic_provider::SemanticBackendProvider,
+};
+
+/// How long `verify diagnostics` waits for the server to publish diagnostics.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DiagnosticsWait {
+    timeout: Duration,
+    poll_interval: Duration,
+}
+
+impl DiagnosticsWait {
+    /// Five seconds covers a cold start of the bundled servers on a small
+    /// workspace without letting a wedged server stall the client.
+    pub(crate) const DEFAULT: Self = Self {
+        timeout: Duration::from_secs(5),
+        poll_interval: Duration::from_millis(100),
+    };
+}
+
+/// Document opened against the LSP host while diagnostics are collected.
+struct OpenDocument {
+    language: Language,
+    uri: Uri,
+    text: String,
+}
+
+/// Handles the `verify diagnostics` command.
+///
+/// # Errors
+///
+/// Returns a `DispatchError` if:
+/// - `--uri` is missing, malformed, or does not name a readable local file
+/// - The file extension is not recognized
+/// - The semantic backend fails to start
+/// - The LSP host lock is poisoned
+/// - The LSP host returns an error, or publishes no diagnostics in time
+pub fn handle<W: Write>(
+    request: &CommandRequest,
+    writer: &mut ResponseWriter<W>,
+    backends: &mut FusionBackends<SemanticBackendProvider>,
+) -> Result<DispatchResult, DispatchError> {
+    handle_with_wait(request, writer, backends, DiagnosticsWait::DEFAULT)
+}
+
+fn handle_with_wait<W: Write>(
+    request: &CommandRequest,
+    writer: &mut ResponseWriter<W>,
+    backends: &mut FusionBackends<SemanticBackendProvider>,
+    wait: DiagnosticsWait,
+) -> Result<DispatchResult, DispatchError> {
+    let (url, uri) = parse_uri_argument(&request.arguments)?;
+    let language = language_for_uri(&uri)?;
+    let path = resolve_file_path(&url)?;
+    let text = filesystem::read_to_string(&path).map_err(|error| {
+        DispatchError::invalid_arguments(format!("unable to read '{}': {error}", path.display()))
+    })?;
+
+    debug!(
+        target: DISPATCH_TARGET,
+        uri = %uri.as_str(),
+        language = %language,
+        "handling verify diagnostics"
+    );
+
+    backends
+        .ensure_started(BackendKind::Semantic)
+        .map_err(DispatchError::backend_startup)?;
+    writer.check_cancelled(AFTER_BACKEND_STARTUP)?;
+
+    let document = OpenDocument {
+        language,
+        uri: uri.clone(),
+        text,
+    };
+    let diagnostics = backends
+        .provider()
+        .with_lsp_host_mut(|host| collect_diagnostics(host, document, wait))?
+        .ok_or_else(|| DispatchError::internal("LSP host not initialized after backend start"))??;
+
+    let json = serde_json::to_string(&DiagnosticsResponse::new(&uri, &diagnostics))?;
+    writer.write_stdout(json)?;
+
+    Ok(DispatchResult::success())
+}
+
+/// Parses the single `--uri <URI>` argument.
+fn parse_uri_argument(arguments: &[String]) -> Result<(Url, Uri), DispatchError> {
+    let mut uri = None;
+    let mut iter = arguments.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--uri" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| DispatchError::invalid_arguments("--uri requires a value"))?;
+                uri = Some(value);
+            }
+            other => {
+                return Err(DispatchError::invalid_arguments(format!(
+                    "unknown argument: {other}"
+                )));
+            }
+        }
+    }
+
+    let value = uri.ok_or_else(|| DispatchError::invalid_arguments("missing required --uri"))?;
+    let invalid = || DispatchError::invalid_arguments(format!("invalid URI: {value}"));
+    let url = Url::parse(value).map_err(|_| invalid())?;
+    let uri = value.parse().map_err(|_| invalid())?;
+    Ok((url, uri))
+}
+
+fn resolve_file_path(uri: &Url) -> Result<PathBuf, DispatchError> {
+    if uri.scheme() != "file" {
+        return Err(DispatchError::invalid_arguments(format!(
+            "unsupported URI scheme '{}': expected file",
+            uri.scheme()
+        )));
+    }
+
+    uri.to_file_path().map_err(|_| {
+        DispatchError::invalid_arguments(format!("URI is not a valid file path: {uri}"))
+    })
+}
+
+/// Opens the document, waits for its diagnostics, and closes it again.
+///
+/// The document is closed even when collection fails; the collection error
+/// takes precedence over a failure to close.
+fn collect_diagnostics(
+    host: &mut LspHost,
+    document: OpenDocument,
+    wait: DiagnosticsWait,
+) -> Result<Vec<Diagnostic>, DispatchError> {
+    let OpenDocument {
+        language,
+        uri,
+        text,
+    } = document;
+    let lsp_error = |operation: &str, error: LspHostError| {
+        DispatchError::lsp_host(language.as_str(), format!("{operation} failed: {error}"))
+    };
+
+    host.initialize(language)
+        .map_err(|error| lsp_error("initialization", error))?;
+    host.did_open(
+        language,
+        DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: uri.clone(),
+                language_id: language.as_str().to_owned(),
+                version: 1,
+                text,
+            },
+        },
+    )
+    .map_err(|error| lsp_error("didOpen", error))?;
+
+    let collected = wait_for_diagnostics(host, language, &uri, wait);
+    let closed = host
+        .did_close(
+            language,
+            DidCloseTextDocumentParams {
+                text_document: TextDocumentIdentifier { uri },
+            },
+        )
+        .map_err(|error| lsp_error("didClose", error));
+
+    let diagnostics = collected?;
+    closed?;
+    Ok(diagnostics)
+}
+
+/// Polls the host until the server reports diagnostics for `uri`.
+///
+/// Only server errors are retried; unknown languages and missing
+/// capabilities fail straight away.
+fn wait_for_diagnostics(
+    host: &mut LspHost,
+    language: Language,
+    uri: &Uri,
+    wait: DiagnosticsWait,
+) -> Result<Vec<Diagnostic>, DispatchError> {
+    let deadline = Instant::now() + wait.timeout;
+    loop {
+        match host.diagnostics(language, uri.clone()) {
+            Ok(diagnostics) => return Ok(diagnostics),
+            Err(LspHostError::Server { source, .. }) => {
+                if Instant::now() >= deadline {
+                    return Err(DispatchError::diagnostics_timeout(
+                        uri.as_str(),
+                        wait.timeout,
+                        source.to_string(),
+                    ));
+                }
+                thread::sleep(wait.poll_interval);
+            }
+            Err(error) => {
+                return Err(DispatchError::lsp_host(
+                    language.as_str(),
+                    format!("diagnostics failed: {error}"),
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "diagnostics_tests.rs"]
+mod tests;
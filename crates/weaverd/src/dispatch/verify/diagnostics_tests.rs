@@ -0,0 +1,157 @@
+//! Unit tests for `verify::diagnostics`.
+
+use lsp_types::{Diagnostic, DiagnosticSeverity, Position, Range};
+use rstest::rstest;
+use tempfile::TempDir;
+use weaver_lsp_host::{
+    ServerCapabilitySet,
+    test_support::{CallKind, RecordingLanguageServer, RecordingServerHandle, ResponseSet},
+};
+
+use super::*;
+use crate::{
+    dispatch::{observe::test_support::semantic_backends_with_server, request::CommandDescriptor},
+    tests::support::{fs as test_fs, stream::stdout_payload},
+};
+
+const NO_WAIT: DiagnosticsWait = DiagnosticsWait {
+    timeout: Duration::ZERO,
+    poll_interval: Duration::ZERO,
+};
+
+fn diagnostics_request(arguments: &[&str]) -> CommandRequest {
+    CommandRequest {
+        command: CommandDescriptor {
+            domain: String::from("verify"),
+            operation: String::from("diagnostics"),
+        },
+        arguments: arguments
+            .iter()
+            .map(|&argument| argument.to_owned())
+            .collect(),
+        patch: None,
+    }
+}
+
+fn diagnostic(
+    line: u32,
+    character: u32,
+    message: &str,
+    severity: DiagnosticSeverity,
+) -> Diagnostic {
+    let position = Position { line, character };
+    Diagnostic {
+        range: Range {
+            start: position,
+            end: position,
+        },
+        severity: Some(severity),
+        message: String::from(message),
+        ..Diagnostic::default()
+    }
+}
+
+/// Writes `main.rs` into a fresh workspace and returns its file URI.
+fn workspace_file() -> (TempDir, String) {
+    let workspace = TempDir::new().expect("workspace");
+    let path = workspace.path().join("main.rs");
+    test_fs::write(&path, "fn main() {}\n").expect("write main.rs");
+    let uri = Url::from_file_path(&path).expect("file uri").to_string();
+    (workspace, uri)
+}
+
+fn run_with_responses(
+    responses: ResponseSet,
+    arguments: &[&str],
+) -> (
+    Result<DispatchResult, DispatchError>,
+    String,
+    RecordingServerHandle,
+) {
+    let server =
+        RecordingLanguageServer::new(ServerCapabilitySet::new(false, false, true), responses);
+    let recorder = server.handle();
+    let (mut backends, _dir) =
+        semantic_backends_with_server(Language::Rust, server).expect("backends");
+    let mut output = Vec::new();
+    let mut writer = ResponseWriter::new(&mut output);
+    let result = handle_with_wait(
+        &diagnostics_request(arguments),
+        &mut writer,
+        &mut backends,
+        NO_WAIT,
+    );
+    (
+        result,
+        String::from_utf8(output).expect("utf-8 output"),
+        recorder,
+    )
+}
+
+#[rstest]
+fn reports_published_diagnostics() {
+    let (_workspace, uri) = workspace_file();
+    let responses = ResponseSet {
+        diagnostics: vec![
+            diagnostic(0, 3, "unused function", DiagnosticSeverity::WARNING),
+            diagnostic(4, 0, "mismatched types", DiagnosticSeverity::ERROR),
+        ],
+        ..ResponseSet::default()
+    };
+
+    let (result, output, server) = run_with_responses(responses, &["--uri", &uri]);
+
+    assert_eq!(result.expect("diagnostics").status, 0);
+    assert_eq!(
+        stdout_payload(&output),
+        serde_json::json!({"diagnostics": [
+            {"uri": uri, "line": 1, "column": 4, "message": "unused function", "severity": "warning"},
+            {"uri": uri, "line": 5, "column": 1, "message": "mismatched types", "severity": "error"},
+        ]})
+    );
+    assert_eq!(
+        server.calls(),
+        vec![
+            CallKind::Initialise,
+            CallKind::DidOpen,
+            CallKind::Diagnostics,
+            CallKind::DidClose,
+        ]
+    );
+}
+
+#[rstest]
+fn times_out_when_no_diagnostics_are_published() {
+    let (_workspace, uri) = workspace_file();
+    let responses = ResponseSet {
+        diagnostics_error: Some(String::from("server is still indexing")),
+        ..ResponseSet::default()
+    };
+
+    let (result, output, server) = run_with_responses(responses, &["--uri", &uri]);
+
+    let error = result.expect_err("diagnostics should time out");
+    assert!(
+        matches!(&error, DispatchError::DiagnosticsTimeout { message, .. }
+            if message.contains("server is still indexing")),
+        "unexpected error: {error:?}"
+    );
+    assert_eq!(error.exit_status(), 1);
+    assert!(output.is_empty(), "nothing should be written: {output}");
+    assert_eq!(server.calls().last(), Some(&CallKind::DidClose));
+}
+
+#[rstest]
+#[case::missing_uri(&[])]
+#[case::unknown_flag(&["--uri", "file:///src/main.rs", "--position", "1:1"])]
+#[case::not_a_file_uri(&["--uri", "https://example.com/main.rs"])]
+#[case::missing_file(&["--uri", "file:///definitely/missing/main.rs"])]
+fn rejects_invalid_requests(#[case] arguments: &[&str]) {
+    let (result, _output, server) = run_with_responses(ResponseSet::default(), arguments);
+
+    assert!(matches!(
+        result,
+        Err(DispatchError::InvalidArguments { .. })
+    ));
+    assert!(server.calls().is_empty());
+}
@@ -0,0 +1,7 @@
+//! Handlers for the `verify` domain.
+//!
+//! This module contains operation handlers that check code correctness,
+//! starting with language-server diagnostics for a single document.
+
+pub mod diagnostics;
+pub mod responses;
@@ -0,0 +1,71 @@
+//! Response types for verify domain operations.
+//!
+//! This module converts LSP diagnostics into the JSON format documented in
+//! the users guide and rendered by the CLI.
+
+use lsp_types::{Diagnostic, DiagnosticSeverity, Uri};
+use serde::Serialize;
+
+/// Payload for `verify diagnostics`.
+///
+/// ```json
+/// {"diagnostics":[{"uri":"file:///src/main.rs","line":12,"column":5,"message":"...","severity":"error"}]}
+/// ```
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct DiagnosticsResponse {
+    /// Diagnostics reported for the document, in server order.
+    pub diagnostics: Vec<DiagnosticItem>,
+}
+
+impl DiagnosticsResponse {
+    /// Builds the payload for diagnostics published against `uri`.
+    #[must_use]
+    pub fn new(uri: &Uri, diagnostics: &[Diagnostic]) -> Self {
+        Self {
+            diagnostics: diagnostics
+                .iter()
+                .map(|diagnostic| DiagnosticItem::new(uri, diagnostic))
+                .collect(),
+        }
+    }
+}
+
+/// A single diagnostic in the response format.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct DiagnosticItem {
+    /// The document URI the diagnostic belongs to.
+    pub uri: String,
+    /// Line number (1-indexed).
+    pub line: u32,
+    /// Column number (1-indexed).
+    pub column: u32,
+    /// Human-readable diagnostic message.
+    pub message: String,
+    /// Severity label, omitted when the server does not report one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<&'static str>,
+}
+
+impl DiagnosticItem {
+    fn new(uri: &Uri, diagnostic: &Diagnostic) -> Self {
+        let start = diagnostic.range.start;
+        Self {
+            uri: uri.to_string(),
+            // Convert from 0-indexed (LSP) to 1-indexed (user-facing)
+            line: start.line.saturating_add(1),
+            column: start.character.saturating_add(1),
+            message: diagnostic.message.clone(),
+            severity: diagnostic.severity.and_then(severity_label),
+        }
+    }
+}
+
+fn severity_label(severity: DiagnosticSeverity) -> Option<&'static str> {
+    match severity {
+        DiagnosticSeverity::ERROR => Some("error"),
+        DiagnosticSeverity::WARNING => Some("warning"),
+        DiagnosticSeverity::INFORMATION => Some("information"),
+        DiagnosticSeverity::HINT => Some("hint"),
+        _ => None,
+    }
+}
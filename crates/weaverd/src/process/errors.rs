@@ -27,6 +27,14 @@ pub enum LaunchError {
         #[source]
         source: io::Error,
     },
+    /// The configured workspace root cannot be used.
+    #[error("configured workspace root '{path}' {reason}")]
+    InvalidWorkspaceRoot {
+        /// Configured workspace root.
+        path: PathBuf,
+        /// Why the root was rejected.
+        reason: String,
+    },
     /// Preparing the socket filesystem failed.
     #[error("failed to prepare daemon socket: {source}")]
     Socket {
@@ -9,6 +9,7 @@ use std::{
 use tracing::info;
 use weaver_cards::DEFAULT_CACHE_CAPACITY;
 use weaver_config::RuntimePaths;
+use weaver_plugins::process::SandboxExecutor;
 
 use super::{
     FOREGROUND_ENV_VAR,
@@ -17,12 +18,13 @@ use super::{
     daemonizer::{Daemonizer, SystemDaemonizer},
     errors::LaunchError,
     guard::{HealthState, ProcessGuard},
-    shutdown::{ShutdownSignal, SystemShutdownSignal},
+    shutdown::{ShutdownSignal, SystemShutdownSignal, wait_for_shutdown_or_idle},
+    workspace::resolve_workspace_root,
 };
 use crate::{
     StructuredHealthReporter,
     bootstrap::{ConfigLoader, StaticConfigLoader, SystemConfigLoader, bootstrap_with},
-    dispatch::{BackendManager, DispatchConnectionHandler},
+    dispatch::{BackendManager, DispatchConnectionHandler, check_plugin_health},
     health::HealthReporter,
     semantic_provider::SemanticBackendProvider,
     transport::SocketListener,
@@ -91,7 +93,7 @@ pub(crate) fn run_daemon_with<L, D, S>(plan: LaunchPlan<L, D, S>) -> Result<(),
 where
     L: ConfigLoader,
     D: Daemonizer,
-    S: ShutdownSignal,
+    S: ShutdownSignal + 'static,
 {
     let LaunchPlan { process, services } = plan;
     let ProcessControl {
@@ -117,15 +119,16 @@ where
             }
         })?;
     let mut guard = ProcessGuard::acquire(runtime_dir, runtime_paths)?;
-    let workspace_root =
-        env::current_dir().map_err(|source| LaunchError::WorkspaceRoot { source })?;
+    // Resolved before daemonising, which changes the working directory.
+    let workspace_root = resolve_workspace_root(config.workspace_root())?;
     if matches!(mode, LaunchMode::Background) {
         daemonizer.daemonize(guard.paths())?;
     }
     let pid = std::process::id();
     guard.write_pid(pid)?;
     guard.write_health(HealthState::Starting)?;
-    let listener = SocketListener::bind(config.daemon_socket())?;
+    let listener =
+        SocketListener::bind(config.daemon_socket())?.with_workers(config.daemon_workers());
 
     // Create a single provider and backends instance shared by daemon and dispatch
     let provider =
@@ -136,21 +139,32 @@ where
     // Create backend manager using the same backends from the daemon
     let backends = Arc::new(Mutex::new(daemon.into_backends()));
     let backend_manager = BackendManager::new(backends);
+    let plugin_executor = config
+        .plugin_inline_limit()
+        .map_or_else(SandboxExecutor::default, |limit| {
+            SandboxExecutor::default().with_inline_limit(limit)
+        });
     let handler = Arc::new(
-        DispatchConnectionHandler::new(
+        DispatchConnectionHandler::with_plugin_executor(
             backend_manager,
             workspace_root,
             config.daemon_socket().to_string(),
             guard.paths().runtime_dir().to_path_buf(),
+            plugin_executor,
         )
         .map_err(|error| LaunchError::WorkspaceRoot {
             source: io::Error::new(io::ErrorKind::InvalidInput, error.to_string()),
         })?,
     );
 
+    // Plugins are spawned through the sandbox, which needs a single-threaded
+    // caller, so they are pinged before the listener starts its workers.
+    check_plugin_health(&workspace_root, plugin_executor);
     let listener_handle = listener.start(handler)?;
     guard.write_health(HealthState::Ready)?;
-    shutdown.wait()?;
+    wait_for_shutdown_or_idle(shutdown, config.daemon_idle_timeout(), || {
+        listener_handle.idle_for()
+    })?;
     guard.write_health(HealthState::Stopping)?;
     listener_handle.shutdown();
     listener_handle.join()?;
@@ -8,6 +8,7 @@ mod files;
 mod guard;
 pub(crate) mod launch;
 pub(crate) mod shutdown;
+mod workspace;
 
 pub use errors::LaunchError;
 pub use launch::{LaunchMode, run_daemon};
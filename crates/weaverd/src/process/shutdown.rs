@@ -1,13 +1,21 @@
 //! Handles OS-level shutdown signals for the daemon lifecycle.
+//!
+//! Besides termination signals, the daemon can shut itself down once it has
+//! had no client connections for a configured idle timeout.
 
-use std::{io, time::Duration};
+use std::{
+    io,
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
 
 use signal_hook::{
     consts::signal::{SIGHUP, SIGINT, SIGQUIT, SIGTERM},
     iterator::Signals,
 };
 use thiserror::Error;
-use tracing::info;
+use tracing::{info, warn};
 
 use super::PROCESS_TARGET;
 
@@ -55,3 +63,55 @@ impl ShutdownSignal for SystemShutdownSignal {
         Ok(())
     }
 }
+
+/// How often the idle timeout is re-evaluated while waiting for shutdown.
+const IDLE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Blocks until `shutdown` fires or the daemon has been idle for long enough.
+///
+/// `idle_for` reports how long the daemon has had no connections in flight,
+/// or `None` while requests are being served. Without an `idle_timeout` this
+/// simply waits on `shutdown`. Otherwise the signal is awaited on a helper
+/// thread, which is abandoned if the idle timeout fires first; the process
+/// exits shortly afterwards, so the blocked signal listener is never resumed.
+pub(crate) fn wait_for_shutdown_or_idle<S>(
+    shutdown: S,
+    idle_timeout: Option<Duration>,
+    idle_for: impl Fn() -> Option<Duration>,
+) -> Result<(), ShutdownError>
+where
+    S: ShutdownSignal + 'static,
+{
+    let Some(idle_timeout) = idle_timeout else {
+        return shutdown.wait();
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        // The receiver is gone once the idle timeout has fired.
+        let _ = sender.send(shutdown.wait());
+    });
+
+    loop {
+        match receiver.recv_timeout(IDLE_POLL_INTERVAL) {
+            Ok(result) => return result,
+            Err(RecvTimeoutError::Disconnected) => {
+                warn!(
+                    target: PROCESS_TARGET,
+                    "shutdown signal listener stopped unexpectedly"
+                );
+                return Ok(());
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if idle_for().is_some_and(|idle| idle >= idle_timeout) {
+                    info!(
+                        target: PROCESS_TARGET,
+                        idle_timeout_secs = idle_timeout.as_secs(),
+                        "idle timeout elapsed without client connections; shutting down"
+                    );
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! Resolves the workspace root the daemon serves requests against.
+//!
+//! Dispatch handlers and plugin path resolution join relative paths onto this
+//! root, so it is fixed once at startup rather than read from the process
+//! working directory on each request. A configured root must be an absolute
+//! path to an existing directory; without one the daemon falls back to the
+//! working directory it was launched from.
+
+use std::{env, path::PathBuf};
+
+use camino::Utf8Path;
+use cap_std::fs::Dir;
+
+use super::errors::LaunchError;
+
+/// Returns the configured workspace root, or the working directory if unset.
+///
+/// # Errors
+///
+/// Returns [`LaunchError::InvalidWorkspaceRoot`] if the configured root is
+/// relative or does not name an accessible directory, and
+/// [`LaunchError::WorkspaceRoot`] if the working directory cannot be read.
+pub(super) fn resolve_workspace_root(
+    configured: Option<&Utf8Path>,
+) -> Result<PathBuf, LaunchError> {
+    let Some(root) = configured else {
+        return env::current_dir().map_err(|source| LaunchError::WorkspaceRoot { source });
+    };
+    let invalid = |reason: String| LaunchError::InvalidWorkspaceRoot {
+        path: root.as_std_path().to_path_buf(),
+        reason,
+    };
+    if !root.is_absolute() {
+        return Err(invalid(String::from("must be an absolute path")));
+    }
+    Dir::open_ambient_dir(root, cap_std::ambient_authority())
+        .map_err(|error| invalid(format!("is not an accessible directory: {error}")))?;
+    Ok(root.as_std_path().to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for workspace root resolution.
+
+    use camino::Utf8PathBuf;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::tests::support::fs as test_fs;
+
+    fn utf8_path(directory: &TempDir) -> Utf8PathBuf {
+        Utf8PathBuf::from_path_buf(directory.path().to_path_buf()).expect("utf-8 temp path")
+    }
+
+    #[test]
+    fn configured_root_is_used_instead_of_working_directory() {
+        let workspace = TempDir::new().expect("workspace");
+        let root = utf8_path(&workspace);
+
+        let resolved = resolve_workspace_root(Some(&root)).expect("valid root");
+
+        assert_eq!(resolved, workspace.path());
+        assert_ne!(env::current_dir().ok(), Some(resolved));
+    }
+
+    #[test]
+    fn unset_root_falls_back_to_working_directory() {
+        let resolved = resolve_workspace_root(None).expect("working directory");
+
+        assert_eq!(Some(resolved), env::current_dir().ok());
+    }
+
+    #[test]
+    fn relative_root_is_rejected() {
+        let error = resolve_workspace_root(Some(Utf8Path::new("workspace")))
+            .expect_err("relative root should be rejected");
+
+        assert!(
+            error.to_string().contains("must be an absolute path"),
+            "unexpected error: {error}"
+        );
+    }
+
+    #[test]
+    fn missing_or_file_roots_are_rejected() {
+        let workspace = TempDir::new().expect("workspace");
+        let root = utf8_path(&workspace);
+        let file = root.join("notes.txt");
+        test_fs::write(&file, "notes\n").expect("write file");
+
+        for candidate in [root.join("missing"), file] {
+            let error = resolve_workspace_root(Some(&candidate))
+                .expect_err("non-directory root should be rejected");
+
+            assert!(
+                error.to_string().contains("is not an accessible directory"),
+                "unexpected error for {candidate}: {error}"
+            );
+        }
+    }
+}
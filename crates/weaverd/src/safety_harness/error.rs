@@ -22,6 +22,37 @@ pub struct VerificationFailure {
     column: Option<u32>,
     /// Human-readable message describing the problem.
     message: String,
+    /// How the failure compares with the diagnostics present before the edit.
+    baseline: Option<BaselineComparison>,
+}
+
+/// Relates a semantic diagnostic to the file's pre-edit diagnostics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BaselineComparison {
+    is_new: bool,
+    preexisting_count: usize,
+}
+
+impl BaselineComparison {
+    /// Builds a comparison for a diagnostic.
+    ///
+    /// `preexisting_count` is the number of diagnostics of failing severity
+    /// the file already had before the edit.
+    #[must_use]
+    pub const fn new(is_new: bool, preexisting_count: usize) -> Self {
+        Self {
+            is_new,
+            preexisting_count,
+        }
+    }
+
+    /// Whether the edit introduced the diagnostic.
+    #[must_use]
+    pub const fn is_new(&self) -> bool { self.is_new }
+
+    /// Number of failing-severity diagnostics present before the edit.
+    #[must_use]
+    pub const fn preexisting_count(&self) -> usize { self.preexisting_count }
 }
 
 impl VerificationFailure {
@@ -33,6 +64,7 @@ impl VerificationFailure {
             line: None,
             column: None,
             message: message.into(),
+            baseline: None,
         }
     }
 
@@ -44,6 +76,13 @@ impl VerificationFailure {
         self
     }
 
+    /// Records how this failure compares with the pre-edit diagnostics.
+    #[must_use]
+    pub const fn with_baseline(mut self, baseline: BaselineComparison) -> Self {
+        self.baseline = Some(baseline);
+        self
+    }
+
     /// Path to the affected file.
     #[must_use]
     pub fn file(&self) -> &Path { &self.file }
@@ -59,6 +98,10 @@ impl VerificationFailure {
     /// Human-readable message describing the problem.
     #[must_use]
     pub fn message(&self) -> &str { &self.message }
+
+    /// Comparison with the pre-edit diagnostics, for semantic failures.
+    #[must_use]
+    pub const fn baseline(&self) -> Option<BaselineComparison> { self.baseline }
 }
 
 impl std::fmt::Display for VerificationFailure {
@@ -31,7 +31,7 @@ mod transaction;
 mod verification;
 
 pub use edit::{FileEdit, Position, TextEdit, TextRange};
-pub use error::{SafetyHarnessError, VerificationFailure};
+pub use error::{BaselineComparison, SafetyHarnessError, VerificationFailure};
 pub use locks::{SemanticLockResult, SyntacticLockResult};
 pub use transaction::{ContentChange, ContentTransaction, EditTransaction, TransactionOutcome};
 pub use verification::{
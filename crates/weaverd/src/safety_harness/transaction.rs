@@ -44,6 +44,11 @@ pub enum TransactionOutcome {
         /// Details about the new diagnostics.
         failures: Vec<VerificationFailure>,
     },
+    /// All checks passed and, as requested, no changes were committed.
+    Validated {
+        /// Files that committing would modify, writes before deletions.
+        files: Vec<PathBuf>,
+    },
     /// No edits were provided.
     NoChanges,
 }
@@ -127,6 +132,33 @@ impl<'a> ContentTransaction<'a> {
         self,
         workspace_dir: &Dir,
         workspace_root: &Path,
+    ) -> Result<TransactionOutcome, SafetyHarnessError> {
+        self.run(workspace_dir, workspace_root, true)
+    }
+
+    /// Runs both locks over the changes without committing them.
+    ///
+    /// Passing changes report [`TransactionOutcome::Validated`], so callers
+    /// can preview a change set that [`Self::execute`] would commit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when:
+    /// - A file cannot be read.
+    /// - The semantic backend is unavailable.
+    pub fn validate_only(
+        self,
+        workspace_dir: &Dir,
+        workspace_root: &Path,
+    ) -> Result<TransactionOutcome, SafetyHarnessError> {
+        self.run(workspace_dir, workspace_root, false)
+    }
+
+    fn run(
+        self,
+        workspace_dir: &Dir,
+        workspace_root: &Path,
+        commit: bool,
     ) -> Result<TransactionOutcome, SafetyHarnessError> {
         let ContentTransaction {
             changes,
@@ -165,6 +197,7 @@ impl<'a> ContentTransaction<'a> {
             workspace_root,
             syntactic_lock,
             semantic_lock,
+            commit,
         })
     }
 }
@@ -250,6 +283,7 @@ impl<'a> EditTransaction<'a> {
             workspace_root,
             syntactic_lock: self.syntactic_lock,
             semantic_lock: self.semantic_lock,
+            commit: true,
         })
     }
 }
@@ -294,6 +328,16 @@ fn execute_with_locks(
         return Ok(TransactionOutcome::SemanticLockFailed { failures });
     }
 
+    if !execution.commit {
+        let files = execution
+            .paths_to_write
+            .iter()
+            .cloned()
+            .chain(execution.deletions.iter().map(|plan| plan.path.clone()))
+            .collect();
+        return Ok(TransactionOutcome::Validated { files });
+    }
+
     commit_changes_with_deletes(CommitPlan {
         dir: execution.workspace_dir,
         workspace_root: execution.workspace_root,
@@ -309,8 +353,9 @@ fn execute_with_locks(
 
 /// Parameter object for executing the Double-Lock pipeline.
 ///
-/// Bundles the verification context, files to write, planned deletions, and
-/// the syntactic/semantic lock interfaces used by the pipeline.
+/// Bundles the verification context, files to write, planned deletions, the
+/// syntactic/semantic lock interfaces used by the pipeline, and whether to
+/// commit once both locks pass.
 struct TransactionExecution<'a> {
     context: &'a VerificationContext,
     paths_to_write: &'a [PathBuf],
@@ -319,6 +364,7 @@ struct TransactionExecution<'a> {
     workspace_root: &'a Path,
     syntactic_lock: &'a dyn SyntacticLock,
     semantic_lock: &'a dyn SemanticLock,
+    commit: bool,
 }
 
 pub(super) fn relative_workspace_path<'a>(
@@ -64,8 +64,11 @@ pub(super) struct CommitPlan<'a> {
 /// Phase 1 (prepare): All modified content is written to temporary files.
 /// Phase 2 (commit): Temporary files are atomically renamed to targets.
 ///
-/// If any rename fails, all previously renamed files are rolled back to
-/// their original content. This provides multi-file transaction semantics.
+/// If preparing any file fails, the temporary files written so far are
+/// removed and no target is touched. If any rename fails, all previously
+/// renamed files are rolled back to their original content, which is itself
+/// staged and renamed into place. This provides multi-file transaction
+/// semantics.
 ///
 /// # Rollback Limitations
 ///
@@ -76,24 +79,13 @@ pub(super) fn commit_changes_with_deletes(plan: CommitPlan<'_>) -> Result<(), Sa
     let mut prepared: Vec<PreparedFile> = Vec::new();
 
     for path in plan.paths {
-        let content = plan
-            .context
-            .modified(path)
-            .ok_or_else(|| SafetyHarnessError::ModifiedContentMissing { path: path.clone() })?;
-
-        let original = plan
-            .context
-            .original(path)
-            .cloned()
-            .ok_or_else(|| SafetyHarnessError::OriginalContentMissing { path: path.clone() })?;
-        let existed = file_exists(plan.dir, plan.workspace_root, path)?;
-        let temp_path = prepare_file(plan.dir, plan.workspace_root, path, content)?;
-        prepared.push(PreparedFile {
-            path: path.clone(),
-            temp_path,
-            original,
-            existed,
-        });
+        match prepare_planned_file(&plan, path) {
+            Ok(prepared_file) => prepared.push(prepared_file),
+            Err(error) => {
+                cleanup_prepared_temp_files(plan.dir, &prepared);
+                return Err(error);
+            }
+        }
     }
     // Phase 2: Commit all files (atomic renames)
     let committed = persist_prepared_files(plan.dir, plan.workspace_root, prepared)?;
@@ -101,6 +93,32 @@ pub(super) fn commit_changes_with_deletes(plan: CommitPlan<'_>) -> Result<(), Sa
     Ok(())
 }
 
+/// Stages the modified content for `path` and records what rollback needs.
+fn prepare_planned_file(
+    plan: &CommitPlan<'_>,
+    path: &Path,
+) -> Result<PreparedFile, SafetyHarnessError> {
+    let content =
+        plan.context
+            .modified(path)
+            .ok_or_else(|| SafetyHarnessError::ModifiedContentMissing {
+                path: path.to_path_buf(),
+            })?;
+    let original = plan.context.original(path).cloned().ok_or_else(|| {
+        SafetyHarnessError::OriginalContentMissing {
+            path: path.to_path_buf(),
+        }
+    })?;
+    let existed = file_exists(plan.dir, plan.workspace_root, path)?;
+    let temp_path = prepare_file(plan.dir, plan.workspace_root, path, content)?;
+    Ok(PreparedFile {
+        path: path.to_path_buf(),
+        temp_path,
+        original,
+        existed,
+    })
+}
+
 /// Persists prepared temp files, rolling back if any commit fails.
 ///
 /// Paths have already been validated by apply-patch before reaching this
@@ -196,28 +214,39 @@ fn prepare_file(
             .map_err(|err| SafetyHarnessError::file_write(path.to_path_buf(), err))?;
     }
 
+    stage_temp_file(dir, relative, content)
+        .map_err(|err| SafetyHarnessError::file_write(path.to_path_buf(), err))
+}
+
+/// Writes `content` to a fresh temporary file beside `relative`.
+fn stage_temp_file(dir: &Dir, relative: &Path, content: &str) -> io::Result<PathBuf> {
     for attempt in 0..16 {
-        let temp_path = unique_temp_path(relative, attempt)
-            .map_err(|err| SafetyHarnessError::file_write(path.to_path_buf(), err))?;
+        let temp_path = unique_temp_path(relative, attempt)?;
         match write_temp_file(dir, temp_path.as_path(), content) {
             Ok(()) => return Ok(temp_path),
             Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
             Err(err) => {
                 let _ = dir.remove_file(temp_path.as_path());
-                return Err(SafetyHarnessError::file_write(path.to_path_buf(), err));
+                return Err(err);
             }
         }
     }
 
-    Err(SafetyHarnessError::file_write(
-        path.to_path_buf(),
-        io::Error::new(
-            io::ErrorKind::AlreadyExists,
-            "could not create a unique transaction temporary file",
-        ),
+    Err(io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        "could not create a unique transaction temporary file",
     ))
 }
 
+/// Restores `content` at `relative` by staging it and renaming it into place,
+/// so an interrupted rollback never leaves a half-written file.
+fn restore_file(dir: &Dir, relative: &Path, content: &str) -> io::Result<()> {
+    let temp_path = stage_temp_file(dir, relative, content)?;
+    dir.rename(&temp_path, dir, relative).inspect_err(|_| {
+        cleanup_temp_file(dir, &temp_path);
+    })
+}
+
 /// Rolls back committed files to their original content.
 ///
 /// This is a best-effort operation: if restoration fails for any file,
@@ -238,7 +267,7 @@ fn rollback_writes(dir: &Dir, workspace_root: &Path, committed: &[CommittedFile]
             }
         } else {
             // Restore original content (best effort)
-            if let Err(err) = dir.write(relative, &committed_file.original) {
+            if let Err(err) = restore_file(dir, relative, &committed_file.original) {
                 warn!(
                     path = %committed_file.path.display(),
                     error = %err,
@@ -260,7 +289,7 @@ fn rollback_deletes_and_writes(
         let Ok(relative) = relative_workspace_path(&deletion.path, workspace_root) else {
             continue;
         };
-        if let Err(err) = dir.write(relative, &deletion.original) {
+        if let Err(err) = restore_file(dir, relative, &deletion.original) {
             warn!(
                 path = %deletion.path.display(),
                 error = %err,
@@ -311,63 +340,4 @@ fn unique_temp_path(relative: &Path, attempt: u8) -> io::Result<PathBuf> {
 }
 
 #[cfg(test)]
-mod tests {
-    //! Tests for transaction commit cleanup helpers.
-
-    use cap_std::ambient_authority;
-    use rstest::{fixture, rstest};
-    use tempfile::TempDir;
-
-    use super::*;
-
-    #[fixture]
-    fn workspace_dir() -> Result<(TempDir, Dir), String> {
-        let tempdir =
-            tempfile::tempdir().map_err(|e| format!("create temporary directory: {e}"))?;
-        let dir = Dir::open_ambient_dir(tempdir.path(), ambient_authority())
-            .map_err(|e| format!("open temporary directory capability: {e}"))?;
-        Ok((tempdir, dir))
-    }
-
-    fn prepared_file(path: &str, temp_path: &str) -> PreparedFile {
-        PreparedFile {
-            path: PathBuf::from(path),
-            temp_path: PathBuf::from(temp_path),
-            original: String::new(),
-            existed: false,
-        }
-    }
-
-    #[rstest]
-    #[case::seeded(true)]
-    #[case::missing(false)]
-    fn cleanup_prepared_temp_files_handles_seeded_and_missing_temps(
-        #[case] seed_temps: bool,
-        workspace_dir: Result<(TempDir, Dir), String>,
-    ) -> Result<(), String> {
-        let (_tempdir, dir) = workspace_dir?;
-        let prepared = [
-            prepared_file("first.txt", ".first.tmp"),
-            prepared_file("second.txt", ".second.tmp"),
-        ];
-
-        if seed_temps {
-            dir.write(".first.tmp", "first")
-                .map_err(|e| format!("write first temporary file: {e}"))?;
-            dir.write(".second.tmp", "second")
-                .map_err(|e| format!("write second temporary file: {e}"))?;
-        }
-
-        cleanup_prepared_temp_files(&dir, &prepared);
-
-        assert!(matches!(
-            dir.metadata(".first.tmp"),
-            Err(err) if err.kind() == io::ErrorKind::NotFound
-        ));
-        assert!(matches!(
-            dir.metadata(".second.tmp"),
-            Err(err) if err.kind() == io::ErrorKind::NotFound
-        ));
-        Ok(())
-    }
-}
+mod tests;
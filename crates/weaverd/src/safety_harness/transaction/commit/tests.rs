@@ -0,0 +1,122 @@
+//! Tests for transaction commit and rollback helpers.
+
+use cap_std::ambient_authority;
+use rstest::{fixture, rstest};
+use tempfile::TempDir;
+
+use super::*;
+
+#[fixture]
+fn workspace_dir() -> Result<(TempDir, Dir), String> {
+    let tempdir = tempfile::tempdir().map_err(|e| format!("create temporary directory: {e}"))?;
+    let dir = Dir::open_ambient_dir(tempdir.path(), ambient_authority())
+        .map_err(|e| format!("open temporary directory capability: {e}"))?;
+    Ok((tempdir, dir))
+}
+
+fn prepared_file(path: &str, temp_path: &str) -> PreparedFile {
+    PreparedFile {
+        path: PathBuf::from(path),
+        temp_path: PathBuf::from(temp_path),
+        original: String::new(),
+        existed: false,
+    }
+}
+
+#[rstest]
+#[case::seeded(true)]
+#[case::missing(false)]
+fn cleanup_prepared_temp_files_handles_seeded_and_missing_temps(
+    #[case] seed_temps: bool,
+    workspace_dir: Result<(TempDir, Dir), String>,
+) -> Result<(), String> {
+    let (_tempdir, dir) = workspace_dir?;
+    let prepared = [
+        prepared_file("first.txt", ".first.tmp"),
+        prepared_file("second.txt", ".second.tmp"),
+    ];
+
+    if seed_temps {
+        dir.write(".first.tmp", "first")
+            .map_err(|e| format!("write first temporary file: {e}"))?;
+        dir.write(".second.tmp", "second")
+            .map_err(|e| format!("write second temporary file: {e}"))?;
+    }
+
+    cleanup_prepared_temp_files(&dir, &prepared);
+
+    assert!(matches!(
+        dir.metadata(".first.tmp"),
+        Err(err) if err.kind() == io::ErrorKind::NotFound
+    ));
+    assert!(matches!(
+        dir.metadata(".second.tmp"),
+        Err(err) if err.kind() == io::ErrorKind::NotFound
+    ));
+    Ok(())
+}
+
+fn leftover_temp_files(dir: &Dir) -> Result<Vec<String>, String> {
+    let mut leftovers = Vec::new();
+    for entry in dir.entries().map_err(|e| format!("list workspace: {e}"))? {
+        let name = entry
+            .map_err(|e| format!("read workspace entry: {e}"))?
+            .file_name()
+            .to_string_lossy()
+            .into_owned();
+        if name.ends_with(".tmp") {
+            leftovers.push(name);
+        }
+    }
+    Ok(leftovers)
+}
+
+/// Fails the second write either while staging it, because its parent is a
+/// regular file, or while renaming it, because its target is a non-empty
+/// directory.
+#[rstest]
+#[case::staging_fails("blocker/second.rs")]
+#[case::rename_fails("occupied")]
+fn failed_second_write_keeps_first_file_intact(
+    #[case] second: &str,
+    workspace_dir: Result<(TempDir, Dir), String>,
+) -> Result<(), String> {
+    let (tempdir, dir) = workspace_dir?;
+    dir.write("first.rs", "original")
+        .map_err(|e| format!("write first file: {e}"))?;
+    dir.write("blocker", "not a directory")
+        .map_err(|e| format!("write blocker file: {e}"))?;
+    dir.create_dir("occupied")
+        .map_err(|e| format!("create occupied directory: {e}"))?;
+    dir.write("occupied/keep.txt", "keep")
+        .map_err(|e| format!("write occupied file: {e}"))?;
+
+    let first = tempdir.path().join("first.rs");
+    let second = tempdir.path().join(second);
+    let mut context = VerificationContext::new();
+    context.add_original(first.clone(), String::from("original"));
+    context.add_modified(first.clone(), String::from("updated"));
+    context.add_original(second.clone(), String::new());
+    context.add_modified(second.clone(), String::from("new"));
+    let paths = [first, second.clone()];
+
+    let result = commit_changes_with_deletes(CommitPlan {
+        dir: &dir,
+        workspace_root: tempdir.path(),
+        context: &context,
+        paths: &paths,
+        deletions: &[],
+    });
+
+    assert!(matches!(
+        result,
+        Err(SafetyHarnessError::FileWriteError { ref path, .. }) if *path == second
+    ));
+    assert_eq!(
+        dir.read_to_string("first.rs")
+            .map_err(|e| format!("read first file: {e}"))?,
+        "original"
+    );
+    assert_eq!(leftover_temp_files(&dir)?, Vec::<String>::new());
+    Ok(())
+}
@@ -56,6 +56,82 @@ fn content_transaction_commits_writes_and_deletes() -> Result<(), String> {
     Ok(())
 }
 
+#[test]
+fn validate_only_reports_files_without_committing() -> Result<(), String> {
+    let dir = TempDir::new().map_err(|e| format!("temp dir: {e}"))?;
+    let keep_path = temp_file(&dir, "keep.txt", "hello")?;
+    let delete_path = temp_file(&dir, "delete.txt", "goodbye")?;
+    let created_path = dir.path().join("created.txt");
+
+    let syntactic = ConfigurableSyntacticLock::passing();
+    let semantic = ConfigurableSemanticLock::passing();
+    let mut transaction = build_content_transaction(
+        &syntactic,
+        &semantic,
+        keep_path.clone(),
+        delete_path.clone(),
+    );
+    transaction.add_change(ContentChange::write(
+        created_path.clone(),
+        String::from("new"),
+    ));
+    let workspace_dir = open_workspace_dir(dir.path())?;
+
+    let outcome = transaction
+        .validate_only(&workspace_dir, dir.path())
+        .map_err(|e| format!("validation failed: {e}"))?;
+    assert_eq!(
+        outcome,
+        TransactionOutcome::Validated {
+            files: vec![keep_path.clone(), created_path.clone(), delete_path.clone()],
+        }
+    );
+    assert!(!outcome.committed());
+    assert_eq!(read_file(&keep_path)?, "hello");
+    assert!(file_exists(&delete_path)?, "delete file should remain");
+    assert!(
+        !file_exists(&created_path)?,
+        "created file should not exist"
+    );
+    Ok(())
+}
+
+#[rstest]
+#[case::syntactic(LockFailureKind::Syntactic)]
+#[case::semantic(LockFailureKind::Semantic)]
+fn validate_only_reports_lock_failures(#[case] kind: LockFailureKind) -> Result<(), String> {
+    let dir = TempDir::new().map_err(|e| format!("temp dir: {e}"))?;
+    let keep_path = temp_file(&dir, "keep.txt", "hello")?;
+    let delete_path = temp_file(&dir, "delete.txt", "goodbye")?;
+    let failure = VerificationFailure::new(keep_path.clone(), "broken");
+    let (syntactic, semantic) = match kind {
+        LockFailureKind::Syntactic => (
+            ConfigurableSyntacticLock::failing(vec![failure]),
+            ConfigurableSemanticLock::passing(),
+        ),
+        LockFailureKind::Semantic => (
+            ConfigurableSyntacticLock::passing(),
+            ConfigurableSemanticLock::failing(vec![failure]),
+        ),
+    };
+    let transaction = build_content_transaction(&syntactic, &semantic, keep_path, delete_path);
+    let workspace_dir = open_workspace_dir(dir.path())?;
+
+    let outcome = transaction
+        .validate_only(&workspace_dir, dir.path())
+        .map_err(|e| format!("validation failed: {e}"))?;
+    let failed = match kind {
+        LockFailureKind::Syntactic => {
+            matches!(outcome, TransactionOutcome::SyntacticLockFailed { .. })
+        }
+        LockFailureKind::Semantic => {
+            matches!(outcome, TransactionOutcome::SemanticLockFailed { .. })
+        }
+    };
+    assert!(failed, "unexpected outcome: {outcome:?}");
+    Ok(())
+}
+
 #[rstest]
 #[case::syntactic(LockFailureKind::Syntactic, "syntax error")]
 #[case::semantic(LockFailureKind::Semantic, "type error")]
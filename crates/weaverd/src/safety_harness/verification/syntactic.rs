@@ -97,7 +97,8 @@ impl TreeSitterSyntacticLockAdapter {
 
 /// Converts a weaver-syntax validation failure to a harness verification failure.
 fn convert_failure(f: weaver_syntax::ValidationFailure) -> VerificationFailure {
-    VerificationFailure::new(f.path, &f.message).at_location(f.line, f.column)
+    let (line, column) = f.line_column();
+    VerificationFailure::new(f.path, f.message).at_location(line, column)
 }
 
 #[cfg(test)]
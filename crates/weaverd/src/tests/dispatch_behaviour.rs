@@ -168,11 +168,11 @@ fn when_valid_act_request(world: &RefCell<DispatchWorld>) {
         .send_request(r#"{"command":{"domain":"act","operation":"apply-patch"}}"#);
 }
 
-#[when("a valid verify diagnostics request is sent")]
+#[when("a valid verify syntax request is sent")]
 fn when_valid_verify_request(world: &RefCell<DispatchWorld>) {
     world
         .borrow_mut()
-        .send_request(r#"{"command":{"domain":"verify","operation":"diagnostics"}}"#);
+        .send_request(r#"{"command":{"domain":"verify","operation":"syntax"}}"#);
 }
 
 #[when("a malformed JSONL request is sent")]
@@ -264,7 +264,9 @@ fn then_unknown_operation_payload_lists_known_operations(
             "diagnostics",
             "call-hierarchy",
             "get-card",
-            "graph-slice"
+            "graph-slice",
+            "analyze",
+            "list-plugins"
         ]),
         "act" => serde_json::json!([
             "rename-symbol",
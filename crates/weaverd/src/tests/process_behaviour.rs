@@ -32,6 +32,21 @@ fn when_daemon_starts_foreground(world: &RefCell<ProcessTestWorld>) -> Result<()
     Ok(())
 }
 
+#[given("the daemon idle timeout is {seconds} second")]
+fn given_idle_timeout(world: &RefCell<ProcessTestWorld>, seconds: u64) {
+    world.borrow_mut().set_idle_timeout_secs(seconds);
+}
+
+#[when("the daemon starts in foreground mode without a shutdown signal")]
+fn when_daemon_starts_foreground_without_signal(
+    world: &RefCell<ProcessTestWorld>,
+) -> Result<(), String> {
+    world
+        .borrow_mut()
+        .start_foreground(LaunchMode::Foreground, false)?;
+    Ok(())
+}
+
 #[when("the daemon starts in foreground mode with invalid configuration")]
 fn when_daemon_starts_invalid(world: &RefCell<ProcessTestWorld>) -> Result<(), String> {
     world.borrow_mut().start_foreground_with_invalid_config()?;
@@ -261,3 +276,9 @@ fn assert_daemon_error_contains(world: &RefCell<ProcessTestWorld>, needle: &str)
 
 #[scenario(path = "tests/features/daemon_process.feature")]
 fn daemon_process(#[from(world)] _: RefCell<ProcessTestWorld>) {}
+
+#[scenario(
+    path = "tests/features/daemon_process.feature",
+    name = "Idle daemon shuts itself down"
+)]
+fn idle_daemon_shuts_itself_down(#[from(world)] _: RefCell<ProcessTestWorld>) {}
@@ -15,6 +15,7 @@ use crate::bootstrap::ConfigLoader;
 #[derive(Clone)]
 pub struct TestConfigLoader {
     socket_dir: Arc<Mutex<TempDir>>,
+    idle_timeout_secs: u64,
 }
 
 impl TestConfigLoader {
@@ -23,9 +24,13 @@ impl TestConfigLoader {
         let dir = TempDir::new().expect("failed to create temporary directory for socket");
         Self {
             socket_dir: Arc::new(Mutex::new(dir)),
+            idle_timeout_secs: 0,
         }
     }
 
+    /// Configures the daemon idle timeout reported by the loader.
+    pub fn set_idle_timeout_secs(&mut self, seconds: u64) { self.idle_timeout_secs = seconds; }
+
     /// Returns the directory backing the temporary runtime.
     #[must_use]
     pub fn runtime_dir(&self) -> PathBuf {
@@ -53,6 +58,7 @@ impl ConfigLoader for TestConfigLoader {
     fn load(&self) -> Result<Config, Arc<OrthoError>> {
         Ok(Config {
             daemon_socket: SocketEndpoint::unix(self.socket_path()),
+            daemon_idle_timeout: self.idle_timeout_secs,
             ..Config::default()
         })
     }
@@ -5,6 +5,7 @@ mod config_loader;
 pub mod fs;
 mod process_world;
 mod reporter;
+pub mod stream;
 mod world;
 
 pub use backend_provider::RecordingBackendProvider;
@@ -156,6 +156,10 @@ impl ProcessTestWorld {
 
     pub fn trigger_shutdown(&self) { self.shutdown.trigger(); }
 
+    pub fn set_idle_timeout_secs(&mut self, seconds: u64) {
+        self.loader.set_idle_timeout_secs(seconds);
+    }
+
     pub fn reset_observations(&mut self) {
         self.wait_error = None;
         self.health_history.borrow_mut().clear();
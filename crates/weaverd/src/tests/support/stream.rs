@@ -0,0 +1,19 @@
+//! Helpers for decoding daemon response streams in tests.
+
+/// Decodes the JSON payload carried by the first stdout message in `output`.
+///
+/// `output` holds the JSONL stream written by a dispatch handler; stderr
+/// messages such as warnings may precede the stdout message.
+///
+/// # Panics
+///
+/// Panics if `output` contains no stdout message or its data is not JSON.
+pub fn stdout_payload(output: &str) -> serde_json::Value {
+    let data = output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .find(|envelope| envelope["stream"] == "stdout")
+        .and_then(|envelope| envelope["data"].as_str().map(String::from))
+        .unwrap_or_else(|| panic!("no stdout message in: {output}"));
+    serde_json::from_str(&data).expect("stdout payload should be JSON")
+}
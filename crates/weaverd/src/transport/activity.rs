@@ -0,0 +1,99 @@
+//! Connection activity tracking used to detect an idle daemon.
+
+use std::{
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// Counts in-flight connections and records when activity last changed.
+///
+/// The worker pool marks a connection as started when it is queued and as
+/// finished once its handler returns, so the idle clock restarts on every
+/// accepted connection and only runs while no connection is in flight.
+#[derive(Debug)]
+pub(crate) struct ConnectionActivity {
+    state: Mutex<ActivityState>,
+}
+
+#[derive(Debug)]
+struct ActivityState {
+    active: usize,
+    last_change: Instant,
+}
+
+impl ConnectionActivity {
+    /// Creates a tracker whose idle clock starts now.
+    pub(crate) fn new() -> Self {
+        Self {
+            state: Mutex::new(ActivityState {
+                active: 0,
+                last_change: Instant::now(),
+            }),
+        }
+    }
+
+    /// Records a newly accepted connection.
+    pub(crate) fn connection_started(&self) {
+        let mut state = self.lock();
+        state.active += 1;
+        state.last_change = Instant::now();
+    }
+
+    /// Records that a connection has been fully served.
+    pub(crate) fn connection_finished(&self) {
+        let mut state = self.lock();
+        state.active = state.active.saturating_sub(1);
+        state.last_change = Instant::now();
+    }
+
+    /// Returns how long the daemon has had no connections in flight.
+    ///
+    /// Returns `None` while any connection is still being served.
+    pub(crate) fn idle_for(&self) -> Option<Duration> {
+        let state = self.lock();
+        (state.active == 0).then(|| state.last_change.elapsed())
+    }
+
+    // The state is updated with plain assignments, so a poisoned lock still
+    // holds consistent data.
+    fn lock(&self) -> MutexGuard<'_, ActivityState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for connection activity tracking.
+
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    fn reports_idle_only_when_no_connections_are_active() {
+        let activity = ConnectionActivity::new();
+        assert!(activity.idle_for().is_some());
+
+        activity.connection_started();
+        assert_eq!(activity.idle_for(), None);
+
+        activity.connection_finished();
+        assert!(activity.idle_for().is_some());
+    }
+
+    #[rstest]
+    fn accepted_connections_restart_the_idle_clock() {
+        let activity = ConnectionActivity::new();
+        std::thread::sleep(Duration::from_millis(20));
+        let before = activity.idle_for().expect("idle before connection");
+
+        activity.connection_started();
+        activity.connection_finished();
+
+        let after = activity.idle_for().expect("idle after connection");
+        assert!(
+            after < before,
+            "idle clock should restart: {after:?} >= {before:?}"
+        );
+    }
+}
@@ -22,6 +22,10 @@ pub enum ListenerError {
         #[source]
         source: io::Error,
     },
+    #[error(
+        "weaverd does not terminate TLS for {endpoint}; listen on plain TCP behind a TLS proxy"
+    )]
+    TlsUnsupported { endpoint: String },
     #[error("failed to enable non-blocking listener: {source}")]
     NonBlocking {
         #[source]
@@ -64,6 +68,11 @@ pub enum ListenerError {
         #[source]
         source: io::Error,
     },
+    #[error("failed to spawn connection worker thread: {source}")]
+    SpawnWorker {
+        #[source]
+        source: io::Error,
+    },
     #[error("listener thread panicked")]
     ThreadPanic,
 }
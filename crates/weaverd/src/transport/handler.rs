@@ -4,7 +4,7 @@
 use std::os::unix::net::UnixStream;
 use std::{
     io::{self, Read, Write},
-    net::TcpStream,
+    net::{Shutdown, TcpStream},
 };
 
 /// Stream types accepted by the daemon listener.
@@ -14,6 +14,39 @@ pub enum ConnectionStream {
     Unix(UnixStream),
 }
 
+impl ConnectionStream {
+    /// Creates an independent handle to the same underlying socket.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the operating system cannot duplicate the socket.
+    pub fn try_clone(&self) -> io::Result<Self> {
+        match self {
+            Self::Tcp(stream) => stream.try_clone().map(Self::Tcp),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.try_clone().map(Self::Unix),
+        }
+    }
+
+    /// Shuts down the read, write, or both halves of the socket.
+    ///
+    /// Unlike dropping the stream, this affects every handle created by
+    /// [`ConnectionStream::try_clone`], so the peer sees end-of-file even
+    /// while another clone is still alive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the operating system rejects the shutdown, for
+    /// example because the peer has already disconnected.
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        match self {
+            Self::Tcp(stream) => stream.shutdown(how),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.shutdown(how),
+        }
+    }
+}
+
 impl Read for ConnectionStream {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         match self {
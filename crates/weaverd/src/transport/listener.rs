@@ -1,10 +1,18 @@
 //! Socket listener implementation for the daemon transport.
 //!
 //! The listener binds to a configured [`SocketEndpoint`] and runs a background
-//! accept loop that hands each connection to a [`ConnectionHandler`]. It tracks
-//! the background thread via [`ListenerHandle`], enforces a simple concurrency
-//! limit for handler threads, and cleans up Unix socket files during shutdown
-//! or early error paths.
+//! accept loop that hands each connection to a [`ConnectionHandler`] running on
+//! a bounded [`WorkerPool`]. It tracks the background thread via
+//! [`ListenerHandle`] and cleans up Unix socket files during shutdown or early
+//! error paths.
+//!
+//! Ordering guarantees: each connection is served start to finish by a single
+//! worker, so one client's request and response stream are never interleaved
+//! with another's. Connections are handed to workers in the order they are
+//! accepted, but requests on different connections run concurrently and may
+//! complete in any order. When every worker is busy the accept loop stops
+//! accepting, leaving further clients queued by the operating system instead
+//! of dropping them.
 
 #[cfg(test)]
 use std::net::SocketAddr;
@@ -15,22 +23,28 @@ use std::{
     net::{TcpListener, ToSocketAddrs},
     sync::{
         Arc,
-        atomic::{AtomicBool, AtomicUsize, Ordering},
+        atomic::{AtomicBool, Ordering},
     },
     thread,
     time::Duration,
 };
 
-use tracing::{info, warn};
-use weaver_config::SocketEndpoint;
+use tracing::{debug, info, warn};
+use weaver_config::{DEFAULT_DAEMON_WORKERS, SocketEndpoint};
 
 #[cfg(unix)]
 use super::listener_unix::{bind_unix, cleanup_unix_socket};
-use super::{ConnectionHandler, ConnectionStream, LISTENER_TARGET, ListenerError};
+use super::{
+    ConnectionHandler,
+    ConnectionStream,
+    LISTENER_TARGET,
+    ListenerError,
+    activity::ConnectionActivity,
+    worker_pool::WorkerPool,
+};
 
 const ACCEPT_BACKOFF: Duration = Duration::from_millis(25);
 const ERROR_BACKOFF: Duration = Duration::from_millis(150);
-const MAX_HANDLER_THREADS: usize = 128;
 const READ_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// Listener that binds to a socket endpoint and spawns a background accept loop.
@@ -38,6 +52,7 @@ const READ_TIMEOUT: Duration = Duration::from_secs(5);
 pub(crate) struct SocketListener {
     endpoint: SocketEndpoint,
     listener: ListenerKind,
+    workers: usize,
 }
 
 /// Bound socket variants backed by TCP or Unix transports.
@@ -52,15 +67,20 @@ impl SocketListener {
     /// Binds to the provided socket endpoint.
     ///
     /// Returns a listener ready to start an accept loop. Binding can fail due to
-    /// address resolution errors, sockets already in use, or Unix socket
-    /// filesystem conflicts.
+    /// address resolution errors, sockets already in use, Unix socket
+    /// filesystem conflicts, or a `tcp+tls` endpoint, which the daemon leaves
+    /// to a TLS-terminating proxy.
     pub(crate) fn bind(endpoint: &SocketEndpoint) -> Result<Self, ListenerError> {
         match endpoint {
-            SocketEndpoint::Tcp { host, port } => {
+            SocketEndpoint::Tcp { tls: true, .. } => Err(ListenerError::TlsUnsupported {
+                endpoint: endpoint.to_string(),
+            }),
+            SocketEndpoint::Tcp { host, port, .. } => {
                 let listener = bind_tcp(host, *port)?;
                 Ok(Self {
                     endpoint: endpoint.clone(),
                     listener: ListenerKind::Tcp(listener),
+                    workers: DEFAULT_DAEMON_WORKERS,
                 })
             }
             SocketEndpoint::Unix { path } => {
@@ -70,6 +90,7 @@ impl SocketListener {
                     Ok(Self {
                         endpoint: endpoint.clone(),
                         listener: ListenerKind::Unix(listener),
+                        workers: DEFAULT_DAEMON_WORKERS,
                     })
                 }
 
@@ -83,6 +104,15 @@ impl SocketListener {
         }
     }
 
+    /// Sets how many worker threads serve accepted connections.
+    ///
+    /// Defaults to [`DEFAULT_DAEMON_WORKERS`]; zero is treated as one.
+    #[must_use]
+    pub(crate) fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
     #[cfg(test)]
     /// Returns the bound address for TCP listeners in tests.
     ///
@@ -98,11 +128,12 @@ impl SocketListener {
 
     /// Starts the accept loop in a background thread and returns its handle.
     ///
-    /// The listener switches into non-blocking mode, spawns the accept loop, and
-    /// hands accepted connections to the supplied [`ConnectionHandler`]. On Unix
-    /// platforms, socket cleanup is attempted if non-blocking configuration
-    /// fails. Returns `ListenerError::NonBlocking` when the listener cannot be
-    /// configured for non-blocking accepts.
+    /// The listener switches into non-blocking mode, spawns the worker pool and
+    /// the accept loop, and hands accepted connections to the supplied
+    /// [`ConnectionHandler`] on the pool. On Unix platforms, socket cleanup is
+    /// attempted if start-up fails. Returns `ListenerError::NonBlocking` when
+    /// the listener cannot be configured for non-blocking accepts, or
+    /// `ListenerError::SpawnWorker` when the worker pool cannot be started.
     pub(crate) fn start(
         mut self,
         handler: Arc<dyn ConnectionHandler>,
@@ -117,10 +148,13 @@ impl SocketListener {
             cleanup_unix_socket(&self.endpoint);
             return Err(ListenerError::NonBlocking { source: error });
         }
+        let activity = Arc::new(ConnectionActivity::new());
+        let pool = WorkerPool::new(self.workers, &handler, Arc::clone(&activity))?;
         let shutdown_flag = Arc::clone(&shutdown);
-        let handle = thread::spawn(move || run_accept_loop(&mut self, shutdown_flag, handler));
+        let handle = thread::spawn(move || run_accept_loop(&mut self, shutdown_flag, pool));
         Ok(ListenerHandle {
             shutdown,
+            activity,
             handle: Some(handle),
         })
     }
@@ -138,6 +172,7 @@ impl Drop for SocketListener {
 /// Use this handle to signal shutdown and wait for the accept loop to stop.
 pub(crate) struct ListenerHandle {
     shutdown: Arc<AtomicBool>,
+    activity: Arc<ConnectionActivity>,
     handle: Option<thread::JoinHandle<()>>,
 }
 
@@ -145,8 +180,17 @@ impl ListenerHandle {
     /// Signals the accept loop to shut down.
     pub(crate) fn shutdown(&self) { self.shutdown.store(true, Ordering::SeqCst); }
 
+    /// Returns how long the listener has had no connections in flight.
+    ///
+    /// The idle clock restarts whenever a connection is accepted or finishes,
+    /// and `None` is returned while any connection is still being served.
+    pub(crate) fn idle_for(&self) -> Option<Duration> { self.activity.idle_for() }
+
     /// Waits for the accept loop to complete.
     ///
+    /// Workers finish every connection they have already taken on before the
+    /// accept loop exits, so this also waits for in-flight requests.
+    ///
     /// Returns an error if the background thread panics.
     pub(crate) fn join(mut self) -> Result<(), ListenerError> {
         if let Some(handle) = self.handle.take() {
@@ -174,108 +218,77 @@ impl Drop for ListenerHandle {
     }
 }
 
-struct HandlerLimiter {
-    active: Arc<AtomicUsize>,
-    max: usize,
+/// Accept loop state carried between cycles.
+struct AcceptLoop {
+    pool: WorkerPool,
+    /// Connection accepted while every worker was busy, awaiting a worker.
+    pending: Option<ConnectionStream>,
+    last_error: Option<io::ErrorKind>,
 }
 
-impl HandlerLimiter {
-    fn new(max: usize) -> Self {
-        Self {
-            active: Arc::new(AtomicUsize::new(0)),
-            max,
+impl AcceptLoop {
+    /// Runs one accept cycle and returns how long to back off, if at all.
+    ///
+    /// A pending connection is dispatched before any new connection is
+    /// accepted, which keeps hand-over in accept order.
+    fn cycle(&mut self, listener: &mut SocketListener) -> Option<Duration> {
+        if let Some(stream) = self.pending.take() {
+            return self.dispatch(stream);
+        }
+        match accept_connection(listener) {
+            Ok(Some(stream)) => {
+                self.last_error = None;
+                let delay = self.dispatch(stream);
+                if self.pending.is_some() {
+                    debug!(
+                        target: LISTENER_TARGET,
+                        workers = self.pool.size(),
+                        "all workers busy, deferring connection"
+                    );
+                }
+                delay
+            }
+            Ok(None) => Some(ACCEPT_BACKOFF),
+            Err(error) => handle_accept_error(error, &mut self.last_error),
         }
     }
 
-    fn try_acquire(&self) -> Option<HandlerPermit> {
-        let mut current = self.active.load(Ordering::SeqCst);
-        loop {
-            if current >= self.max {
-                return None;
-            }
-            match self.active.compare_exchange(
-                current,
-                current + 1,
-                Ordering::SeqCst,
-                Ordering::SeqCst,
-            ) {
-                Ok(_) => return Some(HandlerPermit::new(Arc::clone(&self.active))),
-                Err(next) => current = next,
+    fn dispatch(&mut self, stream: ConnectionStream) -> Option<Duration> {
+        match self.pool.try_dispatch(stream) {
+            Ok(()) => None,
+            Err(stream) => {
+                self.pending = Some(stream);
+                Some(ACCEPT_BACKOFF)
             }
         }
     }
 }
 
-struct HandlerPermit {
-    active: Arc<AtomicUsize>,
-}
-
-impl HandlerPermit {
-    fn new(active: Arc<AtomicUsize>) -> Self { Self { active } }
-}
-
-impl Drop for HandlerPermit {
-    fn drop(&mut self) { self.active.fetch_sub(1, Ordering::SeqCst); }
-}
-
-fn run_accept_loop(
-    listener: &mut SocketListener,
-    shutdown: Arc<AtomicBool>,
-    handler: Arc<dyn ConnectionHandler>,
-) {
+fn run_accept_loop(listener: &mut SocketListener, shutdown: Arc<AtomicBool>, pool: WorkerPool) {
     info!(
         target: LISTENER_TARGET,
         endpoint = %listener.endpoint,
+        workers = pool.size(),
         "socket listener active"
     );
-    let mut last_error = None::<io::ErrorKind>;
-    let limiter = HandlerLimiter::new(MAX_HANDLER_THREADS);
+    let mut accept_loop = AcceptLoop {
+        pool,
+        pending: None,
+        last_error: None,
+    };
     while !shutdown.load(Ordering::SeqCst) {
-        if let Some(delay) = handle_accept_cycle(listener, &handler, &limiter, &mut last_error) {
+        if let Some(delay) = accept_loop.cycle(listener) {
             thread::sleep(delay);
         }
     }
+    // Dropping the loop drops any deferred connection and waits for the
+    // workers to finish the connections already queued.
+    drop(accept_loop);
 
     #[cfg(unix)]
     cleanup_unix_socket(&listener.endpoint);
 }
 
-fn handle_accept_cycle(
-    listener: &mut SocketListener,
-    handler: &Arc<dyn ConnectionHandler>,
-    limiter: &HandlerLimiter,
-    last_error: &mut Option<io::ErrorKind>,
-) -> Option<Duration> {
-    match accept_connection(listener) {
-        Ok(Some(stream)) => handle_accepted_stream(stream, handler, limiter, last_error),
-        Ok(None) => Some(ACCEPT_BACKOFF),
-        Err(error) => handle_accept_error(error, last_error),
-    }
-}
-
-fn handle_accepted_stream(
-    stream: ConnectionStream,
-    handler: &Arc<dyn ConnectionHandler>,
-    limiter: &HandlerLimiter,
-    last_error: &mut Option<io::ErrorKind>,
-) -> Option<Duration> {
-    *last_error = None;
-    if let Some(permit) = limiter.try_acquire() {
-        let handler = Arc::clone(handler);
-        thread::spawn(move || {
-            let _permit = permit;
-            handler.handle(stream);
-        });
-    } else {
-        warn!(
-            target: LISTENER_TARGET,
-            max_threads = limiter.max,
-            "listener at capacity, dropping connection"
-        );
-    }
-    None
-}
-
 fn handle_accept_error(
     error: io::Error,
     last_error: &mut Option<io::ErrorKind>,
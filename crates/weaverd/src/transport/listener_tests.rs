@@ -64,6 +64,42 @@ fn tcp_listener_accepts_connections(
     handle.join().expect("join listener");
 }
 
+#[rstest]
+fn tcp_listener_defers_connections_while_workers_are_busy(
+    tcp_endpoint: SocketEndpoint,
+    counting_fixture: CountingFixture,
+) {
+    let listener = SocketListener::bind(&tcp_endpoint)
+        .expect("bind tcp listener")
+        .with_workers(1);
+    let addr = listener
+        .local_addr()
+        .expect("listener should report local address");
+    let CountingFixture { count, handler } = counting_fixture;
+    let handler: Arc<dyn ConnectionHandler> = handler;
+    let handle = listener.start(handler).expect("start listener");
+
+    let _clients: Vec<_> = (0..5)
+        .map(|_| TcpStream::connect(addr).expect("connect client"))
+        .collect();
+
+    assert!(
+        wait_for_count(&count, 5),
+        "every connection should be served once a worker frees up"
+    );
+    handle.shutdown();
+    handle.join().expect("join listener");
+}
+
+#[rstest]
+fn tls_endpoints_are_rejected() {
+    let endpoint = SocketEndpoint::tcp_tls("127.0.0.1", 0);
+
+    let result = SocketListener::bind(&endpoint);
+
+    assert!(matches!(result, Err(ListenerError::TlsUnsupported { .. })));
+}
+
 #[cfg(unix)]
 #[allow_fixture_expansion_lints]
 #[fixture]
@@ -1,8 +1,9 @@
 //! Socket listener for daemon transport endpoints.
 //!
 //! The transport module binds to configured socket endpoints and accepts
-//! connections in a background thread.
+//! connections in a background thread, serving them on a bounded worker pool.
 
+mod activity;
 mod errors;
 mod handler;
 mod listener;
@@ -12,6 +13,7 @@ mod listener_tests;
 mod listener_unix;
 #[cfg(test)]
 mod test_utils;
+mod worker_pool;
 
 #[doc(hidden)]
 pub use self::handler::{ConnectionHandler, ConnectionStream};
@@ -0,0 +1,150 @@
+//! Bounded worker pool that serves accepted connections.
+//!
+//! The pool owns a fixed number of worker threads that pull connections from a
+//! bounded queue holding at most one waiting connection per worker. The accept
+//! loop hands connections over with [`WorkerPool::try_dispatch`]; when the
+//! queue is full the stream is handed back so the caller can stop accepting
+//! until a worker frees up, leaving further clients in the operating system's
+//! accept backlog rather than dropping them.
+//!
+//! Each worker serves one connection at a time from start to finish, so the
+//! request and response stream on a single connection are never interleaved
+//! with another client's. Connections are taken off the queue in the order
+//! they were accepted, but they may complete in any order.
+//!
+//! The pool reports each connection to a shared [`ConnectionActivity`] when it
+//! is queued and again once it has been served, which lets the daemon detect
+//! when it has gone idle.
+
+use std::{
+    io,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        Arc,
+        Mutex,
+        mpsc::{self, Receiver, SyncSender, TrySendError},
+    },
+    thread::{self, JoinHandle},
+};
+
+use tracing::warn;
+
+use super::{
+    ConnectionHandler,
+    ConnectionStream,
+    LISTENER_TARGET,
+    ListenerError,
+    activity::ConnectionActivity,
+};
+
+type ConnectionQueue = Arc<Mutex<Receiver<ConnectionStream>>>;
+
+/// Fixed-size pool of threads running a [`ConnectionHandler`].
+pub(crate) struct WorkerPool {
+    sender: Option<SyncSender<ConnectionStream>>,
+    workers: Vec<JoinHandle<()>>,
+    activity: Arc<ConnectionActivity>,
+}
+
+impl WorkerPool {
+    /// Spawns `size` workers that pass queued connections to `handler`.
+    ///
+    /// A `size` of zero is treated as one. Returns
+    /// `ListenerError::SpawnWorker` when a worker thread cannot be started;
+    /// workers spawned before the failure are shut down again.
+    pub(crate) fn new(
+        size: usize,
+        handler: &Arc<dyn ConnectionHandler>,
+        activity: Arc<ConnectionActivity>,
+    ) -> Result<Self, ListenerError> {
+        let size = size.max(1);
+        let (sender, receiver) = mpsc::sync_channel(size);
+        let queue: ConnectionQueue = Arc::new(Mutex::new(receiver));
+        let mut pool = Self {
+            sender: Some(sender),
+            workers: Vec::with_capacity(size),
+            activity,
+        };
+        for index in 0..size {
+            let worker = Worker {
+                queue: Arc::clone(&queue),
+                handler: Arc::clone(handler),
+                activity: Arc::clone(&pool.activity),
+            };
+            let worker = worker
+                .spawn(index)
+                .map_err(|source| ListenerError::SpawnWorker { source })?;
+            pool.workers.push(worker);
+        }
+        Ok(pool)
+    }
+
+    /// Returns the number of worker threads in the pool.
+    pub(crate) fn size(&self) -> usize { self.workers.len() }
+
+    /// Queues a connection for the next free worker.
+    ///
+    /// Returns the stream unchanged when the queue is full so the caller can
+    /// retry once a worker becomes available.
+    pub(crate) fn try_dispatch(&self, stream: ConnectionStream) -> Result<(), ConnectionStream> {
+        let Some(sender) = &self.sender else {
+            return Err(stream);
+        };
+        // Count the connection before queueing it so a worker can never
+        // finish it before it has been recorded as started.
+        self.activity.connection_started();
+        sender.try_send(stream).map_err(|error| {
+            self.activity.connection_finished();
+            match error {
+                TrySendError::Full(stream) | TrySendError::Disconnected(stream) => stream,
+            }
+        })
+    }
+}
+
+impl Drop for WorkerPool {
+    /// Closes the queue and waits for the workers to drain it.
+    ///
+    /// Connections already queued are still served, so shutdown completes
+    /// once every in-flight request has finished.
+    fn drop(&mut self) {
+        drop(self.sender.take());
+        for worker in self.workers.drain(..) {
+            if worker.join().is_err() {
+                warn!(target: LISTENER_TARGET, "worker thread panicked during shutdown");
+            }
+        }
+    }
+}
+
+/// State moved onto each worker thread.
+struct Worker {
+    queue: ConnectionQueue,
+    handler: Arc<dyn ConnectionHandler>,
+    activity: Arc<ConnectionActivity>,
+}
+
+impl Worker {
+    fn spawn(self, index: usize) -> io::Result<JoinHandle<()>> {
+        thread::Builder::new()
+            .name(format!("weaverd-worker-{index}"))
+            .spawn(move || self.run())
+    }
+
+    fn run(&self) {
+        // The queue lock is released before the handler runs so other workers
+        // can pick up connections while this one is busy.
+        while let Some(stream) = next_connection(&self.queue) {
+            let outcome = panic::catch_unwind(AssertUnwindSafe(|| self.handler.handle(stream)));
+            if outcome.is_err() {
+                warn!(target: LISTENER_TARGET, "connection handler panicked");
+            }
+            self.activity.connection_finished();
+        }
+    }
+}
+
+fn next_connection(queue: &ConnectionQueue) -> Option<ConnectionStream> {
+    let receiver = queue.lock().ok()?;
+    receiver.recv().ok()
+}
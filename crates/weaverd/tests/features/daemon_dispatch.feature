@@ -37,6 +37,6 @@ Feature: Daemon JSONL request dispatch
 
   Scenario: Dispatching a valid verify command
     Given a daemon connection is established
-    When a valid verify diagnostics request is sent
+    When a valid verify syntax request is sent
     Then the response includes an exit message with status 1
     And the response includes a not implemented message
@@ -59,3 +59,10 @@ Feature: Daemon process management
     Given a fresh daemon process world
     When we wait for the daemon to become ready
     Then waiting for readiness fails
+
+  Scenario: Idle daemon shuts itself down
+    Given a fresh daemon process world
+    And the daemon idle timeout is 1 second
+    When the daemon starts in foreground mode without a shutdown signal
+    Then the daemon run succeeds
+    And the runtime artefacts are removed
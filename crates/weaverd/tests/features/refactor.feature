@@ -41,7 +41,7 @@ Feature: Act refactor
     When the act refactor command executes
     Then the refactor command fails with status 1
     And the target file is unchanged
-    And the stderr stream contains "did not return diff output"
+    And the stderr stream contains "response does not satisfy the rename-symbol contract"
 
   Scenario: Refactor refuses unsupported languages deterministically
     Given a workspace file for refactoring